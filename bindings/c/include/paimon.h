@@ -0,0 +1,73 @@
+/*
+ * Licensed to the Apache Software Foundation (ASF) under one
+ * or more contributor license agreements.  See the NOTICE file
+ * distributed with this work for additional information
+ * regarding copyright ownership.  The ASF licenses this file
+ * to you under the Apache License, Version 2.0 (the
+ * "License"); you may not use this file except in compliance
+ * with the License.  You may obtain a copy of the License at
+ *
+ *   http://www.apache.org/licenses/LICENSE-2.0
+ *
+ * Unless required by applicable law or agreed to in writing,
+ * software distributed under the License is distributed on an
+ * "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+ * KIND, either express or implied.  See the License for the
+ * specific language governing permissions and limitations
+ * under the License.
+ */
+
+#ifndef PAIMON_H
+#define PAIMON_H
+
+#include <stddef.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * C bindings of the rust implementation of Apache Paimon.
+ *
+ * Functions return 0 on success and -1 on failure, the message of the failure is got by
+ * paimon_last_error of the thread. The arrow structs are of the Arrow C Data Interface.
+ */
+
+struct ArrowSchema;
+struct ArrowArray;
+struct ArrowArrayStream;
+
+typedef struct PaimonTable PaimonTable;
+typedef struct PaimonPlan PaimonPlan;
+typedef struct PaimonWrite PaimonWrite;
+
+/* Get the message of the last failure of the thread, NULL if none. */
+const char *paimon_last_error(void);
+
+/* Open the table like "database.table" of the filesystem catalog of the warehouse. */
+int paimon_table_open(const char *warehouse, const char *identifier, PaimonTable **out);
+void paimon_table_free(PaimonTable *table);
+
+/* Export the schema of the rows of the table, released by the caller. */
+int paimon_table_schema(const PaimonTable *table, struct ArrowSchema *out);
+
+/* Plan the splits of the latest snapshot of the table. */
+int paimon_table_plan(const PaimonTable *table, PaimonPlan **out);
+size_t paimon_plan_num_splits(const PaimonPlan *plan);
+/* Read the split at the index into a stream, released by the caller. */
+int paimon_plan_read_split(const PaimonPlan *plan, size_t index, struct ArrowArrayStream *out);
+void paimon_plan_free(PaimonPlan *plan);
+
+/* Create a batch write of the table. */
+int paimon_table_new_write(const PaimonTable *table, PaimonWrite **out);
+/* Write the struct array of rows, the array is moved into the write. */
+int paimon_write_write(PaimonWrite *write, struct ArrowArray *array, const struct ArrowSchema *schema);
+/* Commit the rows written, the write can only be freed afterwards. */
+int paimon_write_commit(PaimonWrite *write);
+void paimon_write_free(PaimonWrite *write);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* PAIMON_H */
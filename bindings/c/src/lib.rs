@@ -0,0 +1,467 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! C bindings of the rust implementation of Apache Paimon, see `include/paimon.h`.
+//!
+//! Tables of filesystem catalogs are opened by handles, the splits planned are read as
+//! streams of the Arrow C Data Interface and written from its arrays by batch writes.
+//! Functions return `0` on success and `-1` on failure, with the message of the failure
+//! kept by [`paimon_last_error`] of the thread.
+
+use arrow::array::{
+    Array, ArrayData, RecordBatch, RecordBatchOptions, RecordBatchReader, StructArray,
+};
+use arrow::datatypes::{DataType, Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::ffi::{from_ffi, FFI_ArrowArray, FFI_ArrowSchema};
+use arrow::ffi_stream::FFI_ArrowArrayStream;
+use paimon::catalog::{Catalog, FileSystemCatalog, Identifier};
+use paimon::format::{to_arrow_schema, ArrowRecordBatchIter};
+use paimon::io::FileIOBuilder;
+use paimon::table::{BatchWriteBuilder, FileStoreTable, Plan, Table, TableWrite};
+use std::cell::RefCell;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::future::Future;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, OnceLock};
+use tokio::runtime::Runtime;
+
+/// A table opened.
+pub struct PaimonTable {
+    table: FileStoreTable,
+}
+
+/// The splits planned of a snapshot of a table.
+pub struct PaimonPlan {
+    table: FileStoreTable,
+    plan: Plan,
+}
+
+/// A batch write of a table.
+pub struct PaimonWrite {
+    builder: BatchWriteBuilder,
+    write: TableWrite,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Keep the message of the error as the last error of the thread and return `-1`.
+fn fail(message: impl ToString) -> c_int {
+    let message = message.to_string().replace('\0', " ");
+    let message = CString::new(message).expect("nul bytes are replaced");
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    -1
+}
+
+fn block_on<F: Future>(future: F) -> Result<F::Output, String> {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    let runtime = match RUNTIME.get() {
+        Some(runtime) => runtime,
+        None => {
+            let runtime = Runtime::new().map_err(|e| e.to_string())?;
+            RUNTIME.get_or_init(|| runtime)
+        }
+    };
+    Ok(runtime.block_on(future))
+}
+
+/// Run the fallible function, keeping the message of its error or its panic, which must not
+/// unwind into the caller.
+fn run(f: impl FnOnce() -> Result<(), String>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => 0,
+        Ok(Err(message)) => fail(message),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            fail(format!("panicked: {message}"))
+        }
+    }
+}
+
+/// Convert the imported array to a record batch, failing instead of panicking on arrays
+/// which are malformed, not of the struct type or with null rows.
+fn to_record_batch(data: ArrayData) -> Result<RecordBatch, String> {
+    data.validate_full().map_err(|e| e.to_string())?;
+    if !matches!(data.data_type(), DataType::Struct(_)) {
+        return Err(format!(
+            "array is of {} instead of the struct type",
+            data.data_type()
+        ));
+    }
+    let array = StructArray::from(data);
+    if array.null_count() > 0 {
+        return Err("struct array has null rows".to_string());
+    }
+    let num_rows = array.len();
+    let (fields, columns, _) = array.into_parts();
+    RecordBatch::try_new_with_options(
+        Arc::new(Schema::new(fields)),
+        columns,
+        &RecordBatchOptions::new().with_row_count(Some(num_rows)),
+    )
+    .map_err(|e| e.to_string())
+}
+
+unsafe fn to_str<'a>(value: *const c_char, name: &str) -> Result<&'a str, String> {
+    if value.is_null() {
+        return Err(format!("{name} is null"));
+    }
+    CStr::from_ptr(value)
+        .to_str()
+        .map_err(|e| format!("{name} is not utf-8: {e}"))
+}
+
+/// Get the message of the last failure of the thread, null if none.
+///
+/// The message is valid until the next failure of the thread.
+#[no_mangle]
+pub extern "C" fn paimon_last_error() -> *const c_char {
+    catch_unwind(|| {
+        LAST_ERROR.with(|last| match last.borrow().as_ref() {
+            Some(message) => message.as_ptr(),
+            None => std::ptr::null(),
+        })
+    })
+    .unwrap_or(std::ptr::null())
+}
+
+/// Open the table of the identifier like `database.table` of the filesystem catalog of the
+/// warehouse, freed by [`paimon_table_free`].
+///
+/// # Safety
+///
+/// `warehouse` and `identifier` are nul terminated strings, `out` is a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_table_open(
+    warehouse: *const c_char,
+    identifier: *const c_char,
+    out: *mut *mut PaimonTable,
+) -> c_int {
+    run(|| {
+        let warehouse = to_str(warehouse, "warehouse")?;
+        let identifier: Identifier = to_str(identifier, "identifier")?
+            .parse()
+            .map_err(|e: paimon::Error| e.to_string())?;
+        let file_io = FileIOBuilder::from_url(warehouse)
+            .and_then(|builder| builder.build())
+            .map_err(|e| e.to_string())?;
+        let catalog = FileSystemCatalog::new(file_io, warehouse);
+        let table = block_on(catalog.get_table(&identifier))?.map_err(|e| e.to_string())?;
+        *out = Box::into_raw(Box::new(PaimonTable { table }));
+        Ok(())
+    })
+}
+
+/// Free the table.
+///
+/// # Safety
+///
+/// `table` is null or opened by [`paimon_table_open`] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_table_free(table: *mut PaimonTable) {
+    run(|| {
+        if !table.is_null() {
+            drop(Box::from_raw(table));
+        }
+        Ok(())
+    });
+}
+
+/// Export the arrow schema of the rows of the table.
+///
+/// # Safety
+///
+/// `table` is a valid table, `out` points to an `ArrowSchema` released by the caller.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_table_schema(
+    table: *const PaimonTable,
+    out: *mut FFI_ArrowSchema,
+) -> c_int {
+    run(|| {
+        let schema = to_arrow_schema((*table).table.schema().fields());
+        let schema = FFI_ArrowSchema::try_from(&schema).map_err(|e| e.to_string())?;
+        std::ptr::write(out, schema);
+        Ok(())
+    })
+}
+
+/// Plan the splits of the latest snapshot of the table, freed by [`paimon_plan_free`].
+///
+/// # Safety
+///
+/// `table` is a valid table, `out` is a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_table_plan(
+    table: *const PaimonTable,
+    out: *mut *mut PaimonPlan,
+) -> c_int {
+    run(|| {
+        let table = (*table).table.clone();
+        let plan = block_on(table.new_scan().plan())?.map_err(|e| e.to_string())?;
+        *out = Box::into_raw(Box::new(PaimonPlan { table, plan }));
+        Ok(())
+    })
+}
+
+/// Get the number of the splits of the plan, `0` on failure.
+///
+/// # Safety
+///
+/// `plan` is a valid plan.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_plan_num_splits(plan: *const PaimonPlan) -> usize {
+    let mut num_splits = 0;
+    run(|| {
+        num_splits = (*plan).plan.splits().len();
+        Ok(())
+    });
+    num_splits
+}
+
+/// Read the split at the index of the plan into an arrow stream.
+///
+/// # Safety
+///
+/// `plan` is a valid plan, `out` points to an `ArrowArrayStream` released by the caller.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_plan_read_split(
+    plan: *const PaimonPlan,
+    index: usize,
+    out: *mut FFI_ArrowArrayStream,
+) -> c_int {
+    run(|| {
+        let PaimonPlan { table, plan } = &*plan;
+        let split = plan
+            .splits()
+            .get(index)
+            .ok_or_else(|| format!("split {index} does not exist"))?;
+        let read = table.new_read();
+        let schema = to_arrow_schema(read.read_type().map_err(|e| e.to_string())?.fields());
+        let batches = block_on(read.read(split))?.map_err(|e| e.to_string())?;
+        let reader = SplitReader {
+            schema: SchemaRef::new(schema),
+            batches,
+        };
+        std::ptr::write(out, FFI_ArrowArrayStream::new(Box::new(reader)));
+        Ok(())
+    })
+}
+
+/// Free the plan.
+///
+/// # Safety
+///
+/// `plan` is null or planned by [`paimon_table_plan`] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_plan_free(plan: *mut PaimonPlan) {
+    run(|| {
+        if !plan.is_null() {
+            drop(Box::from_raw(plan));
+        }
+        Ok(())
+    });
+}
+
+/// Create a batch write of the table, freed by [`paimon_write_free`].
+///
+/// # Safety
+///
+/// `table` is a valid table, `out` is a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_table_new_write(
+    table: *const PaimonTable,
+    out: *mut *mut PaimonWrite,
+) -> c_int {
+    run(|| {
+        let builder = (*table).table.new_batch_write_builder();
+        let write = builder.new_write();
+        *out = Box::into_raw(Box::new(PaimonWrite { builder, write }));
+        Ok(())
+    })
+}
+
+/// Write the struct array of the rows of the table, the array is moved into the write and
+/// released by it.
+///
+/// # Safety
+///
+/// `write` is a valid write, `array` and `schema` point to an `ArrowArray` of the struct
+/// type and its `ArrowSchema`, the schema is released by the caller.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_write_write(
+    write: *mut PaimonWrite,
+    array: *mut FFI_ArrowArray,
+    schema: *const FFI_ArrowSchema,
+) -> c_int {
+    run(|| {
+        let array = FFI_ArrowArray::from_raw(array);
+        let data = from_ffi(array, &*schema).map_err(|e| e.to_string())?;
+        let batch = to_record_batch(data)?;
+        block_on((*write).write.write(&batch))?.map_err(|e| e.to_string())
+    })
+}
+
+/// Commit the rows written by the write, which can't be used afterwards except to be freed.
+///
+/// # Safety
+///
+/// `write` is a valid write.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_write_commit(write: *mut PaimonWrite) -> c_int {
+    run(|| {
+        let PaimonWrite { builder, write } = &mut *write;
+        block_on(async {
+            let messages = write.prepare_commit().await?;
+            builder.new_commit().commit(messages).await
+        })?
+        .map_err(|e| e.to_string())
+    })
+}
+
+/// Free the write, the rows not committed are discarded.
+///
+/// # Safety
+///
+/// `write` is null or created by [`paimon_table_new_write`] and not freed.
+#[no_mangle]
+pub unsafe extern "C" fn paimon_write_free(write: *mut PaimonWrite) {
+    run(|| {
+        if !write.is_null() {
+            drop(Box::from_raw(write));
+        }
+        Ok(())
+    });
+}
+
+/// Reader of the record batches of a split.
+struct SplitReader {
+    schema: SchemaRef,
+    batches: ArrowRecordBatchIter,
+}
+
+impl Iterator for SplitReader {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = self.batches.next()?;
+        Some(batch.map_err(|e| ArrowError::ExternalError(Box::new(e))))
+    }
+}
+
+impl RecordBatchReader for SplitReader {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Int32Array;
+    use arrow::ffi_stream::ArrowArrayStreamReader;
+    use paimon::spec::{DataField, Schema};
+    use std::ptr;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_write_and_read() {
+        let warehouse = "file:/tmp/test_paimon_c_write_and_read";
+        let file_io = FileIOBuilder::from_url(warehouse).unwrap().build().unwrap();
+        let catalog = FileSystemCatalog::new(file_io.clone(), warehouse);
+        block_on(async {
+            let _ = file_io.delete_dir(&format!("{warehouse}/")).await;
+            catalog
+                .create_database("db", false, Default::default())
+                .await
+                .unwrap();
+            let schema = Schema::builder()
+                .fields(vec![DataField::new(
+                    0,
+                    "id".to_string(),
+                    "INT".parse().unwrap(),
+                )])
+                .options([("file.format".to_string(), "parquet".to_string())].into())
+                .build();
+            catalog
+                .create_table(&Identifier::new("db", "t"), schema, false)
+                .await
+                .unwrap();
+        })
+        .unwrap();
+
+        let warehouse = CString::new(warehouse).unwrap();
+        let missing = CString::new("db.missing").unwrap();
+        let identifier = CString::new("db.t").unwrap();
+        unsafe {
+            let mut table = ptr::null_mut();
+            assert_eq!(
+                paimon_table_open(warehouse.as_ptr(), missing.as_ptr(), &mut table),
+                -1
+            );
+            let error = CStr::from_ptr(paimon_last_error()).to_str().unwrap();
+            assert!(error.contains("db.missing"));
+            assert_eq!(
+                paimon_table_open(warehouse.as_ptr(), identifier.as_ptr(), &mut table),
+                0
+            );
+
+            let mut schema = FFI_ArrowSchema::empty();
+            assert_eq!(paimon_table_schema(table, &mut schema), 0);
+            let schema = arrow::datatypes::Schema::try_from(&schema).unwrap();
+            let batch = RecordBatch::try_new(
+                Arc::new(schema),
+                vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+            )
+            .unwrap();
+            let data = StructArray::from(batch.clone()).into_data();
+            let mut array = FFI_ArrowArray::new(&data);
+            let array_schema = FFI_ArrowSchema::try_from(data.data_type()).unwrap();
+            let mut write = ptr::null_mut();
+            assert_eq!(paimon_table_new_write(table, &mut write), 0);
+            let ints = Int32Array::from(vec![4]).into_data();
+            let mut ints_array = FFI_ArrowArray::new(&ints);
+            let ints_schema = FFI_ArrowSchema::try_from(ints.data_type()).unwrap();
+            assert_eq!(paimon_write_write(write, &mut ints_array, &ints_schema), -1);
+            let error = CStr::from_ptr(paimon_last_error()).to_str().unwrap();
+            assert!(error.contains("instead of the struct type"), "{error}");
+            assert_eq!(paimon_write_write(write, &mut array, &array_schema), 0);
+            assert_eq!(paimon_write_commit(write), 0);
+            paimon_write_free(write);
+
+            let mut plan = ptr::null_mut();
+            assert_eq!(paimon_table_plan(table, &mut plan), 0);
+            assert_eq!(paimon_plan_num_splits(plan), 1);
+            let mut stream = FFI_ArrowArrayStream::empty();
+            assert_eq!(paimon_plan_read_split(plan, 0, &mut stream), 0);
+            let batches = ArrowArrayStreamReader::try_new(stream)
+                .unwrap()
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+            assert_eq!(batches, vec![batch]);
+            let mut stream = FFI_ArrowArrayStream::empty();
+            assert_eq!(paimon_plan_read_split(plan, 1, &mut stream), -1);
+            paimon_plan_free(plan);
+            paimon_table_free(table);
+        }
+    }
+}
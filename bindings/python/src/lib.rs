@@ -0,0 +1,374 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Python bindings of the rust implementation of Apache Paimon.
+//!
+//! The tables of a filesystem catalog are read into PyArrow tables or record batch readers
+//! pulling the batches as they are read, and written from PyArrow data by batch writes.
+
+use arrow::array::RecordBatchReader;
+use arrow::datatypes::SchemaRef;
+use arrow::error::ArrowError;
+use arrow::ffi_stream::ArrowArrayStreamReader;
+use arrow::pyarrow::{FromPyArrow, PyArrowType, ToPyArrow};
+use arrow::record_batch::RecordBatch;
+use futures::{StreamExt, TryStreamExt};
+use paimon::catalog::{Catalog as _, FileSystemCatalog, Identifier};
+use paimon::format::to_arrow_schema;
+use paimon::io::FileIOBuilder;
+use paimon::spec::{DataField, DataType, Schema};
+use paimon::table::{
+    ArrowRecordBatchStream, FileStoreTable, ParallelReader, Table as _, TableScan,
+};
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use tokio::runtime::Runtime;
+
+create_exception!(paimon_py, PaimonError, PyException, "Errors of paimon.");
+
+fn to_py_err(e: paimon::Error) -> PyErr {
+    PaimonError::new_err(e.to_string())
+}
+
+/// Run the future on the shared runtime with the GIL released.
+fn block_on<F>(py: Python<'_>, future: F) -> PyResult<F::Output>
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    let runtime = match RUNTIME.get() {
+        Some(runtime) => runtime,
+        None => {
+            let runtime = Runtime::new().map_err(|e| PaimonError::new_err(e.to_string()))?;
+            RUNTIME.get_or_init(|| runtime)
+        }
+    };
+    Ok(py.allow_threads(|| runtime.block_on(future)))
+}
+
+/// A catalog of the tables stored in the warehouse of a file system.
+#[pyclass]
+struct Catalog {
+    catalog: Arc<FileSystemCatalog>,
+}
+
+#[pymethods]
+impl Catalog {
+    #[new]
+    fn new(warehouse: &str) -> PyResult<Self> {
+        let file_io = FileIOBuilder::from_url(warehouse)
+            .and_then(|builder| builder.build())
+            .map_err(to_py_err)?;
+        Ok(Self {
+            catalog: Arc::new(FileSystemCatalog::new(file_io, warehouse)),
+        })
+    }
+
+    fn list_databases(&self, py: Python<'_>) -> PyResult<Vec<String>> {
+        block_on(py, self.catalog.list_databases())?.map_err(to_py_err)
+    }
+
+    #[pyo3(signature = (name, ignore_if_exists = false))]
+    fn create_database(&self, py: Python<'_>, name: &str, ignore_if_exists: bool) -> PyResult<()> {
+        let create = self
+            .catalog
+            .create_database(name, ignore_if_exists, HashMap::new());
+        block_on(py, create)?.map_err(to_py_err)
+    }
+
+    /// Create a table of the fields of names and types like `("id", "INT NOT NULL")`.
+    #[pyo3(signature = (
+        identifier,
+        fields,
+        partition_keys = vec![],
+        primary_keys = vec![],
+        options = HashMap::new(),
+        ignore_if_exists = false,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn create_table(
+        &self,
+        py: Python<'_>,
+        identifier: &str,
+        fields: Vec<(String, String)>,
+        partition_keys: Vec<String>,
+        primary_keys: Vec<String>,
+        options: HashMap<String, String>,
+        ignore_if_exists: bool,
+    ) -> PyResult<()> {
+        let identifier: Identifier = identifier.parse().map_err(to_py_err)?;
+        let fields = fields
+            .into_iter()
+            .enumerate()
+            .map(|(id, (name, data_type))| {
+                let data_type: DataType = data_type.parse()?;
+                Ok(DataField::new(id as i32, name, data_type))
+            })
+            .collect::<paimon::Result<Vec<_>>>()
+            .map_err(to_py_err)?;
+        let schema = Schema::builder()
+            .fields(fields)
+            .partition_keys(partition_keys)
+            .primary_keys(primary_keys)
+            .options(options)
+            .build();
+        let create = self
+            .catalog
+            .create_table(&identifier, schema, ignore_if_exists);
+        block_on(py, create)?.map_err(to_py_err)
+    }
+
+    fn list_tables(&self, py: Python<'_>, database: &str) -> PyResult<Vec<String>> {
+        block_on(py, self.catalog.list_tables(database))?.map_err(to_py_err)
+    }
+
+    /// Get the table by its identifier like `database.table`.
+    fn get_table(&self, py: Python<'_>, identifier: &str) -> PyResult<Table> {
+        let identifier: Identifier = identifier.parse().map_err(to_py_err)?;
+        let table = block_on(py, self.catalog.get_table(&identifier))?.map_err(to_py_err)?;
+        Ok(Table { table })
+    }
+}
+
+/// A table of paimon.
+#[pyclass]
+struct Table {
+    table: FileStoreTable,
+}
+
+#[pymethods]
+impl Table {
+    /// The identifier of the table like `database.table`.
+    #[getter]
+    fn identifier(&self) -> String {
+        self.table.identifier().full_name()
+    }
+
+    /// The schema of the table as a `pyarrow.Schema`.
+    #[getter]
+    fn schema(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_arrow_schema(self.table.schema().fields()).to_pyarrow(py)
+    }
+
+    #[getter]
+    fn partition_keys(&self) -> Vec<String> {
+        self.table.partition_keys().to_vec()
+    }
+
+    #[getter]
+    fn primary_keys(&self) -> Vec<String> {
+        self.table.primary_keys().to_vec()
+    }
+
+    fn new_read_builder(&self) -> ReadBuilder {
+        ReadBuilder {
+            table: self.table.clone(),
+            projection: None,
+            snapshot_id: None,
+            tag: None,
+            row_kind_column: None,
+            limit: None,
+        }
+    }
+
+    /// Write the data, anything exporting an arrow stream like a `pyarrow.Table` or a
+    /// `pyarrow.RecordBatchReader`, and commit it by a batch write.
+    fn write_arrow(&self, py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<()> {
+        let reader = ArrowArrayStreamReader::from_pyarrow_bound(data)?;
+        let batches = reader
+            .collect::<Result<Vec<_>, ArrowError>>()
+            .map_err(|e| PaimonError::new_err(e.to_string()))?;
+        let builder = self.table.new_batch_write_builder();
+        block_on(py, async move {
+            let mut write = builder.new_write();
+            for batch in &batches {
+                write.write(batch).await?;
+            }
+            let messages = write.prepare_commit().await?;
+            builder.new_commit().commit(messages).await
+        })?
+        .map_err(to_py_err)
+    }
+}
+
+/// A builder of the reads of a table.
+#[pyclass]
+#[derive(Clone)]
+struct ReadBuilder {
+    table: FileStoreTable,
+    projection: Option<Vec<String>>,
+    snapshot_id: Option<i64>,
+    tag: Option<String>,
+    row_kind_column: Option<String>,
+    limit: Option<usize>,
+}
+
+impl ReadBuilder {
+    fn scan(&self) -> TableScan {
+        let scan = self.table.new_scan();
+        let scan = match self.snapshot_id {
+            Some(snapshot_id) => scan.as_of_snapshot(snapshot_id),
+            None => scan,
+        };
+        let scan = match &self.tag {
+            Some(tag) => scan.with_tag(tag.clone()),
+            None => scan,
+        };
+        match self.limit {
+            Some(limit) => scan.with_limit(limit),
+            None => scan,
+        }
+    }
+
+    /// Plan the splits and read them into a stream of record batches, which are read once
+    /// they are pulled.
+    fn stream(&self, py: Python<'_>) -> PyResult<(SchemaRef, ArrowRecordBatchStream)> {
+        let read = self.table.new_read();
+        let read = match &self.projection {
+            Some(projection) => {
+                read.with_projection(&projection.iter().map(String::as_str).collect::<Vec<_>>())
+            }
+            None => read,
+        };
+        let read = match &self.row_kind_column {
+            Some(name) => read.with_row_kind_column(name.clone()),
+            None => read,
+        };
+        let read = match self.limit {
+            Some(limit) => read.with_limit(limit),
+            None => read,
+        };
+        let schema = read.arrow_schema().map_err(to_py_err)?;
+        let scan = self.scan();
+        let batches = block_on(py, async move {
+            let splits = scan.plan().await?.splits().to_vec();
+            Ok::<_, paimon::Error>(ParallelReader::new(read).read(splits))
+        })?
+        .map_err(to_py_err)?;
+        Ok((schema, batches))
+    }
+}
+
+/// A `RecordBatchReader` pulling the batches from the stream of the splits read, each batch
+/// is read on the shared runtime with the GIL released once it's pulled.
+struct StreamRecordBatchReader {
+    schema: SchemaRef,
+    batches: ArrowRecordBatchStream,
+}
+
+impl Iterator for StreamRecordBatchReader {
+    type Item = Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match Python::with_gil(|py| block_on(py, self.batches.next())) {
+            Ok(batch) => {
+                batch.map(|batch| batch.map_err(|e| ArrowError::ExternalError(Box::new(e))))
+            }
+            Err(e) => Some(Err(ArrowError::ExternalError(Box::new(e)))),
+        }
+    }
+}
+
+impl RecordBatchReader for StreamRecordBatchReader {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[pymethods]
+impl ReadBuilder {
+    /// Only read the given fields, in the given order.
+    fn with_projection(&self, fields: Vec<String>) -> Self {
+        Self {
+            projection: Some(fields),
+            ..self.clone()
+        }
+    }
+
+    /// Read the snapshot of the id instead of the latest one.
+    fn with_snapshot(&self, snapshot_id: i64) -> Self {
+        Self {
+            snapshot_id: Some(snapshot_id),
+            ..self.clone()
+        }
+    }
+
+    /// Read the snapshot of the tag instead of the latest one.
+    fn with_tag(&self, tag: String) -> Self {
+        Self {
+            tag: Some(tag),
+            ..self.clone()
+        }
+    }
+
+    /// Append a column of the given name holding the row kinds of the rows, like `+I`.
+    fn with_row_kind_column(&self, name: String) -> Self {
+        Self {
+            row_kind_column: Some(name),
+            ..self.clone()
+        }
+    }
+
+    /// Stop reading once the given number of rows are read.
+    fn with_limit(&self, limit: usize) -> Self {
+        Self {
+            limit: Some(limit),
+            ..self.clone()
+        }
+    }
+
+    /// Read the table into a `pyarrow.Table`.
+    fn to_arrow(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let (schema, batches) = self.stream(py)?;
+        let batches = block_on(py, batches.try_collect::<Vec<_>>())?.map_err(to_py_err)?;
+        let batches = batches
+            .iter()
+            .map(|batch| batch.to_pyarrow(py))
+            .collect::<PyResult<Vec<_>>>()?;
+        let table = py.import("pyarrow")?.getattr("Table")?;
+        Ok(table
+            .call_method1("from_batches", (batches, schema.to_pyarrow(py)?))?
+            .unbind())
+    }
+
+    /// Read the table into a `pyarrow.RecordBatchReader`, whose batches are read as they are
+    /// pulled instead of all at once.
+    fn to_arrow_batch_reader(
+        &self,
+        py: Python<'_>,
+    ) -> PyResult<PyArrowType<Box<dyn RecordBatchReader + Send>>> {
+        let (schema, batches) = self.stream(py)?;
+        Ok(PyArrowType(Box::new(StreamRecordBatchReader {
+            schema,
+            batches,
+        })))
+    }
+}
+
+#[pymodule]
+fn paimon_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Catalog>()?;
+    m.add_class::<Table>()?;
+    m.add_class::<ReadBuilder>()?;
+    m.add("PaimonError", m.py().get_type::<PaimonError>())?;
+    Ok(())
+}
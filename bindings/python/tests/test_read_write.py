@@ -0,0 +1,81 @@
+# Licensed to the Apache Software Foundation (ASF) under one
+# or more contributor license agreements.  See the NOTICE file
+# distributed with this work for additional information
+# regarding copyright ownership.  The ASF licenses this file
+# to you under the Apache License, Version 2.0 (the
+# "License"); you may not use this file except in compliance
+# with the License.  You may obtain a copy of the License at
+#
+#   http://www.apache.org/licenses/LICENSE-2.0
+#
+# Unless required by applicable law or agreed to in writing,
+# software distributed under the License is distributed on an
+# "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+# KIND, either express or implied.  See the License for the
+# specific language governing permissions and limitations
+# under the License.
+
+import pyarrow as pa
+import pytest
+
+from paimon_py import Catalog, PaimonError
+
+
+def test_read_write(tmp_path):
+    catalog = Catalog(f"file:{tmp_path}")
+    catalog.create_database("db")
+    catalog.create_table(
+        "db.t",
+        [("id", "INT"), ("name", "STRING")],
+        options={"file.format": "parquet"},
+    )
+    assert catalog.list_tables("db") == ["t"]
+
+    table = catalog.get_table("db.t")
+    data = pa.table(
+        {"id": pa.array([1, 2], pa.int32()), "name": ["a", "b"]},
+        schema=table.schema,
+    )
+    table.write_arrow(data)
+
+    read_builder = table.new_read_builder()
+    assert read_builder.to_arrow() == data
+    names = read_builder.with_projection(["name"]).to_arrow_batch_reader().read_all()
+    assert names.column("name").to_pylist() == ["a", "b"]
+    assert read_builder.with_snapshot(1).to_arrow().num_rows == 2
+    row_kinds = read_builder.with_row_kind_column("_row_kind").to_arrow()
+    assert row_kinds.column("_row_kind").to_pylist() == ["+I", "+I"]
+    assert read_builder.with_limit(1).to_arrow().num_rows == 1
+
+    with pytest.raises(PaimonError):
+        catalog.get_table("db.missing")
+
+
+def test_read_batches_incrementally(tmp_path):
+    catalog = Catalog(f"file:{tmp_path}")
+    catalog.create_database("db")
+    catalog.create_table(
+        "db.t",
+        [("id", "INT"), ("name", "STRING")],
+        options={"file.format": "parquet"},
+    )
+    table = catalog.get_table("db.t")
+    for ids in ([1, 2], [3, 4], [5, 6]):
+        data = pa.table(
+            {"id": pa.array(ids, pa.int32()), "name": [str(i) for i in ids]},
+            schema=table.schema,
+        )
+        table.write_arrow(data)
+
+    # the batch of each data file is pulled one by one
+    reader = table.new_read_builder().to_arrow_batch_reader()
+    assert reader.schema == table.schema
+    assert reader.read_next_batch().num_rows == 2
+    assert [batch.num_rows for batch in reader] == [2, 2]
+
+    # the data files are only read once the batches are pulled
+    reader = table.new_read_builder().to_arrow_batch_reader()
+    for data_file in (tmp_path / "db.db" / "t").glob("bucket-*/data-*"):
+        data_file.unlink()
+    with pytest.raises(pa.ArrowException):
+        reader.read_next_batch()
@@ -0,0 +1,393 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Command line tool to inspect and maintain the tables of a filesystem catalog.
+
+use arrow_array::RecordBatch;
+use arrow_cast::pretty::pretty_format_batches;
+use clap::{Parser, Subcommand};
+use paimon::catalog::{Catalog, FileSystemCatalog, Identifier};
+use paimon::io::FileIOBuilder;
+use paimon::manifest::{ManifestFile, ManifestList};
+use paimon::spec::{BinaryRow, FileKind};
+use paimon::table::{FileStoreTable, SnapshotValidator, SystemTable, Table};
+use std::io::Write;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "paimon-cli",
+    about = "Inspect and maintain Apache Paimon tables"
+)]
+struct Cli {
+    /// Warehouse of the filesystem catalog, like `file:/tmp/warehouse`.
+    #[arg(long, short)]
+    warehouse: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Describe the schema and the options of a table.
+    Describe { table: String },
+    /// List the snapshots of a table.
+    Snapshots { table: String },
+    /// List the data files of the latest snapshot of a table.
+    Files { table: String },
+    /// Inspect the manifests of a table.
+    Manifest {
+        #[command(subcommand)]
+        command: ManifestCommand,
+    },
+    /// Expire the snapshots of a table by its retention options or the given ones.
+    Expire {
+        table: String,
+        #[arg(long)]
+        retain_min: Option<usize>,
+        #[arg(long)]
+        retain_max: Option<usize>,
+        #[arg(long)]
+        older_than_millis: Option<u64>,
+    },
+    /// Fully compact a table.
+    Compact { table: String },
+    /// Check the manifests referenced by the snapshots of a table.
+    Validate { table: String },
+    /// Compute and commit the statistics of the columns of a table, all of them by default.
+    Analyze {
+        table: String,
+        #[arg(long, value_delimiter = ',')]
+        columns: Vec<String>,
+    },
+    /// Create a tag of a snapshot of a table, the latest one by default.
+    CreateTag {
+        table: String,
+        tag: String,
+        #[arg(long)]
+        snapshot: Option<i64>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum ManifestCommand {
+    /// Dump the entries of the manifests of a snapshot of a table, the latest one by default.
+    Dump {
+        table: String,
+        #[arg(long)]
+        snapshot: Option<i64>,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli, &mut std::io::stdout()).await {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli, out: &mut impl Write) -> Result<()> {
+    let file_io = FileIOBuilder::from_url(&cli.warehouse)?.build()?;
+    let catalog = FileSystemCatalog::new(file_io, &cli.warehouse);
+    match cli.command {
+        Command::Describe { table } => {
+            let table = get_table(&catalog, &table).await?;
+            describe(&table, out)
+        }
+        Command::Snapshots { table } => {
+            print_system_table(&catalog, &table, "snapshots", out).await
+        }
+        Command::Files { table } => print_system_table(&catalog, &table, "files", out).await,
+        Command::Manifest {
+            command: ManifestCommand::Dump { table, snapshot },
+        } => {
+            let table = get_table(&catalog, &table).await?;
+            dump_manifests(&table, snapshot, out).await
+        }
+        Command::Expire {
+            table,
+            retain_min,
+            retain_max,
+            older_than_millis,
+        } => {
+            let table = get_table(&catalog, &table).await?;
+            let mut expire = table.new_expire_snapshots();
+            if let Some(retain_min) = retain_min {
+                expire = expire.with_retain_min(retain_min);
+            }
+            if let Some(retain_max) = retain_max {
+                expire = expire.with_retain_max(retain_max);
+            }
+            if let Some(older_than_millis) = older_than_millis {
+                expire = expire.with_older_than_millis(older_than_millis);
+            }
+            let expired = expire.expire().await?;
+            writeln!(out, "Expired {expired} snapshots")?;
+            Ok(())
+        }
+        Command::Compact { table } => {
+            let table = get_table(&catalog, &table).await?;
+            table.new_compact().compact().await?;
+            writeln!(out, "Compacted {}", table.identifier())?;
+            Ok(())
+        }
+        Command::Validate { table } => {
+            let table = get_table(&catalog, &table).await?;
+            let issues = SnapshotValidator::new(table).validate_all().await?;
+            if issues.is_empty() {
+                writeln!(out, "No issue found")?;
+            }
+            for (snapshot_id, issues) in issues {
+                for issue in issues {
+                    writeln!(out, "Snapshot {snapshot_id}: {issue}")?;
+                }
+            }
+            Ok(())
+        }
+        Command::Analyze { table, columns } => {
+            let table = get_table(&catalog, &table).await?;
+            let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+            match table.new_analyze().with_columns(&columns).analyze().await? {
+                Some(statistics) => writeln!(
+                    out,
+                    "Analyzed {} of snapshot {}",
+                    table.identifier(),
+                    statistics.snapshot_id()
+                )?,
+                None => writeln!(out, "Nothing to analyze of {}", table.identifier())?,
+            }
+            Ok(())
+        }
+        Command::CreateTag {
+            table,
+            tag,
+            snapshot,
+        } => {
+            let table = get_table(&catalog, &table).await?;
+            let snapshot_id = match snapshot {
+                Some(snapshot_id) => snapshot_id,
+                None => table
+                    .snapshot_manager()
+                    .latest_snapshot_id()
+                    .await?
+                    .ok_or("the table has no snapshot")?,
+            };
+            table.create_tag(&tag, snapshot_id).await?;
+            writeln!(out, "Created tag {tag} of snapshot {snapshot_id}")?;
+            Ok(())
+        }
+    }
+}
+
+async fn get_table(catalog: &FileSystemCatalog, table: &str) -> Result<FileStoreTable> {
+    let identifier: Identifier = table.parse()?;
+    Ok(catalog.get_table(&identifier).await?)
+}
+
+fn describe(table: &FileStoreTable, out: &mut impl Write) -> Result<()> {
+    let schema = table.schema();
+    writeln!(out, "Table: {}", table.identifier())?;
+    writeln!(out, "Schema id: {}", schema.id())?;
+    writeln!(out, "Fields:")?;
+    for field in schema.fields() {
+        writeln!(out, "  {} {}", field.name(), field.data_type())?;
+    }
+    writeln!(
+        out,
+        "Partition keys: [{}]",
+        schema.partition_keys().join(", ")
+    )?;
+    writeln!(out, "Primary keys: [{}]", schema.primary_keys().join(", "))?;
+    let mut options: Vec<_> = schema.options().iter().collect();
+    options.sort();
+    writeln!(out, "Options:")?;
+    for (key, value) in options {
+        writeln!(out, "  {key} = {value}")?;
+    }
+    Ok(())
+}
+
+async fn print_system_table(
+    catalog: &FileSystemCatalog,
+    table: &str,
+    name: &str,
+    out: &mut impl Write,
+) -> Result<()> {
+    let identifier: Identifier = table.parse()?;
+    let identifier = Identifier::new(
+        identifier.database(),
+        format!("{}${name}", identifier.object()),
+    );
+    let system_table: Box<dyn SystemTable> = catalog.get_system_table(&identifier).await?;
+    let batches = system_table
+        .read()
+        .await?
+        .collect::<paimon::Result<Vec<RecordBatch>>>()?;
+    writeln!(out, "{}", pretty_format_batches(&batches)?)?;
+    Ok(())
+}
+
+async fn dump_manifests(
+    table: &FileStoreTable,
+    snapshot_id: Option<i64>,
+    out: &mut impl Write,
+) -> Result<()> {
+    let snapshot_manager = table.snapshot_manager();
+    let snapshot = match snapshot_id {
+        Some(snapshot_id) => snapshot_manager.snapshot(snapshot_id).await?,
+        None => snapshot_manager
+            .latest_snapshot()
+            .await?
+            .ok_or("the table has no snapshot")?,
+    };
+    let path_factory = table.path_factory();
+    let manifest_list = ManifestList::new(table.file_io().clone());
+    let manifest_file = ManifestFile::new(table.file_io().clone());
+    let partition_type = table.schema().logical_partition_type();
+    writeln!(out, "Snapshot: {}", snapshot.id())?;
+    for list in [
+        snapshot.base_manifest_list(),
+        snapshot.delta_manifest_list(),
+    ] {
+        for manifest in manifest_list
+            .read(&path_factory.manifest_path(list))
+            .await?
+        {
+            writeln!(out, "Manifest: {}", manifest.file_name())?;
+            let path = path_factory.manifest_path(manifest.file_name());
+            for entry in manifest_file.read(&path).await? {
+                let partition = BinaryRow::from_serialized_bytes(entry.partition())?;
+                let mut values = Vec::with_capacity(partition_type.fields().len());
+                for (pos, field) in partition_type.fields().iter().enumerate() {
+                    values.push(match partition.get_datum(pos, field.data_type())? {
+                        Some(datum) => datum.to_string(),
+                        None => "null".to_string(),
+                    });
+                }
+                let kind = match entry.kind() {
+                    FileKind::Add => "+",
+                    FileKind::Delete => "-",
+                };
+                writeln!(
+                    out,
+                    "  {kind} partition=[{}] bucket={} level={} file={} rows={}",
+                    values.join(", "),
+                    entry.bucket(),
+                    entry.level(),
+                    entry.file_name(),
+                    entry.file().row_count,
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Int32Array;
+    use paimon::format::to_arrow_schema;
+    use paimon::spec::{DataField, Schema};
+    use std::sync::Arc;
+
+    const WAREHOUSE: &str = "file:/tmp/test_paimon_cli";
+
+    async fn exec(args: &[&str]) -> String {
+        let cli = Cli::try_parse_from(["paimon-cli", "--warehouse", WAREHOUSE].iter().chain(args))
+            .unwrap();
+        let mut out = Vec::new();
+        run(cli, &mut out).await.unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_commands() {
+        let file_io = FileIOBuilder::from_url(WAREHOUSE).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{WAREHOUSE}/")).await;
+        let catalog = FileSystemCatalog::new(file_io, WAREHOUSE);
+        catalog
+            .create_database("db", false, Default::default())
+            .await
+            .unwrap();
+        let schema = Schema::builder()
+            .fields(vec![DataField::new(
+                0,
+                "id".to_string(),
+                "INT".parse().unwrap(),
+            )])
+            .options([("file.format".to_string(), "parquet".to_string())].into())
+            .build();
+        let identifier = Identifier::new("db", "t");
+        catalog
+            .create_table(&identifier, schema, false)
+            .await
+            .unwrap();
+        let table = catalog.get_table(&identifier).await.unwrap();
+        for ids in [vec![1, 2], vec![3]] {
+            let batch = RecordBatch::try_new(
+                Arc::new(to_arrow_schema(table.schema().fields())),
+                vec![Arc::new(Int32Array::from(ids))],
+            )
+            .unwrap();
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            write.write(&batch).await.unwrap();
+            let messages = write.prepare_commit().await.unwrap();
+            builder.new_commit().commit(messages).await.unwrap();
+        }
+
+        let describe = exec(&["describe", "db.t"]).await;
+        assert!(describe.contains("Table: db.t"));
+        assert!(describe.contains("  id INT"));
+        assert!(describe.contains("  file.format = parquet"));
+        assert!(exec(&["snapshots", "db.t"]).await.contains("APPEND"));
+        let dump = exec(&["manifest", "dump", "db.t", "--snapshot", "1"]).await;
+        assert!(dump.starts_with("Snapshot: 1"));
+        assert_eq!(dump.matches("  + partition=[] bucket=0 level=0").count(), 1);
+
+        assert_eq!(
+            exec(&["create-tag", "db.t", "t1"]).await,
+            "Created tag t1 of snapshot 2\n"
+        );
+        assert_eq!(exec(&["compact", "db.t"]).await, "Compacted db.t\n");
+        let files = exec(&["files", "db.t"]).await;
+        assert_eq!(files.matches(".parquet").count(), 1);
+        assert_eq!(
+            exec(&[
+                "expire",
+                "db.t",
+                "--retain-min",
+                "1",
+                "--older-than-millis",
+                &u64::MAX.to_string(),
+            ])
+            .await,
+            "Expired 2 snapshots\n"
+        );
+        assert_eq!(
+            exec(&["analyze", "db.t", "--columns", "id"]).await,
+            "Analyzed db.t of snapshot 3\n"
+        );
+        assert!(exec(&["snapshots", "db.t"]).await.contains("ANALYZE"));
+        assert_eq!(exec(&["validate", "db.t"]).await, "No issue found\n");
+    }
+}
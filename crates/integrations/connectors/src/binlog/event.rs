@@ -0,0 +1,720 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Decoding of the row events of the binlog of MySQL into Maxwell messages.
+//!
+//! Reference: <https://dev.mysql.com/doc/dev/mysql-server/latest/page_protocol_replication_binlog_event.html>
+
+use crate::database::SourceColumn;
+use crate::error::*;
+use serde_json::{json, Map, Value};
+use std::collections::HashMap;
+
+/// Types of the events decoded.
+pub(crate) const TABLE_MAP_EVENT: u8 = 19;
+const WRITE_ROWS_EVENT_V1: u8 = 23;
+const UPDATE_ROWS_EVENT_V1: u8 = 24;
+const DELETE_ROWS_EVENT_V1: u8 = 25;
+pub(crate) const WRITE_ROWS_EVENT: u8 = 30;
+pub(crate) const UPDATE_ROWS_EVENT: u8 = 31;
+pub(crate) const DELETE_ROWS_EVENT: u8 = 32;
+
+/// Size of the header of the events of the binlog version 4.
+pub(crate) const EVENT_HEADER_SIZE: usize = 19;
+
+/// Types of the columns in the table map events.
+const TYPE_TINY: u8 = 1;
+const TYPE_SHORT: u8 = 2;
+pub(crate) const TYPE_LONG: u8 = 3;
+const TYPE_FLOAT: u8 = 4;
+const TYPE_DOUBLE: u8 = 5;
+const TYPE_TIMESTAMP: u8 = 7;
+const TYPE_LONGLONG: u8 = 8;
+const TYPE_INT24: u8 = 9;
+const TYPE_DATE: u8 = 10;
+const TYPE_TIME: u8 = 11;
+const TYPE_DATETIME: u8 = 12;
+const TYPE_YEAR: u8 = 13;
+const TYPE_NEWDATE: u8 = 14;
+pub(crate) const TYPE_VARCHAR: u8 = 15;
+const TYPE_BIT: u8 = 16;
+const TYPE_TIMESTAMP2: u8 = 17;
+const TYPE_DATETIME2: u8 = 18;
+const TYPE_TIME2: u8 = 19;
+const TYPE_JSON: u8 = 245;
+pub(crate) const TYPE_NEWDECIMAL: u8 = 246;
+const TYPE_ENUM: u8 = 247;
+const TYPE_SET: u8 = 248;
+const TYPE_TINY_BLOB: u8 = 249;
+const TYPE_MEDIUM_BLOB: u8 = 250;
+const TYPE_LONG_BLOB: u8 = 251;
+const TYPE_BLOB: u8 = 252;
+const TYPE_VAR_STRING: u8 = 253;
+const TYPE_STRING: u8 = 254;
+const TYPE_GEOMETRY: u8 = 255;
+
+fn truncated() -> Error {
+    SourceUnexpectedSnafu {
+        message: "Truncated binlog event",
+    }
+    .build()
+}
+
+/// Reader of the fields of an event.
+pub(crate) struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.buf.is_empty()
+    }
+
+    pub(crate) fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if self.buf.len() < n {
+            return Err(truncated());
+        }
+        let (bytes, rest) = self.buf.split_at(n);
+        self.buf = rest;
+        Ok(bytes)
+    }
+
+    pub(crate) fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    /// Read the little endian unsigned integer of the bytes.
+    pub(crate) fn uint(&mut self, n: usize) -> Result<u64> {
+        Ok(self
+            .take(n)?
+            .iter()
+            .rev()
+            .fold(0, |value, byte| (value << 8) | *byte as u64))
+    }
+
+    /// Read the big endian unsigned integer of the bytes.
+    fn uint_be(&mut self, n: usize) -> Result<u64> {
+        Ok(self
+            .take(n)?
+            .iter()
+            .fold(0, |value, byte| (value << 8) | *byte as u64))
+    }
+
+    /// Read the length encoded integer.
+    pub(crate) fn lenenc(&mut self) -> Result<u64> {
+        match self.u8()? {
+            0xfc => self.uint(2),
+            0xfd => self.uint(3),
+            0xfe => self.uint(8),
+            value => Ok(value as u64),
+        }
+    }
+
+    /// Read the string terminated by NUL.
+    pub(crate) fn null_terminated(&mut self) -> Result<&'a [u8]> {
+        let end = self
+            .buf
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(truncated)?;
+        let bytes = self.take(end)?;
+        self.take(1)?;
+        Ok(bytes)
+    }
+
+    pub(crate) fn rest(&mut self) -> &'a [u8] {
+        std::mem::take(&mut self.buf)
+    }
+}
+
+fn bit(bitmap: &[u8], i: usize) -> bool {
+    bitmap[i / 8] & (1 << (i % 8)) != 0
+}
+
+/// Type of a column in a table map event, with its metadata.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ColumnType {
+    pub(crate) code: u8,
+    pub(crate) meta: u16,
+}
+
+/// Decoder of the events of a table, keeping the table ids mapped to it.
+#[derive(Debug)]
+pub(crate) struct EventDecoder {
+    database: String,
+    table: String,
+    columns: Vec<SourceColumn>,
+    /// Types of the columns of the table by the table ids mapped to it.
+    table_ids: HashMap<u64, Vec<ColumnType>>,
+}
+
+impl EventDecoder {
+    pub(crate) fn new(database: String, table: String, columns: Vec<SourceColumn>) -> Self {
+        Self {
+            database,
+            table,
+            columns,
+            table_ids: HashMap::new(),
+        }
+    }
+
+    /// Decode the event without the checksum into the Maxwell messages of the rows of the
+    /// table changed.
+    pub(crate) fn decode(&mut self, event: &[u8]) -> Result<Vec<Value>> {
+        if event.len() < EVENT_HEADER_SIZE {
+            return Err(truncated());
+        }
+        let body = &event[EVENT_HEADER_SIZE..];
+        match event[4] {
+            TABLE_MAP_EVENT => {
+                self.decode_table_map(body)?;
+                Ok(vec![])
+            }
+            WRITE_ROWS_EVENT_V1 => self.decode_rows(body, "insert", false),
+            UPDATE_ROWS_EVENT_V1 => self.decode_rows(body, "update", false),
+            DELETE_ROWS_EVENT_V1 => self.decode_rows(body, "delete", false),
+            WRITE_ROWS_EVENT => self.decode_rows(body, "insert", true),
+            UPDATE_ROWS_EVENT => self.decode_rows(body, "update", true),
+            DELETE_ROWS_EVENT => self.decode_rows(body, "delete", true),
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Map the table id to the types of the columns if it's the id of the table, a table
+    /// id may be reused by another table once the table is altered or evicted.
+    fn decode_table_map(&mut self, body: &[u8]) -> Result<()> {
+        let mut reader = Reader::new(body);
+        let table_id = reader.uint(6)?;
+        reader.take(2)?;
+        let database_len = reader.u8()? as usize;
+        let database = reader.take(database_len)?;
+        reader.take(1)?;
+        let table_len = reader.u8()? as usize;
+        let table = reader.take(table_len)?;
+        reader.take(1)?;
+        if database != self.database.as_bytes() || table != self.table.as_bytes() {
+            self.table_ids.remove(&table_id);
+            return Ok(());
+        }
+
+        let column_count = reader.lenenc()? as usize;
+        let codes = reader.take(column_count)?;
+        let meta_len = reader.lenenc()? as usize;
+        let mut meta = Reader::new(reader.take(meta_len)?);
+        let mut types = Vec::with_capacity(column_count);
+        for code in codes {
+            let meta = match *code {
+                TYPE_FLOAT | TYPE_DOUBLE | TYPE_BLOB | TYPE_GEOMETRY | TYPE_JSON
+                | TYPE_TIMESTAMP2 | TYPE_DATETIME2 | TYPE_TIME2 => meta.u8()? as u16,
+                TYPE_VARCHAR | TYPE_VAR_STRING => meta.uint(2)? as u16,
+                TYPE_BIT | TYPE_NEWDECIMAL | TYPE_STRING | TYPE_ENUM | TYPE_SET => {
+                    ((meta.u8()? as u16) << 8) | meta.u8()? as u16
+                }
+                _ => 0,
+            };
+            types.push(ColumnType { code: *code, meta });
+        }
+        if types.len() != self.columns.len() {
+            return SourceUnexpectedSnafu {
+                message: format!(
+                    "Table {} has {} columns in the binlog instead of {}, the sync should be restarted once the table is altered",
+                    self.table,
+                    types.len(),
+                    self.columns.len()
+                ),
+            }
+            .fail();
+        }
+        self.table_ids.insert(table_id, types);
+        Ok(())
+    }
+
+    /// Decode the rows of a rows event of the version 1 or 2, whose rows before and after are
+    /// in pairs for updates.
+    fn decode_rows(&self, body: &[u8], kind: &str, v2: bool) -> Result<Vec<Value>> {
+        let mut reader = Reader::new(body);
+        let table_id = reader.uint(6)?;
+        let Some(types) = self.table_ids.get(&table_id) else {
+            return Ok(vec![]);
+        };
+        reader.take(2)?;
+        if v2 {
+            let extra_len = reader.uint(2)? as usize;
+            reader.take(extra_len.saturating_sub(2))?;
+        }
+        let column_count = reader.lenenc()? as usize;
+        let bitmap_len = column_count.div_ceil(8);
+        let present = reader.take(bitmap_len)?;
+        let present_after = match kind {
+            "update" => reader.take(bitmap_len)?,
+            _ => present,
+        };
+
+        let mut messages = Vec::new();
+        while !reader.is_empty() {
+            let data = self.decode_row(&mut reader, types, present)?;
+            let message = match kind {
+                "update" => {
+                    let before = data;
+                    let data = self.decode_row(&mut reader, types, present_after)?;
+                    let old: Map<String, Value> = before
+                        .into_iter()
+                        .filter(|(name, value)| data.get(name) != Some(value))
+                        .collect();
+                    json!({"database": self.database, "table": self.table, "type": kind, "data": data, "old": old})
+                }
+                _ => {
+                    json!({"database": self.database, "table": self.table, "type": kind, "data": data})
+                }
+            };
+            messages.push(message);
+        }
+        Ok(messages)
+    }
+
+    /// Decode the values of the columns present in the row as text.
+    fn decode_row(
+        &self,
+        reader: &mut Reader,
+        types: &[ColumnType],
+        present: &[u8],
+    ) -> Result<Map<String, Value>> {
+        let present_count = (0..types.len()).filter(|i| bit(present, *i)).count();
+        let nulls = reader.take(present_count.div_ceil(8))?;
+        let mut row = Map::new();
+        let mut index = 0;
+        for (i, (column_type, column)) in types.iter().zip(&self.columns).enumerate() {
+            if !bit(present, i) {
+                continue;
+            }
+            let value = if bit(nulls, index) {
+                Value::Null
+            } else {
+                Value::String(decode_value(reader, *column_type, column)?)
+            };
+            index += 1;
+            row.insert(column.name.clone(), value);
+        }
+        Ok(row)
+    }
+}
+
+/// Decode the value of the column as the text MySQL casts it into, the signedness of the
+/// integers and the labels of the enums and sets are of the declared type of the column.
+pub(crate) fn decode_value(
+    reader: &mut Reader,
+    column_type: ColumnType,
+    column: &SourceColumn,
+) -> Result<String> {
+    let unsigned = column.data_type.to_ascii_lowercase().contains("unsigned");
+    let integer = |reader: &mut Reader, size: usize| -> Result<String> {
+        let value = reader.uint(size)?;
+        if unsigned {
+            return Ok(value.to_string());
+        }
+        let shift = 64 - size * 8;
+        Ok((((value << shift) as i64) >> shift).to_string())
+    };
+    let meta = column_type.meta;
+    let value = match column_type.code {
+        TYPE_TINY => integer(reader, 1)?,
+        TYPE_SHORT => integer(reader, 2)?,
+        TYPE_INT24 => integer(reader, 3)?,
+        TYPE_LONG => integer(reader, 4)?,
+        TYPE_LONGLONG => integer(reader, 8)?,
+        TYPE_FLOAT => f32::from_bits(reader.uint(4)? as u32).to_string(),
+        TYPE_DOUBLE => f64::from_bits(reader.uint(8)?).to_string(),
+        TYPE_NEWDECIMAL => decode_decimal(reader, (meta >> 8) as usize, (meta & 0xff) as usize)?,
+        TYPE_YEAR => match reader.u8()? {
+            0 => "0000".to_string(),
+            year => (1900 + year as u32).to_string(),
+        },
+        TYPE_DATE | TYPE_NEWDATE => {
+            let value = reader.uint(3)?;
+            format!(
+                "{:04}-{:02}-{:02}",
+                value >> 9,
+                (value >> 5) & 0x0f,
+                value & 0x1f
+            )
+        }
+        TYPE_TIME => {
+            let value = reader.uint(3)?;
+            let value = ((value << 40) as i64) >> 40;
+            let sign = if value < 0 { "-" } else { "" };
+            let value = value.unsigned_abs();
+            format!(
+                "{sign}{:02}:{:02}:{:02}",
+                value / 10000,
+                value / 100 % 100,
+                value % 100
+            )
+        }
+        TYPE_DATETIME => {
+            let value = reader.uint(8)?;
+            let (date, time) = (value / 1_000_000, value % 1_000_000);
+            format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+                date / 10000,
+                date / 100 % 100,
+                date % 100,
+                time / 10000,
+                time / 100 % 100,
+                time % 100
+            )
+        }
+        TYPE_TIMESTAMP => format_timestamp(reader.uint(4)?, ""),
+        TYPE_TIMESTAMP2 => {
+            let seconds = reader.uint_be(4)?;
+            let fraction = decode_fraction(reader, meta)?;
+            format_timestamp(seconds, &fraction)
+        }
+        TYPE_DATETIME2 => {
+            let value = reader.uint_be(5)?.wrapping_sub(0x80_0000_0000);
+            let fraction = decode_fraction(reader, meta)?;
+            let (date, time) = (value >> 17, value & 0x1_ffff);
+            let (year_month, day) = (date >> 5, date & 0x1f);
+            format!(
+                "{:04}-{:02}-{:02} {:02}:{:02}:{:02}{fraction}",
+                year_month / 13,
+                year_month % 13,
+                day,
+                time >> 12,
+                (time >> 6) & 0x3f,
+                time & 0x3f
+            )
+        }
+        TYPE_TIME2 => {
+            let value = reader.uint_be(3)? as i64 - 0x80_0000;
+            let fraction = decode_fraction(reader, meta)?;
+            let sign = if value < 0 { "-" } else { "" };
+            let value = value.unsigned_abs();
+            format!(
+                "{sign}{:02}:{:02}:{:02}{fraction}",
+                (value >> 12) & 0x3ff,
+                (value >> 6) & 0x3f,
+                value & 0x3f
+            )
+        }
+        TYPE_VARCHAR | TYPE_VAR_STRING => {
+            let len = reader.uint(if meta < 256 { 1 } else { 2 })? as usize;
+            String::from_utf8_lossy(reader.take(len)?).into_owned()
+        }
+        TYPE_STRING | TYPE_ENUM | TYPE_SET => {
+            let real_type = (meta >> 8) as u8;
+            match real_type {
+                TYPE_ENUM => {
+                    let index = reader.uint((meta & 0xff) as usize)? as usize;
+                    labels(&column.data_type)
+                        .get(index.wrapping_sub(1))
+                        .cloned()
+                        .unwrap_or_default()
+                }
+                TYPE_SET => {
+                    let bits = reader.uint((meta & 0xff) as usize)?;
+                    labels(&column.data_type)
+                        .into_iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i < 64 && bits & (1 << i) != 0)
+                        .map(|(_, label)| label)
+                        .collect::<Vec<_>>()
+                        .join(",")
+                }
+                _ => {
+                    let max_len = (((meta >> 4) & 0x300) ^ 0x300) + (meta & 0xff);
+                    let len = reader.uint(if max_len < 256 { 1 } else { 2 })? as usize;
+                    String::from_utf8_lossy(reader.take(len)?).into_owned()
+                }
+            }
+        }
+        TYPE_BLOB | TYPE_TINY_BLOB | TYPE_MEDIUM_BLOB | TYPE_LONG_BLOB => {
+            let len = reader.uint(meta as usize)? as usize;
+            String::from_utf8_lossy(reader.take(len)?).into_owned()
+        }
+        TYPE_BIT => {
+            let len = (meta & 0xff) as usize + usize::from(meta >> 8 > 0);
+            reader.uint_be(len)?.to_string()
+        }
+        code => {
+            return SourceUnexpectedSnafu {
+                message: format!(
+                    "Column {} of type {} isn't supported by the binlog client, type code {code}",
+                    column.name, column.data_type
+                ),
+            }
+            .fail()
+        }
+    };
+    Ok(value)
+}
+
+/// Labels of the type like `enum('a','b')` or `set('a','b')`.
+fn labels(data_type: &str) -> Vec<String> {
+    let mut labels = Vec::new();
+    let mut chars = data_type.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\'' {
+            continue;
+        }
+        let mut label = String::new();
+        while let Some(c) = chars.next() {
+            if c == '\'' {
+                if chars.peek() == Some(&'\'') {
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            label.push(c);
+        }
+        labels.push(label);
+    }
+    labels
+}
+
+/// Decode the fraction of the seconds of the precision, as the text following the seconds.
+fn decode_fraction(reader: &mut Reader, precision: u16) -> Result<String> {
+    let micros = match precision {
+        1 | 2 => reader.uint_be(1)? * 10000,
+        3 | 4 => reader.uint_be(2)? * 100,
+        5 | 6 => reader.uint_be(3)?,
+        _ => return Ok(String::new()),
+    };
+    Ok(format!(".{micros:06}")[..precision as usize + 1].to_string())
+}
+
+/// Format the seconds since the epoch in UTC, which is the time zone of the sessions of sqlx.
+fn format_timestamp(seconds: u64, fraction: &str) -> String {
+    if seconds == 0 {
+        return format!("0000-00-00 00:00:00{fraction}");
+    }
+    // the civil date of the days since the epoch, of Howard Hinnant's algorithm
+    let days = (seconds / 86400) as i64 + 719468;
+    let era = days / 146097;
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    let time = seconds % 86400;
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}{fraction}",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+/// Decode the packed decimal of the precision and scale.
+///
+/// Impl Reference: <https://github.com/mysql/mysql-server/blob/8.0/strings/decimal.cc>
+fn decode_decimal(reader: &mut Reader, precision: usize, scale: usize) -> Result<String> {
+    const DIGITS_PER_INT: usize = 9;
+    const BYTES_OF_DIGITS: [usize; 10] = [0, 1, 1, 2, 2, 3, 3, 4, 4, 4];
+    let integral = precision - scale.min(precision);
+    let (integral_ints, integral_digits) = (integral / DIGITS_PER_INT, integral % DIGITS_PER_INT);
+    let (fraction_ints, fraction_digits) = (scale / DIGITS_PER_INT, scale % DIGITS_PER_INT);
+    let size = integral_ints * 4
+        + BYTES_OF_DIGITS[integral_digits]
+        + fraction_ints * 4
+        + BYTES_OF_DIGITS[fraction_digits];
+    let mut bytes = reader.take(size)?.to_vec();
+    if bytes.is_empty() {
+        return Ok("0".to_string());
+    }
+    let positive = bytes[0] & 0x80 != 0;
+    bytes[0] ^= 0x80;
+    if !positive {
+        bytes.iter_mut().for_each(|byte| *byte ^= 0xff);
+    }
+
+    let mut digits = Reader::new(&bytes);
+    let mut integer = String::new();
+    if integral_digits > 0 {
+        integer.push_str(
+            &digits
+                .uint_be(BYTES_OF_DIGITS[integral_digits])?
+                .to_string(),
+        );
+    }
+    for _ in 0..integral_ints {
+        integer.push_str(&format!("{:09}", digits.uint_be(4)?));
+    }
+    let integer = integer.trim_start_matches('0');
+    let mut value = String::new();
+    if !positive {
+        value.push('-');
+    }
+    value.push_str(if integer.is_empty() { "0" } else { integer });
+    if scale > 0 {
+        value.push('.');
+        for _ in 0..fraction_ints {
+            value.push_str(&format!("{:09}", digits.uint_be(4)?));
+        }
+        if fraction_digits > 0 {
+            let fraction = digits.uint_be(BYTES_OF_DIGITS[fraction_digits])?;
+            value.push_str(&format!("{fraction:0fraction_digits$}"));
+        }
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(data_type: &str) -> SourceColumn {
+        SourceColumn {
+            name: "c".to_string(),
+            data_type: data_type.to_string(),
+        }
+    }
+
+    fn decode(code: u8, meta: u16, data_type: &str, bytes: &[u8]) -> String {
+        let mut reader = Reader::new(bytes);
+        let value = decode_value(&mut reader, ColumnType { code, meta }, &column(data_type));
+        assert!(reader.is_empty());
+        value.unwrap()
+    }
+
+    #[test]
+    fn test_decode_values() {
+        // the examples of decimal.cc
+        let decimal = (14 << 8) | 4;
+        assert_eq!(
+            decode(
+                TYPE_NEWDECIMAL,
+                decimal,
+                "decimal(14,4)",
+                &[0x81, 0x0d, 0xfb, 0x38, 0xd2, 0x04, 0xd2]
+            ),
+            "1234567890.1234"
+        );
+        assert_eq!(
+            decode(
+                TYPE_NEWDECIMAL,
+                decimal,
+                "decimal(14,4)",
+                &[0x7e, 0xf2, 0x04, 0xc7, 0x2d, 0xfb, 0x2d]
+            ),
+            "-1234567890.1234"
+        );
+        assert_eq!(
+            decode(
+                TYPE_NEWDECIMAL,
+                (5 << 8) | 2,
+                "decimal(5,2)",
+                &[0x80, 0x00, 0x05]
+            ),
+            "0.05"
+        );
+
+        assert_eq!(decode(TYPE_TINY, 0, "tinyint", &[0xff]), "-1");
+        assert_eq!(decode(TYPE_TINY, 0, "tinyint unsigned", &[0xff]), "255");
+        assert_eq!(
+            decode(TYPE_INT24, 0, "mediumint", &[0xfe, 0xff, 0xff]),
+            "-2"
+        );
+        assert_eq!(
+            decode(TYPE_LONGLONG, 0, "bigint(20) unsigned", &[0xff; 8]),
+            u64::MAX.to_string()
+        );
+        assert_eq!(
+            decode(TYPE_DOUBLE, 8, "double", &1.5f64.to_le_bytes()),
+            "1.5"
+        );
+
+        // 2024-02-29 13:14:15.250 of precision 3
+        let date = ((2024 * 13 + 2) << 5) | 29;
+        let packed: u64 = ((date << 17) | (13 << 12) | (14 << 6) | 15) + 0x80_0000_0000;
+        let mut bytes = packed.to_be_bytes()[3..].to_vec();
+        bytes.extend_from_slice(&2500u16.to_be_bytes());
+        assert_eq!(
+            decode(TYPE_DATETIME2, 3, "datetime(3)", &bytes),
+            "2024-02-29 13:14:15.250"
+        );
+        assert_eq!(
+            decode(
+                TYPE_TIMESTAMP2,
+                0,
+                "timestamp",
+                &1709212455u32.to_be_bytes()
+            ),
+            "2024-02-29 13:14:15"
+        );
+        let date = (2024 << 9) | (2 << 5) | 29u32;
+        assert_eq!(
+            decode(TYPE_DATE, 0, "date", &date.to_le_bytes()[..3]),
+            "2024-02-29"
+        );
+        let time = ((13u32 << 12) | (14 << 6) | 15) + 0x80_0000;
+        assert_eq!(
+            decode(TYPE_TIME2, 0, "time", &time.to_be_bytes()[1..]),
+            "13:14:15"
+        );
+
+        assert_eq!(decode(TYPE_VARCHAR, 20, "varchar(20)", b"\x02ab"), "ab");
+        assert_eq!(
+            decode(
+                TYPE_STRING,
+                ((TYPE_STRING as u16) << 8) | 40,
+                "char(10)",
+                b"\x02ab"
+            ),
+            "ab"
+        );
+        assert_eq!(
+            decode(
+                TYPE_STRING,
+                ((TYPE_ENUM as u16) << 8) | 1,
+                "enum('a','it''s')",
+                &[2]
+            ),
+            "it's"
+        );
+        assert_eq!(
+            decode(
+                TYPE_STRING,
+                ((TYPE_SET as u16) << 8) | 1,
+                "set('a','b','c')",
+                &[5]
+            ),
+            "a,c"
+        );
+        assert_eq!(decode(TYPE_BLOB, 2, "text", b"\x02\x00ab"), "ab");
+        assert!(decode_value(
+            &mut Reader::new(&[]),
+            ColumnType {
+                code: TYPE_JSON,
+                meta: 4
+            },
+            &column("json")
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,51 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use snafu::prelude::*;
+
+/// Result type of the connectors.
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// Error type of the connectors.
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)))]
+pub enum Error {
+    #[snafu(display("Connector hitting paimon error: {}", source))]
+    Paimon { source: paimon::Error },
+    #[snafu(display("Connector hitting invalid json: {}", source))]
+    JsonInvalid { source: serde_json::Error },
+    #[snafu(display("Connector hitting invalid CDC message: {}", message))]
+    MessageInvalid { message: String },
+    #[snafu(display("Connector hitting unsupported CDC format: {}", format))]
+    FormatUnsupported { format: String },
+    #[snafu(display("Connector hitting unexpected source error: {}", message))]
+    SourceUnexpected { message: String },
+}
+
+impl From<paimon::Error> for Error {
+    fn from(source: paimon::Error) -> Self {
+        Error::Paimon { source }
+    }
+}
+
+impl From<arrow_schema::ArrowError> for Error {
+    fn from(source: arrow_schema::ArrowError) -> Self {
+        Error::Paimon {
+            source: source.into(),
+        }
+    }
+}
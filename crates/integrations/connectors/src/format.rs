@@ -0,0 +1,304 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use paimon::spec::RowKind;
+use serde_json::{Map, Value};
+use snafu::ResultExt;
+use std::str::FromStr;
+
+/// A row of the change log of a table of a database, the values of its columns are in JSON.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CdcRecord {
+    /// Database of the table changed, if known.
+    pub database: Option<String>,
+    /// Name of the table changed, if known.
+    pub table: Option<String>,
+    pub row_kind: RowKind,
+    /// Values of the columns in the order of the message.
+    pub fields: Map<String, Value>,
+}
+
+/// JSON formats of the CDC messages.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-flink/paimon-flink-cdc/src/main/java/org/apache/paimon/flink/action/cdc/format/DataFormat.java>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CdcFormat {
+    /// Messages of Debezium, whose rows before and after the change are in `before` and
+    /// `after` by the operation `op`, optionally wrapped by `payload` with the schema.
+    DebeziumJson,
+    /// Messages of Canal, whose rows are in `data` by the `type`, while the old values of the
+    /// updated columns are in `old`.
+    CanalJson,
+    /// Messages of Maxwell, whose row is in `data` by the `type`, while the old values of the
+    /// updated columns are in `old`.
+    MaxwellJson,
+}
+
+impl FromStr for CdcFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "debezium-json" => Ok(CdcFormat::DebeziumJson),
+            "canal-json" => Ok(CdcFormat::CanalJson),
+            "maxwell-json" => Ok(CdcFormat::MaxwellJson),
+            format => FormatUnsupportedSnafu { format }.fail(),
+        }
+    }
+}
+
+impl CdcFormat {
+    /// Parse the message into the records of the changes, an update is parsed into the
+    /// `-U` record of the row before and the `+U` record of the row after. Empty messages,
+    /// such as the tombstones of Kafka, and DDL messages are parsed into no record.
+    pub fn parse(&self, message: &[u8]) -> Result<Vec<CdcRecord>> {
+        if message.iter().all(u8::is_ascii_whitespace) {
+            return Ok(vec![]);
+        }
+        let value: Value = serde_json::from_slice(message).context(JsonInvalidSnafu)?;
+        let Value::Object(message) = value else {
+            return Ok(vec![]);
+        };
+        match self {
+            CdcFormat::DebeziumJson => parse_debezium(message),
+            CdcFormat::CanalJson => parse_canal(message),
+            CdcFormat::MaxwellJson => parse_maxwell(message),
+        }
+    }
+}
+
+fn parse_debezium(mut message: Map<String, Value>) -> Result<Vec<CdcRecord>> {
+    if let Some(Value::Object(payload)) = message.remove("payload") {
+        message = payload;
+    }
+    let source = message.get("source").and_then(Value::as_object);
+    let name = |key: &str| {
+        source
+            .and_then(|source| source.get(key))
+            .and_then(Value::as_str)
+            .map(str::to_string)
+    };
+    let (database, table) = (name("db"), name("table"));
+    let record = |row_kind: RowKind, key: &str| match message.get(key) {
+        Some(Value::Object(fields)) => Ok(CdcRecord {
+            database: database.clone(),
+            table: table.clone(),
+            row_kind,
+            fields: fields.clone(),
+        }),
+        _ => MessageInvalidSnafu {
+            message: format!("Debezium message without the row of {key}"),
+        }
+        .fail(),
+    };
+    match message.get("op").and_then(Value::as_str) {
+        Some("c" | "r") => Ok(vec![record(RowKind::Insert, "after")?]),
+        Some("u") => Ok(vec![
+            record(RowKind::UpdateBefore, "before")?,
+            record(RowKind::UpdateAfter, "after")?,
+        ]),
+        Some("d") => Ok(vec![record(RowKind::Delete, "before")?]),
+        op => MessageInvalidSnafu {
+            message: format!("Unknown Debezium operation {op:?}"),
+        }
+        .fail(),
+    }
+}
+
+fn parse_canal(message: Map<String, Value>) -> Result<Vec<CdcRecord>> {
+    if message.get("isDdl").and_then(Value::as_bool) == Some(true) {
+        return Ok(vec![]);
+    }
+    let name = |key: &str| message.get(key).and_then(Value::as_str).map(str::to_string);
+    let (database, table) = (name("database"), name("table"));
+    let rows = |key: &str| match message.get(key) {
+        Some(Value::Array(rows)) => rows
+            .iter()
+            .map(|row| match row {
+                Value::Object(fields) => Ok(fields.clone()),
+                _ => MessageInvalidSnafu {
+                    message: format!("Canal message of a {key} row of {row}"),
+                }
+                .fail(),
+            })
+            .collect::<Result<Vec<_>>>(),
+        _ => Ok(vec![]),
+    };
+    let rows_of_kinds = match message.get("type").and_then(Value::as_str) {
+        Some("INSERT") => rows("data")?
+            .into_iter()
+            .map(|row| (RowKind::Insert, row))
+            .collect(),
+        Some("UPDATE") => {
+            let olds = rows("old")?;
+            let mut rows_of_kinds = Vec::new();
+            for (i, row) in rows("data")?.into_iter().enumerate() {
+                rows_of_kinds.push((RowKind::UpdateBefore, with_old(&row, olds.get(i))));
+                rows_of_kinds.push((RowKind::UpdateAfter, row));
+            }
+            rows_of_kinds
+        }
+        Some("DELETE") => rows("data")?
+            .into_iter()
+            .map(|row| (RowKind::Delete, row))
+            .collect(),
+        // other types, such as CREATE of databases, change no row
+        _ => vec![],
+    };
+    Ok(rows_of_kinds
+        .into_iter()
+        .map(|(row_kind, fields)| CdcRecord {
+            database: database.clone(),
+            table: table.clone(),
+            row_kind,
+            fields,
+        })
+        .collect())
+}
+
+fn parse_maxwell(message: Map<String, Value>) -> Result<Vec<CdcRecord>> {
+    let name = |key: &str| message.get(key).and_then(Value::as_str).map(str::to_string);
+    let record = |row_kind: RowKind, fields: Map<String, Value>| CdcRecord {
+        database: name("database"),
+        table: name("table"),
+        row_kind,
+        fields,
+    };
+    let data = match message.get("data") {
+        Some(Value::Object(data)) => data.clone(),
+        _ => Map::new(),
+    };
+    let old = message.get("old").and_then(Value::as_object);
+    match message.get("type").and_then(Value::as_str) {
+        Some("insert" | "bootstrap-insert") => Ok(vec![record(RowKind::Insert, data)]),
+        Some("update") => Ok(vec![
+            record(RowKind::UpdateBefore, with_old(&data, old)),
+            record(RowKind::UpdateAfter, data),
+        ]),
+        Some("delete") => Ok(vec![record(RowKind::Delete, data)]),
+        // DDL messages and the bootstrap markers change no row
+        _ => Ok(vec![]),
+    }
+}
+
+/// Get the row before an update, which is the row after with the old values of the columns
+/// updated.
+fn with_old(row: &Map<String, Value>, old: Option<&Map<String, Value>>) -> Map<String, Value> {
+    let mut before = row.clone();
+    for (name, value) in old.into_iter().flatten() {
+        before.insert(name.clone(), value.clone());
+    }
+    before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    #[test]
+    fn test_parse_cdc_formats() {
+        let debezium = json!({
+            "payload": {
+                "before": {"id": 1, "name": "a"},
+                "after": {"id": 1, "name": "b"},
+                "source": {"db": "db", "table": "t"},
+                "op": "u"
+            }
+        });
+        let records = CdcFormat::DebeziumJson
+            .parse(debezium.to_string().as_bytes())
+            .unwrap();
+        assert_eq!(
+            records,
+            vec![
+                CdcRecord {
+                    database: Some("db".to_string()),
+                    table: Some("t".to_string()),
+                    row_kind: RowKind::UpdateBefore,
+                    fields: fields(json!({"id": 1, "name": "a"})),
+                },
+                CdcRecord {
+                    database: Some("db".to_string()),
+                    table: Some("t".to_string()),
+                    row_kind: RowKind::UpdateAfter,
+                    fields: fields(json!({"id": 1, "name": "b"})),
+                },
+            ]
+        );
+
+        let canal = json!({
+            "data": [{"id": "1", "name": "b"}, {"id": "2", "name": "c"}],
+            "old": [{"name": "a"}, {}],
+            "database": "db",
+            "table": "t",
+            "isDdl": false,
+            "type": "UPDATE"
+        });
+        let records = CdcFormat::CanalJson
+            .parse(canal.to_string().as_bytes())
+            .unwrap();
+        let kinds: Vec<_> = records
+            .iter()
+            .map(|r| (r.row_kind, r.fields.clone()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    RowKind::UpdateBefore,
+                    fields(json!({"id": "1", "name": "a"}))
+                ),
+                (
+                    RowKind::UpdateAfter,
+                    fields(json!({"id": "1", "name": "b"}))
+                ),
+                (
+                    RowKind::UpdateBefore,
+                    fields(json!({"id": "2", "name": "c"}))
+                ),
+                (
+                    RowKind::UpdateAfter,
+                    fields(json!({"id": "2", "name": "c"}))
+                ),
+            ]
+        );
+        let ddl = json!({"isDdl": true, "type": "ALTER", "sql": "ALTER TABLE t ADD c INT"});
+        assert!(CdcFormat::CanalJson
+            .parse(ddl.to_string().as_bytes())
+            .unwrap()
+            .is_empty());
+
+        let maxwell = json!({"database": "db", "table": "t", "type": "delete", "data": {"id": 1}});
+        let records = CdcFormat::MaxwellJson
+            .parse(maxwell.to_string().as_bytes())
+            .unwrap();
+        assert_eq!(records[0].row_kind, RowKind::Delete);
+        assert_eq!(records[0].table.as_deref(), Some("t"));
+
+        assert!(CdcFormat::DebeziumJson.parse(b"").unwrap().is_empty());
+        assert!(matches!(
+            "avro".parse::<CdcFormat>(),
+            Err(Error::FormatUnsupported { .. })
+        ));
+    }
+}
@@ -0,0 +1,210 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::format::CdcFormat;
+use crate::sink::CdcSink;
+use async_trait::async_trait;
+
+/// A source of the CDC messages, whose offsets are committed once the messages polled are
+/// committed into the table.
+///
+/// The sources are `KafkaSource` with the `kafka` feature and `DatabaseSource` with the
+/// `cdc-sync` feature, other sources are plugged in by implementing this trait, committing
+/// the offsets of the messages polled on [`commit`](Self::commit).
+#[async_trait]
+pub trait MessageSource: Send {
+    /// Poll the next messages, empty if there is no message for now.
+    async fn poll(&mut self) -> Result<Vec<Vec<u8>>>;
+
+    /// Commit the offsets of all the messages polled.
+    async fn commit(&mut self) -> Result<()>;
+}
+
+/// An ingestion of the CDC messages of a [`MessageSource`] into a table by [`CdcSink`].
+///
+/// The messages polled are parsed by the [`CdcFormat`] and written into the table, a
+/// checkpoint is made every `checkpoint_interval` polls, which commits the rows written into
+/// the table and then the offsets of the source. The messages polled after the last
+/// checkpoint are polled again if the ingestion is restarted.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-flink/paimon-flink-cdc/src/main/java/org/apache/paimon/flink/action/cdc/kafka/KafkaSyncTableAction.java>
+#[derive(Debug)]
+pub struct CdcIngestion<S: MessageSource> {
+    source: S,
+    format: CdcFormat,
+    sink: CdcSink,
+    checkpoint_interval: usize,
+    checkpoint_id: i64,
+    /// Number of polls since the last checkpoint.
+    polls: usize,
+}
+
+impl<S: MessageSource> CdcIngestion<S> {
+    pub fn new(source: S, format: CdcFormat, sink: CdcSink) -> Self {
+        Self {
+            source,
+            format,
+            sink,
+            checkpoint_interval: 1,
+            checkpoint_id: 1,
+            polls: 0,
+        }
+    }
+
+    /// Make a checkpoint every number of polls, every poll by default.
+    pub fn with_checkpoint_interval(mut self, polls: usize) -> Self {
+        self.checkpoint_interval = polls.max(1);
+        self
+    }
+
+    /// Get the sink of the table.
+    #[inline]
+    pub fn sink(&self) -> &CdcSink {
+        &self.sink
+    }
+
+    /// Poll the messages of the source once and write them into the table, a checkpoint is
+    /// made if the interval is reached, returns the number of messages polled.
+    pub async fn poll(&mut self) -> Result<usize> {
+        let messages = self.source.poll().await?;
+        let mut records = Vec::new();
+        for message in &messages {
+            records.extend(self.format.parse(message)?);
+        }
+        self.sink.write(&records).await?;
+        self.polls += 1;
+        if self.polls >= self.checkpoint_interval {
+            self.checkpoint().await?;
+        }
+        Ok(messages.len())
+    }
+
+    /// Commit the rows written into the table, then the offsets of the source.
+    pub async fn checkpoint(&mut self) -> Result<()> {
+        self.sink.checkpoint(self.checkpoint_id).await?;
+        self.checkpoint_id += 1;
+        self.polls = 0;
+        self.source.commit().await
+    }
+
+    /// Keep ingesting until the source is drained, which polls no message, and make the last
+    /// checkpoint.
+    pub async fn run_until_drained(&mut self) -> Result<()> {
+        while self.poll().await? > 0 {}
+        self.checkpoint().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_table;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+    use arrow_array::Array;
+    use paimon::table::Table;
+    use std::collections::VecDeque;
+
+    #[derive(Debug, Default)]
+    struct VecSource {
+        polls: VecDeque<Vec<Vec<u8>>>,
+        commits: usize,
+    }
+
+    #[async_trait]
+    impl MessageSource for VecSource {
+        async fn poll(&mut self) -> Result<Vec<Vec<u8>>> {
+            Ok(self.polls.pop_front().unwrap_or_default())
+        }
+
+        async fn commit(&mut self) -> Result<()> {
+            self.commits += 1;
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ingest_canal_messages() {
+        let table = create_table("file:/tmp/test_ingest_canal_messages").await;
+        let message = |kind: &str, data: &str, old: &str| {
+            format!(r#"{{"database":"db","table":"t","isDdl":false,"type":"{kind}","data":{data},"old":{old}}}"#)
+                .into_bytes()
+        };
+        let source = VecSource {
+            polls: VecDeque::from([
+                vec![message(
+                    "INSERT",
+                    r#"[{"id":"1","name":"a"},{"id":"2","name":"b"}]"#,
+                    "null",
+                )],
+                vec![
+                    message("UPDATE", r#"[{"id":"1","name":"c"}]"#, r#"[{"name":"a"}]"#),
+                    message("DELETE", r#"[{"id":"2","name":"b"}]"#, "null"),
+                ],
+                // the new column is added into the table
+                vec![message(
+                    "INSERT",
+                    r#"[{"id":"3","name":"d","age":"30"}]"#,
+                    "null",
+                )],
+            ]),
+            commits: 0,
+        };
+        let sink = CdcSink::new(table, "ingestion");
+        let mut ingestion =
+            CdcIngestion::new(source, CdcFormat::CanalJson, sink).with_checkpoint_interval(2);
+        ingestion.run_until_drained().await.unwrap();
+        assert_eq!(ingestion.source.commits, 3);
+
+        let table = ingestion.sink().table();
+        let names: Vec<_> = table.schema().fields().iter().map(|f| f.name()).collect();
+        assert_eq!(names, vec!["id", "name", "age"]);
+        let snapshot = table
+            .snapshot_manager()
+            .latest_snapshot()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.commit_identifier(), 2);
+        let plan = table.new_scan().plan().await.unwrap();
+        let read = table.new_read();
+        let mut rows = Vec::new();
+        for split in plan.splits() {
+            for batch in read.read(split).await.unwrap() {
+                let batch = batch.unwrap();
+                let ids = batch.column(0).as_primitive::<Int32Type>();
+                let names = batch.column(1).as_string::<i32>();
+                let ages = batch.column(2).as_string::<i32>();
+                rows.extend((0..batch.num_rows()).map(|i| {
+                    (
+                        ids.value(i),
+                        names.value(i).to_string(),
+                        ages.is_valid(i).then(|| ages.value(i).to_string()),
+                    )
+                }));
+            }
+        }
+        assert_eq!(
+            rows,
+            vec![
+                (1, "c".to_string(), None),
+                (3, "d".to_string(), Some("30".to_string()))
+            ]
+        );
+    }
+}
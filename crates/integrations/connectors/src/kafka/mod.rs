@@ -0,0 +1,775 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+mod protocol;
+
+use crate::error::*;
+use crate::ingestion::MessageSource;
+use async_trait::async_trait;
+use protocol::*;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Client id of the requests sent to the brokers.
+const CLIENT_ID: &str = "paimon-connectors";
+
+/// Default max time the brokers wait for the records of a fetch.
+const DEFAULT_MAX_WAIT: Duration = Duration::from_millis(500);
+
+/// Default max bytes fetched from a partition by a poll.
+const DEFAULT_MAX_PARTITION_BYTES: i32 = 1024 * 1024;
+
+/// Error codes of the group coordinator which moved or is starting.
+const ERROR_COORDINATOR_NOT_AVAILABLE: i16 = 15;
+const ERROR_NOT_COORDINATOR: i16 = 16;
+
+/// A connection to a broker, requests are sent one by one and their responses are read
+/// before the next request.
+#[derive(Debug)]
+struct Connection {
+    address: String,
+    stream: TcpStream,
+    correlation_id: i32,
+}
+
+impl Connection {
+    async fn connect(address: &str) -> Result<Self> {
+        let stream = TcpStream::connect(address)
+            .await
+            .map_err(|e| Error::SourceUnexpected {
+                message: format!("Failed to connect to Kafka broker {address}: {e}"),
+            })?;
+        Ok(Self {
+            address: address.to_string(),
+            stream,
+            correlation_id: 0,
+        })
+    }
+
+    /// Send the request of the API and read its response, without the correlation id.
+    async fn call(&mut self, api_key: i16, body: &[u8]) -> Result<Vec<u8>> {
+        self.correlation_id = self.correlation_id.wrapping_add(1);
+        let mut header = Writer::default();
+        header
+            .i16(api_key)
+            .i16(api_version(api_key))
+            .i32(self.correlation_id)
+            .string(CLIENT_ID);
+        let header = header.into_bytes();
+        let mut request = Writer::default();
+        request.i32((header.len() + body.len()) as i32);
+        let mut request = request.into_bytes();
+        request.extend_from_slice(&header);
+        request.extend_from_slice(body);
+
+        let message = |e: std::io::Error| Error::SourceUnexpected {
+            message: format!("Failed to call Kafka broker {}: {e}", self.address),
+        };
+        self.stream.write_all(&request).await.map_err(message)?;
+        let size = self.stream.read_i32().await.map_err(message)?;
+        let mut response = vec![0; size.max(0) as usize];
+        self.stream
+            .read_exact(&mut response)
+            .await
+            .map_err(message)?;
+
+        let correlation_id = Reader::new(&response).i32()?;
+        if correlation_id != self.correlation_id {
+            return SourceUnexpectedSnafu {
+                message: format!(
+                    "Kafka broker {} responded to request {correlation_id} instead of {}",
+                    self.address, self.correlation_id
+                ),
+            }
+            .fail();
+        }
+        response.drain(..4);
+        Ok(response)
+    }
+}
+
+/// Fail with the error code of Kafka if it's not none.
+fn check_error(error_code: i16, action: impl FnOnce() -> String) -> Result<()> {
+    if error_code == ERROR_NONE {
+        return Ok(());
+    }
+    SourceUnexpectedSnafu {
+        message: format!("{} failed with Kafka error code {error_code}", action()),
+    }
+    .fail()
+}
+
+/// A [`MessageSource`] of the messages of a Kafka topic, consumed by a consumer group whose
+/// offsets are committed to the group coordinator of Kafka.
+///
+/// The source is a standalone consumer of all the partitions of the topic, it doesn't join
+/// the group, so there should be only one source of a group. The polls start from the
+/// offsets committed by the group, or the earliest offsets of the partitions without
+/// committed offsets. [`CdcIngestion`](crate::CdcIngestion) commits the offsets of the
+/// messages polled only after they are committed into the table, so the messages after the
+/// last checkpoint are polled again once the ingestion restarts.
+///
+/// Records are read uncommitted, and the topic must be uncompressed since no compression
+/// codec is supported.
+///
+/// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-flink/paimon-flink-cdc/src/main/java/org/apache/paimon/flink/action/cdc/kafka/KafkaActionUtils.java>
+#[derive(Debug)]
+pub struct KafkaSource {
+    bootstrap_servers: Vec<String>,
+    topic: String,
+    group_id: String,
+    max_wait: Duration,
+    max_partition_bytes: i32,
+    /// Addresses of the brokers by their node ids.
+    brokers: HashMap<i32, String>,
+    /// Node ids of the leaders by the partitions.
+    leaders: BTreeMap<i32, i32>,
+    /// Offsets of the next records to poll by the partitions.
+    positions: BTreeMap<i32, i64>,
+    coordinator: Option<String>,
+    connections: HashMap<String, Connection>,
+}
+
+impl KafkaSource {
+    /// Connect to the brokers of the comma separated bootstrap servers, like
+    /// `host1:9092,host2:9092`, and fetch the offsets of the group to poll from.
+    pub async fn connect(
+        bootstrap_servers: &str,
+        topic: impl Into<String>,
+        group_id: impl Into<String>,
+    ) -> Result<Self> {
+        let bootstrap_servers: Vec<_> = bootstrap_servers
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        if bootstrap_servers.is_empty() {
+            return SourceUnexpectedSnafu {
+                message: "No Kafka bootstrap servers".to_string(),
+            }
+            .fail();
+        }
+        let mut source = Self {
+            bootstrap_servers,
+            topic: topic.into(),
+            group_id: group_id.into(),
+            max_wait: DEFAULT_MAX_WAIT,
+            max_partition_bytes: DEFAULT_MAX_PARTITION_BYTES,
+            brokers: HashMap::new(),
+            leaders: BTreeMap::new(),
+            positions: BTreeMap::new(),
+            coordinator: None,
+            connections: HashMap::new(),
+        };
+        source.refresh_metadata().await?;
+        source.fetch_committed_offsets().await?;
+        Ok(source)
+    }
+
+    /// Set the max time the brokers wait for the records of a poll.
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = max_wait;
+        self
+    }
+
+    /// Set the max bytes fetched from a partition by a poll, though a batch of records larger
+    /// than it is still fetched whole.
+    pub fn with_max_partition_bytes(mut self, bytes: i32) -> Self {
+        self.max_partition_bytes = bytes;
+        self
+    }
+
+    /// Call the broker of the address.
+    ///
+    /// The connection is kept only once the call completes, since a call failed or cancelled
+    /// halfway leaves its response unread on the connection.
+    async fn call(&mut self, address: &str, api_key: i16, body: &[u8]) -> Result<Vec<u8>> {
+        let mut connection = match self.connections.remove(address) {
+            Some(connection) => connection,
+            None => Connection::connect(address).await?,
+        };
+        let response = connection.call(api_key, body).await?;
+        self.connections.insert(address.to_string(), connection);
+        Ok(response)
+    }
+
+    /// Fetch the brokers and the leaders of the partitions of the topic from any broker
+    /// known, or the bootstrap servers.
+    async fn refresh_metadata(&mut self) -> Result<()> {
+        let mut body = Writer::default();
+        body.array(&[self.topic.as_str()], |w, topic| {
+            w.string(topic);
+        });
+        let body = body.into_bytes();
+        let servers: Vec<_> = self
+            .brokers
+            .values()
+            .chain(&self.bootstrap_servers)
+            .cloned()
+            .collect();
+        let mut error = None;
+        for server in servers {
+            match self.call(&server, API_METADATA, &body).await {
+                Ok(response) => return self.update_metadata(&response),
+                Err(e) => error = Some(e),
+            }
+        }
+        Err(error.unwrap())
+    }
+
+    fn update_metadata(&mut self, response: &[u8]) -> Result<()> {
+        let mut r = Reader::new(response);
+        let brokers = r.array(|r| {
+            let node_id = r.i32()?;
+            let host = r.string()?;
+            let port = r.i32()?;
+            let _rack = r.nullable_string()?;
+            Ok((node_id, format!("{host}:{port}")))
+        })?;
+        let _controller_id = r.i32()?;
+        let topics = r.array(|r| {
+            let error_code = r.i16()?;
+            let name = r.string()?;
+            let _is_internal = r.i8()?;
+            let partitions = r.array(|r| {
+                let _error_code = r.i16()?;
+                let partition = r.i32()?;
+                let leader = r.i32()?;
+                let _replicas = r.array(|r| r.i32())?;
+                let _isr = r.array(|r| r.i32())?;
+                Ok((partition, leader))
+            })?;
+            Ok((name, error_code, partitions))
+        })?;
+        let Some((_, error_code, partitions)) =
+            topics.into_iter().find(|(name, ..)| *name == self.topic)
+        else {
+            return SourceUnexpectedSnafu {
+                message: format!("Kafka topic {} not found", self.topic),
+            }
+            .fail();
+        };
+        check_error(error_code, || {
+            format!("Metadata of Kafka topic {}", self.topic)
+        })?;
+        self.brokers = brokers.into_iter().collect();
+        self.leaders = partitions.into_iter().collect();
+        Ok(())
+    }
+
+    /// Get the address of the coordinator of the group.
+    async fn coordinator(&mut self) -> Result<String> {
+        if let Some(coordinator) = &self.coordinator {
+            return Ok(coordinator.clone());
+        }
+        let mut body = Writer::default();
+        body.string(&self.group_id);
+        let server = match self.brokers.values().next() {
+            Some(broker) => broker.clone(),
+            None => self.bootstrap_servers[0].clone(),
+        };
+        let response = self
+            .call(&server, API_FIND_COORDINATOR, &body.into_bytes())
+            .await?;
+        let mut r = Reader::new(&response);
+        check_error(r.i16()?, || {
+            format!("Finding the coordinator of Kafka group {}", self.group_id)
+        })?;
+        let _node_id = r.i32()?;
+        let host = r.string()?;
+        let port = r.i32()?;
+        let coordinator = format!("{host}:{port}");
+        self.coordinator = Some(coordinator.clone());
+        Ok(coordinator)
+    }
+
+    /// Start the partitions from the offsets committed by the group, or the earliest offsets.
+    async fn fetch_committed_offsets(&mut self) -> Result<()> {
+        let partitions: Vec<i32> = self.leaders.keys().copied().collect();
+        let mut body = Writer::default();
+        body.string(&self.group_id)
+            .array(&[self.topic.as_str()], |w, topic| {
+                w.string(topic).array(&partitions, |w, p| {
+                    w.i32(*p);
+                });
+            });
+        let coordinator = self.coordinator().await?;
+        let response = self
+            .call(&coordinator, API_OFFSET_FETCH, &body.into_bytes())
+            .await?;
+        let mut r = Reader::new(&response);
+        let topics = r.array(|r| {
+            let _name = r.string()?;
+            r.array(|r| {
+                let partition = r.i32()?;
+                let offset = r.i64()?;
+                let _metadata = r.nullable_string()?;
+                let error_code = r.i16()?;
+                Ok((partition, offset, error_code))
+            })
+        })?;
+        for (partition, offset, error_code) in topics.into_iter().flatten() {
+            check_error(error_code, || {
+                format!("Fetching the offset of Kafka partition {partition}")
+            })?;
+            let offset = match offset {
+                offset if offset >= 0 => offset,
+                _ => self.earliest_offset(partition).await?,
+            };
+            self.positions.insert(partition, offset);
+        }
+        Ok(())
+    }
+
+    /// List the earliest offset of the partition from its leader.
+    async fn earliest_offset(&mut self, partition: i32) -> Result<i64> {
+        let Some(leader) = self
+            .leaders
+            .get(&partition)
+            .and_then(|leader| self.brokers.get(leader))
+            .cloned()
+        else {
+            return SourceUnexpectedSnafu {
+                message: format!("No leader of Kafka partition {partition}"),
+            }
+            .fail();
+        };
+        let mut body = Writer::default();
+        body.i32(-1).array(&[self.topic.as_str()], |w, topic| {
+            w.string(topic).array(&[partition], |w, p| {
+                w.i32(*p).i64(EARLIEST_TIMESTAMP);
+            });
+        });
+        let response = self
+            .call(&leader, API_LIST_OFFSETS, &body.into_bytes())
+            .await?;
+        let mut r = Reader::new(&response);
+        let topics = r.array(|r| {
+            let _name = r.string()?;
+            r.array(|r| {
+                let _partition = r.i32()?;
+                let error_code = r.i16()?;
+                let _timestamp = r.i64()?;
+                let offset = r.i64()?;
+                Ok((error_code, offset))
+            })
+        })?;
+        let Some((error_code, offset)) = topics.into_iter().flatten().next() else {
+            return SourceUnexpectedSnafu {
+                message: format!("No offset of Kafka partition {partition} listed"),
+            }
+            .fail();
+        };
+        check_error(error_code, || {
+            format!("Listing the offset of Kafka partition {partition}")
+        })?;
+        Ok(offset)
+    }
+
+    /// Build the fetch of the records of the partitions from their positions.
+    fn fetch_request(&self, partitions: &[(i32, i64)]) -> Vec<u8> {
+        let mut body = Writer::default();
+        body.i32(-1)
+            .i32(self.max_wait.as_millis() as i32)
+            .i32(1)
+            .i32(i32::MAX)
+            .i8(0)
+            .array(&[self.topic.as_str()], |w, topic| {
+                w.string(topic).array(partitions, |w, (p, offset)| {
+                    w.i32(*p).i64(*offset).i32(self.max_partition_bytes);
+                });
+            });
+        body.into_bytes()
+    }
+
+    /// Move the positions of the partitions by the records fetched, returns the values of the
+    /// records and whether the leaders are stale.
+    async fn handle_fetch_response(&mut self, response: &[u8]) -> Result<(Vec<Vec<u8>>, bool)> {
+        let mut r = Reader::new(response);
+        let _throttle_time = r.i32()?;
+        let topics = r.array(|r| {
+            let _topic = r.string()?;
+            r.array(|r| {
+                let partition = r.i32()?;
+                let error_code = r.i16()?;
+                let _high_watermark = r.i64()?;
+                let _last_stable_offset = r.i64()?;
+                let _aborted = r.array(|r| Ok((r.i64()?, r.i64()?)))?;
+                let records = r.bytes()?.unwrap_or_default();
+                Ok((partition, error_code, records))
+            })
+        })?;
+
+        let mut values = Vec::new();
+        let mut stale = false;
+        for (partition, error_code, records) in topics.into_iter().flatten() {
+            match error_code {
+                ERROR_NONE => {
+                    let (records, next_offset) = decode_records(records)?;
+                    let position = self.positions.entry(partition).or_default();
+                    // a batch fetched may start before the position
+                    values.extend(
+                        records
+                            .into_iter()
+                            .filter(|record| record.offset >= *position)
+                            .filter_map(|record| record.value),
+                    );
+                    *position = next_offset.map_or(*position, |next| next.max(*position));
+                }
+                ERROR_OFFSET_OUT_OF_RANGE => {
+                    let offset = self.earliest_offset(partition).await?;
+                    self.positions.insert(partition, offset);
+                }
+                ERROR_NOT_LEADER_OR_FOLLOWER
+                | ERROR_LEADER_NOT_AVAILABLE
+                | ERROR_UNKNOWN_TOPIC_OR_PARTITION => stale = true,
+                _ => check_error(error_code, || {
+                    format!("Fetching Kafka partition {partition}")
+                })?,
+            }
+        }
+        Ok((values, stale))
+    }
+}
+
+#[async_trait]
+impl MessageSource for KafkaSource {
+    /// Fetch the records of all the partitions from their leaders, the metadata is refreshed
+    /// if the leaders moved or are unreachable.
+    async fn poll(&mut self) -> Result<Vec<Vec<u8>>> {
+        let mut fetches: BTreeMap<i32, Vec<(i32, i64)>> = BTreeMap::new();
+        let mut stale = false;
+        for (partition, position) in &self.positions {
+            match self.leaders.get(partition) {
+                Some(leader) if self.brokers.contains_key(leader) => fetches
+                    .entry(*leader)
+                    .or_default()
+                    .push((*partition, *position)),
+                _ => stale = true,
+            }
+        }
+
+        let mut messages = Vec::new();
+        for (leader, partitions) in fetches {
+            let address = self.brokers[&leader].clone();
+            let request = self.fetch_request(&partitions);
+            // the leader may be unreachable after it moved
+            let Ok(response) = self.call(&address, API_FETCH, &request).await else {
+                stale = true;
+                continue;
+            };
+            let (values, leaders_stale) = self.handle_fetch_response(&response).await?;
+            messages.extend(values);
+            stale |= leaders_stale;
+        }
+        if stale {
+            self.refresh_metadata().await?;
+        }
+        Ok(messages)
+    }
+
+    /// Commit the positions of the partitions as the offsets of the group.
+    async fn commit(&mut self) -> Result<()> {
+        let positions: Vec<(i32, i64)> = self.positions.iter().map(|(p, o)| (*p, *o)).collect();
+        let mut body = Writer::default();
+        body.string(&self.group_id)
+            .i32(-1)
+            .string("")
+            .i64(-1)
+            .array(&[self.topic.as_str()], |w, topic| {
+                w.string(topic).array(&positions, |w, (p, offset)| {
+                    w.i32(*p).i64(*offset).nullable_string(None);
+                });
+            });
+        let coordinator = self.coordinator().await?;
+        let response = match self
+            .call(&coordinator, API_OFFSET_COMMIT, &body.into_bytes())
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                self.coordinator = None;
+                return Err(e);
+            }
+        };
+        let mut r = Reader::new(&response);
+        let topics = r.array(|r| {
+            let _name = r.string()?;
+            r.array(|r| Ok((r.i32()?, r.i16()?)))
+        })?;
+        for (partition, error_code) in topics.into_iter().flatten() {
+            if matches!(
+                error_code,
+                ERROR_COORDINATOR_NOT_AVAILABLE | ERROR_NOT_COORDINATOR
+            ) {
+                self.coordinator = None;
+            }
+            check_error(error_code, || {
+                format!("Committing the offset of Kafka partition {partition}")
+            })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_table, read_rows};
+    use crate::{CdcFormat, CdcIngestion, CdcSink};
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use tokio::sync::Mutex;
+
+    const TOPIC: &str = "cdc";
+
+    /// A fake Kafka broker leading all the partitions of the topic and coordinating all the
+    /// groups, the whole log of a partition is fetched as a batch from offset 0.
+    #[derive(Debug, Default)]
+    struct Broker {
+        partitions: Vec<Vec<Vec<u8>>>,
+        committed: BTreeMap<i32, i64>,
+    }
+
+    async fn serve_broker(broker: Arc<Mutex<Broker>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let broker = broker.clone();
+                tokio::spawn(async move {
+                    while let Ok(size) = stream.read_i32().await {
+                        let mut request = vec![0; size as usize];
+                        stream.read_exact(&mut request).await.unwrap();
+                        let mut r = Reader::new(&request);
+                        let api_key = r.i16().unwrap();
+                        assert_eq!(r.i16().unwrap(), api_version(api_key));
+                        let correlation_id = r.i32().unwrap();
+                        let _client_id = r.string().unwrap();
+
+                        let mut broker = broker.lock().await;
+                        let mut w = Writer::default();
+                        w.i32(correlation_id);
+                        respond(&mut broker, api_key, &mut r, &mut w, address);
+                        let response = w.into_bytes();
+                        let mut framed = Writer::default();
+                        framed.bytes(Some(&response));
+                        stream.write_all(&framed.into_bytes()).await.unwrap();
+                    }
+                });
+            }
+        });
+        address.to_string()
+    }
+
+    fn respond(
+        broker: &mut Broker,
+        api_key: i16,
+        r: &mut Reader,
+        w: &mut Writer,
+        address: std::net::SocketAddr,
+    ) {
+        let host = address.ip().to_string();
+        let port = address.port() as i32;
+        let partitions: Vec<i32> = (0..broker.partitions.len() as i32).collect();
+        match api_key {
+            API_METADATA => {
+                let topics = r.array(|r| r.string()).unwrap();
+                w.array(&[0], |w, node| {
+                    w.i32(*node).string(&host).i32(port).nullable_string(None);
+                })
+                .i32(0)
+                .array(&topics, |w, topic| {
+                    let (error_code, partitions) = match topic == TOPIC {
+                        true => (ERROR_NONE, partitions.as_slice()),
+                        false => (ERROR_UNKNOWN_TOPIC_OR_PARTITION, [].as_slice()),
+                    };
+                    w.i16(error_code)
+                        .string(topic)
+                        .i8(0)
+                        .array(partitions, |w, p| {
+                            w.i16(0).i32(*p).i32(0);
+                            w.array(&[0], |w, n| {
+                                w.i32(*n);
+                            });
+                            w.array(&[0], |w, n| {
+                                w.i32(*n);
+                            });
+                        });
+                });
+            }
+            API_FIND_COORDINATOR => {
+                w.i16(0).i32(0).string(&host).i32(port);
+            }
+            API_OFFSET_FETCH => {
+                let _group = r.string().unwrap();
+                let requested = r
+                    .array(|r| {
+                        let _topic = r.string()?;
+                        r.array(|r| r.i32())
+                    })
+                    .unwrap();
+                w.array(&[TOPIC], |w, topic| {
+                    w.string(topic).array(&requested[0], |w, p| {
+                        let offset = broker.committed.get(p).copied().unwrap_or(-1);
+                        w.i32(*p).i64(offset).nullable_string(None).i16(0);
+                    });
+                });
+            }
+            API_LIST_OFFSETS => {
+                let _replica_id = r.i32().unwrap();
+                let requested = r
+                    .array(|r| {
+                        let _topic = r.string()?;
+                        r.array(|r| Ok((r.i32()?, r.i64()?)))
+                    })
+                    .unwrap();
+                w.array(&[TOPIC], |w, topic| {
+                    w.string(topic).array(&requested[0], |w, (p, _)| {
+                        w.i32(*p).i16(0).i64(-1).i64(0);
+                    });
+                });
+            }
+            API_FETCH => {
+                r.read_bytes(4 + 4 + 4 + 4 + 1).unwrap();
+                let requested = r
+                    .array(|r| {
+                        let _topic = r.string()?;
+                        r.array(|r| Ok((r.i32()?, r.i64()?, r.i32()?)))
+                    })
+                    .unwrap();
+                w.i32(0).array(&[TOPIC], |w, topic| {
+                    w.string(topic).array(&requested[0], |w, (p, offset, _)| {
+                        let log = &broker.partitions[*p as usize];
+                        if *offset > log.len() as i64 {
+                            w.i32(*p)
+                                .i16(ERROR_OFFSET_OUT_OF_RANGE)
+                                .i64(-1)
+                                .i64(-1)
+                                .i32(-1);
+                            w.bytes(None);
+                            return;
+                        }
+                        let values: Vec<_> = log.iter().map(|v| Some(v.as_slice())).collect();
+                        let records = match values.is_empty() {
+                            true => vec![],
+                            false => encode_records(0, &values),
+                        };
+                        w.i32(*p).i16(0).i64(log.len() as i64).i64(log.len() as i64);
+                        w.i32(-1).bytes(Some(&records));
+                    });
+                });
+            }
+            API_OFFSET_COMMIT => {
+                let _group = r.string().unwrap();
+                let _generation_id = r.i32().unwrap();
+                let _member_id = r.string().unwrap();
+                let _retention_time = r.i64().unwrap();
+                let committed = r
+                    .array(|r| {
+                        let _topic = r.string()?;
+                        r.array(|r| {
+                            let partition = r.i32()?;
+                            let offset = r.i64()?;
+                            let _metadata = r.nullable_string()?;
+                            Ok((partition, offset))
+                        })
+                    })
+                    .unwrap();
+                broker.committed.extend(committed[0].iter().copied());
+                w.array(&[TOPIC], |w, topic| {
+                    w.string(topic).array(&committed[0], |w, (p, _)| {
+                        w.i32(*p).i16(0);
+                    });
+                });
+            }
+            _ => panic!("Unexpected api {api_key}"),
+        }
+    }
+
+    fn message(kind: &str, id: i32, name: &str) -> Vec<u8> {
+        format!(
+            r#"{{"database":"db","table":"t","isDdl":false,"type":"{kind}","data":[{{"id":"{id}","name":"{name}"}}],"old":null}}"#
+        )
+        .into_bytes()
+    }
+
+    #[tokio::test]
+    async fn test_ingest_kafka_messages() {
+        let broker = Arc::new(Mutex::new(Broker {
+            partitions: vec![
+                vec![message("INSERT", 1, "a"), message("INSERT", 2, "b")],
+                vec![message("INSERT", 3, "c")],
+                vec![],
+            ],
+            committed: BTreeMap::new(),
+        }));
+        let address = serve_broker(broker.clone()).await;
+        let table = create_table("file:/tmp/test_ingest_kafka_messages").await;
+
+        let source = KafkaSource::connect(&address, TOPIC, "group")
+            .await
+            .unwrap()
+            .with_max_wait(Duration::from_millis(10));
+        let sink = CdcSink::new(table, "kafka");
+        let mut ingestion =
+            CdcIngestion::new(source, CdcFormat::CanalJson, sink).with_checkpoint_interval(10);
+        assert_eq!(ingestion.poll().await.unwrap(), 3);
+        // the offsets are committed only after the rows are committed into the table
+        assert!(broker.lock().await.committed.is_empty());
+        ingestion.checkpoint().await.unwrap();
+        assert_eq!(
+            broker.lock().await.committed,
+            BTreeMap::from([(0, 2), (1, 1), (2, 0)])
+        );
+        assert_eq!(ingestion.poll().await.unwrap(), 0);
+
+        // the group is resumed from its committed offsets by a new source
+        broker.lock().await.partitions[1].push(message("UPDATE", 1, "d"));
+        broker.lock().await.partitions[2].push(message("DELETE", 2, "b"));
+        let table = ingestion.sink().table().clone();
+        let source = KafkaSource::connect(&address, TOPIC, "group")
+            .await
+            .unwrap()
+            .with_max_wait(Duration::from_millis(10));
+        let mut ingestion =
+            CdcIngestion::new(source, CdcFormat::CanalJson, CdcSink::new(table, "kafka"));
+        assert_eq!(ingestion.poll().await.unwrap(), 2);
+        ingestion.run_until_drained().await.unwrap();
+        assert_eq!(
+            broker.lock().await.committed,
+            BTreeMap::from([(0, 2), (1, 2), (2, 1)])
+        );
+
+        assert_eq!(
+            read_rows(ingestion.sink().table()).await,
+            vec![(1, "d".to_string()), (3, "c".to_string())]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_kafka_source_without_topic() {
+        let broker = Arc::new(Mutex::new(Broker::default()));
+        let address = serve_broker(broker).await;
+        let result = KafkaSource::connect(&address, "other", "group").await;
+        assert!(result.is_err());
+    }
+}
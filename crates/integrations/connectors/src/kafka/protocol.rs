@@ -0,0 +1,389 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A minimal implementation of the Kafka protocol, enough for a consumer fetching the records
+//! of a topic and committing the offsets of its group.
+//!
+//! Only the versions of the APIs without tagged fields are spoken, which are supported by the
+//! brokers since Kafka 1.0, including Kafka 4.
+//!
+//! Reference: <https://kafka.apache.org/protocol>
+
+use crate::error::*;
+
+pub(crate) const API_FETCH: i16 = 1;
+pub(crate) const API_LIST_OFFSETS: i16 = 2;
+pub(crate) const API_METADATA: i16 = 3;
+pub(crate) const API_OFFSET_COMMIT: i16 = 8;
+pub(crate) const API_OFFSET_FETCH: i16 = 9;
+pub(crate) const API_FIND_COORDINATOR: i16 = 10;
+
+/// Version of the API requested by its key.
+pub(crate) fn api_version(api_key: i16) -> i16 {
+    match api_key {
+        API_FETCH => 4,
+        API_LIST_OFFSETS => 1,
+        API_METADATA => 1,
+        API_OFFSET_COMMIT => 2,
+        API_OFFSET_FETCH => 1,
+        _ => 0,
+    }
+}
+
+pub(crate) const ERROR_NONE: i16 = 0;
+pub(crate) const ERROR_OFFSET_OUT_OF_RANGE: i16 = 1;
+pub(crate) const ERROR_UNKNOWN_TOPIC_OR_PARTITION: i16 = 3;
+pub(crate) const ERROR_LEADER_NOT_AVAILABLE: i16 = 5;
+pub(crate) const ERROR_NOT_LEADER_OR_FOLLOWER: i16 = 6;
+
+/// Timestamp of ListOffsets asking for the earliest offset.
+pub(crate) const EARLIEST_TIMESTAMP: i64 = -2;
+
+/// Compression codec of the attributes of record batches.
+const COMPRESSION_MASK: i16 = 0x07;
+/// Flag of the attributes of the batches of transaction markers.
+const CONTROL_FLAG: i16 = 0x20;
+/// Length of the fields of a record batch before its records.
+const BATCH_HEADER_LENGTH: usize = 61;
+
+/// Encoder of the fields of requests and responses.
+#[derive(Debug, Default)]
+pub(crate) struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    pub(crate) fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+
+    pub(crate) fn i8(&mut self, v: i8) -> &mut Self {
+        self.buf.push(v as u8);
+        self
+    }
+
+    pub(crate) fn i16(&mut self, v: i16) -> &mut Self {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+
+    pub(crate) fn i32(&mut self, v: i32) -> &mut Self {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+
+    pub(crate) fn i64(&mut self, v: i64) -> &mut Self {
+        self.buf.extend_from_slice(&v.to_be_bytes());
+        self
+    }
+
+    pub(crate) fn string(&mut self, v: &str) -> &mut Self {
+        self.i16(v.len() as i16);
+        self.buf.extend_from_slice(v.as_bytes());
+        self
+    }
+
+    pub(crate) fn nullable_string(&mut self, v: Option<&str>) -> &mut Self {
+        match v {
+            Some(v) => self.string(v),
+            None => self.i16(-1),
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn bytes(&mut self, v: Option<&[u8]>) -> &mut Self {
+        match v {
+            Some(v) => {
+                self.i32(v.len() as i32);
+                self.buf.extend_from_slice(v);
+                self
+            }
+            None => self.i32(-1),
+        }
+    }
+
+    pub(crate) fn array<T>(&mut self, items: &[T], mut f: impl FnMut(&mut Self, &T)) -> &mut Self {
+        self.i32(items.len() as i32);
+        for item in items {
+            f(self, item);
+        }
+        self
+    }
+
+    #[cfg(test)]
+    pub(crate) fn varint(&mut self, v: i64) -> &mut Self {
+        let mut v = ((v << 1) ^ (v >> 63)) as u64;
+        while v >= 0x80 {
+            self.buf.push((v as u8) | 0x80);
+            v >>= 7;
+        }
+        self.buf.push(v as u8);
+        self
+    }
+}
+
+/// Decoder of the fields of requests and responses.
+#[derive(Debug)]
+pub(crate) struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    pub(crate) fn remaining(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub(crate) fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.buf.len() < len {
+            return SourceUnexpectedSnafu {
+                message: "Truncated Kafka message".to_string(),
+            }
+            .fail();
+        }
+        let (bytes, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    pub(crate) fn i8(&mut self) -> Result<i8> {
+        Ok(i8::from_be_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn i16(&mut self) -> Result<i16> {
+        Ok(i16::from_be_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn i32(&mut self) -> Result<i32> {
+        Ok(i32::from_be_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn i64(&mut self) -> Result<i64> {
+        Ok(i64::from_be_bytes(self.read_array()?))
+    }
+
+    pub(crate) fn nullable_string(&mut self) -> Result<Option<String>> {
+        let len = self.i16()?;
+        if len < 0 {
+            return Ok(None);
+        }
+        let bytes = self.read_bytes(len as usize)?;
+        Ok(Some(String::from_utf8_lossy(bytes).into_owned()))
+    }
+
+    pub(crate) fn string(&mut self) -> Result<String> {
+        Ok(self.nullable_string()?.unwrap_or_default())
+    }
+
+    pub(crate) fn bytes(&mut self) -> Result<Option<&'a [u8]>> {
+        let len = self.i32()?;
+        if len < 0 {
+            return Ok(None);
+        }
+        Ok(Some(self.read_bytes(len as usize)?))
+    }
+
+    pub(crate) fn array<T>(&mut self, mut f: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        let len = self.i32()?;
+        (0..len.max(0)).map(|_| f(self)).collect()
+    }
+
+    /// Read a zigzag encoded variable length integer of the records.
+    fn varint(&mut self) -> Result<i64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_array::<1>()?[0];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+            }
+        }
+        SourceUnexpectedSnafu {
+            message: "Invalid varint of Kafka record".to_string(),
+        }
+        .fail()
+    }
+
+    /// Read the bytes of a record field prefixed by a varint length, `None` if it's null.
+    fn varint_bytes(&mut self) -> Result<Option<&'a [u8]>> {
+        let len = self.varint()?;
+        if len < 0 {
+            return Ok(None);
+        }
+        Ok(Some(self.read_bytes(len as usize)?))
+    }
+}
+
+/// A record of a partition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Record {
+    pub(crate) offset: i64,
+    pub(crate) value: Option<Vec<u8>>,
+}
+
+/// Decode the record batches of v2 fetched from a partition, returns the records and the
+/// offset next to the last batch.
+///
+/// The last batch may be truncated by the max bytes of the fetch, which is fetched again by
+/// the next poll. The batches of transaction markers hold no records of the topic, but their
+/// offsets are skipped too.
+///
+/// Reference: <https://kafka.apache.org/documentation/#recordbatch>
+pub(crate) fn decode_records(buf: &[u8]) -> Result<(Vec<Record>, Option<i64>)> {
+    let mut reader = Reader::new(buf);
+    let mut records = Vec::new();
+    let mut next_offset = None;
+    while reader.remaining() >= 12 {
+        let base_offset = reader.i64()?;
+        let length = reader.i32()? as usize;
+        if reader.remaining() < length || length < BATCH_HEADER_LENGTH - 12 {
+            break;
+        }
+        let mut batch = Reader::new(reader.read_bytes(length)?);
+        let _partition_leader_epoch = batch.i32()?;
+        let magic = batch.i8()?;
+        if magic != 2 {
+            return SourceUnexpectedSnafu {
+                message: format!("Unsupported Kafka message format v{magic}"),
+            }
+            .fail();
+        }
+        let _crc = batch.i32()?;
+        let attributes = batch.i16()?;
+        let last_offset_delta = batch.i32()?;
+        // base timestamp, max timestamp, producer id, producer epoch and base sequence
+        batch.read_bytes(8 + 8 + 8 + 2 + 4)?;
+        let count = batch.i32()?;
+        next_offset = Some(base_offset + last_offset_delta as i64 + 1);
+        if attributes & CONTROL_FLAG != 0 {
+            continue;
+        }
+        if attributes & COMPRESSION_MASK != 0 {
+            return SourceUnexpectedSnafu {
+                message: format!(
+                    "Unsupported compression codec {} of Kafka records, the topic must be uncompressed",
+                    attributes & COMPRESSION_MASK
+                ),
+            }
+            .fail();
+        }
+        for _ in 0..count {
+            let length = batch.varint()?;
+            let mut record = Reader::new(batch.read_bytes(length.max(0) as usize)?);
+            let _attributes = record.i8()?;
+            let _timestamp_delta = record.varint()?;
+            let offset_delta = record.varint()?;
+            let _key = record.varint_bytes()?;
+            let value = record.varint_bytes()?;
+            records.push(Record {
+                offset: base_offset + offset_delta,
+                value: value.map(<[u8]>::to_vec),
+            });
+        }
+    }
+    Ok((records, next_offset))
+}
+
+/// Encode the values into an uncompressed record batch, the offsets start from the base offset.
+#[cfg(test)]
+pub(crate) fn encode_records(base_offset: i64, values: &[Option<&[u8]>]) -> Vec<u8> {
+    let mut records = Writer::default();
+    for (delta, value) in values.iter().enumerate() {
+        let mut record = Writer::default();
+        record.i8(0).varint(0).varint(delta as i64).varint(-1);
+        match value {
+            Some(value) => {
+                record.varint(value.len() as i64);
+                record.buf.extend_from_slice(value);
+            }
+            None => {
+                record.varint(-1);
+            }
+        }
+        record.varint(0);
+        let record = record.into_bytes();
+        records.varint(record.len() as i64);
+        records.buf.extend_from_slice(&record);
+    }
+    let records = records.into_bytes();
+
+    let mut batch = Writer::default();
+    batch
+        .i32(0)
+        .i8(2)
+        .i32(0)
+        .i16(0)
+        .i32(values.len() as i32 - 1)
+        .i64(0)
+        .i64(0)
+        .i64(-1)
+        .i16(-1)
+        .i32(-1)
+        .i32(values.len() as i32);
+    batch.buf.extend_from_slice(&records);
+    let batch = batch.into_bytes();
+
+    let mut buf = Writer::default();
+    buf.i64(base_offset).bytes(Some(&batch));
+    buf.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_round_trip() {
+        let mut buf = encode_records(3, &[Some(b"a"), None, Some(&[b'b'; 300])]);
+        buf.extend(encode_records(6, &[Some(b"c")]));
+        let (records, next_offset) = decode_records(&buf).unwrap();
+        assert_eq!(
+            records,
+            vec![
+                Record {
+                    offset: 3,
+                    value: Some(b"a".to_vec())
+                },
+                Record {
+                    offset: 4,
+                    value: None
+                },
+                Record {
+                    offset: 5,
+                    value: Some(vec![b'b'; 300])
+                },
+                Record {
+                    offset: 6,
+                    value: Some(b"c".to_vec())
+                },
+            ]
+        );
+        assert_eq!(next_offset, Some(7));
+
+        // the truncated batch is fetched again
+        let (records, next_offset) = decode_records(&buf[..buf.len() - 1]).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(next_offset, Some(6));
+    }
+}
@@ -0,0 +1,59 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Connectors ingesting CDC data into Apache Paimon tables.
+//!
+//! Messages of the change logs of databases in the JSON formats of [`CdcFormat`] are parsed
+//! into [`CdcRecord`]s of their row kinds, and written into a primary key table by
+//! [`CdcSink`], which evolves the schema of the table for the new columns. [`CdcIngestion`]
+//! polls the messages from a [`MessageSource`] and commits the offsets of the source only
+//! after the rows are committed into the table.
+//!
+//! With the `kafka` feature, `KafkaSource` consumes the messages of a Kafka topic, committing
+//! the offsets of its consumer group.
+//!
+//! With the `cdc-sync` feature, `DatabaseSource` snapshots and tails a table of MySQL by its
+//! binlog or of Postgres by a replication slot, which is synchronized into a table by the
+//! `paimon-cdc-sync` binary.
+
+#[cfg(feature = "cdc-sync")]
+mod binlog;
+
+#[cfg(feature = "cdc-sync")]
+mod database;
+#[cfg(feature = "cdc-sync")]
+pub use database::*;
+
+mod error;
+pub use error::*;
+
+mod format;
+pub use format::*;
+
+mod ingestion;
+pub use ingestion::*;
+
+#[cfg(feature = "kafka")]
+mod kafka;
+#[cfg(feature = "kafka")]
+pub use kafka::*;
+
+mod sink;
+pub use sink::*;
+
+#[cfg(test)]
+mod test_utils;
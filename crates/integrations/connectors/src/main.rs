@@ -0,0 +1,102 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Synchronize a table of MySQL or Postgres into a primary key table of a filesystem catalog.
+//!
+//! Usage: `paimon-cdc-sync <warehouse> <database.table> <source-url> <source-table>
+//! [--server-id <id>] [--incremental-column <column>] [--replication-slot <slot>]
+//! [--interval-secs <secs>]`.
+//!
+//! The table is created with the columns and primary keys of the source table if it doesn't
+//! exist, then the source table is snapshotted and tailed until the process is killed. MySQL
+//! is tailed by its binlog as the replica of the server id, unless the incremental column to
+//! poll is given as a fallback.
+
+use paimon::catalog::{Catalog, FileSystemCatalog, Identifier};
+use paimon::io::FileIOBuilder;
+use paimon_connectors::{CdcFormat, CdcIngestion, CdcSink, DatabaseSource};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const USAGE: &str = "Usage: paimon-cdc-sync <warehouse> <database.table> <source-url> <source-table> [--server-id <id>] [--incremental-column <column>] [--replication-slot <slot>] [--interval-secs <secs>]";
+
+fn usage() -> ! {
+    eprintln!("{USAGE}");
+    std::process::exit(2);
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut positional = Vec::new();
+    let mut flags = HashMap::new();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.strip_prefix("--") {
+            Some(flag) => {
+                let Some(value) = args.next() else { usage() };
+                flags.insert(flag.to_string(), value);
+            }
+            None => positional.push(arg),
+        }
+    }
+    let [warehouse, table, url, source_table] =
+        <[String; 4]>::try_from(positional).unwrap_or_else(|_| usage());
+    let Some((database, object)) = table.split_once('.') else {
+        usage()
+    };
+    let interval = match flags.remove("interval-secs") {
+        Some(secs) => Duration::from_secs(secs.parse()?),
+        None => Duration::from_secs(1),
+    };
+
+    let mut source = DatabaseSource::connect(&url, source_table).await?;
+    if let Some(server_id) = flags.remove("server-id") {
+        source = source.with_server_id(server_id.parse()?)?;
+    }
+    if let Some(column) = flags.remove("incremental-column") {
+        source = source.with_incremental_column(column);
+    }
+    if let Some(slot) = flags.remove("replication-slot") {
+        source = source.with_replication_slot(slot)?;
+    }
+    if let Some(flag) = flags.keys().next() {
+        eprintln!("Unknown option --{flag}");
+        usage();
+    }
+
+    let file_io = FileIOBuilder::from_url(&warehouse)?.build()?;
+    let catalog = FileSystemCatalog::new(file_io, warehouse);
+    let identifier = Identifier::new(database, object);
+    if !catalog.table_exists(&identifier).await? {
+        catalog
+            .create_database(database, true, HashMap::new())
+            .await?;
+        let options = HashMap::from([("file.format".to_string(), "parquet".to_string())]);
+        catalog
+            .create_table(&identifier, source.schema(options)?, true)
+            .await?;
+    }
+    let table = catalog.get_table(&identifier).await?;
+
+    let sink = CdcSink::new(table, "cdc-sync");
+    let mut ingestion = CdcIngestion::new(source, CdcFormat::MaxwellJson, sink);
+    loop {
+        if ingestion.poll().await? == 0 {
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
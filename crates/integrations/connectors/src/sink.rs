@@ -0,0 +1,152 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::format::CdcRecord;
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use arrow_cast::cast;
+use paimon::format::{to_arrow_schema, to_arrow_type};
+use paimon::spec::{DataType, SchemaChange};
+use paimon::table::{CommitMessage, FileStoreTable, Table, TableCommit, TableWrite};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A sink writing [`CdcRecord`]s into a primary key table by a commit user, and committing
+/// the rows written at each checkpoint with the checkpoint id as the commit identifier.
+///
+/// Columns of the records not in the table are added into the table as nullable strings
+/// before the records are written, while the columns of the table not in the records are
+/// null. The values of the columns are casted from their JSON texts to the types of the
+/// columns, values that can't be casted are null.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-flink/paimon-flink-cdc/src/main/java/org/apache/paimon/flink/sink/cdc/CdcRecordStoreWriteOperator.java>
+#[derive(Debug)]
+pub struct CdcSink {
+    table: FileStoreTable,
+    commit_user: String,
+    write: TableWrite,
+    /// Files written before the schema evolved, committed by the next checkpoint.
+    pending: Vec<CommitMessage>,
+}
+
+impl CdcSink {
+    pub fn new(table: FileStoreTable, commit_user: impl Into<String>) -> Self {
+        let commit_user = commit_user.into();
+        Self {
+            write: TableWrite::new(table.clone(), commit_user.clone()),
+            table,
+            commit_user,
+            pending: vec![],
+        }
+    }
+
+    /// Get the table written, of the latest schema evolved.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the user committing the rows written.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Write the records by their row kinds, the new columns of the records are added into
+    /// the table first.
+    pub async fn write(&mut self, records: &[CdcRecord]) -> Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        self.evolve_schema(records).await?;
+        let fields = self.table.schema().fields();
+        let columns = fields
+            .iter()
+            .map(|field| {
+                let texts: StringArray = records
+                    .iter()
+                    .map(|record| match record.fields.get(field.name()) {
+                        None | Some(Value::Null) => None,
+                        Some(Value::String(text)) => Some(text.clone()),
+                        Some(value) => Some(value.to_string()),
+                    })
+                    .collect();
+                cast(&texts, &to_arrow_type(field.data_type()))
+            })
+            .collect::<std::result::Result<Vec<ArrayRef>, _>>()?;
+        let batch = RecordBatch::try_new(Arc::new(to_arrow_schema(fields)), columns)?;
+        let row_kinds: Vec<_> = records.iter().map(|record| record.row_kind).collect();
+        self.write.write_with_row_kinds(&batch, &row_kinds).await?;
+        Ok(())
+    }
+
+    /// Commit the rows written since the last checkpoint into a new snapshot, with the
+    /// checkpoint id as the commit identifier.
+    pub async fn checkpoint(&mut self, checkpoint_id: i64) -> Result<()> {
+        let write = std::mem::replace(
+            &mut self.write,
+            TableWrite::new(self.table.clone(), self.commit_user.clone()),
+        );
+        let mut messages = std::mem::take(&mut self.pending);
+        messages.extend(Self::prepare_commit(write).await?);
+        TableCommit::new(self.table.clone(), self.commit_user.clone(), checkpoint_id)
+            .commit(messages)
+            .await?;
+        Ok(())
+    }
+
+    /// Add the columns of the records not in the table into the table, the files written of
+    /// the previous schema are kept to commit.
+    async fn evolve_schema(&mut self, records: &[CdcRecord]) -> Result<()> {
+        let mut changes: Vec<SchemaChange> = Vec::new();
+        for name in records.iter().flat_map(|record| record.fields.keys()) {
+            let exists = self
+                .table
+                .schema()
+                .fields()
+                .iter()
+                .any(|f| f.name() == name);
+            let added = changes.iter().any(|change| {
+                matches!(change, SchemaChange::AddColumn { field_name, .. } if field_name == name)
+            });
+            if !exists && !added {
+                let data_type: DataType = "STRING".parse()?;
+                changes.push(SchemaChange::add_column(name.clone(), data_type));
+            }
+        }
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let schema = self.table.schema_manager().commit_changes(&changes).await?;
+        self.table = FileStoreTable::new(
+            self.table.file_io().clone(),
+            self.table.identifier().clone(),
+            self.table.location(),
+            schema,
+        );
+        let write = std::mem::replace(
+            &mut self.write,
+            TableWrite::new(self.table.clone(), self.commit_user.clone()),
+        );
+        self.pending.extend(Self::prepare_commit(write).await?);
+        Ok(())
+    }
+
+    async fn prepare_commit(mut write: TableWrite) -> Result<Vec<CommitMessage>> {
+        Ok(write.prepare_commit().await?)
+    }
+}
@@ -0,0 +1,70 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Helpers shared by the tests of the connectors.
+
+use arrow_array::cast::AsArray;
+use arrow_array::types::Int32Type;
+use paimon::catalog::Identifier;
+use paimon::io::FileIOBuilder;
+use paimon::spec::{DataField, Schema};
+use paimon::table::{FileStoreTable, Table};
+use paimon::utils::SchemaManager;
+
+/// Create a primary key table `(id INT NOT NULL, name STRING)` of a bucket at the location,
+/// dropping the table there before.
+pub(crate) async fn create_table(location: &str) -> FileStoreTable {
+    let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+    let _ = file_io.delete_dir(&format!("{location}/")).await;
+    let schema = SchemaManager::new(file_io.clone(), location)
+        .create_table(
+            &Schema::builder()
+                .fields(vec![
+                    DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                    DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                ])
+                .primary_keys(vec!["id".to_string()])
+                .options(
+                    [
+                        ("bucket".to_string(), "1".to_string()),
+                        ("file.format".to_string(), "parquet".to_string()),
+                    ]
+                    .into(),
+                )
+                .build(),
+        )
+        .await
+        .unwrap();
+    FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema)
+}
+
+/// Read the `(id, name)` rows of the latest snapshot of the table.
+#[allow(dead_code)]
+pub(crate) async fn read_rows(table: &FileStoreTable) -> Vec<(i32, String)> {
+    let plan = table.new_scan().plan().await.unwrap();
+    let read = table.new_read();
+    let mut rows = Vec::new();
+    for split in plan.splits() {
+        for batch in read.read(split).await.unwrap() {
+            let batch = batch.unwrap();
+            let ids = batch.column(0).as_primitive::<Int32Type>();
+            let names = batch.column(1).as_string::<i32>();
+            rows.extend((0..batch.num_rows()).map(|i| (ids.value(i), names.value(i).to_string())));
+        }
+    }
+    rows
+}
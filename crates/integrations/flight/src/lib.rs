@@ -0,0 +1,28 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Serving the scans of Apache Paimon tables over Arrow Flight.
+//!
+//! A flight is a table of a catalog, described by the path `[database, table]` or the command
+//! `database.table`. The endpoints of a flight are the splits of the latest snapshot of the
+//! table, each fetched by `DoGet` with its ticket.
+
+// the errors of the flight service are tonic statuses
+#![allow(clippy::result_large_err)]
+
+mod service;
+pub use service::*;
@@ -0,0 +1,48 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Serve the tables of a filesystem catalog over Arrow Flight.
+//!
+//! Usage: `paimon-flight-server <warehouse> [address]`, the address is `0.0.0.0:50051` by
+//! default.
+
+use paimon::catalog::FileSystemCatalog;
+use paimon::io::FileIOBuilder;
+use paimon_flight::PaimonFlightService;
+use std::sync::Arc;
+use tonic::transport::Server;
+
+const DEFAULT_ADDRESS: &str = "0.0.0.0:50051";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let Some(warehouse) = args.next() else {
+        eprintln!("Usage: paimon-flight-server <warehouse> [address]");
+        std::process::exit(2);
+    };
+    let address = args.next().unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+
+    let file_io = FileIOBuilder::from_url(&warehouse)?.build()?;
+    let catalog = FileSystemCatalog::new(file_io, warehouse);
+    let service = PaimonFlightService::new(Arc::new(catalog));
+    Server::builder()
+        .add_service(service.into_server())
+        .serve(address.parse()?)
+        .await?;
+    Ok(())
+}
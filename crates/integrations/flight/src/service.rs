@@ -0,0 +1,334 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow_flight::encode::FlightDataEncoderBuilder;
+use arrow_flight::error::FlightError;
+use arrow_flight::flight_descriptor::DescriptorType;
+use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+use arrow_flight::{
+    Action, ActionType, Criteria, Empty, FlightData, FlightDescriptor, FlightEndpoint, FlightInfo,
+    HandshakeRequest, HandshakeResponse, PollInfo, PutResult, SchemaAsIpc, SchemaResult, Ticket,
+};
+use arrow_schema::Schema;
+use futures::stream::{self, BoxStream};
+use futures::{StreamExt, TryStreamExt};
+use paimon::catalog::{Catalog, Identifier};
+use paimon::format::to_arrow_schema;
+use paimon::table::{FileStoreTable, Table};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tonic::{Request, Response, Status, Streaming};
+
+/// Ticket of a split of a snapshot of a table, encoded as json.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct SplitTicket {
+    database: String,
+    table: String,
+    snapshot_id: i64,
+    split: usize,
+}
+
+/// A [`FlightService`] serving the scans of the tables of a catalog.
+///
+/// Only the flights of the tables are listed, described and fetched, other requests are
+/// unimplemented.
+#[derive(Clone)]
+pub struct PaimonFlightService {
+    catalog: Arc<dyn Catalog>,
+}
+
+impl PaimonFlightService {
+    pub fn new(catalog: Arc<dyn Catalog>) -> Self {
+        Self { catalog }
+    }
+
+    /// Create the server of this service for the tonic transport.
+    pub fn into_server(self) -> FlightServiceServer<Self> {
+        FlightServiceServer::new(self)
+    }
+
+    async fn table(&self, identifier: &Identifier) -> Result<FileStoreTable, Status> {
+        self.catalog.get_table(identifier).await.map_err(to_status)
+    }
+}
+
+/// Get the arrow schema of the rows read from the table.
+fn read_schema(table: &FileStoreTable) -> Result<Schema, Status> {
+    let read_type = table.new_read().read_type().map_err(to_status)?;
+    Ok(to_arrow_schema(read_type.fields()))
+}
+
+/// Get the table described by the path `[database, table]` or the command `database.table`.
+fn identifier(descriptor: &FlightDescriptor) -> Result<Identifier, Status> {
+    match descriptor.r#type() {
+        DescriptorType::Path => match descriptor.path.as_slice() {
+            [database, table] => Ok(Identifier::new(database, table)),
+            path => Err(Status::invalid_argument(format!(
+                "the path of a table should be [database, table], but got {path:?}"
+            ))),
+        },
+        DescriptorType::Cmd => std::str::from_utf8(&descriptor.cmd)
+            .map_err(|e| Status::invalid_argument(e.to_string()))?
+            .parse()
+            .map_err(to_status),
+        DescriptorType::Unknown => Err(Status::invalid_argument("unknown descriptor type")),
+    }
+}
+
+fn flight_info(table: &FileStoreTable, descriptor: FlightDescriptor) -> Result<FlightInfo, Status> {
+    FlightInfo::new()
+        .try_with_schema(&read_schema(table)?)
+        .map_err(|e| Status::internal(e.to_string()))
+        .map(|info| info.with_descriptor(descriptor))
+}
+
+fn to_status(e: paimon::Error) -> Status {
+    match e {
+        paimon::Error::DatabaseNotExist { .. } | paimon::Error::TableNotExist { .. } => {
+            Status::not_found(e.to_string())
+        }
+        paimon::Error::ConfigInvalid { .. } => Status::invalid_argument(e.to_string()),
+        e => Status::internal(e.to_string()),
+    }
+}
+
+#[tonic::async_trait]
+impl FlightService for PaimonFlightService {
+    type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+    type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+    type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+    type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+    type DoActionStream = BoxStream<'static, Result<arrow_flight::Result, Status>>;
+    type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+    type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+    async fn handshake(
+        &self,
+        _request: Request<Streaming<HandshakeRequest>>,
+    ) -> Result<Response<Self::HandshakeStream>, Status> {
+        Err(Status::unimplemented("handshake is not supported"))
+    }
+
+    /// List the flights of all the tables, without endpoints.
+    async fn list_flights(
+        &self,
+        _request: Request<Criteria>,
+    ) -> Result<Response<Self::ListFlightsStream>, Status> {
+        let mut infos = Vec::new();
+        for database in self.catalog.list_databases().await.map_err(to_status)? {
+            let tables = self
+                .catalog
+                .list_tables(&database)
+                .await
+                .map_err(to_status)?;
+            for name in tables {
+                let table = self.table(&Identifier::new(&database, &name)).await?;
+                let descriptor = FlightDescriptor::new_path(vec![database.clone(), name]);
+                infos.push(flight_info(&table, descriptor));
+            }
+        }
+        Ok(Response::new(stream::iter(infos).boxed()))
+    }
+
+    /// Get the flight of the table, with an endpoint for each split of the latest snapshot.
+    async fn get_flight_info(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<FlightInfo>, Status> {
+        let descriptor = request.into_inner();
+        let identifier = identifier(&descriptor)?;
+        let table = self.table(&identifier).await?;
+        let plan = table.new_scan().plan().await.map_err(to_status)?;
+        let mut info = flight_info(&table, descriptor)?;
+        let Some(snapshot_id) = plan.snapshot_id() else {
+            return Ok(Response::new(info.with_total_records(0)));
+        };
+        let mut total_records = 0;
+        for (split, data_split) in plan.splits().iter().enumerate() {
+            total_records += data_split.row_count();
+            let ticket = SplitTicket {
+                database: identifier.database().to_string(),
+                table: identifier.object().to_string(),
+                snapshot_id,
+                split,
+            };
+            let ticket =
+                serde_json::to_vec(&ticket).map_err(|e| Status::internal(e.to_string()))?;
+            info = info.with_endpoint(FlightEndpoint::new().with_ticket(Ticket::new(ticket)));
+        }
+        Ok(Response::new(info.with_total_records(total_records)))
+    }
+
+    async fn poll_flight_info(
+        &self,
+        _request: Request<FlightDescriptor>,
+    ) -> Result<Response<PollInfo>, Status> {
+        Err(Status::unimplemented("poll flight info is not supported"))
+    }
+
+    async fn get_schema(
+        &self,
+        request: Request<FlightDescriptor>,
+    ) -> Result<Response<SchemaResult>, Status> {
+        let table = self.table(&identifier(request.get_ref())?).await?;
+        let schema = read_schema(&table)?;
+        let options = Default::default();
+        let result = SchemaAsIpc::new(&schema, &options)
+            .try_into()
+            .map_err(|e: arrow_schema::ArrowError| Status::internal(e.to_string()))?;
+        Ok(Response::new(result))
+    }
+
+    /// Read the split of the ticket, the splits are planned again from the snapshot.
+    async fn do_get(
+        &self,
+        request: Request<Ticket>,
+    ) -> Result<Response<Self::DoGetStream>, Status> {
+        let ticket: SplitTicket = serde_json::from_slice(&request.get_ref().ticket)
+            .map_err(|e| Status::invalid_argument(format!("invalid ticket: {e}")))?;
+        let table = self
+            .table(&Identifier::new(&ticket.database, &ticket.table))
+            .await?;
+        let plan = table
+            .new_scan()
+            .as_of_snapshot(ticket.snapshot_id)
+            .plan()
+            .await
+            .map_err(to_status)?;
+        let split = plan.splits().get(ticket.split).ok_or_else(|| {
+            Status::not_found(format!(
+                "split {} of snapshot {} does not exist",
+                ticket.split, ticket.snapshot_id
+            ))
+        })?;
+        let read = table.new_read();
+        let batches = read.read(split).await.map_err(to_status)?;
+        let batches = stream::iter(batches).map_err(|e| FlightError::ExternalError(Box::new(e)));
+        let stream = FlightDataEncoderBuilder::new()
+            .with_schema(Arc::new(read_schema(&table)?))
+            .build(batches)
+            .map_err(Status::from);
+        Ok(Response::new(stream.boxed()))
+    }
+
+    async fn do_put(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoPutStream>, Status> {
+        Err(Status::unimplemented("do put is not supported"))
+    }
+
+    async fn do_action(
+        &self,
+        _request: Request<Action>,
+    ) -> Result<Response<Self::DoActionStream>, Status> {
+        Err(Status::unimplemented("no action is supported"))
+    }
+
+    async fn list_actions(
+        &self,
+        _request: Request<Empty>,
+    ) -> Result<Response<Self::ListActionsStream>, Status> {
+        Ok(Response::new(stream::empty().boxed()))
+    }
+
+    async fn do_exchange(
+        &self,
+        _request: Request<Streaming<FlightData>>,
+    ) -> Result<Response<Self::DoExchangeStream>, Status> {
+        Err(Status::unimplemented("do exchange is not supported"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, RecordBatch};
+    use arrow_flight::decode::FlightRecordBatchStream;
+    use paimon::catalog::FileSystemCatalog;
+    use paimon::io::FileIOBuilder;
+    use paimon::spec::{DataField, Schema as TableSchema};
+
+    #[tokio::test]
+    async fn test_do_get() {
+        let warehouse = "file:/tmp/test_paimon_flight_do_get";
+        let file_io = FileIOBuilder::from_url(warehouse).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{warehouse}/")).await;
+        let catalog = FileSystemCatalog::new(file_io, warehouse);
+        let identifier = Identifier::new("db", "t");
+        catalog
+            .create_database("db", false, Default::default())
+            .await
+            .unwrap();
+        catalog
+            .create_table(
+                &identifier,
+                TableSchema::builder()
+                    .fields(vec![DataField::new(
+                        0,
+                        "id".to_string(),
+                        "INT".parse().unwrap(),
+                    )])
+                    .options([("file.format".to_string(), "parquet".to_string())].into())
+                    .build(),
+                false,
+            )
+            .await
+            .unwrap();
+        let table = catalog.get_table(&identifier).await.unwrap();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        let batch = RecordBatch::try_new(
+            Arc::new(read_schema(&table).unwrap()),
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+        )
+        .unwrap();
+        write.write(&batch).await.unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+
+        let service = PaimonFlightService::new(Arc::new(catalog));
+        let info = service
+            .get_flight_info(Request::new(FlightDescriptor::new_cmd("db.t")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(info.total_records, 3);
+        assert_eq!(info.endpoint.len(), 1);
+
+        let ticket = info.endpoint[0].ticket.clone().unwrap();
+        let stream = service
+            .do_get(Request::new(ticket))
+            .await
+            .unwrap()
+            .into_inner();
+        let batches: Vec<RecordBatch> =
+            FlightRecordBatchStream::new_from_flight_data(stream.map_err(FlightError::from))
+                .try_collect()
+                .await
+                .unwrap();
+        assert_eq!(batches, vec![batch]);
+
+        let missing = service
+            .get_flight_info(Request::new(FlightDescriptor::new_path(vec![
+                "db".to_string(),
+                "missing".to_string(),
+            ])))
+            .await;
+        assert_eq!(missing.unwrap_err().code(), tonic::Code::NotFound);
+    }
+}
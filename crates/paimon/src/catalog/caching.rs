@@ -0,0 +1,330 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::catalog::{Catalog, Identifier};
+use crate::manifest::ManifestCache;
+use crate::spec::{Schema, SchemaChange, Snapshot};
+use crate::table::FileStoreTable;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A catalog caching the tables with their schemas and the latest snapshots of the tables of
+/// the wrapped catalog, the cached values expire after the expiration interval since loaded.
+///
+/// Tables altered or dropped through this catalog are invalidated at once, changes by others
+/// are seen after the cached values expire.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/catalog/CachingCatalog.java>
+pub struct CachingCatalog {
+    catalog: Arc<dyn Catalog>,
+    expiration_interval: Duration,
+    manifest_cache: Option<Arc<ManifestCache>>,
+    tables: Mutex<HashMap<Identifier, Cached<FileStoreTable>>>,
+    snapshots: Mutex<HashMap<Identifier, Cached<Option<Snapshot>>>>,
+}
+
+struct Cached<T> {
+    value: T,
+    loaded: Instant,
+}
+
+impl CachingCatalog {
+    /// Default interval after which the cached values expire, the same as paimon-java.
+    pub const DEFAULT_EXPIRATION_INTERVAL: Duration = Duration::from_secs(60);
+
+    pub fn new(catalog: Arc<dyn Catalog>, expiration_interval: Duration) -> Self {
+        Self {
+            catalog,
+            expiration_interval,
+            manifest_cache: None,
+            tables: Default::default(),
+            snapshots: Default::default(),
+        }
+    }
+
+    /// Read the manifest files of the tables through the cache.
+    pub fn with_manifest_cache(mut self, cache: Arc<ManifestCache>) -> Self {
+        self.manifest_cache = Some(cache);
+        self
+    }
+
+    /// Get the wrapped catalog.
+    pub fn wrapped(&self) -> &Arc<dyn Catalog> {
+        &self.catalog
+    }
+
+    /// Get the latest snapshot of the table, none if the table has no snapshot.
+    pub async fn latest_snapshot(
+        &self,
+        identifier: &Identifier,
+    ) -> crate::Result<Option<Snapshot>> {
+        if let Some(snapshot) = self.cached(&self.snapshots, identifier) {
+            return Ok(snapshot);
+        }
+        let snapshot = self
+            .get_table(identifier)
+            .await?
+            .snapshot_manager()
+            .latest_snapshot()
+            .await?;
+        self.cache(&self.snapshots, identifier, snapshot.clone());
+        Ok(snapshot)
+    }
+
+    /// Invalidate the cached table and latest snapshot of the table.
+    pub fn invalidate_table(&self, identifier: &Identifier) {
+        self.tables.lock().unwrap().remove(identifier);
+        self.snapshots.lock().unwrap().remove(identifier);
+    }
+
+    /// Invalidate the cached tables and latest snapshots of the tables of the database.
+    fn invalidate_database(&self, database: &str) {
+        self.tables
+            .lock()
+            .unwrap()
+            .retain(|identifier, _| identifier.database() != database);
+        self.snapshots
+            .lock()
+            .unwrap()
+            .retain(|identifier, _| identifier.database() != database);
+    }
+
+    fn cached<T: Clone>(
+        &self,
+        cache: &Mutex<HashMap<Identifier, Cached<T>>>,
+        identifier: &Identifier,
+    ) -> Option<T> {
+        let mut cache = cache.lock().unwrap();
+        match cache.get(identifier) {
+            Some(cached) if cached.loaded.elapsed() < self.expiration_interval => {
+                Some(cached.value.clone())
+            }
+            Some(_) => {
+                cache.remove(identifier);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn cache<T>(
+        &self,
+        cache: &Mutex<HashMap<Identifier, Cached<T>>>,
+        identifier: &Identifier,
+        value: T,
+    ) {
+        cache.lock().unwrap().insert(
+            identifier.clone(),
+            Cached {
+                value,
+                loaded: Instant::now(),
+            },
+        );
+    }
+}
+
+#[async_trait]
+impl Catalog for CachingCatalog {
+    async fn list_databases(&self) -> crate::Result<Vec<String>> {
+        self.catalog.list_databases().await
+    }
+
+    async fn database_exists(&self, name: &str) -> crate::Result<bool> {
+        self.catalog.database_exists(name).await
+    }
+
+    async fn create_database(
+        &self,
+        name: &str,
+        ignore_if_exists: bool,
+        properties: HashMap<String, String>,
+    ) -> crate::Result<()> {
+        self.catalog
+            .create_database(name, ignore_if_exists, properties)
+            .await
+    }
+
+    async fn drop_database(
+        &self,
+        name: &str,
+        ignore_if_not_exists: bool,
+        cascade: bool,
+    ) -> crate::Result<()> {
+        self.catalog
+            .drop_database(name, ignore_if_not_exists, cascade)
+            .await?;
+        self.invalidate_database(name);
+        Ok(())
+    }
+
+    async fn list_tables(&self, database: &str) -> crate::Result<Vec<String>> {
+        self.catalog.list_tables(database).await
+    }
+
+    async fn table_exists(&self, identifier: &Identifier) -> crate::Result<bool> {
+        if self.cached(&self.tables, identifier).is_some() {
+            return Ok(true);
+        }
+        self.catalog.table_exists(identifier).await
+    }
+
+    async fn get_table(&self, identifier: &Identifier) -> crate::Result<FileStoreTable> {
+        if let Some(table) = self.cached(&self.tables, identifier) {
+            return Ok(table);
+        }
+        let mut table = self.catalog.get_table(identifier).await?;
+        if let Some(cache) = &self.manifest_cache {
+            table = table.with_manifest_cache(cache.clone());
+        }
+        self.cache(&self.tables, identifier, table.clone());
+        Ok(table)
+    }
+
+    async fn create_table(
+        &self,
+        identifier: &Identifier,
+        schema: Schema,
+        ignore_if_exists: bool,
+    ) -> crate::Result<()> {
+        self.catalog
+            .create_table(identifier, schema, ignore_if_exists)
+            .await
+    }
+
+    async fn drop_table(
+        &self,
+        identifier: &Identifier,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()> {
+        self.catalog
+            .drop_table(identifier, ignore_if_not_exists)
+            .await?;
+        self.invalidate_table(identifier);
+        Ok(())
+    }
+
+    async fn alter_table(
+        &self,
+        identifier: &Identifier,
+        changes: Vec<SchemaChange>,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()> {
+        self.catalog
+            .alter_table(identifier, changes, ignore_if_not_exists)
+            .await?;
+        self.invalidate_table(identifier);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::FileSystemCatalog;
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIOBuilder;
+    use crate::spec::DataField;
+    use crate::table::Table;
+    use arrow_array::{Int32Array, RecordBatch};
+
+    #[tokio::test]
+    async fn test_caching_catalog() {
+        let warehouse = "file:/tmp/test_caching_catalog";
+        let file_io = FileIOBuilder::from_url(warehouse).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{warehouse}/")).await;
+        let wrapped: Arc<dyn Catalog> = Arc::new(FileSystemCatalog::new(file_io, warehouse));
+        wrapped
+            .create_database("db", false, HashMap::new())
+            .await
+            .unwrap();
+        let identifier = Identifier::new("db", "t");
+        let schema = Schema::builder()
+            .fields(vec![DataField::new(
+                0,
+                "id".to_string(),
+                "INT".parse().unwrap(),
+            )])
+            .options(HashMap::from([
+                ("bucket".to_string(), "-1".to_string()),
+                ("file.format".to_string(), "parquet".to_string()),
+            ]))
+            .build();
+        wrapped
+            .create_table(&identifier, schema, false)
+            .await
+            .unwrap();
+        let set_option = |value: &str| {
+            vec![SchemaChange::set_option(
+                "snapshot.num-retained.min".to_string(),
+                value.to_string(),
+            )]
+        };
+
+        let catalog = CachingCatalog::new(wrapped.clone(), Duration::from_secs(3600));
+        assert_eq!(
+            catalog.get_table(&identifier).await.unwrap().schema().id(),
+            0
+        );
+        assert_eq!(catalog.latest_snapshot(&identifier).await.unwrap(), None);
+        // changes not through the caching catalog are not seen until the cache expires
+        wrapped
+            .alter_table(&identifier, set_option("1"), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            catalog.get_table(&identifier).await.unwrap().schema().id(),
+            0
+        );
+        catalog
+            .alter_table(&identifier, set_option("2"), false)
+            .await
+            .unwrap();
+        let table = catalog.get_table(&identifier).await.unwrap();
+        assert_eq!(table.schema().id(), 2);
+        assert_eq!(catalog.latest_snapshot(&identifier).await.unwrap(), None);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![Arc::new(Int32Array::from(vec![1]))],
+        )
+        .unwrap();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write.write(&batch).await.unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+        let committed = table.snapshot_manager().latest_snapshot().await.unwrap();
+        assert!(committed.is_some());
+        assert_eq!(catalog.latest_snapshot(&identifier).await.unwrap(), None);
+
+        let expired = CachingCatalog::new(wrapped.clone(), Duration::ZERO);
+        assert_eq!(
+            expired.latest_snapshot(&identifier).await.unwrap(),
+            committed
+        );
+        catalog.invalidate_table(&identifier);
+        assert_eq!(
+            catalog.latest_snapshot(&identifier).await.unwrap(),
+            committed
+        );
+
+        catalog.drop_table(&identifier, false).await.unwrap();
+        assert!(!catalog.table_exists(&identifier).await.unwrap());
+    }
+}
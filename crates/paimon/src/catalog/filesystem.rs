@@ -0,0 +1,419 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::catalog::{Catalog, CatalogLock, Identifier, DB_SUFFIX};
+use crate::error::*;
+use crate::io::FileIO;
+use crate::metrics::MetricsReporter;
+use crate::spec::{Schema, SchemaChange};
+use crate::table::FileStoreTable;
+use crate::utils::SchemaManager;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A catalog discovering databases and tables by the directory layout of the warehouse.
+///
+/// Databases are directories named `{database}.db` under the warehouse, and tables are
+/// directories with schema files under the database.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/catalog/FileSystemCatalog.java>
+#[derive(Debug, Clone)]
+pub struct FileSystemCatalog {
+    file_io: Arc<dyn FileIO>,
+    warehouse: String,
+    lock: Option<Arc<dyn CatalogLock>>,
+    metrics_reporter: Option<Arc<dyn MetricsReporter>>,
+}
+
+impl FileSystemCatalog {
+    pub fn new(file_io: Arc<dyn FileIO>, warehouse: impl Into<String>) -> Self {
+        Self {
+            file_io,
+            warehouse: warehouse.into().trim_end_matches('/').to_string(),
+            lock: None,
+            metrics_reporter: None,
+        }
+    }
+
+    /// Hold the lock of the tables when committing them or altering their schemas, for
+    /// warehouses on stores without atomic renames.
+    pub fn with_lock(mut self, lock: Arc<dyn CatalogLock>) -> Self {
+        self.lock = Some(lock);
+        self
+    }
+
+    /// Report the metrics of the tables got from this catalog to the reporter.
+    pub fn with_metrics_reporter(mut self, reporter: Arc<dyn MetricsReporter>) -> Self {
+        self.metrics_reporter = Some(reporter);
+        self
+    }
+
+    /// Get the warehouse path.
+    #[inline]
+    pub fn warehouse(&self) -> &str {
+        &self.warehouse
+    }
+
+    /// Get the path of the database directory.
+    pub fn database_path(&self, database: &str) -> String {
+        format!("{}/{}{}", self.warehouse, database, DB_SUFFIX)
+    }
+
+    /// Get the path of the table directory.
+    pub fn table_path(&self, identifier: &Identifier) -> String {
+        format!(
+            "{}/{}",
+            self.database_path(identifier.database()),
+            identifier.object()
+        )
+    }
+
+    /// Get the file io of the warehouse.
+    #[inline]
+    pub fn file_io(&self) -> &Arc<dyn FileIO> {
+        &self.file_io
+    }
+
+    pub(crate) fn schema_manager(&self, identifier: &Identifier) -> SchemaManager {
+        SchemaManager::new(self.file_io.clone(), self.table_path(identifier))
+    }
+
+    async fn assert_database_exists(&self, database: &str) -> crate::Result<()> {
+        if !self.database_exists(database).await? {
+            return DatabaseNotExistSnafu { database }.fail();
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Catalog for FileSystemCatalog {
+    async fn list_databases(&self) -> crate::Result<Vec<String>> {
+        let mut databases: Vec<String> = self
+            .file_io
+            .list_status(&self.warehouse)
+            .await?
+            .into_iter()
+            .filter(|status| status.is_dir)
+            .filter_map(|status| {
+                let name = status.path.rsplit('/').next()?;
+                name.strip_suffix(DB_SUFFIX).map(|db| db.to_string())
+            })
+            .collect();
+        databases.sort();
+        Ok(databases)
+    }
+
+    async fn database_exists(&self, name: &str) -> crate::Result<bool> {
+        self.file_io
+            .exists(&format!("{}/", self.database_path(name)))
+            .await
+    }
+
+    async fn create_database(
+        &self,
+        name: &str,
+        ignore_if_exists: bool,
+        properties: HashMap<String, String>,
+    ) -> crate::Result<()> {
+        if self.database_exists(name).await? {
+            if ignore_if_exists {
+                return Ok(());
+            }
+            return DatabaseAlreadyExistSnafu { database: name }.fail();
+        }
+        if !properties.is_empty() {
+            return ConfigInvalidSnafu {
+                message: "Create database with properties is unsupported in filesystem catalog",
+            }
+            .fail();
+        }
+        self.file_io
+            .mkdirs(&format!("{}/", self.database_path(name)))
+            .await
+    }
+
+    async fn drop_database(
+        &self,
+        name: &str,
+        ignore_if_not_exists: bool,
+        cascade: bool,
+    ) -> crate::Result<()> {
+        if !self.database_exists(name).await? {
+            if ignore_if_not_exists {
+                return Ok(());
+            }
+            return DatabaseNotExistSnafu { database: name }.fail();
+        }
+        if !cascade && !self.list_tables(name).await?.is_empty() {
+            return DatabaseNotEmptySnafu { database: name }.fail();
+        }
+        self.file_io
+            .delete_dir(&format!("{}/", self.database_path(name)))
+            .await
+    }
+
+    async fn list_tables(&self, database: &str) -> crate::Result<Vec<String>> {
+        self.assert_database_exists(database).await?;
+
+        let mut tables = Vec::new();
+        for status in self
+            .file_io
+            .list_status(&self.database_path(database))
+            .await?
+        {
+            if !status.is_dir {
+                continue;
+            }
+            let Some(name) = status.path.rsplit('/').next() else {
+                continue;
+            };
+            let identifier = Identifier::new(database, name);
+            if self.table_exists(&identifier).await? {
+                tables.push(name.to_string());
+            }
+        }
+        tables.sort();
+        Ok(tables)
+    }
+
+    async fn table_exists(&self, identifier: &Identifier) -> crate::Result<bool> {
+        Ok(!self
+            .schema_manager(identifier)
+            .list_all_ids()
+            .await?
+            .is_empty())
+    }
+
+    async fn get_table(&self, identifier: &Identifier) -> crate::Result<FileStoreTable> {
+        let Some(schema) = self.schema_manager(identifier).latest().await? else {
+            return TableNotExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail();
+        };
+        let table = FileStoreTable::new(
+            self.file_io.clone(),
+            identifier.clone(),
+            self.table_path(identifier),
+            schema,
+        );
+        let table = match &self.lock {
+            Some(lock) => table.with_catalog_lock(lock.clone()),
+            None => table,
+        };
+        Ok(match &self.metrics_reporter {
+            Some(reporter) => table.with_metrics_reporter(reporter.clone()),
+            None => table,
+        })
+    }
+
+    async fn create_table(
+        &self,
+        identifier: &Identifier,
+        schema: Schema,
+        ignore_if_exists: bool,
+    ) -> crate::Result<()> {
+        self.assert_database_exists(identifier.database()).await?;
+        if self.table_exists(identifier).await? {
+            if ignore_if_exists {
+                return Ok(());
+            }
+            return TableAlreadyExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail();
+        }
+        self.schema_manager(identifier)
+            .create_table(&schema)
+            .await
+            .map(|_| ())
+    }
+
+    async fn drop_table(
+        &self,
+        identifier: &Identifier,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()> {
+        if !self.table_exists(identifier).await? {
+            if ignore_if_not_exists {
+                return Ok(());
+            }
+            return TableNotExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail();
+        }
+        self.file_io
+            .delete_dir(&format!("{}/", self.table_path(identifier)))
+            .await
+    }
+
+    async fn alter_table(
+        &self,
+        identifier: &Identifier,
+        changes: Vec<SchemaChange>,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()> {
+        if !self.table_exists(identifier).await? {
+            if ignore_if_not_exists {
+                return Ok(());
+            }
+            return TableNotExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail();
+        }
+        let schema_manager = self.schema_manager(identifier);
+        let commit = || async { schema_manager.commit_changes(&changes).await.map(|_| ()) };
+        match &self.lock {
+            Some(lock) => {
+                lock.run_with_lock(identifier.database(), identifier.object(), commit)
+                    .await
+            }
+            None => commit().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataField, DataType, IntType};
+    use crate::Error;
+
+    async fn test_catalog(name: &str) -> FileSystemCatalog {
+        let warehouse = format!("file:/tmp/{name}");
+        let file_io = FileIOBuilder::from_url(&warehouse)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{warehouse}/")).await;
+        FileSystemCatalog::new(file_io, warehouse)
+    }
+
+    fn test_schema() -> Schema {
+        Schema::builder()
+            .fields(vec![DataField::new(
+                0,
+                "id".to_string(),
+                DataType::Int(IntType::new()),
+            )])
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_databases() {
+        let catalog = test_catalog("test_fs_catalog_databases").await;
+        assert!(catalog.list_databases().await.unwrap().is_empty());
+
+        catalog
+            .create_database("db2", false, HashMap::new())
+            .await
+            .unwrap();
+        catalog
+            .create_database("db1", false, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(catalog.list_databases().await.unwrap(), vec!["db1", "db2"]);
+        assert!(matches!(
+            catalog.create_database("db1", false, HashMap::new()).await,
+            Err(Error::DatabaseAlreadyExist { .. })
+        ));
+        catalog
+            .create_database("db1", true, HashMap::new())
+            .await
+            .unwrap();
+
+        catalog
+            .create_table(&Identifier::new("db1", "t"), test_schema(), false)
+            .await
+            .unwrap();
+        assert!(matches!(
+            catalog.drop_database("db1", false, false).await,
+            Err(Error::DatabaseNotEmpty { .. })
+        ));
+        catalog.drop_database("db1", false, true).await.unwrap();
+        assert_eq!(catalog.list_databases().await.unwrap(), vec!["db2"]);
+        assert!(matches!(
+            catalog.drop_database("db1", false, true).await,
+            Err(Error::DatabaseNotExist { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tables() {
+        let catalog = test_catalog("test_fs_catalog_tables").await;
+        let identifier = Identifier::new("db", "t1");
+        assert!(matches!(
+            catalog
+                .create_table(&identifier, test_schema(), false)
+                .await,
+            Err(Error::DatabaseNotExist { .. })
+        ));
+        catalog
+            .create_database("db", false, HashMap::new())
+            .await
+            .unwrap();
+        catalog
+            .create_table(&identifier, test_schema(), false)
+            .await
+            .unwrap();
+        catalog
+            .create_table(&Identifier::new("db", "t0"), test_schema(), false)
+            .await
+            .unwrap();
+        assert!(matches!(
+            catalog
+                .create_table(&identifier, test_schema(), false)
+                .await,
+            Err(Error::TableAlreadyExist { .. })
+        ));
+        assert_eq!(catalog.list_tables("db").await.unwrap(), vec!["t0", "t1"]);
+
+        catalog
+            .alter_table(
+                &identifier,
+                vec![SchemaChange::set_option(
+                    "bucket".to_string(),
+                    "2".to_string(),
+                )],
+                false,
+            )
+            .await
+            .unwrap();
+        let table = catalog.get_table(&identifier).await.unwrap();
+        assert_eq!(table.schema().id(), 1);
+        assert_eq!(
+            table.location(),
+            "file:/tmp/test_fs_catalog_tables/db.db/t1"
+        );
+
+        catalog.drop_table(&identifier, false).await.unwrap();
+        assert!(matches!(
+            catalog.get_table(&identifier).await,
+            Err(Error::TableNotExist { .. })
+        ));
+        assert!(matches!(
+            catalog.drop_table(&identifier, false).await,
+            Err(Error::TableNotExist { .. })
+        ));
+        assert_eq!(catalog.list_tables("db").await.unwrap(), vec!["t0"]);
+    }
+}
@@ -0,0 +1,848 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::catalog::thrift::{
+    TMessage, TStruct, TValue, MESSAGE_CALL, MESSAGE_EXCEPTION, MESSAGE_REPLY,
+};
+use crate::catalog::{Catalog, Identifier, DB_SUFFIX};
+use crate::error::*;
+use crate::io::FileIO;
+use crate::spec::{DataType, Schema, SchemaChange};
+use crate::table::FileStoreTable;
+use crate::utils::SchemaManager;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+/// Key of the table parameter marking a paimon table in hive metastore.
+const TABLE_TYPE_PROP: &str = "table_type";
+const PAIMON_TABLE_TYPE_VALUE: &str = "paimon";
+const STORAGE_HANDLER_PROP: &str = "storage_handler";
+const STORAGE_HANDLER_CLASS: &str = "org.apache.paimon.hive.PaimonStorageHandler";
+const SERDE_CLASS: &str = "org.apache.paimon.hive.PaimonSerDe";
+const INPUT_FORMAT_CLASS: &str = "org.apache.paimon.hive.mapred.PaimonInputFormat";
+const OUTPUT_FORMAT_CLASS: &str = "org.apache.paimon.hive.mapred.PaimonOutputFormat";
+
+/// Table parameters maintained by hive itself, which are not translated into paimon options.
+const HIVE_RESERVED_PROPS: &[&str] = &[
+    TABLE_TYPE_PROP,
+    STORAGE_HANDLER_PROP,
+    "EXTERNAL",
+    "comment",
+    "transient_lastDdlTime",
+    "last_modified_by",
+    "last_modified_time",
+    "numFiles",
+    "numRows",
+    "rawDataSize",
+    "totalSize",
+    "COLUMN_STATS_ACCURATE",
+    "bucketing_version",
+];
+
+/// An exception declared by a hive metastore method, `id` is the field id of it in the result.
+#[derive(Debug)]
+struct HmsException {
+    id: i16,
+    message: String,
+}
+
+/// A client of hive metastore speaking thrift binary protocol over an unframed socket.
+#[derive(Debug)]
+struct HmsClient {
+    address: String,
+    conn: Mutex<Option<TcpStream>>,
+    seq_id: AtomicI32,
+}
+
+impl HmsClient {
+    fn new(address: String) -> Self {
+        Self {
+            address,
+            conn: Mutex::new(None),
+            seq_id: AtomicI32::new(0),
+        }
+    }
+
+    /// Call the method, returns the success value, none for void methods.
+    async fn call(
+        &self,
+        method: &str,
+        args: TStruct,
+    ) -> crate::Result<Result<Option<TValue>, HmsException>> {
+        let request = TMessage {
+            name: method.to_string(),
+            message_type: MESSAGE_CALL,
+            seq_id: self.seq_id.fetch_add(1, Ordering::Relaxed),
+            body: args,
+        };
+
+        let mut conn = self.conn.lock().await;
+        // the connection is taken out during the roundtrip and only put back after a whole reply
+        // of the call, so calls failed or cancelled halfway drop the connection in unknown state
+        let mut stream = conn.take();
+        let reply = self.roundtrip(&mut stream, &request).await?;
+
+        if reply.message_type == MESSAGE_EXCEPTION {
+            *conn = stream;
+            return CatalogUnexpectedSnafu {
+                message: format!(
+                    "Hive metastore failed to call {}: {}",
+                    method,
+                    reply.body.get_str(1).unwrap_or_default()
+                ),
+            }
+            .fail();
+        }
+        if reply.message_type != MESSAGE_REPLY
+            || reply.name != method
+            || reply.seq_id != request.seq_id
+        {
+            return CatalogUnexpectedSnafu {
+                message: format!(
+                    "Hive metastore replied {} with seq id {} for call {} with seq id {}",
+                    reply.name, reply.seq_id, method, request.seq_id
+                ),
+            }
+            .fail();
+        }
+        *conn = stream;
+
+        let mut success = None;
+        for (id, value) in reply.body.fields() {
+            if *id == 0 {
+                success = Some(value.clone());
+            } else {
+                let message = value
+                    .as_struct()
+                    .and_then(|s| s.get_str(1))
+                    .unwrap_or_default()
+                    .to_string();
+                return Ok(Err(HmsException { id: *id, message }));
+            }
+        }
+        Ok(Ok(success))
+    }
+
+    async fn roundtrip(
+        &self,
+        conn: &mut Option<TcpStream>,
+        request: &TMessage,
+    ) -> crate::Result<TMessage> {
+        let io_error = |e: std::io::Error| {
+            CatalogUnexpectedSnafu {
+                message: format!("Failed to talk to hive metastore {}: {}", self.address, e),
+            }
+            .build()
+        };
+
+        if conn.is_none() {
+            *conn = Some(TcpStream::connect(&self.address).await.map_err(io_error)?);
+        }
+        let stream = conn.as_mut().unwrap();
+        stream
+            .write_all(&request.encode())
+            .await
+            .map_err(io_error)?;
+
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 8192];
+        loop {
+            let n = stream.read(&mut chunk).await.map_err(io_error)?;
+            if n == 0 {
+                return CatalogUnexpectedSnafu {
+                    message: format!("Hive metastore {} closed the connection", self.address),
+                }
+                .fail();
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            let decoded = TMessage::decode(&buf).map_err(|e| {
+                CatalogUnexpectedSnafu {
+                    message: format!("Invalid reply from hive metastore: {e}"),
+                }
+                .build()
+            })?;
+            if let Some(reply) = decoded {
+                return Ok(reply);
+            }
+        }
+    }
+}
+
+/// The subset of a hive metastore table used by paimon.
+#[derive(Debug)]
+struct HmsTable {
+    location: String,
+    parameters: HashMap<String, String>,
+}
+
+impl HmsTable {
+    fn from_thrift(table: &TStruct) -> Self {
+        let location = table
+            .get(7)
+            .and_then(|sd| sd.as_struct())
+            .and_then(|sd| sd.get_str(2))
+            .unwrap_or_default()
+            .to_string();
+        let parameters = table
+            .get(9)
+            .and_then(|p| p.as_string_map())
+            .unwrap_or_default();
+        Self {
+            location,
+            parameters,
+        }
+    }
+
+    fn is_paimon_table(&self) -> bool {
+        self.parameters
+            .get(TABLE_TYPE_PROP)
+            .is_some_and(|v| v.eq_ignore_ascii_case(PAIMON_TABLE_TYPE_VALUE))
+    }
+
+    /// Get the table parameters not maintained by hive, which are paimon options set via hive.
+    fn options(&self) -> HashMap<String, String> {
+        self.parameters
+            .iter()
+            .filter(|(k, _)| {
+                !HIVE_RESERVED_PROPS.contains(&k.as_str()) && !k.starts_with("spark.sql.")
+            })
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+}
+
+/// A catalog using hive metastore to discover databases and tables, while schemas of tables
+/// are stored in the file system like [`FileSystemCatalog`](crate::catalog::FileSystemCatalog).
+///
+/// Altering a table only commits the schema changes in the file system, columns registered in
+/// hive metastore are not synchronized.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-hive/paimon-hive-catalog/src/main/java/org/apache/paimon/hive/HiveCatalog.java>
+#[derive(Debug)]
+pub struct HiveCatalog {
+    client: HmsClient,
+    file_io: Arc<dyn FileIO>,
+    warehouse: String,
+}
+
+impl HiveCatalog {
+    /// Create a hive catalog with the metastore uri like `thrift://localhost:9083`.
+    pub fn new(
+        file_io: Arc<dyn FileIO>,
+        warehouse: impl Into<String>,
+        uri: &str,
+    ) -> crate::Result<Self> {
+        let Some(address) = uri.strip_prefix("thrift://") else {
+            return ConfigInvalidSnafu {
+                message: format!("Invalid hive metastore uri {uri}, expected thrift://host:port"),
+            }
+            .fail();
+        };
+        Ok(Self {
+            client: HmsClient::new(address.trim_end_matches('/').to_string()),
+            file_io,
+            warehouse: warehouse.into().trim_end_matches('/').to_string(),
+        })
+    }
+
+    /// Get the warehouse path.
+    #[inline]
+    pub fn warehouse(&self) -> &str {
+        &self.warehouse
+    }
+
+    fn unexpected<T>(method: &str, e: HmsException) -> crate::Result<T> {
+        CatalogUnexpectedSnafu {
+            message: format!("Hive metastore failed to call {method}: {}", e.message),
+        }
+        .fail()
+    }
+
+    /// Get the location of the database, none if it doesn't exist.
+    async fn database_location(&self, name: &str) -> crate::Result<Option<String>> {
+        let args = TStruct::new().field(1, TValue::string(name));
+        match self.client.call("get_database", args).await? {
+            Ok(database) => {
+                let location = database
+                    .as_ref()
+                    .and_then(|d| d.as_struct())
+                    .and_then(|d| d.get_str(3))
+                    .filter(|l| !l.is_empty())
+                    .map(|l| l.to_string())
+                    .unwrap_or_else(|| format!("{}/{}{}", self.warehouse, name, DB_SUFFIX));
+                Ok(Some(location))
+            }
+            // NoSuchObjectException
+            Err(HmsException { id: 1, .. }) => Ok(None),
+            Err(e) => Self::unexpected("get_database", e),
+        }
+    }
+
+    /// Get the paimon table registered in hive metastore, none if it doesn't exist or is not a paimon table.
+    async fn hms_table(&self, identifier: &Identifier) -> crate::Result<Option<HmsTable>> {
+        let args = TStruct::new()
+            .field(1, TValue::string(identifier.database()))
+            .field(2, TValue::string(identifier.object()));
+        match self.client.call("get_table", args).await? {
+            Ok(table) => Ok(table
+                .as_ref()
+                .and_then(|t| t.as_struct())
+                .map(HmsTable::from_thrift)
+                .filter(|t| t.is_paimon_table())),
+            // NoSuchObjectException
+            Err(HmsException { id: 2, .. }) => Ok(None),
+            Err(e) => Self::unexpected("get_table", e),
+        }
+    }
+
+    async fn hms_table_or_fail(&self, identifier: &Identifier) -> crate::Result<HmsTable> {
+        match self.hms_table(identifier).await? {
+            Some(table) => Ok(table),
+            None => TableNotExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail(),
+        }
+    }
+
+    fn to_thrift_table(identifier: &Identifier, location: &str, schema: &Schema) -> TStruct {
+        let cols = schema.fields().iter().map(|field| {
+            let mut col = TStruct::new()
+                .field(1, TValue::string(field.name()))
+                .field(2, TValue::string(to_hive_type(field.data_type())));
+            if let Some(description) = field.description() {
+                col = col.field(3, TValue::string(description));
+            }
+            col
+        });
+        let serde_info = TStruct::new()
+            .field(2, TValue::string(SERDE_CLASS))
+            .field(3, TValue::string_map(&HashMap::new()));
+        let sd = TStruct::new()
+            .field(1, TValue::struct_list(cols))
+            .field(2, TValue::string(location))
+            .field(3, TValue::string(INPUT_FORMAT_CLASS))
+            .field(4, TValue::string(OUTPUT_FORMAT_CLASS))
+            .field(5, TValue::Bool(false))
+            .field(6, TValue::I32(-1))
+            .field(7, TValue::Struct(serde_info))
+            .field(8, TValue::string_list(vec![]))
+            .field(9, TValue::struct_list(vec![]))
+            .field(10, TValue::string_map(&HashMap::new()));
+
+        let mut parameters = HashMap::from([
+            (
+                TABLE_TYPE_PROP.to_string(),
+                PAIMON_TABLE_TYPE_VALUE.to_uppercase(),
+            ),
+            (
+                STORAGE_HANDLER_PROP.to_string(),
+                STORAGE_HANDLER_CLASS.to_string(),
+            ),
+        ]);
+        if let Some(comment) = schema.comment() {
+            parameters.insert("comment".to_string(), comment.to_string());
+        }
+
+        TStruct::new()
+            .field(1, TValue::string(identifier.object()))
+            .field(2, TValue::string(identifier.database()))
+            .field(
+                3,
+                TValue::string(std::env::var("USER").unwrap_or_else(|_| "paimon".to_string())),
+            )
+            .field(4, TValue::I32(chrono::Utc::now().timestamp() as i32))
+            .field(5, TValue::I32(0))
+            .field(6, TValue::I32(0))
+            .field(7, TValue::Struct(sd))
+            .field(8, TValue::struct_list(vec![]))
+            .field(9, TValue::string_map(&parameters))
+            .field(12, TValue::string("MANAGED_TABLE"))
+    }
+}
+
+/// Convert the paimon type into the type string of hive.
+fn to_hive_type(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Boolean(_) => "boolean".to_string(),
+        DataType::TinyInt(_) => "tinyint".to_string(),
+        DataType::SmallInt(_) => "smallint".to_string(),
+        DataType::Int(_) => "int".to_string(),
+        DataType::BigInt(_) => "bigint".to_string(),
+        DataType::Float(_) => "float".to_string(),
+        DataType::Double(_) => "double".to_string(),
+        DataType::Decimal(t) => format!("decimal({},{})", t.precision(), t.scale()),
+        DataType::Char(t) => format!("char({})", t.length()),
+        DataType::VarChar(t) if t.length() <= 65535 => format!("varchar({})", t.length()),
+        DataType::VarChar(_) => "string".to_string(),
+        DataType::Binary(_) | DataType::VarBinary(_) => "binary".to_string(),
+        DataType::Date(_) => "date".to_string(),
+        DataType::Time(_) => "string".to_string(),
+        DataType::Timestamp(_) | DataType::LocalZonedTimestamp(_) => "timestamp".to_string(),
+        DataType::Array(t) => format!("array<{}>", to_hive_type(t.element_type())),
+        DataType::Multiset(t) => format!("map<{},int>", to_hive_type(t.element_type())),
+        DataType::Map(t) => format!(
+            "map<{},{}>",
+            to_hive_type(t.key_type()),
+            to_hive_type(t.value_type())
+        ),
+        DataType::Row(t) => format!(
+            "struct<{}>",
+            t.fields()
+                .iter()
+                .map(|f| format!("{}:{}", f.name(), to_hive_type(f.data_type())))
+                .collect::<Vec<_>>()
+                .join(",")
+        ),
+    }
+}
+
+#[async_trait]
+impl Catalog for HiveCatalog {
+    async fn list_databases(&self) -> crate::Result<Vec<String>> {
+        match self
+            .client
+            .call("get_all_databases", TStruct::new())
+            .await?
+        {
+            Ok(databases) => Ok(databases
+                .and_then(|d| d.as_string_list())
+                .unwrap_or_default()),
+            Err(e) => Self::unexpected("get_all_databases", e),
+        }
+    }
+
+    async fn database_exists(&self, name: &str) -> crate::Result<bool> {
+        Ok(self.database_location(name).await?.is_some())
+    }
+
+    async fn create_database(
+        &self,
+        name: &str,
+        ignore_if_exists: bool,
+        mut properties: HashMap<String, String>,
+    ) -> crate::Result<()> {
+        let location = properties
+            .remove("location")
+            .unwrap_or_else(|| format!("{}/{}{}", self.warehouse, name, DB_SUFFIX));
+        let mut database = TStruct::new().field(1, TValue::string(name));
+        if let Some(comment) = properties.remove("comment") {
+            database = database.field(2, TValue::string(comment));
+        }
+        database = database
+            .field(3, TValue::string(location))
+            .field(4, TValue::string_map(&properties));
+
+        let args = TStruct::new().field(1, TValue::Struct(database));
+        match self.client.call("create_database", args).await? {
+            Ok(_) => Ok(()),
+            // AlreadyExistsException
+            Err(HmsException { id: 1, .. }) if ignore_if_exists => Ok(()),
+            Err(HmsException { id: 1, .. }) => DatabaseAlreadyExistSnafu { database: name }.fail(),
+            Err(e) => Self::unexpected("create_database", e),
+        }
+    }
+
+    async fn drop_database(
+        &self,
+        name: &str,
+        ignore_if_not_exists: bool,
+        cascade: bool,
+    ) -> crate::Result<()> {
+        if !self.database_exists(name).await? {
+            if ignore_if_not_exists {
+                return Ok(());
+            }
+            return DatabaseNotExistSnafu { database: name }.fail();
+        }
+        let tables = self.list_tables(name).await?;
+        if !cascade && !tables.is_empty() {
+            return DatabaseNotEmptySnafu { database: name }.fail();
+        }
+        for table in tables {
+            self.drop_table(&Identifier::new(name, table), true).await?;
+        }
+
+        let args = TStruct::new()
+            .field(1, TValue::string(name))
+            .field(2, TValue::Bool(true))
+            .field(3, TValue::Bool(cascade));
+        match self.client.call("drop_database", args).await? {
+            Ok(_) => Ok(()),
+            // NoSuchObjectException
+            Err(HmsException { id: 1, .. }) if ignore_if_not_exists => Ok(()),
+            Err(HmsException { id: 1, .. }) => DatabaseNotExistSnafu { database: name }.fail(),
+            Err(e) => Self::unexpected("drop_database", e),
+        }
+    }
+
+    async fn list_tables(&self, database: &str) -> crate::Result<Vec<String>> {
+        if !self.database_exists(database).await? {
+            return DatabaseNotExistSnafu { database }.fail();
+        }
+        let args = TStruct::new().field(1, TValue::string(database));
+        let names = match self.client.call("get_all_tables", args).await? {
+            Ok(tables) => tables.and_then(|t| t.as_string_list()).unwrap_or_default(),
+            Err(e) => return Self::unexpected("get_all_tables", e),
+        };
+
+        let mut tables = Vec::new();
+        for name in names {
+            if self.table_exists(&Identifier::new(database, &name)).await? {
+                tables.push(name);
+            }
+        }
+        Ok(tables)
+    }
+
+    async fn table_exists(&self, identifier: &Identifier) -> crate::Result<bool> {
+        Ok(self.hms_table(identifier).await?.is_some())
+    }
+
+    async fn get_table(&self, identifier: &Identifier) -> crate::Result<FileStoreTable> {
+        let hms_table = self.hms_table_or_fail(identifier).await?;
+        let Some(schema) = SchemaManager::new(self.file_io.clone(), &hms_table.location)
+            .latest()
+            .await?
+        else {
+            return TableNotExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail();
+        };
+
+        // options in the schema take precedence over the ones set via hive
+        let mut options = hms_table.options();
+        options.extend(schema.options().clone());
+        Ok(FileStoreTable::new(
+            self.file_io.clone(),
+            identifier.clone(),
+            hms_table.location,
+            schema.copy_with_options(options),
+        ))
+    }
+
+    async fn create_table(
+        &self,
+        identifier: &Identifier,
+        schema: Schema,
+        ignore_if_exists: bool,
+    ) -> crate::Result<()> {
+        let Some(database_location) = self.database_location(identifier.database()).await? else {
+            return DatabaseNotExistSnafu {
+                database: identifier.database(),
+            }
+            .fail();
+        };
+        if self.table_exists(identifier).await? {
+            if ignore_if_exists {
+                return Ok(());
+            }
+            return TableAlreadyExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail();
+        }
+
+        let location = format!(
+            "{}/{}",
+            database_location.trim_end_matches('/'),
+            identifier.object()
+        );
+        SchemaManager::new(self.file_io.clone(), &location)
+            .create_table(&schema)
+            .await?;
+
+        let args = TStruct::new().field(
+            1,
+            TValue::Struct(Self::to_thrift_table(identifier, &location, &schema)),
+        );
+        let result = match self.client.call("create_table", args).await {
+            Ok(Ok(_)) => return Ok(()),
+            // AlreadyExistsException
+            Ok(Err(HmsException { id: 1, .. })) => TableAlreadyExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail(),
+            Ok(Err(e)) => Self::unexpected("create_table", e),
+            Err(e) => Err(e),
+        };
+        // clean up the schema committed to the file system
+        let _ = self.file_io.delete_dir(&format!("{location}/")).await;
+        result
+    }
+
+    async fn drop_table(
+        &self,
+        identifier: &Identifier,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()> {
+        let Some(hms_table) = self.hms_table(identifier).await? else {
+            if ignore_if_not_exists {
+                return Ok(());
+            }
+            return TableNotExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail();
+        };
+
+        let args = TStruct::new()
+            .field(1, TValue::string(identifier.database()))
+            .field(2, TValue::string(identifier.object()))
+            .field(3, TValue::Bool(true));
+        match self.client.call("drop_table", args).await? {
+            Ok(_) => {}
+            // NoSuchObjectException
+            Err(HmsException { id: 1, .. }) if ignore_if_not_exists => return Ok(()),
+            Err(HmsException { id: 1, .. }) => {
+                return TableNotExistSnafu {
+                    identifier: identifier.full_name(),
+                }
+                .fail()
+            }
+            Err(e) => return Self::unexpected("drop_table", e),
+        }
+        self.file_io
+            .delete_dir(&format!("{}/", hms_table.location))
+            .await
+    }
+
+    async fn alter_table(
+        &self,
+        identifier: &Identifier,
+        changes: Vec<SchemaChange>,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()> {
+        let Some(hms_table) = self.hms_table(identifier).await? else {
+            if ignore_if_not_exists {
+                return Ok(());
+            }
+            return TableNotExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail();
+        };
+        SchemaManager::new(self.file_io.clone(), &hms_table.location)
+            .commit_changes(&changes)
+            .await
+            .map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataField, IntType, VarCharType};
+    use tokio::net::TcpListener;
+
+    /// Serve a fake hive metastore holding the given tables, keyed by `db.table`.
+    async fn serve_fake_metastore(tables: HashMap<String, TStruct>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 4096];
+            loop {
+                let Ok(n) = stream.read(&mut chunk).await else {
+                    return;
+                };
+                if n == 0 {
+                    return;
+                }
+                buf.extend_from_slice(&chunk[..n]);
+                let Some(call) = TMessage::decode(&buf).unwrap() else {
+                    continue;
+                };
+                buf.clear();
+                let body = match call.name.as_str() {
+                    "get_all_databases" => {
+                        TStruct::new().field(0, TValue::string_list(vec!["db".to_string()]))
+                    }
+                    "get_database" if call.body.get_str(1) == Some("db") => TStruct::new().field(
+                        0,
+                        TValue::Struct(TStruct::new().field(1, TValue::string("db"))),
+                    ),
+                    "get_database" => TStruct::new().field(
+                        1,
+                        TValue::Struct(TStruct::new().field(1, TValue::string("no such db"))),
+                    ),
+                    "get_all_tables" => TStruct::new().field(
+                        0,
+                        TValue::string_list(vec!["t".to_string(), "hive_t".to_string()]),
+                    ),
+                    "get_table" => {
+                        let key = format!(
+                            "{}.{}",
+                            call.body.get_str(1).unwrap(),
+                            call.body.get_str(2).unwrap()
+                        );
+                        match tables.get(&key) {
+                            Some(table) => TStruct::new().field(0, TValue::Struct(table.clone())),
+                            None => TStruct::new().field(
+                                2,
+                                TValue::Struct(
+                                    TStruct::new().field(1, TValue::string("no such table")),
+                                ),
+                            ),
+                        }
+                    }
+                    _ => TStruct::new(),
+                };
+                let reply = TMessage {
+                    name: call.name,
+                    message_type: MESSAGE_REPLY,
+                    seq_id: call.seq_id,
+                    body,
+                };
+                stream.write_all(&reply.encode()).await.unwrap();
+            }
+        });
+        format!("thrift://{address}")
+    }
+
+    #[tokio::test]
+    async fn test_hms_client_cancelled_call() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(async move {
+            // the first connection never replies, the others reply to a call each
+            let (_hanging, _) = listener.accept().await.unwrap();
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap();
+                let call = TMessage::decode(&buf[..n]).unwrap().unwrap();
+                let reply = TMessage {
+                    name: call.name,
+                    message_type: MESSAGE_REPLY,
+                    seq_id: call.seq_id,
+                    body: TStruct::new().field(0, TValue::string_list(vec!["db".to_string()])),
+                };
+                stream.write_all(&reply.encode()).await.unwrap();
+            }
+        });
+
+        let client = HmsClient::new(address);
+        let timeout = std::time::Duration::from_millis(200);
+        let cancelled =
+            tokio::time::timeout(timeout, client.call("get_all_databases", TStruct::new())).await;
+        assert!(cancelled.is_err());
+        // the connection left by the cancelled call isn't reused
+        let success =
+            tokio::time::timeout(timeout, client.call("get_all_databases", TStruct::new()))
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap()
+                .unwrap();
+        assert_eq!(success.as_string_list().unwrap(), vec!["db".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_hive_catalog_get_table() {
+        let location = "file:/tmp/test_hive_catalog_get_table/db.db/t";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "id".to_string(), DataType::Int(IntType::new())),
+                DataField::new(
+                    1,
+                    "name".to_string(),
+                    DataType::VarChar(VarCharType::new(VarCharType::MAX_LENGTH).unwrap()),
+                ),
+            ])
+            .options(HashMap::from([("bucket".to_string(), "2".to_string())]))
+            .build();
+        SchemaManager::new(file_io.clone(), location)
+            .create_table(&schema)
+            .await
+            .unwrap();
+
+        let mut paimon_table =
+            HiveCatalog::to_thrift_table(&Identifier::new("db", "t"), location, &schema);
+        paimon_table = paimon_table.field(
+            9,
+            TValue::string_map(&HashMap::from([
+                ("table_type".to_string(), "PAIMON".to_string()),
+                ("bucket".to_string(), "4".to_string()),
+                ("snapshot.num-retained.max".to_string(), "10".to_string()),
+                (
+                    "transient_lastDdlTime".to_string(),
+                    "1725614754".to_string(),
+                ),
+            ])),
+        );
+        let hive_table = TStruct::new().field(1, TValue::string("hive_t"));
+        let uri = serve_fake_metastore(HashMap::from([
+            ("db.t".to_string(), paimon_table),
+            ("db.hive_t".to_string(), hive_table),
+        ]))
+        .await;
+
+        let catalog =
+            HiveCatalog::new(file_io, "file:/tmp/test_hive_catalog_get_table", &uri).unwrap();
+        assert_eq!(catalog.list_databases().await.unwrap(), vec!["db"]);
+        assert!(!catalog.database_exists("unknown").await.unwrap());
+        assert_eq!(catalog.list_tables("db").await.unwrap(), vec!["t"]);
+
+        let table = catalog
+            .get_table(&Identifier::new("db", "t"))
+            .await
+            .unwrap();
+        assert_eq!(table.location(), location);
+        let options = table.schema().options();
+        assert_eq!(options.get("bucket"), Some(&"2".to_string()));
+        assert_eq!(
+            options.get("snapshot.num-retained.max"),
+            Some(&"10".to_string())
+        );
+        assert!(!options.contains_key("transient_lastDdlTime"));
+        assert!(matches!(
+            catalog.get_table(&Identifier::new("db", "hive_t")).await,
+            Err(crate::Error::TableNotExist { .. })
+        ));
+    }
+
+    #[test]
+    fn test_to_hive_type() {
+        let typ: DataType = "ROW<a ARRAY<STRING>, b MAP<VARCHAR(10), DECIMAL(10, 2)>>"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            to_hive_type(&typ),
+            "struct<a:array<string>,b:map<varchar(10),decimal(10,2)>>"
+        );
+        assert!(HiveCatalog::new(
+            FileIOBuilder::from_url("memory:/")
+                .unwrap()
+                .build()
+                .unwrap(),
+            "memory:/",
+            "localhost:9083"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,317 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::io::FileIO;
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default timeout of acquiring catalog locks, which is also the default expiration of the
+/// locks.
+pub const DEFAULT_LOCK_ACQUIRE_TIMEOUT: Duration = Duration::from_secs(8 * 60);
+
+/// Default max sleep between two attempts of acquiring catalog locks.
+pub const DEFAULT_LOCK_CHECK_MAX_SLEEP: Duration = Duration::from_secs(8);
+
+/// A lock of the tables of a catalog shared by the writers of the tables.
+///
+/// Commits of tables with a catalog lock create their snapshot files while holding the lock
+/// of the table, so that concurrent writers don't overwrite the snapshots of each other on
+/// stores without atomic renames, such as object stores.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/catalog/CatalogLock.java>
+#[async_trait]
+pub trait CatalogLock: Send + Sync + Debug {
+    /// Acquire the lock of the table, waiting until it's released by others or timing out.
+    async fn acquire(&self, database: &str, table: &str) -> crate::Result<()>;
+
+    /// Release the lock of the table acquired.
+    async fn release(&self, database: &str, table: &str) -> crate::Result<()>;
+}
+
+impl dyn CatalogLock {
+    /// Run the function while holding the lock of the table, the lock is released whatever
+    /// the function returns.
+    pub async fn run_with_lock<T, F, Fut>(
+        &self,
+        database: &str,
+        table: &str,
+        f: F,
+    ) -> crate::Result<T>
+    where
+        F: FnOnce() -> Fut + Send,
+        Fut: Future<Output = crate::Result<T>> + Send,
+        T: Send,
+    {
+        self.acquire(database, table).await?;
+        let result = f().await;
+        let released = self.release(database, table).await;
+        let value = result?;
+        released?;
+        Ok(value)
+    }
+}
+
+/// Try to acquire the lock until succeeded, sleeping exponentially longer up to the max sleep
+/// between the attempts, fails once the timeout is reached.
+pub(crate) async fn acquire_with_retries<F, Fut>(
+    lock_id: &str,
+    acquire_timeout: Duration,
+    check_max_sleep: Duration,
+    mut try_acquire: F,
+) -> crate::Result<()>
+where
+    F: FnMut() -> Fut + Send,
+    Fut: Future<Output = crate::Result<bool>> + Send,
+{
+    let start = Instant::now();
+    let mut sleep = Duration::from_millis(100).min(check_max_sleep);
+    loop {
+        if try_acquire().await? {
+            return Ok(());
+        }
+        if start.elapsed() >= acquire_timeout {
+            return CatalogUnexpectedSnafu {
+                message: format!(
+                    "Failed to acquire catalog lock {lock_id} within {acquire_timeout:?}"
+                ),
+            }
+            .fail();
+        }
+        tokio::time::sleep(sleep).await;
+        sleep = (sleep * 2).min(check_max_sleep);
+    }
+}
+
+/// A lock of tables by lock files in a directory of a file system whose files can be written
+/// atomically by [`FileIO::try_to_write_atomic`], such as a local or webhdfs directory, or an
+/// object store with conditional writes.
+///
+/// The lock file `{database}.{table}.lock` holds the time the lock is acquired and the token
+/// of its holder, it's created atomically and deleted on release by its holder. Locks of
+/// writers that failed to release them expire, after the acquire timeout by default.
+///
+/// An expired lock is taken over by the writer atomically creating the marker
+/// `{database}.{table}.lock.{acquired time}-{token}` of its holder, so only one of the writers
+/// seeing the expired lock replaces it. The markers are kept, since a late writer may still
+/// see the expired lock after it's replaced.
+#[derive(Debug, Clone)]
+pub struct FileSystemCatalogLock {
+    file_io: Arc<dyn FileIO>,
+    lock_dir: String,
+    acquire_timeout: Duration,
+    check_max_sleep: Duration,
+    expiration: Duration,
+    /// The tokens of the locks held, by the paths of the lock files.
+    tokens: Arc<Mutex<HashMap<String, String>>>,
+}
+
+impl FileSystemCatalogLock {
+    pub fn new(file_io: Arc<dyn FileIO>, lock_dir: impl Into<String>) -> Self {
+        Self {
+            file_io,
+            lock_dir: lock_dir.into().trim_end_matches('/').to_string(),
+            acquire_timeout: DEFAULT_LOCK_ACQUIRE_TIMEOUT,
+            check_max_sleep: DEFAULT_LOCK_CHECK_MAX_SLEEP,
+            expiration: DEFAULT_LOCK_ACQUIRE_TIMEOUT,
+            tokens: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Set the timeout of acquiring the lock.
+    pub fn with_acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.acquire_timeout = timeout;
+        self
+    }
+
+    /// Set the max sleep between two attempts of acquiring the lock.
+    pub fn with_check_max_sleep(mut self, sleep: Duration) -> Self {
+        self.check_max_sleep = sleep;
+        self
+    }
+
+    /// Set the time after which the locks not released are expired.
+    pub fn with_expiration(mut self, expiration: Duration) -> Self {
+        self.expiration = expiration;
+        self
+    }
+
+    /// Get the path of the lock file of the table.
+    pub fn lock_path(&self, database: &str, table: &str) -> String {
+        format!("{}/{database}.{table}.lock", self.lock_dir)
+    }
+
+    async fn try_acquire(&self, path: &str) -> crate::Result<bool> {
+        let now = Utc::now().timestamp_millis();
+        let token = uuid::Uuid::new_v4().to_string();
+        let content = Bytes::from(format!("{now} {token}"));
+        if self
+            .file_io
+            .try_to_write_atomic(path, content.clone())
+            .await?
+        {
+            self.hold(path, token);
+            return Ok(true);
+        }
+        // the lock may be released or be expired meanwhile
+        let Ok(held) = self.file_io.read_file_utf8(path).await else {
+            return Ok(false);
+        };
+        let expired = match held.split_whitespace().next().map(str::parse::<i64>) {
+            Some(Ok(acquired_at)) => now - acquired_at > self.expiration.as_millis() as i64,
+            _ => true,
+        };
+        if !expired {
+            return Ok(false);
+        }
+
+        // only the writer creating the marker of the expired holder replaces the lock
+        let holder: String = held
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join("-")
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect();
+        let marker = format!("{path}.{holder}");
+        if !self
+            .file_io
+            .try_to_write_atomic(&marker, content.clone())
+            .await?
+        {
+            return Ok(false);
+        }
+        self.file_io.delete_file(path).await?;
+        if self.file_io.try_to_write_atomic(path, content).await? {
+            self.hold(path, token);
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    fn hold(&self, path: &str, token: String) {
+        self.tokens.lock().unwrap().insert(path.to_string(), token);
+    }
+}
+
+#[async_trait]
+impl CatalogLock for FileSystemCatalogLock {
+    async fn acquire(&self, database: &str, table: &str) -> crate::Result<()> {
+        let path = self.lock_path(database, table);
+        acquire_with_retries(&path, self.acquire_timeout, self.check_max_sleep, || {
+            self.try_acquire(&path)
+        })
+        .await
+    }
+
+    /// Release the lock, the lock file is kept if it's taken over by others after expired.
+    async fn release(&self, database: &str, table: &str) -> crate::Result<()> {
+        let path = self.lock_path(database, table);
+        let Some(token) = self.tokens.lock().unwrap().remove(&path) else {
+            return Ok(());
+        };
+        if !self.file_io.exists(&path).await? {
+            return Ok(());
+        }
+        let held = self.file_io.read_file_utf8(&path).await?;
+        if held.split_whitespace().nth(1) != Some(token.as_str()) {
+            return Ok(());
+        }
+        self.file_io.delete_file(&path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_file_system_catalog_lock() {
+        let lock_dir = "file:/tmp/test_file_system_catalog_lock";
+        let file_io = FileIOBuilder::from_url(lock_dir).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{lock_dir}/")).await;
+        let lock = FileSystemCatalogLock::new(file_io.clone(), lock_dir)
+            .with_acquire_timeout(Duration::from_millis(200))
+            .with_check_max_sleep(Duration::from_millis(50));
+        let path = lock.lock_path("db", "t");
+        let expiring: Arc<dyn CatalogLock> =
+            Arc::new(lock.clone().with_expiration(Duration::from_millis(100)));
+        let lock: Arc<dyn CatalogLock> = Arc::new(lock.with_expiration(Duration::from_secs(60)));
+
+        let result = lock
+            .run_with_lock("db", "t", || async {
+                assert!(file_io.exists(&path).await.unwrap());
+                // the lock is not reentrant, nested acquiring times out
+                lock.run_with_lock("db", "t", || async { Ok(()) }).await
+            })
+            .await;
+        assert!(matches!(result, Err(Error::CatalogUnexpected { .. })));
+        assert!(!file_io.exists(&path).await.unwrap());
+        // locks of other tables are independent
+        lock.acquire("db", "t").await.unwrap();
+        lock.run_with_lock("db", "t2", || async { Ok(()) })
+            .await
+            .unwrap();
+
+        // the lock never released expires
+        let value = expiring.run_with_lock("db", "t", || async { Ok(1) }).await;
+        assert_eq!(value.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_file_system_catalog_lock_take_over() {
+        let lock_dir = "file:/tmp/test_file_system_catalog_lock_take_over";
+        let file_io = FileIOBuilder::from_url(lock_dir).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{lock_dir}/")).await;
+        let new_lock = || {
+            FileSystemCatalogLock::new(file_io.clone(), lock_dir)
+                .with_expiration(Duration::from_millis(100))
+        };
+        let expired = new_lock();
+        let path = expired.lock_path("db", "t");
+        expired.acquire("db", "t").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        // only one of the writers seeing the expired lock takes it over
+        let locks: Vec<_> = (0..8).map(|_| new_lock()).collect();
+        let acquired = futures::future::join_all(locks.iter().map(|lock| lock.try_acquire(&path)))
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .filter(|acquired| *acquired)
+            .count();
+        assert_eq!(acquired, 1);
+
+        // the expired holder doesn't release the lock taken over
+        let held = file_io.read_file_utf8(&path).await.unwrap();
+        expired.release("db", "t").await.unwrap();
+        assert_eq!(file_io.read_file_utf8(&path).await.unwrap(), held);
+        for lock in &locks {
+            lock.release("db", "t").await.unwrap();
+        }
+        assert!(!file_io.exists(&path).await.unwrap());
+    }
+}
@@ -0,0 +1,231 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Catalog API for paimon.
+
+mod caching;
+pub use caching::*;
+
+mod filesystem;
+pub use filesystem::*;
+
+#[cfg(feature = "catalog-hive")]
+mod hive;
+#[cfg(feature = "catalog-hive")]
+pub use hive::*;
+#[cfg(feature = "catalog-hive")]
+mod thrift;
+
+mod lock;
+pub use lock::*;
+
+#[cfg(feature = "catalog-jdbc")]
+mod jdbc;
+#[cfg(feature = "catalog-jdbc")]
+pub use jdbc::*;
+
+#[cfg(feature = "catalog-zookeeper")]
+mod zookeeper;
+#[cfg(feature = "catalog-zookeeper")]
+pub use zookeeper::*;
+
+#[cfg(feature = "catalog-rest")]
+mod rest;
+#[cfg(feature = "catalog-rest")]
+pub use rest::*;
+
+use crate::error::{ConfigInvalidSnafu, TableNotExistSnafu};
+use crate::spec::{Schema, SchemaChange};
+use crate::table::{system_table, FileStoreTable, FormatTable, SystemTable, SYSTEM_TABLE_SPLITTER};
+use crate::Error;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Name of the default database.
+pub const DEFAULT_DATABASE: &str = "default";
+
+/// Suffix of the directory of a database.
+pub const DB_SUFFIX: &str = ".db";
+
+/// Identifies an object like a table in a catalog.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/catalog/Identifier.java>
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Identifier {
+    database: String,
+    object: String,
+}
+
+impl Identifier {
+    pub fn new(database: impl Into<String>, object: impl Into<String>) -> Self {
+        Self {
+            database: database.into(),
+            object: object.into(),
+        }
+    }
+
+    /// Get the database name.
+    #[inline]
+    pub fn database(&self) -> &str {
+        &self.database
+    }
+
+    /// Get the object name, e.g. the table name.
+    #[inline]
+    pub fn object(&self) -> &str {
+        &self.object
+    }
+
+    /// Get the full name like `database.object`.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.database, self.object)
+    }
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.database, self.object)
+    }
+}
+
+impl FromStr for Identifier {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('.') {
+            Some((database, object)) if !database.is_empty() && !object.is_empty() => {
+                Ok(Identifier::new(database, object))
+            }
+            _ => ConfigInvalidSnafu {
+                message: format!(
+                    "Cannot get splits from '{s}' to get database and object, it should be like 'database.object'"
+                ),
+            }
+            .fail(),
+        }
+    }
+}
+
+/// Catalog manages the metadata of databases and tables.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/catalog/Catalog.java>
+#[async_trait]
+pub trait Catalog: Send + Sync {
+    /// List the names of all databases.
+    async fn list_databases(&self) -> crate::Result<Vec<String>>;
+
+    /// Check whether the database exists.
+    async fn database_exists(&self, name: &str) -> crate::Result<bool>;
+
+    /// Create a database with the given properties.
+    async fn create_database(
+        &self,
+        name: &str,
+        ignore_if_exists: bool,
+        properties: HashMap<String, String>,
+    ) -> crate::Result<()>;
+
+    /// Drop a database, tables in it are dropped too if `cascade` is true.
+    async fn drop_database(
+        &self,
+        name: &str,
+        ignore_if_not_exists: bool,
+        cascade: bool,
+    ) -> crate::Result<()>;
+
+    /// List the names of all tables in the database.
+    async fn list_tables(&self, database: &str) -> crate::Result<Vec<String>>;
+
+    /// Check whether the table exists.
+    async fn table_exists(&self, identifier: &Identifier) -> crate::Result<bool>;
+
+    /// Get the table with its latest schema.
+    async fn get_table(&self, identifier: &Identifier) -> crate::Result<FileStoreTable>;
+
+    /// Get the system table of the table, the object of the identifier is named like
+    /// `table$snapshots`.
+    async fn get_system_table(
+        &self,
+        identifier: &Identifier,
+    ) -> crate::Result<Box<dyn SystemTable>> {
+        let not_exist = || {
+            TableNotExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .build()
+        };
+        let (table, name) = identifier
+            .object()
+            .split_once(SYSTEM_TABLE_SPLITTER)
+            .ok_or_else(not_exist)?;
+        let table = self
+            .get_table(&Identifier::new(identifier.database(), table))
+            .await?;
+        system_table(table, name).ok_or_else(not_exist)
+    }
+
+    /// Get the format table of the plain files under the location of the table, whose option
+    /// `type` is `format-table`.
+    async fn get_format_table(&self, identifier: &Identifier) -> crate::Result<FormatTable> {
+        let table = self.get_table(identifier).await?;
+        FormatTable::new(
+            table.file_io().clone(),
+            identifier.clone(),
+            table.location(),
+            table.schema().clone(),
+        )
+    }
+
+    /// Create a table with the given schema.
+    async fn create_table(
+        &self,
+        identifier: &Identifier,
+        schema: Schema,
+        ignore_if_exists: bool,
+    ) -> crate::Result<()>;
+
+    /// Drop a table with all its files.
+    async fn drop_table(
+        &self,
+        identifier: &Identifier,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()>;
+
+    /// Apply the schema changes on a table.
+    async fn alter_table(
+        &self,
+        identifier: &Identifier,
+        changes: Vec<SchemaChange>,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_identifier_from_str() {
+        let identifier: Identifier = "db.tbl".parse().unwrap();
+        assert_eq!(identifier, Identifier::new("db", "tbl"));
+        assert_eq!(identifier.full_name(), "db.tbl");
+        assert!("tbl".parse::<Identifier>().is_err());
+        assert!(".tbl".parse::<Identifier>().is_err());
+    }
+}
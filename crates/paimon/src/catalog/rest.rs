@@ -0,0 +1,644 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::catalog::{Catalog, Identifier};
+use crate::error::*;
+use crate::io::FileIOBuilder;
+use crate::spec::{Schema, SchemaChange, Snapshot};
+use crate::table::FileStoreTable;
+use async_trait::async_trait;
+use reqwest::{Method, StatusCode};
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use std::collections::HashMap;
+use url::Url;
+
+/// Option of the uri of the rest catalog server.
+pub const REST_URI: &str = "uri";
+/// Option of the bearer token to authenticate with.
+pub const REST_TOKEN: &str = "token";
+/// Option of the warehouse passed to the server to get the catalog config.
+pub const REST_WAREHOUSE: &str = "warehouse";
+/// Option of the path prefix of resources, usually set by the server config.
+pub const REST_PREFIX: &str = "prefix";
+/// Prefix of options which are sent as http headers.
+pub const REST_HEADER_PREFIX: &str = "header.";
+
+/// An error response of the rest catalog server.
+#[derive(Debug, Deserialize)]
+struct RestError {
+    #[serde(skip)]
+    status: StatusCode,
+    #[serde(default)]
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ConfigResponse {
+    #[serde(default)]
+    defaults: HashMap<String, String>,
+    #[serde(default)]
+    overrides: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListDatabasesResponse {
+    #[serde(default)]
+    databases: Vec<String>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListTablesResponse {
+    #[serde(default)]
+    tables: Vec<String>,
+    next_page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateDatabaseRequest<'a> {
+    name: &'a str,
+    options: &'a HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize)]
+struct IdentifierBody<'a> {
+    database: &'a str,
+    object: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct CreateTableRequest<'a> {
+    identifier: IdentifierBody<'a>,
+    schema: &'a Schema,
+}
+
+#[derive(Debug, Serialize)]
+struct AlterTableRequest<'a> {
+    changes: &'a [SchemaChange],
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GetTableResponse {
+    path: String,
+    schema_id: i64,
+    schema: Schema,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetSnapshotResponse {
+    snapshot: Snapshot,
+}
+
+/// A catalog talking to a server implementing paimon's rest catalog protocol.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/master/paimon-core/src/main/java/org/apache/paimon/rest/RESTCatalog.java>
+#[derive(Debug, Clone)]
+pub struct RestCatalog {
+    client: reqwest::Client,
+    base_url: Url,
+    options: HashMap<String, String>,
+}
+
+impl RestCatalog {
+    /// Create a rest catalog, options are merged with the config provided by the server.
+    pub async fn new(options: HashMap<String, String>) -> crate::Result<Self> {
+        let Some(uri) = options.get(REST_URI) else {
+            return ConfigInvalidSnafu {
+                message: format!("Option {REST_URI} is required for rest catalog"),
+            }
+            .fail();
+        };
+        let base_url = Url::parse(&format!("{}/v1/", uri.trim_end_matches('/'))).map_err(|e| {
+            ConfigInvalidSnafu {
+                message: format!("Invalid rest catalog uri {uri}: {e}"),
+            }
+            .build()
+        })?;
+        let mut catalog = Self {
+            client: reqwest::Client::new(),
+            base_url,
+            options: options.clone(),
+        };
+
+        let mut query = vec![];
+        if let Some(warehouse) = options.get(REST_WAREHOUSE) {
+            query.push((REST_WAREHOUSE, warehouse.as_str()));
+        }
+        let config: ConfigResponse = catalog
+            .request(Method::GET, &["config"], &query, None::<&()>)
+            .await?
+            .or_else(|e| Self::unexpected("get config", e))?;
+        let mut merged = config.defaults;
+        merged.extend(options);
+        merged.extend(config.overrides);
+        catalog.options = merged;
+        Ok(catalog)
+    }
+
+    /// Get the options merged with the server config.
+    #[inline]
+    pub fn options(&self) -> &HashMap<String, String> {
+        &self.options
+    }
+
+    fn unexpected<T>(action: &str, e: RestError) -> crate::Result<T> {
+        CatalogUnexpectedSnafu {
+            message: format!(
+                "Rest catalog failed to {action} with status {}: {}",
+                e.status, e.message
+            ),
+        }
+        .fail()
+    }
+
+    /// Build the url of a resource under the prefix, path segments are escaped.
+    fn resource_url(&self, segments: &[&str]) -> Url {
+        let mut url = self.base_url.clone();
+        {
+            let mut path = url.path_segments_mut().expect("http url must have a path");
+            path.pop_if_empty();
+            if segments.first() != Some(&"config") {
+                if let Some(prefix) = self.options.get(REST_PREFIX).filter(|p| !p.is_empty()) {
+                    path.push(prefix);
+                }
+            }
+            path.extend(segments);
+        }
+        url
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        segments: &[&str],
+        query: &[(&str, &str)],
+        body: Option<&impl Serialize>,
+    ) -> crate::Result<Result<T, RestError>> {
+        let http_error = |e: reqwest::Error| {
+            CatalogUnexpectedSnafu {
+                message: format!("Failed to request rest catalog: {e}"),
+            }
+            .build()
+        };
+
+        let mut request = self
+            .client
+            .request(method, self.resource_url(segments))
+            .query(query);
+        if let Some(token) = self.options.get(REST_TOKEN) {
+            request = request.bearer_auth(token);
+        }
+        for (key, value) in &self.options {
+            if let Some(header) = key.strip_prefix(REST_HEADER_PREFIX) {
+                request = request.header(header, value);
+            }
+        }
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+
+        let response = request.send().await.map_err(http_error)?;
+        let status = response.status();
+        let bytes = response.bytes().await.map_err(http_error)?;
+        // void responses may have no body
+        let bytes: &[u8] = if bytes.is_empty() { b"null" } else { &bytes };
+
+        if !status.is_success() {
+            let mut error = serde_json::from_slice::<RestError>(bytes).unwrap_or(RestError {
+                status,
+                message: String::from_utf8_lossy(bytes).to_string(),
+            });
+            error.status = status;
+            return Ok(Err(error));
+        }
+        let value = serde_json::from_slice(bytes).context(JsonUnexpectedSnafu {
+            message: "Failed to parse response of rest catalog",
+        })?;
+        Ok(Ok(value))
+    }
+
+    /// Load the latest snapshot of the table from the server, none if the table has no snapshot.
+    pub async fn load_snapshot(&self, identifier: &Identifier) -> crate::Result<Option<Snapshot>> {
+        let response: Result<GetSnapshotResponse, _> = self
+            .request(
+                Method::GET,
+                &[
+                    "databases",
+                    identifier.database(),
+                    "tables",
+                    identifier.object(),
+                    "snapshot",
+                ],
+                &[],
+                None::<&()>,
+            )
+            .await?;
+        match response {
+            Ok(response) => Ok(Some(response.snapshot)),
+            Err(e) if e.status == StatusCode::NOT_FOUND => Ok(None),
+            Err(e) => Self::unexpected("load snapshot", e),
+        }
+    }
+
+    fn table_not_exist<T>(identifier: &Identifier) -> crate::Result<T> {
+        TableNotExistSnafu {
+            identifier: identifier.full_name(),
+        }
+        .fail()
+    }
+}
+
+#[async_trait]
+impl Catalog for RestCatalog {
+    async fn list_databases(&self) -> crate::Result<Vec<String>> {
+        let mut databases = vec![];
+        let mut page_token = None;
+        loop {
+            let query: Vec<(&str, &str)> = page_token
+                .as_deref()
+                .map(|t| vec![("pageToken", t)])
+                .unwrap_or_default();
+            let response: ListDatabasesResponse = self
+                .request(Method::GET, &["databases"], &query, None::<&()>)
+                .await?
+                .or_else(|e| Self::unexpected("list databases", e))?;
+            databases.extend(response.databases);
+            match response.next_page_token.filter(|t| !t.is_empty()) {
+                Some(token) => page_token = Some(token),
+                None => return Ok(databases),
+            }
+        }
+    }
+
+    async fn database_exists(&self, name: &str) -> crate::Result<bool> {
+        let response: Result<IgnoredAny, _> = self
+            .request(Method::GET, &["databases", name], &[], None::<&()>)
+            .await?;
+        match response {
+            Ok(_) => Ok(true),
+            Err(e) if e.status == StatusCode::NOT_FOUND => Ok(false),
+            Err(e) => Self::unexpected("get database", e),
+        }
+    }
+
+    async fn create_database(
+        &self,
+        name: &str,
+        ignore_if_exists: bool,
+        properties: HashMap<String, String>,
+    ) -> crate::Result<()> {
+        let body = CreateDatabaseRequest {
+            name,
+            options: &properties,
+        };
+        let response: Result<IgnoredAny, _> = self
+            .request(Method::POST, &["databases"], &[], Some(&body))
+            .await?;
+        match response {
+            Ok(_) => Ok(()),
+            Err(e) if e.status == StatusCode::CONFLICT && ignore_if_exists => Ok(()),
+            Err(e) if e.status == StatusCode::CONFLICT => {
+                DatabaseAlreadyExistSnafu { database: name }.fail()
+            }
+            Err(e) => Self::unexpected("create database", e),
+        }
+    }
+
+    async fn drop_database(
+        &self,
+        name: &str,
+        ignore_if_not_exists: bool,
+        cascade: bool,
+    ) -> crate::Result<()> {
+        if !cascade && !self.list_tables(name).await?.is_empty() {
+            return DatabaseNotEmptySnafu { database: name }.fail();
+        }
+        let response: Result<IgnoredAny, _> = self
+            .request(Method::DELETE, &["databases", name], &[], None::<&()>)
+            .await?;
+        match response {
+            Ok(_) => Ok(()),
+            Err(e) if e.status == StatusCode::NOT_FOUND && ignore_if_not_exists => Ok(()),
+            Err(e) if e.status == StatusCode::NOT_FOUND => {
+                DatabaseNotExistSnafu { database: name }.fail()
+            }
+            Err(e) => Self::unexpected("drop database", e),
+        }
+    }
+
+    async fn list_tables(&self, database: &str) -> crate::Result<Vec<String>> {
+        let mut tables = vec![];
+        let mut page_token = None;
+        loop {
+            let query: Vec<(&str, &str)> = page_token
+                .as_deref()
+                .map(|t| vec![("pageToken", t)])
+                .unwrap_or_default();
+            let response: Result<ListTablesResponse, _> = self
+                .request(
+                    Method::GET,
+                    &["databases", database, "tables"],
+                    &query,
+                    None::<&()>,
+                )
+                .await?;
+            let response = match response {
+                Ok(response) => response,
+                Err(e) if e.status == StatusCode::NOT_FOUND => {
+                    return DatabaseNotExistSnafu { database }.fail()
+                }
+                Err(e) => return Self::unexpected("list tables", e),
+            };
+            tables.extend(response.tables);
+            match response.next_page_token.filter(|t| !t.is_empty()) {
+                Some(token) => page_token = Some(token),
+                None => return Ok(tables),
+            }
+        }
+    }
+
+    async fn table_exists(&self, identifier: &Identifier) -> crate::Result<bool> {
+        match self.get_table(identifier).await {
+            Ok(_) => Ok(true),
+            Err(crate::Error::TableNotExist { .. }) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn get_table(&self, identifier: &Identifier) -> crate::Result<FileStoreTable> {
+        let response: Result<GetTableResponse, _> = self
+            .request(
+                Method::GET,
+                &[
+                    "databases",
+                    identifier.database(),
+                    "tables",
+                    identifier.object(),
+                ],
+                &[],
+                None::<&()>,
+            )
+            .await?;
+        let response = match response {
+            Ok(response) => response,
+            Err(e) if e.status == StatusCode::NOT_FOUND => {
+                return Self::table_not_exist(identifier)
+            }
+            Err(e) => return Self::unexpected("get table", e),
+        };
+
+        let file_io = FileIOBuilder::from_url(&response.path)?
+            .with_props(self.options.iter())
+            .build()?;
+        Ok(FileStoreTable::new(
+            file_io,
+            identifier.clone(),
+            response.path,
+            response.schema.to_table_schema(response.schema_id)?,
+        ))
+    }
+
+    async fn create_table(
+        &self,
+        identifier: &Identifier,
+        schema: Schema,
+        ignore_if_exists: bool,
+    ) -> crate::Result<()> {
+        let body = CreateTableRequest {
+            identifier: IdentifierBody {
+                database: identifier.database(),
+                object: identifier.object(),
+            },
+            schema: &schema,
+        };
+        let response: Result<IgnoredAny, _> = self
+            .request(
+                Method::POST,
+                &["databases", identifier.database(), "tables"],
+                &[],
+                Some(&body),
+            )
+            .await?;
+        match response {
+            Ok(_) => Ok(()),
+            Err(e) if e.status == StatusCode::CONFLICT && ignore_if_exists => Ok(()),
+            Err(e) if e.status == StatusCode::CONFLICT => TableAlreadyExistSnafu {
+                identifier: identifier.full_name(),
+            }
+            .fail(),
+            Err(e) if e.status == StatusCode::NOT_FOUND => DatabaseNotExistSnafu {
+                database: identifier.database(),
+            }
+            .fail(),
+            Err(e) => Self::unexpected("create table", e),
+        }
+    }
+
+    async fn drop_table(
+        &self,
+        identifier: &Identifier,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()> {
+        let response: Result<IgnoredAny, _> = self
+            .request(
+                Method::DELETE,
+                &[
+                    "databases",
+                    identifier.database(),
+                    "tables",
+                    identifier.object(),
+                ],
+                &[],
+                None::<&()>,
+            )
+            .await?;
+        match response {
+            Ok(_) => Ok(()),
+            Err(e) if e.status == StatusCode::NOT_FOUND && ignore_if_not_exists => Ok(()),
+            Err(e) if e.status == StatusCode::NOT_FOUND => Self::table_not_exist(identifier),
+            Err(e) => Self::unexpected("drop table", e),
+        }
+    }
+
+    async fn alter_table(
+        &self,
+        identifier: &Identifier,
+        changes: Vec<SchemaChange>,
+        ignore_if_not_exists: bool,
+    ) -> crate::Result<()> {
+        let body = AlterTableRequest { changes: &changes };
+        let response: Result<IgnoredAny, _> = self
+            .request(
+                Method::POST,
+                &[
+                    "databases",
+                    identifier.database(),
+                    "tables",
+                    identifier.object(),
+                ],
+                &[],
+                Some(&body),
+            )
+            .await?;
+        match response {
+            Ok(_) => Ok(()),
+            Err(e) if e.status == StatusCode::NOT_FOUND && ignore_if_not_exists => Ok(()),
+            Err(e) if e.status == StatusCode::NOT_FOUND => Self::table_not_exist(identifier),
+            Err(e) => Self::unexpected("alter table", e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    type Handler = dyn Fn(&str, &str) -> (u16, String) + Send + Sync;
+
+    /// Serve http requests with the handler of `(method, path with query) -> (status, body)`,
+    /// recording the authorization headers.
+    async fn serve(handler: Arc<Handler>, auth: Arc<Mutex<Vec<String>>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = Vec::new();
+                let mut chunk = [0u8; 4096];
+                let head_end = loop {
+                    let n = stream.read(&mut chunk).await.unwrap();
+                    buf.extend_from_slice(&chunk[..n]);
+                    if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                        break pos;
+                    }
+                };
+                let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+                let mut lines = head.lines();
+                let mut request_line = lines.next().unwrap().split(' ');
+                let (method, target) = (request_line.next().unwrap(), request_line.next().unwrap());
+                let mut content_length = 0;
+                for line in lines {
+                    let (name, value) = line.split_once(':').unwrap();
+                    match name.to_ascii_lowercase().as_str() {
+                        "authorization" => auth.lock().unwrap().push(value.trim().to_string()),
+                        "content-length" => content_length = value.trim().parse().unwrap(),
+                        _ => {}
+                    }
+                }
+                while buf.len() < head_end + 4 + content_length {
+                    let n = stream.read(&mut chunk).await.unwrap();
+                    buf.extend_from_slice(&chunk[..n]);
+                }
+
+                let (status, body) = handler(method, target);
+                let response = format!(
+                    "HTTP/1.1 {status} OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        format!("http://{address}")
+    }
+
+    #[tokio::test]
+    async fn test_rest_catalog() {
+        let handler: Arc<Handler> = Arc::new(|method, target| match (method, target) {
+            ("GET", "/v1/config?warehouse=wh") => (
+                200,
+                r#"{"defaults":{"prefix":"paimon"},"overrides":{}}"#.to_string(),
+            ),
+            ("GET", "/v1/paimon/databases") => (
+                200,
+                r#"{"databases":["db1"],"nextPageToken":"p1"}"#.to_string(),
+            ),
+            ("GET", "/v1/paimon/databases?pageToken=p1") => {
+                (200, r#"{"databases":["db2"]}"#.to_string())
+            }
+            ("GET", "/v1/paimon/databases/db1") => (200, r#"{"name":"db1"}"#.to_string()),
+            ("POST", "/v1/paimon/databases") => (
+                409,
+                r#"{"message":"Database db1 already exists","code":409}"#.to_string(),
+            ),
+            ("GET", "/v1/paimon/databases/db1/tables") => (200, r#"{"tables":["t"]}"#.to_string()),
+            ("GET", "/v1/paimon/databases/db1/tables/t") => (
+                200,
+                r#"{"id":"1","name":"t","path":"memory:/db1.db/t","schemaId":3,
+                    "schema":{"fields":[{"id":0,"name":"id","type":"INT"}],"primaryKeys":["id"],
+                    "options":{"bucket":"2"}}}"#
+                    .to_string(),
+            ),
+            ("GET", "/v1/paimon/databases/db1/tables/t/snapshot") => (
+                404,
+                r#"{"message":"Table db1.t has no snapshot"}"#.to_string(),
+            ),
+            _ => (404, r#"{"message":"not found"}"#.to_string()),
+        });
+        let auth = Arc::new(Mutex::new(vec![]));
+        let uri = serve(handler, auth.clone()).await;
+
+        let catalog = RestCatalog::new(HashMap::from([
+            (REST_URI.to_string(), uri),
+            (REST_WAREHOUSE.to_string(), "wh".to_string()),
+            (REST_TOKEN.to_string(), "secret".to_string()),
+        ]))
+        .await
+        .unwrap();
+        assert_eq!(
+            catalog.options().get(REST_PREFIX),
+            Some(&"paimon".to_string())
+        );
+
+        assert_eq!(catalog.list_databases().await.unwrap(), vec!["db1", "db2"]);
+        assert!(catalog.database_exists("db1").await.unwrap());
+        assert!(!catalog.database_exists("db3").await.unwrap());
+        assert!(matches!(
+            catalog.create_database("db1", false, HashMap::new()).await,
+            Err(crate::Error::DatabaseAlreadyExist { .. })
+        ));
+        catalog
+            .create_database("db1", true, HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(catalog.list_tables("db1").await.unwrap(), vec!["t"]);
+        assert!(matches!(
+            catalog.list_tables("db3").await,
+            Err(crate::Error::DatabaseNotExist { .. })
+        ));
+
+        let identifier = Identifier::new("db1", "t");
+        let table = catalog.get_table(&identifier).await.unwrap();
+        assert_eq!(table.location(), "memory:/db1.db/t");
+        assert_eq!(table.schema().id(), 3);
+        assert_eq!(table.schema().primary_keys(), &["id".to_string()]);
+        assert!(!catalog
+            .table_exists(&Identifier::new("db1", "unknown"))
+            .await
+            .unwrap());
+        assert_eq!(catalog.load_snapshot(&identifier).await.unwrap(), None);
+
+        assert!(auth.lock().unwrap().iter().all(|a| a == "Bearer secret"));
+    }
+}
@@ -0,0 +1,443 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A minimal implementation of the thrift binary protocol, enough to talk to services like hive metastore.
+//!
+//! Values are decoded into generic [`TValue`]s instead of generated structs, callers pick the fields they need by id.
+//!
+//! Reference: <https://github.com/apache/thrift/blob/master/doc/specs/thrift-binary-protocol.md>
+
+use std::collections::HashMap;
+
+const VERSION_1: u32 = 0x8001_0000;
+const VERSION_MASK: u32 = 0xffff_0000;
+
+pub(crate) const MESSAGE_CALL: u8 = 1;
+pub(crate) const MESSAGE_REPLY: u8 = 2;
+pub(crate) const MESSAGE_EXCEPTION: u8 = 3;
+
+const TYPE_STOP: u8 = 0;
+const TYPE_BOOL: u8 = 2;
+const TYPE_BYTE: u8 = 3;
+const TYPE_DOUBLE: u8 = 4;
+const TYPE_I16: u8 = 6;
+const TYPE_I32: u8 = 8;
+const TYPE_I64: u8 = 10;
+const TYPE_STRING: u8 = 11;
+const TYPE_STRUCT: u8 = 12;
+const TYPE_MAP: u8 = 13;
+const TYPE_SET: u8 = 14;
+const TYPE_LIST: u8 = 15;
+
+/// Max nesting depth of decoded values, to protect against malformed input.
+const MAX_DEPTH: usize = 64;
+
+/// A value of thrift.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum TValue {
+    Bool(bool),
+    Byte(i8),
+    Double(f64),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    Binary(Vec<u8>),
+    Struct(TStruct),
+    Map {
+        key_type: u8,
+        value_type: u8,
+        entries: Vec<(TValue, TValue)>,
+    },
+    Set {
+        element_type: u8,
+        elements: Vec<TValue>,
+    },
+    List {
+        element_type: u8,
+        elements: Vec<TValue>,
+    },
+}
+
+impl TValue {
+    pub(crate) fn string(s: impl Into<String>) -> Self {
+        TValue::Binary(s.into().into_bytes())
+    }
+
+    pub(crate) fn string_list(values: impl IntoIterator<Item = String>) -> Self {
+        TValue::List {
+            element_type: TYPE_STRING,
+            elements: values.into_iter().map(TValue::string).collect(),
+        }
+    }
+
+    pub(crate) fn struct_list(values: impl IntoIterator<Item = TStruct>) -> Self {
+        TValue::List {
+            element_type: TYPE_STRUCT,
+            elements: values.into_iter().map(TValue::Struct).collect(),
+        }
+    }
+
+    pub(crate) fn string_map(values: &HashMap<String, String>) -> Self {
+        TValue::Map {
+            key_type: TYPE_STRING,
+            value_type: TYPE_STRING,
+            entries: values
+                .iter()
+                .map(|(k, v)| (TValue::string(k.clone()), TValue::string(v.clone())))
+                .collect(),
+        }
+    }
+
+    fn type_id(&self) -> u8 {
+        match self {
+            TValue::Bool(_) => TYPE_BOOL,
+            TValue::Byte(_) => TYPE_BYTE,
+            TValue::Double(_) => TYPE_DOUBLE,
+            TValue::I16(_) => TYPE_I16,
+            TValue::I32(_) => TYPE_I32,
+            TValue::I64(_) => TYPE_I64,
+            TValue::Binary(_) => TYPE_STRING,
+            TValue::Struct(_) => TYPE_STRUCT,
+            TValue::Map { .. } => TYPE_MAP,
+            TValue::Set { .. } => TYPE_SET,
+            TValue::List { .. } => TYPE_LIST,
+        }
+    }
+
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        match self {
+            TValue::Binary(bytes) => std::str::from_utf8(bytes).ok(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn as_struct(&self) -> Option<&TStruct> {
+        match self {
+            TValue::Struct(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Get the strings of a list or set, non-string elements are skipped.
+    pub(crate) fn as_string_list(&self) -> Option<Vec<String>> {
+        match self {
+            TValue::List { elements, .. } | TValue::Set { elements, .. } => Some(
+                elements
+                    .iter()
+                    .filter_map(|e| e.as_str().map(|s| s.to_string()))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Get the entries of a map with string keys and values, other entries are skipped.
+    pub(crate) fn as_string_map(&self) -> Option<HashMap<String, String>> {
+        match self {
+            TValue::Map { entries, .. } => Some(
+                entries
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.as_str()?.to_string(), v.as_str()?.to_string())))
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+}
+
+/// A struct of thrift, fields are kept in the order of writing.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct TStruct {
+    fields: Vec<(i16, TValue)>,
+}
+
+impl TStruct {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the field with the given id, replacing the existing one.
+    pub(crate) fn field(mut self, id: i16, value: TValue) -> Self {
+        match self.fields.iter_mut().find(|(i, _)| *i == id) {
+            Some(field) => field.1 = value,
+            None => self.fields.push((id, value)),
+        }
+        self
+    }
+
+    pub(crate) fn get(&self, id: i16) -> Option<&TValue> {
+        self.fields.iter().find(|(i, _)| *i == id).map(|(_, v)| v)
+    }
+
+    pub(crate) fn get_str(&self, id: i16) -> Option<&str> {
+        self.get(id).and_then(|v| v.as_str())
+    }
+
+    pub(crate) fn fields(&self) -> &[(i16, TValue)] {
+        &self.fields
+    }
+}
+
+/// A message of thrift, the body is the arguments of a call or the result of a reply.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct TMessage {
+    pub(crate) name: String,
+    pub(crate) message_type: u8,
+    pub(crate) seq_id: i32,
+    pub(crate) body: TStruct,
+}
+
+impl TMessage {
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(VERSION_1 | self.message_type as u32).to_be_bytes());
+        write_binary(&mut buf, self.name.as_bytes());
+        buf.extend_from_slice(&self.seq_id.to_be_bytes());
+        write_struct(&mut buf, &self.body);
+        buf
+    }
+
+    /// Decode a message from the buffer, returns `Ok(None)` if the buffer doesn't contain a whole message yet.
+    pub(crate) fn decode(buf: &[u8]) -> Result<Option<TMessage>, String> {
+        let mut reader = Reader { buf, pos: 0 };
+        match reader.read_message() {
+            Ok(message) => Ok(Some(message)),
+            Err(DecodeError::Incomplete) => Ok(None),
+            Err(DecodeError::Invalid(message)) => Err(message),
+        }
+    }
+}
+
+fn write_binary(buf: &mut Vec<u8>, bytes: &[u8]) {
+    buf.extend_from_slice(&(bytes.len() as i32).to_be_bytes());
+    buf.extend_from_slice(bytes);
+}
+
+fn write_struct(buf: &mut Vec<u8>, s: &TStruct) {
+    for (id, value) in &s.fields {
+        buf.push(value.type_id());
+        buf.extend_from_slice(&id.to_be_bytes());
+        write_value(buf, value);
+    }
+    buf.push(TYPE_STOP);
+}
+
+fn write_value(buf: &mut Vec<u8>, value: &TValue) {
+    match value {
+        TValue::Bool(v) => buf.push(*v as u8),
+        TValue::Byte(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        TValue::Double(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        TValue::I16(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        TValue::I32(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        TValue::I64(v) => buf.extend_from_slice(&v.to_be_bytes()),
+        TValue::Binary(v) => write_binary(buf, v),
+        TValue::Struct(v) => write_struct(buf, v),
+        TValue::Map {
+            key_type,
+            value_type,
+            entries,
+        } => {
+            buf.push(*key_type);
+            buf.push(*value_type);
+            buf.extend_from_slice(&(entries.len() as i32).to_be_bytes());
+            for (k, v) in entries {
+                write_value(buf, k);
+                write_value(buf, v);
+            }
+        }
+        TValue::Set {
+            element_type,
+            elements,
+        }
+        | TValue::List {
+            element_type,
+            elements,
+        } => {
+            buf.push(*element_type);
+            buf.extend_from_slice(&(elements.len() as i32).to_be_bytes());
+            for e in elements {
+                write_value(buf, e);
+            }
+        }
+    }
+}
+
+enum DecodeError {
+    Incomplete,
+    Invalid(String),
+}
+
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn read_bytes(&mut self, len: usize) -> Result<&[u8], DecodeError> {
+        if self.buf.len() - self.pos < len {
+            return Err(DecodeError::Incomplete);
+        }
+        let bytes = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    fn read_size(&mut self) -> Result<usize, DecodeError> {
+        let size = i32::from_be_bytes(self.read_array()?);
+        usize::try_from(size).map_err(|_| DecodeError::Invalid(format!("negative size {size}")))
+    }
+
+    fn read_message(&mut self) -> Result<TMessage, DecodeError> {
+        let header = u32::from_be_bytes(self.read_array()?);
+        if header & VERSION_MASK != VERSION_1 {
+            return Err(DecodeError::Invalid(format!(
+                "unsupported thrift message header {header:#x}"
+            )));
+        }
+        let len = self.read_size()?;
+        let name = String::from_utf8_lossy(self.read_bytes(len)?).to_string();
+        let seq_id = i32::from_be_bytes(self.read_array()?);
+        let body = self.read_struct(0)?;
+        Ok(TMessage {
+            name,
+            message_type: (header & 0xff) as u8,
+            seq_id,
+            body,
+        })
+    }
+
+    fn read_struct(&mut self, depth: usize) -> Result<TStruct, DecodeError> {
+        let mut s = TStruct::new();
+        loop {
+            let [type_id] = self.read_array()?;
+            if type_id == TYPE_STOP {
+                return Ok(s);
+            }
+            let id = i16::from_be_bytes(self.read_array()?);
+            let value = self.read_value(type_id, depth + 1)?;
+            s.fields.push((id, value));
+        }
+    }
+
+    fn read_value(&mut self, type_id: u8, depth: usize) -> Result<TValue, DecodeError> {
+        if depth > MAX_DEPTH {
+            return Err(DecodeError::Invalid(
+                "thrift value is nested too deep".to_string(),
+            ));
+        }
+        let value = match type_id {
+            TYPE_BOOL => TValue::Bool(self.read_array::<1>()?[0] != 0),
+            TYPE_BYTE => TValue::Byte(i8::from_be_bytes(self.read_array()?)),
+            TYPE_DOUBLE => TValue::Double(f64::from_be_bytes(self.read_array()?)),
+            TYPE_I16 => TValue::I16(i16::from_be_bytes(self.read_array()?)),
+            TYPE_I32 => TValue::I32(i32::from_be_bytes(self.read_array()?)),
+            TYPE_I64 => TValue::I64(i64::from_be_bytes(self.read_array()?)),
+            TYPE_STRING => {
+                let len = self.read_size()?;
+                TValue::Binary(self.read_bytes(len)?.to_vec())
+            }
+            TYPE_STRUCT => TValue::Struct(self.read_struct(depth)?),
+            TYPE_MAP => {
+                let [key_type, value_type] = self.read_array()?;
+                let size = self.read_size()?;
+                let mut entries = Vec::new();
+                for _ in 0..size {
+                    let k = self.read_value(key_type, depth + 1)?;
+                    let v = self.read_value(value_type, depth + 1)?;
+                    entries.push((k, v));
+                }
+                TValue::Map {
+                    key_type,
+                    value_type,
+                    entries,
+                }
+            }
+            TYPE_SET | TYPE_LIST => {
+                let [element_type] = self.read_array()?;
+                let size = self.read_size()?;
+                let mut elements = Vec::new();
+                for _ in 0..size {
+                    elements.push(self.read_value(element_type, depth + 1)?);
+                }
+                if type_id == TYPE_SET {
+                    TValue::Set {
+                        element_type,
+                        elements,
+                    }
+                } else {
+                    TValue::List {
+                        element_type,
+                        elements,
+                    }
+                }
+            }
+            _ => {
+                return Err(DecodeError::Invalid(format!(
+                    "unknown thrift type {type_id}"
+                )))
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_round_trip() {
+        let message = TMessage {
+            name: "get_table".to_string(),
+            message_type: MESSAGE_CALL,
+            seq_id: 7,
+            body: TStruct::new()
+                .field(1, TValue::string("db"))
+                .field(2, TValue::I64(-1))
+                .field(
+                    3,
+                    TValue::string_map(&HashMap::from([("k".to_string(), "v".to_string())])),
+                )
+                .field(4, TValue::string_list(vec!["a".to_string()]))
+                .field(
+                    5,
+                    TValue::Struct(TStruct::new().field(1, TValue::Bool(true))),
+                ),
+        };
+        let bytes = message.encode();
+        assert_eq!(&bytes[..4], &[0x80, 0x01, 0x00, 0x01]);
+        for len in 0..bytes.len() {
+            assert_eq!(TMessage::decode(&bytes[..len]), Ok(None));
+        }
+        let decoded = TMessage::decode(&bytes).unwrap().unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(decoded.body.get_str(1), Some("db"));
+        assert_eq!(
+            decoded.body.get(3).unwrap().as_string_map().unwrap()["k"],
+            "v"
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_message() {
+        assert!(TMessage::decode(&[0, 0, 0, 1, 0, 0, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,468 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A catalog lock by the ephemeral nodes of zookeeper, with a minimal client of the zookeeper
+//! protocol, only creating and deleting nodes.
+//!
+//! Reference: <https://github.com/apache/zookeeper/blob/master/zookeeper-jute/src/main/resources/zookeeper.jute>
+
+use crate::catalog::{
+    acquire_with_retries, CatalogLock, DEFAULT_LOCK_ACQUIRE_TIMEOUT, DEFAULT_LOCK_CHECK_MAX_SLEEP,
+};
+use crate::error::*;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+const OP_CREATE: i32 = 1;
+const OP_DELETE: i32 = 2;
+const OP_PING: i32 = 11;
+const OP_CLOSE: i32 = -11;
+/// Transaction id of pings, and of the notifications of watches to skip.
+const XID_PING: i32 = -2;
+const XID_NOTIFICATION: i32 = -1;
+
+const ERROR_NO_NODE: i32 = -101;
+const ERROR_NODE_EXISTS: i32 = -110;
+
+const CREATE_PERSISTENT: i32 = 0;
+const CREATE_EPHEMERAL: i32 = 1;
+/// Permissions of all operations, granted to `world:anyone`.
+const PERMS_ALL: i32 = 31;
+
+/// Max length of the frames read, to protect against malformed input.
+const MAX_FRAME_LENGTH: usize = 16 * 1024 * 1024;
+
+/// A session of zookeeper over a connection, the ephemeral nodes created by the session are
+/// deleted by the server once the session is closed or expired.
+#[derive(Debug)]
+struct ZooKeeperSession {
+    address: String,
+    stream: TcpStream,
+    xid: i32,
+}
+
+impl ZooKeeperSession {
+    async fn connect(address: &str, session_timeout: Duration) -> crate::Result<Self> {
+        let stream = TcpStream::connect(address)
+            .await
+            .map_err(|e| io_error(address, e))?;
+        let mut session = Self {
+            address: address.to_string(),
+            stream,
+            xid: 0,
+        };
+        let mut request = Vec::new();
+        // protocol version, last zxid seen, timeout, session id and password
+        put_i32(&mut request, 0);
+        put_i64(&mut request, 0);
+        put_i32(&mut request, session_timeout.as_millis() as i32);
+        put_i64(&mut request, 0);
+        put_buffer(&mut request, &[0; 16]);
+        session.write_frame(&request).await?;
+        let response = session.read_frame().await?;
+        let session_id = response
+            .get(8..16)
+            .map(|id| i64::from_be_bytes(id.try_into().unwrap()));
+        if !matches!(session_id, Some(id) if id != 0) {
+            return CatalogUnexpectedSnafu {
+                message: format!("Zookeeper {address} refused to create a session"),
+            }
+            .fail();
+        }
+        Ok(session)
+    }
+
+    /// Send the request of the operation, returns the error code of the reply, 0 if succeeded.
+    async fn call(&mut self, op: i32, body: &[u8]) -> crate::Result<i32> {
+        let xid = if op == OP_PING {
+            XID_PING
+        } else {
+            self.xid += 1;
+            self.xid
+        };
+        let mut request = Vec::with_capacity(8 + body.len());
+        put_i32(&mut request, xid);
+        put_i32(&mut request, op);
+        request.extend_from_slice(body);
+        self.write_frame(&request).await?;
+        loop {
+            // the reply header of xid, zxid and error code
+            let reply = self.read_frame().await?;
+            let Some(header) = reply.get(..16) else {
+                return CatalogUnexpectedSnafu {
+                    message: format!("Zookeeper {} replied a truncated header", self.address),
+                }
+                .fail();
+            };
+            let reply_xid = i32::from_be_bytes(header[..4].try_into().unwrap());
+            if reply_xid == XID_NOTIFICATION {
+                continue;
+            }
+            if reply_xid != xid {
+                return CatalogUnexpectedSnafu {
+                    message: format!(
+                        "Zookeeper {} replied xid {reply_xid} for request of xid {xid}",
+                        self.address
+                    ),
+                }
+                .fail();
+            }
+            return Ok(i32::from_be_bytes(header[12..16].try_into().unwrap()));
+        }
+    }
+
+    async fn create(&mut self, path: &str, flags: i32) -> crate::Result<i32> {
+        let mut body = Vec::new();
+        put_string(&mut body, path);
+        put_buffer(&mut body, &[]);
+        // the acl of a single permission
+        put_i32(&mut body, 1);
+        put_i32(&mut body, PERMS_ALL);
+        put_string(&mut body, "world");
+        put_string(&mut body, "anyone");
+        put_i32(&mut body, flags);
+        self.call(OP_CREATE, &body).await
+    }
+
+    async fn delete(&mut self, path: &str) -> crate::Result<i32> {
+        let mut body = Vec::new();
+        put_string(&mut body, path);
+        // any version
+        put_i32(&mut body, -1);
+        self.call(OP_DELETE, &body).await
+    }
+
+    async fn write_frame(&mut self, payload: &[u8]) -> crate::Result<()> {
+        let mut frame = Vec::with_capacity(4 + payload.len());
+        put_i32(&mut frame, payload.len() as i32);
+        frame.extend_from_slice(payload);
+        self.stream
+            .write_all(&frame)
+            .await
+            .map_err(|e| io_error(&self.address, e))
+    }
+
+    async fn read_frame(&mut self) -> crate::Result<Vec<u8>> {
+        let length = self
+            .stream
+            .read_i32()
+            .await
+            .map_err(|e| io_error(&self.address, e))?;
+        if length < 0 || length as usize > MAX_FRAME_LENGTH {
+            return CatalogUnexpectedSnafu {
+                message: format!(
+                    "Zookeeper {} replied a frame of {length} bytes",
+                    self.address
+                ),
+            }
+            .fail();
+        }
+        let mut frame = vec![0; length as usize];
+        self.stream
+            .read_exact(&mut frame)
+            .await
+            .map_err(|e| io_error(&self.address, e))?;
+        Ok(frame)
+    }
+}
+
+fn io_error(address: &str, e: std::io::Error) -> crate::Error {
+    CatalogUnexpectedSnafu {
+        message: format!("Failed to talk to zookeeper {address}: {e}"),
+    }
+    .build()
+}
+
+fn zookeeper_error(operation: &str, path: &str, code: i32) -> crate::Error {
+    CatalogUnexpectedSnafu {
+        message: format!("Failed to {operation} zookeeper node {path}, error code {code}"),
+    }
+    .build()
+}
+
+fn put_i32(buf: &mut Vec<u8>, value: i32) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_i64(buf: &mut Vec<u8>, value: i64) {
+    buf.extend_from_slice(&value.to_be_bytes());
+}
+
+fn put_buffer(buf: &mut Vec<u8>, value: &[u8]) {
+    put_i32(buf, value.len() as i32);
+    buf.extend_from_slice(value);
+}
+
+fn put_string(buf: &mut Vec<u8>, value: &str) {
+    put_buffer(buf, value.as_bytes());
+}
+
+/// A lock of tables by the ephemeral nodes `{lock_path}/{database}.{table}` of zookeeper.
+///
+/// Each lock is held by a session of its own, which is kept alive by pings until the lock is
+/// released. Locks of writers that crashed are released once their sessions expire.
+#[derive(Debug)]
+pub struct ZooKeeperCatalogLock {
+    address: String,
+    lock_path: String,
+    session_timeout: Duration,
+    acquire_timeout: Duration,
+    check_max_sleep: Duration,
+    /// Sessions holding the locks, by the paths of the lock nodes.
+    sessions: Mutex<HashMap<String, Arc<Mutex<ZooKeeperSession>>>>,
+}
+
+impl ZooKeeperCatalogLock {
+    /// Default timeout of the sessions holding the locks.
+    pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+    /// Create the lock of the zookeeper of the address like `localhost:2181`, whose nodes are
+    /// under the lock path like `/paimon/locks`.
+    pub fn new(address: impl Into<String>, lock_path: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            lock_path: lock_path.into().trim_end_matches('/').to_string(),
+            session_timeout: Self::DEFAULT_SESSION_TIMEOUT,
+            acquire_timeout: DEFAULT_LOCK_ACQUIRE_TIMEOUT,
+            check_max_sleep: DEFAULT_LOCK_CHECK_MAX_SLEEP,
+            sessions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Set the timeout of the sessions, after which the locks of the sessions not alive are
+    /// released.
+    pub fn with_session_timeout(mut self, timeout: Duration) -> Self {
+        self.session_timeout = timeout;
+        self
+    }
+
+    /// Set the timeout of acquiring the lock.
+    pub fn with_acquire_timeout(mut self, timeout: Duration) -> Self {
+        self.acquire_timeout = timeout;
+        self
+    }
+
+    /// Set the max sleep between two attempts of acquiring the lock.
+    pub fn with_check_max_sleep(mut self, sleep: Duration) -> Self {
+        self.check_max_sleep = sleep;
+        self
+    }
+
+    fn node_path(&self, database: &str, table: &str) -> String {
+        format!("{}/{database}.{table}", self.lock_path)
+    }
+
+    /// Create the persistent nodes of the lock path if absent.
+    async fn create_parents(&self, session: &mut ZooKeeperSession) -> crate::Result<()> {
+        let mut path = String::new();
+        for part in self.lock_path.split('/').filter(|part| !part.is_empty()) {
+            path.push('/');
+            path.push_str(part);
+            match session.create(&path, CREATE_PERSISTENT).await? {
+                0 | ERROR_NODE_EXISTS => {}
+                code => return Err(zookeeper_error("create", &path, code)),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CatalogLock for ZooKeeperCatalogLock {
+    async fn acquire(&self, database: &str, table: &str) -> crate::Result<()> {
+        let path = self.node_path(database, table);
+        let mut session = ZooKeeperSession::connect(&self.address, self.session_timeout).await?;
+        self.create_parents(&mut session).await?;
+        let session = Arc::new(Mutex::new(session));
+        let acquired = acquire_with_retries(
+            &path,
+            self.acquire_timeout,
+            self.check_max_sleep,
+            || async {
+                match session.lock().await.create(&path, CREATE_EPHEMERAL).await? {
+                    0 => Ok(true),
+                    ERROR_NODE_EXISTS => Ok(false),
+                    code => Err(zookeeper_error("create", &path, code)),
+                }
+            },
+        )
+        .await;
+        if let Err(e) = acquired {
+            let _ = session.lock().await.call(OP_CLOSE, &[]).await;
+            return Err(e);
+        }
+
+        // ping the session until the lock is released and the session is dropped
+        let keep_alive = Arc::downgrade(&session);
+        let interval = self.session_timeout / 3;
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let Some(session) = keep_alive.upgrade() else {
+                    return;
+                };
+                if session.lock().await.call(OP_PING, &[]).await.is_err() {
+                    return;
+                }
+            }
+        });
+        self.sessions.lock().await.insert(path, session);
+        Ok(())
+    }
+
+    async fn release(&self, database: &str, table: &str) -> crate::Result<()> {
+        let path = self.node_path(database, table);
+        let Some(session) = self.sessions.lock().await.remove(&path) else {
+            return CatalogUnexpectedSnafu {
+                message: format!("Zookeeper lock {path} is not held"),
+            }
+            .fail();
+        };
+        let mut session = session.lock().await;
+        let deleted = session.delete(&path).await;
+        // the ephemeral node is deleted along with the session anyway
+        let _ = session.call(OP_CLOSE, &[]).await;
+        match deleted? {
+            0 | ERROR_NO_NODE => Ok(()),
+            code => Err(zookeeper_error("delete", &path, code)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use tokio::net::{TcpListener, TcpStream};
+
+    async fn read_frame(stream: &mut TcpStream) -> Option<Vec<u8>> {
+        let length = stream.read_i32().await.ok()?;
+        let mut frame = vec![0; length as usize];
+        stream.read_exact(&mut frame).await.ok()?;
+        Some(frame)
+    }
+
+    async fn write_frame(stream: &mut TcpStream, payload: &[u8]) {
+        let mut frame = Vec::new();
+        put_buffer(&mut frame, payload);
+        stream.write_all(&frame).await.unwrap();
+    }
+
+    fn get_string(buf: &[u8]) -> String {
+        let length = i32::from_be_bytes(buf[..4].try_into().unwrap()) as usize;
+        String::from_utf8(buf[4..4 + length].to_vec()).unwrap()
+    }
+
+    /// A fake zookeeper of nodes, deleting the ephemeral nodes of closed sessions.
+    async fn serve(listener: TcpListener) {
+        let nodes = Arc::new(Mutex::new(HashSet::<String>::new()));
+        let mut next_session = 1i64;
+        loop {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let nodes = nodes.clone();
+            let session_id = next_session;
+            next_session += 1;
+            tokio::spawn(async move {
+                read_frame(&mut stream).await.unwrap();
+                let mut response = Vec::new();
+                put_i32(&mut response, 0);
+                put_i32(&mut response, 30000);
+                put_i64(&mut response, session_id);
+                put_buffer(&mut response, &[0; 16]);
+                write_frame(&mut stream, &response).await;
+
+                let mut ephemerals = Vec::new();
+                while let Some(request) = read_frame(&mut stream).await {
+                    let xid = i32::from_be_bytes(request[..4].try_into().unwrap());
+                    let op = i32::from_be_bytes(request[4..8].try_into().unwrap());
+                    let code = match op {
+                        OP_CREATE => {
+                            let path = get_string(&request[8..]);
+                            let flags = i32::from_be_bytes(
+                                request[request.len() - 4..].try_into().unwrap(),
+                            );
+                            if nodes.lock().await.insert(path.clone()) {
+                                if flags == CREATE_EPHEMERAL {
+                                    ephemerals.push(path);
+                                }
+                                0
+                            } else {
+                                ERROR_NODE_EXISTS
+                            }
+                        }
+                        OP_DELETE => {
+                            let path = get_string(&request[8..]);
+                            if nodes.lock().await.remove(&path) {
+                                0
+                            } else {
+                                ERROR_NO_NODE
+                            }
+                        }
+                        _ => 0,
+                    };
+                    let mut reply = Vec::new();
+                    put_i32(&mut reply, xid);
+                    put_i64(&mut reply, 1);
+                    put_i32(&mut reply, code);
+                    write_frame(&mut stream, &reply).await;
+                    if op == OP_CLOSE {
+                        break;
+                    }
+                }
+                let mut nodes = nodes.lock().await;
+                for path in ephemerals {
+                    nodes.remove(&path);
+                }
+            });
+        }
+    }
+
+    #[tokio::test]
+    async fn test_zookeeper_catalog_lock() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        tokio::spawn(serve(listener));
+
+        let lock = ZooKeeperCatalogLock::new(address, "/paimon/locks")
+            .with_acquire_timeout(Duration::from_millis(300))
+            .with_check_max_sleep(Duration::from_millis(50));
+        lock.acquire("default", "t").await.unwrap();
+        // another table is locked independently
+        lock.acquire("default", "other").await.unwrap();
+
+        let err = lock.acquire("default", "t").await.unwrap_err();
+        assert!(err.to_string().contains("Failed to acquire"), "{err}");
+
+        lock.release("default", "t").await.unwrap();
+        lock.acquire("default", "t").await.unwrap();
+        lock.release("default", "t").await.unwrap();
+        lock.release("default", "other").await.unwrap();
+        assert!(lock.release("default", "t").await.is_err());
+
+        let lock: Arc<dyn CatalogLock> = Arc::new(lock);
+        let value = lock
+            .run_with_lock("default", "t", || async { Ok(1) })
+            .await
+            .unwrap();
+        assert_eq!(value, 1);
+    }
+}
@@ -0,0 +1,237 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::format::ArrowRecordBatchIter;
+use crate::io::{FileIO, FileRead};
+use crate::table::DeletionFile;
+use arrow_array::BooleanArray;
+use arrow_select::filter::filter_record_batch;
+use roaring::RoaringBitmap;
+
+/// Magic number at the beginning of serialized bitmap deletion vectors.
+pub const BITMAP_DELETION_VECTOR_MAGIC_NUMBER: i32 = 1581511376;
+
+/// Positions of the deleted rows of a data file, backed by a roaring bitmap.
+///
+/// A serialized deletion vector is the big endian magic number followed by the bitmap in the
+/// portable format of RoaringBitmap.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/deletionvectors/BitmapDeletionVector.java>
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeletionVector {
+    bitmap: RoaringBitmap,
+}
+
+impl DeletionVector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mark the row at the position as deleted, returns false if it is deleted already.
+    pub fn delete(&mut self, position: u32) -> bool {
+        self.bitmap.insert(position)
+    }
+
+    /// Mark the rows deleted by the other deletion vector as deleted too.
+    pub fn merge(&mut self, other: &DeletionVector) {
+        self.bitmap |= &other.bitmap;
+    }
+
+    /// Whether the row at the position is deleted.
+    pub fn is_deleted(&self, position: u32) -> bool {
+        self.bitmap.contains(position)
+    }
+
+    /// Whether no row is deleted.
+    pub fn is_empty(&self) -> bool {
+        self.bitmap.is_empty()
+    }
+
+    /// Get the number of the deleted rows.
+    pub fn cardinality(&self) -> u64 {
+        self.bitmap.len()
+    }
+
+    /// Serialize the deletion vector to bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + self.bitmap.serialized_size());
+        bytes.extend_from_slice(&BITMAP_DELETION_VECTOR_MAGIC_NUMBER.to_be_bytes());
+        self.bitmap
+            .serialize_into(&mut bytes)
+            .expect("write to vec never fails");
+        bytes
+    }
+
+    /// Deserialize the deletion vector from the bytes.
+    pub fn from_bytes(bytes: &[u8]) -> crate::Result<Self> {
+        let Some((magic_number, bitmap)) = bytes.split_first_chunk::<4>() else {
+            return DataCorruptedSnafu {
+                message: "Deletion vector is too short to contain the magic number".to_string(),
+            }
+            .fail();
+        };
+        let magic_number = i32::from_be_bytes(*magic_number);
+        if magic_number != BITMAP_DELETION_VECTOR_MAGIC_NUMBER {
+            return DataCorruptedSnafu {
+                message: format!("Invalid magic number {magic_number} of deletion vector"),
+            }
+            .fail();
+        }
+        let bitmap = RoaringBitmap::deserialize_from(bitmap).map_err(|e| Error::DataCorrupted {
+            message: format!("Invalid bitmap of deletion vector: {e}"),
+        })?;
+        Ok(Self { bitmap })
+    }
+
+    /// Read the deletion vector of the deletion file.
+    ///
+    /// The deletion vector is stored in the index file as its length, the serialized bytes
+    /// and the CRC32 checksum of the bytes.
+    pub async fn read(file_io: &dyn FileIO, deletion_file: &DeletionFile) -> crate::Result<Self> {
+        let (Ok(offset), Ok(length)) = (
+            u64::try_from(deletion_file.offset()),
+            usize::try_from(deletion_file.length()),
+        ) else {
+            return DataCorruptedSnafu {
+                message: format!("Invalid range of deletion file {deletion_file:?}"),
+            }
+            .fail();
+        };
+        let input = file_io.new_input(deletion_file.path())?;
+        let bytes = input
+            .reader()
+            .await?
+            .read(offset..offset + 8 + length as u64)
+            .await?;
+        if bytes.len() != 8 + length {
+            return DataCorruptedSnafu {
+                message: format!("Deletion file {deletion_file:?} is truncated"),
+            }
+            .fail();
+        }
+        let actual_length = i32::from_be_bytes(bytes[..4].try_into().unwrap());
+        if actual_length as i64 != deletion_file.length() {
+            return DataCorruptedSnafu {
+                message: format!(
+                    "Size not match, actual size: {actual_length}, expected size: {length}, file path: {}",
+                    deletion_file.path()
+                ),
+            }
+            .fail();
+        }
+        let body = &bytes[4..4 + length];
+        verify_checksum(body, &bytes[4 + length..], deletion_file.path())?;
+        Self::from_bytes(body)
+    }
+
+    /// Filter out the deleted rows of the record batches read from the data file in order.
+    pub fn apply(self, batches: ArrowRecordBatchIter) -> ArrowRecordBatchIter {
+        let mut position = 0u64;
+        Box::new(batches.map(move |batch| {
+            let batch = batch?;
+            let start = position;
+            position += batch.num_rows() as u64;
+            let predicate: BooleanArray = (start..position)
+                .map(|pos| Some(!u32::try_from(pos).is_ok_and(|pos| self.is_deleted(pos))))
+                .collect();
+            Ok(filter_record_batch(&batch, &predicate)?)
+        }))
+    }
+}
+
+impl From<RoaringBitmap> for DeletionVector {
+    /// Create the deletion vector of the positions of the deleted rows.
+    fn from(bitmap: RoaringBitmap) -> Self {
+        Self { bitmap }
+    }
+}
+
+/// Verify the big endian CRC32 checksum of the bytes.
+pub(crate) fn verify_checksum(bytes: &[u8], checksum: &[u8], path: &str) -> crate::Result<()> {
+    let expected = crc32fast::hash(bytes) as i32;
+    let actual = i32::from_be_bytes(checksum.try_into().map_err(|_| Error::DataCorrupted {
+        message: format!("Missing checksum of deletion vector in {path}"),
+    })?);
+    if actual != expected {
+        return DataCorruptedSnafu {
+            message: format!(
+                "Checksum not match, actual checksum: {actual}, expected checksum: {expected}, file path: {path}"
+            ),
+        }
+        .fail();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Array, Int32Array, RecordBatch};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_serialize_deletion_vector() {
+        let mut deletion_vector = DeletionVector::new();
+        assert!(deletion_vector.delete(1));
+        assert!(deletion_vector.delete(70000));
+        assert!(!deletion_vector.delete(1));
+        assert_eq!(deletion_vector.cardinality(), 2);
+
+        let bytes = deletion_vector.to_bytes();
+        assert_eq!(&bytes[..4], &[94, 67, 242, 208]);
+        let deserialized = DeletionVector::from_bytes(&bytes).unwrap();
+        assert_eq!(deserialized, deletion_vector);
+        assert!(deserialized.is_deleted(70000));
+        assert!(!deserialized.is_deleted(2));
+
+        assert!(matches!(
+            DeletionVector::from_bytes(&bytes[4..]),
+            Err(Error::DataCorrupted { .. })
+        ));
+    }
+
+    #[test]
+    fn test_apply_deletion_vector() {
+        let batch = |values: Vec<i32>| {
+            RecordBatch::try_from_iter([(
+                "id",
+                Arc::new(Int32Array::from(values)) as Arc<dyn Array>,
+            )])
+        };
+        let batches: ArrowRecordBatchIter = Box::new(
+            vec![batch(vec![0, 1, 2]), batch(vec![3, 4])]
+                .into_iter()
+                .map(|batch| Ok(batch?)),
+        );
+        let mut deletion_vector = DeletionVector::new();
+        deletion_vector.delete(1);
+        deletion_vector.delete(3);
+        deletion_vector.delete(4);
+
+        let batches = deletion_vector
+            .apply(batches)
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(
+            batches[0].column(0).as_ref(),
+            &Int32Array::from(vec![0, 2]) as &dyn Array
+        );
+        assert_eq!(batches[1].num_rows(), 0);
+    }
+}
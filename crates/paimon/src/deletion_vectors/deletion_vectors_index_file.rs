@@ -0,0 +1,191 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::deletion_vectors::deletion_vector::verify_checksum;
+use crate::deletion_vectors::DeletionVector;
+use crate::error::*;
+use crate::io::FileIO;
+use crate::spec::IndexFileMeta;
+use crate::utils::FileStorePathFactory;
+use bytes::Bytes;
+use indexmap::IndexMap;
+use std::sync::Arc;
+
+/// Index type of the index files of deletion vectors.
+pub const DELETION_VECTORS_INDEX: &str = "DELETION_VECTORS";
+
+const VERSION_ID_V1: u8 = 1;
+
+/// Index file of the deletion vectors of the data files in a bucket.
+///
+/// The file starts with the version byte, followed by the deletion vectors of the data files,
+/// each as its length, the serialized bytes and the CRC32 checksum of the bytes. The range of
+/// each deletion vector is recorded in [`IndexFileMeta::deletion_vectors_ranges`].
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/deletionvectors/DeletionVectorsIndexFile.java>
+#[derive(Debug, Clone)]
+pub struct DeletionVectorsIndexFile {
+    file_io: Arc<dyn FileIO>,
+    path_factory: FileStorePathFactory,
+}
+
+impl DeletionVectorsIndexFile {
+    pub fn new(file_io: Arc<dyn FileIO>, path_factory: FileStorePathFactory) -> Self {
+        Self {
+            file_io,
+            path_factory,
+        }
+    }
+
+    /// Read all the deletion vectors of the index file, keyed by the names of the data files.
+    pub async fn read_all(
+        &self,
+        file_meta: &IndexFileMeta,
+    ) -> crate::Result<IndexMap<String, DeletionVector>> {
+        let path = self.path_factory.index_path(&file_meta.file_name);
+        let bytes = self.file_io.new_input(&path)?.read().await?;
+        match bytes.first() {
+            Some(&VERSION_ID_V1) => {}
+            version => {
+                return DataCorruptedSnafu {
+                    message: format!(
+                        "Version not match, actual version: {version:?}, expected version: {VERSION_ID_V1}, file path: {path}"
+                    ),
+                }
+                .fail()
+            }
+        }
+
+        let mut deletion_vectors = IndexMap::new();
+        let Some(ranges) = &file_meta.deletion_vectors_ranges else {
+            return Ok(deletion_vectors);
+        };
+        for (data_file, (offset, length)) in ranges {
+            let (start, length) = (*offset as usize, *length as usize);
+            let Some(slice) = bytes.get(start..start + 8 + length) else {
+                return DataCorruptedSnafu {
+                    message: format!(
+                        "Deletion vector of {data_file} is out of the range of {path}"
+                    ),
+                }
+                .fail();
+            };
+            let actual_length = i32::from_be_bytes(slice[..4].try_into().unwrap());
+            if actual_length as usize != length {
+                return DataCorruptedSnafu {
+                    message: format!(
+                        "Size not match, actual size: {actual_length}, expected size: {length}, file path: {path}"
+                    ),
+                }
+                .fail();
+            }
+            let body = &slice[4..4 + length];
+            verify_checksum(body, &slice[4 + length..], &path)?;
+            deletion_vectors.insert(data_file.clone(), DeletionVector::from_bytes(body)?);
+        }
+        Ok(deletion_vectors)
+    }
+
+    /// Write the deletion vectors keyed by the names of the data files into a new index file.
+    pub async fn write(
+        &self,
+        deletion_vectors: &IndexMap<String, DeletionVector>,
+    ) -> crate::Result<IndexFileMeta> {
+        let mut bytes = vec![VERSION_ID_V1];
+        let mut ranges = IndexMap::with_capacity(deletion_vectors.len());
+        for (data_file, deletion_vector) in deletion_vectors {
+            let body = deletion_vector.to_bytes();
+            ranges.insert(data_file.clone(), (bytes.len() as i32, body.len() as i32));
+            bytes.extend_from_slice(&(body.len() as i32).to_be_bytes());
+            bytes.extend_from_slice(&body);
+            bytes.extend_from_slice(&(crc32fast::hash(&body) as i32).to_be_bytes());
+        }
+
+        let file_name = format!("index-{}-0", uuid::Uuid::new_v4());
+        let file_size = bytes.len() as i32;
+        self.file_io
+            .new_output(&self.path_factory.index_path(&file_name))?
+            .write(Bytes::from(bytes))
+            .await?;
+        Ok(IndexFileMeta {
+            index_type: DELETION_VECTORS_INDEX.to_string(),
+            file_name,
+            file_size,
+            row_count: deletion_vectors.len() as i32,
+            deletion_vectors_ranges: Some(ranges),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::RowType;
+    use crate::table::DeletionFile;
+
+    #[tokio::test]
+    async fn test_write_and_read_deletion_vectors() {
+        let root = "file:/tmp/test_write_and_read_deletion_vectors";
+        let file_io = FileIOBuilder::from_url(root).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{root}/")).await;
+        let path_factory = FileStorePathFactory::new(root, RowType::new(vec![]), "default");
+        let index_file = DeletionVectorsIndexFile::new(file_io.clone(), path_factory.clone());
+
+        let mut deletion_vectors = IndexMap::new();
+        for (data_file, positions) in [("f1.parquet", vec![0, 5]), ("f2.parquet", vec![3])] {
+            let mut deletion_vector = DeletionVector::new();
+            for position in positions {
+                deletion_vector.delete(position);
+            }
+            deletion_vectors.insert(data_file.to_string(), deletion_vector);
+        }
+
+        let file_meta = index_file.write(&deletion_vectors).await.unwrap();
+        assert_eq!(file_meta.index_type, DELETION_VECTORS_INDEX);
+        assert_eq!(file_meta.row_count, 2);
+        assert_eq!(
+            index_file.read_all(&file_meta).await.unwrap(),
+            deletion_vectors
+        );
+
+        let (offset, length) = file_meta.deletion_vectors_ranges.as_ref().unwrap()["f2.parquet"];
+        assert_eq!(
+            offset,
+            1 + 8 + deletion_vectors["f1.parquet"].to_bytes().len() as i32
+        );
+        let deletion_file = DeletionFile::new(
+            path_factory.index_path(&file_meta.file_name),
+            offset as i64,
+            length as i64,
+        );
+        assert_eq!(
+            DeletionVector::read(file_io.as_ref(), &deletion_file)
+                .await
+                .unwrap(),
+            deletion_vectors["f2.parquet"]
+        );
+
+        let corrupted = DeletionFile::new(deletion_file.path(), offset as i64, length as i64 - 1);
+        assert!(matches!(
+            DeletionVector::read(file_io.as_ref(), &corrupted).await,
+            Err(Error::DataCorrupted { .. })
+        ));
+
+        file_io.delete_dir(&format!("{root}/")).await.unwrap();
+    }
+}
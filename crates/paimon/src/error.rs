@@ -23,11 +23,13 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 /// Error type for paimon.
 #[derive(Debug, Snafu)]
 pub enum Error {
-    #[snafu(display("Paimon data invalid for {}: {:?}", message, source))]
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon data invalid for {}: {:?}", message, source)
+    )]
     DataInvalid {
         message: String,
-        #[snafu(backtrace)]
-        source: snafu::Whatever,
+        source: Box<dyn std::error::Error + Send + Sync>,
     },
     #[snafu(
         visibility(pub(crate)),
@@ -40,13 +42,36 @@ pub enum Error {
     )]
     IoUnexpected {
         message: String,
-        source: opendal::Error,
+        #[snafu(source(from(opendal::Error, Box::new)))]
+        source: Box<opendal::Error>,
     },
     #[snafu(
         visibility(pub(crate)),
         display("Paimon hitting unsupported io error {}", message)
     )]
     IoUnsupported { message: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display(
+            "Paimon memory pool exhausted: {} failed to reserve {} bytes, {} bytes available",
+            consumer,
+            requested,
+            available
+        )
+    )]
+    MemoryExhausted {
+        consumer: String,
+        requested: usize,
+        available: usize,
+    },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unexpected spill error {}: {:?}", message, source)
+    )]
+    SpillUnexpected {
+        message: String,
+        source: std::io::Error,
+    },
     #[snafu(
         visibility(pub(crate)),
         display("Paimon hitting invalid config: {}", message)
@@ -58,13 +83,219 @@ pub enum Error {
     )]
     DataUnexpected {
         message: String,
-        source: apache_avro::Error,
+        #[snafu(source(from(apache_avro::Error, Box::new)))]
+        source: Box<apache_avro::Error>,
     },
     #[snafu(
         visibility(pub(crate)),
         display("Paimon hitting invalid file index format: {}", message)
     )]
     FileIndexFormatInvalid { message: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting corrupted data: {}", message)
+    )]
+    DataCorrupted { message: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting invalid schema: {}", message)
+    )]
+    SchemaInvalid { message: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon catalog database {} does not exist", database)
+    )]
+    DatabaseNotExist { database: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon catalog database {} already exists", database)
+    )]
+    DatabaseAlreadyExist { database: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon catalog database {} is not empty", database)
+    )]
+    DatabaseNotEmpty { database: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon catalog table {} does not exist", identifier)
+    )]
+    TableNotExist { identifier: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon catalog table {} already exists", identifier)
+    )]
+    TableAlreadyExist { identifier: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon branch {} does not exist", branch)
+    )]
+    BranchNotExist { branch: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon branch {} already exists", branch)
+    )]
+    BranchAlreadyExist { branch: String },
+    #[snafu(visibility(pub(crate)), display("Paimon tag {} does not exist", tag))]
+    TagNotExist { tag: String },
+    #[snafu(visibility(pub(crate)), display("Paimon tag {} already exists", tag))]
+    TagAlreadyExist { tag: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unexpected catalog error: {}", message)
+    )]
+    CatalogUnexpected { message: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting column {} not exist", column)
+    )]
+    ColumnNotExist { column: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting column {} already exists", column)
+    )]
+    ColumnAlreadyExist { column: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unsupported version {} of {}", version, message)
+    )]
+    VersionUnsupported { message: String, version: i32 },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unexpected json error {}: {:?}", message, source)
+    )]
+    JsonUnexpected {
+        message: String,
+        source: serde_json::Error,
+    },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unexpected arrow error {}: {:?}", message, source)
+    )]
+    ArrowUnexpected {
+        message: String,
+        source: arrow_schema::ArrowError,
+    },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unexpected parquet error {}: {:?}", message, source)
+    )]
+    ParquetUnexpected {
+        message: String,
+        #[snafu(source(from(parquet::errors::ParquetError, Box::new)))]
+        source: Box<parquet::errors::ParquetError>,
+    },
+    #[cfg(feature = "format-orc")]
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unexpected orc error {}: {:?}", message, source)
+    )]
+    OrcUnexpected {
+        message: String,
+        #[snafu(source(from(orc_rust::error::OrcError, Box::new)))]
+        source: Box<orc_rust::error::OrcError>,
+    },
+    #[cfg(feature = "lookup-cache")]
+    #[snafu(
+        visibility(pub(crate)),
+        display(
+            "Paimon hitting unexpected lookup cache error {}: {:?}",
+            message,
+            source
+        )
+    )]
+    LookupCacheUnexpected {
+        message: String,
+        #[snafu(source(from(sqlx::Error, Box::new)))]
+        source: Box<sqlx::Error>,
+    },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unsupported file format {}", format)
+    )]
+    FileFormatUnsupported { format: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display(
+            "Paimon hitting unsupported compression {} of file format {}",
+            compression,
+            format
+        )
+    )]
+    FileCompressionUnsupported { format: String, compression: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting invalid write: {}", message)
+    )]
+    WriteInvalid { message: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting commit conflict: {}", message)
+    )]
+    CommitConflict { message: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unexpected compaction failure: {}", message)
+    )]
+    CompactionUnexpected { message: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display("Paimon hitting unexpected read failure: {}", message)
+    )]
+    ReadUnexpected { message: String },
+    #[snafu(
+        visibility(pub(crate)),
+        display(
+            "Paimon hitting expired snapshot {} to read, the earliest snapshot is {}",
+            snapshot_id,
+            earliest
+        )
+    )]
+    SnapshotExpired { snapshot_id: i64, earliest: i64 },
+}
+
+/// The category of an [`Error`], telling callers how to react to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// A transient failure of the storage like throttles or timeouts, which may succeed
+    /// when retried later.
+    Retryable,
+    /// The data read is corrupted, retrying won't help.
+    Corrupted,
+    /// A concurrent change conflicts with the operation, which may be redone against the
+    /// latest state.
+    Conflict,
+    /// The operation, format or version is not supported.
+    Unsupported,
+    /// Any other failure, retrying won't help.
+    Permanent,
+}
+
+impl Error {
+    /// The category of this error.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Error::IoUnexpected { source, .. }
+                if source.is_temporary() || source.kind() == opendal::ErrorKind::RateLimited =>
+            {
+                ErrorCategory::Retryable
+            }
+            Error::DataCorrupted { .. } | Error::FileIndexFormatInvalid { .. } => {
+                ErrorCategory::Corrupted
+            }
+            Error::CommitConflict { .. } => ErrorCategory::Conflict,
+            Error::IoUnsupported { .. }
+            | Error::VersionUnsupported { .. }
+            | Error::FileFormatUnsupported { .. }
+            | Error::FileCompressionUnsupported { .. } => ErrorCategory::Unsupported,
+            _ => ErrorCategory::Permanent,
+        }
+    }
+
+    /// Whether this error is a transient failure which may succeed when retried.
+    pub fn is_retryable(&self) -> bool {
+        self.category() == ErrorCategory::Retryable
+    }
 }
 
 impl From<opendal::Error> for Error {
@@ -72,7 +303,7 @@ impl From<opendal::Error> for Error {
         // TODO: Simple use IoUnexpected for now
         Error::IoUnexpected {
             message: "IO operation failed on underlying storage".to_string(),
-            source,
+            source: Box::new(source),
         }
     }
 }
@@ -80,8 +311,102 @@ impl From<opendal::Error> for Error {
 impl From<apache_avro::Error> for Error {
     fn from(source: apache_avro::Error) -> Self {
         Error::DataUnexpected {
+            message: "".to_string(),
+            source: Box::new(source),
+        }
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(source: serde_json::Error) -> Self {
+        Error::JsonUnexpected {
             message: "".to_string(),
             source,
         }
     }
 }
+
+impl From<arrow_schema::ArrowError> for Error {
+    fn from(source: arrow_schema::ArrowError) -> Self {
+        Error::ArrowUnexpected {
+            message: "".to_string(),
+            source,
+        }
+    }
+}
+
+impl From<parquet::errors::ParquetError> for Error {
+    fn from(source: parquet::errors::ParquetError) -> Self {
+        Error::ParquetUnexpected {
+            message: "".to_string(),
+            source: Box::new(source),
+        }
+    }
+}
+
+#[cfg(feature = "format-orc")]
+impl From<orc_rust::error::OrcError> for Error {
+    fn from(source: orc_rust::error::OrcError) -> Self {
+        Error::OrcUnexpected {
+            message: "".to_string(),
+            source: Box::new(source),
+        }
+    }
+}
+
+#[cfg(feature = "lookup-cache")]
+impl From<sqlx::Error> for Error {
+    fn from(source: sqlx::Error) -> Self {
+        Error::LookupCacheUnexpected {
+            message: "".to_string(),
+            source: Box::new(source),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_category() {
+        let io_error = |e: opendal::Error| Error::from(e);
+        assert_eq!(
+            io_error(
+                opendal::Error::new(opendal::ErrorKind::Unexpected, "timeout").set_temporary()
+            )
+            .category(),
+            ErrorCategory::Retryable
+        );
+        assert!(io_error(opendal::Error::new(
+            opendal::ErrorKind::RateLimited,
+            "slow down"
+        ))
+        .is_retryable());
+        assert_eq!(
+            io_error(opendal::Error::new(opendal::ErrorKind::NotFound, "missing")).category(),
+            ErrorCategory::Permanent
+        );
+        assert_eq!(
+            Error::DataCorrupted {
+                message: "bad crc".to_string()
+            }
+            .category(),
+            ErrorCategory::Corrupted
+        );
+        assert_eq!(
+            Error::CommitConflict {
+                message: "overlap".to_string()
+            }
+            .category(),
+            ErrorCategory::Conflict
+        );
+        assert_eq!(
+            Error::FileFormatUnsupported {
+                format: "avro".to_string()
+            }
+            .category(),
+            ErrorCategory::Unsupported
+        );
+    }
+}
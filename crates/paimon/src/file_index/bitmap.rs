@@ -0,0 +1,404 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::{ConfigInvalidSnafu, FileIndexFormatInvalidSnafu, VersionUnsupportedSnafu};
+use crate::file_index::{FileIndexReader, FileIndexResult, FileIndexWriter};
+use crate::format::to_datum;
+use crate::spec::{DataType, Datum};
+use arrow_array::Array;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use indexmap::IndexMap;
+use roaring::RoaringBitmap;
+use std::collections::HashMap;
+
+/// Name of the bitmap index in file index files.
+pub const BITMAP: &str = "bitmap";
+
+const VERSION_1: u8 = 1;
+
+/// Whether the values of the type can be indexed by bitmaps.
+pub(crate) fn is_bitmap_indexable(data_type: &DataType) -> bool {
+    !matches!(
+        data_type,
+        DataType::Decimal(_)
+            | DataType::Array(_)
+            | DataType::Map(_)
+            | DataType::Multiset(_)
+            | DataType::Row(_)
+    )
+}
+
+/// Serialize the value as the identifier of its bitmap, in the big endian layout of the data
+/// output of Java.
+pub(crate) fn serialize_value(value: &Datum, bytes: &mut BytesMut) -> crate::Result<()> {
+    match value {
+        Datum::Boolean(v) => bytes.put_u8(*v as u8),
+        Datum::TinyInt(v) => bytes.put_i8(*v),
+        Datum::SmallInt(v) => bytes.put_i16(*v),
+        Datum::Int(v) | Datum::Date(v) | Datum::Time(v) => bytes.put_i32(*v),
+        Datum::BigInt(v) => bytes.put_i64(*v),
+        Datum::Float(v) => bytes.put_f32(*v),
+        Datum::Double(v) => bytes.put_f64(*v),
+        Datum::String(v) => {
+            bytes.put_i32(v.len() as i32);
+            bytes.put_slice(v.as_bytes());
+        }
+        Datum::Bytes(v) => {
+            bytes.put_i32(v.len() as i32);
+            bytes.put_slice(v);
+        }
+        Datum::Timestamp { millis, .. } | Datum::LocalZonedTimestamp { millis, .. } => {
+            bytes.put_i64(*millis)
+        }
+        Datum::Decimal { .. } => {
+            return ConfigInvalidSnafu {
+                message: "bitmap index does not support decimals".to_string(),
+            }
+            .fail()
+        }
+    }
+    Ok(())
+}
+
+/// Writer of the bitmap index of a column, the bitmaps of the rows of each distinct value and
+/// of the null values.
+///
+/// ```text
+/// +-------------------------------------------------+-----------------
+/// | version (1 byte)                                |
+/// +-------------------------------------------------+
+/// | row count (4 bytes int)                         |
+/// +-------------------------------------------------+
+/// | non-null value bitmap number (4 bytes int)      |
+/// +-------------------------------------------------+
+/// | has null value (1 byte)                         |
+/// +-------------------------------------------------+
+/// | null value offset (4 bytes if has null value)   |       HEAD
+/// +-------------------------------------------------+
+/// | value 1 | offset 1                              |
+/// +-------------------------------------------------+
+/// | value 2 | offset 2                              |
+/// +-------------------------------------------------+
+/// | ...                                             |
+/// +-------------------------------------------------+-----------------
+/// | serialized bitmap 1                             |
+/// +-------------------------------------------------+
+/// | serialized bitmap 2                             |       BODY
+/// +-------------------------------------------------+
+/// | ...                                             |
+/// +-------------------------------------------------+-----------------
+/// ```
+///
+/// Offsets are relative to the body, the bitmap of a single row isn't in the body and its
+/// offset is `-1 - row`.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-common/src/main/java/org/apache/paimon/fileindex/bitmap/BitmapFileIndex.java>
+#[derive(Debug, Clone)]
+pub struct BitmapFileIndexWriter {
+    data_type: DataType,
+    row_count: u32,
+    null_bitmap: RoaringBitmap,
+    bitmaps: IndexMap<Vec<u8>, RoaringBitmap>,
+}
+
+impl BitmapFileIndexWriter {
+    pub fn new(data_type: DataType) -> crate::Result<Self> {
+        if !is_bitmap_indexable(&data_type) {
+            return ConfigInvalidSnafu {
+                message: format!("bitmap index does not support type {data_type}"),
+            }
+            .fail();
+        }
+        Ok(Self {
+            data_type,
+            row_count: 0,
+            null_bitmap: RoaringBitmap::new(),
+            bitmaps: IndexMap::new(),
+        })
+    }
+}
+
+/// Offset of the bitmap in the head, the negative row if there is a single row.
+fn offset(bitmap: &RoaringBitmap, body: &mut Vec<u8>) -> i32 {
+    if bitmap.len() == 1 {
+        return -1 - bitmap.min().unwrap_or_default() as i32;
+    }
+    let offset = body.len() as i32;
+    bitmap
+        .serialize_into(body)
+        .expect("write to vec never fails");
+    offset
+}
+
+impl FileIndexWriter for BitmapFileIndexWriter {
+    fn write(&mut self, column: &dyn Array) -> crate::Result<()> {
+        for row in 0..column.len() {
+            match to_datum(column, row, &self.data_type)? {
+                Some(value) => {
+                    let mut bytes = BytesMut::new();
+                    serialize_value(&value, &mut bytes)?;
+                    self.bitmaps
+                        .entry(bytes.to_vec())
+                        .or_default()
+                        .insert(self.row_count);
+                }
+                None => {
+                    self.null_bitmap.insert(self.row_count);
+                }
+            }
+            self.row_count += 1;
+        }
+        Ok(())
+    }
+
+    fn serialized_bytes(&self) -> crate::Result<Bytes> {
+        let mut body = Vec::new();
+        let mut head = BytesMut::new();
+        head.put_u8(VERSION_1);
+        head.put_i32(self.row_count as i32);
+        head.put_i32(self.bitmaps.len() as i32);
+        head.put_u8(!self.null_bitmap.is_empty() as u8);
+        if !self.null_bitmap.is_empty() {
+            head.put_i32(offset(&self.null_bitmap, &mut body));
+        }
+        for (value, bitmap) in &self.bitmaps {
+            head.put_slice(value);
+            head.put_i32(offset(bitmap, &mut body));
+        }
+        head.extend_from_slice(&body);
+        Ok(head.freeze())
+    }
+}
+
+/// Whether the literal is of the type of the column, a value serialized as its bitmap identifier.
+fn is_type_of(value: &Datum, data_type: &DataType) -> bool {
+    matches!(
+        (value, data_type),
+        (Datum::Boolean(_), DataType::Boolean(_))
+            | (Datum::TinyInt(_), DataType::TinyInt(_))
+            | (Datum::SmallInt(_), DataType::SmallInt(_))
+            | (Datum::Int(_), DataType::Int(_))
+            | (Datum::BigInt(_), DataType::BigInt(_))
+            | (Datum::Float(_), DataType::Float(_))
+            | (Datum::Double(_), DataType::Double(_))
+            | (Datum::String(_), DataType::Char(_) | DataType::VarChar(_))
+            | (
+                Datum::Bytes(_),
+                DataType::Binary(_) | DataType::VarBinary(_)
+            )
+            | (Datum::Date(_), DataType::Date(_))
+            | (Datum::Time(_), DataType::Time(_))
+            | (Datum::Timestamp { .. }, DataType::Timestamp(_))
+            | (
+                Datum::LocalZonedTimestamp { .. },
+                DataType::LocalZonedTimestamp(_)
+            )
+    )
+}
+
+/// Length of the serialized value at the head of the bytes.
+fn value_length(data_type: &DataType, bytes: &[u8]) -> crate::Result<usize> {
+    let length = match data_type {
+        DataType::Boolean(_) | DataType::TinyInt(_) => 1,
+        DataType::SmallInt(_) => 2,
+        DataType::Int(_) | DataType::Date(_) | DataType::Time(_) | DataType::Float(_) => 4,
+        DataType::BigInt(_)
+        | DataType::Double(_)
+        | DataType::Timestamp(_)
+        | DataType::LocalZonedTimestamp(_) => 8,
+        DataType::Char(_) | DataType::VarChar(_) | DataType::Binary(_) | DataType::VarBinary(_) => {
+            if bytes.len() < 4 {
+                return FileIndexFormatInvalidSnafu {
+                    message: "bitmap index is truncated".to_string(),
+                }
+                .fail();
+            }
+            4 + (&bytes[..4]).get_i32() as usize
+        }
+        _ => {
+            return ConfigInvalidSnafu {
+                message: format!("bitmap index does not support type {data_type}"),
+            }
+            .fail()
+        }
+    };
+    if bytes.len() < length {
+        return FileIndexFormatInvalidSnafu {
+            message: "bitmap index is truncated".to_string(),
+        }
+        .fail();
+    }
+    Ok(length)
+}
+
+/// Reader of the bitmap index of a column, selecting the rows of the file by the bitmaps of
+/// the values of predicates.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-common/src/main/java/org/apache/paimon/fileindex/bitmap/BitmapFileIndex.java>
+pub struct BitmapFileIndexReader {
+    data_type: DataType,
+    null_offset: Option<i32>,
+    offsets: HashMap<Vec<u8>, i32>,
+    body: Bytes,
+}
+
+impl BitmapFileIndexReader {
+    pub fn new(data_type: DataType, mut bytes: Bytes) -> crate::Result<Self> {
+        let truncated = || {
+            FileIndexFormatInvalidSnafu {
+                message: "bitmap index is truncated".to_string(),
+            }
+            .build()
+        };
+        if bytes.remaining() < 10 {
+            return Err(truncated());
+        }
+        let version = bytes.get_u8();
+        if version != VERSION_1 {
+            return VersionUnsupportedSnafu {
+                message: "bitmap index".to_string(),
+                version: version as i32,
+            }
+            .fail();
+        }
+        let _row_count = bytes.get_i32();
+        let bitmap_count = bytes.get_i32();
+        let null_offset = if bytes.get_u8() == 1 {
+            if bytes.remaining() < 4 {
+                return Err(truncated());
+            }
+            Some(bytes.get_i32())
+        } else {
+            None
+        };
+        let mut offsets = HashMap::with_capacity(bitmap_count.max(0) as usize);
+        for _ in 0..bitmap_count {
+            let length = value_length(&data_type, &bytes)?;
+            let value = bytes.split_to(length).to_vec();
+            if bytes.remaining() < 4 {
+                return Err(truncated());
+            }
+            offsets.insert(value, bytes.get_i32());
+        }
+        Ok(Self {
+            data_type,
+            null_offset,
+            offsets,
+            body: bytes,
+        })
+    }
+
+    fn bitmap(&self, offset: i32) -> FileIndexResult {
+        if offset < 0 {
+            return FileIndexResult::Bitmap(RoaringBitmap::from_iter([(-1 - offset) as u32]));
+        }
+        match self
+            .body
+            .get(offset as usize..)
+            .and_then(|bytes| RoaringBitmap::deserialize_from(bytes).ok())
+        {
+            Some(bitmap) => FileIndexResult::Bitmap(bitmap),
+            // rows of corrupted bitmaps can't be selected, keep all of them
+            None => FileIndexResult::Remain,
+        }
+    }
+}
+
+impl FileIndexReader for BitmapFileIndexReader {
+    fn visit_equal(&self, literal: &Datum) -> FileIndexResult {
+        if !is_type_of(literal, &self.data_type) {
+            return FileIndexResult::Remain;
+        }
+        let mut value = BytesMut::new();
+        if serialize_value(literal, &mut value).is_err() {
+            return FileIndexResult::Remain;
+        }
+        match self.offsets.get(value.as_ref()) {
+            Some(offset) => self.bitmap(*offset),
+            None => FileIndexResult::Skip,
+        }
+    }
+
+    fn visit_is_null(&self) -> FileIndexResult {
+        match self.null_offset {
+            Some(offset) => self.bitmap(offset),
+            None => FileIndexResult::Skip,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, StringArray};
+
+    #[test]
+    fn test_write_bitmap_index() {
+        let mut writer = BitmapFileIndexWriter::new("INT".parse().unwrap()).unwrap();
+        writer
+            .write(&Int32Array::from(vec![Some(1), Some(2), Some(1)]))
+            .unwrap();
+        writer.write(&Int32Array::from(vec![None])).unwrap();
+        let bytes = writer.serialized_bytes().unwrap();
+
+        let mut head = vec![VERSION_1];
+        head.extend_from_slice(&4_i32.to_be_bytes());
+        head.extend_from_slice(&2_i32.to_be_bytes());
+        // the single null row and the single row of value 2 are inlined as negative offsets
+        head.push(1);
+        head.extend_from_slice(&(-4_i32).to_be_bytes());
+        head.extend_from_slice(&1_i32.to_be_bytes());
+        head.extend_from_slice(&0_i32.to_be_bytes());
+        head.extend_from_slice(&2_i32.to_be_bytes());
+        head.extend_from_slice(&(-2_i32).to_be_bytes());
+        assert_eq!(&bytes[..head.len()], head.as_slice());
+        let bitmap = RoaringBitmap::deserialize_from(&bytes[head.len()..]).unwrap();
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![0, 2]);
+
+        assert!(BitmapFileIndexWriter::new("DECIMAL(10, 2)".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_read_bitmap_index() {
+        let data_type: DataType = "STRING".parse().unwrap();
+        let mut writer = BitmapFileIndexWriter::new(data_type.clone()).unwrap();
+        writer
+            .write(&StringArray::from(vec![
+                Some("a"),
+                Some("b"),
+                None,
+                Some("a"),
+                Some("c"),
+                Some("a"),
+            ]))
+            .unwrap();
+        let reader =
+            BitmapFileIndexReader::new(data_type, writer.serialized_bytes().unwrap()).unwrap();
+
+        let rows = |rows: &[u32]| FileIndexResult::Bitmap(rows.iter().copied().collect());
+        let string = |v: &str| Datum::String(v.to_string());
+        assert_eq!(reader.visit_equal(&string("a")), rows(&[0, 3, 5]));
+        assert_eq!(reader.visit_equal(&string("c")), rows(&[4]));
+        assert_eq!(reader.visit_equal(&string("d")), FileIndexResult::Skip);
+        assert_eq!(
+            reader.visit_in(&[string("b"), string("c"), string("d")]),
+            rows(&[1, 4])
+        );
+        assert_eq!(reader.visit_is_null(), rows(&[2]));
+        assert_eq!(reader.visit_equal(&Datum::Int(1)), FileIndexResult::Remain);
+    }
+}
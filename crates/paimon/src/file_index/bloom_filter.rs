@@ -0,0 +1,268 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::{ConfigInvalidSnafu, FileIndexFormatInvalidSnafu};
+use crate::file_index::{FileIndexReader, FileIndexResult, FileIndexWriter};
+use crate::format::to_datum;
+use crate::spec::{DataType, Datum};
+use arrow_array::Array;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use twox_hash::XxHash64;
+
+/// Name of the bloom filter index in file index files.
+pub const BLOOM_FILTER: &str = "bloom-filter";
+
+/// Bloom filter of 64 bits hashes, each hash sets `num_hash_functions` bits derived from its
+/// upper and lower 32 bits.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/utils/BloomFilter64.java>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter64 {
+    num_hash_functions: i32,
+    bit_set: Vec<u8>,
+}
+
+impl BloomFilter64 {
+    /// Create an empty bloom filter of the expected number of items and false positive rate.
+    pub fn new(items: u64, fpp: f64) -> Self {
+        let ln2 = std::f64::consts::LN_2;
+        let items = items.max(1) as f64;
+        let num_bits = (-items * fpp.ln() / (ln2 * ln2)) as i32;
+        let num_bits = num_bits + (8 - num_bits % 8);
+        let num_hash_functions = ((num_bits as f64 / items * ln2).round() as i32).max(1);
+        Self {
+            num_hash_functions,
+            bit_set: vec![0; num_bits as usize / 8],
+        }
+    }
+
+    /// Deserialize the bloom filter from the big endian number of hash functions followed by
+    /// the bit set.
+    pub fn from_bytes(mut bytes: Bytes) -> crate::Result<Self> {
+        if bytes.len() <= 4 {
+            return FileIndexFormatInvalidSnafu {
+                message: format!("bloom filter of {} bytes is too short", bytes.len()),
+            }
+            .fail();
+        }
+        let num_hash_functions = bytes.get_i32();
+        Ok(Self {
+            num_hash_functions,
+            bit_set: bytes.to_vec(),
+        })
+    }
+
+    /// Serialize the bloom filter, which is the reverse of [`Self::from_bytes`].
+    pub fn to_bytes(&self) -> Bytes {
+        let mut bytes = BytesMut::with_capacity(4 + self.bit_set.len());
+        bytes.put_i32(self.num_hash_functions);
+        bytes.put_slice(&self.bit_set);
+        bytes.freeze()
+    }
+
+    /// Positions of the bits of the hash.
+    fn positions(&self, hash: u64) -> impl Iterator<Item = usize> {
+        let num_bits = (self.bit_set.len() * 8) as i32;
+        let hash1 = hash as i32;
+        let hash2 = (hash >> 32) as i32;
+        (1..=self.num_hash_functions).map(move |i| {
+            let combined = hash1.wrapping_add(i.wrapping_mul(hash2));
+            // flip all the bits of negative hashes
+            let combined = if combined < 0 { !combined } else { combined };
+            (combined % num_bits) as usize
+        })
+    }
+
+    pub fn add_hash(&mut self, hash: u64) {
+        for pos in self.positions(hash).collect::<Vec<_>>() {
+            self.bit_set[pos >> 3] |= 1 << (pos & 7);
+        }
+    }
+
+    /// Whether the hash may have been added, false means it is definitely not.
+    pub fn test_hash(&self, hash: u64) -> bool {
+        self.positions(hash)
+            .all(|pos| self.bit_set[pos >> 3] & (1 << (pos & 7)) != 0)
+    }
+}
+
+/// Hash the value into 64 bits as paimon-java does for bloom filters, none if the type of the
+/// value isn't supported.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fileindex/bloomfilter/FastHash.java>
+pub fn fast_hash(value: &Datum) -> Option<u64> {
+    let hash = match value {
+        Datum::Boolean(_) => return None,
+        Datum::TinyInt(v) => long_hash(*v as i64),
+        Datum::SmallInt(v) => long_hash(*v as i64),
+        Datum::Int(v) | Datum::Date(v) | Datum::Time(v) => long_hash(*v as i64),
+        Datum::BigInt(v) => long_hash(*v),
+        Datum::Float(v) => long_hash(v.to_bits() as i32 as i64),
+        Datum::Double(v) => long_hash(v.to_bits() as i64),
+        Datum::String(v) => XxHash64::oneshot(0, v.as_bytes()),
+        Datum::Bytes(v) => XxHash64::oneshot(0, v),
+        Datum::Timestamp { millis, .. } | Datum::LocalZonedTimestamp { millis, .. } => {
+            long_hash(*millis)
+        }
+        Datum::Decimal {
+            unscaled,
+            precision,
+            ..
+        } => {
+            if *precision <= 18 {
+                long_hash(*unscaled as i64)
+            } else {
+                XxHash64::oneshot(0, &unscaled_bytes(*unscaled))
+            }
+        }
+    };
+    Some(hash)
+}
+
+/// Thomas Wang's integer hash function.
+fn long_hash(key: i64) -> u64 {
+    let mut key = key as u64;
+    key = (!key).wrapping_add(key << 21);
+    key ^= key >> 24;
+    key = key.wrapping_add(key << 3).wrapping_add(key << 8);
+    key ^= key >> 14;
+    key = key.wrapping_add(key << 2).wrapping_add(key << 4);
+    key ^= key >> 28;
+    key.wrapping_add(key << 31)
+}
+
+/// The minimal big endian two's complement bytes of the unscaled value, as
+/// `BigInteger#toByteArray` of Java.
+fn unscaled_bytes(unscaled: i128) -> Vec<u8> {
+    let bytes = unscaled.to_be_bytes();
+    let sign = if unscaled < 0 { 0xff } else { 0 };
+    // a leading byte is redundant if it is the sign and the next byte has the same sign bit
+    let start = (0..15)
+        .find(|&i| bytes[i] != sign || (bytes[i + 1] & 0x80) != (sign & 0x80))
+        .unwrap_or(15);
+    bytes[start..].to_vec()
+}
+
+/// Whether the values of the type can be hashed by [`fast_hash`].
+pub(crate) fn is_hashable(data_type: &DataType) -> bool {
+    !matches!(
+        data_type,
+        DataType::Boolean(_)
+            | DataType::Array(_)
+            | DataType::Map(_)
+            | DataType::Multiset(_)
+            | DataType::Row(_)
+    )
+}
+
+/// Writer of the bloom filter index of a column, null values are not added.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fileindex/bloomfilter/BloomFilterFileIndex.java>
+#[derive(Debug, Clone)]
+pub struct BloomFilterFileIndexWriter {
+    data_type: DataType,
+    filter: BloomFilter64,
+}
+
+impl BloomFilterFileIndexWriter {
+    /// Create the writer of the expected number of items and false positive rate, which are
+    /// 1000000 and 0.1 by default.
+    pub fn new(data_type: DataType, items: Option<u64>, fpp: Option<f64>) -> crate::Result<Self> {
+        if !is_hashable(&data_type) {
+            return ConfigInvalidSnafu {
+                message: format!("bloom filter index does not support type {data_type}"),
+            }
+            .fail();
+        }
+        Ok(Self {
+            data_type,
+            filter: BloomFilter64::new(items.unwrap_or(1_000_000), fpp.unwrap_or(0.1)),
+        })
+    }
+}
+
+impl FileIndexWriter for BloomFilterFileIndexWriter {
+    fn write(&mut self, column: &dyn Array) -> crate::Result<()> {
+        for row in 0..column.len() {
+            if let Some(hash) = to_datum(column, row, &self.data_type)?
+                .as_ref()
+                .and_then(fast_hash)
+            {
+                self.filter.add_hash(hash);
+            }
+        }
+        Ok(())
+    }
+
+    fn serialized_bytes(&self) -> crate::Result<Bytes> {
+        Ok(self.filter.to_bytes())
+    }
+}
+
+/// Reader of the bloom filter index of a column.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fileindex/bloomfilter/BloomFilterFileIndex.java>
+#[derive(Debug, Clone)]
+pub struct BloomFilterFileIndexReader {
+    filter: BloomFilter64,
+}
+
+impl BloomFilterFileIndexReader {
+    pub fn new(bytes: Bytes) -> crate::Result<Self> {
+        Ok(Self {
+            filter: BloomFilter64::from_bytes(bytes)?,
+        })
+    }
+}
+
+impl FileIndexReader for BloomFilterFileIndexReader {
+    fn visit_equal(&self, literal: &Datum) -> FileIndexResult {
+        match fast_hash(literal) {
+            Some(hash) if !self.filter.test_hash(hash) => FileIndexResult::Skip,
+            _ => FileIndexResult::Remain,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bloom_filter() {
+        let mut filter = BloomFilter64::new(200, 0.01);
+        for i in 0..100 {
+            filter.add_hash(fast_hash(&Datum::Int(i)).unwrap());
+            filter.add_hash(fast_hash(&Datum::String(format!("s-{i}"))).unwrap());
+        }
+        let filter = BloomFilter64::from_bytes(filter.to_bytes()).unwrap();
+        for i in 0..100 {
+            assert!(filter.test_hash(fast_hash(&Datum::Int(i)).unwrap()));
+            assert!(filter.test_hash(fast_hash(&Datum::String(format!("s-{i}"))).unwrap()));
+        }
+        let false_positives = (100..10100)
+            .filter(|i| filter.test_hash(fast_hash(&Datum::Int(*i)).unwrap()))
+            .count();
+        assert!(false_positives < 500, "{false_positives} false positives");
+
+        assert_eq!(unscaled_bytes(127), vec![0x7f]);
+        assert_eq!(unscaled_bytes(128), vec![0x00, 0x80]);
+        assert_eq!(unscaled_bytes(-128), vec![0x80]);
+        assert_eq!(unscaled_bytes(-129), vec![0xff, 0x7f]);
+        assert_eq!(unscaled_bytes(0), vec![0x00]);
+    }
+}
@@ -20,7 +20,7 @@ use std::collections::HashMap;
 use bytes::{Buf, BufMut, Bytes, BytesMut};
 
 use crate::{
-    io::{FileIO, FileRead, FileStatus, InputFile, OutputFile},
+    io::{FileIO, FileRead, InputFile, OutputFile},
     Error,
 };
 
@@ -31,6 +31,9 @@ const READ_BLOCK_SIZE: u64 = 1024 * 1024;
 /// `MAGIC`` is used to mark the beginning of a FileFormat structure.
 pub const MAGIC: u64 = 1493475289347502;
 
+/// Suffix of the file index files of data files, which are the extra files of the data files.
+pub const INDEX_PATH_SUFFIX: &str = ".index";
+
 /// Used to mark an empty INDEX.
 pub const EMPTY_INDEX_FLAG: i64 = -1;
 
@@ -98,13 +101,22 @@ enum Version {
 ///
 /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fileindex/FileIndexFormat.java>
 pub async fn write_column_indexes(
+    file_io: &dyn FileIO,
     path: &str,
     indexes: HashMap<String, HashMap<String, Bytes>>,
 ) -> crate::Result<OutputFile> {
-    let file_io = FileIO::from_url(path)?.build()?;
     let output = file_io.new_output(path)?;
     let mut writer = output.writer().await?;
+    writer.write(serialize_column_indexes(indexes)?).await?;
+    writer.close().await?;
+    Ok(output)
+}
 
+/// Serialize the indexes of the columns by their index names into the bytes of a file index
+/// file, in the format of [`write_column_indexes`].
+pub fn serialize_column_indexes(
+    indexes: HashMap<String, HashMap<String, Bytes>>,
+) -> crate::Result<Bytes> {
     let mut body_info: HashMap<String, HashMap<String, IndexInfo>> = HashMap::new();
     let mut total_data_size = 0;
 
@@ -180,11 +192,8 @@ pub async fn write_column_indexes(
     // Redundant length for future compatibility
     head_buffer.put_i32_le(0);
 
-    // Write into
-    writer.write(head_buffer.freeze()).await?;
-    writer.write(body.freeze()).await?;
-    writer.close().await?;
-    Ok(output)
+    head_buffer.extend_from_slice(&body);
+    Ok(head_buffer.freeze())
 }
 
 fn calculate_head_length(
@@ -212,11 +221,16 @@ fn calculate_head_length(
 }
 
 pub struct FileIndex {
-    reader: Box<dyn FileRead>,
+    reader: Box<dyn FileRead + Sync>,
     header: HashMap<String, HashMap<String, IndexInfo>>,
 }
 
 impl FileIndex {
+    /// Whether there are indexes of the column.
+    pub fn contains_column(&self, column_name: &str) -> bool {
+        self.header.contains_key(column_name)
+    }
+
     pub async fn get_column_index(
         &self,
         column_name: &str,
@@ -252,6 +266,9 @@ impl FileIndex {
         &self,
         index_info: &IndexInfo,
     ) -> crate::Result<Bytes> {
+        if index_info.start_pos == EMPTY_INDEX_FLAG {
+            return Ok(Bytes::new());
+        }
         let data_bytes = self
             .reader
             .read(index_info.start_pos as u64..(index_info.start_pos + index_info.length) as u64)
@@ -262,17 +279,28 @@ impl FileIndex {
 }
 
 pub struct FileIndexFormatReader {
-    reader: Box<dyn FileRead>,
-    stat: FileStatus,
+    reader: Box<dyn FileRead + Sync>,
+    size: u64,
 }
 
 impl FileIndexFormatReader {
     pub async fn get_file_index(input_file: InputFile) -> crate::Result<FileIndex> {
         let reader = input_file.reader().await?;
-        let mut file_reader = Self {
-            reader: Box::new(reader),
-            stat: input_file.metadata().await?,
-        };
+        let size = input_file.metadata().await?.size;
+        Self::read_file_index(Box::new(reader), size).await
+    }
+
+    /// Get the file index of the bytes, like the index embedded in the meta of a data file.
+    pub async fn get_file_index_of_bytes(bytes: Bytes) -> crate::Result<FileIndex> {
+        let size = bytes.len() as u64;
+        Self::read_file_index(Box::new(bytes), size).await
+    }
+
+    async fn read_file_index(
+        reader: Box<dyn FileRead + Sync>,
+        size: u64,
+    ) -> crate::Result<FileIndex> {
+        let mut file_reader = Self { reader, size };
         let header = file_reader.read_header().await?;
         Ok(FileIndex {
             header,
@@ -281,8 +309,8 @@ impl FileIndexFormatReader {
     }
 
     async fn read_header(&mut self) -> crate::Result<HashMap<String, HashMap<String, IndexInfo>>> {
-        let read_size = if self.stat.size < READ_BLOCK_SIZE {
-            self.stat.size
+        let read_size = if self.size < READ_BLOCK_SIZE {
+            self.size
         } else {
             READ_BLOCK_SIZE
         };
@@ -398,19 +426,21 @@ impl FileIndexFormatReader {
 mod file_index_format_tests {
 
     use super::*;
+    use crate::io::FileIOBuilder;
     use bytes::Bytes;
     use std::collections::HashMap;
 
     #[tokio::test]
     async fn test_single_column_single_index() -> crate::Result<()> {
         let path = "memory:/tmp/test_single_column_single_index";
+        let file_io = FileIOBuilder::from_url(path)?.build()?;
 
         let mut indexes = HashMap::new();
         let mut index_map = HashMap::new();
         index_map.insert("index1".to_string(), Bytes::from("sample_data"));
         indexes.insert("column111".to_string(), index_map);
 
-        let output = write_column_indexes(path, indexes.clone()).await?;
+        let output = write_column_indexes(file_io.as_ref(), path, indexes.clone()).await?;
 
         let input = output.to_input_file();
 
@@ -427,6 +457,7 @@ mod file_index_format_tests {
     #[tokio::test]
     async fn test_multiple_columns_multiple_indexes() -> crate::Result<()> {
         let path = "memory:/tmp/test_multiple_columns_multiple_indexes";
+        let file_io = FileIOBuilder::from_url(path)?.build()?;
 
         let mut indexes = HashMap::new();
         for col_num in 1..5 {
@@ -441,7 +472,7 @@ mod file_index_format_tests {
             indexes.insert(column_name, index_map);
         }
 
-        let output = write_column_indexes(path, indexes.clone()).await?;
+        let output = write_column_indexes(file_io.as_ref(), path, indexes.clone()).await?;
 
         let input = output.to_input_file();
 
@@ -459,6 +490,7 @@ mod file_index_format_tests {
     #[tokio::test]
     async fn test_empty_file_index() -> crate::Result<()> {
         let path = "memory:/tmp/test_empty_file_index";
+        let file_io = FileIOBuilder::from_url(path)?.build()?;
 
         let mut indexes = HashMap::new();
         let mut a_index = HashMap::new();
@@ -466,7 +498,7 @@ mod file_index_format_tests {
         a_index.insert("c".to_string(), Bytes::new());
         indexes.insert("a".to_string(), a_index);
 
-        let output = write_column_indexes(path, indexes.clone()).await?;
+        let output = write_column_indexes(file_io.as_ref(), path, indexes.clone()).await?;
 
         let input = output.to_input_file();
 
@@ -483,15 +515,16 @@ mod file_index_format_tests {
     #[tokio::test]
     async fn test_large_data_set() -> crate::Result<()> {
         let path = "memory:/tmp/test_large_data_set";
+        let file_io = FileIOBuilder::from_url(path)?.build()?;
 
         let mut indexes = HashMap::new();
         let mut large_data = HashMap::new();
         large_data.insert("large_index".to_string(), random_bytes(100_000_000)); // 100MB data
         indexes.insert("large_column".to_string(), large_data);
 
-        write_column_indexes(path, indexes.clone()).await?;
+        write_column_indexes(file_io.as_ref(), path, indexes.clone()).await?;
 
-        let output = write_column_indexes(path, indexes.clone()).await?;
+        let output = write_column_indexes(file_io.as_ref(), path, indexes.clone()).await?;
 
         let input = output.to_input_file();
 
@@ -0,0 +1,224 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::file_index::{
+    BitmapFileIndexReader, BloomFilterFileIndexReader, FileIndex, BITMAP, BLOOM_FILTER,
+};
+use crate::predicate::{LeafFunction, Predicate};
+use crate::spec::{DataType, Datum};
+use roaring::RoaringBitmap;
+use std::collections::HashMap;
+
+/// Result of testing a file by its indexes, whether it may contain rows matching a predicate.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-common/src/main/java/org/apache/paimon/fileindex/FileIndexResult.java>
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileIndexResult {
+    /// The file may contain matched rows.
+    Remain,
+    /// The file contains no matched row.
+    Skip,
+    /// Only the rows at the positions of the bitmap may be matched.
+    Bitmap(RoaringBitmap),
+}
+
+impl FileIndexResult {
+    fn of_bitmap(bitmap: RoaringBitmap) -> Self {
+        if bitmap.is_empty() {
+            FileIndexResult::Skip
+        } else {
+            FileIndexResult::Bitmap(bitmap)
+        }
+    }
+
+    /// Whether the file may contain matched rows.
+    pub fn remain(&self) -> bool {
+        !matches!(self, FileIndexResult::Skip)
+    }
+
+    /// Rows matching both results.
+    pub fn and(self, other: FileIndexResult) -> FileIndexResult {
+        match (self, other) {
+            (FileIndexResult::Skip, _) | (_, FileIndexResult::Skip) => FileIndexResult::Skip,
+            (FileIndexResult::Remain, other) | (other, FileIndexResult::Remain) => other,
+            (FileIndexResult::Bitmap(a), FileIndexResult::Bitmap(b)) => Self::of_bitmap(a & b),
+        }
+    }
+
+    /// Rows matching either result.
+    pub fn or(self, other: FileIndexResult) -> FileIndexResult {
+        match (self, other) {
+            (FileIndexResult::Remain, _) | (_, FileIndexResult::Remain) => FileIndexResult::Remain,
+            (FileIndexResult::Skip, other) | (other, FileIndexResult::Skip) => other,
+            (FileIndexResult::Bitmap(a), FileIndexResult::Bitmap(b)) => Self::of_bitmap(a | b),
+        }
+    }
+}
+
+/// Reader of an index of a column in file index files, testing which rows of the file may
+/// match the predicates on the column.
+///
+/// Functions not supported by the index remain all the rows.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fileindex/FileIndexReader.java>
+pub trait FileIndexReader: Send + Sync {
+    /// Test the rows of values equal to the literal.
+    fn visit_equal(&self, _literal: &Datum) -> FileIndexResult {
+        FileIndexResult::Remain
+    }
+
+    /// Test the rows of values equal to any of the literals.
+    fn visit_in(&self, literals: &[Datum]) -> FileIndexResult {
+        literals
+            .iter()
+            .fold(FileIndexResult::Skip, |result, literal| {
+                result.or(self.visit_equal(literal))
+            })
+    }
+
+    /// Test the rows of null values.
+    fn visit_is_null(&self) -> FileIndexResult {
+        FileIndexResult::Remain
+    }
+}
+
+/// Reader of an empty index, whose column is all null in the file.
+struct EmptyFileIndexReader;
+
+impl FileIndexReader for EmptyFileIndexReader {
+    fn visit_equal(&self, _literal: &Datum) -> FileIndexResult {
+        FileIndexResult::Skip
+    }
+}
+
+/// Predicate tested by the indexes of a file index file, to skip data files or rows of them
+/// not matching it.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fileindex/FileIndexPredicate.java>
+pub struct FileIndexPredicate {
+    readers: HashMap<String, Vec<Box<dyn FileIndexReader>>>,
+}
+
+impl FileIndexPredicate {
+    /// Load the indexes of the columns of the predicate from the file index, indexes of unknown
+    /// types are ignored.
+    pub async fn new(file_index: &FileIndex, predicate: &Predicate) -> crate::Result<Self> {
+        let mut columns = Vec::new();
+        collect_fields(predicate, &mut columns);
+        let mut readers = HashMap::new();
+        for (column, data_type) in columns {
+            if readers.contains_key(column) || !file_index.contains_column(column) {
+                continue;
+            }
+            let mut column_readers: Vec<Box<dyn FileIndexReader>> = Vec::new();
+            for (index_type, bytes) in file_index.get_column_index(column).await? {
+                if bytes.is_empty() {
+                    column_readers.push(Box::new(EmptyFileIndexReader));
+                    continue;
+                }
+                match index_type.as_str() {
+                    BLOOM_FILTER => {
+                        column_readers.push(Box::new(BloomFilterFileIndexReader::new(bytes)?))
+                    }
+                    BITMAP => column_readers.push(Box::new(BitmapFileIndexReader::new(
+                        data_type.clone(),
+                        bytes,
+                    )?)),
+                    _ => {}
+                }
+            }
+            readers.insert(column.to_string(), column_readers);
+        }
+        Ok(Self { readers })
+    }
+
+    /// Whether the file may contain rows matching the predicate.
+    pub fn test(&self, predicate: &Predicate) -> bool {
+        self.evaluate(predicate).remain()
+    }
+
+    /// Test the rows of the file which may match the predicate.
+    pub fn evaluate(&self, predicate: &Predicate) -> FileIndexResult {
+        match predicate {
+            Predicate::Leaf {
+                function,
+                field_name,
+                literals,
+                ..
+            } => {
+                let Some(readers) = self.readers.get(field_name) else {
+                    return FileIndexResult::Remain;
+                };
+                readers
+                    .iter()
+                    .fold(FileIndexResult::Remain, |result, reader| {
+                        result.and(match function {
+                            LeafFunction::Equal => reader.visit_equal(&literals[0]),
+                            LeafFunction::In => reader.visit_in(literals),
+                            LeafFunction::IsNull => reader.visit_is_null(),
+                            _ => FileIndexResult::Remain,
+                        })
+                    })
+            }
+            Predicate::And(children) => children
+                .iter()
+                .fold(FileIndexResult::Remain, |result, child| {
+                    result.and(self.evaluate(child))
+                }),
+            Predicate::Or(children) => children
+                .iter()
+                .fold(FileIndexResult::Skip, |result, child| {
+                    result.or(self.evaluate(child))
+                }),
+            Predicate::Not(_) => FileIndexResult::Remain,
+        }
+    }
+}
+
+fn collect_fields<'a>(predicate: &'a Predicate, fields: &mut Vec<(&'a str, &'a DataType)>) {
+    match predicate {
+        Predicate::Leaf {
+            field_name,
+            data_type,
+            ..
+        } => fields.push((field_name, data_type)),
+        Predicate::And(children) | Predicate::Or(children) => {
+            for child in children {
+                collect_fields(child, fields);
+            }
+        }
+        Predicate::Not(child) => collect_fields(child, fields),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_combine_file_index_results() {
+        use FileIndexResult::*;
+        let bitmap = |rows: &[u32]| Bitmap(rows.iter().copied().collect());
+        assert_eq!(Remain.and(bitmap(&[1, 2])), bitmap(&[1, 2]));
+        assert_eq!(bitmap(&[1, 2]).and(bitmap(&[2, 3])), bitmap(&[2]));
+        assert_eq!(bitmap(&[1]).and(bitmap(&[2])), Skip);
+        assert_eq!(Skip.and(Remain), Skip);
+        assert_eq!(Skip.or(bitmap(&[1])), bitmap(&[1]));
+        assert_eq!(bitmap(&[1]).or(bitmap(&[2])), bitmap(&[1, 2]));
+        assert_eq!(bitmap(&[1]).or(Remain), Remain);
+    }
+}
@@ -0,0 +1,255 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::ConfigInvalidSnafu;
+use crate::file_index::{
+    serialize_column_indexes, BitmapFileIndexWriter, BloomFilterFileIndexWriter, BITMAP,
+    BLOOM_FILTER, INDEX_PATH_SUFFIX,
+};
+use crate::io::FileIO;
+use crate::spec::{CoreOptions, DataField};
+use arrow_array::{Array, RecordBatch};
+use bytes::Bytes;
+use std::collections::HashMap;
+
+/// Writer of an index of a column of a data file, the values of the rows are written in the
+/// order of the rows.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fileindex/FileIndexWriter.java>
+pub trait FileIndexWriter: Send {
+    /// Write the values of the next rows.
+    fn write(&mut self, column: &dyn Array) -> crate::Result<()>;
+
+    /// Serialize the index of the values written.
+    fn serialized_bytes(&self) -> crate::Result<Bytes>;
+}
+
+/// An index of a column to write.
+#[derive(Debug, Clone)]
+struct IndexedColumn {
+    /// Position of the column in the data files.
+    position: usize,
+    field: DataField,
+    index_type: &'static str,
+    options: HashMap<String, String>,
+}
+
+impl IndexedColumn {
+    fn writer(&self) -> crate::Result<Box<dyn FileIndexWriter>> {
+        let data_type = self.field.data_type().clone();
+        Ok(match self.index_type {
+            BLOOM_FILTER => Box::new(BloomFilterFileIndexWriter::new(
+                data_type,
+                self.options.get("items").and_then(|v| v.parse().ok()),
+                self.options.get("fpp").and_then(|v| v.parse().ok()),
+            )?),
+            _ => Box::new(BitmapFileIndexWriter::new(data_type)?),
+        })
+    }
+}
+
+/// Indexes of the columns of data files, configured by `file-index.<type>.columns` with the
+/// options `file-index.<type>.<column>.<key>`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FileIndexOptions {
+    columns: Vec<IndexedColumn>,
+    in_manifest_threshold: usize,
+}
+
+impl FileIndexOptions {
+    /// Get the indexes of the columns among the fields of the data files.
+    pub(crate) fn of_fields(options: &CoreOptions, fields: &[DataField]) -> crate::Result<Self> {
+        let mut columns = Vec::new();
+        for (index_type, names) in options.file_index_columns() {
+            let index_type = match index_type {
+                BLOOM_FILTER => BLOOM_FILTER,
+                BITMAP => BITMAP,
+                _ => {
+                    return ConfigInvalidSnafu {
+                        message: format!("file index type {index_type} is not supported"),
+                    }
+                    .fail()
+                }
+            };
+            for name in names {
+                let Some(position) = fields.iter().position(|f| f.name() == name) else {
+                    return ConfigInvalidSnafu {
+                        message: format!("file index column {name} does not exist"),
+                    }
+                    .fail();
+                };
+                let column = IndexedColumn {
+                    position,
+                    field: fields[position].clone(),
+                    index_type,
+                    options: ["items", "fpp"]
+                        .into_iter()
+                        .filter_map(|key| {
+                            let value = options.file_index_option(index_type, name, key)?;
+                            Some((key.to_string(), value.to_string()))
+                        })
+                        .collect(),
+                };
+                // fail early on columns of unsupported types
+                column.writer()?;
+                columns.push(column);
+            }
+        }
+        Ok(Self {
+            columns,
+            in_manifest_threshold: options.file_index_in_manifest_threshold().max(0) as usize,
+        })
+    }
+
+    /// Create the writer of the indexes of a data file, none if no column is indexed.
+    pub(crate) fn writer(&self) -> crate::Result<Option<DataFileIndexWriter>> {
+        if self.columns.is_empty() {
+            return Ok(None);
+        }
+        let writers = self
+            .columns
+            .iter()
+            .map(|column| Ok((column.clone(), column.writer()?)))
+            .collect::<crate::Result<_>>()?;
+        Ok(Some(DataFileIndexWriter {
+            writers,
+            in_manifest_threshold: self.in_manifest_threshold,
+        }))
+    }
+}
+
+/// Writer of the indexes of the columns of a data file.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/io/FileIndexWriter.java>
+pub(crate) struct DataFileIndexWriter {
+    writers: Vec<(IndexedColumn, Box<dyn FileIndexWriter>)>,
+    in_manifest_threshold: usize,
+}
+
+/// Index of a data file, embedded in its meta or written into an extra file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DataFileIndex {
+    Embedded(Vec<u8>),
+    ExtraFile(String),
+}
+
+impl DataFileIndexWriter {
+    /// Write the rows of the batch in the layout of the data file.
+    pub(crate) fn write(&mut self, batch: &RecordBatch) -> crate::Result<()> {
+        for (column, writer) in &mut self.writers {
+            writer.write(batch.column(column.position).as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Serialize the indexes, which are embedded if they are not larger than
+    /// `file-index.in-manifest-threshold`, otherwise written into the file of the data file
+    /// name with [`INDEX_PATH_SUFFIX`] besides the data file.
+    pub(crate) async fn close(
+        self,
+        file_io: &dyn FileIO,
+        data_file_path: &str,
+    ) -> crate::Result<DataFileIndex> {
+        let mut indexes: HashMap<String, HashMap<String, Bytes>> = HashMap::new();
+        for (column, writer) in self.writers {
+            indexes
+                .entry(column.field.name().to_string())
+                .or_default()
+                .insert(column.index_type.to_string(), writer.serialized_bytes()?);
+        }
+        let bytes = serialize_column_indexes(indexes)?;
+        if bytes.len() <= self.in_manifest_threshold {
+            return Ok(DataFileIndex::Embedded(bytes.to_vec()));
+        }
+        let path = format!("{data_file_path}{INDEX_PATH_SUFFIX}");
+        file_io.new_output(&path)?.write(bytes).await?;
+        let name = path.rsplit('/').next().unwrap_or_default().to_string();
+        Ok(DataFileIndex::ExtraFile(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_index::{FileIndexFormatReader, FileIndexPredicate};
+    use crate::io::FileIOBuilder;
+    use crate::predicate::PredicateBuilder;
+    use crate::spec::{Datum, RowType};
+    use arrow_array::{BooleanArray, Int32Array, StringArray};
+    use std::sync::Arc;
+
+    fn fields() -> Vec<DataField> {
+        vec![
+            DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+            DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+            DataField::new(2, "flag".to_string(), "BOOLEAN".parse().unwrap()),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_write_file_index() {
+        let options = HashMap::from([
+            (
+                "file-index.bloom-filter.columns".to_string(),
+                "id".to_string(),
+            ),
+            (
+                "file-index.bloom-filter.id.items".to_string(),
+                "10".to_string(),
+            ),
+            ("file-index.bitmap.columns".to_string(), "name".to_string()),
+        ]);
+        let file_index =
+            FileIndexOptions::of_fields(&CoreOptions::new(&options), &fields()).unwrap();
+        let mut writer = file_index.writer().unwrap().unwrap();
+        let batch = RecordBatch::try_new(
+            Arc::new(crate::format::to_arrow_schema(&fields())),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                Arc::new(BooleanArray::from(vec![true, false])),
+            ],
+        )
+        .unwrap();
+        writer.write(&batch).unwrap();
+        let path = "memory:/tmp/test_write_file_index/data-0.parquet";
+        let file_io = FileIOBuilder::from_url(path).unwrap().build().unwrap();
+        let DataFileIndex::Embedded(bytes) = writer.close(file_io.as_ref(), path).await.unwrap()
+        else {
+            panic!("the index of 2 rows is embedded");
+        };
+        let file_index = FileIndexFormatReader::get_file_index_of_bytes(Bytes::from(bytes))
+            .await
+            .unwrap();
+        assert!(file_index.contains_column("id") && file_index.contains_column("name"));
+        let builder = PredicateBuilder::new(RowType::new(fields()));
+        let predicate = builder.equal(0, Datum::Int(3));
+        let file_index_predicate = FileIndexPredicate::new(&file_index, &predicate)
+            .await
+            .unwrap();
+        assert!(!file_index_predicate.test(&predicate));
+
+        for (key, value) in [
+            ("file-index.hash.columns", "id"),
+            ("file-index.bloom-filter.columns", "age"),
+            ("file-index.bloom-filter.columns", "flag"),
+        ] {
+            let options = HashMap::from([(key.to_string(), value.to_string())]);
+            assert!(FileIndexOptions::of_fields(&CoreOptions::new(&options), &fields()).is_err());
+        }
+    }
+}
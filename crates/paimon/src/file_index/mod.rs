@@ -15,5 +15,17 @@
 // specific language governing permissions and limitations
 // under the License.
 
+mod bitmap;
+pub use bitmap::*;
+
+mod bloom_filter;
+pub use bloom_filter::*;
+
 mod file_index_format;
 pub use file_index_format::*;
+
+mod file_index_predicate;
+pub use file_index_predicate::*;
+
+mod file_index_writer;
+pub use file_index_writer::*;
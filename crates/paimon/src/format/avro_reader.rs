@@ -0,0 +1,580 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::DataTypeInvalidSnafu;
+use crate::format::{to_arrow_field, to_arrow_type, ArrowRecordBatchIter, FormatReader};
+use crate::spec::{DataField, DataType};
+use apache_avro::schema::Schema as AvroSchema;
+use apache_avro::types::Value;
+use apache_avro::Reader;
+use arrow_array::types::{
+    Date32Type, Decimal128Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type,
+    Int8Type, Time32MillisecondType,
+};
+use arrow_array::{
+    ArrayRef, ArrowPrimitiveType, BinaryArray, BooleanArray, ListArray, MapArray, PrimitiveArray,
+    RecordBatch, StringArray, StructArray,
+};
+use arrow_buffer::{NullBuffer, OffsetBuffer};
+use arrow_schema::{DataType as ArrowDataType, Field, Schema, SchemaRef, TimeUnit};
+use bytes::buf::Reader as BytesReader;
+use bytes::{Buf, Bytes};
+use std::sync::Arc;
+
+/// Reader of avro files, decoding the avro records into arrow record batches.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-format/src/main/java/org/apache/paimon/format/avro/AvroBulkFormat.java>
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct AvroReader;
+
+impl FormatReader for AvroReader {
+    fn read(
+        &self,
+        bytes: Bytes,
+        fields: &[DataField],
+        batch_size: usize,
+    ) -> crate::Result<ArrowRecordBatchIter> {
+        let reader = Reader::new(bytes.reader())?;
+        let record_fields = match reader.writer_schema() {
+            AvroSchema::Record(record) => record.fields.clone(),
+            schema => {
+                return DataTypeInvalidSnafu {
+                    message: format!("avro schema {schema:?} of data file is not a record"),
+                }
+                .fail()
+            }
+        };
+        // the read fields in the file with their positions in the records
+        let (fields, positions): (Vec<DataField>, Vec<usize>) = fields
+            .iter()
+            .filter_map(|field| {
+                record_fields
+                    .iter()
+                    .position(|f| f.name == field.name())
+                    .map(|position| (field.clone(), position))
+            })
+            .unzip();
+        let schema = Arc::new(Schema::new(
+            fields.iter().map(to_arrow_field).collect::<Vec<_>>(),
+        ));
+        Ok(Box::new(AvroRecordBatchIter {
+            reader,
+            schema,
+            fields,
+            positions,
+            batch_size,
+        }))
+    }
+}
+
+struct AvroRecordBatchIter {
+    reader: Reader<'static, BytesReader<Bytes>>,
+    schema: SchemaRef,
+    fields: Vec<DataField>,
+    positions: Vec<usize>,
+    batch_size: usize,
+}
+
+impl AvroRecordBatchIter {
+    fn next_batch(&mut self) -> crate::Result<Option<RecordBatch>> {
+        let mut records = Vec::with_capacity(self.batch_size);
+        while records.len() < self.batch_size {
+            match self.reader.next() {
+                Some(record) => records.push(record?),
+                None => break,
+            }
+        }
+        if records.is_empty() {
+            return Ok(None);
+        }
+        let columns = self
+            .fields
+            .iter()
+            .zip(&self.positions)
+            .map(|(field, position)| {
+                let values = records
+                    .iter()
+                    .map(|record| match record {
+                        Value::Record(columns) => Ok(&columns[*position].1),
+                        value => DataTypeInvalidSnafu {
+                            message: format!("avro value {value:?} is not a record"),
+                        }
+                        .fail(),
+                    })
+                    .collect::<crate::Result<Vec<_>>>()?;
+                to_arrow_array(field.data_type(), &values)
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Some(RecordBatch::try_new(self.schema.clone(), columns)?))
+    }
+}
+
+impl Iterator for AvroRecordBatchIter {
+    type Item = crate::Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_batch().transpose()
+    }
+}
+
+/// Get the value out of the union of nullable types.
+fn unwrap_union(value: &Value) -> &Value {
+    match value {
+        Value::Union(_, value) => value,
+        value => value,
+    }
+}
+
+fn null_buffer(values: &[&Value]) -> Option<NullBuffer> {
+    let valid: Vec<bool> = values
+        .iter()
+        .map(|value| !matches!(unwrap_union(value), Value::Null))
+        .collect();
+    Some(NullBuffer::from(valid)).filter(|nulls| nulls.null_count() > 0)
+}
+
+fn invalid_value<T>(value: &Value, data_type: &DataType) -> crate::Result<T> {
+    DataTypeInvalidSnafu {
+        message: format!("avro value {value:?} for type {data_type}"),
+    }
+    .fail()
+}
+
+/// Convert the non-null values with the function, an error is returned if the function
+/// can't convert a value.
+fn convert<'a, T>(
+    values: &[&'a Value],
+    data_type: &DataType,
+    f: impl Fn(&'a Value) -> Option<T>,
+) -> crate::Result<Vec<Option<T>>> {
+    values
+        .iter()
+        .map(|value| match unwrap_union(value) {
+            Value::Null => Ok(None),
+            value => match f(value) {
+                Some(v) => Ok(Some(v)),
+                None => invalid_value(value, data_type),
+            },
+        })
+        .collect()
+}
+
+fn primitive<'a, T: ArrowPrimitiveType>(
+    values: &[&'a Value],
+    data_type: &DataType,
+    f: impl Fn(&'a Value) -> Option<T::Native>,
+) -> crate::Result<ArrayRef> {
+    Ok(Arc::new(
+        convert(values, data_type, f)?
+            .into_iter()
+            .collect::<PrimitiveArray<T>>(),
+    ))
+}
+
+/// Convert big-endian two's complement bytes to the unscaled value of decimal.
+fn unscaled_decimal(bytes: &[u8]) -> i128 {
+    let sign = if matches!(bytes.first(), Some(b) if b & 0x80 != 0) {
+        -1
+    } else {
+        0
+    };
+    bytes
+        .iter()
+        .fold(sign, |unscaled, b| (unscaled << 8) | *b as i128)
+}
+
+/// Convert the timestamp value to the time unit of arrow type.
+fn timestamp(value: &Value, unit: &TimeUnit) -> Option<i64> {
+    let (v, value_unit) = match value {
+        Value::TimestampMillis(v) | Value::LocalTimestampMillis(v) => (*v, TimeUnit::Millisecond),
+        Value::TimestampMicros(v) | Value::LocalTimestampMicros(v) => (*v, TimeUnit::Microsecond),
+        Value::TimestampNanos(v) | Value::LocalTimestampNanos(v) => (*v, TimeUnit::Nanosecond),
+        Value::Long(v) => return Some(*v),
+        _ => return None,
+    };
+    let scale = |unit: &TimeUnit| match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 3,
+        TimeUnit::Microsecond => 6,
+        TimeUnit::Nanosecond => 9,
+    };
+    let (from, to) = (scale(&value_unit), scale(unit));
+    if from <= to {
+        v.checked_mul(10_i64.pow(to - from))
+    } else {
+        Some(v / 10_i64.pow(from - to))
+    }
+}
+
+/// Convert the avro values of the paimon data type to arrow array.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-format/src/main/java/org/apache/paimon/format/avro/FieldReaderFactory.java>
+fn to_arrow_array(data_type: &DataType, values: &[&Value]) -> crate::Result<ArrayRef> {
+    let arrow_type = to_arrow_type(data_type);
+    Ok(match data_type {
+        DataType::Boolean(_) => Arc::new(
+            convert(values, data_type, |value| match value {
+                Value::Boolean(v) => Some(*v),
+                _ => None,
+            })?
+            .into_iter()
+            .collect::<BooleanArray>(),
+        ),
+        DataType::TinyInt(_) => primitive::<Int8Type>(values, data_type, |value| match value {
+            Value::Int(v) => i8::try_from(*v).ok(),
+            _ => None,
+        })?,
+        DataType::SmallInt(_) => primitive::<Int16Type>(values, data_type, |value| match value {
+            Value::Int(v) => i16::try_from(*v).ok(),
+            _ => None,
+        })?,
+        DataType::Int(_) => primitive::<Int32Type>(values, data_type, |value| match value {
+            Value::Int(v) => Some(*v),
+            _ => None,
+        })?,
+        DataType::BigInt(_) => primitive::<Int64Type>(values, data_type, |value| match value {
+            Value::Long(v) => Some(*v),
+            _ => None,
+        })?,
+        DataType::Float(_) => primitive::<Float32Type>(values, data_type, |value| match value {
+            Value::Float(v) => Some(*v),
+            _ => None,
+        })?,
+        DataType::Double(_) => primitive::<Float64Type>(values, data_type, |value| match value {
+            Value::Double(v) => Some(*v),
+            _ => None,
+        })?,
+        DataType::Date(_) => primitive::<Date32Type>(values, data_type, |value| match value {
+            Value::Date(v) | Value::Int(v) => Some(*v),
+            _ => None,
+        })?,
+        DataType::Time(_) => {
+            primitive::<Time32MillisecondType>(values, data_type, |value| match value {
+                Value::TimeMillis(v) | Value::Int(v) => Some(*v),
+                Value::TimeMicros(v) => i32::try_from(v / 1000).ok(),
+                _ => None,
+            })?
+        }
+        DataType::Timestamp(_) | DataType::LocalZonedTimestamp(_) => {
+            let unit = match &arrow_type {
+                ArrowDataType::Timestamp(unit, _) => *unit,
+                _ => unreachable!("timestamps are converted to arrow timestamps"),
+            };
+            let array = primitive::<Int64Type>(values, data_type, |value| timestamp(value, &unit))?;
+            arrow_cast::cast(&array, &arrow_type)?
+        }
+        DataType::Decimal(t) => {
+            let array = convert(values, data_type, |value| match value {
+                Value::Decimal(v) => Vec::<u8>::try_from(v)
+                    .ok()
+                    .map(|bytes| unscaled_decimal(&bytes)),
+                Value::Bytes(bytes) | Value::Fixed(_, bytes) => Some(unscaled_decimal(bytes)),
+                _ => None,
+            })?
+            .into_iter()
+            .collect::<PrimitiveArray<Decimal128Type>>()
+            .with_precision_and_scale(t.precision() as u8, t.scale() as i8)?;
+            Arc::new(array)
+        }
+        DataType::Char(_) | DataType::VarChar(_) => Arc::new(
+            convert(values, data_type, |value| match value {
+                Value::String(v) | Value::Enum(_, v) => Some(v.as_str()),
+                _ => None,
+            })?
+            .into_iter()
+            .collect::<StringArray>(),
+        ),
+        DataType::Binary(_) | DataType::VarBinary(_) => Arc::new(
+            convert(values, data_type, |value| match value {
+                Value::Bytes(v) | Value::Fixed(_, v) => Some(v.as_slice()),
+                _ => None,
+            })?
+            .into_iter()
+            .collect::<BinaryArray>(),
+        ),
+        DataType::Array(t) => {
+            let elements = convert(values, data_type, |value| match value {
+                Value::Array(elements) => Some(elements),
+                _ => None,
+            })?;
+            let offsets = OffsetBuffer::from_lengths(
+                elements.iter().map(|elements| elements.map_or(0, Vec::len)),
+            );
+            let children: Vec<&Value> = elements.iter().flatten().flat_map(|e| e.iter()).collect();
+            let field = match &arrow_type {
+                ArrowDataType::List(field) => field.clone(),
+                _ => unreachable!("arrays are converted to arrow lists"),
+            };
+            Arc::new(ListArray::try_new(
+                field,
+                offsets,
+                to_arrow_array(t.element_type(), &children)?,
+                null_buffer(values),
+            )?)
+        }
+        DataType::Map(_) | DataType::Multiset(_) => {
+            let (key_type, value_type) = match data_type {
+                DataType::Map(t) => (t.key_type().clone(), t.value_type().clone()),
+                DataType::Multiset(t) => (
+                    t.element_type().clone(),
+                    DataType::Int(crate::spec::IntType::with_nullable(false)),
+                ),
+                _ => unreachable!(),
+            };
+            // maps with string keys are avro maps, otherwise arrays of key value records
+            let entries = convert(values, data_type, |value| match value {
+                Value::Map(entries) => Some(
+                    entries
+                        .iter()
+                        .map(|(k, v)| (Value::String(k.clone()), v))
+                        .collect::<Vec<_>>(),
+                ),
+                Value::Array(entries) => entries
+                    .iter()
+                    .map(|entry| match entry {
+                        Value::Record(kv) if kv.len() == 2 => Some((kv[0].1.clone(), &kv[1].1)),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => None,
+            })?;
+            let offsets = OffsetBuffer::from_lengths(
+                entries
+                    .iter()
+                    .map(|entries| entries.as_ref().map_or(0, Vec::len)),
+            );
+            let entries: Vec<&(Value, &Value)> = entries.iter().flatten().flatten().collect();
+            let keys: Vec<&Value> = entries.iter().map(|(k, _)| k).collect();
+            let values_of_entries: Vec<&Value> = entries.iter().map(|(_, v)| *v).collect();
+            let field = match &arrow_type {
+                ArrowDataType::Map(field, _) => field.clone(),
+                _ => unreachable!("maps are converted to arrow maps"),
+            };
+            let struct_fields = match field.data_type() {
+                ArrowDataType::Struct(fields) => fields.clone(),
+                _ => unreachable!("entries of maps are arrow structs"),
+            };
+            let entries = StructArray::try_new(
+                struct_fields,
+                vec![
+                    to_arrow_array(&key_type, &keys)?,
+                    to_arrow_array(&value_type, &values_of_entries)?,
+                ],
+                None,
+            )?;
+            Arc::new(MapArray::try_new(
+                field,
+                offsets,
+                entries,
+                null_buffer(values),
+                false,
+            )?)
+        }
+        DataType::Row(t) => {
+            let records = convert(values, data_type, |value| match value {
+                Value::Record(fields) => Some(fields),
+                _ => None,
+            })?;
+            let columns = t
+                .fields()
+                .iter()
+                .map(|field| {
+                    let children: Vec<&Value> = records
+                        .iter()
+                        .map(|record| {
+                            record
+                                .and_then(|record| {
+                                    record
+                                        .iter()
+                                        .find(|(name, _)| name == field.name())
+                                        .map(|(_, value)| value)
+                                })
+                                .unwrap_or(&Value::Null)
+                        })
+                        .collect();
+                    to_arrow_array(field.data_type(), &children)
+                })
+                .collect::<crate::Result<Vec<_>>>()?;
+            let fields = t
+                .fields()
+                .iter()
+                .map(to_arrow_field)
+                .collect::<Vec<Field>>();
+            Arc::new(StructArray::try_new(
+                fields.into(),
+                columns,
+                null_buffer(values),
+            )?)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use apache_avro::{Decimal, Writer};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::TimestampMicrosecondType;
+    use arrow_array::Array;
+    use arrow_select::concat::concat_batches;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_read_avro() {
+        let schema = AvroSchema::parse_str(
+            r#"{
+                "type": "record",
+                "name": "record",
+                "fields": [
+                    {"name": "id", "type": "int"},
+                    {"name": "name", "type": ["null", "string"], "default": null},
+                    {"name": "price", "type": {"type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2}},
+                    {"name": "ts", "type": {"type": "long", "logicalType": "timestamp-micros"}},
+                    {"name": "tags", "type": {"type": "array", "items": "string"}},
+                    {"name": "attrs", "type": {"type": "map", "values": "int"}},
+                    {"name": "counts", "type": {"type": "array", "items": {
+                        "type": "record", "name": "entry", "fields": [
+                            {"name": "key", "type": "int"},
+                            {"name": "value", "type": "long"}
+                        ]
+                    }}},
+                    {"name": "nested", "type": ["null", {
+                        "type": "record", "name": "nested", "fields": [
+                            {"name": "a", "type": "boolean"}
+                        ]
+                    }], "default": null}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let mut writer = Writer::new(&schema, Vec::new());
+        for i in 0..3 {
+            let nullable = |value: Value| {
+                if i == 1 {
+                    Value::Union(0, Box::new(Value::Null))
+                } else {
+                    Value::Union(1, Box::new(value))
+                }
+            };
+            writer
+                .append(Value::Record(vec![
+                    ("id".to_string(), Value::Int(i)),
+                    (
+                        "name".to_string(),
+                        nullable(Value::String(format!("name-{i}"))),
+                    ),
+                    (
+                        "price".to_string(),
+                        Value::Decimal(Decimal::from((-1234 - i as i64).to_be_bytes())),
+                    ),
+                    (
+                        "ts".to_string(),
+                        Value::TimestampMicros(1_725_614_755_039_000 + i as i64),
+                    ),
+                    (
+                        "tags".to_string(),
+                        Value::Array((0..i).map(|t| Value::String(t.to_string())).collect()),
+                    ),
+                    (
+                        "attrs".to_string(),
+                        Value::Map(HashMap::from([("k".to_string(), Value::Int(i))])),
+                    ),
+                    (
+                        "counts".to_string(),
+                        Value::Array(vec![Value::Record(vec![
+                            ("key".to_string(), Value::Int(i)),
+                            ("value".to_string(), Value::Long(i as i64 * 10)),
+                        ])]),
+                    ),
+                    (
+                        "nested".to_string(),
+                        nullable(Value::Record(vec![(
+                            "a".to_string(),
+                            Value::Boolean(i == 0),
+                        )])),
+                    ),
+                ]))
+                .unwrap();
+        }
+        let bytes = Bytes::from(writer.into_inner().unwrap());
+
+        let fields = [
+            ("nested", "ROW<a BOOLEAN NOT NULL>"),
+            ("counts", "MAP<INT NOT NULL, BIGINT NOT NULL> NOT NULL"),
+            ("attrs", "MAP<STRING NOT NULL, INT NOT NULL> NOT NULL"),
+            ("tags", "ARRAY<STRING NOT NULL> NOT NULL"),
+            ("ts", "TIMESTAMP(6) NOT NULL"),
+            ("price", "DECIMAL(10, 2) NOT NULL"),
+            ("unknown", "INT"),
+            ("name", "STRING"),
+            ("id", "INT NOT NULL"),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, (name, data_type))| {
+            DataField::new(id as i32, name.to_string(), data_type.parse().unwrap())
+        })
+        .collect::<Vec<_>>();
+        let batches = AvroReader
+            .read(bytes, &fields, 2)
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+        let batch = concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(batch.num_columns(), 8);
+        assert!(batch.schema().column_with_name("unknown").is_none());
+
+        let nested = batch.column(0).as_struct();
+        assert!(nested.is_null(1));
+        assert!(nested.column(0).as_boolean().value(0));
+        assert!(!nested.column(0).as_boolean().value(2));
+
+        let counts = batch.column(1).as_map();
+        assert_eq!(counts.value_offsets(), &[0, 1, 2, 3]);
+        assert_eq!(
+            counts.values().as_primitive::<Int64Type>().values(),
+            &[0, 10, 20]
+        );
+        let attrs = batch.column(2).as_map();
+        assert_eq!(attrs.keys().as_string::<i32>().value(2), "k");
+        assert_eq!(attrs.values().as_primitive::<Int32Type>().value(2), 2);
+
+        let tags = batch.column(3).as_list::<i32>();
+        assert_eq!(tags.value_offsets(), &[0, 0, 1, 3]);
+        assert_eq!(tags.values().as_string::<i32>().value(2), "1");
+
+        assert_eq!(
+            batch
+                .column(4)
+                .as_primitive::<TimestampMicrosecondType>()
+                .value(1),
+            1_725_614_755_039_001
+        );
+        let price = batch.column(5).as_primitive::<Decimal128Type>();
+        assert_eq!(price.data_type(), &ArrowDataType::Decimal128(10, 2));
+        assert_eq!(price.value(2), -1236);
+        assert_eq!(
+            batch.column(6).as_ref(),
+            &StringArray::from(vec![Some("name-0"), None, Some("name-2")]) as &dyn Array
+        );
+        assert_eq!(
+            batch.column(7).as_primitive::<Int32Type>().values(),
+            &[0, 1, 2]
+        );
+    }
+}
@@ -0,0 +1,597 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::{DataTypeInvalidSnafu, FileCompressionUnsupportedSnafu};
+use crate::format::{FileCompression, FormatWriter};
+use crate::spec::{DataField, DataType};
+use apache_avro::schema::Schema as AvroSchema;
+use apache_avro::types::Value;
+use apache_avro::{Codec, Decimal, Writer};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Decimal128Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type,
+    Int8Type, Time32MillisecondType,
+};
+use arrow_array::{Array, RecordBatch};
+use arrow_schema::{DataType as ArrowDataType, TimeUnit};
+use bytes::Bytes;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Name of the top level record of the avro data files.
+const RECORD_NAME: &str = "org.apache.paimon.avro.generated.record";
+
+/// Writer of avro data files, each written batch is a block of the file.
+///
+/// The blocks of `zstd` are compressed at the default level of the codec of apache-avro, the
+/// level of `file.compression.zstd-level` is not applied.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-format/src/main/java/org/apache/paimon/format/avro/AvroFileFormat.java>
+pub(crate) struct AvroWriter {
+    fields: Vec<DataField>,
+    schema: AvroSchema,
+    codec: Codec,
+    marker: [u8; 16],
+    bytes: Vec<u8>,
+}
+
+impl AvroWriter {
+    pub(crate) fn try_new(
+        fields: &[DataField],
+        compression: &FileCompression,
+    ) -> crate::Result<Self> {
+        let codec = match compression.codec.as_str() {
+            "none" | "uncompressed" | "null" => Codec::Null,
+            "deflate" => Codec::Deflate,
+            "snappy" => Codec::Snappy,
+            "zstd" | "zstandard" => Codec::Zstandard,
+            codec => {
+                return FileCompressionUnsupportedSnafu {
+                    format: "avro",
+                    compression: codec,
+                }
+                .fail()
+            }
+        };
+        let record_fields = fields
+            .iter()
+            .map(|field| avro_field(RECORD_NAME, field))
+            .collect::<crate::Result<Vec<_>>>()?;
+        let schema = AvroSchema::parse(&json!({
+            "type": "record",
+            "name": RECORD_NAME,
+            "fields": record_fields,
+        }))?;
+        let marker = *uuid::Uuid::new_v4().as_bytes();
+        // an empty writer only writes the header
+        let bytes = Writer::builder()
+            .schema(&schema)
+            .writer(Vec::new())
+            .codec(codec)
+            .marker(marker)
+            .build()
+            .into_inner()?;
+        Ok(Self {
+            fields: fields.to_vec(),
+            schema,
+            codec,
+            marker,
+            bytes,
+        })
+    }
+}
+
+impl FormatWriter for AvroWriter {
+    fn write(&mut self, batch: &RecordBatch) -> crate::Result<()> {
+        let columns = self
+            .fields
+            .iter()
+            .zip(batch.columns())
+            .map(|(field, column)| to_avro_values(field.data_type(), column.as_ref()))
+            .collect::<crate::Result<Vec<_>>>()?;
+        let mut writer =
+            Writer::append_to_with_codec(&self.schema, &mut self.bytes, self.codec, self.marker);
+        for row in 0..batch.num_rows() {
+            writer.append(Value::Record(
+                self.fields
+                    .iter()
+                    .zip(&columns)
+                    .map(|(field, values)| (field.name().to_string(), values[row].clone()))
+                    .collect(),
+            ))?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    fn length(&self) -> u64 {
+        self.bytes.len() as u64
+    }
+
+    fn close(self: Box<Self>) -> crate::Result<Bytes> {
+        Ok(Bytes::from(self.bytes))
+    }
+}
+
+/// Convert the field to the avro field of the record with the given name, nullable types are
+/// unions with null.
+fn avro_field(record_name: &str, field: &DataField) -> crate::Result<serde_json::Value> {
+    let data_type = field.data_type();
+    let avro_type = avro_type(&format!("{record_name}_{}", field.name()), data_type)?;
+    Ok(if data_type.is_nullable() {
+        json!({"name": field.name(), "type": avro_type, "default": null})
+    } else {
+        json!({"name": field.name(), "type": avro_type})
+    })
+}
+
+/// Convert the data type to the avro type, named types are named by the given name.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-format/src/main/java/org/apache/paimon/format/avro/AvroSchemaConverter.java>
+fn avro_type(name: &str, data_type: &DataType) -> crate::Result<serde_json::Value> {
+    let avro_type = match data_type {
+        DataType::Boolean(_) => json!("boolean"),
+        DataType::TinyInt(_) | DataType::SmallInt(_) | DataType::Int(_) => json!("int"),
+        DataType::BigInt(_) => json!("long"),
+        DataType::Float(_) => json!("float"),
+        DataType::Double(_) => json!("double"),
+        DataType::Char(_) | DataType::VarChar(_) => json!("string"),
+        DataType::Binary(_) | DataType::VarBinary(_) => json!("bytes"),
+        DataType::Date(_) => json!({"type": "int", "logicalType": "date"}),
+        DataType::Time(_) => json!({"type": "int", "logicalType": "time-millis"}),
+        DataType::Timestamp(_) | DataType::LocalZonedTimestamp(_) => {
+            let logical_type = match to_arrow_time_unit(data_type) {
+                Some(TimeUnit::Millisecond) => "timestamp-millis",
+                Some(TimeUnit::Microsecond) => "timestamp-micros",
+                _ => {
+                    return DataTypeInvalidSnafu {
+                        message: format!(
+                            "avro does not support {data_type}, only precision up to 6"
+                        ),
+                    }
+                    .fail()
+                }
+            };
+            json!({"type": "long", "logicalType": logical_type})
+        }
+        DataType::Decimal(t) => json!({
+            "type": "bytes",
+            "logicalType": "decimal",
+            "precision": t.precision(),
+            "scale": t.scale(),
+        }),
+        DataType::Array(t) => json!({
+            "type": "array",
+            "items": avro_type(name, t.element_type())?,
+        }),
+        DataType::Map(_) | DataType::Multiset(_) => {
+            let (key_type, value_type) = map_types(data_type);
+            // maps with string keys are avro maps, otherwise arrays of key value records
+            if matches!(key_type, DataType::Char(_) | DataType::VarChar(_)) {
+                json!({"type": "map", "values": avro_type(name, &value_type)?})
+            } else {
+                json!({
+                    "type": "array",
+                    "items": {
+                        "type": "record",
+                        "name": name,
+                        "fields": [
+                            {"name": "key", "type": avro_type(&format!("{name}_key"), &key_type)?},
+                            {"name": "value", "type": avro_type(&format!("{name}_value"), &value_type)?},
+                        ],
+                    },
+                })
+            }
+        }
+        DataType::Row(t) => json!({
+            "type": "record",
+            "name": name,
+            "fields": t
+                .fields()
+                .iter()
+                .map(|field| avro_field(name, field))
+                .collect::<crate::Result<Vec<_>>>()?,
+        }),
+    };
+    Ok(if data_type.is_nullable() {
+        json!(["null", avro_type])
+    } else {
+        avro_type
+    })
+}
+
+fn to_arrow_time_unit(data_type: &DataType) -> Option<TimeUnit> {
+    match crate::format::to_arrow_type(data_type) {
+        ArrowDataType::Timestamp(unit, _) => Some(unit),
+        _ => None,
+    }
+}
+
+/// The key and value types of maps, multisets are maps of the elements to their counts.
+fn map_types(data_type: &DataType) -> (DataType, DataType) {
+    match data_type {
+        DataType::Map(t) => (t.key_type().clone(), t.value_type().clone()),
+        DataType::Multiset(t) => (
+            t.element_type().clone(),
+            DataType::Int(crate::spec::IntType::with_nullable(false)),
+        ),
+        _ => unreachable!("only maps and multisets have keys and values"),
+    }
+}
+
+/// The minimal big endian two's complement bytes of the unscaled decimal.
+fn decimal_bytes(unscaled: i128) -> Vec<u8> {
+    let bytes = unscaled.to_be_bytes();
+    let sign = if unscaled < 0 { 0xff } else { 0 };
+    let start = bytes
+        .windows(2)
+        .position(|w| w[0] != sign || (w[1] & 0x80 != 0) != (sign != 0))
+        .unwrap_or(bytes.len() - 1);
+    bytes[start..].to_vec()
+}
+
+/// Convert the arrow array to the avro values of its rows, the values of nullable types are
+/// unions with null.
+fn to_avro_values(data_type: &DataType, array: &dyn Array) -> crate::Result<Vec<Value>> {
+    let values: Vec<Value> = match data_type {
+        DataType::Boolean(_) => array
+            .as_boolean()
+            .iter()
+            .map(value_of(Value::Boolean))
+            .collect(),
+        DataType::TinyInt(_) => array
+            .as_primitive::<Int8Type>()
+            .iter()
+            .map(value_of(|v| Value::Int(v as i32)))
+            .collect(),
+        DataType::SmallInt(_) => array
+            .as_primitive::<Int16Type>()
+            .iter()
+            .map(value_of(|v| Value::Int(v as i32)))
+            .collect(),
+        DataType::Int(_) => array
+            .as_primitive::<Int32Type>()
+            .iter()
+            .map(value_of(Value::Int))
+            .collect(),
+        DataType::BigInt(_) => array
+            .as_primitive::<Int64Type>()
+            .iter()
+            .map(value_of(Value::Long))
+            .collect(),
+        DataType::Float(_) => array
+            .as_primitive::<Float32Type>()
+            .iter()
+            .map(value_of(Value::Float))
+            .collect(),
+        DataType::Double(_) => array
+            .as_primitive::<Float64Type>()
+            .iter()
+            .map(value_of(Value::Double))
+            .collect(),
+        DataType::Char(_) | DataType::VarChar(_) => array
+            .as_string::<i32>()
+            .iter()
+            .map(value_of(|v: &str| Value::String(v.to_string())))
+            .collect(),
+        DataType::Binary(_) | DataType::VarBinary(_) => array
+            .as_binary::<i32>()
+            .iter()
+            .map(value_of(|v: &[u8]| Value::Bytes(v.to_vec())))
+            .collect(),
+        DataType::Date(_) => array
+            .as_primitive::<Date32Type>()
+            .iter()
+            .map(value_of(Value::Date))
+            .collect(),
+        DataType::Time(_) => array
+            .as_primitive::<Time32MillisecondType>()
+            .iter()
+            .map(value_of(Value::TimeMillis))
+            .collect(),
+        DataType::Timestamp(_) | DataType::LocalZonedTimestamp(_) => {
+            let timestamp = match to_arrow_time_unit(data_type) {
+                Some(TimeUnit::Millisecond) => Value::TimestampMillis,
+                _ => Value::TimestampMicros,
+            };
+            arrow_cast::cast(array, &ArrowDataType::Int64)?
+                .as_primitive::<Int64Type>()
+                .iter()
+                .map(value_of(timestamp))
+                .collect()
+        }
+        DataType::Decimal(_) => array
+            .as_primitive::<Decimal128Type>()
+            .iter()
+            .map(value_of(|v| {
+                Value::Decimal(Decimal::from(decimal_bytes(v)))
+            }))
+            .collect(),
+        DataType::Array(t) => {
+            let list = array.as_list::<i32>();
+            let elements = to_avro_values(t.element_type(), list.values().as_ref())?;
+            let offsets = list.value_offsets();
+            (0..list.len())
+                .map(|i| {
+                    list.is_valid(i).then(|| {
+                        let (start, end) = (offsets[i] as usize, offsets[i + 1] as usize);
+                        Value::Array(elements[start..end].to_vec())
+                    })
+                })
+                .map(value_of(|v| v))
+                .collect()
+        }
+        DataType::Map(_) | DataType::Multiset(_) => {
+            let (key_type, value_type) = map_types(data_type);
+            let map = array.as_map();
+            let keys = to_avro_values(&key_type, map.keys().as_ref())?;
+            let values = to_avro_values(&value_type, map.values().as_ref())?;
+            let offsets = map.value_offsets();
+            let string_keys = matches!(key_type, DataType::Char(_) | DataType::VarChar(_));
+            (0..map.len())
+                .map(|i| {
+                    if !map.is_valid(i) {
+                        return Ok(None);
+                    }
+                    let entries = (offsets[i] as usize..offsets[i + 1] as usize)
+                        .map(|j| (keys[j].clone(), values[j].clone()));
+                    if !string_keys {
+                        return Ok(Some(Value::Array(
+                            entries
+                                .map(|(k, v)| {
+                                    Value::Record(vec![
+                                        ("key".to_string(), k),
+                                        ("value".to_string(), v),
+                                    ])
+                                })
+                                .collect(),
+                        )));
+                    }
+                    entries
+                        .map(|(k, v)| match unwrap_union(k) {
+                            Value::String(k) => Ok((k, v)),
+                            k => DataTypeInvalidSnafu {
+                                message: format!("avro map key {k:?} is not a string"),
+                            }
+                            .fail(),
+                        })
+                        .collect::<crate::Result<HashMap<_, _>>>()
+                        .map(|entries| Some(Value::Map(entries)))
+                })
+                .collect::<crate::Result<Vec<_>>>()?
+                .into_iter()
+                .map(value_of(|v| v))
+                .collect()
+        }
+        DataType::Row(t) => {
+            let row = array.as_struct();
+            let columns = t
+                .fields()
+                .iter()
+                .zip(row.columns())
+                .map(|(field, column)| to_avro_values(field.data_type(), column.as_ref()))
+                .collect::<crate::Result<Vec<_>>>()?;
+            (0..row.len())
+                .map(|i| {
+                    row.is_valid(i).then(|| {
+                        Value::Record(
+                            t.fields()
+                                .iter()
+                                .zip(&columns)
+                                .map(|(field, values)| {
+                                    (field.name().to_string(), values[i].clone())
+                                })
+                                .collect(),
+                        )
+                    })
+                })
+                .map(value_of(|v| v))
+                .collect()
+        }
+    };
+    if !data_type.is_nullable() {
+        return Ok(values);
+    }
+    Ok(values
+        .into_iter()
+        .map(|value| match value {
+            Value::Null => Value::Union(0, Box::new(Value::Null)),
+            value => Value::Union(1, Box::new(value)),
+        })
+        .collect())
+}
+
+fn unwrap_union(value: Value) -> Value {
+    match value {
+        Value::Union(_, value) => *value,
+        value => value,
+    }
+}
+
+/// Convert an optional arrow value to an avro value, None is null.
+fn value_of<T>(f: impl Fn(T) -> Value) -> impl Fn(Option<T>) -> Value {
+    move |value| value.map_or(Value::Null, &f)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::avro_reader::AvroReader;
+    use crate::format::{to_arrow_schema, FormatReader};
+    use arrow_array::{
+        ArrayRef, BooleanArray, Date32Array, Decimal128Array, Int32Array, ListArray, MapArray,
+        StringArray, StructArray, TimestampMicrosecondArray,
+    };
+    use arrow_buffer::OffsetBuffer;
+    use arrow_schema::Field;
+    use arrow_select::concat::concat_batches;
+    use std::sync::Arc;
+
+    fn fields() -> Vec<DataField> {
+        [
+            ("id", "INT NOT NULL"),
+            ("name", "STRING"),
+            ("price", "DECIMAL(10, 2)"),
+            ("dt", "DATE"),
+            ("ts", "TIMESTAMP(6)"),
+            ("tags", "ARRAY<STRING>"),
+            ("attrs", "MAP<STRING NOT NULL, INT>"),
+            ("counts", "MAP<INT NOT NULL, INT>"),
+            ("nested", "ROW<a BOOLEAN>"),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(id, (name, data_type))| {
+            DataField::new(id as i32, name.to_string(), data_type.parse().unwrap())
+        })
+        .collect()
+    }
+
+    fn map_array(field: &Field, keys: ArrayRef) -> ArrayRef {
+        let entries = match field.data_type() {
+            ArrowDataType::Map(entries, _) => entries.clone(),
+            _ => unreachable!(),
+        };
+        let struct_fields = match entries.data_type() {
+            ArrowDataType::Struct(fields) => fields.clone(),
+            _ => unreachable!(),
+        };
+        let values = StructArray::new(
+            struct_fields,
+            vec![
+                keys,
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(3)])),
+            ],
+            None,
+        );
+        Arc::new(
+            MapArray::try_new(
+                entries,
+                OffsetBuffer::from_lengths([2, 0, 1]),
+                values,
+                None,
+                false,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn batch(fields: &[DataField]) -> RecordBatch {
+        let schema = Arc::new(to_arrow_schema(fields));
+        let tags = match schema.field(5).data_type() {
+            ArrowDataType::List(field) => field.clone(),
+            _ => unreachable!(),
+        };
+        let nested = match schema.field(8).data_type() {
+            ArrowDataType::Struct(fields) => fields.clone(),
+            _ => unreachable!(),
+        };
+        RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])),
+                Arc::new(
+                    Decimal128Array::from(vec![Some(-12345), Some(0), None])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+                Arc::new(Date32Array::from(vec![Some(19000), None, Some(19002)])),
+                Arc::new(TimestampMicrosecondArray::from(vec![
+                    Some(1_725_614_755_039_001),
+                    None,
+                    Some(-1),
+                ])),
+                Arc::new(ListArray::new(
+                    tags,
+                    OffsetBuffer::from_lengths([2, 0, 0]),
+                    Arc::new(StringArray::from(vec![Some("x"), None])),
+                    Some(vec![true, true, false].into()),
+                )),
+                map_array(
+                    schema.field(6),
+                    Arc::new(StringArray::from(vec!["k1", "k2", "k3"])),
+                ),
+                map_array(schema.field(7), Arc::new(Int32Array::from(vec![7, 8, 9]))),
+                Arc::new(StructArray::new(
+                    nested,
+                    vec![Arc::new(BooleanArray::from(vec![
+                        Some(true),
+                        None,
+                        Some(false),
+                    ]))],
+                    Some(vec![true, true, false].into()),
+                )),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_write_avro() {
+        let fields = fields();
+        let batch = batch(&fields);
+        for compression in ["none", "deflate", "snappy", "zstd"] {
+            let mut writer: Box<dyn FormatWriter> =
+                Box::new(AvroWriter::try_new(&fields, &FileCompression::new(compression)).unwrap());
+            writer.write(&batch.slice(0, 2)).unwrap();
+            let length = writer.length();
+            writer.write(&batch.slice(2, 1)).unwrap();
+            assert!(writer.length() > length);
+            let bytes = writer.close().unwrap();
+
+            let batches = AvroReader
+                .read(bytes, &fields, 1024)
+                .unwrap()
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap();
+            let read = concat_batches(&batch.schema(), &batches).unwrap();
+            // the entries of avro maps are unordered
+            for i in [0, 1, 2, 3, 4, 5, 7, 8] {
+                assert_eq!(
+                    read.column(i),
+                    batch.column(i),
+                    "column {i} of {compression}"
+                );
+            }
+            let attrs = read.column(6).as_map();
+            assert_eq!(attrs.value_offsets(), &[0, 2, 2, 3]);
+            assert_eq!(attrs.value(2).column(0).as_string::<i32>().value(0), "k3");
+        }
+
+        assert!(matches!(
+            AvroWriter::try_new(&fields, &FileCompression::new("lz4")),
+            Err(crate::Error::FileCompressionUnsupported { .. })
+        ));
+        let precision_9 = [DataField::new(
+            0,
+            "ts".to_string(),
+            "TIMESTAMP(9)".parse().unwrap(),
+        )];
+        assert!(AvroWriter::try_new(&precision_9, &FileCompression::new("none")).is_err());
+    }
+
+    #[test]
+    fn test_decimal_bytes() {
+        assert_eq!(decimal_bytes(0), vec![0]);
+        assert_eq!(decimal_bytes(-1), vec![0xff]);
+        assert_eq!(decimal_bytes(127), vec![0x7f]);
+        assert_eq!(decimal_bytes(128), vec![0, 0x80]);
+        assert_eq!(decimal_bytes(-128), vec![0x80]);
+        assert_eq!(decimal_bytes(-129), vec![0xff, 0x7f]);
+    }
+}
@@ -0,0 +1,85 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::{to_arrow_field, ArrowRecordBatchIter, FormatReader};
+use crate::spec::DataField;
+use arrow_csv::reader::Format;
+use arrow_csv::ReaderBuilder;
+use arrow_schema::{DataType as ArrowDataType, Field, Schema};
+use bytes::{Buf, Bytes};
+use std::sync::Arc;
+
+/// Reader of csv files, whose columns are mapped to the fields by the names in the headers,
+/// or by their positions if the files have no headers.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-format/src/main/java/org/apache/paimon/format/csv/CsvFileFormat.java>
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CsvReader {
+    pub(crate) include_header: bool,
+    pub(crate) delimiter: u8,
+}
+
+impl CsvReader {
+    /// Get the format of the csv files of the reader.
+    pub(crate) fn format(&self) -> Format {
+        Format::default()
+            .with_header(self.include_header)
+            .with_delimiter(self.delimiter)
+    }
+}
+
+impl FormatReader for CsvReader {
+    fn read(
+        &self,
+        bytes: Bytes,
+        fields: &[DataField],
+        batch_size: usize,
+    ) -> crate::Result<ArrowRecordBatchIter> {
+        // only the header, or the first line, to get the names and the number of the columns
+        let (columns, _) = self
+            .format()
+            .infer_schema(bytes.clone().reader(), Some(1))?;
+        let mut projection = Vec::new();
+        let columns: Vec<Field> = columns
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let field = if self.include_header {
+                    fields.iter().find(|field| field.name() == column.name())
+                } else {
+                    fields.get(i)
+                };
+                match field {
+                    Some(field) => {
+                        projection.push(i);
+                        to_arrow_field(field).with_nullable(true)
+                    }
+                    None => Field::new(column.name(), ArrowDataType::Utf8, true),
+                }
+            })
+            .collect();
+        let reader = ReaderBuilder::new(Arc::new(Schema::new(columns)))
+            .with_format(self.format())
+            .with_projection(projection)
+            .with_batch_size(batch_size)
+            .build(bytes.reader())?;
+        Ok(Box::new(
+            reader.map(|batch| batch.map_err(crate::Error::from)),
+        ))
+    }
+}
@@ -0,0 +1,459 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::DataTypeInvalidSnafu;
+use crate::format::parquet_reader::ParquetReader;
+use crate::format::{format_reader, to_arrow_schema, to_arrow_type, ArrowRecordBatchIter};
+use crate::io::{CoalescingFileRead, FileIO};
+use crate::memory::MemoryPool;
+use crate::spec::{
+    key_field, CoreOptions, DataField, DataFileMeta, DataType, RowType, TableSchema,
+    KEY_FIELD_ID_START, SEQUENCE_NUMBER_FIELD_ID, VALUE_KIND_FIELD_ID,
+};
+use crate::utils::SchemaManager;
+use arrow_array::cast::AsArray;
+use arrow_array::{new_null_array, Array, ArrayRef, RecordBatch, StructArray};
+use arrow_schema::{DataType as ArrowDataType, SchemaRef};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Default number of rows of the record batches read.
+pub const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// Reader of data files into arrow record batches of the fields to read.
+///
+/// Data files may be written by older schemas, fields are mapped by their ids to the schema
+/// the file was written with, so that renamed fields are read from the columns of the old
+/// names, fields added later are read as nulls and fields of updated types are casted. The
+/// mappings are built once for each schema id.
+///
+/// The row types of the read fields may be pruned to some of their nested fields, the nested
+/// fields are also mapped by their ids and only they are read from the files.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/utils/BulkFormatMapping.java>
+#[derive(Debug, Clone)]
+pub struct DataFileReader {
+    file_io: Arc<dyn FileIO>,
+    schema_manager: SchemaManager,
+    table_schema: TableSchema,
+    read_fields: Vec<DataField>,
+    batch_size: usize,
+    mappings: Arc<Mutex<HashMap<i64, Arc<FieldMapping>>>>,
+    memory_pool: Option<Arc<MemoryPool>>,
+}
+
+/// Mapping of the read fields to the fields of the data files of a schema.
+#[derive(Debug)]
+struct FieldMapping {
+    /// The fields of the read fields in the data files, none if the field doesn't exist yet.
+    data_fields: Vec<Option<DataField>>,
+    /// The arrow types of the data fields.
+    data_types: Vec<Option<ArrowDataType>>,
+    /// The fields to read from the data files.
+    columns: Vec<DataField>,
+}
+
+impl DataFileReader {
+    pub fn new(
+        file_io: Arc<dyn FileIO>,
+        schema_manager: SchemaManager,
+        table_schema: TableSchema,
+        read_fields: Vec<DataField>,
+    ) -> Self {
+        Self {
+            file_io,
+            schema_manager,
+            table_schema,
+            read_fields,
+            batch_size: DEFAULT_BATCH_SIZE,
+            mappings: Default::default(),
+            memory_pool: None,
+        }
+    }
+
+    /// Set the max number of rows of the record batches read.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Reserve the size of each data file read from the memory pool until its batches are
+    /// dropped, reads fail if the pool is exhausted.
+    pub fn with_memory_pool(mut self, memory_pool: Arc<MemoryPool>) -> Self {
+        self.memory_pool = Some(memory_pool);
+        self
+    }
+
+    /// Get the arrow schema of the record batches read.
+    pub fn read_schema(&self) -> SchemaRef {
+        Arc::new(to_arrow_schema(&self.read_fields))
+    }
+
+    /// Read the data file at the path.
+    ///
+    /// The format is decided by the extension of the file name, or the `file.format` option
+    /// of the table if the file name has no extension.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "read.file",
+            skip_all,
+            fields(path = path, file_size = file.file_size, row_count = file.row_count)
+        )
+    )]
+    pub async fn read(
+        &self,
+        path: &str,
+        file: &DataFileMeta,
+    ) -> crate::Result<ArrowRecordBatchIter> {
+        let mapping = self.mapping(file.schema_id).await?;
+        let columns = &mapping.columns;
+
+        let options = CoreOptions::new(self.table_schema.options());
+        let format = match file.file_format() {
+            Some(format) => format.to_string(),
+            None => options.file_format(),
+        };
+        let input = self.file_io.new_input(path)?;
+        let reservation = match &self.memory_pool {
+            Some(memory_pool) => {
+                let mut reservation = memory_pool.reservation(format!("DataFileReader of {path}"));
+                reservation.try_grow(file.file_size.max(0) as usize)?;
+                Some(reservation)
+            }
+            None => None,
+        };
+        let batches = if format.eq_ignore_ascii_case("parquet") && options.read_coalesce_enabled() {
+            let file_size = match u64::try_from(file.file_size) {
+                Ok(file_size) if file_size > 0 => file_size,
+                _ => input.metadata().await?.size,
+            };
+            let reader = CoalescingFileRead::new(
+                input.reader().await?,
+                options.read_coalesce_max_gap(),
+                options.read_coalesce_max_size(),
+                options.read_prefetch_ranges(),
+            );
+            ParquetReader
+                .read_ranges(&reader, file_size, columns, self.batch_size)
+                .await?
+        } else {
+            let bytes = input.read().await?;
+            format_reader(&format)?.read(bytes, columns, self.batch_size)?
+        };
+
+        let schema = self.read_schema();
+        Ok(Box::new(batches.map(move |batch| {
+            // the file is accounted until the batches are dropped
+            let _reservation = &reservation;
+            let batch = batch?;
+            let columns = schema
+                .fields()
+                .iter()
+                .zip(mapping.data_fields.iter().zip(&mapping.data_types))
+                .map(|(field, (data_field, data_type))| {
+                    let column = data_field
+                        .as_ref()
+                        .and_then(|data_field| batch.column_by_name(data_field.name()));
+                    let column = match (column, data_type) {
+                        (Some(column), Some(data_type)) => project_struct(column, data_type)?,
+                        _ => return Ok(new_null_array(field.data_type(), batch.num_rows())),
+                    };
+                    Ok(if column.data_type() == field.data_type() {
+                        column
+                    } else {
+                        arrow_cast::cast(&column, field.data_type())?
+                    })
+                })
+                .collect::<crate::Result<Vec<ArrayRef>>>()?;
+            Ok(RecordBatch::try_new(schema.clone(), columns)?)
+        })))
+    }
+
+    /// Get the mapping of the read fields to the fields of the data files of the schema.
+    async fn mapping(&self, schema_id: i64) -> crate::Result<Arc<FieldMapping>> {
+        if let Some(mapping) = self.mappings.lock().unwrap().get(&schema_id) {
+            return Ok(mapping.clone());
+        }
+        let data_schema = if schema_id == self.table_schema.id() {
+            self.table_schema.clone()
+        } else {
+            self.schema_manager.schema(schema_id).await?
+        };
+        let data_fields: Vec<Option<DataField>> = self
+            .read_fields
+            .iter()
+            .map(|field| {
+                data_field(&data_schema, field).map(|data_field| {
+                    let data_type = prune_type(data_field.data_type(), field.data_type());
+                    data_field.with_data_type(data_type)
+                })
+            })
+            .collect();
+        for (field, data_field) in self.read_fields.iter().zip(&data_fields) {
+            let Some(data_field) = data_field else {
+                continue;
+            };
+            let (from, to) = (
+                to_arrow_type(data_field.data_type()),
+                to_arrow_type(field.data_type()),
+            );
+            if !arrow_cast::can_cast_types(&from, &to) {
+                return DataTypeInvalidSnafu {
+                    message: format!(
+                        "field {} of schema {schema_id} can't be read as {}",
+                        data_field.name(),
+                        field.data_type()
+                    ),
+                }
+                .fail();
+            }
+        }
+        let columns = data_fields.iter().flatten().cloned().collect();
+        let data_types = data_fields
+            .iter()
+            .map(|field| field.as_ref().map(|field| to_arrow_type(field.data_type())))
+            .collect();
+        let mapping = Arc::new(FieldMapping {
+            data_fields,
+            data_types,
+            columns,
+        });
+        self.mappings
+            .lock()
+            .unwrap()
+            .insert(schema_id, mapping.clone());
+        Ok(mapping)
+    }
+}
+
+/// Get the field in the data schema of the read field, the key fields and the system fields of
+/// primary key tables are resolved by the ids of their value fields.
+fn data_field(data_schema: &TableSchema, field: &DataField) -> Option<DataField> {
+    match field.id() {
+        SEQUENCE_NUMBER_FIELD_ID | VALUE_KIND_FIELD_ID => Some(field.clone()),
+        id if id >= KEY_FIELD_ID_START => data_schema
+            .fields()
+            .iter()
+            .find(|f| f.id() == id - KEY_FIELD_ID_START)
+            .map(key_field),
+        id => data_schema.fields().iter().find(|f| f.id() == id).cloned(),
+    }
+}
+
+/// Prune the row types of the data type to the nested fields of the read type, by their ids.
+fn prune_type(data_type: &DataType, read_type: &DataType) -> DataType {
+    let (DataType::Row(data_row), DataType::Row(read_row)) = (data_type, read_type) else {
+        return data_type.clone();
+    };
+    let fields = data_row
+        .fields()
+        .iter()
+        .filter_map(|data_field| {
+            let read_field = read_row
+                .fields()
+                .iter()
+                .find(|f| f.id() == data_field.id())?;
+            let data_type = prune_type(data_field.data_type(), read_field.data_type());
+            Some(data_field.clone().with_data_type(data_type))
+        })
+        .collect();
+    DataType::Row(RowType::with_nullable(data_type.is_nullable(), fields))
+}
+
+/// Project the struct columns to the fields of the struct type by their names, for the formats
+/// reading the whole struct columns.
+fn project_struct(column: &ArrayRef, data_type: &ArrowDataType) -> crate::Result<ArrayRef> {
+    let ArrowDataType::Struct(fields) = data_type else {
+        return Ok(column.clone());
+    };
+    if column.data_type() == data_type {
+        return Ok(column.clone());
+    }
+    let Some(array) = column.as_struct_opt() else {
+        return Ok(column.clone());
+    };
+    let children = fields
+        .iter()
+        .map(|field| match array.column_by_name(field.name()) {
+            Some(child) => project_struct(child, field.data_type()),
+            None => Ok(new_null_array(field.data_type(), array.len())),
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+    Ok(Arc::new(StructArray::try_new(
+        fields.clone(),
+        children,
+        array.nulls().cloned(),
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataType, IntType, RowType, Schema, SchemaChange, VarCharType};
+    use crate::test_utils::data_file;
+    use arrow_array::{Int32Array, Int64Array, StringArray};
+    use arrow_select::concat::concat_batches;
+    use bytes::Bytes;
+
+    use parquet::arrow::ArrowWriter;
+
+    #[tokio::test]
+    async fn test_read_parquet_with_schema_evolution() {
+        let table_path = "file:/tmp/test_read_parquet_with_schema_evolution";
+        let file_io = FileIOBuilder::from_url(table_path)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{table_path}/")).await;
+        let schema_manager = SchemaManager::new(file_io.clone(), table_path);
+        let old_schema = schema_manager
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "id".to_string(), DataType::Int(IntType::new())),
+                        DataField::new(
+                            1,
+                            "name".to_string(),
+                            DataType::VarChar(VarCharType::default()),
+                        ),
+                    ])
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        // the file is written with the old schema
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(old_schema.fields())),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec!["a", "b", "c"])),
+            ],
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let path = format!("{table_path}/bucket-0/data-0.parquet");
+        file_io
+            .new_output(&path)
+            .unwrap()
+            .write(Bytes::from(bytes))
+            .await
+            .unwrap();
+        let file = DataFileMeta {
+            row_count: 3,
+            max_sequence_number: 2,
+            ..data_file("data-0.parquet")
+        };
+
+        let new_schema = schema_manager
+            .commit_changes(&[
+                SchemaChange::rename_column("name".to_string(), "full_name".to_string()),
+                SchemaChange::update_column_type(
+                    "id".to_string(),
+                    DataType::BigInt(crate::spec::BigIntType::new()),
+                ),
+                SchemaChange::add_column("age".to_string(), DataType::Int(IntType::new())),
+            ])
+            .await
+            .unwrap();
+        let read_fields = ["age", "full_name", "id"]
+            .iter()
+            .map(|name| {
+                new_schema
+                    .fields()
+                    .iter()
+                    .find(|f| f.name() == *name)
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+        let reader = DataFileReader::new(file_io, schema_manager, new_schema, read_fields)
+            .with_batch_size(2);
+
+        let batches = reader
+            .read(&path, &file)
+            .await
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+        let batch = concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(batch.schema(), reader.read_schema());
+        assert_eq!(batch.column(0).null_count(), 3);
+        assert_eq!(
+            batch.column(1).as_ref(),
+            &StringArray::from(vec!["a", "b", "c"]) as &dyn arrow_array::Array
+        );
+        assert_eq!(
+            batch.column(2).as_ref(),
+            &Int64Array::from(vec![1, 2, 3]) as &dyn arrow_array::Array
+        );
+
+        assert!(matches!(
+            reader
+                .read(
+                    &path,
+                    &DataFileMeta {
+                        file_name: "data-0.orc2".to_string(),
+                        ..file.clone()
+                    }
+                )
+                .await,
+            Err(crate::Error::FileFormatUnsupported { .. })
+        ));
+
+        // the mapping of the old schema is built only once
+        let schema_path = format!("{table_path}/schema/schema-0");
+        reader.file_io.delete_file(&schema_path).await.unwrap();
+        assert!(reader.read(&path, &file).await.is_ok());
+
+        // the fields which can't be cast to the read types are rejected
+        let read_fields = vec![DataField::new(
+            0,
+            "id".to_string(),
+            DataType::Row(RowType::new(vec![DataField::new(
+                10,
+                "f0".to_string(),
+                DataType::Int(IntType::new()),
+            )])),
+        )];
+        let schema = reader.table_schema.clone();
+        let reader =
+            DataFileReader::new(reader.file_io, reader.schema_manager, schema, read_fields);
+        let err = reader
+            .read(
+                &path,
+                &DataFileMeta {
+                    schema_id: 1,
+                    ..file
+                },
+            )
+            .await
+            .err()
+            .unwrap();
+        assert!(
+            matches!(&err, crate::Error::DataTypeInvalid { message }
+                if message.contains("field id of schema 1 can't be read as ROW<")),
+            "{err}"
+        );
+    }
+}
@@ -0,0 +1,388 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::file_index::{DataFileIndex, DataFileIndexWriter, FileIndexOptions};
+use crate::format::{
+    format_writer, to_binary_row, FileCompression, FormatWriter, StatsCollector, StatsMode,
+};
+use crate::io::FileIO;
+use crate::spec::{DataField, DataFileMeta, DataType, FileSource, RowKind, EMPTY_BINARY_ROW};
+use crate::utils::DataFilePathFactory;
+use arrow_array::cast::AsArray;
+use arrow_array::types::Int8Type;
+use arrow_array::{Array, RecordBatch};
+use chrono::Utc;
+use std::sync::Arc;
+
+/// Writer of the data files of a bucket, rolling to a new file when the target file size is
+/// reached.
+///
+/// The record batches are of the layout of the data files. Key value files of primary key
+/// tables start with the key fields, followed by the sequence number and the value kind, the
+/// min and max keys of a file are the keys of its first and last rows, so the batches are
+/// expected to be sorted by key. The statistics of the key fields and the value fields are
+/// collected into the metas of the files, along with the file indexes of the columns if any.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/io/RollingFileWriter.java>
+pub(crate) struct DataFileWriter {
+    file_io: Arc<dyn FileIO>,
+    path_factory: DataFilePathFactory,
+    fields: Vec<DataField>,
+    stats_modes: Vec<StatsMode>,
+    file_index: FileIndexOptions,
+    compression: FileCompression,
+    key_arity: usize,
+    schema_id: i64,
+    level: i32,
+    file_source: FileSource,
+    target_file_size: u64,
+    current: Option<Box<RollingFile>>,
+    files: Vec<DataFileMeta>,
+}
+
+impl std::fmt::Debug for DataFileWriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataFileWriter")
+            .field("path_factory", &self.path_factory)
+            .field("fields", &self.fields)
+            .field("files", &self.files)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The data file being written.
+struct RollingFile {
+    file_name: String,
+    writer: Box<dyn FormatWriter>,
+    row_count: i64,
+    delete_row_count: i64,
+    min_key: Option<Vec<u8>>,
+    max_key: Vec<u8>,
+    min_sequence_number: i64,
+    max_sequence_number: i64,
+    key_stats: StatsCollector,
+    value_stats: StatsCollector,
+    index_writer: Option<DataFileIndexWriter>,
+}
+
+impl DataFileWriter {
+    pub(crate) fn new(
+        file_io: Arc<dyn FileIO>,
+        path_factory: DataFilePathFactory,
+        fields: Vec<DataField>,
+        compression: FileCompression,
+        key_arity: usize,
+        schema_id: i64,
+        target_file_size: i64,
+    ) -> Self {
+        Self {
+            file_io,
+            path_factory,
+            stats_modes: vec![StatsMode::default(); fields.len()],
+            file_index: FileIndexOptions::default(),
+            fields,
+            compression,
+            key_arity,
+            schema_id,
+            level: 0,
+            file_source: FileSource::Append,
+            target_file_size: target_file_size.max(1) as u64,
+            current: None,
+            files: vec![],
+        }
+    }
+
+    /// Collect the statistics of the fields by the modes instead of the default `truncate(16)`.
+    pub(crate) fn with_stats_modes(mut self, stats_modes: Vec<StatsMode>) -> Self {
+        self.stats_modes = stats_modes;
+        self
+    }
+
+    /// Write the file indexes of the columns of the files.
+    pub(crate) fn with_file_index(mut self, file_index: FileIndexOptions) -> Self {
+        self.file_index = file_index;
+        self
+    }
+
+    /// Write the files produced by compaction into the given level instead of level 0.
+    pub(crate) fn with_compact_level(mut self, level: i32) -> Self {
+        self.level = level;
+        self.file_source = FileSource::Compact;
+        self
+    }
+
+    /// Write the batch whose rows have sequence numbers within the given range.
+    pub(crate) async fn write(
+        &mut self,
+        batch: &RecordBatch,
+        min_sequence_number: i64,
+        max_sequence_number: i64,
+    ) -> crate::Result<()> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        // the sequence number and the value kind are between the key and the value fields
+        let value_start = if self.key_arity > 0 {
+            self.key_arity + 2
+        } else {
+            0
+        };
+        let file = match &mut self.current {
+            Some(file) => file,
+            None => self.current.insert(Box::new(RollingFile {
+                file_name: self.path_factory.new_file_name(),
+                writer: format_writer(self.path_factory.format(), &self.fields, &self.compression)?,
+                row_count: 0,
+                delete_row_count: 0,
+                min_key: None,
+                max_key: vec![],
+                min_sequence_number,
+                max_sequence_number,
+                key_stats: StatsCollector::new(
+                    self.fields[..self.key_arity].to_vec(),
+                    self.stats_modes[..self.key_arity].to_vec(),
+                ),
+                value_stats: StatsCollector::new(
+                    self.fields[value_start..].to_vec(),
+                    self.stats_modes[value_start..].to_vec(),
+                ),
+                index_writer: self.file_index.writer()?,
+            })),
+        };
+        file.writer.write(batch)?;
+        if let Some(index_writer) = &mut file.index_writer {
+            index_writer.write(batch)?;
+        }
+        file.key_stats.collect(&batch.columns()[..self.key_arity])?;
+        file.value_stats.collect(&batch.columns()[value_start..])?;
+        file.row_count += batch.num_rows() as i64;
+        file.min_sequence_number = file.min_sequence_number.min(min_sequence_number);
+        file.max_sequence_number = file.max_sequence_number.max(max_sequence_number);
+        if self.key_arity > 0 {
+            let key_columns: Vec<&dyn Array> = batch.columns()[..self.key_arity]
+                .iter()
+                .map(|c| c.as_ref())
+                .collect();
+            let key_types: Vec<&DataType> = self.fields[..self.key_arity]
+                .iter()
+                .map(DataField::data_type)
+                .collect();
+            if file.min_key.is_none() {
+                file.min_key =
+                    Some(to_binary_row(&key_columns, &key_types, 0)?.to_serialized_bytes());
+            }
+            file.max_key = to_binary_row(&key_columns, &key_types, batch.num_rows() - 1)?
+                .to_serialized_bytes();
+            let value_kinds = batch.column(self.key_arity + 1).as_primitive::<Int8Type>();
+            for value_kind in value_kinds.values() {
+                if !RowKind::from_value(*value_kind)?.is_add() {
+                    file.delete_row_count += 1;
+                }
+            }
+        }
+
+        if file.writer.length() >= self.target_file_size {
+            self.roll().await?;
+        }
+        Ok(())
+    }
+
+    /// Close the file being written, the next batch will be written into a new file.
+    pub(crate) async fn roll(&mut self) -> crate::Result<()> {
+        let Some(file) = self.current.take() else {
+            return Ok(());
+        };
+        let bytes = file.writer.close()?;
+        let file_size = bytes.len() as i64;
+        let path = self.path_factory.to_path(&file.file_name);
+        self.file_io.new_output(&path)?.write(bytes).await?;
+        let (embedded_index, extra_files) = match file.index_writer {
+            Some(index_writer) => match index_writer.close(self.file_io.as_ref(), &path).await? {
+                DataFileIndex::Embedded(index) => (Some(index), vec![]),
+                DataFileIndex::ExtraFile(name) => (None, vec![name]),
+            },
+            None => (None, vec![]),
+        };
+
+        let empty_row = EMPTY_BINARY_ROW.to_serialized_bytes();
+        self.files.push(DataFileMeta {
+            file_name: file.file_name,
+            file_size,
+            row_count: file.row_count,
+            min_key: file.min_key.unwrap_or_else(|| empty_row.clone()),
+            max_key: if self.key_arity > 0 {
+                file.max_key
+            } else {
+                empty_row
+            },
+            key_stats: file.key_stats.stats()?,
+            value_stats: file.value_stats.stats()?,
+            min_sequence_number: file.min_sequence_number,
+            max_sequence_number: file.max_sequence_number,
+            schema_id: self.schema_id,
+            level: self.level,
+            extra_files,
+            creation_time: Utc::now(),
+            delete_row_count: Some(file.delete_row_count),
+            embedded_index,
+            file_source: Some(self.file_source.clone()),
+            value_stats_cols: None,
+        });
+        Ok(())
+    }
+
+    /// Close the writer and get the metas of the files written.
+    pub(crate) async fn close(mut self) -> crate::Result<Vec<DataFileMeta>> {
+        self.roll().await?;
+        Ok(self.files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::{format_reader, to_arrow_schema};
+    use crate::io::FileIOBuilder;
+    use crate::spec::{key_value_fields, BinaryRow, RowKind};
+    use arrow_array::{Int32Array, Int64Array, Int8Array, StringArray};
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_roll_key_value_files() {
+        let bucket_path = "file:/tmp/test_roll_key_value_files/bucket-0";
+        let file_io = FileIOBuilder::from_url(bucket_path)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{bucket_path}/")).await;
+        let value_fields = vec![
+            DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+            DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+        ];
+        let fields = key_value_fields(&value_fields[..1], &value_fields);
+        let mut writer = DataFileWriter::new(
+            file_io.clone(),
+            DataFilePathFactory::new(bucket_path, "parquet"),
+            fields.clone(),
+            FileCompression::new("zstd"),
+            1,
+            0,
+            1,
+        );
+
+        let schema = Arc::new(to_arrow_schema(&fields));
+        for (ids, kinds) in [
+            (vec![1, 2], vec![RowKind::Insert, RowKind::Delete]),
+            (vec![3], vec![RowKind::Insert]),
+        ] {
+            let n = ids.len() as i64;
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(ids.clone())),
+                    Arc::new(Int64Array::from_iter_values(
+                        ids.iter().map(|id| *id as i64),
+                    )),
+                    Arc::new(Int8Array::from_iter_values(
+                        kinds.iter().map(|kind| kind.to_value()),
+                    )),
+                    Arc::new(Int32Array::from(ids.clone())),
+                    Arc::new(StringArray::from_iter_values(
+                        ids.iter().map(|id| format!("n{id}")),
+                    )),
+                ],
+            )
+            .unwrap();
+            writer
+                .write(&batch, ids[0] as i64, ids[0] as i64 + n - 1)
+                .await
+                .unwrap();
+        }
+        let files = writer.close().await.unwrap();
+
+        // every batch exceeds the target file size of one byte
+        assert_eq!(files.len(), 2);
+        let key = |id| {
+            let mut writer = crate::spec::BinaryRowWriter::new(1);
+            writer.write_int(0, id);
+            writer.build().to_serialized_bytes()
+        };
+        assert_eq!(
+            (files[0].row_count, files[0].delete_row_count),
+            (2, Some(1))
+        );
+        assert_eq!(
+            (files[0].min_key.clone(), files[0].max_key.clone()),
+            (key(1), key(2))
+        );
+        assert_eq!(
+            (files[1].min_sequence_number, files[1].max_sequence_number),
+            (3, 3)
+        );
+        assert!(files[0].file_name.ends_with("-0.parquet"));
+        assert!(files[1].file_name.ends_with("-1.parquet"));
+        assert_eq!(
+            BinaryRow::from_serialized_bytes(&files[1].min_key)
+                .unwrap()
+                .get_int(0)
+                .unwrap(),
+            3
+        );
+
+        let bytes = file_io
+            .new_input(&format!("{bucket_path}/{}", files[0].file_name))
+            .unwrap()
+            .read()
+            .await
+            .unwrap();
+        assert_eq!(bytes.len() as i64, files[0].file_size);
+        let batches = format_reader("parquet")
+            .unwrap()
+            .read(bytes.clone(), &fields, 1024)
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches[0].num_rows(), 2);
+
+        let stats = &files[0].value_stats;
+        assert_eq!(stats.null_counts(), &[Some(0), Some(0)]);
+        let max_values = BinaryRow::from_serialized_bytes(stats.max_values()).unwrap();
+        assert_eq!(max_values.get_int(0).unwrap(), 2);
+        assert_eq!(max_values.get_string(1).unwrap(), "n2");
+        let key_stats = &files[1].key_stats;
+        let min_keys = BinaryRow::from_serialized_bytes(key_stats.min_values()).unwrap();
+        assert_eq!(min_keys.get_int(0).unwrap(), 3);
+
+        // the field ids are written into the parquet schema
+        let reader = SerializedFileReader::new(bytes).unwrap();
+        let field_ids: Vec<i32> = reader
+            .metadata()
+            .file_metadata()
+            .schema()
+            .get_fields()
+            .iter()
+            .map(|field| field.get_basic_info().id())
+            .collect();
+        assert_eq!(field_ids, fields.iter().map(|f| f.id()).collect::<Vec<_>>());
+
+        file_io
+            .delete_dir(&format!("{bucket_path}/"))
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,220 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::spec::{BinaryRow, BinaryRowWriter, DataType, Datum};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Decimal128Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type,
+    Int8Type, Time32MillisecondType, TimestampMicrosecondType, TimestampMillisecondType,
+    TimestampNanosecondType,
+};
+use arrow_array::{
+    new_null_array, Array, ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array,
+    Float32Array, Float64Array, Int16Array, Int32Array, Int64Array, Int8Array, StringArray,
+    Time32MillisecondArray, TimestampNanosecondArray,
+};
+use arrow_schema::{DataType as ArrowDataType, TimeUnit};
+use std::sync::Arc;
+
+/// Get the value at the row of the arrow array as a datum of the paimon data type, none if
+/// the value is null.
+///
+/// The array must be of the arrow type converted from the data type by
+/// [`to_arrow_type`](crate::format::to_arrow_type), nested types are not supported.
+pub fn to_datum(
+    array: &dyn Array,
+    row: usize,
+    data_type: &DataType,
+) -> crate::Result<Option<Datum>> {
+    if array.is_null(row) {
+        return Ok(None);
+    }
+    let datum = match (data_type, array.data_type()) {
+        (DataType::Boolean(_), ArrowDataType::Boolean) => {
+            Datum::Boolean(array.as_boolean().value(row))
+        }
+        (DataType::TinyInt(_), ArrowDataType::Int8) => {
+            Datum::TinyInt(array.as_primitive::<Int8Type>().value(row))
+        }
+        (DataType::SmallInt(_), ArrowDataType::Int16) => {
+            Datum::SmallInt(array.as_primitive::<Int16Type>().value(row))
+        }
+        (DataType::Int(_), ArrowDataType::Int32) => {
+            Datum::Int(array.as_primitive::<Int32Type>().value(row))
+        }
+        (DataType::BigInt(_), ArrowDataType::Int64) => {
+            Datum::BigInt(array.as_primitive::<Int64Type>().value(row))
+        }
+        (DataType::Float(_), ArrowDataType::Float32) => {
+            Datum::Float(array.as_primitive::<Float32Type>().value(row))
+        }
+        (DataType::Double(_), ArrowDataType::Float64) => {
+            Datum::Double(array.as_primitive::<Float64Type>().value(row))
+        }
+        (DataType::Char(_) | DataType::VarChar(_), ArrowDataType::Utf8) => {
+            Datum::String(array.as_string::<i32>().value(row).to_string())
+        }
+        (DataType::Binary(_) | DataType::VarBinary(_), ArrowDataType::Binary) => {
+            Datum::Bytes(array.as_binary::<i32>().value(row).to_vec())
+        }
+        (DataType::Date(_), ArrowDataType::Date32) => {
+            Datum::Date(array.as_primitive::<Date32Type>().value(row))
+        }
+        (DataType::Time(_), ArrowDataType::Time32(TimeUnit::Millisecond)) => {
+            Datum::Time(array.as_primitive::<Time32MillisecondType>().value(row))
+        }
+        (DataType::Decimal(t), ArrowDataType::Decimal128(_, _)) => Datum::Decimal {
+            unscaled: array.as_primitive::<Decimal128Type>().value(row),
+            precision: t.precision(),
+            scale: t.scale(),
+        },
+        (
+            DataType::Timestamp(_) | DataType::LocalZonedTimestamp(_),
+            ArrowDataType::Timestamp(unit, _),
+        ) => {
+            let (millis, nanos) = match unit {
+                TimeUnit::Second => {
+                    return DataTypeInvalidSnafu {
+                        message: format!("converting timestamps of seconds into {data_type:?}"),
+                    }
+                    .fail()
+                }
+                TimeUnit::Millisecond => (
+                    array.as_primitive::<TimestampMillisecondType>().value(row),
+                    0,
+                ),
+                TimeUnit::Microsecond => {
+                    let micros = array.as_primitive::<TimestampMicrosecondType>().value(row);
+                    (
+                        micros.div_euclid(1_000),
+                        micros.rem_euclid(1_000) as i32 * 1_000,
+                    )
+                }
+                TimeUnit::Nanosecond => {
+                    let nanos = array.as_primitive::<TimestampNanosecondType>().value(row);
+                    (
+                        nanos.div_euclid(1_000_000),
+                        nanos.rem_euclid(1_000_000) as i32,
+                    )
+                }
+            };
+            match data_type {
+                DataType::Timestamp(_) => Datum::Timestamp { millis, nanos },
+                _ => Datum::LocalZonedTimestamp { millis, nanos },
+            }
+        }
+        (data_type, arrow_type) => {
+            return DataTypeInvalidSnafu {
+                message: format!("converting arrow {arrow_type} into datum of {data_type:?}"),
+            }
+            .fail()
+        }
+    };
+    Ok(Some(datum))
+}
+
+/// Create an arrow array of the datum repeated by the length, which is of the arrow type of
+/// the datum, or of the null type if the datum is none.
+pub fn to_arrow_array(datum: Option<&Datum>, len: usize) -> crate::Result<ArrayRef> {
+    let Some(datum) = datum else {
+        return Ok(new_null_array(&ArrowDataType::Null, len));
+    };
+    let array: ArrayRef = match datum {
+        Datum::Boolean(v) => Arc::new(BooleanArray::from(vec![*v; len])),
+        Datum::TinyInt(v) => Arc::new(Int8Array::from(vec![*v; len])),
+        Datum::SmallInt(v) => Arc::new(Int16Array::from(vec![*v; len])),
+        Datum::Int(v) => Arc::new(Int32Array::from(vec![*v; len])),
+        Datum::BigInt(v) => Arc::new(Int64Array::from(vec![*v; len])),
+        Datum::Float(v) => Arc::new(Float32Array::from(vec![*v; len])),
+        Datum::Double(v) => Arc::new(Float64Array::from(vec![*v; len])),
+        Datum::String(v) => Arc::new(StringArray::from(vec![v.as_str(); len])),
+        Datum::Bytes(v) => Arc::new(BinaryArray::from(vec![v.as_slice(); len])),
+        Datum::Date(v) => Arc::new(Date32Array::from(vec![*v; len])),
+        Datum::Time(v) => Arc::new(Time32MillisecondArray::from(vec![*v; len])),
+        Datum::Timestamp { millis, nanos } => Arc::new(TimestampNanosecondArray::from(vec![
+                millis * 1_000_000 + *nanos as i64;
+                len
+            ])),
+        Datum::LocalZonedTimestamp { millis, nanos } => Arc::new(
+            TimestampNanosecondArray::from(vec![millis * 1_000_000 + *nanos as i64; len])
+                .with_timezone("UTC"),
+        ),
+        Datum::Decimal {
+            unscaled,
+            precision,
+            scale,
+        } => Arc::new(
+            Decimal128Array::from(vec![*unscaled; len])
+                .with_precision_and_scale(*precision as u8, *scale as i8)?,
+        ),
+    };
+    Ok(array)
+}
+
+/// Get the values at the row of the arrow arrays as a binary row of the paimon data types.
+pub fn to_binary_row(
+    columns: &[&dyn Array],
+    data_types: &[&DataType],
+    row: usize,
+) -> crate::Result<BinaryRow> {
+    let mut writer = BinaryRowWriter::new(columns.len() as i32);
+    for (pos, (column, data_type)) in columns.iter().zip(data_types).enumerate() {
+        let datum = to_datum(*column, row, data_type)?;
+        writer.write_datum(pos, datum.as_ref(), data_type)?;
+    }
+    Ok(writer.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, StringArray, TimestampMicrosecondArray};
+
+    #[test]
+    fn test_to_binary_row() {
+        let ids = Int32Array::from(vec![Some(1), None]);
+        let names = StringArray::from(vec!["a", "b"]);
+        let ts = TimestampMicrosecondArray::from(vec![1_001_001, -1]);
+        let types: Vec<DataType> = ["INT", "STRING", "TIMESTAMP(6)"]
+            .iter()
+            .map(|t| t.parse().unwrap())
+            .collect();
+        let type_refs: Vec<&DataType> = types.iter().collect();
+        let columns: [&dyn Array; 3] = [&ids, &names, &ts];
+
+        let row = to_binary_row(&columns, &type_refs, 0).unwrap();
+        assert_eq!(row.get_int(0).unwrap(), 1);
+        assert_eq!(row.get_string(1).unwrap(), "a");
+        assert_eq!(row.get_timestamp(2, 6).unwrap(), (1_001, 1_000));
+
+        let row = to_binary_row(&columns, &type_refs, 1).unwrap();
+        assert!(row.is_null_at(0).unwrap());
+        assert_eq!(
+            to_datum(&ts, 1, &types[2]).unwrap(),
+            Some(Datum::Timestamp {
+                millis: -1,
+                nanos: 999_000
+            })
+        );
+
+        assert!(matches!(
+            to_datum(&ids, 0, &types[1]),
+            Err(Error::DataTypeInvalid { .. })
+        ));
+    }
+}
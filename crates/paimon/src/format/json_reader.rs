@@ -0,0 +1,52 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::{to_arrow_field, ArrowRecordBatchIter, FormatReader};
+use crate::spec::DataField;
+use arrow_json::ReaderBuilder;
+use arrow_schema::Schema;
+use bytes::{Buf, Bytes};
+use std::sync::Arc;
+
+/// Reader of files of line-delimited json objects, whose keys are mapped to the fields by
+/// their names, other keys are ignored.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-format/src/main/java/org/apache/paimon/format/json/JsonFileFormat.java>
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct JsonReader;
+
+impl FormatReader for JsonReader {
+    fn read(
+        &self,
+        bytes: Bytes,
+        fields: &[DataField],
+        batch_size: usize,
+    ) -> crate::Result<ArrowRecordBatchIter> {
+        let schema = Schema::new(
+            fields
+                .iter()
+                .map(|field| to_arrow_field(field).with_nullable(true))
+                .collect::<Vec<_>>(),
+        );
+        let reader = ReaderBuilder::new(Arc::new(schema))
+            .with_batch_size(batch_size)
+            .build(bytes.reader())?;
+        Ok(Box::new(
+            reader.map(|batch| batch.map_err(crate::Error::from)),
+        ))
+    }
+}
@@ -0,0 +1,207 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! File formats of data files, which are read into and written from arrow record batches.
+
+mod avro_reader;
+
+mod avro_writer;
+
+mod csv_reader;
+
+mod data_file_reader;
+pub use data_file_reader::*;
+
+mod data_file_writer;
+pub(crate) use data_file_writer::*;
+
+mod datum;
+pub use datum::*;
+
+mod json_reader;
+
+#[cfg(feature = "format-orc")]
+mod orc_reader;
+
+#[cfg(feature = "format-orc")]
+mod orc_writer;
+
+mod parquet_reader;
+
+mod parquet_writer;
+
+mod schema;
+pub use schema::*;
+
+mod stats_collector;
+pub(crate) use stats_collector::*;
+
+use crate::error::*;
+use crate::spec::{CoreOptions, DataField};
+use arrow_array::RecordBatch;
+use bytes::{Buf, Bytes};
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+/// An iterator of arrow record batches read from data files.
+pub type ArrowRecordBatchIter = Box<dyn Iterator<Item = crate::Result<RecordBatch>> + Send>;
+
+/// Reader of a file format.
+pub(crate) trait FormatReader: Send + Sync {
+    /// Read the top-level columns of the given fields from the file, the fields are of the
+    /// schema the file was written with.
+    ///
+    /// Columns absent in the file are not in the batches, and the columns may be in the order
+    /// of the file. The row types of the fields may be pruned to the nested fields to read,
+    /// readers may still read the whole struct columns.
+    fn read(
+        &self,
+        bytes: Bytes,
+        fields: &[DataField],
+        batch_size: usize,
+    ) -> crate::Result<ArrowRecordBatchIter>;
+}
+
+/// Get the reader of the file format, like `parquet`.
+pub(crate) fn format_reader(format: &str) -> crate::Result<Box<dyn FormatReader>> {
+    match format.to_lowercase().as_str() {
+        "avro" => Ok(Box::new(avro_reader::AvroReader)),
+        "parquet" => Ok(Box::new(parquet_reader::ParquetReader)),
+        #[cfg(feature = "format-orc")]
+        "orc" => Ok(Box::new(orc_reader::OrcReader)),
+        _ => FileFormatUnsupportedSnafu { format }.fail(),
+    }
+}
+
+/// Get the reader of the format of plain files, like the `csv` and `json` files of format
+/// tables, in addition to the formats of data files.
+pub(crate) fn plain_format_reader(
+    format: &str,
+    options: &CoreOptions,
+) -> crate::Result<Box<dyn FormatReader>> {
+    match format.to_lowercase().as_str() {
+        "csv" => Ok(Box::new(csv_reader::CsvReader {
+            include_header: options.csv_include_header(),
+            delimiter: options.csv_field_delimiter(),
+        })),
+        "json" => Ok(Box::new(json_reader::JsonReader)),
+        format => format_reader(format),
+    }
+}
+
+/// Number of the records read at most to infer the fields of plain files.
+const INFER_MAX_RECORDS: usize = 1000;
+
+/// Infer the fields of a plain file of the format, like `csv`, `json` or `parquet`, from its
+/// content, the types of `csv` and `json` files are inferred from the first records.
+///
+/// Columns of `csv` files without headers are named like `column_1`.
+pub fn infer_fields(
+    format: &str,
+    bytes: Bytes,
+    options: &CoreOptions,
+) -> crate::Result<Vec<DataField>> {
+    let schema = match format.to_lowercase().as_str() {
+        "csv" => {
+            let reader = csv_reader::CsvReader {
+                include_header: options.csv_include_header(),
+                delimiter: options.csv_field_delimiter(),
+            };
+            reader
+                .format()
+                .infer_schema(bytes.reader(), Some(INFER_MAX_RECORDS))?
+                .0
+        }
+        "json" => arrow_json::reader::infer_json_schema(bytes.reader(), Some(INFER_MAX_RECORDS))?.0,
+        "parquet" => ParquetRecordBatchReaderBuilder::try_new(bytes)?
+            .schema()
+            .as_ref()
+            .clone(),
+        _ => return FileFormatUnsupportedSnafu { format }.fail(),
+    };
+    schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(id, field)| {
+            Ok(DataField::new(
+                id as i32,
+                field.name().clone(),
+                from_arrow_type(field.data_type())?,
+            ))
+        })
+        .collect()
+}
+
+/// Writer of a file format, the file is built in memory and returned when closed.
+pub(crate) trait FormatWriter: Send {
+    /// Write the record batch of the fields the writer is created with.
+    fn write(&mut self, batch: &RecordBatch) -> crate::Result<()>;
+
+    /// Get the estimated size in bytes of the file written so far.
+    fn length(&self) -> u64;
+
+    /// Finish the file and get its content.
+    fn close(self: Box<Self>) -> crate::Result<Bytes>;
+}
+
+/// Compression of the files written by the format writers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FileCompression {
+    /// Codec of the compression in lower case, like `zstd`.
+    pub(crate) codec: String,
+    /// Level of the codec of `zstd`.
+    pub(crate) zstd_level: i32,
+}
+
+impl FileCompression {
+    /// Create the compression of the codec at the default level.
+    pub(crate) fn new(codec: &str) -> Self {
+        Self {
+            codec: codec.to_lowercase(),
+            zstd_level: CoreOptions::DEFAULT_FILE_COMPRESSION_ZSTD_LEVEL,
+        }
+    }
+
+    /// Get the compression of the data files configured by the options of the table.
+    pub(crate) fn of_options(options: &CoreOptions) -> Self {
+        Self {
+            zstd_level: options.file_compression_zstd_level(),
+            ..Self::new(&options.file_compression())
+        }
+    }
+}
+
+/// Create a writer of the format for files of the fields, compressed by the given compression.
+pub(crate) fn format_writer(
+    format: &str,
+    fields: &[DataField],
+    compression: &FileCompression,
+) -> crate::Result<Box<dyn FormatWriter>> {
+    match format.to_lowercase().as_str() {
+        "avro" => Ok(Box::new(avro_writer::AvroWriter::try_new(
+            fields,
+            compression,
+        )?)),
+        "parquet" => Ok(Box::new(parquet_writer::ParquetWriter::try_new(
+            fields,
+            compression,
+        )?)),
+        #[cfg(feature = "format-orc")]
+        "orc" => Ok(Box::new(orc_writer::OrcWriter::try_new(fields)?)),
+        _ => FileFormatUnsupportedSnafu { format }.fail(),
+    }
+}
@@ -0,0 +1,107 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::{ArrowRecordBatchIter, FormatReader};
+use crate::spec::DataField;
+use bytes::Bytes;
+use orc_rust::projection::ProjectionMask;
+use orc_rust::ArrowReaderBuilder;
+
+/// Reader of orc files.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-format/src/main/java/org/apache/paimon/format/orc/OrcReaderFactory.java>
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct OrcReader;
+
+impl FormatReader for OrcReader {
+    fn read(
+        &self,
+        bytes: Bytes,
+        fields: &[DataField],
+        batch_size: usize,
+    ) -> crate::Result<ArrowRecordBatchIter> {
+        let builder = ArrowReaderBuilder::try_new(bytes)?;
+        let columns: Vec<&str> = fields.iter().map(DataField::name).collect();
+        let mask = ProjectionMask::named_roots(builder.file_metadata().root_data_type(), &columns);
+        let reader = builder
+            .with_projection(mask)
+            .with_batch_size(batch_size)
+            .build();
+        Ok(Box::new(
+            reader.map(|batch| batch.map_err(crate::Error::from)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Array, Int32Array, RecordBatch, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use orc_rust::ArrowWriterBuilder;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_read_orc_with_projection() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("age", DataType::Int32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])),
+                Arc::new(Int32Array::from(vec![10, 20, 30])),
+            ],
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        let mut writer = ArrowWriterBuilder::new(&mut bytes, schema)
+            .try_build()
+            .unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        let batches = OrcReader
+            .read(
+                Bytes::from(bytes),
+                &[
+                    DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                    DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                    DataField::new(3, "unknown".to_string(), "INT".parse().unwrap()),
+                ],
+                2,
+            )
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+        let names: Vec<_> = batches[0]
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().clone())
+            .collect();
+        assert_eq!(names, vec!["id", "name"]);
+        assert_eq!(
+            batches[1].column(1).as_ref(),
+            &StringArray::from(vec![Some("c")]) as &dyn Array
+        );
+    }
+}
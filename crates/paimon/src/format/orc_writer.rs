@@ -0,0 +1,136 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::DataTypeInvalidSnafu;
+use crate::format::{to_arrow_schema, FormatWriter};
+use crate::spec::{DataField, DataType};
+use arrow_array::RecordBatch;
+use arrow_schema::SchemaRef;
+use bytes::Bytes;
+use orc_rust::ArrowWriterBuilder;
+use std::sync::Arc;
+
+/// Writer of orc data files.
+///
+/// The writer of orc-rust is not `Send`, so the batches are buffered and encoded when the
+/// file is closed. It only supports the primitive types and writes the files uncompressed, so
+/// `file.compression` is not applied to orc files.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-format/src/main/java/org/apache/paimon/format/orc/OrcFileFormat.java>
+pub(crate) struct OrcWriter {
+    schema: SchemaRef,
+    batches: Vec<RecordBatch>,
+    /// Estimated in memory size of the buffered batches.
+    length: usize,
+}
+
+impl OrcWriter {
+    pub(crate) fn try_new(fields: &[DataField]) -> crate::Result<Self> {
+        for field in fields {
+            match field.data_type() {
+                DataType::Boolean(_)
+                | DataType::TinyInt(_)
+                | DataType::SmallInt(_)
+                | DataType::Int(_)
+                | DataType::BigInt(_)
+                | DataType::Float(_)
+                | DataType::Double(_)
+                | DataType::Char(_)
+                | DataType::VarChar(_)
+                | DataType::Binary(_)
+                | DataType::VarBinary(_) => {}
+                data_type => {
+                    return DataTypeInvalidSnafu {
+                        message: format!(
+                            "orc writer does not support type {data_type} of field {}",
+                            field.name()
+                        ),
+                    }
+                    .fail()
+                }
+            }
+        }
+        Ok(Self {
+            schema: Arc::new(to_arrow_schema(fields)),
+            batches: vec![],
+            length: 0,
+        })
+    }
+}
+
+impl FormatWriter for OrcWriter {
+    fn write(&mut self, batch: &RecordBatch) -> crate::Result<()> {
+        let batch = RecordBatch::try_new(self.schema.clone(), batch.columns().to_vec())?;
+        self.length += batch.get_array_memory_size();
+        self.batches.push(batch);
+        Ok(())
+    }
+
+    fn length(&self) -> u64 {
+        self.length as u64
+    }
+
+    fn close(self: Box<Self>) -> crate::Result<Bytes> {
+        let mut bytes = Vec::new();
+        let mut writer = ArrowWriterBuilder::new(&mut bytes, self.schema).try_build()?;
+        for batch in &self.batches {
+            writer.write(batch)?;
+        }
+        writer.close()?;
+        Ok(Bytes::from(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::orc_reader::OrcReader;
+    use crate::format::FormatReader;
+    use arrow_array::{Int32Array, StringArray};
+
+    #[test]
+    fn test_write_orc() {
+        let fields = vec![
+            DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+            DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+        ];
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(&fields)),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])),
+            ],
+        )
+        .unwrap();
+        let mut writer: Box<dyn FormatWriter> = Box::new(OrcWriter::try_new(&fields).unwrap());
+        writer.write(&batch.slice(0, 2)).unwrap();
+        writer.write(&batch.slice(2, 1)).unwrap();
+        assert!(writer.length() > 0);
+        let bytes = writer.close().unwrap();
+
+        let batches = OrcReader
+            .read(bytes, &fields, 1024)
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        let read = arrow_select::concat::concat_batches(&batch.schema(), &batches).unwrap();
+        assert_eq!(read, batch);
+
+        let dates = [DataField::new(0, "dt".to_string(), "DATE".parse().unwrap())];
+        assert!(OrcWriter::try_new(&dates).is_err());
+    }
+}
@@ -0,0 +1,355 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::{ArrowRecordBatchIter, FormatReader};
+use crate::io::{CoalescingFileRead, FileRead};
+use crate::spec::{DataField, DataType};
+use bytes::{Buf, Bytes};
+use parquet::arrow::arrow_reader::{ArrowReaderMetadata, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::ProjectionMask;
+use parquet::errors::ParquetError;
+use parquet::file::metadata::{ParquetMetaData, ParquetMetaDataReader};
+use parquet::file::reader::{ChunkReader, Length};
+use parquet::file::FOOTER_SIZE;
+use parquet::schema::types::SchemaDescriptor;
+use std::sync::Arc;
+
+/// Reader of parquet files.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-format/src/main/java/org/apache/paimon/format/parquet/ParquetReaderFactory.java>
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ParquetReader;
+
+impl FormatReader for ParquetReader {
+    fn read(
+        &self,
+        bytes: Bytes,
+        fields: &[DataField],
+        batch_size: usize,
+    ) -> crate::Result<ArrowRecordBatchIter> {
+        let builder = ParquetRecordBatchReaderBuilder::try_new(bytes)?;
+        let mask = projection(builder.parquet_schema(), fields);
+        let reader = builder
+            .with_projection(mask)
+            .with_batch_size(batch_size)
+            .build()?;
+        Ok(Box::new(
+            reader.map(|batch| batch.map_err(crate::Error::from)),
+        ))
+    }
+}
+
+impl ParquetReader {
+    /// Read the columns of the fields from the file of the size, only the footer and the
+    /// column chunks of the columns are read, in coalesced ranges.
+    pub(crate) async fn read_ranges<R: FileRead + Sync>(
+        &self,
+        reader: &CoalescingFileRead<R>,
+        file_size: u64,
+        fields: &[DataField],
+        batch_size: usize,
+    ) -> crate::Result<ArrowRecordBatchIter> {
+        let metadata = read_metadata(reader, file_size).await?;
+        let metadata = ArrowReaderMetadata::try_new(Arc::new(metadata), Default::default())?;
+        let mask = projection(metadata.parquet_schema(), fields);
+        let ranges = metadata
+            .metadata()
+            .row_groups()
+            .iter()
+            .flat_map(|row_group| row_group.columns().iter().enumerate())
+            .filter(|(i, _)| mask.leaf_included(*i))
+            .map(|(_, column)| {
+                let (start, length) = column.byte_range();
+                start..start + length
+            })
+            .collect::<Vec<_>>();
+        let chunks = ranges
+            .iter()
+            .map(|range| range.start)
+            .zip(reader.read_ranges(&ranges).await?)
+            .collect();
+        let chunks = ColumnChunks {
+            chunks,
+            len: file_size,
+        };
+        let reader = ParquetRecordBatchReaderBuilder::new_with_metadata(chunks, metadata)
+            .with_projection(mask)
+            .with_batch_size(batch_size)
+            .build()?;
+        Ok(Box::new(
+            reader.map(|batch| batch.map_err(crate::Error::from)),
+        ))
+    }
+}
+
+/// Read the metadata in the footer of the file, the tail of the file is read at once with
+/// the metadata, unless the metadata is larger than the tail.
+async fn read_metadata<R: FileRead + Sync>(
+    reader: &CoalescingFileRead<R>,
+    file_size: u64,
+) -> crate::Result<ParquetMetaData> {
+    if file_size < FOOTER_SIZE as u64 {
+        return Err(ParquetError::General(format!(
+            "file of {file_size} bytes is too small to be parquet"
+        ))
+        .into());
+    }
+    let tail_size = file_size.min(FOOTER_PREFETCH_SIZE);
+    let tail = reader.read(file_size - tail_size..file_size).await?;
+    let footer: &[u8; FOOTER_SIZE] = tail[tail.len() - FOOTER_SIZE..].try_into().unwrap();
+    let length =
+        (ParquetMetaDataReader::decode_footer_tail(footer)?.metadata_length() + FOOTER_SIZE) as u64;
+    if length > file_size {
+        return Err(ParquetError::General(format!(
+            "metadata of {length} bytes exceeds the file of {file_size} bytes"
+        ))
+        .into());
+    }
+    let metadata = if length <= tail_size {
+        tail.slice((tail_size - length) as usize..tail.len() - FOOTER_SIZE)
+    } else {
+        reader
+            .read(file_size - length..file_size - FOOTER_SIZE as u64)
+            .await?
+    };
+    Ok(ParquetMetaDataReader::decode_metadata(&metadata)?)
+}
+
+/// Size of the tail of files read for the metadata.
+const FOOTER_PREFETCH_SIZE: u64 = 64 * 1024;
+
+/// Column chunks read from the file by their offsets, read by the parquet reader as the file.
+struct ColumnChunks {
+    chunks: Vec<(u64, Bytes)>,
+    len: u64,
+}
+
+impl ColumnChunks {
+    fn slice(&self, start: u64, length: Option<usize>) -> parquet::errors::Result<Bytes> {
+        self.chunks
+            .iter()
+            // readers from the offset read at least a byte, it may be the end of another chunk
+            .find(|(offset, chunk)| {
+                *offset <= start
+                    && start + length.unwrap_or(1) as u64 <= offset + chunk.len() as u64
+            })
+            .map(|(offset, chunk)| {
+                let start = (start - offset) as usize;
+                chunk.slice(start..length.map_or(chunk.len(), |length| start + length))
+            })
+            .ok_or_else(|| ParquetError::General(format!("range at {start} is not read")))
+    }
+}
+
+impl Length for ColumnChunks {
+    fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+impl ChunkReader for ColumnChunks {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn get_read(&self, start: u64) -> parquet::errors::Result<Self::T> {
+        Ok(self.slice(start, None)?.reader())
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+        self.slice(start, Some(length))
+    }
+}
+
+/// Mask of the leaf columns of the fields, the leaf columns of the nested fields of the
+/// pruned row types are skipped.
+fn projection(parquet_schema: &SchemaDescriptor, fields: &[DataField]) -> ProjectionMask {
+    let leaves = parquet_schema
+        .columns()
+        .iter()
+        .enumerate()
+        .filter(|(_, column)| {
+            let parts = column.path().parts();
+            fields
+                .iter()
+                .any(|field| field.name() == parts[0] && selects(field.data_type(), &parts[1..]))
+        })
+        .map(|(index, _)| index)
+        .collect::<Vec<_>>();
+    ProjectionMask::leaves(parquet_schema, leaves)
+}
+
+fn selects(data_type: &DataType, path: &[String]) -> bool {
+    match (data_type, path.first()) {
+        (DataType::Row(row), Some(name)) => row
+            .fields()
+            .iter()
+            .any(|field| field.name() == name && selects(field.data_type(), &path[1..])),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::to_arrow_schema;
+    use crate::spec::RowType;
+    use arrow_array::cast::AsArray;
+    use arrow_array::{Int32Array, RecordBatch, StructArray};
+    use arrow_schema::DataType as ArrowDataType;
+    use parquet::arrow::ArrowWriter;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_read_parquet_with_nested_projection() {
+        let row = |fields| DataType::Row(RowType::new(fields));
+        let fields = vec![
+            DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+            DataField::new(
+                1,
+                "info".to_string(),
+                row(vec![
+                    DataField::new(2, "a".to_string(), "INT".parse().unwrap()),
+                    DataField::new(3, "b".to_string(), "INT".parse().unwrap()),
+                ]),
+            ),
+        ];
+        let schema = Arc::new(to_arrow_schema(&fields));
+        let ArrowDataType::Struct(info_fields) = schema.field(1).data_type().clone() else {
+            unreachable!()
+        };
+        let info = StructArray::try_new(
+            info_fields,
+            vec![
+                Arc::new(Int32Array::from(vec![10, 20])),
+                Arc::new(Int32Array::from(vec![30, 40])),
+            ],
+            None,
+        )
+        .unwrap();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![1, 2])), Arc::new(info)],
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut bytes, schema, None).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        // only the leaf column of info.b is read
+        let pruned = DataField::new(
+            1,
+            "info".to_string(),
+            row(vec![DataField::new(
+                3,
+                "b".to_string(),
+                "INT".parse().unwrap(),
+            )]),
+        );
+        let batches = ParquetReader
+            .read(Bytes::from(bytes), &[pruned], 1024)
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches[0].num_columns(), 1);
+        let info = batches[0].column(0).as_struct();
+        assert_eq!(info.column_names(), vec!["b"]);
+        assert_eq!(
+            info.column(0)
+                .as_primitive::<arrow_array::types::Int32Type>(),
+            &Int32Array::from(vec![30, 40])
+        );
+    }
+
+    /// Reader of the bytes counting the reads.
+    struct CountingRead {
+        bytes: Bytes,
+        reads: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait::async_trait]
+    impl FileRead for CountingRead {
+        async fn read(&self, range: std::ops::Range<u64>) -> crate::Result<Bytes> {
+            self.reads.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            self.bytes.read(range).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_parquet_ranges() {
+        let fields = vec![
+            DataField::new(0, "a".to_string(), "INT".parse().unwrap()),
+            DataField::new(1, "b".to_string(), "INT".parse().unwrap()),
+            DataField::new(2, "c".to_string(), "INT".parse().unwrap()),
+        ];
+        let schema = Arc::new(to_arrow_schema(&fields));
+        let mut bytes = Vec::new();
+        let properties = parquet::file::properties::WriterProperties::builder()
+            .set_max_row_group_size(100)
+            .build();
+        let mut writer =
+            ArrowWriter::try_new(&mut bytes, schema.clone(), Some(properties)).unwrap();
+        let column = |offset: i32| Arc::new(Int32Array::from_iter_values(offset..offset + 300));
+        let batch =
+            RecordBatch::try_new(schema, vec![column(0), column(1000), column(2000)]).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+        let bytes = Bytes::from(bytes);
+
+        let reads = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let read = |max_gap| {
+            let reader = CoalescingFileRead::new(
+                CountingRead {
+                    bytes: bytes.clone(),
+                    reads: reads.clone(),
+                },
+                max_gap,
+                u64::MAX,
+                1,
+            );
+            let fields = [fields[0].clone(), fields[2].clone()];
+            let file_size = bytes.len() as u64;
+            async move {
+                let batches = ParquetReader
+                    .read_ranges(&reader, file_size, &fields, 1024)
+                    .await
+                    .unwrap()
+                    .collect::<crate::Result<Vec<_>>>()
+                    .unwrap();
+                batches
+                    .iter()
+                    .flat_map(|batch| {
+                        let a = batch
+                            .column(0)
+                            .as_primitive::<arrow_array::types::Int32Type>();
+                        let c = batch
+                            .column(1)
+                            .as_primitive::<arrow_array::types::Int32Type>();
+                        a.values().iter().zip(c.values()).map(|(a, c)| (*a, *c))
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+        let expected = (0..300).map(|i| (i, 2000 + i)).collect::<Vec<_>>();
+        // the footer and the column chunks of a and c of the 3 row groups, the adjacent chunks
+        // of c and a of the next row group are coalesced
+        assert_eq!(read(0).await, expected);
+        assert_eq!(reads.swap(0, std::sync::atomic::Ordering::SeqCst), 5);
+        // the footer and all the column chunks coalesced
+        assert_eq!(read(u64::MAX).await, expected);
+        assert_eq!(reads.swap(0, std::sync::atomic::Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,163 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::FileCompressionUnsupportedSnafu;
+use crate::format::{to_arrow_field, FileCompression, FormatWriter};
+use crate::spec::DataField;
+use arrow_array::RecordBatch;
+use arrow_schema::{Schema, SchemaRef};
+use bytes::Bytes;
+use parquet::arrow::{ArrowWriter, PARQUET_FIELD_ID_META_KEY};
+use parquet::basic::{Compression, GzipLevel, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Writer of parquet files, the ids of the fields are written as the field ids of the
+/// parquet schema like paimon-java.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-format/src/main/java/org/apache/paimon/format/parquet/ParquetWriterFactory.java>
+pub(crate) struct ParquetWriter {
+    schema: SchemaRef,
+    writer: ArrowWriter<Vec<u8>>,
+}
+
+impl ParquetWriter {
+    pub(crate) fn try_new(
+        fields: &[DataField],
+        compression: &FileCompression,
+    ) -> crate::Result<Self> {
+        let compression = match compression.codec.as_str() {
+            "none" | "uncompressed" => Compression::UNCOMPRESSED,
+            "snappy" => Compression::SNAPPY,
+            "gzip" => Compression::GZIP(GzipLevel::default()),
+            "lz4" => Compression::LZ4,
+            "lz4_raw" => Compression::LZ4_RAW,
+            "zstd" => Compression::ZSTD(ZstdLevel::try_new(compression.zstd_level)?),
+            codec => {
+                return FileCompressionUnsupportedSnafu {
+                    format: "parquet",
+                    compression: codec,
+                }
+                .fail()
+            }
+        };
+        let properties = WriterProperties::builder()
+            .set_compression(compression)
+            .build();
+        let schema = Arc::new(Schema::new(
+            fields
+                .iter()
+                .map(|field| {
+                    to_arrow_field(field).with_metadata(HashMap::from([(
+                        PARQUET_FIELD_ID_META_KEY.to_string(),
+                        field.id().to_string(),
+                    )]))
+                })
+                .collect::<Vec<_>>(),
+        ));
+        Ok(Self {
+            writer: ArrowWriter::try_new(Vec::new(), schema.clone(), Some(properties))?,
+            schema,
+        })
+    }
+}
+
+impl FormatWriter for ParquetWriter {
+    fn write(&mut self, batch: &RecordBatch) -> crate::Result<()> {
+        let batch = RecordBatch::try_new(self.schema.clone(), batch.columns().to_vec())?;
+        Ok(self.writer.write(&batch)?)
+    }
+
+    fn length(&self) -> u64 {
+        (self.writer.bytes_written() + self.writer.in_progress_size()) as u64
+    }
+
+    fn close(self: Box<Self>) -> crate::Result<Bytes> {
+        Ok(Bytes::from(self.writer.into_inner()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::parquet_reader::ParquetReader;
+    use crate::format::FormatReader;
+    use arrow_array::Int32Array;
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+
+    #[test]
+    fn test_parquet_compression() {
+        let fields = vec![DataField::new(
+            0,
+            "id".to_string(),
+            "INT NOT NULL".parse().unwrap(),
+        )];
+        let zstd_9 = FileCompression {
+            zstd_level: 9,
+            ..FileCompression::new("ZSTD")
+        };
+        for (option, compression) in [
+            (FileCompression::new("none"), Compression::UNCOMPRESSED),
+            (FileCompression::new("snappy"), Compression::SNAPPY),
+            (FileCompression::new("lz4"), Compression::LZ4),
+            (
+                FileCompression::new("gzip"),
+                Compression::GZIP(GzipLevel::default()),
+            ),
+            (
+                FileCompression::new("ZSTD"),
+                Compression::ZSTD(ZstdLevel::default()),
+            ),
+            // the levels are not in the metadata
+            (zstd_9, Compression::ZSTD(ZstdLevel::default())),
+        ] {
+            let mut writer: Box<dyn FormatWriter> =
+                Box::new(ParquetWriter::try_new(&fields, &option).unwrap());
+            let batch = RecordBatch::try_new(
+                Arc::new(Schema::new(vec![to_arrow_field(&fields[0])])),
+                vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+            )
+            .unwrap();
+            writer.write(&batch).unwrap();
+            let bytes = writer.close().unwrap();
+            let reader = SerializedFileReader::new(bytes.clone()).unwrap();
+            let column = reader.metadata().row_group(0).column(0);
+            assert_eq!(column.compression(), compression);
+
+            // the pages are decompressed transparently
+            let batches = ParquetReader
+                .read(bytes, &fields, 1024)
+                .unwrap()
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap();
+            assert_eq!(batches[0].column(0), batch.column(0));
+        }
+        assert!(matches!(
+            ParquetWriter::try_new(&fields, &FileCompression::new("lzo")),
+            Err(crate::Error::FileCompressionUnsupported { .. })
+        ));
+        assert!(ParquetWriter::try_new(
+            &fields,
+            &FileCompression {
+                zstd_level: 100,
+                ..FileCompression::new("zstd")
+            }
+        )
+        .is_err());
+    }
+}
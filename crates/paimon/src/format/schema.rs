@@ -0,0 +1,224 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::DataTypeInvalidSnafu;
+use crate::spec::{DataField, DataType};
+use arrow_schema::{DataType as ArrowDataType, Field, Fields, Schema, TimeUnit};
+use std::sync::Arc;
+
+/// Convert the paimon data type to arrow data type.
+///
+/// Timestamps with precision up to 3, 6 and 9 are converted to timestamps of milliseconds,
+/// microseconds and nanoseconds, timestamps with local time zone are in `UTC`.
+pub fn to_arrow_type(data_type: &DataType) -> ArrowDataType {
+    let time_unit = |precision: u32| match precision {
+        0..=3 => TimeUnit::Millisecond,
+        4..=6 => TimeUnit::Microsecond,
+        _ => TimeUnit::Nanosecond,
+    };
+    match data_type {
+        DataType::Boolean(_) => ArrowDataType::Boolean,
+        DataType::TinyInt(_) => ArrowDataType::Int8,
+        DataType::SmallInt(_) => ArrowDataType::Int16,
+        DataType::Int(_) => ArrowDataType::Int32,
+        DataType::BigInt(_) => ArrowDataType::Int64,
+        DataType::Decimal(t) => ArrowDataType::Decimal128(t.precision() as u8, t.scale() as i8),
+        DataType::Double(_) => ArrowDataType::Float64,
+        DataType::Float(_) => ArrowDataType::Float32,
+        DataType::Binary(_) | DataType::VarBinary(_) => ArrowDataType::Binary,
+        DataType::Char(_) | DataType::VarChar(_) => ArrowDataType::Utf8,
+        DataType::Date(_) => ArrowDataType::Date32,
+        DataType::Time(_) => ArrowDataType::Time32(TimeUnit::Millisecond),
+        DataType::Timestamp(t) => ArrowDataType::Timestamp(time_unit(t.precision()), None),
+        DataType::LocalZonedTimestamp(t) => {
+            ArrowDataType::Timestamp(time_unit(t.precision()), Some("UTC".into()))
+        }
+        DataType::Array(t) => ArrowDataType::List(Arc::new(Field::new(
+            "element",
+            to_arrow_type(t.element_type()),
+            t.element_type().is_nullable(),
+        ))),
+        DataType::Multiset(t) => ArrowDataType::Map(
+            Arc::new(Field::new(
+                "entries",
+                ArrowDataType::Struct(Fields::from(vec![
+                    Field::new("key", to_arrow_type(t.element_type()), false),
+                    Field::new("value", ArrowDataType::Int32, false),
+                ])),
+                false,
+            )),
+            false,
+        ),
+        DataType::Map(t) => ArrowDataType::Map(
+            Arc::new(Field::new(
+                "entries",
+                ArrowDataType::Struct(Fields::from(vec![
+                    Field::new("key", to_arrow_type(t.key_type()), false),
+                    Field::new(
+                        "value",
+                        to_arrow_type(t.value_type()),
+                        t.value_type().is_nullable(),
+                    ),
+                ])),
+                false,
+            )),
+            false,
+        ),
+        DataType::Row(t) => ArrowDataType::Struct(t.fields().iter().map(to_arrow_field).collect()),
+    }
+}
+
+/// Convert the paimon data field to arrow field.
+pub fn to_arrow_field(field: &DataField) -> Field {
+    Field::new(
+        field.name(),
+        to_arrow_type(field.data_type()),
+        field.data_type().is_nullable(),
+    )
+}
+
+/// Convert the paimon data fields to arrow schema.
+pub fn to_arrow_schema(fields: &[DataField]) -> Schema {
+    Schema::new(fields.iter().map(to_arrow_field).collect::<Vec<_>>())
+}
+
+/// Convert the arrow data type to nullable paimon data type, such as the types inferred from
+/// files of other engines.
+///
+/// Unsigned integers are widened to the signed integers of larger sizes, and timestamps with
+/// time zones are timestamps with local time zone.
+pub fn from_arrow_type(data_type: &ArrowDataType) -> crate::Result<DataType> {
+    type_string(data_type)?.parse()
+}
+
+fn type_string(data_type: &ArrowDataType) -> crate::Result<String> {
+    let precision = |unit: &TimeUnit| match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 3,
+        TimeUnit::Microsecond => 6,
+        TimeUnit::Nanosecond => 9,
+    };
+    Ok(match data_type {
+        ArrowDataType::Null | ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 => {
+            "STRING".to_string()
+        }
+        ArrowDataType::Boolean => "BOOLEAN".to_string(),
+        ArrowDataType::Int8 => "TINYINT".to_string(),
+        ArrowDataType::Int16 | ArrowDataType::UInt8 => "SMALLINT".to_string(),
+        ArrowDataType::Int32 | ArrowDataType::UInt16 => "INT".to_string(),
+        ArrowDataType::Int64 | ArrowDataType::UInt32 => "BIGINT".to_string(),
+        ArrowDataType::UInt64 => "DECIMAL(20, 0)".to_string(),
+        ArrowDataType::Float16 | ArrowDataType::Float32 => "FLOAT".to_string(),
+        ArrowDataType::Float64 => "DOUBLE".to_string(),
+        ArrowDataType::Decimal128(precision, scale) => format!("DECIMAL({precision}, {scale})"),
+        ArrowDataType::Binary | ArrowDataType::LargeBinary => "BYTES".to_string(),
+        ArrowDataType::FixedSizeBinary(length) => format!("BINARY({length})"),
+        ArrowDataType::Date32 | ArrowDataType::Date64 => "DATE".to_string(),
+        ArrowDataType::Time32(unit) | ArrowDataType::Time64(unit) => {
+            format!("TIME({})", precision(unit))
+        }
+        ArrowDataType::Timestamp(unit, None) => format!("TIMESTAMP({})", precision(unit)),
+        ArrowDataType::Timestamp(unit, Some(_)) => format!("TIMESTAMP_LTZ({})", precision(unit)),
+        ArrowDataType::List(field) | ArrowDataType::LargeList(field) => {
+            format!("ARRAY<{}>", type_string(field.data_type())?)
+        }
+        ArrowDataType::Map(entries, _) => match entries.data_type() {
+            ArrowDataType::Struct(fields) if fields.len() == 2 => format!(
+                "MAP<{} NOT NULL, {}>",
+                type_string(fields[0].data_type())?,
+                type_string(fields[1].data_type())?
+            ),
+            data_type => {
+                return DataTypeInvalidSnafu {
+                    message: format!("arrow map entries of {data_type} are not a key and value"),
+                }
+                .fail()
+            }
+        },
+        ArrowDataType::Struct(fields) => format!(
+            "ROW<{}>",
+            fields
+                .iter()
+                .map(|field| Ok(format!(
+                    "`{}` {}",
+                    field.name().replace('`', "``"),
+                    type_string(field.data_type())?
+                )))
+                .collect::<crate::Result<Vec<_>>>()?
+                .join(", ")
+        ),
+        data_type => {
+            return DataTypeInvalidSnafu {
+                message: format!("arrow type {data_type} is not supported in paimon"),
+            }
+            .fail()
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_arrow_schema() {
+        let fields = vec![
+            DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+            DataField::new(1, "price".to_string(), "DECIMAL(10, 2)".parse().unwrap()),
+            DataField::new(2, "ts".to_string(), "TIMESTAMP(6)".parse().unwrap()),
+            DataField::new(3, "ltz".to_string(), "TIMESTAMP_LTZ(3)".parse().unwrap()),
+            DataField::new(4, "tags".to_string(), "ARRAY<STRING>".parse().unwrap()),
+            DataField::new(
+                5,
+                "attrs".to_string(),
+                "MAP<STRING NOT NULL, INT>".parse().unwrap(),
+            ),
+            DataField::new(6, "nested".to_string(), "ROW<a BYTES>".parse().unwrap()),
+        ];
+        let schema = to_arrow_schema(&fields);
+        assert!(!schema.field(0).is_nullable());
+        assert_eq!(schema.field(0).data_type(), &ArrowDataType::Int32);
+        assert_eq!(
+            schema.field(1).data_type(),
+            &ArrowDataType::Decimal128(10, 2)
+        );
+        assert_eq!(
+            schema.field(2).data_type(),
+            &ArrowDataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        assert_eq!(
+            schema.field(3).data_type(),
+            &ArrowDataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into()))
+        );
+        assert_eq!(
+            schema.field(4).data_type(),
+            &ArrowDataType::List(Arc::new(Field::new("element", ArrowDataType::Utf8, true)))
+        );
+        assert!(matches!(
+            schema.field(5).data_type(),
+            ArrowDataType::Map(_, false)
+        ));
+        assert_eq!(
+            schema.field(6).data_type(),
+            &ArrowDataType::Struct(Fields::from(vec![Field::new(
+                "a",
+                ArrowDataType::Binary,
+                true
+            )]))
+        );
+    }
+}
@@ -0,0 +1,319 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::ConfigInvalidSnafu;
+use crate::format::to_datum;
+use crate::spec::{BinaryRowWriter, BinaryTableStats, CoreOptions, DataField, DataType, Datum};
+use arrow_array::{Array, ArrayRef};
+use arrow_ord::sort::sort_to_indices;
+use arrow_schema::SortOptions;
+
+/// Mode of the statistics collected of a field, `metadata.stats-mode`.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/statistics/FieldStatsCollector.java>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum StatsMode {
+    /// No statistics are collected.
+    None,
+    /// Only the null counts are collected.
+    Counts,
+    /// The min and max strings are truncated to the number of characters, with the last
+    /// character of the max value incremented so that it's still an upper bound.
+    Truncate(usize),
+    /// The min and max values are collected as they are.
+    Full,
+}
+
+impl Default for StatsMode {
+    /// The default `truncate(16)` of paimon-java.
+    fn default() -> Self {
+        StatsMode::Truncate(16)
+    }
+}
+
+impl StatsMode {
+    /// Parse the mode, one of `none`, `counts`, `truncate(<length>)` and `full`.
+    pub(crate) fn parse(mode: &str) -> crate::Result<Self> {
+        let mode = mode.trim().to_lowercase();
+        match mode.as_str() {
+            "none" => return Ok(StatsMode::None),
+            "counts" => return Ok(StatsMode::Counts),
+            "full" => return Ok(StatsMode::Full),
+            _ => {}
+        }
+        mode.strip_prefix("truncate(")
+            .and_then(|length| length.strip_suffix(')'))
+            .and_then(|length| length.trim().parse().ok())
+            .map(StatsMode::Truncate)
+            .ok_or_else(|| {
+                ConfigInvalidSnafu {
+                    message: format!("invalid stats mode {mode}"),
+                }
+                .build()
+            })
+    }
+
+    /// Get the modes of the fields by `fields.<field>.stats-mode` and `metadata.stats-mode`.
+    pub(crate) fn of_fields(
+        options: &CoreOptions,
+        fields: &[DataField],
+    ) -> crate::Result<Vec<Self>> {
+        fields
+            .iter()
+            .map(|field| Self::parse(&options.field_stats_mode(field.name())))
+            .collect()
+    }
+}
+
+/// Collector of the min values, max values and null counts of the columns written into a data
+/// file, by the [`StatsMode`]s of the fields.
+///
+/// Values of nested types have no min and max values. If the max string can't be truncated
+/// into an upper bound, neither the min nor the max value of the field is kept.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/statistics/TruncateFieldStatsCollector.java>
+#[derive(Debug, Clone)]
+pub(crate) struct StatsCollector {
+    fields: Vec<DataField>,
+    modes: Vec<StatsMode>,
+    min_values: Vec<Option<Datum>>,
+    max_values: Vec<Option<Datum>>,
+    null_counts: Vec<i64>,
+}
+
+impl StatsCollector {
+    pub(crate) fn new(fields: Vec<DataField>, modes: Vec<StatsMode>) -> Self {
+        Self {
+            modes,
+            min_values: vec![None; fields.len()],
+            max_values: vec![None; fields.len()],
+            null_counts: vec![0; fields.len()],
+            fields,
+        }
+    }
+
+    /// Collect the statistics of the columns of the fields.
+    pub(crate) fn collect(&mut self, columns: &[ArrayRef]) -> crate::Result<()> {
+        for (pos, (column, field)) in columns.iter().zip(&self.fields).enumerate() {
+            self.null_counts[pos] += column.null_count() as i64;
+            if matches!(self.modes[pos], StatsMode::None | StatsMode::Counts)
+                || column.null_count() == column.len()
+                || !has_min_max(field.data_type())
+            {
+                continue;
+            }
+            let min = extreme(column.as_ref(), field.data_type(), false)?;
+            if !self.min_values[pos].as_ref().is_some_and(|v| v <= &min) {
+                self.min_values[pos] = Some(min);
+            }
+            let max = extreme(column.as_ref(), field.data_type(), true)?;
+            if !self.max_values[pos].as_ref().is_some_and(|v| v >= &max) {
+                self.max_values[pos] = Some(max);
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the statistics collected as binary rows of the fields.
+    pub(crate) fn stats(&self) -> crate::Result<BinaryTableStats> {
+        let arity = self.fields.len() as i32;
+        let mut min_values = BinaryRowWriter::new(arity);
+        let mut max_values = BinaryRowWriter::new(arity);
+        for (pos, field) in self.fields.iter().enumerate() {
+            let (min, max) = match (
+                self.modes[pos],
+                &self.min_values[pos],
+                &self.max_values[pos],
+            ) {
+                (
+                    StatsMode::Truncate(length),
+                    Some(Datum::String(min)),
+                    Some(Datum::String(max)),
+                ) => match truncate_max(max, length) {
+                    Some(max) => (
+                        Some(Datum::String(truncate_min(min, length))),
+                        Some(Datum::String(max)),
+                    ),
+                    None => (None, None),
+                },
+                (_, min, max) => (min.clone(), max.clone()),
+            };
+            min_values.write_datum(pos, min.as_ref(), field.data_type())?;
+            max_values.write_datum(pos, max.as_ref(), field.data_type())?;
+        }
+        let null_counts = self
+            .null_counts
+            .iter()
+            .zip(&self.modes)
+            .map(|(count, mode)| (mode != &StatsMode::None).then_some(*count))
+            .collect();
+        Ok(BinaryTableStats::new(
+            min_values.build().to_serialized_bytes(),
+            max_values.build().to_serialized_bytes(),
+            null_counts,
+        ))
+    }
+}
+
+fn has_min_max(data_type: &DataType) -> bool {
+    !matches!(
+        data_type,
+        DataType::Array(_) | DataType::Map(_) | DataType::Multiset(_) | DataType::Row(_)
+    )
+}
+
+/// Get the min or max non-null value of the column.
+fn extreme(column: &dyn Array, data_type: &DataType, max: bool) -> crate::Result<Datum> {
+    let options = SortOptions {
+        descending: max,
+        nulls_first: false,
+    };
+    let indices = sort_to_indices(column, Some(options), Some(1))?;
+    Ok(to_datum(column, indices.value(0) as usize, data_type)?
+        .expect("nulls are sorted last in a column of non-null values"))
+}
+
+fn truncate_min(value: &str, length: usize) -> String {
+    value.chars().take(length).collect()
+}
+
+/// Truncate the max value and increment its last character, none if no character can be
+/// incremented.
+fn truncate_max(value: &str, length: usize) -> Option<String> {
+    if value.chars().count() <= length {
+        return Some(value.to_string());
+    }
+    let mut chars: Vec<char> = value.chars().take(length).collect();
+    while let Some(last) = chars.pop() {
+        let next = (last as u32 + 1..=char::MAX as u32).find_map(char::from_u32);
+        if let Some(next) = next {
+            chars.push(next);
+            return Some(chars.into_iter().collect());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::to_arrow_field;
+    use crate::spec::BinaryRow;
+    use arrow_array::{new_null_array, Int32Array, StringArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_collect_stats() {
+        let fields = vec![
+            DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+            DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+            DataField::new(2, "tags".to_string(), "ARRAY<INT>".parse().unwrap()),
+        ];
+        let mut collector = StatsCollector::new(fields.clone(), vec![StatsMode::default(); 3]);
+        let tags = || new_null_array(to_arrow_field(&fields[2]).data_type(), 2);
+        collector
+            .collect(&[
+                Arc::new(Int32Array::from(vec![Some(3), None])),
+                Arc::new(StringArray::from(vec!["b", "a"])),
+                tags(),
+            ])
+            .unwrap();
+        collector
+            .collect(&[
+                Arc::new(Int32Array::from(vec![Some(5), Some(-1)])),
+                Arc::new(StringArray::from(vec!["a very long string a", "c"])),
+                tags(),
+            ])
+            .unwrap();
+        let stats = collector.stats().unwrap();
+        assert_eq!(stats.null_counts(), &[Some(1), Some(0), Some(4)]);
+        let min = BinaryRow::from_serialized_bytes(stats.min_values()).unwrap();
+        let max = BinaryRow::from_serialized_bytes(stats.max_values()).unwrap();
+        assert_eq!((min.get_int(0).unwrap(), max.get_int(0).unwrap()), (-1, 5));
+        assert_eq!(min.get_string(1).unwrap(), "a");
+        assert_eq!(max.get_string(1).unwrap(), "c");
+        assert!(min.is_null_at(2).unwrap() && max.is_null_at(2).unwrap());
+
+        assert_eq!(truncate_min("a very long string a", 16), "a very long stri");
+        assert_eq!(
+            truncate_max("a very long string a", 16).as_deref(),
+            Some("a very long strj")
+        );
+        assert_eq!(truncate_max("short", 16).as_deref(), Some("short"));
+        assert_eq!(truncate_max("a\u{10FFFF}b", 2).as_deref(), Some("b"));
+        assert_eq!(truncate_max("\u{10FFFF}\u{10FFFF}b", 2), None);
+    }
+
+    #[test]
+    fn test_stats_modes() {
+        let options = [
+            ("metadata.stats-mode".to_string(), "truncate(2)".to_string()),
+            ("fields.id.stats-mode".to_string(), "none".to_string()),
+            ("fields.age.stats-mode".to_string(), "counts".to_string()),
+            ("fields.code.stats-mode".to_string(), "full".to_string()),
+        ]
+        .into();
+        let fields: Vec<_> = ["id", "age", "code", "name", "key"]
+            .into_iter()
+            .enumerate()
+            .map(|(id, name)| {
+                let data_type = if id < 2 { "INT" } else { "STRING" };
+                DataField::new(id as i32, name.to_string(), data_type.parse().unwrap())
+            })
+            .collect();
+        let modes = StatsMode::of_fields(&CoreOptions::new(&options), &fields).unwrap();
+        assert_eq!(
+            modes,
+            vec![
+                StatsMode::None,
+                StatsMode::Counts,
+                StatsMode::Full,
+                StatsMode::Truncate(2),
+                StatsMode::Truncate(2),
+            ]
+        );
+        assert!(StatsMode::parse("truncate(x)").is_err());
+
+        let mut collector = StatsCollector::new(fields, modes);
+        let strings = |values: [&str; 2]| Arc::new(StringArray::from(values.to_vec()));
+        collector
+            .collect(&[
+                Arc::new(Int32Array::from(vec![Some(1), None])),
+                Arc::new(Int32Array::from(vec![Some(2), None])),
+                strings(["abc", "abd"]),
+                strings(["abc", "abd"]),
+                strings(["\u{10FFFF}\u{10FFFF}\u{10FFFF}", "a"]),
+            ])
+            .unwrap();
+        let stats = collector.stats().unwrap();
+        assert_eq!(
+            stats.null_counts(),
+            &[None, Some(1), Some(0), Some(0), Some(0)]
+        );
+        let int_type: DataType = "INT".parse().unwrap();
+        let string_type: DataType = "STRING".parse().unwrap();
+        let string = |value: &str| Some(Datum::String(value.to_string()));
+        assert_eq!(stats.min_value(0, &int_type).unwrap(), None);
+        assert_eq!(stats.max_value(1, &int_type).unwrap(), None);
+        assert_eq!(stats.max_value(2, &string_type).unwrap(), string("abd"));
+        assert_eq!(stats.min_value(3, &string_type).unwrap(), string("ab"));
+        assert_eq!(stats.max_value(3, &string_type).unwrap(), string("ac"));
+        // no upper bound of the max value is truncated
+        assert_eq!(stats.min_value(4, &string_type).unwrap(), None);
+        assert_eq!(stats.max_value(4, &string_type).unwrap(), None);
+    }
+}
@@ -0,0 +1,463 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::iceberg::{
+    IcebergManifestEntry, IcebergManifestFileMeta, IcebergManifestWriter, IcebergMetadata,
+    IcebergPartitionField, IcebergPartitionSpec, IcebergRef, IcebergSchema, IcebergSnapshot,
+    IcebergSortOrder,
+};
+use crate::spec::{BinaryRow, CommitKind, Snapshot};
+use crate::table::{FileStoreTable, Table, TableScan};
+use bytes::Bytes;
+use serde_json::json;
+use std::collections::HashMap;
+
+const VERSION_HINT: &str = "version-hint.text";
+
+/// A callback of the commits writing the Iceberg metadata of each committed snapshot, enabled
+/// by `metadata.iceberg.storage`, so that Iceberg readers query the latest snapshot.
+///
+/// All the data files of the snapshot are written into a manifest, while the metadata of the
+/// previous snapshot is removed. The data files of primary key tables are only included once
+/// they are compacted into the max level, where their rows are merged. The columns of parquet
+/// data files carry the ids of their fields, while a default name mapping of the schema maps the
+/// columns of ORC and Avro data files, which are written without field ids.
+///
+/// The metadata is stored in `metadata` under the table for the `table-location` storage, or
+/// under `<warehouse>/iceberg/<database>/<table>` for `hadoop-catalog`, with
+/// `version-hint.text` pointing at the metadata of the latest snapshot.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-core/src/main/java/org/apache/paimon/iceberg/IcebergCommitCallback.java>
+#[derive(Debug, Clone)]
+pub struct IcebergCommitCallback {
+    table: FileStoreTable,
+    location: String,
+    schema: IcebergSchema,
+    spec: IcebergPartitionSpec,
+    writer: IcebergManifestWriter,
+}
+
+impl IcebergCommitCallback {
+    /// Create the callback of the table, `None` if the Iceberg compatibility is disabled.
+    pub fn create(table: &FileStoreTable) -> crate::Result<Option<Self>> {
+        let options = table.core_options();
+        let table_location = table.location().trim_end_matches('/');
+        let location = match options.metadata_iceberg_storage().as_str() {
+            "disabled" => return Ok(None),
+            "table-location" => table_location.to_string(),
+            "hadoop-catalog" => {
+                let warehouse = table_location
+                    .rsplitn(3, '/')
+                    .nth(2)
+                    .unwrap_or(table_location);
+                let identifier = table.identifier();
+                format!(
+                    "{warehouse}/iceberg/{}/{}",
+                    identifier.database(),
+                    identifier.object()
+                )
+            }
+            storage => {
+                return ConfigInvalidSnafu {
+                    message: format!("Unsupported iceberg metadata storage {storage}"),
+                }
+                .fail()
+            }
+        };
+        if options.deletion_vectors_enabled() {
+            return ConfigInvalidSnafu {
+                message: "Iceberg compatibility doesn't support deletion vectors",
+            }
+            .fail();
+        }
+
+        let table_schema = table.schema();
+        let schema = IcebergSchema::new(table_schema.id(), table_schema.fields())?;
+        let partition_type = table_schema.logical_partition_type();
+        let spec = IcebergPartitionSpec {
+            spec_id: 0,
+            fields: partition_type
+                .fields()
+                .iter()
+                .enumerate()
+                .map(|(i, field)| IcebergPartitionField {
+                    name: field.name().to_string(),
+                    transform: "identity".to_string(),
+                    source_id: field.id(),
+                    field_id: IcebergPartitionSpec::FIRST_FIELD_ID + i as i32,
+                })
+                .collect(),
+        };
+        let partition_types = partition_type
+            .fields()
+            .iter()
+            .map(|field| field.data_type().clone())
+            .collect();
+        let writer = IcebergManifestWriter::new(schema.clone(), spec.clone(), partition_types)?;
+        Ok(Some(Self {
+            table: table.clone(),
+            location,
+            schema,
+            spec,
+            writer,
+        }))
+    }
+
+    /// Get the location of the Iceberg table.
+    #[inline]
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Get the directory of the Iceberg metadata.
+    pub fn metadata_dir(&self) -> String {
+        format!("{}/metadata", self.location)
+    }
+
+    /// Get the path of the Iceberg metadata of the snapshot.
+    pub fn metadata_path(&self, snapshot_id: i64) -> String {
+        format!("{}/v{snapshot_id}.metadata.json", self.metadata_dir())
+    }
+
+    /// Get the id of the latest snapshot whose metadata is written.
+    pub async fn latest_snapshot_id(&self) -> crate::Result<Option<i64>> {
+        let path = format!("{}/{VERSION_HINT}", self.metadata_dir());
+        let file_io = self.table.file_io();
+        if !file_io.exists(&path).await? {
+            return Ok(None);
+        }
+        Ok(file_io.read_file_utf8(&path).await?.trim().parse().ok())
+    }
+
+    /// Write the Iceberg metadata of the committed snapshot.
+    pub async fn call(&self, snapshot: &Snapshot) -> crate::Result<()> {
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+        let max_level = self.table.core_options().num_levels() - 1;
+        let primary_keyed = !self.table.schema().primary_keys().is_empty();
+        let partition_fields = self.table.schema().logical_partition_type();
+
+        let mut entries = Vec::new();
+        for entry in TableScan::new(self.table.clone())
+            .data_files(snapshot)
+            .await?
+        {
+            let file = entry.file();
+            if primary_keyed && file.level != max_level {
+                continue;
+            }
+            let partition = BinaryRow::from_serialized_bytes(entry.partition())?;
+            let values = partition_fields
+                .fields()
+                .iter()
+                .enumerate()
+                .map(|(pos, field)| partition.get_datum(pos, field.data_type()))
+                .collect::<crate::Result<_>>()?;
+            entries.push(IcebergManifestEntry {
+                snapshot_id: snapshot.id(),
+                sequence_number: snapshot.id(),
+                file_path: format!(
+                    "{}/{}",
+                    path_factory.bucket_path(&partition, entry.bucket())?,
+                    file.file_name
+                ),
+                file_format: file.file_format().unwrap_or("orc").to_uppercase(),
+                partition: values,
+                record_count: file.row_count,
+                file_size_in_bytes: file.file_size,
+            });
+        }
+
+        let dir = self.metadata_dir();
+        let uuid = uuid::Uuid::new_v4();
+        let manifest_path = format!("{dir}/{uuid}-m0.avro");
+        let manifest = self.writer.write_manifest(&entries)?;
+        let manifest_length = manifest.len() as i64;
+        file_io
+            .new_output(&manifest_path)?
+            .write(Bytes::from(manifest))
+            .await?;
+        let manifest_list_path = format!("{dir}/snap-{}-1-{uuid}.avro", snapshot.id());
+        let manifest_meta = IcebergManifestFileMeta {
+            manifest_path,
+            manifest_length,
+            partition_spec_id: self.spec.spec_id,
+            sequence_number: snapshot.id(),
+            added_snapshot_id: snapshot.id(),
+            added_files_count: entries.len() as i32,
+            added_rows_count: entries.iter().map(|e| e.record_count).sum(),
+        };
+        let parent_snapshot_id =
+            (snapshot.id() > Snapshot::FIRST_SNAPSHOT_ID).then(|| snapshot.id() - 1);
+        let manifest_list = self.writer.write_manifest_list(
+            snapshot.id(),
+            parent_snapshot_id,
+            snapshot.id(),
+            &[manifest_meta],
+        )?;
+        file_io
+            .new_output(&manifest_list_path)?
+            .write(Bytes::from(manifest_list))
+            .await?;
+
+        let previous = match self.latest_snapshot_id().await? {
+            Some(id) if id != snapshot.id() => {
+                let json = file_io.read_file_utf8(&self.metadata_path(id)).await?;
+                Some(IcebergMetadata::from_json(&json)?)
+            }
+            _ => None,
+        };
+        let metadata = self.metadata(
+            snapshot,
+            previous.as_ref(),
+            parent_snapshot_id,
+            manifest_list_path,
+        )?;
+        file_io
+            .new_output(&self.metadata_path(snapshot.id()))?
+            .write(Bytes::from(metadata.to_json()?))
+            .await?;
+        file_io
+            .new_output(&format!("{dir}/{VERSION_HINT}"))?
+            .write(Bytes::from(snapshot.id().to_string()))
+            .await?;
+
+        // only the latest snapshot is kept, whose data files are kept by the table
+        if let Some(previous) = previous {
+            for previous_snapshot in &previous.snapshots {
+                let list = &previous_snapshot.manifest_list;
+                file_io.delete_file(list).await?;
+                let uuid = list
+                    .rsplit('/')
+                    .next()
+                    .and_then(|name| name.strip_suffix(".avro"))
+                    .and_then(|name| name.rsplit_once("-1-"))
+                    .map(|(_, uuid)| uuid);
+                if let Some(uuid) = uuid {
+                    file_io
+                        .delete_file(&format!("{dir}/{uuid}-m0.avro"))
+                        .await?;
+                }
+            }
+            file_io
+                .delete_file(&self.metadata_path(previous.current_snapshot_id))
+                .await?;
+        }
+        Ok(())
+    }
+
+    fn metadata(
+        &self,
+        snapshot: &Snapshot,
+        previous: Option<&IcebergMetadata>,
+        parent_snapshot_id: Option<i64>,
+        manifest_list: String,
+    ) -> crate::Result<IcebergMetadata> {
+        let operation = match snapshot.commit_kind() {
+            CommitKind::APPEND => "append",
+            CommitKind::OVERWRITE => "overwrite",
+            CommitKind::COMPACT | CommitKind::ANALYZE => "replace",
+        };
+        let name_mapping: Vec<_> = self
+            .schema
+            .fields
+            .iter()
+            .map(|field| json!({"field-id": field.id, "names": [field.name]}))
+            .collect();
+        let properties = HashMap::from([(
+            "schema.name-mapping.default".to_string(),
+            serde_json::to_string(&name_mapping)?,
+        )]);
+        Ok(IcebergMetadata {
+            format_version: IcebergMetadata::FORMAT_VERSION,
+            table_uuid: previous
+                .map(|previous| previous.table_uuid.clone())
+                .unwrap_or_else(|| uuid::Uuid::new_v4().to_string()),
+            location: self.location.clone(),
+            last_sequence_number: snapshot.id(),
+            last_updated_ms: snapshot.time_millis() as i64,
+            last_column_id: self.table.schema().highest_field_id(),
+            schemas: vec![self.schema.clone()],
+            current_schema_id: self.schema.schema_id,
+            partition_specs: vec![self.spec.clone()],
+            default_spec_id: self.spec.spec_id,
+            last_partition_id: IcebergPartitionSpec::FIRST_FIELD_ID - 1
+                + self.spec.fields.len() as i32,
+            sort_orders: vec![IcebergSortOrder {
+                order_id: 0,
+                fields: vec![],
+            }],
+            default_sort_order_id: 0,
+            properties,
+            current_snapshot_id: snapshot.id(),
+            snapshots: vec![IcebergSnapshot {
+                sequence_number: snapshot.id(),
+                snapshot_id: snapshot.id(),
+                parent_snapshot_id,
+                timestamp_ms: snapshot.time_millis() as i64,
+                summary: HashMap::from([("operation".to_string(), operation.to_string())]),
+                manifest_list,
+                schema_id: self.schema.schema_id,
+            }],
+            refs: HashMap::from([(
+                "main".to_string(),
+                IcebergRef {
+                    snapshot_id: snapshot.id(),
+                    typ: "branch".to_string(),
+                },
+            )]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataField, Schema};
+    use crate::utils::SchemaManager;
+    use apache_avro::types::Value;
+    use apache_avro::Reader;
+    use arrow_array::{Int32Array, RecordBatch, StringArray};
+    use std::sync::Arc;
+
+    fn read_avro(bytes: &[u8]) -> Vec<HashMap<String, Value>> {
+        Reader::new(bytes)
+            .unwrap()
+            .map(|record| match record.unwrap() {
+                Value::Record(fields) => fields.into_iter().collect(),
+                record => panic!("unexpected record {record:?}"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_iceberg_commit_callback() {
+        let location = "file:/tmp/test_iceberg_commit_callback";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+                        DataField::new(1, "dt".to_string(), "STRING".parse().unwrap()),
+                    ])
+                    .partition_keys(vec!["dt".to_string()])
+                    .options(HashMap::from([
+                        ("file.format".to_string(), "parquet".to_string()),
+                        (
+                            "metadata.iceberg.storage".to_string(),
+                            "table-location".to_string(),
+                        ),
+                    ]))
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(
+            file_io.clone(),
+            Identifier::new("db", "t"),
+            location,
+            schema,
+        );
+        for (ids, dts) in [(vec![1, 2], vec!["a", "b"]), (vec![3], vec!["a"])] {
+            let batch = RecordBatch::try_new(
+                Arc::new(to_arrow_schema(table.schema().fields())),
+                vec![
+                    Arc::new(Int32Array::from(ids)),
+                    Arc::new(StringArray::from(dts)),
+                ],
+            )
+            .unwrap();
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            write.write(&batch).await.unwrap();
+            let messages = write.prepare_commit().await.unwrap();
+            builder.new_commit().commit(messages).await.unwrap();
+        }
+
+        let callback = IcebergCommitCallback::create(&table).unwrap().unwrap();
+        assert_eq!(callback.latest_snapshot_id().await.unwrap(), Some(2));
+        // the metadata of the previous snapshot is removed
+        assert!(!file_io.exists(&callback.metadata_path(1)).await.unwrap());
+        let json = file_io
+            .read_file_utf8(&callback.metadata_path(2))
+            .await
+            .unwrap();
+        let metadata = IcebergMetadata::from_json(&json).unwrap();
+        assert_eq!(metadata.current_snapshot_id, 2);
+        assert_eq!(metadata.location, location);
+        assert_eq!(metadata.schemas[0].fields[1].typ, "string");
+        assert_eq!(metadata.partition_specs[0].fields[0].source_id, 1);
+        assert_eq!(metadata.partition_specs[0].fields[0].field_id, 1000);
+
+        let manifest_list = file_io
+            .new_input(&metadata.snapshots[0].manifest_list)
+            .unwrap()
+            .read()
+            .await
+            .unwrap();
+        let manifests = read_avro(&manifest_list);
+        assert_eq!(manifests.len(), 1);
+        assert_eq!(manifests[0]["added_rows_count"], Value::Long(3));
+        let Value::String(manifest_path) = &manifests[0]["manifest_path"] else {
+            panic!("manifest path should be a string");
+        };
+        let manifest = file_io
+            .new_input(manifest_path)
+            .unwrap()
+            .read()
+            .await
+            .unwrap();
+        let entries = read_avro(&manifest);
+        assert_eq!(entries.len(), 3);
+        let mut partitions = Vec::new();
+        for entry in &entries {
+            let Value::Record(data_file) = &entry["data_file"] else {
+                panic!("data file should be a record");
+            };
+            let data_file: HashMap<_, _> = data_file.iter().cloned().collect();
+            let Value::String(file_path) = &data_file["file_path"] else {
+                panic!("file path should be a string");
+            };
+            assert!(file_io.exists(file_path).await.unwrap());
+            assert_eq!(
+                data_file["file_format"],
+                Value::String("PARQUET".to_string())
+            );
+            partitions.push(data_file["partition"].clone());
+        }
+        let partition = |dt: &str| {
+            Value::Record(vec![(
+                "dt".to_string(),
+                Value::Union(1, Box::new(Value::String(dt.to_string()))),
+            )])
+        };
+        assert_eq!(
+            partitions.iter().filter(|p| **p == partition("a")).count(),
+            2
+        );
+        assert_eq!(
+            partitions.iter().filter(|p| **p == partition("b")).count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,331 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::iceberg::{IcebergPartitionSpec, IcebergSchema};
+use crate::spec::{DataType, Datum};
+use apache_avro::types::Value;
+use apache_avro::{Codec, Schema, Writer};
+use serde_json::json;
+
+/// Status of the entries of the data files added by the snapshot.
+const ADDED: i32 = 1;
+/// Content of the manifests and the data files of rows, rather than deletes.
+const DATA: i32 = 0;
+
+/// An entry of a data file in an Iceberg manifest.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-core/src/main/java/org/apache/paimon/iceberg/manifest/IcebergManifestEntry.java>
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcebergManifestEntry {
+    pub snapshot_id: i64,
+    pub sequence_number: i64,
+    pub file_path: String,
+    /// Format of the data file in upper case, like `PARQUET`.
+    pub file_format: String,
+    /// Values of the partition fields of the data file.
+    pub partition: Vec<Option<Datum>>,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+}
+
+/// Metadata of an Iceberg manifest in the manifest list of a snapshot.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-core/src/main/java/org/apache/paimon/iceberg/manifest/IcebergManifestFileMeta.java>
+#[derive(Debug, Clone, PartialEq)]
+pub struct IcebergManifestFileMeta {
+    pub manifest_path: String,
+    pub manifest_length: i64,
+    pub partition_spec_id: i32,
+    pub sequence_number: i64,
+    pub added_snapshot_id: i64,
+    pub added_files_count: i32,
+    pub added_rows_count: i64,
+}
+
+fn nullable(value: Option<Value>) -> Value {
+    match value {
+        Some(value) => Value::Union(1, Box::new(value)),
+        None => Value::Union(0, Box::new(Value::Null)),
+    }
+}
+
+/// Avro schema and value of the partition fields of the type.
+fn partition_avro(data_type: &DataType) -> crate::Result<serde_json::Value> {
+    Ok(match data_type {
+        DataType::Boolean(_) => json!("boolean"),
+        DataType::TinyInt(_) | DataType::SmallInt(_) | DataType::Int(_) => json!("int"),
+        DataType::BigInt(_) => json!("long"),
+        DataType::Float(_) => json!("float"),
+        DataType::Double(_) => json!("double"),
+        DataType::Char(_) | DataType::VarChar(_) => json!("string"),
+        DataType::Binary(_) | DataType::VarBinary(_) => json!("bytes"),
+        DataType::Date(_) => json!({"type": "int", "logicalType": "date"}),
+        DataType::Timestamp(t) if t.precision() <= 6 => {
+            json!({"type": "long", "logicalType": "timestamp-micros", "adjust-to-utc": false})
+        }
+        DataType::LocalZonedTimestamp(t) if t.precision() <= 6 => {
+            json!({"type": "long", "logicalType": "timestamp-micros", "adjust-to-utc": true})
+        }
+        _ => {
+            return DataTypeInvalidSnafu {
+                message: format!(
+                    "Partition type {data_type} is not supported by iceberg compatibility"
+                ),
+            }
+            .fail()
+        }
+    })
+}
+
+fn partition_value(datum: &Datum) -> Value {
+    match datum {
+        Datum::Boolean(v) => Value::Boolean(*v),
+        Datum::TinyInt(v) => Value::Int(*v as i32),
+        Datum::SmallInt(v) => Value::Int(*v as i32),
+        Datum::Int(v) | Datum::Time(v) => Value::Int(*v),
+        Datum::BigInt(v) => Value::Long(*v),
+        Datum::Float(v) => Value::Float(*v),
+        Datum::Double(v) => Value::Double(*v),
+        Datum::String(v) => Value::String(v.clone()),
+        Datum::Bytes(v) => Value::Bytes(v.clone()),
+        Datum::Date(v) => Value::Date(*v),
+        Datum::Timestamp { millis, nanos } | Datum::LocalZonedTimestamp { millis, nanos } => {
+            Value::TimestampMicros(millis * 1000 + *nanos as i64 / 1000)
+        }
+        Datum::Decimal { unscaled, .. } => Value::Bytes(unscaled.to_be_bytes().to_vec()),
+    }
+}
+
+/// Writer of the Iceberg manifests and manifest lists in Avro.
+///
+/// Only the required fields of the format version 2 are written, the optional ones such as
+/// the statistics of the columns are read as nulls by Iceberg.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-core/src/main/java/org/apache/paimon/iceberg/manifest/IcebergManifestFile.java>
+#[derive(Debug, Clone)]
+pub struct IcebergManifestWriter {
+    schema: IcebergSchema,
+    spec: IcebergPartitionSpec,
+    partition_types: Vec<DataType>,
+}
+
+impl IcebergManifestWriter {
+    /// Create a writer of the manifests of the schema, partitioned by the spec whose fields are
+    /// of the partition types.
+    pub fn new(
+        schema: IcebergSchema,
+        spec: IcebergPartitionSpec,
+        partition_types: Vec<DataType>,
+    ) -> crate::Result<Self> {
+        for data_type in &partition_types {
+            partition_avro(data_type)?;
+        }
+        Ok(Self {
+            schema,
+            spec,
+            partition_types,
+        })
+    }
+
+    fn manifest_schema(&self) -> crate::Result<Schema> {
+        let mut partition_fields = Vec::with_capacity(self.spec.fields.len());
+        for (field, data_type) in self.spec.fields.iter().zip(&self.partition_types) {
+            partition_fields.push(json!({
+                "name": field.name,
+                "type": ["null", partition_avro(data_type)?],
+                "default": null,
+                "field-id": field.field_id,
+            }));
+        }
+        let schema = json!({
+            "type": "record",
+            "name": "manifest_entry",
+            "fields": [
+                {"name": "status", "type": "int", "field-id": 0},
+                {"name": "snapshot_id", "type": ["null", "long"], "default": null, "field-id": 1},
+                {"name": "sequence_number", "type": ["null", "long"], "default": null, "field-id": 3},
+                {"name": "file_sequence_number", "type": ["null", "long"], "default": null, "field-id": 4},
+                {"name": "data_file", "field-id": 2, "type": {
+                    "type": "record",
+                    "name": "r2",
+                    "fields": [
+                        {"name": "content", "type": "int", "field-id": 134},
+                        {"name": "file_path", "type": "string", "field-id": 100},
+                        {"name": "file_format", "type": "string", "field-id": 101},
+                        {"name": "partition", "field-id": 102, "type": {
+                            "type": "record",
+                            "name": "r102",
+                            "fields": partition_fields,
+                        }},
+                        {"name": "record_count", "type": "long", "field-id": 103},
+                        {"name": "file_size_in_bytes", "type": "long", "field-id": 104},
+                    ],
+                }},
+            ],
+        });
+        Ok(Schema::parse(&schema)?)
+    }
+
+    /// Write the entries into the bytes of a manifest.
+    pub fn write_manifest(&self, entries: &[IcebergManifestEntry]) -> crate::Result<Vec<u8>> {
+        let schema = self.manifest_schema()?;
+        let mut writer = Writer::with_codec(&schema, Vec::new(), Codec::Deflate);
+        let schema_json = serde_json::to_string(&self.schema)?;
+        let spec_json = serde_json::to_string(&self.spec.fields)?;
+        for (key, value) in [
+            ("schema", schema_json),
+            ("schema-id", self.schema.schema_id.to_string()),
+            ("partition-spec", spec_json),
+            ("partition-spec-id", self.spec.spec_id.to_string()),
+            ("format-version", "2".to_string()),
+            ("content", "data".to_string()),
+        ] {
+            writer.add_user_metadata(key.to_string(), value)?;
+        }
+        for entry in entries {
+            let partition = entry
+                .partition
+                .iter()
+                .zip(&self.spec.fields)
+                .map(|(datum, field)| {
+                    (
+                        field.name.clone(),
+                        nullable(datum.as_ref().map(partition_value)),
+                    )
+                })
+                .collect();
+            let data_file = Value::Record(vec![
+                ("content".to_string(), Value::Int(DATA)),
+                (
+                    "file_path".to_string(),
+                    Value::String(entry.file_path.clone()),
+                ),
+                (
+                    "file_format".to_string(),
+                    Value::String(entry.file_format.clone()),
+                ),
+                ("partition".to_string(), Value::Record(partition)),
+                ("record_count".to_string(), Value::Long(entry.record_count)),
+                (
+                    "file_size_in_bytes".to_string(),
+                    Value::Long(entry.file_size_in_bytes),
+                ),
+            ]);
+            let record = Value::Record(vec![
+                ("status".to_string(), Value::Int(ADDED)),
+                (
+                    "snapshot_id".to_string(),
+                    nullable(Some(Value::Long(entry.snapshot_id))),
+                ),
+                (
+                    "sequence_number".to_string(),
+                    nullable(Some(Value::Long(entry.sequence_number))),
+                ),
+                (
+                    "file_sequence_number".to_string(),
+                    nullable(Some(Value::Long(entry.sequence_number))),
+                ),
+                ("data_file".to_string(), data_file),
+            ]);
+            writer.append(record)?;
+        }
+        Ok(writer.into_inner()?)
+    }
+
+    /// Write the manifests of the snapshot into the bytes of a manifest list.
+    pub fn write_manifest_list(
+        &self,
+        snapshot_id: i64,
+        parent_snapshot_id: Option<i64>,
+        sequence_number: i64,
+        manifests: &[IcebergManifestFileMeta],
+    ) -> crate::Result<Vec<u8>> {
+        let schema = Schema::parse(&json!({
+            "type": "record",
+            "name": "manifest_file",
+            "fields": [
+                {"name": "manifest_path", "type": "string", "field-id": 500},
+                {"name": "manifest_length", "type": "long", "field-id": 501},
+                {"name": "partition_spec_id", "type": "int", "field-id": 502},
+                {"name": "content", "type": "int", "field-id": 517},
+                {"name": "sequence_number", "type": "long", "field-id": 515},
+                {"name": "min_sequence_number", "type": "long", "field-id": 516},
+                {"name": "added_snapshot_id", "type": "long", "field-id": 503},
+                {"name": "added_files_count", "type": "int", "field-id": 504},
+                {"name": "existing_files_count", "type": "int", "field-id": 505},
+                {"name": "deleted_files_count", "type": "int", "field-id": 506},
+                {"name": "added_rows_count", "type": "long", "field-id": 512},
+                {"name": "existing_rows_count", "type": "long", "field-id": 513},
+                {"name": "deleted_rows_count", "type": "long", "field-id": 514},
+            ],
+        }))?;
+        let mut writer = Writer::with_codec(&schema, Vec::new(), Codec::Deflate);
+        let parent_snapshot_id = parent_snapshot_id.map_or("null".to_string(), |id| id.to_string());
+        for (key, value) in [
+            ("snapshot-id", snapshot_id.to_string()),
+            ("parent-snapshot-id", parent_snapshot_id),
+            ("sequence-number", sequence_number.to_string()),
+            ("format-version", "2".to_string()),
+        ] {
+            writer.add_user_metadata(key.to_string(), value)?;
+        }
+        for manifest in manifests {
+            let record = Value::Record(vec![
+                (
+                    "manifest_path".to_string(),
+                    Value::String(manifest.manifest_path.clone()),
+                ),
+                (
+                    "manifest_length".to_string(),
+                    Value::Long(manifest.manifest_length),
+                ),
+                (
+                    "partition_spec_id".to_string(),
+                    Value::Int(manifest.partition_spec_id),
+                ),
+                ("content".to_string(), Value::Int(DATA)),
+                (
+                    "sequence_number".to_string(),
+                    Value::Long(manifest.sequence_number),
+                ),
+                (
+                    "min_sequence_number".to_string(),
+                    Value::Long(manifest.sequence_number),
+                ),
+                (
+                    "added_snapshot_id".to_string(),
+                    Value::Long(manifest.added_snapshot_id),
+                ),
+                (
+                    "added_files_count".to_string(),
+                    Value::Int(manifest.added_files_count),
+                ),
+                ("existing_files_count".to_string(), Value::Int(0)),
+                ("deleted_files_count".to_string(), Value::Int(0)),
+                (
+                    "added_rows_count".to_string(),
+                    Value::Long(manifest.added_rows_count),
+                ),
+                ("existing_rows_count".to_string(), Value::Long(0)),
+                ("deleted_rows_count".to_string(), Value::Long(0)),
+            ]);
+            writer.append(record)?;
+        }
+        Ok(writer.into_inner()?)
+    }
+}
@@ -0,0 +1,193 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::spec::{DataField, DataType};
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use std::collections::HashMap;
+
+/// Metadata of an Iceberg table of format version 2, stored as `v<version>.metadata.json`.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-core/src/main/java/org/apache/paimon/iceberg/metadata/IcebergMetadata.java>
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergMetadata {
+    pub format_version: i32,
+    pub table_uuid: String,
+    pub location: String,
+    pub last_sequence_number: i64,
+    pub last_updated_ms: i64,
+    pub last_column_id: i32,
+    pub schemas: Vec<IcebergSchema>,
+    pub current_schema_id: i64,
+    pub partition_specs: Vec<IcebergPartitionSpec>,
+    pub default_spec_id: i32,
+    pub last_partition_id: i32,
+    pub sort_orders: Vec<IcebergSortOrder>,
+    pub default_sort_order_id: i32,
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+    pub current_snapshot_id: i64,
+    pub snapshots: Vec<IcebergSnapshot>,
+    #[serde(default)]
+    pub refs: HashMap<String, IcebergRef>,
+}
+
+impl IcebergMetadata {
+    pub const FORMAT_VERSION: i32 = 2;
+
+    /// Deserialize the metadata from its json representation.
+    pub fn from_json(json: &str) -> crate::Result<IcebergMetadata> {
+        serde_json::from_str(json).context(JsonUnexpectedSnafu {
+            message: "Failed to parse iceberg metadata",
+        })
+    }
+
+    /// Serialize the metadata into its json representation.
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string_pretty(self).context(JsonUnexpectedSnafu {
+            message: "Failed to serialize iceberg metadata",
+        })
+    }
+}
+
+/// Schema of an Iceberg table, whose fields keep the ids of the fields of the table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergSchema {
+    #[serde(rename = "type")]
+    pub typ: String,
+    pub schema_id: i64,
+    pub fields: Vec<IcebergDataField>,
+}
+
+impl IcebergSchema {
+    pub fn new(schema_id: i64, fields: &[DataField]) -> crate::Result<Self> {
+        Ok(Self {
+            typ: "struct".to_string(),
+            schema_id,
+            fields: fields
+                .iter()
+                .map(IcebergDataField::new)
+                .collect::<crate::Result<_>>()?,
+        })
+    }
+}
+
+/// Field of an Iceberg schema, only of the primitive types.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergDataField {
+    pub id: i32,
+    pub name: String,
+    pub required: bool,
+    #[serde(rename = "type")]
+    pub typ: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+}
+
+impl IcebergDataField {
+    pub fn new(field: &DataField) -> crate::Result<Self> {
+        Ok(Self {
+            id: field.id(),
+            name: field.name().to_string(),
+            required: !field.data_type().is_nullable(),
+            typ: iceberg_type(field.data_type())?,
+            doc: field.description().map(str::to_string),
+        })
+    }
+}
+
+/// Get the Iceberg type of the primitive type.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-core/src/main/java/org/apache/paimon/iceberg/metadata/IcebergDataField.java>
+pub fn iceberg_type(data_type: &DataType) -> crate::Result<String> {
+    Ok(match data_type {
+        DataType::Boolean(_) => "boolean".to_string(),
+        DataType::TinyInt(_) | DataType::SmallInt(_) | DataType::Int(_) => "int".to_string(),
+        DataType::BigInt(_) => "long".to_string(),
+        DataType::Float(_) => "float".to_string(),
+        DataType::Double(_) => "double".to_string(),
+        DataType::Decimal(t) => format!("decimal({}, {})", t.precision(), t.scale()),
+        DataType::Char(_) | DataType::VarChar(_) => "string".to_string(),
+        DataType::Binary(_) | DataType::VarBinary(_) => "binary".to_string(),
+        DataType::Date(_) => "date".to_string(),
+        DataType::Timestamp(t) if t.precision() <= 6 => "timestamp".to_string(),
+        DataType::LocalZonedTimestamp(t) if t.precision() <= 6 => "timestamptz".to_string(),
+        _ => {
+            return DataTypeInvalidSnafu {
+                message: format!("Type {data_type} is not supported by iceberg compatibility"),
+            }
+            .fail()
+        }
+    })
+}
+
+/// Spec of the partitions of an Iceberg table, by the identity transforms of the partition
+/// keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergPartitionSpec {
+    pub spec_id: i32,
+    pub fields: Vec<IcebergPartitionField>,
+}
+
+impl IcebergPartitionSpec {
+    /// Ids of the partition fields start from 1000 like Iceberg.
+    pub const FIRST_FIELD_ID: i32 = 1000;
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergPartitionField {
+    pub name: String,
+    pub transform: String,
+    pub source_id: i32,
+    pub field_id: i32,
+}
+
+/// Sort order of an Iceberg table, the data files are unsorted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergSortOrder {
+    pub order_id: i32,
+    pub fields: Vec<serde_json::Value>,
+}
+
+/// Snapshot of an Iceberg table, of the same id as the snapshot of the table.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergSnapshot {
+    pub sequence_number: i64,
+    pub snapshot_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_snapshot_id: Option<i64>,
+    pub timestamp_ms: i64,
+    pub summary: HashMap<String, String>,
+    pub manifest_list: String,
+    pub schema_id: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct IcebergRef {
+    pub snapshot_id: i64,
+    #[serde(rename = "type")]
+    pub typ: String,
+}
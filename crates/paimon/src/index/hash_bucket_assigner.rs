@@ -0,0 +1,208 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::index::{HashIndexFile, HASH_INDEX};
+use crate::spec::{BinaryRow, FileKind, IndexFileMeta};
+use crate::table::{FileStoreTable, IndexIncrement};
+use indexmap::IndexMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// Max number of buckets of a partition of a dynamic bucket table.
+const MAX_BUCKETS: i32 = i16::MAX as i32;
+
+/// Assigner of the buckets of the keys of dynamic bucket tables.
+///
+/// The buckets of the keys already written are restored from the hash index files of the latest
+/// snapshot, new keys are assigned to the first bucket with less than
+/// `dynamic-bucket.target-row-num` keys, or a new bucket if all buckets are full. The hashes of
+/// the buckets assigned new keys are written into new hash index files on commit.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/index/HashBucketAssigner.java>
+#[derive(Debug)]
+pub(crate) struct HashBucketAssigner {
+    table: FileStoreTable,
+    target_row_num: i64,
+    partitions: HashMap<Vec<u8>, PartitionIndex>,
+}
+
+impl HashBucketAssigner {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self {
+            target_row_num: table.core_options().dynamic_bucket_target_row_num(),
+            table,
+            partitions: HashMap::new(),
+        }
+    }
+
+    /// Assign the bucket of the key hash in the partition.
+    pub async fn assign(&mut self, partition: &BinaryRow, hash: i32) -> crate::Result<i32> {
+        let partition_bytes = partition.to_serialized_bytes();
+        if !self.partitions.contains_key(&partition_bytes) {
+            let index = self.load(partition, &partition_bytes).await?;
+            self.partitions.insert(partition_bytes.clone(), index);
+        }
+        let target_row_num = self.target_row_num;
+        self.partitions
+            .get_mut(&partition_bytes)
+            .expect("partition index is loaded")
+            .assign(hash, target_row_num)
+    }
+
+    /// Write the hashes of the buckets assigned new keys into new hash index files, returns the
+    /// index files changed of the partitions and buckets.
+    pub async fn prepare_commit(&mut self) -> crate::Result<Vec<(BinaryRow, i32, IndexIncrement)>> {
+        let index_file =
+            HashIndexFile::new(self.table.file_io().clone(), self.table.path_factory());
+        let mut increments = Vec::new();
+        for index in self.partitions.values_mut() {
+            for bucket in std::mem::take(&mut index.modified_buckets) {
+                let hashes: Vec<i32> = index
+                    .hash_to_bucket
+                    .iter()
+                    .filter(|(_, b)| **b == bucket)
+                    .map(|(hash, _)| *hash)
+                    .collect();
+                let new_file = index_file.write(&hashes).await?;
+                let deleted_index_files = index
+                    .index_files
+                    .insert(bucket, new_file.clone())
+                    .into_iter()
+                    .collect();
+                let increment = IndexIncrement {
+                    new_index_files: vec![new_file],
+                    deleted_index_files,
+                };
+                increments.push((index.partition.clone(), bucket, increment));
+            }
+        }
+        Ok(increments)
+    }
+
+    /// Load the hash index of the partition from the index manifest of the latest snapshot.
+    async fn load(
+        &self,
+        partition: &BinaryRow,
+        partition_bytes: &[u8],
+    ) -> crate::Result<PartitionIndex> {
+        let mut index = PartitionIndex {
+            partition: partition.clone(),
+            hash_to_bucket: IndexMap::new(),
+            non_full_buckets: IndexMap::new(),
+            total_buckets: HashSet::new(),
+            index_files: HashMap::new(),
+            modified_buckets: BTreeSet::new(),
+        };
+        let Some(snapshot) = self.table.snapshot_manager().latest_snapshot().await? else {
+            return Ok(index);
+        };
+        let path_factory = self.table.path_factory();
+        let mut entries = self.table.index_manifest_entries(&snapshot).await?;
+        entries.retain(|entry| {
+            entry.kind == FileKind::Add
+                && entry.index_file.index_type == HASH_INDEX
+                && entry.partition == partition_bytes
+        });
+        entries.sort_by_key(|entry| entry.bucket);
+        let index_file = HashIndexFile::new(self.table.file_io().clone(), path_factory);
+        for entry in entries {
+            for hash in index_file.read(&entry.index_file).await? {
+                index.hash_to_bucket.insert(hash, entry.bucket);
+            }
+            index
+                .non_full_buckets
+                .insert(entry.bucket, entry.index_file.row_count as i64);
+            index.total_buckets.insert(entry.bucket);
+            index.index_files.insert(entry.bucket, entry.index_file);
+        }
+        Ok(index)
+    }
+}
+
+/// Hash index of a partition.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/index/PartitionIndex.java>
+#[derive(Debug)]
+struct PartitionIndex {
+    partition: BinaryRow,
+    hash_to_bucket: IndexMap<i32, i32>,
+    /// Number of keys of the buckets which may not be full, in the order of the buckets.
+    non_full_buckets: IndexMap<i32, i64>,
+    total_buckets: HashSet<i32>,
+    /// Current hash index files of the buckets.
+    index_files: HashMap<i32, IndexFileMeta>,
+    /// Buckets assigned new keys since the last commit.
+    modified_buckets: BTreeSet<i32>,
+}
+
+impl PartitionIndex {
+    fn assign(&mut self, hash: i32, target_row_num: i64) -> crate::Result<i32> {
+        if let Some(bucket) = self.hash_to_bucket.get(&hash) {
+            return Ok(*bucket);
+        }
+        while let Some((&bucket, count)) = self.non_full_buckets.first_mut() {
+            if *count < target_row_num {
+                *count += 1;
+                return Ok(self.assign_to(hash, bucket));
+            }
+            self.non_full_buckets.shift_remove_index(0);
+        }
+        let Some(bucket) = (0..MAX_BUCKETS).find(|b| !self.total_buckets.contains(b)) else {
+            return WriteInvalidSnafu {
+                message: format!(
+                    "Too many buckets {MAX_BUCKETS}, the target row number may be too small"
+                ),
+            }
+            .fail();
+        };
+        self.non_full_buckets.insert(bucket, 1);
+        self.total_buckets.insert(bucket);
+        Ok(self.assign_to(hash, bucket))
+    }
+
+    fn assign_to(&mut self, hash: i32, bucket: i32) -> i32 {
+        self.hash_to_bucket.insert(hash, bucket);
+        self.modified_buckets.insert(bucket);
+        bucket
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partition_index_assign() {
+        let mut index = PartitionIndex {
+            partition: BinaryRow::new(0),
+            hash_to_bucket: IndexMap::from([(10, 1)]),
+            non_full_buckets: IndexMap::from([(1, 1)]),
+            total_buckets: HashSet::from([1]),
+            index_files: HashMap::new(),
+            modified_buckets: BTreeSet::new(),
+        };
+        assert_eq!(index.assign(10, 2).unwrap(), 1);
+        assert!(index.modified_buckets.is_empty());
+        // bucket 1 is full after the new key, then the unused bucket 0 is created
+        let buckets: Vec<i32> = [20, 30, 40, 50, 20]
+            .iter()
+            .map(|hash| index.assign(*hash, 2).unwrap())
+            .collect();
+        assert_eq!(buckets, vec![1, 0, 0, 2, 1]);
+        assert_eq!(index.modified_buckets, BTreeSet::from([0, 1, 2]));
+    }
+}
@@ -0,0 +1,108 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::io::FileIO;
+use crate::spec::IndexFileMeta;
+use crate::utils::FileStorePathFactory;
+use bytes::Bytes;
+use std::sync::Arc;
+
+/// Index type of the hash index files of dynamic bucket tables.
+pub const HASH_INDEX: &str = "HASH";
+
+/// Index file of the hashes of the keys in a bucket of a dynamic bucket table, stored as
+/// big-endian ints.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/index/HashIndexFile.java>
+#[derive(Debug, Clone)]
+pub struct HashIndexFile {
+    file_io: Arc<dyn FileIO>,
+    path_factory: FileStorePathFactory,
+}
+
+impl HashIndexFile {
+    pub fn new(file_io: Arc<dyn FileIO>, path_factory: FileStorePathFactory) -> Self {
+        Self {
+            file_io,
+            path_factory,
+        }
+    }
+
+    /// Read the hashes of the index file.
+    pub async fn read(&self, file_meta: &IndexFileMeta) -> crate::Result<Vec<i32>> {
+        let path = self.path_factory.index_path(&file_meta.file_name);
+        let bytes = self.file_io.new_input(&path)?.read().await?;
+        if bytes.len() % 4 != 0 {
+            return DataCorruptedSnafu {
+                message: format!(
+                    "Size of hash index file {path} is {}, not a multiple of 4",
+                    bytes.len()
+                ),
+            }
+            .fail();
+        }
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|int| i32::from_be_bytes(int.try_into().unwrap()))
+            .collect())
+    }
+
+    /// Write the hashes into a new index file.
+    pub async fn write(&self, hashes: &[i32]) -> crate::Result<IndexFileMeta> {
+        let bytes: Vec<u8> = hashes.iter().flat_map(|hash| hash.to_be_bytes()).collect();
+        let file_name = format!("index-{}-0", uuid::Uuid::new_v4());
+        let file_size = bytes.len() as i32;
+        self.file_io
+            .new_output(&self.path_factory.index_path(&file_name))?
+            .write(Bytes::from(bytes))
+            .await?;
+        Ok(IndexFileMeta {
+            index_type: HASH_INDEX.to_string(),
+            file_name,
+            file_size,
+            row_count: hashes.len() as i32,
+            deletion_vectors_ranges: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::RowType;
+
+    #[tokio::test]
+    async fn test_write_and_read_hash_index() {
+        let file_io = FileIOBuilder::from_url("memory:/")
+            .unwrap()
+            .build()
+            .unwrap();
+        let path_factory = FileStorePathFactory::new(
+            "memory:/test_hash_index",
+            RowType::new(vec![]),
+            "__DEFAULT_PARTITION__",
+        );
+        let index_file = HashIndexFile::new(file_io, path_factory);
+        let hashes = vec![1, -1, i32::MAX, i32::MIN];
+        let meta = index_file.write(&hashes).await.unwrap();
+        assert_eq!(meta.index_type, HASH_INDEX);
+        assert_eq!((meta.file_size, meta.row_count), (16, 4));
+        assert_eq!(index_file.read(&meta).await.unwrap(), hashes);
+    }
+}
@@ -0,0 +1,129 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::io::FileRead;
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt};
+use std::ops::Range;
+
+/// Reader of ranges of a file, coalescing the ranges close to each other into fewer reads and
+/// reading the next coalesced ranges ahead while the current one is read.
+///
+/// Requests to object stores are dominated by their latency, reading the gap between two
+/// ranges is usually cheaper than another request.
+#[derive(Debug)]
+pub struct CoalescingFileRead<R> {
+    reader: R,
+    max_gap: u64,
+    max_size: u64,
+    prefetch: usize,
+}
+
+impl<R: FileRead + Sync> CoalescingFileRead<R> {
+    /// Create a reader coalescing the ranges apart by at most `max_gap` bytes until the
+    /// coalesced range exceeds `max_size` bytes, reading `prefetch` coalesced ranges ahead.
+    pub fn new(reader: R, max_gap: u64, max_size: u64, prefetch: usize) -> Self {
+        Self {
+            reader,
+            max_gap,
+            max_size,
+            prefetch,
+        }
+    }
+
+    /// Read a single range of the file.
+    pub async fn read(&self, range: Range<u64>) -> crate::Result<Bytes> {
+        self.reader.read(range).await
+    }
+
+    /// Read the ranges of the file, the bytes are in the order of the ranges.
+    pub async fn read_ranges(&self, ranges: &[Range<u64>]) -> crate::Result<Vec<Bytes>> {
+        let coalesced = coalesce_ranges(ranges, self.max_gap, self.max_size);
+        let chunks: Vec<Bytes> = futures::stream::iter(coalesced.iter().cloned())
+            .map(|range| self.reader.read(range))
+            .buffered(self.prefetch + 1)
+            .try_collect()
+            .await?;
+        Ok(ranges
+            .iter()
+            .map(|range| {
+                if range.is_empty() {
+                    return Bytes::new();
+                }
+                // the coalesced ranges are sorted and disjoint
+                let i = coalesced.partition_point(|coalesced| coalesced.end < range.end);
+                let start = coalesced[i].start;
+                chunks[i].slice((range.start - start) as usize..(range.end - start) as usize)
+            })
+            .collect())
+    }
+}
+
+/// Coalesce the ranges into sorted disjoint ranges covering all of them.
+pub(crate) fn coalesce_ranges(
+    ranges: &[Range<u64>],
+    max_gap: u64,
+    max_size: u64,
+) -> Vec<Range<u64>> {
+    let mut sorted = ranges
+        .iter()
+        .filter(|range| !range.is_empty())
+        .cloned()
+        .collect::<Vec<_>>();
+    sorted.sort_by_key(|range| range.start);
+    let mut coalesced: Vec<Range<u64>> = Vec::with_capacity(sorted.len());
+    for range in sorted {
+        match coalesced.last_mut() {
+            // overlapping ranges are always coalesced
+            Some(last)
+                if range.start <= last.end
+                    || (range.start - last.end <= max_gap
+                        && range.end.max(last.end) - last.start <= max_size) =>
+            {
+                last.end = last.end.max(range.end);
+            }
+            _ => coalesced.push(range),
+        }
+    }
+    coalesced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_coalesced_ranges() {
+        assert_eq!(
+            coalesce_ranges(&[20..30, 0..10, 12..15, 25..40, 100..110], 5, 100),
+            vec![0..40, 100..110]
+        );
+        // ranges are not coalesced beyond the max size
+        assert_eq!(
+            coalesce_ranges(&[0..10, 12..20], 5, 15),
+            vec![0..10, 12..20]
+        );
+
+        let bytes = Bytes::from((0..=255).collect::<Vec<u8>>());
+        let reader = CoalescingFileRead::new(bytes.clone(), 5, 100, 1);
+        let ranges = [20..30, 0..10, 12..15, 25..40, 100..110, 5..5];
+        let chunks = reader.read_ranges(&ranges).await.unwrap();
+        for (range, chunk) in ranges.iter().zip(chunks) {
+            assert_eq!(chunk, bytes.slice(range.start as usize..range.end as usize));
+        }
+    }
+}
@@ -17,39 +17,197 @@
 
 use crate::error::*;
 use std::collections::HashMap;
+use std::fmt::Debug;
 use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
+use async_trait::async_trait;
 use bytes::Bytes;
 use chrono::{DateTime, Utc};
-use opendal::Operator;
+use opendal::layers::RetryLayer;
+use opendal::{ErrorKind, Metakey, Operator};
 use snafu::ResultExt;
+use tokio::sync::Mutex;
 use url::Url;
 
 use super::Storage;
+use crate::spec::parse_duration;
+
+/// Max number of attempts to list a directory whose entries are changing.
+const MAX_LIST_ATTEMPTS: usize = 3;
+
+/// Whether [`FileIO::try_to_write_atomic`] writes by conditional puts of the object store
+/// instead of renames, only supported by s3 and gcs.
+pub const FS_CONDITIONAL_WRITE: &str = "fs.conditional-write";
+
+/// Expiration of the requests presigned for conditional puts.
+const CONDITIONAL_WRITE_EXPIRATION: Duration = Duration::from_secs(300);
+
+/// Max times of retrying the operations failed by retryable errors, 0 to disable retries.
+pub const FS_RETRY_MAX_TIMES: &str = "fs.retry.max-times";
+/// Delay before the first retry like `1 s`, doubled for each following retry.
+pub const FS_RETRY_MIN_DELAY: &str = "fs.retry.min-delay";
+/// Max delay between two retries like `60 s`.
+pub const FS_RETRY_MAX_DELAY: &str = "fs.retry.max-delay";
+
+/// Exponential backoff of retrying operations failed by [retryable](Error::is_retryable)
+/// errors, like throttles of object stores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Backoff {
+    max_times: usize,
+    min_delay: Duration,
+    max_delay: Duration,
+}
 
-#[derive(Clone, Debug)]
-pub struct FileIO {
-    storage: Arc<Storage>,
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            max_times: 3,
+            min_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
 }
 
-impl FileIO {
-    /// Try to infer file io scheme from path.
+impl Backoff {
+    fn from_props(props: &HashMap<String, String>) -> Result<Self> {
+        let invalid = |key: &str, value: &str| Error::ConfigInvalid {
+            message: format!("Invalid value '{}' of '{}'", value, key),
+        };
+        let mut backoff = Self::default();
+        if let Some(v) = props.get(FS_RETRY_MAX_TIMES) {
+            backoff.max_times = v
+                .trim()
+                .parse()
+                .map_err(|_| invalid(FS_RETRY_MAX_TIMES, v))?;
+        }
+        if let Some(v) = props.get(FS_RETRY_MIN_DELAY) {
+            backoff.min_delay = parse_duration(v).ok_or_else(|| invalid(FS_RETRY_MIN_DELAY, v))?;
+        }
+        if let Some(v) = props.get(FS_RETRY_MAX_DELAY) {
+            backoff.max_delay = parse_duration(v).ok_or_else(|| invalid(FS_RETRY_MAX_DELAY, v))?;
+        }
+        Ok(backoff)
+    }
+
+    /// The delay before the retry of the attempt, counted from 0.
+    fn delay(&self, attempt: usize) -> Duration {
+        let factor = 1u32.checked_shl(attempt as u32).unwrap_or(u32::MAX);
+        self.min_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    fn layer(&self) -> RetryLayer {
+        RetryLayer::new()
+            .with_max_times(self.max_times)
+            .with_min_delay(self.min_delay)
+            .with_max_delay(self.max_delay)
+            .with_factor(2.0)
+            .with_jitter()
+    }
+}
+
+/// The file system of tables, the spec readers and writers read and write their files through
+/// `&dyn FileIO`.
+///
+/// [`OpenDalFileIO`] is the implementation of the storages of OpenDAL, built by
+/// [`FileIOBuilder`].
+///
+/// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java>
+#[async_trait]
+pub trait FileIO: Debug + Send + Sync {
+    /// Create a new input file to read data.
+    fn new_input(&self, path: &str) -> Result<InputFile>;
+
+    /// Create a new output file to write data.
+    fn new_output(&self, path: &str) -> Result<OutputFile>;
+
+    /// Return a file status object that represents the path.
+    async fn get_status(&self, path: &str) -> Result<FileStatus>;
+
+    /// List the statuses of the files/directories in the given path if the path is a directory.
+    async fn list_status(&self, path: &str) -> Result<Vec<FileStatus>>;
+
+    /// Check if exists.
+    async fn exists(&self, path: &str) -> Result<bool>;
+
+    /// Delete a file.
+    async fn delete_file(&self, path: &str) -> Result<()>;
+
+    /// Delete a dir recursively.
+    async fn delete_dir(&self, path: &str) -> Result<()>;
+
+    /// Make the given file and all non-existent parents into directories.
+    async fn mkdirs(&self, path: &str) -> Result<()>;
+
+    /// Renames the file/directory src to dst.
+    async fn rename(&self, src: &str, dst: &str) -> Result<()>;
+
+    /// Read the whole file as an utf8 string.
+    async fn read_file_utf8(&self, path: &str) -> Result<String> {
+        let bytes = self.new_input(path)?.read().await?;
+        String::from_utf8(bytes.to_vec()).map_err(|e| Error::ConfigInvalid {
+            message: format!("File '{}' is not valid utf8: {}", path, e),
+        })
+    }
+
+    /// Write the content to the path if the path is absent, returns `false` if the path already
+    /// exists.
     ///
-    /// The input HashMap is paimon-java's [`Options`](https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/options/Options.java#L60)
-    pub fn from_url(path: &str) -> crate::Result<FileIOBuilder> {
-        let url = Url::parse(path).map_err(|_| Error::ConfigInvalid {
-            message: format!("Invalid URL: {}", path),
-        })?;
+    /// Concurrent writers of the same path, in any process, see exactly one success.
+    async fn try_to_write_atomic(&self, path: &str, content: Bytes) -> Result<bool>;
 
-        Ok(FileIOBuilder::new(url.scheme()))
+    /// Write the content to the path if the path is absent while the caller holds a lock
+    /// excluding the other writers of the path, such as a [catalog lock], returns `false` if
+    /// the path already exists.
+    ///
+    /// The content is written into a temporary file renamed to the path after the path is
+    /// checked absent, which is only safe under the lock.
+    ///
+    /// [catalog lock]: crate::catalog::CatalogLock
+    async fn try_to_write_locked(&self, path: &str, content: Bytes) -> Result<bool> {
+        if self.exists(path).await? {
+            return Ok(false);
+        }
+        let tmp_path = temp_path(path);
+        self.new_output(&tmp_path)?.write(content).await?;
+        let renamed = self.rename(&tmp_path, path).await;
+        if renamed.is_err() {
+            let _ = self.delete_file(&tmp_path).await;
+        }
+        renamed.map(|_| true)
     }
+}
 
+/// The [`FileIO`] of the storages of OpenDAL.
+#[derive(Clone, Debug)]
+pub struct OpenDalFileIO {
+    storage: Arc<Storage>,
+    conditional_write: bool,
+    backoff: Backoff,
+}
+
+impl OpenDalFileIO {
+    /// Create the operator of the path, retrying the operations failed by retryable errors.
+    fn create_operator<'a>(&self, path: &'a str) -> Result<(Operator, &'a str)> {
+        let (op, relative_path) = self.storage.create(path)?;
+        if self.backoff.max_times == 0 {
+            return Ok((op, relative_path));
+        }
+        Ok((op.layer(self.backoff.layer()), relative_path))
+    }
+}
+
+#[async_trait]
+impl FileIO for OpenDalFileIO {
     /// Create a new input file to read data.
     ///
     /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java#L76>
-    pub fn new_input(&self, path: &str) -> crate::Result<InputFile> {
-        let (op, relative_path) = self.storage.create(path)?;
+    fn new_input(&self, path: &str) -> Result<InputFile> {
+        let (op, relative_path) = self.create_operator(path)?;
         let path = path.to_string();
         let relative_path_pos = path.len() - relative_path.len();
         Ok(InputFile {
@@ -62,8 +220,8 @@ impl FileIO {
     /// Create a new output file to write data.
     ///
     /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java#L87>
-    pub fn new_output(&self, path: &str) -> Result<OutputFile> {
-        let (op, relative_path) = self.storage.create(path)?;
+    fn new_output(&self, path: &str) -> Result<OutputFile> {
+        let (op, relative_path) = self.create_operator(path)?;
         let path = path.to_string();
         let relative_path_pos = path.len() - relative_path.len();
         Ok(OutputFile {
@@ -76,8 +234,8 @@ impl FileIO {
     /// Return a file status object that represents the path.
     ///
     /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java#L97>
-    pub async fn get_status(&self, path: &str) -> Result<FileStatus> {
-        let (op, relative_path) = self.storage.create(path)?;
+    async fn get_status(&self, path: &str) -> Result<FileStatus> {
+        let (op, relative_path) = self.create_operator(path)?;
         let meta = op.stat(relative_path).await.context(IoUnexpectedSnafu {
             message: format!("Failed to get file status for '{}'", path),
         })?;
@@ -95,21 +253,45 @@ impl FileIO {
     /// References: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java#L105>
     ///
     /// FIXME: how to handle large dir? Better to return a stream instead?
-    pub async fn list_status(&self, path: &str) -> Result<Vec<FileStatus>> {
-        let (op, relative_path) = self.storage.create(path)?;
-
-        let entries = op.list(relative_path).await.context(IoUnexpectedSnafu {
-            message: format!("Failed to list files in '{}'", path),
-        })?;
+    async fn list_status(&self, path: &str) -> Result<Vec<FileStatus>> {
+        // opendal only lists the children of a path ending with `/`.
+        let path = if path.ends_with('/') {
+            path.to_string()
+        } else {
+            format!("{}/", path)
+        };
+        let (op, relative_path) = self.create_operator(&path)?;
+
+        // the metadata of an entry renamed or deleted by another writer while listing is
+        // missing, so the listing is retried
+        let mut attempts = 0;
+        let entries = loop {
+            attempts += 1;
+            match op
+                .list_with(relative_path)
+                .metakey(Metakey::ContentLength | Metakey::LastModified)
+                .await
+            {
+                Err(e) if e.kind() == ErrorKind::NotFound && attempts < MAX_LIST_ATTEMPTS => {}
+                result => {
+                    break result.context(IoUnexpectedSnafu {
+                        message: format!("Failed to list files in '{}'", path),
+                    })?
+                }
+            }
+        };
 
         let mut statuses = Vec::new();
 
         for entry in entries {
+            if entry.path() == relative_path {
+                continue;
+            }
             let meta = entry.metadata();
             statuses.push(FileStatus {
                 size: meta.content_length(),
                 is_dir: meta.is_dir(),
-                path: path.to_string(),
+                path: format!("{}{}", path, entry.name().trim_end_matches('/')),
                 last_modified: meta.last_modified(),
             });
         }
@@ -120,8 +302,8 @@ impl FileIO {
     /// Check if exists.
     ///
     /// References: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java#L128>
-    pub async fn exists(&self, path: &str) -> Result<bool> {
-        let (op, relative_path) = self.storage.create(path)?;
+    async fn exists(&self, path: &str) -> Result<bool> {
+        let (op, relative_path) = self.create_operator(path)?;
 
         op.is_exist(relative_path).await.context(IoUnexpectedSnafu {
             message: format!("Failed to check existence of '{}'", path),
@@ -131,8 +313,8 @@ impl FileIO {
     /// Delete a file.
     ///
     /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java#L139>
-    pub async fn delete_file(&self, path: &str) -> Result<()> {
-        let (op, relative_path) = self.storage.create(path)?;
+    async fn delete_file(&self, path: &str) -> Result<()> {
+        let (op, relative_path) = self.create_operator(path)?;
 
         op.delete(relative_path).await.context(IoUnexpectedSnafu {
             message: format!("Failed to delete file '{}'", path),
@@ -144,8 +326,8 @@ impl FileIO {
     /// Delete a dir recursively.
     ///
     /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java#L139>
-    pub async fn delete_dir(&self, path: &str) -> Result<()> {
-        let (op, relative_path) = self.storage.create(path)?;
+    async fn delete_dir(&self, path: &str) -> Result<()> {
+        let (op, relative_path) = self.create_operator(path)?;
 
         op.remove_all(relative_path)
             .await
@@ -161,8 +343,8 @@ impl FileIO {
     /// Has the semantics of Unix 'mkdir -p'. Existence of the directory hierarchy is not an error.
     ///
     /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java#L150>
-    pub async fn mkdirs(&self, path: &str) -> Result<()> {
-        let (op, relative_path) = self.storage.create(path)?;
+    async fn mkdirs(&self, path: &str) -> Result<()> {
+        let (op, relative_path) = self.create_operator(path)?;
 
         op.create_dir(relative_path)
             .await
@@ -176,9 +358,9 @@ impl FileIO {
     /// Renames the file/directory src to dst.
     ///
     /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java#L159>
-    pub async fn rename(&self, src: &str, dst: &str) -> Result<()> {
-        let (op_src, relative_path_src) = self.storage.create(src)?;
-        let (_, relative_path_dst) = self.storage.create(dst)?;
+    async fn rename(&self, src: &str, dst: &str) -> Result<()> {
+        let (op_src, relative_path_src) = self.create_operator(src)?;
+        let (_, relative_path_dst) = self.create_operator(dst)?;
 
         op_src
             .rename(relative_path_src, relative_path_dst)
@@ -189,6 +371,270 @@ impl FileIO {
 
         Ok(())
     }
+
+    /// Write the content into a temporary file and move it to the path if the path is
+    /// absent, returns `false` if the path already exists.
+    ///
+    /// The move never overwrites, so concurrent writers of the same path, in any process,
+    /// will see exactly one success: a hard link on the local file system, a rename of
+    /// webhdfs, or a put only if absent with [`FS_CONDITIONAL_WRITE`] on the object stores.
+    /// Other object stores can't write atomically and fail, their writers hold a catalog lock
+    /// and write by [`FileIO::try_to_write_locked`] instead.
+    ///
+    /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/fs/FileIO.java>
+    async fn try_to_write_atomic(&self, path: &str, content: Bytes) -> Result<bool> {
+        if self.conditional_write {
+            return self.put_if_absent(path, content).await;
+        }
+
+        let tmp_path = temp_path(path);
+        let result = match self.storage.as_ref() {
+            #[cfg(feature = "storage-fs")]
+            Storage::LocalFs => self.write_and_link(&tmp_path, path, content).await,
+            #[cfg(feature = "storage-memory")]
+            Storage::Memory(_) => self.write_and_rename(&tmp_path, path, content).await,
+            #[cfg(feature = "storage-webhdfs")]
+            Storage::WebHdfs { config } => {
+                self.write_and_rename_webhdfs(config, &tmp_path, path, content)
+                    .await
+            }
+            #[allow(unreachable_patterns)]
+            _ => {
+                return IoUnsupportedSnafu {
+                    message: format!(
+                        "Atomic write of '{}' needs '{}' or a catalog lock on this storage",
+                        path, FS_CONDITIONAL_WRITE
+                    ),
+                }
+                .fail()
+            }
+        };
+
+        let _ = self.delete_file(&tmp_path).await;
+        result
+    }
+
+    /// Write by [`FileIO::try_to_write_atomic`] if the storage supports it. Otherwise the
+    /// object is put after the path is checked absent, since object stores can't rename, but
+    /// their puts replace objects whole so readers never see partial contents.
+    async fn try_to_write_locked(&self, path: &str, content: Bytes) -> Result<bool> {
+        if self.atomic_write_supported() {
+            return self.try_to_write_atomic(path, content).await;
+        }
+        if self.exists(path).await? {
+            return Ok(false);
+        }
+        self.new_output(path)?.write(content).await?;
+        Ok(true)
+    }
+}
+
+impl OpenDalFileIO {
+    /// Whether [`FileIO::try_to_write_atomic`] is supported by the storage.
+    fn atomic_write_supported(&self) -> bool {
+        if self.conditional_write {
+            return true;
+        }
+        match self.storage.as_ref() {
+            #[cfg(feature = "storage-fs")]
+            Storage::LocalFs => true,
+            #[cfg(feature = "storage-memory")]
+            Storage::Memory(_) => true,
+            #[cfg(feature = "storage-webhdfs")]
+            Storage::WebHdfs { .. } => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Hard link the temporary file to the path, which fails if the path exists.
+    #[cfg(feature = "storage-fs")]
+    async fn write_and_link(&self, tmp_path: &str, path: &str, content: Bytes) -> Result<bool> {
+        self.new_output(tmp_path)?.write(content).await?;
+
+        let (_, src) = self.create_operator(tmp_path)?;
+        let (_, dst) = self.create_operator(path)?;
+        let (src, dst) = (format!("/{}", src), format!("/{}", dst));
+        let linked = tokio::task::spawn_blocking(move || std::fs::hard_link(src, dst))
+            .await
+            .map_err(std::io::Error::other);
+        match linked.and_then(|r| r) {
+            Ok(()) => Ok(true),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(Error::IoUnexpected {
+                message: format!("Failed to link '{}' to '{}'", tmp_path, path),
+                source: Box::new(
+                    opendal::Error::new(ErrorKind::Unexpected, "hard link failed").set_source(e),
+                ),
+            }),
+        }
+    }
+
+    /// Rename the temporary file to the path if absent, the memory storage is only shared in
+    /// this process, so a process-wide lock makes the check-and-rename atomic.
+    #[cfg(feature = "storage-memory")]
+    async fn write_and_rename(&self, tmp_path: &str, path: &str, content: Bytes) -> Result<bool> {
+        self.new_output(tmp_path)?.write(content).await?;
+
+        let _guard = RENAME_LOCK.lock().await;
+        if self.exists(path).await? {
+            return Ok(false);
+        }
+        self.rename(tmp_path, path).await.map(|_| true)
+    }
+
+    /// Rename the temporary file to the path by the rename of webhdfs, which returns `false`
+    /// instead of overwriting the path if it exists.
+    #[cfg(feature = "storage-webhdfs")]
+    async fn write_and_rename_webhdfs(
+        &self,
+        config: &opendal::services::WebhdfsConfig,
+        tmp_path: &str,
+        path: &str,
+        content: Bytes,
+    ) -> Result<bool> {
+        self.new_output(tmp_path)?.write(content).await?;
+
+        let request = super::webhdfs_rename_request(config, tmp_path, path)?;
+        let message = || format!("Failed to rename '{}' to '{}'", tmp_path, path);
+        let response = opendal::raw::HttpClient::new()
+            .context(IoUnexpectedSnafu {
+                message: "Failed to create http client".to_string(),
+            })?
+            .send(request)
+            .await
+            .context(IoUnexpectedSnafu { message: message() })?;
+        let status = response.status().as_u16();
+        let renamed = match status {
+            200 => serde_json::from_slice::<serde_json::Value>(&response.into_body().to_bytes())
+                .ok()
+                .and_then(|body| body["boolean"].as_bool()),
+            _ => None,
+        };
+        renamed.ok_or_else(|| Error::IoUnexpected {
+            message: message(),
+            source: Box::new(opendal::Error::new(
+                ErrorKind::Unexpected,
+                format!("unexpected response of status {}", status),
+            )),
+        })
+    }
+}
+
+impl OpenDalFileIO {
+    /// Put the content by a presigned request with the precondition of the path being absent,
+    /// returns `false` if the precondition failed.
+    ///
+    /// A retried put isn't idempotent: the failed attempt may have landed before the
+    /// connection broke, failing the precondition of the retry. So once an attempt failed,
+    /// the object is read back and the put succeeded if it holds the content.
+    async fn put_if_absent(&self, path: &str, content: Bytes) -> Result<bool> {
+        let mut attempt = 0;
+        loop {
+            match self.try_put_if_absent(path, content.clone()).await {
+                Err(e) if e.is_retryable() && attempt < self.backoff.max_times => {
+                    tokio::time::sleep(self.backoff.delay(attempt)).await;
+                    attempt += 1;
+                }
+                Ok(false) if attempt > 0 => return self.holds_content(path, &content).await,
+                Err(e) if attempt > 0 => {
+                    return match self.holds_content(path, &content).await {
+                        Ok(true) => Ok(true),
+                        _ => Err(e),
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Whether the file of the path exists and holds exactly the content.
+    async fn holds_content(&self, path: &str, content: &Bytes) -> Result<bool> {
+        let (op, relative_path) = self.create_operator(path)?;
+        match op.read(relative_path).await {
+            Ok(buffer) => Ok(buffer.to_bytes() == content),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(false),
+            Err(e) => Err(e).context(IoUnexpectedSnafu {
+                message: format!("Failed to read back '{}'", path),
+            }),
+        }
+    }
+
+    async fn try_put_if_absent(&self, path: &str, content: Bytes) -> Result<bool> {
+        let (op, relative_path) = self.create_operator(path)?;
+        let Some((name, value)) = self.storage.if_absent_header() else {
+            return IoUnsupportedSnafu {
+                message: format!("Conditional write is not supported for '{}'", path),
+            }
+            .fail();
+        };
+        let presigned = op
+            .presign_write(relative_path, CONDITIONAL_WRITE_EXPIRATION)
+            .await
+            .context(IoUnexpectedSnafu {
+                message: format!("Failed to presign the write of '{}'", path),
+            })?;
+
+        let mut request = http::Request::builder()
+            .method(presigned.method().clone())
+            .uri(presigned.uri().clone());
+        // Object stores require the length of the content, instead of the empty body the
+        // request is presigned with.
+        for (header, header_value) in presigned.header() {
+            if header != http::header::CONTENT_LENGTH {
+                request = request.header(header, header_value);
+            }
+        }
+        let request = request
+            .header(http::header::CONTENT_LENGTH, content.len())
+            .header(name, value)
+            .body(opendal::Buffer::from(content))
+            .map_err(|e| Error::IoUnexpected {
+                message: format!("Failed to build the conditional write of '{}'", path),
+                source: Box::new(opendal::Error::new(ErrorKind::Unexpected, e.to_string())),
+            })?;
+        let response = opendal::raw::HttpClient::new()
+            .context(IoUnexpectedSnafu {
+                message: "Failed to create http client".to_string(),
+            })?
+            .send(request)
+            .await
+            .context(IoUnexpectedSnafu {
+                message: format!("Failed to write '{}' conditionally", path),
+            })?;
+
+        match response.status().as_u16() {
+            200..=299 => Ok(true),
+            412 => Ok(false),
+            status => {
+                let source = opendal::Error::new(
+                    ErrorKind::Unexpected,
+                    format!("unexpected status {}", status),
+                );
+                // Throttles and failures of the servers are transient. 409 is returned by s3
+                // when a concurrent conditional write is in progress, which may be a former
+                // attempt of this put, so it's retried until the write settles.
+                let source = match status {
+                    409 | 429 | 500 | 502 | 503 | 504 => source.set_temporary(),
+                    _ => source,
+                };
+                Err(Error::IoUnexpected {
+                    message: format!("Failed to write '{}' conditionally", path),
+                    source: Box::new(source),
+                })
+            }
+        }
+    }
+}
+
+/// Global lock to make the check-and-rename of the memory storage atomic.
+#[cfg(feature = "storage-memory")]
+static RENAME_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Create a hidden temporary path next to the given path.
+fn temp_path(path: &str) -> String {
+    let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+    format!("{}/.{}.{}.tmp", parent, name, uuid::Uuid::new_v4())
 }
 
 #[derive(Debug)]
@@ -198,6 +644,17 @@ pub struct FileIOBuilder {
 }
 
 impl FileIOBuilder {
+    /// Try to infer file io scheme from path.
+    ///
+    /// The input HashMap is paimon-java's [`Options`](https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/options/Options.java#L60)
+    pub fn from_url(path: &str) -> crate::Result<FileIOBuilder> {
+        let url = Url::parse(path).map_err(|_| Error::ConfigInvalid {
+            message: format!("Invalid URL: {}", path),
+        })?;
+
+        Ok(FileIOBuilder::new(url.scheme()))
+    }
+
     pub fn new(scheme_str: impl ToString) -> Self {
         Self {
             scheme_str: Some(scheme_str.to_string()),
@@ -223,11 +680,25 @@ impl FileIOBuilder {
         self
     }
 
-    pub fn build(self) -> crate::Result<FileIO> {
+    pub fn build(self) -> crate::Result<Arc<dyn FileIO>> {
+        let conditional_write = match self.props.get(FS_CONDITIONAL_WRITE) {
+            Some(v) => v.parse::<bool>().map_err(|_| Error::ConfigInvalid {
+                message: format!("Invalid value '{}' of '{}'", v, FS_CONDITIONAL_WRITE),
+            })?,
+            None => false,
+        };
+        let backoff = Backoff::from_props(&self.props)?;
         let storage = Storage::build(self)?;
-        Ok(FileIO {
+        if conditional_write && storage.if_absent_header().is_none() {
+            return Err(Error::ConfigInvalid {
+                message: format!("'{}' is not supported by the storage", FS_CONDITIONAL_WRITE),
+            });
+        }
+        Ok(Arc::new(OpenDalFileIO {
             storage: Arc::new(storage),
-        })
+            conditional_write,
+            backoff,
+        }))
     }
 }
 
@@ -243,6 +714,13 @@ impl FileRead for opendal::Reader {
     }
 }
 
+#[async_trait::async_trait]
+impl FileRead for Bytes {
+    async fn read(&self, range: Range<u64>) -> crate::Result<Bytes> {
+        Ok(self.slice(range.start as usize..range.end as usize))
+    }
+}
+
 #[async_trait::async_trait]
 pub trait FileWrite: Send + Unpin + 'static {
     async fn write(&mut self, bs: Bytes) -> crate::Result<()>;
@@ -357,23 +835,28 @@ mod file_action_test {
     use std::fs;
 
     use super::*;
+    use crate::io::memory_config_build;
     use bytes::Bytes;
 
-    fn setup_memory_file_io() -> FileIO {
-        let storage = Storage::Memory;
-        FileIO {
+    fn setup_memory_file_io() -> OpenDalFileIO {
+        let storage = Storage::Memory(memory_config_build().unwrap());
+        OpenDalFileIO {
             storage: Arc::new(storage),
+            conditional_write: false,
+            backoff: Backoff::default(),
         }
     }
 
-    fn setup_fs_file_io() -> FileIO {
+    fn setup_fs_file_io() -> OpenDalFileIO {
         let storage = Storage::LocalFs;
-        FileIO {
+        OpenDalFileIO {
             storage: Arc::new(storage),
+            conditional_write: false,
+            backoff: Backoff::default(),
         }
     }
 
-    async fn common_test_get_status(file_io: &FileIO, path: &str) {
+    async fn common_test_get_status(file_io: &dyn FileIO, path: &str) {
         let output = file_io.new_output(path).unwrap();
         let mut writer = output.writer().await.unwrap();
         writer.write(Bytes::from("hello world")).await.unwrap();
@@ -385,7 +868,7 @@ mod file_action_test {
         file_io.delete_file(path).await.unwrap();
     }
 
-    async fn common_test_exists(file_io: &FileIO, path: &str) {
+    async fn common_test_exists(file_io: &dyn FileIO, path: &str) {
         let output = file_io.new_output(path).unwrap();
         let mut writer = output.writer().await.unwrap();
         writer.write(Bytes::from("hello world")).await.unwrap();
@@ -397,7 +880,7 @@ mod file_action_test {
         file_io.delete_file(path).await.unwrap();
     }
 
-    async fn common_test_delete_file(file_io: &FileIO, path: &str) {
+    async fn common_test_delete_file(file_io: &dyn FileIO, path: &str) {
         let output = file_io.new_output(path).unwrap();
         let mut writer = output.writer().await.unwrap();
         writer.write(Bytes::from("hello world")).await.unwrap();
@@ -409,7 +892,7 @@ mod file_action_test {
         assert!(!exists);
     }
 
-    async fn common_test_mkdirs(file_io: &FileIO, dir_path: &str) {
+    async fn common_test_mkdirs(file_io: &dyn FileIO, dir_path: &str) {
         file_io.mkdirs(dir_path).await.unwrap();
 
         let exists = file_io.exists(dir_path).await.unwrap();
@@ -418,7 +901,7 @@ mod file_action_test {
         let _ = fs::remove_dir_all(dir_path.strip_prefix("file:/").unwrap());
     }
 
-    async fn common_test_rename(file_io: &FileIO, src: &str, dst: &str) {
+    async fn common_test_rename(file_io: &dyn FileIO, src: &str, dst: &str) {
         let output = file_io.new_output(src).unwrap();
         let mut writer = output.writer().await.unwrap();
         writer.write(Bytes::from("hello world")).await.unwrap();
@@ -464,6 +947,117 @@ mod file_action_test {
         common_test_mkdirs(&file_io, "file:/tmp/test_fs_dir/").await;
     }
 
+    #[tokio::test]
+    async fn test_list_status_fs() {
+        let file_io = setup_fs_file_io();
+        let dir = "file:/tmp/test_list_status_fs";
+        for name in ["a", "b", "sub/c"] {
+            file_io
+                .new_output(&format!("{dir}/{name}"))
+                .unwrap()
+                .write(Bytes::from("hello world"))
+                .await
+                .unwrap();
+        }
+
+        let mut statuses = file_io.list_status(dir).await.unwrap();
+        statuses.sort_by(|a, b| a.path.cmp(&b.path));
+        let paths: Vec<_> = statuses.iter().map(|s| s.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "file:/tmp/test_list_status_fs/a",
+                "file:/tmp/test_list_status_fs/b",
+                "file:/tmp/test_list_status_fs/sub"
+            ]
+        );
+        assert!(statuses[2].is_dir);
+
+        file_io.delete_dir(dir).await.unwrap();
+        assert!(file_io.list_status(dir).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_try_to_write_atomic_fs() {
+        let file_io = setup_fs_file_io();
+        let dir = "file:/tmp/test_try_to_write_atomic_fs";
+        let path = format!("{dir}/snapshot-1");
+        let _ = file_io.delete_dir(dir).await;
+
+        assert!(file_io
+            .try_to_write_atomic(&path, Bytes::from("first"))
+            .await
+            .unwrap());
+        assert!(!file_io
+            .try_to_write_atomic(&path, Bytes::from("second"))
+            .await
+            .unwrap());
+        assert_eq!(file_io.read_file_utf8(&path).await.unwrap(), "first");
+        // No temporary files are left behind.
+        assert_eq!(file_io.list_status(dir).await.unwrap().len(), 1);
+
+        file_io.delete_dir(dir).await.unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_try_to_write_atomic_fs_never_overwrites() {
+        let file_io = setup_fs_file_io();
+        let dir = "file:/tmp/test_try_to_write_atomic_fs_never_overwrites";
+        let path = format!("{dir}/snapshot-1");
+        let _ = file_io.delete_dir(dir).await;
+
+        // A file written by another process, bypassing any lock of this one.
+        fs::create_dir_all("/tmp/test_try_to_write_atomic_fs_never_overwrites").unwrap();
+        fs::write(
+            "/tmp/test_try_to_write_atomic_fs_never_overwrites/snapshot-1",
+            "other",
+        )
+        .unwrap();
+        assert!(!file_io
+            .try_to_write_atomic(&path, Bytes::from("first"))
+            .await
+            .unwrap());
+        assert_eq!(file_io.read_file_utf8(&path).await.unwrap(), "other");
+
+        // Exactly one of the concurrent writers succeeds.
+        let path = format!("{dir}/snapshot-2");
+        let writes = (0..8).map(|i| {
+            let (file_io, path) = (setup_fs_file_io(), path.clone());
+            tokio::spawn(async move {
+                file_io
+                    .try_to_write_atomic(&path, Bytes::from(i.to_string()))
+                    .await
+                    .unwrap()
+            })
+        });
+        let results = futures::future::join_all(writes).await;
+        assert_eq!(
+            results.into_iter().filter(|r| *r.as_ref().unwrap()).count(),
+            1
+        );
+        assert_eq!(file_io.list_status(dir).await.unwrap().len(), 2);
+
+        file_io.delete_dir(dir).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_memory_file_io_keeps_files() {
+        let file_io = FileIOBuilder::from_url("memory:/")
+            .unwrap()
+            .build()
+            .unwrap();
+        let path = "memory:/test_memory_file_io_keeps_files";
+        file_io
+            .new_output(path)
+            .unwrap()
+            .write(Bytes::from("hello world"))
+            .await
+            .unwrap();
+
+        assert!(file_io.exists(path).await.unwrap());
+        assert_eq!(file_io.read_file_utf8(path).await.unwrap(), "hello world");
+    }
+
     #[tokio::test]
     async fn test_rename_fs() {
         let file_io = setup_fs_file_io();
@@ -474,28 +1068,384 @@ mod file_action_test {
         )
         .await;
     }
+
+    #[test]
+    fn test_conditional_write_unsupported() {
+        let result = FileIOBuilder::from_url("file:/")
+            .unwrap()
+            .with_prop(FS_CONDITIONAL_WRITE, "true")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let props = HashMap::from([
+            (FS_RETRY_MAX_TIMES.to_string(), "5".to_string()),
+            (FS_RETRY_MIN_DELAY.to_string(), "100 ms".to_string()),
+            (FS_RETRY_MAX_DELAY.to_string(), "1 s".to_string()),
+        ]);
+        let backoff = Backoff::from_props(&props).unwrap();
+        assert_eq!(backoff.max_times, 5);
+        assert_eq!(backoff.delay(0), Duration::from_millis(100));
+        assert_eq!(backoff.delay(2), Duration::from_millis(400));
+        assert_eq!(backoff.delay(4), Duration::from_secs(1));
+        assert_eq!(backoff.delay(100), Duration::from_secs(1));
+
+        assert_eq!(
+            Backoff::from_props(&HashMap::new()).unwrap(),
+            Backoff::default()
+        );
+        let props = HashMap::from([(FS_RETRY_MIN_DELAY.to_string(), "soon".to_string())]);
+        assert!(Backoff::from_props(&props).is_err());
+    }
+
+    /// A fake s3 of objects, putting objects only if absent with `If-None-Match: *`,
+    /// throttling the first puts, and dropping the connections of the following puts after
+    /// storing their objects.
+    #[cfg(feature = "storage-s3")]
+    async fn serve_s3(
+        listener: tokio::net::TcpListener,
+        objects: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+        throttles: Arc<std::sync::atomic::AtomicUsize>,
+        drops: Arc<std::sync::atomic::AtomicUsize>,
+    ) {
+        use std::sync::atomic::Ordering;
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let objects = objects.clone();
+            let throttles = throttles.clone();
+            let drops = drops.clone();
+            tokio::spawn(async move {
+                let mut stream = BufReader::new(stream);
+                loop {
+                    let mut request_line = String::new();
+                    if stream.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                        return;
+                    }
+                    let mut parts = request_line.split(' ');
+                    let method = parts.next().unwrap().to_string();
+                    let key = parts.next().unwrap().split('?').next().unwrap().to_string();
+                    let (mut length, mut if_none_match) = (0, false);
+                    loop {
+                        let mut line = String::new();
+                        stream.read_line(&mut line).await.unwrap();
+                        let line = line.trim_end().to_ascii_lowercase();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some(v) = line.strip_prefix("content-length:") {
+                            length = v.trim().parse().unwrap();
+                        }
+                        if_none_match |= line == "if-none-match: *";
+                    }
+                    let mut body = vec![0; length];
+                    stream.read_exact(&mut body).await.unwrap();
+
+                    if method == "GET" || method == "HEAD" {
+                        let response = match objects.lock().await.get(&key) {
+                            Some(object) => {
+                                let mut response = format!(
+                                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n",
+                                    object.len()
+                                )
+                                .into_bytes();
+                                if method == "GET" {
+                                    response.extend_from_slice(object);
+                                }
+                                response
+                            }
+                            None => b"HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\n\r\n".to_vec(),
+                        };
+                        stream.write_all(&response).await.unwrap();
+                        continue;
+                    }
+
+                    let countdown = |counter: &std::sync::atomic::AtomicUsize| {
+                        counter
+                            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                            .is_ok()
+                    };
+                    let status = {
+                        let mut objects = objects.lock().await;
+                        if countdown(&throttles) {
+                            "503 Slow Down"
+                        } else if if_none_match && objects.contains_key(&key) {
+                            "412 Precondition Failed"
+                        } else {
+                            objects.insert(key, body);
+                            if countdown(&drops) {
+                                return;
+                            }
+                            "200 OK"
+                        }
+                    };
+                    let response = format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\n\r\n");
+                    stream.write_all(response.as_bytes()).await.unwrap();
+                }
+            });
+        }
+    }
+
+    #[cfg(feature = "storage-s3")]
+    async fn setup_s3_file_io(
+        throttles: usize,
+        drops: usize,
+        conditional_write: bool,
+    ) -> (Arc<dyn FileIO>, Arc<Mutex<HashMap<String, Vec<u8>>>>) {
+        use crate::io::{S3_ACCESS_KEY, S3_ENDPOINT, S3_REGION, S3_SECRET_KEY};
+        use std::sync::atomic::AtomicUsize;
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let objects = Arc::new(Mutex::new(HashMap::new()));
+        tokio::spawn(serve_s3(
+            listener,
+            objects.clone(),
+            Arc::new(AtomicUsize::new(throttles)),
+            Arc::new(AtomicUsize::new(drops)),
+        ));
+
+        let file_io = FileIOBuilder::from_url("s3://bucket/")
+            .unwrap()
+            .with_props([
+                (S3_ENDPOINT, endpoint.as_str()),
+                (S3_REGION, "us-east-1"),
+                (S3_ACCESS_KEY, "access"),
+                (S3_SECRET_KEY, "secret"),
+                (FS_CONDITIONAL_WRITE, &conditional_write.to_string()),
+                (FS_RETRY_MIN_DELAY, "10 ms"),
+            ])
+            .build()
+            .unwrap();
+        (file_io, objects)
+    }
+
+    #[cfg(feature = "storage-s3")]
+    #[tokio::test]
+    async fn test_try_to_write_atomic_conditional_s3() {
+        let (file_io, objects) = setup_s3_file_io(2, 0, true).await;
+        let path = "s3://bucket/snapshot/snapshot-1";
+        // The throttled puts are retried.
+        assert!(file_io
+            .try_to_write_atomic(path, Bytes::from("first"))
+            .await
+            .unwrap());
+        assert!(!file_io
+            .try_to_write_atomic(path, Bytes::from("second"))
+            .await
+            .unwrap());
+
+        // Objects are put directly, no temporary objects are written.
+        let objects = objects.lock().await;
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects["/bucket/snapshot/snapshot-1"], b"first");
+    }
+
+    #[cfg(feature = "storage-s3")]
+    #[tokio::test]
+    async fn test_try_to_write_atomic_s3_needs_conditional_write() {
+        let (file_io, _) = setup_s3_file_io(0, 0, false).await;
+        let result = file_io
+            .try_to_write_atomic("s3://bucket/snapshot/snapshot-1", Bytes::from("first"))
+            .await;
+        assert!(matches!(result, Err(Error::IoUnsupported { .. })));
+    }
+
+    #[cfg(feature = "storage-s3")]
+    #[tokio::test]
+    async fn test_try_to_write_locked_s3() {
+        let (file_io, objects) = setup_s3_file_io(0, 0, false).await;
+        let path = "s3://bucket/snapshot/snapshot-1";
+        assert!(file_io
+            .try_to_write_locked(path, Bytes::from("first"))
+            .await
+            .unwrap());
+        assert!(!file_io
+            .try_to_write_locked(path, Bytes::from("second"))
+            .await
+            .unwrap());
+
+        let objects = objects.lock().await;
+        assert_eq!(objects.len(), 1);
+        assert_eq!(objects["/bucket/snapshot/snapshot-1"], b"first");
+    }
+
+    #[cfg(feature = "storage-s3")]
+    #[tokio::test]
+    async fn test_try_to_write_atomic_conditional_s3_dropped_connection() {
+        let (file_io, objects) = setup_s3_file_io(0, 1, true).await;
+        let path = "s3://bucket/snapshot/snapshot-1";
+        // The first put lands but its response is lost, so the retry fails the precondition
+        // and the object is read back.
+        assert!(file_io
+            .try_to_write_atomic(path, Bytes::from("first"))
+            .await
+            .unwrap());
+        assert_eq!(
+            objects.lock().await["/bucket/snapshot/snapshot-1"],
+            b"first"
+        );
+
+        // Other contents are still rejected.
+        let (file_io, objects) = setup_s3_file_io(0, 1, true).await;
+        objects
+            .lock()
+            .await
+            .insert("/bucket/snapshot/snapshot-1".to_string(), b"other".to_vec());
+        assert!(!file_io
+            .try_to_write_atomic(path, Bytes::from("first"))
+            .await
+            .unwrap());
+    }
+
+    /// A fake webhdfs name node of files, creating files by the two steps of webhdfs and
+    /// renaming them only if the destination is absent like hdfs.
+    #[cfg(feature = "storage-webhdfs")]
+    async fn serve_webhdfs(
+        listener: tokio::net::TcpListener,
+        files: Arc<Mutex<HashMap<String, Vec<u8>>>>,
+    ) {
+        use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+
+        let address = listener.local_addr().unwrap();
+        loop {
+            let (stream, _) = listener.accept().await.unwrap();
+            let files = files.clone();
+            tokio::spawn(async move {
+                let mut stream = BufReader::new(stream);
+                loop {
+                    let mut request_line = String::new();
+                    if stream.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+                        return;
+                    }
+                    let mut parts = request_line.split(' ');
+                    let method = parts.next().unwrap().to_string();
+                    let uri = parts.next().unwrap().to_string();
+                    let (path, query) = uri.split_once('?').unwrap_or((&uri, ""));
+                    let mut length = 0;
+                    loop {
+                        let mut line = String::new();
+                        stream.read_line(&mut line).await.unwrap();
+                        let line = line.trim_end().to_ascii_lowercase();
+                        if line.is_empty() {
+                            break;
+                        }
+                        if let Some(v) = line.strip_prefix("content-length:") {
+                            length = v.trim().parse().unwrap();
+                        }
+                    }
+                    let mut body = vec![0; length];
+                    stream.read_exact(&mut body).await.unwrap();
+
+                    let op = query
+                        .split('&')
+                        .find_map(|kv| kv.strip_prefix("op="))
+                        .unwrap_or("");
+                    let file = path
+                        .strip_prefix("/webhdfs/v1")
+                        .or_else(|| path.strip_prefix("/data"))
+                        .unwrap()
+                        .to_string();
+                    let mut files = files.lock().await;
+                    let (status, body) = match (method.as_str(), op) {
+                        ("PUT", "CREATE") => (
+                            "200 OK",
+                            format!(r#"{{"Location":"http://{address}/data{file}"}}"#).into_bytes(),
+                        ),
+                        ("PUT", "RENAME") => {
+                            let destination = query
+                                .split('&')
+                                .find_map(|kv| kv.strip_prefix("destination="))
+                                .unwrap()
+                                .to_string();
+                            let renamed = !files.contains_key(&destination)
+                                && match files.remove(&file) {
+                                    Some(content) => files.insert(destination, content).is_none(),
+                                    None => false,
+                                };
+                            ("200 OK", format!(r#"{{"boolean":{renamed}}}"#).into_bytes())
+                        }
+                        ("PUT", _) => {
+                            files.insert(file, body);
+                            ("201 Created", vec![])
+                        }
+                        ("GET", "OPEN") => match files.get(&file) {
+                            Some(content) => ("200 OK", content.clone()),
+                            None => ("404 Not Found", vec![]),
+                        },
+                        ("DELETE", _) => {
+                            let deleted = files.remove(&file).is_some();
+                            ("200 OK", format!(r#"{{"boolean":{deleted}}}"#).into_bytes())
+                        }
+                        _ => ("400 Bad Request", vec![]),
+                    };
+                    let mut response = format!(
+                        "HTTP/1.1 {status}\r\ncontent-length: {}\r\n\r\n",
+                        body.len()
+                    )
+                    .into_bytes();
+                    response.extend_from_slice(&body);
+                    stream.write_all(&response).await.unwrap();
+                }
+            });
+        }
+    }
+
+    #[cfg(feature = "storage-webhdfs")]
+    #[tokio::test]
+    async fn test_try_to_write_atomic_webhdfs() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let root = format!("webhdfs://{}", listener.local_addr().unwrap());
+        let files = Arc::new(Mutex::new(HashMap::new()));
+        tokio::spawn(serve_webhdfs(listener, files.clone()));
+
+        let file_io = FileIOBuilder::from_url(&root).unwrap().build().unwrap();
+        let path = format!("{root}/snapshot/snapshot-1");
+        assert!(file_io
+            .try_to_write_atomic(&path, Bytes::from("first"))
+            .await
+            .unwrap());
+        // The rename of webhdfs never overwrites.
+        assert!(!file_io
+            .try_to_write_atomic(&path, Bytes::from("second"))
+            .await
+            .unwrap());
+
+        // The temporary files are deleted.
+        let files = files.lock().await;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files["/snapshot/snapshot-1"], b"first");
+    }
 }
 
 #[cfg(test)]
 mod input_output_test {
     use super::*;
+    use crate::io::memory_config_build;
     use bytes::Bytes;
 
-    fn setup_memory_file_io() -> FileIO {
-        let storage = Storage::Memory;
-        FileIO {
+    fn setup_memory_file_io() -> OpenDalFileIO {
+        let storage = Storage::Memory(memory_config_build().unwrap());
+        OpenDalFileIO {
             storage: Arc::new(storage),
+            conditional_write: false,
+            backoff: Backoff::default(),
         }
     }
 
-    fn setup_fs_file_io() -> FileIO {
+    fn setup_fs_file_io() -> OpenDalFileIO {
         let storage = Storage::LocalFs;
-        FileIO {
+        OpenDalFileIO {
             storage: Arc::new(storage),
+            conditional_write: false,
+            backoff: Backoff::default(),
         }
     }
 
-    async fn common_test_output_file_write_and_read(file_io: &FileIO, path: &str) {
+    async fn common_test_output_file_write_and_read(file_io: &dyn FileIO, path: &str) {
         let output = file_io.new_output(path).unwrap();
         let mut writer = output.writer().await.unwrap();
         writer.write(Bytes::from("hello world")).await.unwrap();
@@ -509,7 +1459,7 @@ mod input_output_test {
         file_io.delete_file(path).await.unwrap();
     }
 
-    async fn common_test_output_file_exists(file_io: &FileIO, path: &str) {
+    async fn common_test_output_file_exists(file_io: &dyn FileIO, path: &str) {
         let output = file_io.new_output(path).unwrap();
         let mut writer = output.writer().await.unwrap();
         writer.write(Bytes::from("hello world")).await.unwrap();
@@ -521,7 +1471,7 @@ mod input_output_test {
         file_io.delete_file(path).await.unwrap();
     }
 
-    async fn common_test_input_file_metadata(file_io: &FileIO, path: &str) {
+    async fn common_test_input_file_metadata(file_io: &dyn FileIO, path: &str) {
         let output = file_io.new_output(path).unwrap();
         let mut writer = output.writer().await.unwrap();
         writer.write(Bytes::from("hello world")).await.unwrap();
@@ -535,7 +1485,7 @@ mod input_output_test {
         file_io.delete_file(path).await.unwrap();
     }
 
-    async fn common_test_input_file_partial_read(file_io: &FileIO, path: &str) {
+    async fn common_test_input_file_partial_read(file_io: &dyn FileIO, path: &str) {
         let output = file_io.new_output(path).unwrap();
         let mut writer = output.writer().await.unwrap();
         writer.write(Bytes::from("hello world")).await.unwrap();
@@ -15,6 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+mod coalesce;
+pub use coalesce::*;
+
 mod file_io;
 pub use file_io::*;
 
@@ -30,3 +33,23 @@ use storage_fs::*;
 mod storage_memory;
 #[cfg(feature = "storage-memory")]
 use storage_memory::*;
+
+#[cfg(feature = "storage-s3")]
+mod storage_s3;
+#[cfg(feature = "storage-s3")]
+pub use storage_s3::*;
+
+#[cfg(feature = "storage-oss")]
+mod storage_oss;
+#[cfg(feature = "storage-oss")]
+pub use storage_oss::*;
+
+#[cfg(feature = "storage-gcs")]
+mod storage_gcs;
+#[cfg(feature = "storage-gcs")]
+pub use storage_gcs::*;
+
+#[cfg(feature = "storage-webhdfs")]
+mod storage_webhdfs;
+#[cfg(feature = "storage-webhdfs")]
+pub use storage_webhdfs::*;
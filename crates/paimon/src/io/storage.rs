@@ -16,6 +16,13 @@
 // under the License.
 
 use opendal::{Operator, Scheme};
+#[cfg(any(
+    feature = "storage-s3",
+    feature = "storage-oss",
+    feature = "storage-gcs",
+    feature = "storage-webhdfs"
+))]
+use std::sync::Arc;
 
 use crate::error;
 
@@ -25,21 +32,58 @@ use super::FileIOBuilder;
 #[derive(Debug)]
 pub enum Storage {
     #[cfg(feature = "storage-memory")]
-    Memory,
+    Memory(Operator),
     #[cfg(feature = "storage-fs")]
     LocalFs,
+    #[cfg(feature = "storage-s3")]
+    S3 {
+        scheme_str: String,
+        config: Arc<opendal::services::S3Config>,
+    },
+    #[cfg(feature = "storage-oss")]
+    Oss {
+        config: Arc<opendal::services::OssConfig>,
+    },
+    #[cfg(feature = "storage-gcs")]
+    Gcs {
+        scheme_str: String,
+        config: Arc<opendal::services::GcsConfig>,
+    },
+    #[cfg(feature = "storage-webhdfs")]
+    WebHdfs {
+        config: Arc<opendal::services::WebhdfsConfig>,
+    },
 }
 
 impl Storage {
     pub(crate) fn build(file_io_builder: FileIOBuilder) -> crate::Result<Self> {
-        let (scheme_str, _) = file_io_builder.into_parts();
+        #[allow(unused_variables)]
+        let (scheme_str, props) = file_io_builder.into_parts();
         let scheme = Self::parse_scheme(&scheme_str)?;
 
         match scheme {
             #[cfg(feature = "storage-memory")]
-            Scheme::Memory => Ok(Self::Memory),
+            Scheme::Memory => Ok(Self::Memory(super::memory_config_build()?)),
             #[cfg(feature = "storage-fs")]
             Scheme::Fs => Ok(Self::LocalFs),
+            #[cfg(feature = "storage-s3")]
+            Scheme::S3 => Ok(Self::S3 {
+                scheme_str,
+                config: Arc::new(super::s3_config_parse(&props)?),
+            }),
+            #[cfg(feature = "storage-oss")]
+            Scheme::Oss => Ok(Self::Oss {
+                config: Arc::new(super::oss_config_parse(&props)?),
+            }),
+            #[cfg(feature = "storage-gcs")]
+            Scheme::Gcs => Ok(Self::Gcs {
+                scheme_str,
+                config: Arc::new(super::gcs_config_parse(&props)?),
+            }),
+            #[cfg(feature = "storage-webhdfs")]
+            Scheme::Webhdfs => Ok(Self::WebHdfs {
+                config: Arc::new(super::webhdfs_config_parse(&props)?),
+            }),
             _ => Err(error::Error::IoUnsupported {
                 message: "Unsupported storage feature".to_string(),
             }),
@@ -49,13 +93,11 @@ impl Storage {
     pub(crate) fn create<'a>(&self, path: &'a str) -> crate::Result<(Operator, &'a str)> {
         match self {
             #[cfg(feature = "storage-memory")]
-            Storage::Memory => {
-                let op = super::memory_config_build()?;
-
+            Storage::Memory(op) => {
                 if let Some(stripped) = path.strip_prefix("memory:/") {
-                    Ok((op, stripped))
+                    Ok((op.clone(), stripped))
                 } else {
-                    Ok((op, &path[1..]))
+                    Ok((op.clone(), &path[1..]))
                 }
             }
             #[cfg(feature = "storage-fs")]
@@ -68,14 +110,100 @@ impl Storage {
                     Ok((op, &path[1..]))
                 }
             }
+            #[cfg(feature = "storage-s3")]
+            Storage::S3 { scheme_str, config } => {
+                let (bucket, relative_path) = split_authority(scheme_str, path)?;
+                Ok((super::s3_config_build(config, bucket)?, relative_path))
+            }
+            #[cfg(feature = "storage-oss")]
+            Storage::Oss { config } => {
+                let (bucket, relative_path) = split_authority("oss", path)?;
+                Ok((super::oss_config_build(config, bucket)?, relative_path))
+            }
+            #[cfg(feature = "storage-gcs")]
+            Storage::Gcs { scheme_str, config } => {
+                let (bucket, relative_path) = split_authority(scheme_str, path)?;
+                Ok((super::gcs_config_build(config, bucket)?, relative_path))
+            }
+            #[cfg(feature = "storage-webhdfs")]
+            Storage::WebHdfs { config } => {
+                let (authority, relative_path) = split_authority("webhdfs", path)?;
+                Ok((
+                    super::webhdfs_config_build(config, authority)?,
+                    relative_path,
+                ))
+            }
+        }
+    }
+
+    /// The header of the precondition of the object being absent, if conditional writes
+    /// are supported by the storage.
+    pub(crate) fn if_absent_header(&self) -> Option<(&'static str, &'static str)> {
+        #[cfg(feature = "storage-s3")]
+        if let Storage::S3 { .. } = self {
+            return Some(("If-None-Match", "*"));
+        }
+        #[cfg(feature = "storage-gcs")]
+        if let Storage::Gcs { .. } = self {
+            return Some(("x-goog-if-generation-match", "0"));
         }
+        None
     }
 
     fn parse_scheme(scheme: &str) -> crate::Result<Scheme> {
         match scheme {
             "memory" => Ok(Scheme::Memory),
             "file" | "" => Ok(Scheme::Fs),
+            "s3" | "s3a" | "s3n" => Ok(Scheme::S3),
+            "gs" | "gcs" => Ok(Scheme::Gcs),
             s => Ok(s.parse::<Scheme>()?),
         }
     }
 }
+
+/// Split path like `s3://bucket/path/to/file` into `bucket` and `path/to/file`.
+#[allow(dead_code)]
+pub(super) fn split_authority<'a>(
+    scheme: &str,
+    path: &'a str,
+) -> crate::Result<(&'a str, &'a str)> {
+    let invalid = || error::Error::ConfigInvalid {
+        message: format!("Invalid path '{}' for scheme '{}'", path, scheme),
+    };
+    let rest = path
+        .strip_prefix(scheme)
+        .and_then(|s| s.strip_prefix("://"))
+        .ok_or_else(invalid)?;
+    match rest.split_once('/') {
+        Some((authority, relative_path)) if !authority.is_empty() => Ok((authority, relative_path)),
+        None if !rest.is_empty() => Ok((rest, "")),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_authority() {
+        assert_eq!(
+            split_authority("s3", "s3://bucket/path/to/file").unwrap(),
+            ("bucket", "path/to/file")
+        );
+        assert_eq!(
+            split_authority("oss", "oss://bucket").unwrap(),
+            ("bucket", "")
+        );
+        assert!(split_authority("s3", "s3:///path").is_err());
+        assert!(split_authority("s3", "oss://bucket/path").is_err());
+    }
+
+    #[test]
+    fn test_parse_scheme() {
+        assert_eq!(Storage::parse_scheme("file").unwrap(), Scheme::Fs);
+        assert_eq!(Storage::parse_scheme("s3a").unwrap(), Scheme::S3);
+        assert_eq!(Storage::parse_scheme("gs").unwrap(), Scheme::Gcs);
+        assert_eq!(Storage::parse_scheme("oss").unwrap(), Scheme::Oss);
+    }
+}
@@ -0,0 +1,51 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use opendal::services::GcsConfig;
+use opendal::Operator;
+
+use crate::Result;
+
+/// Endpoint of the gcs service, defaults to `https://storage.googleapis.com`.
+pub const GCS_ENDPOINT: &str = "gcs.endpoint";
+/// Base64 encoded service account credential.
+pub const GCS_CREDENTIAL: &str = "gcs.credential";
+/// Path of the service account credential file.
+pub const GCS_CREDENTIAL_PATH: &str = "gcs.credential-path";
+/// Service account used to access the bucket.
+pub const GCS_SERVICE_ACCOUNT: &str = "gcs.service-account";
+
+/// Parse paimon gcs options into [`GcsConfig`].
+pub(crate) fn gcs_config_parse(props: &HashMap<String, String>) -> Result<GcsConfig> {
+    let mut cfg = GcsConfig::default();
+    cfg.endpoint = props.get(GCS_ENDPOINT).cloned();
+    cfg.credential = props.get(GCS_CREDENTIAL).cloned();
+    cfg.credential_path = props.get(GCS_CREDENTIAL_PATH).cloned();
+    cfg.service_account = props.get(GCS_SERVICE_ACCOUNT).cloned();
+
+    Ok(cfg)
+}
+
+/// Build new opendal operator for the given bucket.
+pub(crate) fn gcs_config_build(cfg: &GcsConfig, bucket: &str) -> Result<Operator> {
+    let mut cfg = cfg.clone();
+    cfg.bucket = bucket.to_string();
+
+    Ok(Operator::from_config(cfg)?.finish())
+}
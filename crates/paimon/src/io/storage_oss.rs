@@ -0,0 +1,48 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use opendal::services::OssConfig;
+use opendal::Operator;
+
+use crate::Result;
+
+/// Endpoint of the oss service, for example `https://oss-cn-hangzhou.aliyuncs.com`.
+pub const OSS_ENDPOINT: &str = "fs.oss.endpoint";
+/// Access key id used to sign requests.
+pub const OSS_ACCESS_KEY_ID: &str = "fs.oss.accessKeyId";
+/// Access key secret used to sign requests.
+pub const OSS_ACCESS_KEY_SECRET: &str = "fs.oss.accessKeySecret";
+
+/// Parse paimon oss options into [`OssConfig`].
+pub(crate) fn oss_config_parse(props: &HashMap<String, String>) -> Result<OssConfig> {
+    let mut cfg = OssConfig::default();
+    cfg.endpoint = props.get(OSS_ENDPOINT).cloned();
+    cfg.access_key_id = props.get(OSS_ACCESS_KEY_ID).cloned();
+    cfg.access_key_secret = props.get(OSS_ACCESS_KEY_SECRET).cloned();
+
+    Ok(cfg)
+}
+
+/// Build new opendal operator for the given bucket.
+pub(crate) fn oss_config_build(cfg: &OssConfig, bucket: &str) -> Result<Operator> {
+    let mut cfg = cfg.clone();
+    cfg.bucket = bucket.to_string();
+
+    Ok(Operator::from_config(cfg)?.finish())
+}
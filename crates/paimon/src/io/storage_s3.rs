@@ -0,0 +1,59 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use opendal::services::S3Config;
+use opendal::Operator;
+
+use crate::Result;
+
+/// Endpoint of the s3 compatible service, for example `https://s3.us-east-1.amazonaws.com`.
+pub const S3_ENDPOINT: &str = "s3.endpoint";
+/// Region of the s3 bucket.
+pub const S3_REGION: &str = "s3.region";
+/// Access key used to sign requests.
+pub const S3_ACCESS_KEY: &str = "s3.access-key";
+/// Secret key used to sign requests.
+pub const S3_SECRET_KEY: &str = "s3.secret-key";
+/// Session token of temporary credentials.
+pub const S3_SESSION_TOKEN: &str = "s3.session-token";
+/// Whether to use path style access instead of virtual host style.
+pub const S3_PATH_STYLE_ACCESS: &str = "s3.path.style.access";
+
+/// Parse paimon s3 options into [`S3Config`].
+pub(crate) fn s3_config_parse(props: &HashMap<String, String>) -> Result<S3Config> {
+    let mut cfg = S3Config::default();
+    cfg.endpoint = props.get(S3_ENDPOINT).cloned();
+    cfg.region = props.get(S3_REGION).cloned();
+    cfg.access_key_id = props.get(S3_ACCESS_KEY).cloned();
+    cfg.secret_access_key = props.get(S3_SECRET_KEY).cloned();
+    cfg.session_token = props.get(S3_SESSION_TOKEN).cloned();
+    cfg.enable_virtual_host_style = props
+        .get(S3_PATH_STYLE_ACCESS)
+        .is_some_and(|v| v.eq_ignore_ascii_case("false"));
+
+    Ok(cfg)
+}
+
+/// Build new opendal operator for the given bucket.
+pub(crate) fn s3_config_build(cfg: &S3Config, bucket: &str) -> Result<Operator> {
+    let mut cfg = cfg.clone();
+    cfg.bucket = bucket.to_string();
+
+    Ok(Operator::from_config(cfg)?.finish())
+}
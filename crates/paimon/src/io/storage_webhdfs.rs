@@ -0,0 +1,78 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::HashMap;
+
+use opendal::raw::percent_encode_path;
+use opendal::services::WebhdfsConfig;
+use opendal::{Buffer, Operator};
+
+use super::storage::split_authority;
+use crate::error::Error;
+use crate::Result;
+
+/// Delegation token used to access the webhdfs service.
+pub const WEBHDFS_DELEGATION: &str = "webhdfs.delegation";
+
+/// Parse paimon webhdfs options into [`WebhdfsConfig`].
+pub(crate) fn webhdfs_config_parse(props: &HashMap<String, String>) -> Result<WebhdfsConfig> {
+    let mut cfg = WebhdfsConfig::default();
+    cfg.delegation = props.get(WEBHDFS_DELEGATION).cloned();
+    cfg.root = Some("/".to_string());
+
+    Ok(cfg)
+}
+
+/// Build new opendal operator for the given name node address, e.g. `namenode:9870`.
+pub(crate) fn webhdfs_config_build(cfg: &WebhdfsConfig, authority: &str) -> Result<Operator> {
+    let mut cfg = cfg.clone();
+    cfg.endpoint = Some(format!("http://{}", authority));
+
+    Ok(Operator::from_config(cfg)?.finish())
+}
+
+/// Build the request renaming the file `src` to `dst`, both paths like
+/// `webhdfs://namenode:9870/path/to/file`.
+///
+/// Renames of hdfs never overwrite files, the response holds `{"boolean": false}` if `dst`
+/// exists, which makes them atomic writes of `dst` across writers.
+pub(crate) fn webhdfs_rename_request(
+    cfg: &WebhdfsConfig,
+    src: &str,
+    dst: &str,
+) -> Result<http::Request<Buffer>> {
+    let (authority, src) = split_authority("webhdfs", src)?;
+    let (_, dst) = split_authority("webhdfs", dst)?;
+    let mut url = format!(
+        "http://{}/webhdfs/v1/{}?op=RENAME&destination={}",
+        authority,
+        percent_encode_path(src),
+        percent_encode_path(&format!("/{}", dst))
+    );
+    if let Some(delegation) = &cfg.delegation {
+        url.push_str(&format!("&delegation={}", delegation));
+    }
+    http::Request::put(url)
+        .body(Buffer::new())
+        .map_err(|e| Error::IoUnexpected {
+            message: format!("Failed to build the rename of '{}'", src),
+            source: Box::new(opendal::Error::new(
+                opendal::ErrorKind::Unexpected,
+                e.to_string(),
+            )),
+        })
+}
@@ -17,8 +17,24 @@
 
 mod error;
 pub use error::Error;
+pub use error::ErrorCategory;
 pub use error::Result;
 
+pub mod catalog;
+pub mod deletion_vectors;
 pub mod file_index;
+pub mod format;
+pub mod iceberg;
+pub mod index;
 pub mod io;
+pub mod manifest;
+pub mod memory;
+pub mod mergetree;
+pub mod metrics;
+pub mod predicate;
 pub mod spec;
+pub mod table;
+pub mod utils;
+
+#[cfg(test)]
+mod test_utils;
@@ -0,0 +1,136 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::io::FileIO;
+use crate::spec::objects_file::{from_avro_bytes, to_avro_bytes};
+use crate::spec::IndexManifestEntry;
+use apache_avro::Schema;
+use bytes::Bytes;
+use std::sync::Arc;
+
+/// The latest version of [`IndexManifestEntry`] layout written by paimon.
+pub const INDEX_MANIFEST_ENTRY_VERSION: i32 = 1;
+
+/// Avro writer schema of index manifest file, which is the same as the one produced by paimon-java.
+const INDEX_MANIFEST_ENTRY_SCHEMA: &str = r#"["null", {
+    "type": "record",
+    "name": "record",
+    "namespace": "org.apache.paimon.avro.generated",
+    "fields": [
+        {"name": "_VERSION", "type": "int"},
+        {"name": "_KIND", "type": "int"},
+        {"name": "_PARTITION", "type": "bytes"},
+        {"name": "_BUCKET", "type": "int"},
+        {"name": "_INDEX_TYPE", "type": "string"},
+        {"name": "_FILE_NAME", "type": "string"},
+        {"name": "_FILE_SIZE", "type": "long"},
+        {"name": "_ROW_COUNT", "type": "long"},
+        {"name": "_DELETIONS_VECTORS_RANGES", "type": ["null", {"type": "array", "items": ["null", {
+            "type": "record",
+            "name": "record__DELETIONS_VECTORS_RANGES",
+            "fields": [
+                {"name": "f0", "type": "string"},
+                {"name": "f1", "type": "int"},
+                {"name": "f2", "type": "int"}
+            ]
+        }]}], "default": null}
+    ]
+}]"#;
+
+/// This file includes several [`IndexManifestEntry`]s, representing all the index files of the
+/// table at the corresponding snapshot.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/manifest/IndexManifestFile.java>
+#[derive(Clone, Debug)]
+pub struct IndexManifestFile {
+    file_io: Arc<dyn FileIO>,
+}
+
+impl IndexManifestFile {
+    pub fn new(file_io: Arc<dyn FileIO>) -> Self {
+        Self { file_io }
+    }
+
+    /// Read all [`IndexManifestEntry`]s from the index manifest file at the given path.
+    pub async fn read(&self, path: &str) -> crate::Result<Vec<IndexManifestEntry>> {
+        let bytes = self.file_io.new_input(path)?.read().await?;
+        from_avro_bytes::<IndexManifestEntry>(&bytes)
+    }
+
+    /// Write the given [`IndexManifestEntry`]s into a new index manifest file at the given path.
+    pub async fn write(&self, path: &str, entries: &[IndexManifestEntry]) -> crate::Result<()> {
+        let schema = Schema::parse_str(INDEX_MANIFEST_ENTRY_SCHEMA)?;
+        let bytes = to_avro_bytes(&schema, entries)?;
+        self.file_io
+            .new_output(path)?
+            .write(Bytes::from(bytes))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{FileKind, IndexFileMeta};
+    use indexmap::IndexMap;
+
+    #[tokio::test]
+    async fn test_read_index_manifest_file() {
+        let workdir =
+            std::env::current_dir().unwrap_or_else(|err| panic!("current_dir must exist: {err}"));
+        let path = format!(
+            "file:{}",
+            workdir
+                .join(
+                    "tests/fixtures/manifest/index-manifest-85cc6729-f5af-431a-a1c3-ef45319328fb-0"
+                )
+                .display()
+        );
+        let file_io = FileIOBuilder::from_url(&path).unwrap().build().unwrap();
+
+        let entries = IndexManifestFile::new(file_io).read(&path).await.unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].index_file.index_type, "HASH");
+        assert_eq!(entries[1].index_file.index_type, "DELETION_VECTORS");
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_index_manifest_file() {
+        let path = "file:/tmp/test_write_and_read_index_manifest_file";
+        let file_io = FileIOBuilder::from_url(path).unwrap().build().unwrap();
+        let index_manifest_file = IndexManifestFile::new(file_io.clone());
+        let entries = vec![IndexManifestEntry {
+            kind: FileKind::Add,
+            partition: vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            bucket: 0,
+            index_file: IndexFileMeta {
+                index_type: "DELETION_VECTORS".into(),
+                file_name: "index-0".into(),
+                file_size: 33,
+                row_count: 1,
+                deletion_vectors_ranges: Some(IndexMap::from([("f1.parquet".into(), (1, 24))])),
+            },
+            version: 1,
+        }];
+
+        index_manifest_file.write(path, &entries).await.unwrap();
+        assert_eq!(index_manifest_file.read(path).await.unwrap(), entries);
+
+        file_io.delete_file(path).await.unwrap();
+    }
+}
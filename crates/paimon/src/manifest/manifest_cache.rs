@@ -0,0 +1,174 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::io::{FileIO, FileIOBuilder};
+use crate::spec::ManifestEntry;
+use bytes::Bytes;
+use indexmap::IndexMap;
+use std::sync::{Arc, Mutex};
+
+/// Cache of the decoded entries of manifest files by their file names, manifest files are
+/// immutable and named uniquely so the cached entries never go stale.
+///
+/// The least recently used manifests are evicted when the number of cached entries exceeds
+/// the max. The content of manifest files can be also kept in a local directory, to be read
+/// instead of the remote files when the entries are evicted or after restarts.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/utils/ObjectsCache.java>
+#[derive(Debug)]
+pub struct ManifestCache {
+    max_entries: usize,
+    local: Option<(Arc<dyn FileIO>, String)>,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Debug, Default)]
+struct CacheState {
+    /// The cached manifests from the least to the most recently used.
+    manifests: IndexMap<String, Arc<Vec<ManifestEntry>>>,
+    entries: usize,
+}
+
+impl ManifestCache {
+    /// Create a cache of at most the given number of manifest entries in memory.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            local: None,
+            state: Default::default(),
+        }
+    }
+
+    /// Keep the content of the manifest files in the local directory.
+    pub fn with_local_dir(mut self, dir: &str) -> crate::Result<Self> {
+        let dir = dir.trim_end_matches('/');
+        let file_io = FileIOBuilder::from_url(dir)?.build()?;
+        self.local = Some((file_io, dir.to_string()));
+        Ok(self)
+    }
+
+    /// Get the cached entries of the manifest file.
+    pub fn get(&self, file_name: &str) -> Option<Arc<Vec<ManifestEntry>>> {
+        let mut state = self.state.lock().unwrap();
+        let index = state.manifests.get_index_of(file_name)?;
+        let last = state.manifests.len() - 1;
+        state.manifests.move_index(index, last);
+        state
+            .manifests
+            .get_index(last)
+            .map(|(_, entries)| entries.clone())
+    }
+
+    /// Cache the entries of the manifest file, manifests of more entries than the max are not
+    /// cached.
+    pub fn put(&self, file_name: &str, entries: Arc<Vec<ManifestEntry>>) {
+        if entries.len() > self.max_entries {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        state.entries += entries.len();
+        if let Some(replaced) = state.manifests.insert(file_name.to_string(), entries) {
+            state.entries -= replaced.len();
+        }
+        while state.entries > self.max_entries {
+            let Some((_, evicted)) = state.manifests.shift_remove_index(0) else {
+                break;
+            };
+            state.entries -= evicted.len();
+        }
+    }
+
+    /// Number of the manifest entries cached in memory.
+    pub fn cached_entries(&self) -> usize {
+        self.state.lock().unwrap().entries
+    }
+
+    /// Read the content of the manifest file kept in the local directory.
+    pub(crate) async fn read_local(&self, file_name: &str) -> crate::Result<Option<Bytes>> {
+        let Some((file_io, dir)) = &self.local else {
+            return Ok(None);
+        };
+        let input = file_io.new_input(&format!("{dir}/{file_name}"))?;
+        if !input.exists().await? {
+            return Ok(None);
+        }
+        Ok(Some(input.read().await?))
+    }
+
+    /// Keep the content of the manifest file in the local directory.
+    pub(crate) async fn write_local(&self, file_name: &str, bytes: Bytes) -> crate::Result<()> {
+        let Some((file_io, dir)) = &self.local else {
+            return Ok(());
+        };
+        // concurrent readers of the same manifest write the same content
+        file_io
+            .try_to_write_atomic(&format!("{dir}/{file_name}"), bytes)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::manifest::ManifestFile;
+
+    #[tokio::test]
+    async fn test_read_manifest_file_with_cache() {
+        let workdir = std::env::current_dir().unwrap();
+        let fixture = format!(
+            "file:{}",
+            workdir
+                .join("tests/fixtures/manifest/manifest-8ded1f09-fcda-489e-9167-582ac0f9f846-0")
+                .display()
+        );
+        let location = "file:/tmp/test_read_manifest_file_with_cache";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let entries = ManifestFile::new(file_io.clone())
+            .read(&fixture)
+            .await
+            .unwrap();
+        let path = format!("{location}/manifest/manifest-1");
+        ManifestFile::new(file_io.clone())
+            .write(&path, &entries)
+            .await
+            .unwrap();
+
+        let local_dir = format!("{location}/cache");
+        let cache = Arc::new(ManifestCache::new(3).with_local_dir(&local_dir).unwrap());
+        let manifest_file = ManifestFile::new(file_io.clone()).with_cache(Some(cache.clone()));
+        assert_eq!(manifest_file.read(&path).await.unwrap(), entries);
+        assert_eq!(cache.cached_entries(), 2);
+
+        // the entries are read from the cache, then from the local directory
+        file_io.delete_file(&path).await.unwrap();
+        assert_eq!(manifest_file.read(&path).await.unwrap(), entries);
+        let cache = Arc::new(ManifestCache::new(3).with_local_dir(&local_dir).unwrap());
+        let manifest_file = ManifestFile::new(file_io.clone()).with_cache(Some(cache.clone()));
+        assert_eq!(manifest_file.read(&path).await.unwrap(), entries);
+
+        // the least recently used manifest is evicted
+        cache.put("manifest-2", Arc::new(entries[..1].to_vec()));
+        assert!(cache.get("manifest-1").is_some());
+        cache.put("manifest-3", Arc::new(entries[..1].to_vec()));
+        assert!(cache.get("manifest-2").is_none());
+        assert_eq!(cache.cached_entries(), 3);
+        cache.put("manifest-4", Arc::new([entries.clone(), entries].concat()));
+        assert!(cache.get("manifest-4").is_none());
+    }
+}
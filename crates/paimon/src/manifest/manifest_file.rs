@@ -0,0 +1,234 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::io::FileIO;
+use crate::manifest::ManifestCache;
+use crate::spec::objects_file::{from_avro_bytes, to_avro_bytes};
+use crate::spec::ManifestEntry;
+use crate::Error;
+use apache_avro::Schema;
+use bytes::Bytes;
+use std::sync::{Arc, OnceLock};
+
+/// The latest version of [`ManifestEntry`] layout written by paimon.
+pub const MANIFEST_ENTRY_VERSION: i32 = 2;
+
+/// Avro writer schema of manifest file, which is the same as the one produced by paimon-java.
+const MANIFEST_ENTRY_SCHEMA: &str = r#"["null", {
+    "type": "record",
+    "name": "record",
+    "namespace": "org.apache.paimon.avro.generated",
+    "fields": [
+        {"name": "_VERSION", "type": "int"},
+        {"name": "_KIND", "type": "int"},
+        {"name": "_PARTITION", "type": "bytes"},
+        {"name": "_BUCKET", "type": "int"},
+        {"name": "_TOTAL_BUCKETS", "type": "int"},
+        {"name": "_FILE", "type": ["null", {
+            "type": "record",
+            "name": "record__FILE",
+            "fields": [
+                {"name": "_FILE_NAME", "type": "string"},
+                {"name": "_FILE_SIZE", "type": "long"},
+                {"name": "_ROW_COUNT", "type": "long"},
+                {"name": "_MIN_KEY", "type": "bytes"},
+                {"name": "_MAX_KEY", "type": "bytes"},
+                {"name": "_KEY_STATS", "type": ["null", {
+                    "type": "record",
+                    "name": "record__FILE__KEY_STATS",
+                    "fields": [
+                        {"name": "_MIN_VALUES", "type": "bytes"},
+                        {"name": "_MAX_VALUES", "type": "bytes"},
+                        {"name": "_NULL_COUNTS", "type": ["null", {"type": "array", "items": ["null", "long"]}], "default": null}
+                    ]
+                }], "default": null},
+                {"name": "_VALUE_STATS", "type": ["null", {
+                    "type": "record",
+                    "name": "record__FILE__VALUE_STATS",
+                    "fields": [
+                        {"name": "_MIN_VALUES", "type": "bytes"},
+                        {"name": "_MAX_VALUES", "type": "bytes"},
+                        {"name": "_NULL_COUNTS", "type": ["null", {"type": "array", "items": ["null", "long"]}], "default": null}
+                    ]
+                }], "default": null},
+                {"name": "_MIN_SEQUENCE_NUMBER", "type": "long"},
+                {"name": "_MAX_SEQUENCE_NUMBER", "type": "long"},
+                {"name": "_SCHEMA_ID", "type": "long"},
+                {"name": "_LEVEL", "type": "int"},
+                {"name": "_EXTRA_FILES", "type": {"type": "array", "items": "string"}},
+                {"name": "_CREATION_TIME", "type": ["null", {"type": "long", "logicalType": "timestamp-millis"}], "default": null},
+                {"name": "_DELETE_ROW_COUNT", "type": ["null", "long"], "default": null},
+                {"name": "_EMBEDDED_FILE_INDEX", "type": ["null", "bytes"], "default": null},
+                {"name": "_FILE_SOURCE", "type": ["null", "int"], "default": null},
+                {"name": "_VALUE_STATS_COLS", "type": ["null", {"type": "array", "items": "string"}], "default": null}
+            ]
+        }], "default": null}
+    ]
+}]"#;
+
+/// The parsed avro writer schema of manifest file.
+pub(crate) fn manifest_entry_schema() -> &'static Schema {
+    static SCHEMA: OnceLock<Schema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        Schema::parse_str(MANIFEST_ENTRY_SCHEMA).expect("manifest entry schema must be valid")
+    })
+}
+
+/// This file includes several [`ManifestEntry`]s, representing the additional changes since last snapshot.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/manifest/ManifestFile.java>
+#[derive(Clone, Debug)]
+pub struct ManifestFile {
+    file_io: Arc<dyn FileIO>,
+    cache: Option<Arc<ManifestCache>>,
+}
+
+impl ManifestFile {
+    pub fn new(file_io: Arc<dyn FileIO>) -> Self {
+        Self {
+            file_io,
+            cache: None,
+        }
+    }
+
+    /// Read the entries of manifest files through the cache.
+    pub fn with_cache(mut self, cache: Option<Arc<ManifestCache>>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Read all [`ManifestEntry`]s from the manifest file at the given path.
+    ///
+    /// Entries written in the v1 layout don't carry the fields introduced by v2
+    /// (such as `_DELETE_ROW_COUNT` and `_EMBEDDED_FILE_INDEX`), those fields
+    /// will be left as `None`.
+    pub async fn read(&self, path: &str) -> crate::Result<Vec<ManifestEntry>> {
+        let Some(cache) = &self.cache else {
+            let bytes = self.file_io.new_input(path)?.read().await?;
+            return Self::decode(path, &bytes);
+        };
+        let file_name = path.rsplit('/').next().unwrap_or(path);
+        if let Some(entries) = cache.get(file_name) {
+            return Ok(entries.to_vec());
+        }
+        // the local content not decoded is read again from the manifest file
+        let local = match cache.read_local(file_name).await? {
+            Some(bytes) => Self::decode(path, &bytes).ok(),
+            None => None,
+        };
+        let entries = match local {
+            Some(entries) => entries,
+            None => {
+                let bytes = self.file_io.new_input(path)?.read().await?;
+                let entries = Self::decode(path, &bytes)?;
+                cache.write_local(file_name, bytes).await?;
+                entries
+            }
+        };
+        cache.put(file_name, Arc::new(entries.clone()));
+        Ok(entries)
+    }
+
+    /// Decode the entries of the content of the manifest file at the given path.
+    fn decode(path: &str, bytes: &[u8]) -> crate::Result<Vec<ManifestEntry>> {
+        let entries = from_avro_bytes::<ManifestEntry>(bytes)?;
+
+        for entry in &entries {
+            if entry.version() < 1 || entry.version() > MANIFEST_ENTRY_VERSION {
+                return Err(Error::VersionUnsupported {
+                    message: format!("manifest entry in '{}'", path),
+                    version: entry.version(),
+                });
+            }
+        }
+
+        Ok(entries)
+    }
+
+    /// Write the given [`ManifestEntry`]s into a new manifest file at the given path.
+    pub async fn write(&self, path: &str, entries: &[ManifestEntry]) -> crate::Result<()> {
+        self.write_bytes(path, Self::encode(entries)?).await
+    }
+
+    /// Encode the given [`ManifestEntry`]s into the content of a manifest file.
+    pub(crate) fn encode(entries: &[ManifestEntry]) -> crate::Result<Bytes> {
+        Ok(Bytes::from(to_avro_bytes(
+            manifest_entry_schema(),
+            entries,
+        )?))
+    }
+
+    /// Write the content encoded by [`Self::encode`] into a new manifest file at the given path.
+    pub(crate) async fn write_bytes(&self, path: &str, bytes: Bytes) -> crate::Result<()> {
+        self.file_io.new_output(path)?.write(bytes).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::FileKind;
+
+    fn fixture_path(name: &str) -> String {
+        let workdir =
+            std::env::current_dir().unwrap_or_else(|err| panic!("current_dir must exist: {err}"));
+        format!(
+            "file:{}",
+            workdir.join("tests/fixtures/manifest").join(name).display()
+        )
+    }
+
+    #[tokio::test]
+    async fn test_read_manifest_file() {
+        let path = fixture_path("manifest-8ded1f09-fcda-489e-9167-582ac0f9f846-0");
+        let file_io = FileIOBuilder::from_url(&path).unwrap().build().unwrap();
+
+        let entries = ManifestFile::new(file_io).read(&path).await.unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind(), &FileKind::Delete);
+        assert_eq!(entries[0].file().file_name, "f1.parquet");
+        assert_eq!(entries[1].kind(), &FileKind::Add);
+        assert_eq!(entries[1].file().file_name, "f2.parquet");
+        assert!(entries
+            .iter()
+            .all(|entry| entry.version() == MANIFEST_ENTRY_VERSION));
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_manifest_file() {
+        let fixture = fixture_path("manifest-8ded1f09-fcda-489e-9167-582ac0f9f846-0");
+        let file_io = FileIOBuilder::from_url(&fixture).unwrap().build().unwrap();
+        let manifest_file = ManifestFile::new(file_io.clone());
+        let entries = manifest_file.read(&fixture).await.unwrap();
+
+        let path = "file:/tmp/test_write_and_read_manifest_file";
+        manifest_file.write(path, &entries).await.unwrap();
+        assert_eq!(manifest_file.read(path).await.unwrap(), entries);
+
+        file_io.delete_file(path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_read_missing_manifest_file() {
+        let path = fixture_path("manifest-not-exist");
+        let file_io = FileIOBuilder::from_url(&path).unwrap().build().unwrap();
+
+        assert!(ManifestFile::new(file_io).read(&path).await.is_err());
+    }
+}
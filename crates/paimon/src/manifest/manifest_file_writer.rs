@@ -0,0 +1,331 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::manifest::manifest_file::manifest_entry_schema;
+use crate::manifest::ManifestFile;
+use crate::spec::{
+    BinaryRow, BinaryTableStatsAggregator, DataField, FileKind, ManifestEntry, ManifestFileMeta,
+    RowType,
+};
+use crate::Error;
+use apache_avro::{to_value, Codec, Writer};
+use bytes::Bytes;
+use futures::{pin_mut, Stream, TryStreamExt};
+
+/// Max size of the avro blocks buffered before compressed into the manifest file.
+const MAX_BLOCK_SIZE: usize = 16000;
+
+/// A writer of [`ManifestEntry`]s into manifest files, rolling to a new file once the current
+/// one reaches the target file size.
+///
+/// The [`ManifestFileMeta`]s of the files written, with the stats of their partitions, buckets
+/// and levels, are returned on [`ManifestFileWriter::close`].
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/manifest/ManifestFile.java#L140>
+pub struct ManifestFileWriter {
+    manifest_file: ManifestFile,
+    manifest_dir: String,
+    partition_type: RowType,
+    schema_id: i64,
+    target_file_size: usize,
+    uuid: String,
+    file_count: usize,
+    current: Option<RollingFile>,
+    written: Vec<ManifestFileMeta>,
+}
+
+impl ManifestFileWriter {
+    /// Create a writer of the manifest files in the given directory of a table of the given
+    /// partition type and schema id.
+    pub fn new(
+        manifest_file: ManifestFile,
+        manifest_dir: impl Into<String>,
+        partition_type: RowType,
+        schema_id: i64,
+        target_file_size: i64,
+    ) -> Self {
+        Self {
+            manifest_file,
+            manifest_dir: manifest_dir.into().trim_end_matches('/').to_string(),
+            partition_type,
+            schema_id,
+            target_file_size: target_file_size.max(1) as usize,
+            uuid: uuid::Uuid::new_v4().to_string(),
+            file_count: 0,
+            current: None,
+            written: vec![],
+        }
+    }
+
+    /// Write the entry into the current manifest file, rolling to a new file if the target
+    /// file size is reached.
+    pub async fn write(&mut self, entry: &ManifestEntry) -> crate::Result<()> {
+        let fields = self.partition_type.fields();
+        let target_file_size = self.target_file_size;
+        let file = self
+            .current
+            .get_or_insert_with(|| RollingFile::new(fields.to_vec(), target_file_size));
+        file.write(entry)?;
+        if file.written >= self.target_file_size {
+            self.roll().await?;
+        }
+        Ok(())
+    }
+
+    /// Write all the entries of the stream, failing on the first error of the stream.
+    pub async fn write_stream(
+        &mut self,
+        entries: impl Stream<Item = crate::Result<ManifestEntry>>,
+    ) -> crate::Result<()> {
+        pin_mut!(entries);
+        while let Some(entry) = entries.try_next().await? {
+            self.write(&entry).await?;
+        }
+        Ok(())
+    }
+
+    /// Close the current manifest file and return the metas of all files written in order.
+    pub async fn close(mut self) -> crate::Result<Vec<ManifestFileMeta>> {
+        self.roll().await?;
+        Ok(self.written)
+    }
+
+    /// Close the current manifest file if any, the next entry is written into a new file.
+    async fn roll(&mut self) -> crate::Result<()> {
+        let Some(file) = self.current.take() else {
+            return Ok(());
+        };
+        let file_name = format!("manifest-{}-{}", self.uuid, self.file_count);
+        self.file_count += 1;
+        let bytes = file.writer.into_inner().map_err(Error::from)?;
+        let file_size = bytes.len() as i64;
+        self.manifest_file
+            .write_bytes(
+                &format!("{}/{file_name}", self.manifest_dir),
+                Bytes::from(bytes),
+            )
+            .await?;
+
+        let partition_stats = file.partition_stats.stats()?;
+        self.written.push(
+            ManifestFileMeta::new(
+                file_name,
+                file_size,
+                file.num_added_files,
+                file.num_deleted_files,
+                partition_stats,
+                self.schema_id,
+            )
+            .with_bucket_range(file.min_bucket, file.max_bucket)
+            .with_level_range(file.min_level, file.max_level),
+        );
+        Ok(())
+    }
+}
+
+/// The manifest file being written, with the stats of the entries written so far.
+struct RollingFile {
+    writer: Writer<'static, Vec<u8>>,
+    /// Number of the bytes flushed into the file, the entries buffered are not included.
+    written: usize,
+    num_added_files: i64,
+    num_deleted_files: i64,
+    partition_stats: BinaryTableStatsAggregator,
+    min_bucket: i32,
+    max_bucket: i32,
+    min_level: i32,
+    max_level: i32,
+}
+
+impl RollingFile {
+    fn new(partition_fields: Vec<DataField>, target_file_size: usize) -> Self {
+        let writer = Writer::builder()
+            .schema(manifest_entry_schema())
+            .writer(Vec::new())
+            .codec(Codec::Snappy)
+            .block_size(target_file_size.min(MAX_BLOCK_SIZE))
+            .build();
+        Self {
+            writer,
+            written: 0,
+            num_added_files: 0,
+            num_deleted_files: 0,
+            partition_stats: BinaryTableStatsAggregator::new(partition_fields),
+            min_bucket: i32::MAX,
+            max_bucket: i32::MIN,
+            min_level: i32::MAX,
+            max_level: i32::MIN,
+        }
+    }
+
+    fn write(&mut self, entry: &ManifestEntry) -> crate::Result<()> {
+        let value = to_value(entry)?.resolve(manifest_entry_schema())?;
+        self.written += self.writer.append(value)?;
+
+        match entry.kind() {
+            FileKind::Add => self.num_added_files += 1,
+            FileKind::Delete => self.num_deleted_files += 1,
+        }
+        self.min_bucket = self.min_bucket.min(entry.bucket());
+        self.max_bucket = self.max_bucket.max(entry.bucket());
+        self.min_level = self.min_level.min(entry.level());
+        self.max_level = self.max_level.max(entry.level());
+
+        self.partition_stats
+            .update(&BinaryRow::from_serialized_bytes(entry.partition())?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{BinaryRowWriter, DataFileMeta, DataType, Datum, IntType};
+    use crate::test_utils;
+
+    fn entry(kind: FileKind, dt: Option<i32>, bucket: i32, level: i32) -> ManifestEntry {
+        let mut writer = BinaryRowWriter::new(1);
+        writer
+            .write_datum(
+                0,
+                dt.map(Datum::Int).as_ref(),
+                &DataType::Int(IntType::new()),
+            )
+            .unwrap();
+        let file = DataFileMeta {
+            file_size: 1024,
+            row_count: 10,
+            max_sequence_number: 9,
+            level,
+            delete_row_count: Some(0),
+            ..test_utils::data_file(&format!("data-{}.parquet", uuid::Uuid::new_v4()))
+        };
+        ManifestEntry::new(
+            kind,
+            writer.build().to_serialized_bytes(),
+            bucket,
+            4,
+            file,
+            2,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_manifest_file_writer() {
+        let dir = "file:/tmp/test_manifest_file_writer";
+        let file_io = FileIOBuilder::from_url(dir).unwrap().build().unwrap();
+        let manifest_file = ManifestFile::new(file_io.clone());
+        let partition_type = RowType::new(vec![DataField::new(
+            0,
+            "dt".to_string(),
+            DataType::Int(IntType::new()),
+        )]);
+        let entries: Vec<_> = (0..200)
+            .map(|i| {
+                let kind = if i % 4 == 0 {
+                    FileKind::Delete
+                } else {
+                    FileKind::Add
+                };
+                entry(kind, (i % 10 != 0).then_some(i), i % 4, i % 3)
+            })
+            .collect();
+
+        let mut writer =
+            ManifestFileWriter::new(manifest_file.clone(), dir, partition_type.clone(), 3, 1);
+        writer
+            .write_stream(futures::stream::iter(entries.iter().cloned().map(Ok)))
+            .await
+            .unwrap();
+        // every entry rolls into a new file for the target size of one byte
+        assert_eq!(writer.close().await.unwrap().len(), 200);
+
+        let mut writer =
+            ManifestFileWriter::new(manifest_file.clone(), dir, partition_type, 3, 4 * 1024);
+        for entry in &entries {
+            writer.write(entry).await.unwrap();
+        }
+        let metas = writer.close().await.unwrap();
+        assert!(metas.len() > 1);
+
+        let mut read = Vec::new();
+        for meta in &metas {
+            let path = format!("{dir}/{}", meta.file_name());
+            let meta_entries = manifest_file.read(&path).await.unwrap();
+            assert_eq!(
+                meta.file_size(),
+                file_io.get_status(&path).await.unwrap().size as i64
+            );
+            assert_eq!(meta.schema_id(), 3);
+            assert_eq!(
+                meta.num_added_files() + meta.num_deleted_files(),
+                meta_entries.len() as i64
+            );
+            assert_eq!(
+                meta.num_deleted_files(),
+                meta_entries
+                    .iter()
+                    .filter(|e| e.kind() == &FileKind::Delete)
+                    .count() as i64
+            );
+            let buckets = meta_entries.iter().map(ManifestEntry::bucket);
+            assert_eq!(meta.min_bucket(), buckets.clone().min());
+            assert_eq!(meta.max_bucket(), buckets.max());
+            let levels = meta_entries.iter().map(ManifestEntry::level);
+            assert_eq!(meta.min_level(), levels.clone().min());
+            assert_eq!(meta.max_level(), levels.max());
+
+            let dts: Vec<_> = meta_entries
+                .iter()
+                .filter_map(|e| {
+                    BinaryRow::from_serialized_bytes(e.partition())
+                        .unwrap()
+                        .get_datum(0, &DataType::Int(IntType::new()))
+                        .unwrap()
+                })
+                .map(|dt| match dt {
+                    Datum::Int(dt) => dt,
+                    dt => panic!("unexpected partition {dt:?}"),
+                })
+                .collect();
+            let stats = meta.partition_stats();
+            let datum = |bytes: &[u8]| {
+                BinaryRow::from_serialized_bytes(bytes)
+                    .unwrap()
+                    .get_datum(0, &DataType::Int(IntType::new()))
+                    .unwrap()
+            };
+            assert_eq!(
+                datum(stats.min_values()),
+                dts.iter().min().copied().map(Datum::Int)
+            );
+            assert_eq!(
+                datum(stats.max_values()),
+                dts.iter().max().copied().map(Datum::Int)
+            );
+            assert_eq!(
+                stats.null_counts(),
+                &[Some((meta_entries.len() - dts.len()) as i64)]
+            );
+            read.extend(meta_entries);
+        }
+        assert_eq!(read, entries);
+
+        file_io.delete_dir(dir).await.unwrap();
+    }
+}
@@ -0,0 +1,156 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::io::FileIO;
+use crate::spec::objects_file::{from_avro_bytes, to_avro_bytes};
+use crate::spec::ManifestFileMeta;
+use apache_avro::Schema;
+use bytes::Bytes;
+use std::sync::Arc;
+
+/// Avro writer schema of manifest list, which is the same as the one produced by paimon-java.
+const MANIFEST_LIST_SCHEMA: &str = r#"["null", {
+    "type": "record",
+    "name": "record",
+    "namespace": "org.apache.paimon.avro.generated",
+    "fields": [
+        {"name": "_VERSION", "type": "int"},
+        {"name": "_FILE_NAME", "type": "string"},
+        {"name": "_FILE_SIZE", "type": "long"},
+        {"name": "_NUM_ADDED_FILES", "type": "long"},
+        {"name": "_NUM_DELETED_FILES", "type": "long"},
+        {"name": "_PARTITION_STATS", "type": ["null", {
+            "type": "record",
+            "name": "record__PARTITION_STATS",
+            "fields": [
+                {"name": "_MIN_VALUES", "type": "bytes"},
+                {"name": "_MAX_VALUES", "type": "bytes"},
+                {"name": "_NULL_COUNTS", "type": ["null", {"type": "array", "items": ["null", "long"]}], "default": null}
+            ]
+        }], "default": null},
+        {"name": "_SCHEMA_ID", "type": "long"},
+        {"name": "_MIN_BUCKET", "type": ["null", "int"], "default": null},
+        {"name": "_MAX_BUCKET", "type": ["null", "int"], "default": null},
+        {"name": "_MIN_LEVEL", "type": ["null", "int"], "default": null},
+        {"name": "_MAX_LEVEL", "type": ["null", "int"], "default": null}
+    ]
+}]"#;
+
+/// This file includes several [`ManifestFileMeta`], representing all data of the whole table at the corresponding snapshot.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/manifest/ManifestList.java>
+#[derive(Clone, Debug)]
+pub struct ManifestList {
+    file_io: Arc<dyn FileIO>,
+}
+
+impl ManifestList {
+    pub fn new(file_io: Arc<dyn FileIO>) -> Self {
+        Self { file_io }
+    }
+
+    /// Read all [`ManifestFileMeta`]s from the manifest list at the given path.
+    pub async fn read(&self, path: &str) -> crate::Result<Vec<ManifestFileMeta>> {
+        let bytes = self.file_io.new_input(path)?.read().await?;
+        from_avro_bytes::<ManifestFileMeta>(&bytes)
+    }
+
+    /// Write the given [`ManifestFileMeta`]s into a new manifest list at the given path.
+    pub async fn write(&self, path: &str, metas: &[ManifestFileMeta]) -> crate::Result<()> {
+        let schema = Schema::parse_str(MANIFEST_LIST_SCHEMA)?;
+        let bytes = to_avro_bytes(&schema, metas)?;
+        self.file_io
+            .new_output(path)?
+            .write(Bytes::from(bytes))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::BinaryTableStats;
+    use apache_avro::Reader;
+
+    fn sample_metas() -> Vec<ManifestFileMeta> {
+        let value_bytes = vec![
+            0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 49, 0, 0, 0, 0, 0, 0, 129,
+        ];
+        vec![
+            ManifestFileMeta::new(
+                "manifest-19d138df-233f-46f7-beb6-fadaf4741c0e".to_string(),
+                10,
+                10,
+                10,
+                BinaryTableStats::new(
+                    value_bytes.clone(),
+                    value_bytes.clone(),
+                    vec![Some(1), Some(2)],
+                ),
+                1,
+            ),
+            ManifestFileMeta::new(
+                "manifest-a703ee48-c411-413e-b84e-c03bdb179631".to_string(),
+                11,
+                0,
+                10,
+                BinaryTableStats::new(
+                    value_bytes.clone(),
+                    value_bytes.clone(),
+                    vec![Some(1), Some(2)],
+                ),
+                2,
+            )
+            .with_bucket_range(0, 3)
+            .with_level_range(0, 5),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_write_and_read_manifest_list() {
+        let path = "file:/tmp/test_write_and_read_manifest_list";
+        let file_io = FileIOBuilder::from_url(path).unwrap().build().unwrap();
+        let manifest_list = ManifestList::new(file_io.clone());
+
+        manifest_list.write(path, &sample_metas()).await.unwrap();
+        let metas = manifest_list.read(path).await.unwrap();
+        assert_eq!(metas, sample_metas());
+
+        file_io.delete_file(path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_write_manifest_list_schema() {
+        let path = "file:/tmp/test_write_manifest_list_schema";
+        let file_io = FileIOBuilder::from_url(path).unwrap().build().unwrap();
+        ManifestList::new(file_io.clone())
+            .write(path, &sample_metas())
+            .await
+            .unwrap();
+
+        let bytes = file_io.new_input(path).unwrap().read().await.unwrap();
+        let reader = Reader::new(&bytes[..]).unwrap();
+        assert_eq!(
+            reader.writer_schema(),
+            &Schema::parse_str(MANIFEST_LIST_SCHEMA).unwrap()
+        );
+        assert_eq!(reader.count(), 2);
+
+        file_io.delete_file(path).await.unwrap();
+    }
+}
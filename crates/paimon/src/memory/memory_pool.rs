@@ -0,0 +1,179 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::MemoryExhaustedSnafu;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+/// A pool of memory shared by the writers and readers of tables, bounding the memory they
+/// reserve in total by a global limit.
+///
+/// Each operator holds a [`MemoryReservation`] of the pool for the memory it buffers, and
+/// releases it once the memory is freed. Writers flush their buffers when failing to reserve
+/// more memory, while readers fail to read.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/memory/MemoryPoolFactory.java>
+#[derive(Debug)]
+pub struct MemoryPool {
+    limit: usize,
+    used: AtomicUsize,
+}
+
+impl MemoryPool {
+    /// Create a pool of at most `limit` bytes.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            used: AtomicUsize::new(0),
+        }
+    }
+
+    /// Create a pool without limit, only accounting the memory reserved.
+    pub fn unbounded() -> Self {
+        Self::new(usize::MAX)
+    }
+
+    /// Max bytes of the memory reserved.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Bytes of the memory reserved.
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Acquire)
+    }
+
+    /// Bytes of the memory left to reserve.
+    pub fn available(&self) -> usize {
+        self.limit.saturating_sub(self.used())
+    }
+
+    /// Create an empty reservation of the pool for the consumer, named for the errors.
+    pub fn reservation(self: &Arc<Self>, consumer: impl Into<String>) -> MemoryReservation {
+        MemoryReservation {
+            pool: self.clone(),
+            consumer: consumer.into(),
+            size: 0,
+        }
+    }
+
+    fn try_reserve(&self, size: usize) -> bool {
+        self.used
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |used| {
+                used.checked_add(size).filter(|used| *used <= self.limit)
+            })
+            .is_ok()
+    }
+
+    fn release(&self, size: usize) {
+        self.used.fetch_sub(size, Ordering::AcqRel);
+    }
+}
+
+impl Default for MemoryPool {
+    fn default() -> Self {
+        Self::unbounded()
+    }
+}
+
+/// Memory of a [`MemoryPool`] reserved by an operator, released when dropped.
+#[derive(Debug)]
+pub struct MemoryReservation {
+    pool: Arc<MemoryPool>,
+    consumer: String,
+    size: usize,
+}
+
+impl MemoryReservation {
+    /// Name of the consumer of the reservation.
+    pub fn consumer(&self) -> &str {
+        &self.consumer
+    }
+
+    /// Bytes of the memory reserved.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Reserve more bytes of the pool, fails without reserving if the pool is exhausted.
+    pub fn try_grow(&mut self, additional: usize) -> crate::Result<()> {
+        if !self.pool.try_reserve(additional) {
+            return MemoryExhaustedSnafu {
+                consumer: self.consumer.clone(),
+                requested: additional,
+                available: self.pool.available(),
+            }
+            .fail();
+        }
+        self.size += additional;
+        Ok(())
+    }
+
+    /// Release some bytes of the reservation, at most the bytes reserved.
+    pub fn shrink(&mut self, size: usize) {
+        let size = size.min(self.size);
+        self.pool.release(size);
+        self.size -= size;
+    }
+
+    /// Release all of the reservation, returning the bytes released.
+    pub fn free(&mut self) -> usize {
+        let size = self.size;
+        self.shrink(size);
+        size
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.free();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_reservations() {
+        let pool = Arc::new(MemoryPool::new(100));
+        let mut writer = pool.reservation("writer");
+        let mut reader = pool.reservation("reader");
+        writer.try_grow(60).unwrap();
+        reader.try_grow(30).unwrap();
+        assert_eq!(pool.used(), 90);
+        assert_eq!(pool.available(), 10);
+
+        let err = reader.try_grow(20).unwrap_err();
+        assert!(matches!(
+            err,
+            crate::Error::MemoryExhausted {
+                requested: 20,
+                available: 10,
+                ..
+            }
+        ));
+        assert_eq!(reader.size(), 30);
+
+        writer.shrink(40);
+        reader.try_grow(20).unwrap();
+        assert_eq!(pool.used(), 70);
+        assert_eq!(writer.free(), 20);
+        drop(reader);
+        assert_eq!(pool.used(), 0);
+    }
+}
@@ -0,0 +1,517 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::ConfigInvalidSnafu;
+use crate::format::to_arrow_schema;
+use crate::mergetree::{KeyValues, MergeFunction};
+use crate::spec::{CoreOptions, DataField, DataType, TableSchema};
+use arrow_array::builder::{BooleanBuilder, PrimitiveBuilder, StringBuilder};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Decimal128Type, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+};
+use arrow_array::{
+    Array, ArrayRef, ArrowNativeTypeOp, ArrowPrimitiveType, RecordBatch, UInt32Array,
+};
+use arrow_ord::ord::make_comparator;
+use arrow_schema::SortOptions;
+use arrow_select::take::take;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Aggregate function of a field of the `aggregation` merge engine.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/aggregate/FieldAggregator.java>
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FieldAggregator {
+    /// Fields of primary keys, keeping the last value.
+    PrimaryKey,
+    Sum,
+    Max,
+    Min,
+    LastValue,
+    LastNonNullValue,
+    ListAgg(String),
+    BoolAnd,
+    BoolOr,
+}
+
+impl FieldAggregator {
+    fn new(name: &str, field: &DataField, options: &CoreOptions) -> crate::Result<FieldAggregator> {
+        let aggregator = match name {
+            "sum" => FieldAggregator::Sum,
+            "max" => FieldAggregator::Max,
+            "min" => FieldAggregator::Min,
+            "last_value" => FieldAggregator::LastValue,
+            "last_non_null_value" => FieldAggregator::LastNonNullValue,
+            "listagg" => {
+                FieldAggregator::ListAgg(options.field_list_agg_delimiter(field.name()).to_string())
+            }
+            "bool_and" => FieldAggregator::BoolAnd,
+            "bool_or" => FieldAggregator::BoolOr,
+            _ => {
+                return ConfigInvalidSnafu {
+                    message: format!(
+                        "aggregate function {name} of field {} is not supported",
+                        field.name()
+                    ),
+                }
+                .fail()
+            }
+        };
+        let data_type = field.data_type();
+        let supported = match aggregator {
+            FieldAggregator::Sum => matches!(
+                data_type,
+                DataType::TinyInt(_)
+                    | DataType::SmallInt(_)
+                    | DataType::Int(_)
+                    | DataType::BigInt(_)
+                    | DataType::Float(_)
+                    | DataType::Double(_)
+                    | DataType::Decimal(_)
+            ),
+            FieldAggregator::Max | FieldAggregator::Min => !matches!(
+                data_type,
+                DataType::Array(_) | DataType::Map(_) | DataType::Multiset(_) | DataType::Row(_)
+            ),
+            FieldAggregator::ListAgg(_) => {
+                matches!(data_type, DataType::Char(_) | DataType::VarChar(_))
+            }
+            FieldAggregator::BoolAnd | FieldAggregator::BoolOr => {
+                matches!(data_type, DataType::Boolean(_))
+            }
+            _ => true,
+        };
+        if !supported {
+            return ConfigInvalidSnafu {
+                message: format!(
+                    "aggregate function {name} does not support type {data_type} of field {}",
+                    field.name()
+                ),
+            }
+            .fail();
+        }
+        Ok(aggregator)
+    }
+
+    fn supports_retract(&self) -> bool {
+        matches!(
+            self,
+            FieldAggregator::PrimaryKey
+                | FieldAggregator::Sum
+                | FieldAggregator::LastValue
+                | FieldAggregator::LastNonNullValue
+        )
+    }
+
+    /// Aggregate the values of each range of the column, `retracts` is whether the rows are
+    /// retract records.
+    fn aggregate(
+        &self,
+        column: &ArrayRef,
+        retracts: &[bool],
+        ranges: &[Range<usize>],
+    ) -> crate::Result<ArrayRef> {
+        match self {
+            FieldAggregator::PrimaryKey | FieldAggregator::LastValue => {
+                // retract records retract the value to null
+                pick(column, ranges, |_, row| (!retracts[row]).then_some(row))
+            }
+            FieldAggregator::LastNonNullValue => pick(column, ranges, |current, row| {
+                match (column.is_null(row), retracts[row]) {
+                    (true, _) => current,
+                    (false, true) => None,
+                    (false, false) => Some(row),
+                }
+            }),
+            FieldAggregator::Max | FieldAggregator::Min => {
+                let comparator = make_comparator(column, column, SortOptions::default())?;
+                let max = *self == FieldAggregator::Max;
+                pick(column, ranges, |current, row| {
+                    if column.is_null(row) {
+                        return current;
+                    }
+                    match current {
+                        Some(current) if comparator(row, current).is_gt() != max => Some(current),
+                        _ => Some(row),
+                    }
+                })
+            }
+            FieldAggregator::Sum => match column.data_type() {
+                arrow_schema::DataType::Int8 => sum::<Int8Type>(column, retracts, ranges),
+                arrow_schema::DataType::Int16 => sum::<Int16Type>(column, retracts, ranges),
+                arrow_schema::DataType::Int32 => sum::<Int32Type>(column, retracts, ranges),
+                arrow_schema::DataType::Int64 => sum::<Int64Type>(column, retracts, ranges),
+                arrow_schema::DataType::Float32 => sum::<Float32Type>(column, retracts, ranges),
+                arrow_schema::DataType::Float64 => sum::<Float64Type>(column, retracts, ranges),
+                _ => sum::<Decimal128Type>(column, retracts, ranges),
+            },
+            FieldAggregator::ListAgg(delimiter) => {
+                let column = column.as_string::<i32>();
+                let mut builder = StringBuilder::with_capacity(ranges.len(), 0);
+                for range in ranges {
+                    let mut values = range
+                        .clone()
+                        .filter(|row| column.is_valid(*row))
+                        .map(|row| column.value(row));
+                    match values.next() {
+                        Some(first) => {
+                            let value = values.fold(first.to_string(), |mut value, v| {
+                                value.push_str(delimiter);
+                                value.push_str(v);
+                                value
+                            });
+                            builder.append_value(value);
+                        }
+                        None => builder.append_null(),
+                    }
+                }
+                Ok(Arc::new(builder.finish()))
+            }
+            FieldAggregator::BoolAnd | FieldAggregator::BoolOr => {
+                let column = column.as_boolean();
+                let and = *self == FieldAggregator::BoolAnd;
+                let mut builder = BooleanBuilder::with_capacity(ranges.len());
+                for range in ranges {
+                    let value = range
+                        .clone()
+                        .filter(|row| column.is_valid(*row))
+                        .map(|row| column.value(row))
+                        .reduce(|a, b| if and { a && b } else { a || b });
+                    builder.append_option(value);
+                }
+                Ok(Arc::new(builder.finish()))
+            }
+        }
+    }
+}
+
+/// Pick the row of the value of each range, updating the picked row by the rows in order.
+fn pick(
+    column: &ArrayRef,
+    ranges: &[Range<usize>],
+    f: impl Fn(Option<usize>, usize) -> Option<usize>,
+) -> crate::Result<ArrayRef> {
+    let indices: UInt32Array = ranges
+        .iter()
+        .map(|range| range.clone().fold(None, &f).map(|row| row as u32))
+        .collect();
+    Ok(take(column, &indices, None)?)
+}
+
+fn sum<T: ArrowPrimitiveType>(
+    column: &ArrayRef,
+    retracts: &[bool],
+    ranges: &[Range<usize>],
+) -> crate::Result<ArrayRef> {
+    let column = column.as_primitive::<T>();
+    let mut builder = PrimitiveBuilder::<T>::with_capacity(ranges.len())
+        .with_data_type(column.data_type().clone());
+    for range in ranges {
+        let mut sum: Option<T::Native> = None;
+        for row in range.clone().filter(|row| column.is_valid(*row)) {
+            let value = column.value(row);
+            sum = Some(match (sum, retracts[row]) {
+                (Some(sum), false) => sum.add_wrapping(value),
+                (Some(sum), true) => sum.sub_wrapping(value),
+                (None, false) => value,
+                (None, true) => value.neg_wrapping(),
+            });
+        }
+        builder.append_option(sum);
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Merge function of the `aggregation` merge engine, the value fields are aggregated by the
+/// aggregate functions configured by `fields.<field>.aggregate-function`, fields without
+/// aggregate function keep the last non-null value.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/aggregate/AggregateMergeFunction.java>
+#[derive(Debug, Clone)]
+pub(crate) struct AggregateMergeFunction {
+    value_fields: Vec<DataField>,
+    aggregators: Vec<FieldAggregator>,
+    ignore_retracts: Vec<bool>,
+    ignore_delete: bool,
+}
+
+impl AggregateMergeFunction {
+    pub(crate) fn new(
+        table_schema: &TableSchema,
+        read_fields: &[DataField],
+    ) -> crate::Result<Self> {
+        let options = CoreOptions::new(table_schema.options());
+        let aggregators = read_fields
+            .iter()
+            .map(|field| {
+                if table_schema
+                    .primary_keys()
+                    .iter()
+                    .any(|k| k == field.name())
+                {
+                    return Ok(FieldAggregator::PrimaryKey);
+                }
+                let name = options
+                    .field_aggregate_function(field.name())
+                    .unwrap_or("last_non_null_value");
+                FieldAggregator::new(name, field, &options)
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Self {
+            value_fields: read_fields.to_vec(),
+            aggregators,
+            ignore_retracts: read_fields
+                .iter()
+                .map(|field| options.field_ignore_retract(field.name()))
+                .collect(),
+            ignore_delete: options.ignore_delete(),
+        })
+    }
+}
+
+impl MergeFunction for AggregateMergeFunction {
+    fn value_fields(&self) -> &[DataField] {
+        &self.value_fields
+    }
+
+    fn merge(&self, kvs: &KeyValues, ranges: &[Range<usize>]) -> crate::Result<RecordBatch> {
+        let retracts = (0..kvs.num_rows())
+            .map(|row| Ok(!kvs.row_kind(row)?.is_add()))
+            .collect::<crate::Result<Vec<bool>>>()?;
+        let has_retract = retracts.contains(&true);
+        let columns = self
+            .aggregators
+            .iter()
+            .enumerate()
+            .map(|(i, aggregator)| {
+                let column = kvs.value_column(i);
+                if !has_retract {
+                    return aggregator.aggregate(column, &retracts, ranges);
+                }
+                if self.ignore_delete || self.ignore_retracts[i] {
+                    // aggregate the rows without the retract records
+                    let rows: UInt32Array = (0..kvs.num_rows())
+                        .filter(|row| !retracts[*row])
+                        .map(|row| row as u32)
+                        .collect();
+                    let mut offset = 0;
+                    let ranges: Vec<Range<usize>> = ranges
+                        .iter()
+                        .map(|range| {
+                            let len = range.clone().filter(|row| !retracts[*row]).count();
+                            offset += len;
+                            offset - len..offset
+                        })
+                        .collect();
+                    let column = take(column, &rows, None)?;
+                    return aggregator.aggregate(&column, &vec![false; rows.len()], &ranges);
+                }
+                if !aggregator.supports_retract() {
+                    return ConfigInvalidSnafu {
+                        message: format!(
+                            "aggregate function of field {} does not support retraction, \
+                            configure 'fields.{}.ignore-retract' to ignore retract records",
+                            self.value_fields[i].name(),
+                            self.value_fields[i].name()
+                        ),
+                    }
+                    .fail();
+                }
+                aggregator.aggregate(column, &retracts, ranges)
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(RecordBatch::try_new(
+            Arc::new(to_arrow_schema(&self.value_fields)),
+            columns,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Schema;
+    use arrow_array::{BooleanArray, Int32Array, Int64Array, StringArray};
+
+    fn merge(
+        options: &[(&str, &str)],
+        kinds: Vec<i8>,
+        values: Vec<(&str, &str, ArrayRef)>,
+        key_lengths: &[usize],
+    ) -> crate::Result<RecordBatch> {
+        let fields: Vec<DataField> = values
+            .iter()
+            .enumerate()
+            .map(|(id, (name, data_type, _))| {
+                DataField::new(id as i32, name.to_string(), data_type.parse().unwrap())
+            })
+            .collect();
+        let table_schema = Schema::builder()
+            .fields(fields.clone())
+            .primary_keys(vec!["id".to_string()])
+            .options(
+                options
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            )
+            .build()
+            .to_table_schema(0)
+            .unwrap();
+        let function = AggregateMergeFunction::new(&table_schema, &fields)?;
+
+        let rows = kinds.len();
+        let mut columns: Vec<(&str, ArrayRef)> = vec![
+            ("_KEY_id", values[0].2.clone()),
+            (
+                "_SEQUENCE_NUMBER",
+                Arc::new(Int64Array::from_iter_values(0..rows as i64)),
+            ),
+            ("_VALUE_KIND", Arc::new(arrow_array::Int8Array::from(kinds))),
+        ];
+        columns.extend(values.into_iter().map(|(name, _, column)| (name, column)));
+        let kvs = KeyValues::new(RecordBatch::try_from_iter(columns).unwrap(), 1);
+        let ranges: Vec<Range<usize>> = key_lengths
+            .iter()
+            .scan(0, |offset, len| {
+                *offset += len;
+                Some(*offset - len..*offset)
+            })
+            .collect();
+        function.merge(&kvs, &ranges)
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let options = [
+            ("fields.s.aggregate-function", "sum"),
+            ("fields.mx.aggregate-function", "max"),
+            ("fields.mn.aggregate-function", "min"),
+            ("fields.lv.aggregate-function", "last_value"),
+            ("fields.la.aggregate-function", "listagg"),
+            ("fields.la.list-agg-delimiter", "|"),
+            ("fields.ba.aggregate-function", "bool_and"),
+            ("fields.bo.aggregate-function", "bool_or"),
+        ];
+        let merged = merge(
+            &options,
+            vec![0; 4],
+            vec![
+                ("id", "INT", Arc::new(Int32Array::from(vec![1, 1, 1, 2]))),
+                (
+                    "s",
+                    "INT",
+                    Arc::new(Int32Array::from(vec![Some(1), None, Some(2), None])),
+                ),
+                ("mx", "INT", Arc::new(Int32Array::from(vec![3, 5, 4, 1]))),
+                ("mn", "INT", Arc::new(Int32Array::from(vec![3, 5, 4, 1]))),
+                (
+                    "lv",
+                    "STRING",
+                    Arc::new(StringArray::from(vec![Some("a"), Some("b"), None, None])),
+                ),
+                (
+                    "lnn",
+                    "STRING",
+                    Arc::new(StringArray::from(vec![Some("a"), Some("b"), None, None])),
+                ),
+                (
+                    "la",
+                    "STRING",
+                    Arc::new(StringArray::from(vec![
+                        Some("a"),
+                        None,
+                        Some("c"),
+                        Some("d"),
+                    ])),
+                ),
+                (
+                    "ba",
+                    "BOOLEAN",
+                    Arc::new(BooleanArray::from(vec![true, false, true, true])),
+                ),
+                (
+                    "bo",
+                    "BOOLEAN",
+                    Arc::new(BooleanArray::from(vec![false, true, false, false])),
+                ),
+            ],
+            &[3, 1],
+        )
+        .unwrap();
+        let expected: Vec<ArrayRef> = vec![
+            Arc::new(Int32Array::from(vec![1, 2])),
+            Arc::new(Int32Array::from(vec![Some(3), None])),
+            Arc::new(Int32Array::from(vec![5, 1])),
+            Arc::new(Int32Array::from(vec![3, 1])),
+            Arc::new(StringArray::from(vec![None::<&str>, None])),
+            Arc::new(StringArray::from(vec![Some("b"), None])),
+            Arc::new(StringArray::from(vec!["a|c", "d"])),
+            Arc::new(BooleanArray::from(vec![false, true])),
+            Arc::new(BooleanArray::from(vec![true, false])),
+        ];
+        for (column, expected) in merged.columns().iter().zip(expected) {
+            assert_eq!(column.as_ref(), expected.as_ref());
+        }
+    }
+
+    #[test]
+    fn test_aggregate_with_retract() {
+        let values = || -> Vec<(&str, &str, ArrayRef)> {
+            vec![
+                ("id", "INT", Arc::new(Int32Array::from(vec![1, 1, 1]))),
+                ("s", "BIGINT", Arc::new(Int64Array::from(vec![5, 2, 1]))),
+                ("mx", "INT", Arc::new(Int32Array::from(vec![1, 3, 2]))),
+            ]
+        };
+        let sum_and_max = [
+            ("fields.s.aggregate-function", "sum"),
+            ("fields.mx.aggregate-function", "max"),
+        ];
+        assert!(matches!(
+            merge(&sum_and_max, vec![0, 1, 0], values(), &[3]),
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+
+        let options = [
+            sum_and_max[0],
+            sum_and_max[1],
+            ("fields.mx.ignore-retract", "true"),
+        ];
+        let merged = merge(&options, vec![0, 1, 0], values(), &[3]).unwrap();
+        assert_eq!(
+            merged.column(1).as_ref(),
+            &Int64Array::from(vec![4]) as &dyn Array
+        );
+        assert_eq!(
+            merged.column(2).as_ref(),
+            &Int32Array::from(vec![2]) as &dyn Array
+        );
+
+        assert!(matches!(
+            merge(
+                &[("fields.default-aggregate-function", "bool_or")],
+                vec![0, 0, 0],
+                values(),
+                &[3]
+            ),
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+    }
+}
@@ -0,0 +1,133 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::Error;
+use crate::mergetree::{Levels, MergeTreeCompactRewriter, UniversalCompaction};
+use crate::spec::DataFileMeta;
+use crate::table::CompactIncrement;
+use tokio::task::JoinHandle;
+
+/// Manager of the compactions on write of a bucket of primary key tables.
+///
+/// The sorted runs picked by the universal compaction are compacted in the background, one
+/// compaction at a time, and the files changed by the compactions finished are committed
+/// along with the files written.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/MergeTreeCompactManager.java>
+#[derive(Debug)]
+pub(crate) struct MergeTreeCompactManager {
+    levels: Levels,
+    strategy: UniversalCompaction,
+    rewriter: MergeTreeCompactRewriter,
+    task: Option<JoinHandle<crate::Result<CompactIncrement>>>,
+    /// The files changed by the compactions finished since the last commit.
+    increment: CompactIncrement,
+}
+
+impl MergeTreeCompactManager {
+    pub(crate) fn new(
+        levels: Levels,
+        strategy: UniversalCompaction,
+        rewriter: MergeTreeCompactRewriter,
+    ) -> Self {
+        Self {
+            levels,
+            strategy,
+            rewriter,
+            task: None,
+            increment: CompactIncrement::default(),
+        }
+    }
+
+    /// Add a new file of level 0 written.
+    pub(crate) fn add_new_file(&mut self, file: DataFileMeta) {
+        self.levels.add_level0_file(file);
+    }
+
+    /// Start compacting the sorted runs picked, unless a compaction is running.
+    pub(crate) fn trigger_compaction(&mut self) {
+        if self.task.is_some() {
+            return;
+        }
+        let runs = self.levels.level_sorted_runs();
+        let Some(unit) = self.strategy.pick(self.levels.number_of_levels(), &runs) else {
+            return;
+        };
+        // a single file is not rewritten into its own level
+        if unit.files.len() < 2 && unit.files.iter().all(|f| f.level == unit.output_level) {
+            return;
+        }
+        // deletes are dropped if no older files of their keys remain below the output level
+        let drop_delete =
+            unit.output_level != 0 && unit.output_level >= self.levels.non_empty_highest_level();
+        let rewriter = self.rewriter.clone();
+        self.task = Some(tokio::spawn(async move {
+            rewriter
+                .rewrite(unit.files, unit.output_level, drop_delete)
+                .await
+        }));
+    }
+
+    /// Apply the result of the running compaction to the levels, waiting for it if `blocking`
+    /// or skipping it if it is not finished.
+    pub(crate) async fn finish_compaction(&mut self, blocking: bool) -> crate::Result<()> {
+        match &self.task {
+            Some(task) if blocking || task.is_finished() => {}
+            _ => return Ok(()),
+        }
+        let Some(task) = self.task.take() else {
+            return Ok(());
+        };
+        let result = task.await.map_err(|e| Error::CompactionUnexpected {
+            message: format!("Compaction task failed: {e}"),
+        })??;
+        self.levels
+            .update(&result.compact_before, &result.compact_after);
+
+        let factory = self.rewriter.factory();
+        for file in result.compact_before {
+            match self
+                .increment
+                .compact_after
+                .iter()
+                .position(|after| after.file_name == file.file_name)
+            {
+                // the files of the previous compactions are not committed, so are deleted
+                Some(index) => {
+                    self.increment.compact_after.remove(index);
+                    let path = factory.path_factory().to_path(&file.file_name);
+                    factory.file_io().delete_file(&path).await?;
+                }
+                None => self.increment.compact_before.push(file),
+            }
+        }
+        self.increment.compact_after.extend(result.compact_after);
+        self.increment
+            .changelog_files
+            .extend(result.changelog_files);
+        Ok(())
+    }
+
+    /// Wait for the running compaction and compact the sorted runs picked once more, then take
+    /// the files changed by the compactions since the last commit.
+    pub(crate) async fn prepare_commit(&mut self) -> crate::Result<CompactIncrement> {
+        self.finish_compaction(true).await?;
+        self.trigger_compaction();
+        self.finish_compaction(true).await?;
+        Ok(std::mem::take(&mut self.increment))
+    }
+}
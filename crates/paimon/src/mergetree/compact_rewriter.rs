@@ -0,0 +1,244 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::to_binary_row;
+use crate::mergetree::{KeyValueFileWriterFactory, KeyValues, LookupChangelog, MergeTreeReader};
+use crate::spec::{BinaryRow, DataField, DataFileMeta, DataType, RowKind};
+use crate::table::{CompactIncrement, DataSplit};
+use arrow_array::{Array, Int64Array, RecordBatch, UInt32Array};
+use arrow_select::concat::concat_batches;
+use arrow_select::take::take_record_batch;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Rewriter of the files of the sorted runs of a bucket of primary key tables into new files
+/// of the output level.
+///
+/// The key values of the files are merged by the merge engine into inserts of the latest
+/// sequence number of the files. Keys whose merged value is deleted are kept as the deletes of
+/// their latest key values, unless deletes are dropped since no older files remain below the
+/// output level.
+///
+/// With the full changelog, compactions into the max level write the changes of the keys in
+/// the files out of the max level, from their merged values in the files of the max level to
+/// their merged values of all the files.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/MergeTreeCompactRewriter.java>
+#[derive(Debug, Clone)]
+pub(crate) struct MergeTreeCompactRewriter {
+    /// The reader merging all the fields of the table.
+    reader: MergeTreeReader,
+    factory: KeyValueFileWriterFactory,
+    partition: BinaryRow,
+    bucket: i32,
+    max_level: i32,
+    /// Whether updates with equal values are skipped in the full changelog, none if the full
+    /// changelog is not produced.
+    full_changelog_row_deduplicate: Option<bool>,
+}
+
+impl MergeTreeCompactRewriter {
+    pub(crate) fn new(
+        reader: MergeTreeReader,
+        factory: KeyValueFileWriterFactory,
+        partition: BinaryRow,
+        bucket: i32,
+        max_level: i32,
+    ) -> Self {
+        Self {
+            reader,
+            factory,
+            partition,
+            bucket,
+            max_level,
+            full_changelog_row_deduplicate: None,
+        }
+    }
+
+    /// Produce the full changelog of the compactions into the max level.
+    pub(crate) fn with_full_changelog(mut self, row_deduplicate: bool) -> Self {
+        self.full_changelog_row_deduplicate = Some(row_deduplicate);
+        self
+    }
+
+    /// Get the factory of the files written.
+    #[inline]
+    pub(crate) fn factory(&self) -> &KeyValueFileWriterFactory {
+        &self.factory
+    }
+
+    /// Rewrite the files into new files of the output level, dropping the deleted keys if
+    /// `drop_delete` is true.
+    pub(crate) async fn rewrite(
+        &self,
+        files: Vec<DataFileMeta>,
+        output_level: i32,
+        drop_delete: bool,
+    ) -> crate::Result<CompactIncrement> {
+        let mut chunks = self.reader.read_key_values(&self.split(&files)).await?;
+        let mut batches = Vec::new();
+        let mut row_kinds = Vec::new();
+        while let Some(kvs) = chunks.next_chunk()? {
+            let ranges = self.reader.key_ranges(&kvs)?;
+            let merged = self.reader.merge(&kvs, &ranges)?;
+            let (values, kinds) = if drop_delete {
+                let kinds = vec![RowKind::Insert; merged.num_rows()];
+                (merged, kinds)
+            } else {
+                self.with_deletes(&kvs, &ranges, merged)?
+            };
+            batches.push(values);
+            row_kinds.extend(kinds);
+        }
+        let values = concat_batches(&self.reader.read_schema(), &batches)?;
+
+        let sequence_number = files
+            .iter()
+            .map(|file| file.max_sequence_number)
+            .max()
+            .unwrap_or_default();
+        let sequence_numbers = Int64Array::from_value(sequence_number, values.num_rows());
+        let key_values = self
+            .factory
+            .key_values(&values, sequence_numbers, &row_kinds)?;
+        let compact_after = self.factory.write_files(&key_values, output_level).await?;
+        let changelog_files = match self.full_changelog_row_deduplicate {
+            Some(row_deduplicate) if output_level == self.max_level => {
+                self.full_changelog(&files, row_deduplicate, sequence_number)
+                    .await?
+            }
+            _ => vec![],
+        };
+        Ok(CompactIncrement {
+            compact_before: files,
+            compact_after,
+            changelog_files,
+        })
+    }
+
+    /// Add the latest key values of the keys whose merged value is deleted as deletes, in the
+    /// order of keys.
+    fn with_deletes(
+        &self,
+        kvs: &KeyValues,
+        ranges: &[Range<usize>],
+        merged: RecordBatch,
+    ) -> crate::Result<(RecordBatch, Vec<RowKind>)> {
+        let key_types: Vec<&DataType> = self
+            .factory
+            .key_fields()
+            .iter()
+            .map(DataField::data_type)
+            .collect();
+        let key_columns: Vec<&dyn Array> = kvs.key_columns().iter().map(|c| c.as_ref()).collect();
+        let merged_key_columns: Vec<&dyn Array> = self
+            .factory
+            .key_indices()
+            .iter()
+            .map(|index| merged.column(*index).as_ref())
+            .collect();
+
+        // the rows are taken from the concatenation of the merged rows and the key values
+        let offset = merged.num_rows() as u32;
+        let mut indices = Vec::with_capacity(ranges.len());
+        let mut row_kinds = Vec::with_capacity(ranges.len());
+        let mut merged_row = 0;
+        for range in ranges {
+            let key = to_binary_row(&key_columns, &key_types, range.start)?;
+            if merged_row < merged.num_rows()
+                && to_binary_row(&merged_key_columns, &key_types, merged_row)? == key
+            {
+                indices.push(merged_row as u32);
+                row_kinds.push(RowKind::Insert);
+                merged_row += 1;
+            } else {
+                indices.push(offset + range.end as u32 - 1);
+                row_kinds.push(RowKind::Delete);
+            }
+        }
+        let values = kvs
+            .values()?
+            .project(&(0..merged.num_columns()).collect::<Vec<_>>())?;
+        let values = RecordBatch::try_new(merged.schema(), values.columns().to_vec())?;
+        let rows = concat_batches(&merged.schema(), [&merged, &values])?;
+        Ok((
+            take_record_batch(&rows, &UInt32Array::from(indices))?,
+            row_kinds,
+        ))
+    }
+
+    /// Write the changelog of the keys in the files out of the max level.
+    async fn full_changelog(
+        &self,
+        files: &[DataFileMeta],
+        row_deduplicate: bool,
+        sequence_number: i64,
+    ) -> crate::Result<Vec<DataFileMeta>> {
+        let (top_level_files, new_files): (Vec<_>, Vec<_>) = files
+            .iter()
+            .cloned()
+            .partition(|file| file.level == self.max_level);
+        let mut chunks = self.reader.read_key_values(&self.split(&new_files)).await?;
+        let key_types: Vec<&DataType> = self
+            .factory
+            .key_fields()
+            .iter()
+            .map(DataField::data_type)
+            .collect();
+        let mut written_keys = HashSet::new();
+        while let Some(kvs) = chunks.next_chunk()? {
+            let key_columns: Vec<&dyn Array> =
+                kvs.key_columns().iter().map(|c| c.as_ref()).collect();
+            for row in 0..kvs.num_rows() {
+                written_keys
+                    .insert(to_binary_row(&key_columns, &key_types, row)?.to_serialized_bytes());
+            }
+        }
+
+        let lookup = LookupChangelog::new(
+            self.reader.clone(),
+            self.partition.clone(),
+            self.bucket,
+            self.factory.path_factory().bucket_path(),
+            top_level_files,
+            row_deduplicate,
+        );
+        let (changelog, row_kinds) = lookup
+            .changelog(
+                &new_files,
+                &written_keys,
+                self.factory.key_fields(),
+                self.factory.key_indices(),
+            )
+            .await?;
+        let sequence_numbers = Int64Array::from_value(sequence_number, changelog.num_rows());
+        let key_values = self
+            .factory
+            .key_values(&changelog, sequence_numbers, &row_kinds)?;
+        self.factory.write_changelog_files(&key_values).await
+    }
+
+    fn split(&self, files: &[DataFileMeta]) -> DataSplit {
+        DataSplit::builder()
+            .snapshot_id(0)
+            .partition(self.partition.clone())
+            .bucket(self.bucket)
+            .bucket_path(self.factory.path_factory().bucket_path().to_string())
+            .data_files(files.to_vec())
+            .build()
+    }
+}
@@ -0,0 +1,114 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::mergetree::{KeyValues, MergeFunction};
+use crate::spec::DataField;
+use arrow_array::{RecordBatch, UInt32Array};
+use arrow_select::take::take_record_batch;
+use std::ops::Range;
+
+/// Merge function of the `deduplicate` merge engine, keeping the latest value of the key.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/DeduplicateMergeFunction.java>
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DeduplicateMergeFunction {
+    value_fields: Vec<DataField>,
+    ignore_delete: bool,
+}
+
+impl DeduplicateMergeFunction {
+    /// Create the merge function, the delete records are skipped if `ignore_delete` is true.
+    pub(crate) fn new(value_fields: Vec<DataField>, ignore_delete: bool) -> Self {
+        Self {
+            value_fields,
+            ignore_delete,
+        }
+    }
+}
+
+impl MergeFunction for DeduplicateMergeFunction {
+    fn value_fields(&self) -> &[DataField] {
+        &self.value_fields
+    }
+
+    fn merge(&self, kvs: &KeyValues, ranges: &[Range<usize>]) -> crate::Result<RecordBatch> {
+        let mut indices = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            for index in range.clone().rev() {
+                if kvs.row_kind(index)?.is_add() {
+                    indices.push(index as u32);
+                    break;
+                }
+                if !self.ignore_delete {
+                    break;
+                }
+            }
+        }
+        Ok(take_record_batch(
+            &kvs.values()?,
+            &UInt32Array::from(indices),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, Int64Array, Int8Array, StringArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_deduplicate() {
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "_KEY_id",
+                Arc::new(Int32Array::from(vec![1, 1, 2, 3, 3])) as _,
+            ),
+            (
+                "_SEQUENCE_NUMBER",
+                Arc::new(Int64Array::from(vec![0, 1, 2, 3, 4])) as _,
+            ),
+            (
+                "_VALUE_KIND",
+                Arc::new(Int8Array::from(vec![0, 2, 0, 0, 3])) as _,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["a", "b", "c", "d", "e"])) as _,
+            ),
+        ])
+        .unwrap();
+        let kvs = KeyValues::new(batch, 1);
+        let ranges = [0..2, 2..3, 3..5];
+
+        let merged = DeduplicateMergeFunction::new(vec![], false)
+            .merge(&kvs, &ranges)
+            .unwrap();
+        assert_eq!(
+            merged.column(0).as_ref(),
+            &StringArray::from(vec!["b", "c"]) as &dyn arrow_array::Array
+        );
+
+        let merged = DeduplicateMergeFunction::new(vec![], true)
+            .merge(&kvs, &ranges)
+            .unwrap();
+        assert_eq!(
+            merged.column(0).as_ref(),
+            &StringArray::from(vec!["b", "c", "d"]) as &dyn arrow_array::Array
+        );
+    }
+}
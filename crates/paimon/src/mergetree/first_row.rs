@@ -0,0 +1,116 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::ConfigInvalidSnafu;
+use crate::mergetree::{KeyValues, MergeFunction};
+use crate::spec::DataField;
+use arrow_array::{RecordBatch, UInt32Array};
+use arrow_select::take::take_record_batch;
+use std::ops::Range;
+
+/// Merge function of the `first-row` merge engine, keeping the first value of the key.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/FirstRowMergeFunction.java>
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FirstRowMergeFunction {
+    value_fields: Vec<DataField>,
+    ignore_delete: bool,
+}
+
+impl FirstRowMergeFunction {
+    /// Create the merge function, the delete records are skipped if `ignore_delete` is true,
+    /// otherwise they are rejected.
+    pub(crate) fn new(value_fields: Vec<DataField>, ignore_delete: bool) -> Self {
+        Self {
+            value_fields,
+            ignore_delete,
+        }
+    }
+}
+
+impl MergeFunction for FirstRowMergeFunction {
+    fn value_fields(&self) -> &[DataField] {
+        &self.value_fields
+    }
+
+    fn merge(&self, kvs: &KeyValues, ranges: &[Range<usize>]) -> crate::Result<RecordBatch> {
+        let mut indices = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            let mut first = None;
+            for index in range.clone() {
+                if !kvs.row_kind(index)?.is_add() {
+                    if self.ignore_delete {
+                        continue;
+                    }
+                    return ConfigInvalidSnafu {
+                        message:
+                            "first row merge engine can not accept delete records by default, \
+                            configure 'first-row.ignore-delete' to ignore delete records",
+                    }
+                    .fail();
+                }
+                first.get_or_insert(index as u32);
+            }
+            indices.extend(first);
+        }
+        Ok(take_record_batch(
+            &kvs.values()?,
+            &UInt32Array::from(indices),
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Array, Int32Array, Int64Array, Int8Array, StringArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_first_row() {
+        let batch = RecordBatch::try_from_iter(vec![
+            ("_KEY_id", Arc::new(Int32Array::from(vec![1, 1, 2, 2])) as _),
+            (
+                "_SEQUENCE_NUMBER",
+                Arc::new(Int64Array::from(vec![0, 1, 2, 3])) as _,
+            ),
+            (
+                "_VALUE_KIND",
+                Arc::new(Int8Array::from(vec![0, 0, 3, 0])) as _,
+            ),
+            (
+                "name",
+                Arc::new(StringArray::from(vec!["a", "b", "c", "d"])) as _,
+            ),
+        ])
+        .unwrap();
+        let kvs = KeyValues::new(batch, 1);
+        let ranges = [0..2, 2..4];
+
+        assert!(matches!(
+            FirstRowMergeFunction::new(vec![], false).merge(&kvs, &ranges),
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+        let merged = FirstRowMergeFunction::new(vec![], true)
+            .merge(&kvs, &ranges)
+            .unwrap();
+        assert_eq!(
+            merged.column(0).as_ref(),
+            &StringArray::from(vec!["a", "d"]) as &dyn Array
+        );
+    }
+}
@@ -0,0 +1,234 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::file_index::FileIndexOptions;
+use crate::format::{
+    to_arrow_schema, DataFileWriter, FileCompression, StatsMode, DEFAULT_BATCH_SIZE,
+};
+use crate::io::FileIO;
+use crate::spec::{DataField, DataFileMeta, RowKind};
+use crate::utils::DataFilePathFactory;
+use arrow_array::cast::AsArray;
+use arrow_array::types::Int64Type;
+use arrow_array::{ArrayRef, Int64Array, Int8Array, RecordBatch};
+use arrow_schema::SchemaRef;
+use std::sync::Arc;
+
+/// Factory of the key value files of a bucket of primary key tables, the data files of the
+/// levels and the changelog files.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/io/KeyValueFileWriterFactory.java>
+#[derive(Debug, Clone)]
+pub(crate) struct KeyValueFileWriterFactory {
+    file_io: Arc<dyn FileIO>,
+    path_factory: DataFilePathFactory,
+    schema_id: i64,
+    target_file_size: i64,
+    /// Positions of the key fields in the value fields.
+    key_indices: Vec<usize>,
+    /// Positions of the sequence fields in the value fields.
+    sequence_indices: Vec<usize>,
+    /// Fields of the data files, the key fields, the system fields and then the value fields.
+    fields: Vec<DataField>,
+    stats_modes: Vec<StatsMode>,
+    file_index: FileIndexOptions,
+    compression: FileCompression,
+    schema: SchemaRef,
+}
+
+impl KeyValueFileWriterFactory {
+    pub(crate) fn new(
+        file_io: Arc<dyn FileIO>,
+        path_factory: DataFilePathFactory,
+        schema_id: i64,
+        target_file_size: i64,
+        key_indices: Vec<usize>,
+        fields: Vec<DataField>,
+        compression: FileCompression,
+    ) -> Self {
+        let schema = Arc::new(to_arrow_schema(&fields));
+        Self {
+            file_io,
+            path_factory,
+            schema_id,
+            target_file_size,
+            key_indices,
+            sequence_indices: vec![],
+            stats_modes: vec![StatsMode::default(); fields.len()],
+            file_index: FileIndexOptions::default(),
+            fields,
+            compression,
+            schema,
+        }
+    }
+
+    /// Collect the statistics of the fields of the data files by the modes.
+    pub(crate) fn with_stats_modes(mut self, stats_modes: Vec<StatsMode>) -> Self {
+        self.stats_modes = stats_modes;
+        self
+    }
+
+    /// Write the file indexes of the value columns of the data files, not the changelog files.
+    pub(crate) fn with_file_index(mut self, file_index: FileIndexOptions) -> Self {
+        self.file_index = file_index;
+        self
+    }
+
+    /// Order the key values of the same key by the value fields at the positions before the
+    /// sequence number.
+    pub(crate) fn with_sequence_indices(mut self, sequence_indices: Vec<usize>) -> Self {
+        self.sequence_indices = sequence_indices;
+        self
+    }
+
+    #[inline]
+    pub(crate) fn file_io(&self) -> &Arc<dyn FileIO> {
+        &self.file_io
+    }
+
+    #[inline]
+    pub(crate) fn path_factory(&self) -> &DataFilePathFactory {
+        &self.path_factory
+    }
+
+    /// Get the positions of the key fields in the value fields.
+    #[inline]
+    pub(crate) fn key_indices(&self) -> &[usize] {
+        &self.key_indices
+    }
+
+    /// Get the positions of the sequence fields in the value fields.
+    #[inline]
+    pub(crate) fn sequence_indices(&self) -> &[usize] {
+        &self.sequence_indices
+    }
+
+    /// Get the key fields of the data files.
+    pub(crate) fn key_fields(&self) -> &[DataField] {
+        &self.fields[..self.key_indices.len()]
+    }
+
+    /// Get the arrow schema of the key values.
+    #[inline]
+    pub(crate) fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Build the key values of the rows of the value fields.
+    pub(crate) fn key_values(
+        &self,
+        batch: &RecordBatch,
+        sequence_numbers: Int64Array,
+        row_kinds: &[RowKind],
+    ) -> crate::Result<RecordBatch> {
+        let columns: Vec<ArrayRef> = self
+            .key_indices
+            .iter()
+            .map(|index| batch.column(*index).clone())
+            .chain([
+                Arc::new(sequence_numbers) as ArrayRef,
+                Arc::new(Int8Array::from_iter_values(
+                    row_kinds.iter().map(|kind| kind.to_value()),
+                )),
+            ])
+            .chain(batch.columns().iter().cloned())
+            .collect();
+        Ok(RecordBatch::try_new(self.schema.clone(), columns)?)
+    }
+
+    /// Write the key values sorted by key and sequence number into new data files of the
+    /// level, the files of levels above 0 are produced by compaction.
+    pub(crate) async fn write_files(
+        &self,
+        sorted: &RecordBatch,
+        level: i32,
+    ) -> crate::Result<Vec<DataFileMeta>> {
+        let mut writer = self.new_data_writer(level);
+        self.write_sorted(&mut writer, sorted).await?;
+        writer.close().await
+    }
+
+    /// Write the key values sorted by key into new changelog files.
+    pub(crate) async fn write_changelog_files(
+        &self,
+        sorted: &RecordBatch,
+    ) -> crate::Result<Vec<DataFileMeta>> {
+        let mut writer = self.new_changelog_writer();
+        self.write_sorted(&mut writer, sorted).await?;
+        writer.close().await
+    }
+
+    /// Create a writer of new data files of the level.
+    pub(crate) fn new_data_writer(&self, level: i32) -> DataFileWriter {
+        let writer = self
+            .new_writer(self.path_factory.clone())
+            .with_file_index(self.file_index.clone());
+        if level > 0 {
+            writer.with_compact_level(level)
+        } else {
+            writer
+        }
+    }
+
+    /// Create a writer of new changelog files.
+    pub(crate) fn new_changelog_writer(&self) -> DataFileWriter {
+        self.new_writer(self.path_factory.changelog())
+    }
+
+    fn new_writer(&self, path_factory: DataFilePathFactory) -> DataFileWriter {
+        DataFileWriter::new(
+            self.file_io.clone(),
+            path_factory,
+            self.fields.clone(),
+            self.compression.clone(),
+            self.key_indices.len(),
+            self.schema_id,
+            self.target_file_size,
+        )
+        .with_stats_modes(self.stats_modes.clone())
+    }
+
+    /// Write the key values sorted by key into the writer, the batches written to a writer
+    /// must be sorted as a whole.
+    pub(crate) async fn write_sorted(
+        &self,
+        writer: &mut DataFileWriter,
+        sorted: &RecordBatch,
+    ) -> crate::Result<()> {
+        let key_arity = self.key_indices.len();
+        let mut offset = 0;
+        while offset < sorted.num_rows() {
+            let batch = sorted.slice(offset, DEFAULT_BATCH_SIZE.min(sorted.num_rows() - offset));
+            offset += batch.num_rows();
+            let sequence_numbers = batch.column(key_arity).as_primitive::<Int64Type>();
+            let min = sequence_numbers
+                .values()
+                .iter()
+                .min()
+                .copied()
+                .unwrap_or_default();
+            let max = sequence_numbers
+                .values()
+                .iter()
+                .max()
+                .copied()
+                .unwrap_or_default();
+            writer.write(&batch, min, max).await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::ConfigInvalidSnafu;
+use crate::spec::DataFileMeta;
+
+/// The data files of the levels of the LSM tree of a bucket of primary key tables.
+///
+/// Each level 0 file is a sorted run of its own, ordered from the newest one, while the files
+/// of each level above 0 are a sorted run of non-overlapping keys, and higher levels are older.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/Levels.java>
+#[derive(Debug, Clone)]
+pub(crate) struct Levels {
+    level0: Vec<DataFileMeta>,
+    /// The files of the levels from level 1.
+    levels: Vec<Vec<DataFileMeta>>,
+}
+
+/// A sorted run and its level.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LevelSortedRun {
+    pub(crate) level: i32,
+    pub(crate) files: Vec<DataFileMeta>,
+}
+
+impl LevelSortedRun {
+    /// Get the total size in bytes of the files.
+    pub(crate) fn total_size(&self) -> i64 {
+        self.files.iter().map(|file| file.file_size).sum()
+    }
+}
+
+impl Levels {
+    /// Restore the levels from the data files, which must be below `num_levels`.
+    pub(crate) fn new(num_levels: i32, files: Vec<DataFileMeta>) -> crate::Result<Self> {
+        let mut levels = Self {
+            level0: vec![],
+            levels: vec![vec![]; num_levels.max(2) as usize - 1],
+        };
+        if let Some(file) = files
+            .iter()
+            .find(|file| file.level < 0 || file.level >= levels.number_of_levels())
+        {
+            return ConfigInvalidSnafu {
+                message: format!(
+                    "Data file {} of level {} is out of num-levels {}",
+                    file.file_name, file.level, num_levels
+                ),
+            }
+            .fail();
+        }
+        levels.update(&[], &files);
+        Ok(levels)
+    }
+
+    /// Get the number of levels including level 0.
+    #[inline]
+    pub(crate) fn number_of_levels(&self) -> i32 {
+        self.levels.len() as i32 + 1
+    }
+
+    /// Add a new file flushed into level 0.
+    pub(crate) fn add_level0_file(&mut self, file: DataFileMeta) {
+        self.update(&[], &[file]);
+    }
+
+    /// Get the highest level with files, or -1 if there is no file.
+    pub(crate) fn non_empty_highest_level(&self) -> i32 {
+        match self.levels.iter().rposition(|files| !files.is_empty()) {
+            Some(index) => index as i32 + 1,
+            None if self.level0.is_empty() => -1,
+            None => 0,
+        }
+    }
+
+    /// Get the sorted runs from the newest one, the level 0 files and then the other levels.
+    pub(crate) fn level_sorted_runs(&self) -> Vec<LevelSortedRun> {
+        self.level0
+            .iter()
+            .map(|file| LevelSortedRun {
+                level: 0,
+                files: vec![file.clone()],
+            })
+            .chain(
+                self.levels
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, files)| !files.is_empty())
+                    .map(|(index, files)| LevelSortedRun {
+                        level: index as i32 + 1,
+                        files: files.clone(),
+                    }),
+            )
+            .collect()
+    }
+
+    /// Replace the files compacted by the files produced by the compaction.
+    pub(crate) fn update(&mut self, before: &[DataFileMeta], after: &[DataFileMeta]) {
+        for file in before {
+            let files = self.files_mut(file.level);
+            files.retain(|f| f.file_name != file.file_name);
+        }
+        for file in after {
+            self.files_mut(file.level).push(file.clone());
+        }
+        // the newest level 0 files first
+        self.level0.sort_by(|a, b| {
+            b.max_sequence_number
+                .cmp(&a.max_sequence_number)
+                .then(a.min_sequence_number.cmp(&b.min_sequence_number))
+                .then(a.file_name.cmp(&b.file_name))
+        });
+    }
+
+    fn files_mut(&mut self, level: i32) -> &mut Vec<DataFileMeta> {
+        match level {
+            0 => &mut self.level0,
+            level => &mut self.levels[level as usize - 1],
+        }
+    }
+}
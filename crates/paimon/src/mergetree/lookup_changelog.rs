@@ -0,0 +1,185 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::to_binary_row;
+use crate::mergetree::MergeTreeReader;
+use crate::spec::{BinaryRow, DataField, DataFileMeta, DataType, RowKind};
+use crate::table::DataSplit;
+use arrow_array::{Array, ArrayRef, RecordBatch, UInt32Array};
+use arrow_ord::sort::{lexsort_to_indices, SortColumn};
+use arrow_select::concat::concat_batches;
+use arrow_select::take::take_record_batch;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+/// Producer of the changelog of the `lookup` changelog producer.
+///
+/// The merged values of the written keys are looked up in the files of the bucket before and
+/// after the write: new keys are inserts, changed keys are updates from the values before to
+/// the values after, and removed keys are deletes of the values before.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/LookupChangelogMergeFunctionWrapper.java>
+#[derive(Debug)]
+pub(crate) struct LookupChangelog {
+    reader: MergeTreeReader,
+    partition: BinaryRow,
+    bucket: i32,
+    bucket_path: String,
+    /// The data files of the bucket before the write.
+    restored_files: Vec<DataFileMeta>,
+    /// Whether updates with equal values before and after are skipped.
+    row_deduplicate: bool,
+}
+
+impl LookupChangelog {
+    /// The reader merges the value fields of the table, which must contain the primary keys.
+    pub(crate) fn new(
+        reader: MergeTreeReader,
+        partition: BinaryRow,
+        bucket: i32,
+        bucket_path: impl Into<String>,
+        restored_files: Vec<DataFileMeta>,
+        row_deduplicate: bool,
+    ) -> Self {
+        Self {
+            reader,
+            partition,
+            bucket,
+            bucket_path: bucket_path.into(),
+            restored_files,
+            row_deduplicate,
+        }
+    }
+
+    /// Get the changelog of the written keys as the rows of the value fields with their row
+    /// kinds, sorted by key.
+    ///
+    /// The keys are the serialized binary rows of the key fields at the given positions of the
+    /// value fields.
+    pub(crate) async fn changelog(
+        &self,
+        new_files: &[DataFileMeta],
+        written_keys: &HashSet<Vec<u8>>,
+        key_fields: &[DataField],
+        key_indices: &[usize],
+    ) -> crate::Result<(RecordBatch, Vec<RowKind>)> {
+        let read = |files: Vec<DataFileMeta>| async move {
+            let split = DataSplit::builder()
+                .snapshot_id(0)
+                .partition(self.partition.clone())
+                .bucket(self.bucket)
+                .bucket_path(self.bucket_path.clone())
+                .data_files(files)
+                .build();
+            let batches = self
+                .reader
+                .read(&split)
+                .await?
+                .collect::<crate::Result<Vec<_>>>()?;
+            Ok::<_, crate::Error>(concat_batches(&self.reader.read_schema(), &batches)?)
+        };
+        let before = read(self.restored_files.clone()).await?;
+        let after = read([&self.restored_files[..], new_files].concat()).await?;
+
+        let key_types: Vec<&DataType> = key_fields.iter().map(DataField::data_type).collect();
+        let written_rows = |batch: &RecordBatch| {
+            let key_columns: Vec<&dyn Array> = key_indices
+                .iter()
+                .map(|index| batch.column(*index).as_ref())
+                .collect();
+            let mut rows = HashMap::new();
+            for row in 0..batch.num_rows() {
+                let key = to_binary_row(&key_columns, &key_types, row)?.to_serialized_bytes();
+                if written_keys.contains(&key) {
+                    rows.insert(key, row);
+                }
+            }
+            Ok::<_, crate::Error>(rows)
+        };
+        let before_rows = written_rows(&before)?;
+        let after_rows = written_rows(&after)?;
+
+        // the rows are taken from the concatenation of the rows before and after
+        let offset = before.num_rows() as u32;
+        let mut indices = Vec::new();
+        let mut row_kinds = Vec::new();
+        for (key, before_row) in &before_rows {
+            match after_rows.get(key) {
+                Some(after_row) => {
+                    if self.row_deduplicate
+                        && self.equals(&before, *before_row, &after, *after_row)?
+                    {
+                        continue;
+                    }
+                    indices.extend([*before_row as u32, offset + *after_row as u32]);
+                    row_kinds.extend([RowKind::UpdateBefore, RowKind::UpdateAfter]);
+                }
+                None => {
+                    indices.push(*before_row as u32);
+                    row_kinds.push(RowKind::Delete);
+                }
+            }
+        }
+        for (key, after_row) in &after_rows {
+            if !before_rows.contains_key(key) {
+                indices.push(offset + *after_row as u32);
+                row_kinds.push(RowKind::Insert);
+            }
+        }
+        let changelog = take_record_batch(
+            &concat_batches(&before.schema(), [&before, &after])?,
+            &UInt32Array::from(indices),
+        )?;
+
+        // sort by key, the updates before are followed by their updates after
+        let ordinals: ArrayRef = Arc::new(UInt32Array::from_iter_values(
+            0..changelog.num_rows() as u32,
+        ));
+        let sort_columns: Vec<SortColumn> = key_indices
+            .iter()
+            .map(|index| changelog.column(*index).clone())
+            .chain([ordinals])
+            .map(|values| SortColumn {
+                values,
+                options: None,
+            })
+            .collect();
+        let sorted = lexsort_to_indices(&sort_columns, None)?;
+        let row_kinds = sorted
+            .values()
+            .iter()
+            .map(|i| row_kinds[*i as usize])
+            .collect();
+        Ok((take_record_batch(&changelog, &sorted)?, row_kinds))
+    }
+
+    fn equals(
+        &self,
+        before: &RecordBatch,
+        before_row: usize,
+        after: &RecordBatch,
+        after_row: usize,
+    ) -> crate::Result<bool> {
+        let fields = self.reader.read_fields();
+        let data_types: Vec<&DataType> = fields.iter().map(DataField::data_type).collect();
+        let value = |batch: &RecordBatch, row: usize| {
+            let columns: Vec<&dyn Array> = batch.columns().iter().map(|c| c.as_ref()).collect();
+            to_binary_row(&columns, &data_types, row)
+        };
+        Ok(value(before, before_row)? == value(after, after_row)?)
+    }
+}
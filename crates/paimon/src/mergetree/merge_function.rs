@@ -0,0 +1,169 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::ConfigInvalidSnafu;
+use crate::mergetree::aggregate::AggregateMergeFunction;
+use crate::mergetree::deduplicate::DeduplicateMergeFunction;
+use crate::mergetree::first_row::FirstRowMergeFunction;
+use crate::mergetree::partial_update::PartialUpdateMergeFunction;
+use crate::spec::{CoreOptions, DataField, MergeEngine, RowKind, TableSchema};
+use arrow_array::cast::AsArray;
+use arrow_array::types::Int8Type;
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_ord::sort::{lexsort_to_indices, SortColumn};
+use arrow_select::take::take_record_batch;
+use std::fmt::Debug;
+use std::ops::Range;
+
+/// Key values in the layout of data files of primary key tables, the key columns, the sequence
+/// number, the value kind and then the value columns.
+#[derive(Debug, Clone)]
+pub(crate) struct KeyValues {
+    batch: RecordBatch,
+    key_arity: usize,
+}
+
+impl KeyValues {
+    pub(crate) fn new(batch: RecordBatch, key_arity: usize) -> Self {
+        Self { batch, key_arity }
+    }
+
+    pub(crate) fn num_rows(&self) -> usize {
+        self.batch.num_rows()
+    }
+
+    pub(crate) fn key_columns(&self) -> &[ArrayRef] {
+        &self.batch.columns()[..self.key_arity]
+    }
+
+    pub(crate) fn row_kind(&self, index: usize) -> crate::Result<RowKind> {
+        let kinds = self
+            .batch
+            .column(self.key_arity + 1)
+            .as_primitive::<Int8Type>();
+        RowKind::from_value(kinds.value(index))
+    }
+
+    pub(crate) fn value_column(&self, index: usize) -> &ArrayRef {
+        self.batch.column(self.key_arity + 2 + index)
+    }
+
+    /// Get the value columns.
+    pub(crate) fn values(&self) -> crate::Result<RecordBatch> {
+        let indices: Vec<usize> = (self.key_arity + 2..self.batch.num_columns()).collect();
+        Ok(self.batch.project(&indices)?)
+    }
+}
+
+/// Function to merge the key values of the same key.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/MergeFunction.java>
+pub(crate) trait MergeFunction: Debug + Send + Sync {
+    /// Get the value fields to merge, which start with the fields to read.
+    fn value_fields(&self) -> &[DataField];
+
+    /// Merge the key values of each range into the values of the key, the key values in a range
+    /// are of the same key and sorted by the sequence fields and sequence number.
+    ///
+    /// Keys whose merged value is deleted are not in the result.
+    fn merge(&self, kvs: &KeyValues, ranges: &[Range<usize>]) -> crate::Result<RecordBatch>;
+}
+
+/// Get the fields of `sequence.field` of the table, which order the records of the same key
+/// before the sequence number.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/UserDefinedSeqComparator.java>
+pub(crate) fn sequence_fields(table_schema: &TableSchema) -> crate::Result<Vec<DataField>> {
+    let options = CoreOptions::new(table_schema.options());
+    let names = options.sequence_field();
+    if !names.is_empty() && options.merge_engine()? == MergeEngine::FirstRow {
+        return ConfigInvalidSnafu {
+            message: "Do not support use sequence field on first-row merge engine".to_string(),
+        }
+        .fail();
+    }
+    names
+        .into_iter()
+        .map(|name| {
+            table_schema
+                .fields()
+                .iter()
+                .find(|f| f.name() == name)
+                .cloned()
+                .ok_or_else(|| {
+                    ConfigInvalidSnafu {
+                        message: format!(
+                            "Sequence field: '{name}' can not be found in table schema"
+                        ),
+                    }
+                    .build()
+                })
+        })
+        .collect()
+}
+
+/// Sort the key values by key, the value columns at `sequence_indices` and then the sequence
+/// number, nulls first.
+pub(crate) fn sort_key_values(
+    batch: &RecordBatch,
+    key_arity: usize,
+    sequence_indices: &[usize],
+) -> crate::Result<RecordBatch> {
+    let sort_columns: Vec<SortColumn> = sort_indices(key_arity, sequence_indices)
+        .into_iter()
+        .map(|index| SortColumn {
+            values: batch.column(index).clone(),
+            options: None,
+        })
+        .collect();
+    let indices = lexsort_to_indices(&sort_columns, None)?;
+    Ok(take_record_batch(batch, &indices)?)
+}
+
+/// Indices of the columns of the key values to sort by, see [`sort_key_values`].
+pub(crate) fn sort_indices(key_arity: usize, sequence_indices: &[usize]) -> Vec<usize> {
+    (0..key_arity)
+        .chain(sequence_indices.iter().map(|index| key_arity + 2 + index))
+        .chain([key_arity])
+        .collect()
+}
+
+/// Create the merge function of the merge engine of the table to read the fields.
+pub(crate) fn merge_function(
+    table_schema: &TableSchema,
+    read_fields: &[DataField],
+) -> crate::Result<Box<dyn MergeFunction>> {
+    let options = CoreOptions::new(table_schema.options());
+    match options.merge_engine()? {
+        MergeEngine::Deduplicate => Ok(Box::new(DeduplicateMergeFunction::new(
+            read_fields.to_vec(),
+            options.ignore_delete(),
+        ))),
+        MergeEngine::Aggregation => Ok(Box::new(AggregateMergeFunction::new(
+            table_schema,
+            read_fields,
+        )?)),
+        MergeEngine::FirstRow => Ok(Box::new(FirstRowMergeFunction::new(
+            read_fields.to_vec(),
+            options.ignore_delete(),
+        ))),
+        MergeEngine::PartialUpdate => Ok(Box::new(PartialUpdateMergeFunction::new(
+            table_schema,
+            read_fields,
+        )?)),
+    }
+}
@@ -0,0 +1,540 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::format::{to_arrow_schema, ArrowRecordBatchIter, DataFileReader, DEFAULT_BATCH_SIZE};
+use crate::io::FileIO;
+use crate::memory::MemoryPool;
+use crate::mergetree::{
+    merge_function, sequence_fields, KeyValues, MergeFunction, SortedRunsMerger,
+};
+use crate::spec::{key_value_fields, DataField, DataFileMeta, RowKind, TableSchema};
+use crate::table::{DataSplit, RowKindRecordBatchIter};
+use crate::utils::SchemaManager;
+use arrow_array::RecordBatch;
+use arrow_ord::partition::partition;
+use arrow_row::{RowConverter, Rows, SortField};
+use arrow_schema::SchemaRef;
+use arrow_select::concat::concat_batches;
+use arrow_select::interleave::interleave;
+use std::cmp::Ordering;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Reader of the data files of a bucket of primary key tables, the key values of all the
+/// sorted runs are merge sorted by key, `sequence.field` and sequence number, and then merged
+/// by the merge engine of the table.
+///
+/// The data files are sorted, so they are merged by a `SortedRunsMerger` holding a batch of
+/// each file in memory at a time.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/MergeTreeReaders.java>
+#[derive(Debug, Clone)]
+pub struct MergeTreeReader {
+    data_file_reader: DataFileReader,
+    key_arity: usize,
+    read_fields: Vec<DataField>,
+    merge_function: Arc<dyn MergeFunction>,
+    /// Positions of the sequence fields in the value fields.
+    sequence_indices: Vec<usize>,
+    /// Positions of the primary key fields in the read fields, if they are all read.
+    primary_key_indices: Option<Vec<usize>>,
+    batch_size: usize,
+}
+
+impl MergeTreeReader {
+    pub fn new(
+        file_io: Arc<dyn FileIO>,
+        schema_manager: SchemaManager,
+        table_schema: TableSchema,
+        read_fields: Vec<DataField>,
+    ) -> crate::Result<Self> {
+        let key_fields = table_schema.trimmed_primary_key_fields();
+        let primary_key_indices = table_schema
+            .primary_keys()
+            .iter()
+            .map(|name| read_fields.iter().position(|f| f.name() == name))
+            .collect();
+        // the sequence fields are merged along with the read fields to order the key values
+        let sequence_fields = sequence_fields(&table_schema)?;
+        let mut merge_fields = read_fields.clone();
+        for field in &sequence_fields {
+            if !merge_fields.iter().any(|f| f.id() == field.id()) {
+                merge_fields.push(field.clone());
+            }
+        }
+        let merge_function = merge_function(&table_schema, &merge_fields)?;
+        let sequence_indices = sequence_fields
+            .iter()
+            .filter_map(|field| {
+                merge_function
+                    .value_fields()
+                    .iter()
+                    .position(|f| f.id() == field.id())
+            })
+            .collect();
+        let data_file_reader = DataFileReader::new(
+            file_io,
+            schema_manager,
+            table_schema,
+            key_value_fields(&key_fields, merge_function.value_fields()),
+        );
+        Ok(Self {
+            data_file_reader,
+            key_arity: key_fields.len(),
+            read_fields,
+            merge_function: merge_function.into(),
+            sequence_indices,
+            primary_key_indices,
+            batch_size: DEFAULT_BATCH_SIZE,
+        })
+    }
+
+    /// Set the max number of rows of the record batches read.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Reserve the size of each data file read from the memory pool, see
+    /// [`DataFileReader::with_memory_pool`].
+    pub fn with_memory_pool(mut self, memory_pool: Arc<MemoryPool>) -> Self {
+        self.data_file_reader = self.data_file_reader.with_memory_pool(memory_pool);
+        self
+    }
+
+    /// Get the fields of the record batches read.
+    pub(crate) fn read_fields(&self) -> &[DataField] {
+        &self.read_fields
+    }
+
+    /// Get the arrow schema of the record batches read.
+    pub fn read_schema(&self) -> SchemaRef {
+        Arc::new(to_arrow_schema(&self.read_fields))
+    }
+
+    /// Read the merged rows of the split, sorted by key.
+    pub async fn read(&self, split: &DataSplit) -> crate::Result<ArrowRecordBatchIter> {
+        let mut chunks = self.read_key_values(split).await?;
+        let reader = self.clone();
+        let batch_size = self.batch_size.max(1);
+        // the merged rows of the current chunk and the offset of the rows not returned yet
+        let mut current: Option<(RecordBatch, usize)> = None;
+        Ok(Box::new(std::iter::from_fn(move || loop {
+            if let Some((merged, offset)) = &mut current {
+                if *offset < merged.num_rows() {
+                    let len = batch_size.min(merged.num_rows() - *offset);
+                    let batch = merged.slice(*offset, len);
+                    *offset += len;
+                    return Some(Ok(batch));
+                }
+            }
+            let merged = chunks.next_chunk().and_then(|kvs| {
+                kvs.map(|kvs| reader.merge(&kvs, &reader.key_ranges(&kvs)?))
+                    .transpose()
+            });
+            match merged {
+                Ok(Some(merged)) => current = Some((merged, 0)),
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        })))
+    }
+
+    /// Read the changes from the merged rows of the before files of the split to the merged
+    /// rows of its data files in the order of keys, which are compared by all the read fields:
+    /// keys only in the before files are deleted, new keys and keys of changed rows are
+    /// inserted, and unchanged keys are dropped.
+    ///
+    /// The primary keys must be read to match the rows.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/DiffReader.java>
+    pub async fn read_diff(&self, split: &DataSplit) -> crate::Result<RowKindRecordBatchIter> {
+        let Some(key_indices) = &self.primary_key_indices else {
+            return ConfigInvalidSnafu {
+                message: "The primary keys must be read to diff the rows".to_string(),
+            }
+            .fail();
+        };
+        let before = self.read_merged(split, split.before_files()).await?;
+        let after = self.read_merged(split, split.data_files()).await?;
+
+        let sort_fields: Vec<SortField> = before
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| SortField::new(field.data_type().clone()))
+            .collect();
+        let key_converter = RowConverter::new(
+            key_indices
+                .iter()
+                .map(|i| sort_fields[*i].clone())
+                .collect(),
+        )?;
+        let value_converter = RowConverter::new(sort_fields)?;
+        let keys = |batch: &RecordBatch| {
+            let columns: Vec<_> = key_indices
+                .iter()
+                .map(|i| batch.column(*i).clone())
+                .collect();
+            key_converter.convert_columns(&columns)
+        };
+        let (before_keys, after_keys) = (keys(&before)?, keys(&after)?);
+        let before_values = value_converter.convert_columns(before.columns())?;
+        let after_values = value_converter.convert_columns(after.columns())?;
+
+        // merge join the keys sorted by their rows, (0, i) is the row i of the before rows and
+        // (1, i) is the row i of the after rows
+        let mut before_rows = sorted_indices(&before_keys).into_iter().peekable();
+        let mut after_rows = sorted_indices(&after_keys).into_iter().peekable();
+        let mut indices = Vec::new();
+        let mut row_kinds = Vec::new();
+        loop {
+            let ordering = match (before_rows.peek(), after_rows.peek()) {
+                (Some(&i), Some(&j)) => before_keys.row(i).cmp(&after_keys.row(j)),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match ordering {
+                Ordering::Less => {
+                    indices.push((0, before_rows.next().unwrap()));
+                    row_kinds.push(RowKind::Delete);
+                }
+                Ordering::Greater => {
+                    indices.push((1, after_rows.next().unwrap()));
+                    row_kinds.push(RowKind::Insert);
+                }
+                Ordering::Equal => {
+                    let (i, j) = (before_rows.next().unwrap(), after_rows.next().unwrap());
+                    if before_values.row(i) != after_values.row(j) {
+                        indices.push((1, j));
+                        row_kinds.push(RowKind::Insert);
+                    }
+                }
+            }
+        }
+        let columns = before
+            .columns()
+            .iter()
+            .zip(after.columns())
+            .map(|(b, a)| interleave(&[b.as_ref(), a.as_ref()], &indices))
+            .collect::<Result<Vec<_>, _>>()?;
+        let diff = RecordBatch::try_new(before.schema(), columns)?;
+
+        let batch_size = self.batch_size.max(1);
+        let batches: Vec<_> = (0..diff.num_rows())
+            .step_by(batch_size)
+            .map(|offset| {
+                let len = batch_size.min(diff.num_rows() - offset);
+                Ok((
+                    diff.slice(offset, len),
+                    row_kinds[offset..offset + len].to_vec(),
+                ))
+            })
+            .collect();
+        Ok(Box::new(batches.into_iter()))
+    }
+
+    /// Read and merge the key values of the files in the bucket of the split.
+    async fn read_merged(
+        &self,
+        split: &DataSplit,
+        files: &[DataFileMeta],
+    ) -> crate::Result<RecordBatch> {
+        let mut chunks = self.read_files(split, files).await?;
+        let mut batches = Vec::new();
+        while let Some(kvs) = chunks.next_chunk()? {
+            batches.push(self.merge(&kvs, &self.key_ranges(&kvs)?)?);
+        }
+        Ok(concat_batches(&self.read_schema(), &batches)?)
+    }
+
+    /// Read the key values of the files of the split in chunks, sorted by key, the sequence
+    /// fields and sequence number.
+    pub(crate) async fn read_key_values(
+        &self,
+        split: &DataSplit,
+    ) -> crate::Result<KeyValuesChunks> {
+        self.read_files(split, split.data_files()).await
+    }
+
+    /// Read the key values of the files in the bucket of the split, sorted like
+    /// [`Self::read_key_values`].
+    async fn read_files(
+        &self,
+        split: &DataSplit,
+        files: &[DataFileMeta],
+    ) -> crate::Result<KeyValuesChunks> {
+        let mut runs = Vec::with_capacity(files.len());
+        for file in files {
+            runs.push(
+                self.data_file_reader
+                    .read(&split.data_file_path(file), file)
+                    .await?,
+            );
+        }
+        Ok(KeyValuesChunks {
+            merger: SortedRunsMerger::new(runs, self.key_arity, &self.sequence_indices),
+            key_arity: self.key_arity,
+            pending: None,
+        })
+    }
+
+    /// Get the ranges of the sorted key values of the same keys.
+    pub(crate) fn key_ranges(&self, kvs: &KeyValues) -> crate::Result<Vec<Range<usize>>> {
+        Ok(if kvs.num_rows() == 0 {
+            vec![]
+        } else if self.key_arity == 0 {
+            // all the primary keys are partition keys, the rows of a bucket are of the same key
+            std::iter::once(0..kvs.num_rows()).collect()
+        } else {
+            partition(kvs.key_columns())?.ranges()
+        })
+    }
+
+    /// Merge the key values of each range into the rows of the read fields, keys whose merged
+    /// value is deleted are not in the result.
+    pub(crate) fn merge(
+        &self,
+        kvs: &KeyValues,
+        ranges: &[Range<usize>],
+    ) -> crate::Result<RecordBatch> {
+        let merged = self.merge_function.merge(kvs, ranges)?;
+        // drop the value fields only required by merging
+        Ok(merged.project(&(0..self.read_fields.len()).collect::<Vec<_>>())?)
+    }
+}
+
+/// Chunks of the sorted key values of data files, all the key values of a key are in the same
+/// chunk.
+pub(crate) struct KeyValuesChunks {
+    merger: SortedRunsMerger,
+    key_arity: usize,
+    /// The key values of the last key merged, which may continue in the next merged batch.
+    pending: Option<RecordBatch>,
+}
+
+impl KeyValuesChunks {
+    /// Get the next chunk, `None` if all the key values are read.
+    pub(crate) fn next_chunk(&mut self) -> crate::Result<Option<KeyValues>> {
+        loop {
+            let Some(batch) = self.merger.next_batch()? else {
+                return Ok(self
+                    .pending
+                    .take()
+                    .map(|batch| KeyValues::new(batch, self.key_arity)));
+            };
+            // the rows merged sort after the pending rows
+            let batch = match self.pending.take() {
+                Some(pending) => concat_batches(&batch.schema(), [&pending, &batch])?,
+                None => batch,
+            };
+            let last_key = if self.key_arity == 0 {
+                // all the primary keys are partition keys, the rows are of the same key
+                0
+            } else {
+                let ranges = partition(&batch.columns()[..self.key_arity])?.ranges();
+                ranges.last().map_or(0, |range| range.start)
+            };
+            self.pending = Some(batch.slice(last_key, batch.num_rows() - last_key));
+            if last_key > 0 {
+                return Ok(Some(KeyValues::new(
+                    batch.slice(0, last_key),
+                    self.key_arity,
+                )));
+            }
+        }
+    }
+}
+
+/// Get the indices of the rows in the order of the rows.
+fn sorted_indices(rows: &Rows) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..rows.num_rows()).collect();
+    indices.sort_by(|a, b| rows.row(*a).cmp(&rows.row(*b)));
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{BinaryRow, DataFileMeta, Schema};
+    use crate::test_utils::data_file;
+    use arrow_array::{Array, Int32Array, Int64Array, Int8Array, RecordBatch, StringArray};
+    use bytes::Bytes;
+
+    use parquet::arrow::ArrowWriter;
+
+    #[tokio::test]
+    async fn test_read_merge_tree() {
+        let table_path = "file:/tmp/test_read_merge_tree";
+        let file_io = FileIOBuilder::from_url(table_path)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{table_path}/")).await;
+        let schema_manager = SchemaManager::new(file_io.clone(), table_path);
+        let table_schema = schema_manager
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+                        DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                    ])
+                    .primary_keys(vec!["id".to_string()])
+                    .options([("file.format".to_string(), "parquet".to_string())].into())
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let schema = Arc::new(to_arrow_schema(&key_value_fields(
+            &table_schema.trimmed_primary_key_fields(),
+            table_schema.fields(),
+        )));
+
+        let bucket_path = format!("{table_path}/bucket-0");
+        // (id, sequence number, value kind, name) of the files
+        let files = [
+            (
+                "data-0.parquet",
+                1,
+                vec![(1, 0, 0, "a"), (2, 1, 0, "b"), (3, 2, 0, "c")],
+            ),
+            (
+                "data-1.parquet",
+                0,
+                vec![(1, 4, 2, "a2"), (3, 3, 3, "c"), (4, 5, 0, "d")],
+            ),
+        ];
+        for (name, _, rows) in &files {
+            let ids: Vec<i32> = rows.iter().map(|r| r.0).collect();
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(ids.clone())),
+                    Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.1))),
+                    Arc::new(Int8Array::from_iter_values(rows.iter().map(|r| r.2))),
+                    Arc::new(Int32Array::from(ids)),
+                    Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.3))),
+                ],
+            )
+            .unwrap();
+            let mut bytes = Vec::new();
+            let mut writer = ArrowWriter::try_new(&mut bytes, schema.clone(), None).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+            file_io
+                .new_output(&format!("{bucket_path}/{name}"))
+                .unwrap()
+                .write(Bytes::from(bytes))
+                .await
+                .unwrap();
+        }
+        let split = DataSplit::builder()
+            .snapshot_id(1)
+            .partition(BinaryRow::new(0))
+            .bucket(0)
+            .bucket_path(bucket_path)
+            .data_files(
+                files
+                    .iter()
+                    .map(|(name, level, _)| DataFileMeta {
+                        row_count: 3,
+                        level: *level,
+                        ..data_file(name)
+                    })
+                    .collect(),
+            )
+            .build();
+
+        let reader = MergeTreeReader::new(
+            file_io,
+            schema_manager,
+            table_schema.clone(),
+            vec![table_schema.fields()[1].clone()],
+        )
+        .unwrap()
+        .with_batch_size(2);
+        let batches = reader
+            .read(&split)
+            .await
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 2);
+        assert_eq!(batches[0].schema(), reader.read_schema());
+        assert_eq!(
+            batches[0].column(0).as_ref(),
+            &StringArray::from(vec!["a2", "b"]) as &dyn Array
+        );
+        assert_eq!(
+            batches[1].column(0).as_ref(),
+            &StringArray::from(vec!["d"]) as &dyn Array
+        );
+    }
+
+    #[test]
+    fn test_key_values_chunks() {
+        let schema = Arc::new(arrow_schema::Schema::new(vec![
+            arrow_schema::Field::new("_KEY_id", arrow_schema::DataType::Int32, false),
+            arrow_schema::Field::new("_SEQUENCE_NUMBER", arrow_schema::DataType::Int64, false),
+            arrow_schema::Field::new("_VALUE_KIND", arrow_schema::DataType::Int8, false),
+        ]));
+        let key_values = |rows: &[(i32, i64)]| -> crate::Result<RecordBatch> {
+            Ok(RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.0))),
+                    Arc::new(Int64Array::from_iter_values(rows.iter().map(|r| r.1))),
+                    Arc::new(Int8Array::from(vec![0; rows.len()])),
+                ],
+            )?)
+        };
+        // the key 2 spans the batches of both runs
+        let runs: Vec<ArrowRecordBatchIter> = vec![
+            Box::new(
+                vec![key_values(&[(1, 0), (2, 1)]), key_values(&[(2, 4), (3, 5)])].into_iter(),
+            ),
+            Box::new(vec![key_values(&[(2, 2)]), key_values(&[(2, 3), (3, 6)])].into_iter()),
+        ];
+        let mut chunks = KeyValuesChunks {
+            merger: SortedRunsMerger::new(runs, 1, &[]),
+            key_arity: 1,
+            pending: None,
+        };
+        let mut keys = vec![];
+        while let Some(kvs) = chunks.next_chunk().unwrap() {
+            let ids = kvs.key_columns()[0]
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap()
+                .values()
+                .to_vec();
+            keys.push(ids);
+        }
+        let all: Vec<i32> = keys.iter().flatten().copied().collect();
+        assert_eq!(all, vec![1, 2, 2, 2, 2, 3, 3]);
+        assert!(keys.len() > 1);
+        // all the key values of a key are in the same chunk
+        for (i, chunk) in keys.iter().enumerate() {
+            for other in &keys[i + 1..] {
+                assert!(chunk.iter().all(|id| !other.contains(id)), "{keys:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,295 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::to_binary_row;
+use crate::format::ArrowRecordBatchIter;
+use crate::memory::MemoryReservation;
+use crate::mergetree::{
+    sort_key_values, KeyValueFileWriterFactory, LookupChangelog, MergeTreeCompactManager,
+    SortedRunsMerger, SpilledRun,
+};
+use crate::spec::{DataField, DataFileMeta, DataType, RowKind};
+use crate::table::{CompactIncrement, DataIncrement};
+use arrow_array::{Array, Int64Array, RecordBatch};
+use arrow_select::concat::concat_batches;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+/// Writer of a bucket of primary key tables.
+///
+/// Rows are buffered in memory as key values with increasing sequence numbers. When the
+/// buffer exceeds `write-buffer-size`, it is sorted by key, `sequence.field` and sequence
+/// number and spilled
+/// into new data files of level 0, which are merged with the other files when read.
+///
+/// The buffer is also flushed or spilled when failing to reserve it from the memory pool
+/// shared by the writers.
+///
+/// With a spill directory, the full buffer is sorted and spilled into a local file instead,
+/// and the sorted runs spilled are merged into data files on flush, when preparing commit or
+/// when the spilled files reach the max disk size.
+///
+/// Changelog files are written along with the data files by the changelog producer, and the
+/// sorted runs are compacted on write by the compact manager if any.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/MergeTreeWriter.java>
+#[derive(Debug)]
+pub(crate) struct MergeTreeWriter {
+    factory: KeyValueFileWriterFactory,
+    write_buffer_size: usize,
+    buffer: Vec<RecordBatch>,
+    buffer_size: usize,
+    spill: Option<Box<BufferSpill>>,
+    reservation: Option<Box<MemoryReservation>>,
+    next_sequence_number: i64,
+    new_files: Vec<DataFileMeta>,
+    changelog_producer: ChangelogMode,
+    changelog_files: Vec<DataFileMeta>,
+    /// Serialized keys written, to look up their changelog.
+    written_keys: HashSet<Vec<u8>>,
+    compact_manager: Option<Box<MergeTreeCompactManager>>,
+}
+
+/// Sorted runs of the write buffer of a [`MergeTreeWriter`] spilled into a local directory.
+#[derive(Debug)]
+struct BufferSpill {
+    dir: PathBuf,
+    max_disk_size: u64,
+    runs: Vec<SpilledRun>,
+}
+
+/// How the changelog files of a [`MergeTreeWriter`] are produced.
+#[derive(Debug)]
+pub(crate) enum ChangelogMode {
+    /// No changelog files are written.
+    None,
+    /// The written key values are also written into changelog files.
+    Input,
+    /// The changelog is produced by looking up the written keys before and after the write.
+    Lookup(Box<LookupChangelog>),
+}
+
+impl MergeTreeWriter {
+    pub(crate) fn new(
+        factory: KeyValueFileWriterFactory,
+        write_buffer_size: i64,
+        next_sequence_number: i64,
+    ) -> Self {
+        Self {
+            factory,
+            write_buffer_size: write_buffer_size.max(0) as usize,
+            buffer: vec![],
+            buffer_size: 0,
+            spill: None,
+            reservation: None,
+            next_sequence_number,
+            new_files: vec![],
+            changelog_producer: ChangelogMode::None,
+            changelog_files: vec![],
+            written_keys: HashSet::new(),
+            compact_manager: None,
+        }
+    }
+
+    /// Set the producer of the changelog files.
+    pub(crate) fn with_changelog_producer(mut self, changelog_producer: ChangelogMode) -> Self {
+        self.changelog_producer = changelog_producer;
+        self
+    }
+
+    /// Spill the full buffer into local files in the directory, until reaching the max disk
+    /// size.
+    pub(crate) fn with_spill_dir(mut self, spill_dir: PathBuf, max_disk_size: i64) -> Self {
+        self.spill = Some(Box::new(BufferSpill {
+            dir: spill_dir,
+            max_disk_size: max_disk_size.max(0) as u64,
+            runs: vec![],
+        }));
+        self
+    }
+
+    /// Reserve the memory of the buffer from the memory pool of the reservation.
+    pub(crate) fn with_memory_reservation(mut self, reservation: MemoryReservation) -> Self {
+        self.reservation = Some(Box::new(reservation));
+        self
+    }
+
+    /// Compact the sorted runs on write by the compact manager.
+    pub(crate) fn with_compact_manager(mut self, compact_manager: MergeTreeCompactManager) -> Self {
+        self.compact_manager = Some(Box::new(compact_manager));
+        self
+    }
+
+    /// Write the rows of the value fields with their row kinds.
+    pub(crate) async fn write(
+        &mut self,
+        batch: &RecordBatch,
+        row_kinds: &[RowKind],
+    ) -> crate::Result<()> {
+        let num_rows = batch.num_rows();
+        if num_rows == 0 {
+            return Ok(());
+        }
+        let start = self.next_sequence_number;
+        self.next_sequence_number += num_rows as i64;
+        let key_values = self.factory.key_values(
+            batch,
+            Int64Array::from_iter_values(start..self.next_sequence_number),
+            row_kinds,
+        )?;
+        let size = key_values.get_array_memory_size();
+        self.buffer_size += size;
+        self.buffer.push(key_values);
+        let reserved = match &mut self.reservation {
+            Some(reservation) => reservation.try_grow(size).is_ok(),
+            None => true,
+        };
+        if !reserved || self.buffer_size >= self.write_buffer_size {
+            if self.spill.is_some() {
+                let sorted = self.sort_buffer()?;
+                let spill = self.spill.as_mut().unwrap();
+                spill
+                    .runs
+                    .push(SpilledRun::write(&spill.dir, sorted).await?);
+                let spilled_size: u64 = spill.runs.iter().map(SpilledRun::size).sum();
+                if spilled_size >= spill.max_disk_size {
+                    self.flush().await?;
+                }
+            } else {
+                self.flush().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sort the buffered key values and clear the buffer.
+    fn sort_buffer(&mut self) -> crate::Result<RecordBatch> {
+        let key_values = concat_batches(self.factory.schema(), &self.buffer)?;
+        self.buffer.clear();
+        self.buffer_size = 0;
+        if let Some(reservation) = &mut self.reservation {
+            reservation.free();
+        }
+        sort_key_values(
+            &key_values,
+            self.factory.key_indices().len(),
+            self.factory.sequence_indices(),
+        )
+    }
+
+    /// Sort the buffered key values, merged with the runs spilled, and write them into new
+    /// data files.
+    pub(crate) async fn flush(&mut self) -> crate::Result<()> {
+        let spilled_runs = match &mut self.spill {
+            Some(spill) => std::mem::take(&mut spill.runs),
+            None => vec![],
+        };
+        if self.buffer.is_empty() && spilled_runs.is_empty() {
+            return Ok(());
+        }
+        let mut runs: Vec<ArrowRecordBatchIter> = Vec::with_capacity(spilled_runs.len() + 1);
+        for run in &spilled_runs {
+            runs.push(run.read().await?);
+        }
+        if !self.buffer.is_empty() {
+            runs.push(Box::new(std::iter::once(Ok(self.sort_buffer()?))));
+        }
+        let key_arity = self.factory.key_indices().len();
+        let mut merger = SortedRunsMerger::new(runs, key_arity, self.factory.sequence_indices());
+
+        let mut writer = self.factory.new_data_writer(0);
+        let mut changelog_writer = match &self.changelog_producer {
+            ChangelogMode::Input => Some(self.factory.new_changelog_writer()),
+            _ => None,
+        };
+        while let Some(sorted) = merger.spawn_next_batch().await? {
+            self.factory.write_sorted(&mut writer, &sorted).await?;
+            if let Some(changelog_writer) = &mut changelog_writer {
+                self.factory.write_sorted(changelog_writer, &sorted).await?;
+            }
+            if let ChangelogMode::Lookup(_) = &self.changelog_producer {
+                let key_columns: Vec<&dyn Array> = sorted.columns()[..key_arity]
+                    .iter()
+                    .map(|c| c.as_ref())
+                    .collect();
+                let key_types: Vec<&DataType> = self
+                    .factory
+                    .key_fields()
+                    .iter()
+                    .map(DataField::data_type)
+                    .collect();
+                for row in 0..sorted.num_rows() {
+                    let key = to_binary_row(&key_columns, &key_types, row)?;
+                    self.written_keys.insert(key.to_serialized_bytes());
+                }
+            }
+        }
+        // the spilled files are deleted once merged
+        drop(merger);
+        drop(spilled_runs);
+
+        let files = writer.close().await?;
+        if let Some(compact_manager) = &mut self.compact_manager {
+            for file in &files {
+                compact_manager.add_new_file(file.clone());
+            }
+            compact_manager.finish_compaction(false).await?;
+            compact_manager.trigger_compaction();
+        }
+        self.new_files.extend(files);
+        if let Some(changelog_writer) = changelog_writer {
+            self.changelog_files.extend(changelog_writer.close().await?);
+        }
+        Ok(())
+    }
+
+    /// Spill the buffered key values and get the files written, along with the files changed
+    /// by the compactions on write.
+    pub(crate) async fn prepare_commit(
+        mut self,
+    ) -> crate::Result<(DataIncrement, CompactIncrement)> {
+        self.flush().await?;
+        if let ChangelogMode::Lookup(lookup) = &self.changelog_producer {
+            let (changelog, row_kinds) = lookup
+                .changelog(
+                    &self.new_files,
+                    &self.written_keys,
+                    self.factory.key_fields(),
+                    self.factory.key_indices(),
+                )
+                .await?;
+            // the changelog is of the latest sequence number written
+            let sequence_numbers =
+                Int64Array::from_value(self.next_sequence_number - 1, changelog.num_rows());
+            let key_values = self
+                .factory
+                .key_values(&changelog, sequence_numbers, &row_kinds)?;
+            let files = self.factory.write_changelog_files(&key_values).await?;
+            self.changelog_files.extend(files);
+        }
+        let compact_increment = match &mut self.compact_manager {
+            Some(compact_manager) => compact_manager.prepare_commit().await?,
+            None => CompactIncrement::default(),
+        };
+        let data_increment = DataIncrement {
+            new_files: self.new_files,
+            changelog_files: self.changelog_files,
+            ..Default::default()
+        };
+        Ok((data_increment, compact_increment))
+    }
+}
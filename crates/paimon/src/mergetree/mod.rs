@@ -0,0 +1,57 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Reading and writing of the LSM trees of primary key tables, the key values in the data files
+//! of a bucket are merged by the merge engine of the table.
+
+mod aggregate;
+
+mod compact_manager;
+pub(crate) use compact_manager::*;
+
+mod compact_rewriter;
+pub(crate) use compact_rewriter::*;
+
+mod deduplicate;
+
+mod first_row;
+
+mod key_value_file_writer_factory;
+pub(crate) use key_value_file_writer_factory::*;
+
+mod levels;
+pub(crate) use levels::*;
+
+mod lookup_changelog;
+pub(crate) use lookup_changelog::*;
+
+mod merge_function;
+pub(crate) use merge_function::*;
+
+mod merge_tree_reader;
+pub use merge_tree_reader::*;
+
+mod merge_tree_writer;
+pub(crate) use merge_tree_writer::*;
+
+mod partial_update;
+
+mod spill;
+pub(crate) use spill::*;
+
+mod universal_compaction;
+pub(crate) use universal_compaction::*;
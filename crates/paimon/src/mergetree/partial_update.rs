@@ -0,0 +1,367 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::ConfigInvalidSnafu;
+use crate::format::to_arrow_schema;
+use crate::mergetree::{KeyValues, MergeFunction};
+use crate::spec::{CoreOptions, DataField, RowKind, TableSchema};
+use arrow_array::{Array, ArrayRef, RecordBatch, UInt32Array};
+use arrow_ord::ord::{make_comparator, DynComparator};
+use arrow_schema::SortOptions;
+use arrow_select::take::take;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Merge function of the `partial-update` merge engine, the non-null fields of later records
+/// override the fields of earlier records.
+///
+/// Fields of a sequence group are updated together, also to nulls, only when the sequence field
+/// of the group is not null and not less than the current one, and retract records retract the
+/// fields of the sequence groups.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/PartialUpdateMergeFunction.java>
+#[derive(Debug, Clone)]
+pub(crate) struct PartialUpdateMergeFunction {
+    value_fields: Vec<DataField>,
+    /// Index of the sequence field of the sequence group of each value field.
+    sequence_indices: Vec<Option<usize>>,
+    ignore_delete: bool,
+    remove_record_on_delete: bool,
+}
+
+impl PartialUpdateMergeFunction {
+    pub(crate) fn new(
+        table_schema: &TableSchema,
+        read_fields: &[DataField],
+    ) -> crate::Result<Self> {
+        let options = CoreOptions::new(table_schema.options());
+        let find_field = |name: &str| match table_schema.fields().iter().find(|f| f.name() == name)
+        {
+            Some(field) => Ok(field.clone()),
+            None => ConfigInvalidSnafu {
+                message: format!("field {name} of sequence group can not be found in table schema"),
+            }
+            .fail(),
+        };
+
+        // the sequence field of the sequence group of the fields
+        let mut groups: HashMap<&str, &str> = HashMap::new();
+        for (sequence_field, fields) in options.sequence_groups() {
+            find_field(sequence_field)?;
+            for field in fields.into_iter().chain([sequence_field]) {
+                find_field(field)?;
+                if groups
+                    .insert(field, sequence_field)
+                    .is_some_and(|s| s != sequence_field)
+                {
+                    return ConfigInvalidSnafu {
+                        message: format!("field {field} is defined repeatedly by multiple groups"),
+                    }
+                    .fail();
+                }
+            }
+        }
+
+        let mut value_fields = read_fields.to_vec();
+        for field in read_fields {
+            if let Some(sequence_field) = groups.get(field.name()) {
+                if !value_fields.iter().any(|f| f.name() == *sequence_field) {
+                    value_fields.push(find_field(sequence_field)?);
+                }
+            }
+        }
+        let sequence_indices = value_fields
+            .iter()
+            .map(|field| {
+                groups.get(field.name()).map(|sequence_field| {
+                    value_fields
+                        .iter()
+                        .position(|f| f.name() == *sequence_field)
+                        .expect("sequence fields must be in value fields")
+                })
+            })
+            .collect();
+        Ok(Self {
+            value_fields,
+            sequence_indices,
+            ignore_delete: options.ignore_delete(),
+            remove_record_on_delete: options.partial_update_remove_record_on_delete(),
+        })
+    }
+}
+
+impl MergeFunction for PartialUpdateMergeFunction {
+    fn value_fields(&self) -> &[DataField] {
+        &self.value_fields
+    }
+
+    fn merge(&self, kvs: &KeyValues, ranges: &[Range<usize>]) -> crate::Result<RecordBatch> {
+        let arity = self.value_fields.len();
+        let columns: Vec<&ArrayRef> = (0..arity).map(|i| kvs.value_column(i)).collect();
+        let comparators = (0..arity)
+            .map(|i| {
+                if self.sequence_indices.contains(&Some(i)) {
+                    Ok(Some(make_comparator(
+                        columns[i],
+                        columns[i],
+                        SortOptions::default(),
+                    )?))
+                } else {
+                    Ok(None)
+                }
+            })
+            .collect::<crate::Result<Vec<Option<DynComparator>>>>()?;
+        let has_sequence_group = comparators.iter().any(Option::is_some);
+
+        // the rows of the merged values of the fields
+        let mut indices: Vec<Vec<Option<u32>>> = vec![Vec::with_capacity(ranges.len()); arity];
+        let mut current: Vec<Option<usize>> = vec![None; arity];
+        for range in ranges {
+            current.fill(None);
+            let mut has_value = false;
+            for row in range.clone() {
+                let kind = kvs.row_kind(row)?;
+                if !kind.is_add() {
+                    if self.ignore_delete {
+                        continue;
+                    }
+                    if self.remove_record_on_delete && kind == RowKind::Delete {
+                        current.fill(None);
+                        has_value = false;
+                        continue;
+                    }
+                    if !has_sequence_group {
+                        return ConfigInvalidSnafu {
+                            message: "partial update can not accept delete records by default, \
+                                configure 'ignore-delete' to ignore delete records, or \
+                                'partial-update.remove-record-on-delete' to remove the whole row \
+                                on delete records, or sequence groups to retract partial columns",
+                        }
+                        .fail();
+                    }
+                }
+
+                // whether the sequence groups accept the record, by the sequence fields
+                let accepted: Vec<bool> = comparators
+                    .iter()
+                    .enumerate()
+                    .map(|(i, comparator)| match comparator {
+                        Some(_) if columns[i].is_null(row) => false,
+                        Some(comparator) => match current[i] {
+                            Some(current) => comparator(row, current).is_ge(),
+                            None => true,
+                        },
+                        None => false,
+                    })
+                    .collect();
+                for (i, sequence_index) in self.sequence_indices.iter().enumerate() {
+                    match sequence_index {
+                        None if kind.is_add() && !columns[i].is_null(row) => {
+                            current[i] = Some(row);
+                        }
+                        Some(s) if accepted[*s] => {
+                            current[i] = (kind.is_add() || *s == i).then_some(row);
+                        }
+                        _ => {}
+                    }
+                }
+                has_value |= kind.is_add();
+            }
+            if has_value {
+                for (indices, current) in indices.iter_mut().zip(&current) {
+                    indices.push(current.map(|row| row as u32));
+                }
+            }
+        }
+
+        let arrays = columns
+            .iter()
+            .zip(indices)
+            .map(|(column, indices)| Ok(take(column, &UInt32Array::from(indices), None)?))
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(RecordBatch::try_new(
+            Arc::new(to_arrow_schema(&self.value_fields)),
+            arrays,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Schema;
+    use arrow_array::{Int32Array, Int64Array, Int8Array, StringArray};
+
+    fn table_schema(options: &[(&str, &str)]) -> TableSchema {
+        Schema::builder()
+            .fields(vec![
+                DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+                DataField::new(1, "a".to_string(), "STRING".parse().unwrap()),
+                DataField::new(2, "b".to_string(), "STRING".parse().unwrap()),
+                DataField::new(3, "g".to_string(), "INT".parse().unwrap()),
+            ])
+            .primary_keys(vec!["id".to_string()])
+            .options(
+                options
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            )
+            .build()
+            .to_table_schema(0)
+            .unwrap()
+    }
+
+    type Row<'a> = (i8, Option<&'a str>, Option<&'a str>, Option<i32>);
+
+    /// Merge the rows of (value kind, a, b, g) of the same key.
+    fn merge(
+        options: &[(&str, &str)],
+        read_fields: &[&str],
+        rows: &[Row],
+    ) -> crate::Result<RecordBatch> {
+        let schema = table_schema(options);
+        let read_fields: Vec<_> = read_fields
+            .iter()
+            .map(|name| {
+                schema
+                    .fields()
+                    .iter()
+                    .find(|f| f.name() == *name)
+                    .unwrap()
+                    .clone()
+            })
+            .collect();
+        let function = PartialUpdateMergeFunction::new(&schema, &read_fields)?;
+
+        let mut columns: Vec<(&str, ArrayRef)> = vec![
+            ("_KEY_id", Arc::new(Int32Array::from(vec![1; rows.len()]))),
+            (
+                "_SEQUENCE_NUMBER",
+                Arc::new(Int64Array::from_iter_values(0..rows.len() as i64)),
+            ),
+            (
+                "_VALUE_KIND",
+                Arc::new(Int8Array::from_iter_values(rows.iter().map(|r| r.0))),
+            ),
+        ];
+        for field in function.value_fields() {
+            let column: ArrayRef = match field.name() {
+                "a" => Arc::new(StringArray::from_iter(rows.iter().map(|r| r.1))),
+                "b" => Arc::new(StringArray::from_iter(rows.iter().map(|r| r.2))),
+                _ => Arc::new(Int32Array::from_iter(rows.iter().map(|r| r.3))),
+            };
+            columns.push((field.name(), column));
+        }
+        let kvs = KeyValues::new(RecordBatch::try_from_iter(columns).unwrap(), 1);
+        let ranges: Vec<_> = std::iter::once(0..kvs.num_rows()).collect();
+        function.merge(&kvs, &ranges)
+    }
+
+    #[test]
+    fn test_partial_update() {
+        let rows = [
+            (0, Some("a0"), None, Some(1)),
+            (0, None, Some("b1"), None),
+            (0, Some("a2"), None, None),
+        ];
+        let merged = merge(&[], &["a", "b", "g"], &rows).unwrap();
+        assert_eq!(
+            merged.column(0).as_ref(),
+            &StringArray::from(vec!["a2"]) as &dyn Array
+        );
+        assert_eq!(
+            merged.column(1).as_ref(),
+            &StringArray::from(vec!["b1"]) as &dyn Array
+        );
+        assert_eq!(
+            merged.column(2).as_ref(),
+            &Int32Array::from(vec![1]) as &dyn Array
+        );
+    }
+
+    #[test]
+    fn test_partial_update_with_delete() {
+        let rows = [(0, Some("a0"), None, None), (3, Some("a0"), None, None)];
+        assert!(matches!(
+            merge(&[], &["a"], &rows),
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+
+        let merged = merge(&[("ignore-delete", "true")], &["a"], &rows).unwrap();
+        assert_eq!(merged.num_rows(), 1);
+
+        let options = [("partial-update.remove-record-on-delete", "true")];
+        assert_eq!(merge(&options, &["a"], &rows).unwrap().num_rows(), 0);
+        let rows = [
+            (0, Some("a0"), Some("b0"), None),
+            (3, None, None, None),
+            (0, Some("a2"), None, None),
+        ];
+        let merged = merge(&options, &["a", "b"], &rows).unwrap();
+        assert_eq!(
+            merged.column(0).as_ref(),
+            &StringArray::from(vec!["a2"]) as &dyn Array
+        );
+        assert!(merged.column(1).is_null(0));
+    }
+
+    #[test]
+    fn test_partial_update_with_sequence_group() {
+        let options = [("fields.g.sequence-group", "b")];
+        let rows = [
+            (0, Some("a0"), Some("b0"), Some(2)),
+            // the sequence field is less than the current one, b is not updated
+            (0, Some("a1"), Some("b1"), Some(1)),
+            // the sequence field is null, b is not updated
+            (0, None, Some("b2"), None),
+        ];
+        // the sequence field of b is read for merging but not in the result
+        let merged = merge(&options, &["b", "a"], &rows).unwrap();
+        assert_eq!(merged.num_columns(), 3);
+        assert_eq!(
+            merged.column(0).as_ref(),
+            &StringArray::from(vec!["b0"]) as &dyn Array
+        );
+        assert_eq!(
+            merged.column(1).as_ref(),
+            &StringArray::from(vec!["a1"]) as &dyn Array
+        );
+
+        // retract the fields of the group
+        let rows = [
+            (0, Some("a0"), Some("b0"), Some(1)),
+            (1, Some("a0"), Some("b0"), Some(2)),
+        ];
+        let merged = merge(&options, &["a", "b", "g"], &rows).unwrap();
+        assert_eq!(
+            merged.column(0).as_ref(),
+            &StringArray::from(vec!["a0"]) as &dyn Array
+        );
+        assert!(merged.column(1).is_null(0));
+        assert_eq!(
+            merged.column(2).as_ref(),
+            &Int32Array::from(vec![2]) as &dyn Array
+        );
+
+        assert!(matches!(
+            merge(&[("fields.x.sequence-group", "b")], &["a"], &rows),
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+    }
+}
@@ -0,0 +1,317 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::SpillUnexpectedSnafu;
+use crate::format::{ArrowRecordBatchIter, DEFAULT_BATCH_SIZE};
+use crate::mergetree::sort_key_values;
+use arrow_array::RecordBatch;
+use arrow_ord::ord::make_comparator;
+use arrow_schema::SortOptions;
+use arrow_select::concat::concat_batches;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
+use snafu::ResultExt;
+use std::cmp::Ordering;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+/// A sorted run of key values spilled into a local file, which is deleted when dropped.
+///
+/// The run is written in row groups of [`DEFAULT_BATCH_SIZE`] rows, so that it can be read
+/// back a batch at a time when merged with the other runs. The file is written and opened on
+/// the blocking threads of tokio, and read by [`SortedRunsMerger::spawn_next_batch`].
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/sort/BinaryExternalSortBuffer.java>
+#[derive(Debug)]
+pub(crate) struct SpilledRun {
+    path: PathBuf,
+    size: u64,
+}
+
+impl SpilledRun {
+    /// Spill the sorted key values into a new file in the directory.
+    pub(crate) async fn write(dir: &Path, sorted: RecordBatch) -> crate::Result<Self> {
+        let dir = dir.to_path_buf();
+        spawn_blocking(move || Self::write_blocking(&dir, &sorted)).await
+    }
+
+    fn write_blocking(dir: &Path, sorted: &RecordBatch) -> crate::Result<Self> {
+        std::fs::create_dir_all(dir).context(SpillUnexpectedSnafu {
+            message: format!("Failed to create spill directory {}", dir.display()),
+        })?;
+        let path = dir.join(format!("paimon-spill-{}.parquet", uuid::Uuid::new_v4()));
+        let file = File::create(&path).context(SpillUnexpectedSnafu {
+            message: format!("Failed to create spill file {}", path.display()),
+        })?;
+        // the file is deleted on errors below
+        let mut run = Self { path, size: 0 };
+        let properties = WriterProperties::builder()
+            .set_max_row_group_size(DEFAULT_BATCH_SIZE)
+            .build();
+        let mut writer = ArrowWriter::try_new(file, sorted.schema(), Some(properties))?;
+        writer.write(sorted)?;
+        writer.close()?;
+        run.size = std::fs::metadata(&run.path)
+            .context(SpillUnexpectedSnafu {
+                message: format!("Failed to get status of spill file {}", run.path.display()),
+            })?
+            .len();
+        Ok(run)
+    }
+
+    /// Size in bytes of the spilled file.
+    pub(crate) fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Read the key values back in batches.
+    pub(crate) async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let path = self.path.clone();
+        spawn_blocking(move || Self::read_blocking(&path)).await
+    }
+
+    fn read_blocking(path: &Path) -> crate::Result<ArrowRecordBatchIter> {
+        let file = File::open(path).context(SpillUnexpectedSnafu {
+            message: format!("Failed to open spill file {}", path.display()),
+        })?;
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file)?
+            .with_batch_size(DEFAULT_BATCH_SIZE)
+            .build()?;
+        Ok(Box::new(
+            reader.map(|batch| batch.map_err(crate::Error::from)),
+        ))
+    }
+}
+
+impl Drop for SpilledRun {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// Merge of sorted runs of key values into sorted batches, holding a batch of each run in
+/// memory at a time.
+///
+/// Each merged batch consists of the rows of all runs up to the smallest of the last rows of
+/// the current batches of the runs, so that no row of the runs left sorts before them.
+pub(crate) struct SortedRunsMerger {
+    runs: Vec<(ArrowRecordBatchIter, Option<RecordBatch>)>,
+    key_arity: usize,
+    sequence_indices: Vec<usize>,
+    sort_indices: Vec<usize>,
+}
+
+impl SortedRunsMerger {
+    /// Merge the runs sorted by [`sort_key_values`].
+    pub(crate) fn new(
+        runs: Vec<ArrowRecordBatchIter>,
+        key_arity: usize,
+        sequence_indices: &[usize],
+    ) -> Self {
+        Self {
+            runs: runs.into_iter().map(|run| (run, None)).collect(),
+            key_arity,
+            sequence_indices: sequence_indices.to_vec(),
+            sort_indices: crate::mergetree::sort_indices(key_arity, sequence_indices),
+        }
+    }
+
+    /// Get the next merged batch like [`Self::next_batch`] on a blocking thread, for the runs
+    /// read from spilled files.
+    pub(crate) async fn spawn_next_batch(&mut self) -> crate::Result<Option<RecordBatch>> {
+        let mut merger = Self {
+            runs: std::mem::take(&mut self.runs),
+            key_arity: self.key_arity,
+            sequence_indices: self.sequence_indices.clone(),
+            sort_indices: self.sort_indices.clone(),
+        };
+        let (runs, batch) = spawn_blocking(move || {
+            let batch = merger.next_batch()?;
+            Ok((merger.runs, batch))
+        })
+        .await?;
+        self.runs = runs;
+        Ok(batch)
+    }
+
+    /// Get the next merged batch, `None` if all runs are exhausted.
+    pub(crate) fn next_batch(&mut self) -> crate::Result<Option<RecordBatch>> {
+        // pull the next non-empty batch of the runs consumed, dropping exhausted runs
+        let mut runs = Vec::with_capacity(self.runs.len());
+        for (mut iter, mut current) in std::mem::take(&mut self.runs) {
+            loop {
+                match current {
+                    Some(batch) if batch.num_rows() > 0 => {
+                        runs.push((iter, Some(batch)));
+                        break;
+                    }
+                    _ => match iter.next() {
+                        Some(batch) => current = Some(batch?),
+                        None => break,
+                    },
+                }
+            }
+        }
+        self.runs = runs;
+
+        let batches: Vec<&RecordBatch> = self
+            .runs
+            .iter()
+            .filter_map(|(_, current)| current.as_ref())
+            .collect();
+        match batches.len() {
+            0 => return Ok(None),
+            1 => return Ok(self.runs[0].1.take()),
+            _ => {}
+        }
+        let mut bound = batches[0].slice(batches[0].num_rows() - 1, 1);
+        for batch in &batches[1..] {
+            let last = batch.slice(batch.num_rows() - 1, 1);
+            if self.compare(&last, 0, &bound, 0)? == Ordering::Less {
+                bound = last;
+            }
+        }
+
+        let mut prefixes = Vec::with_capacity(self.runs.len());
+        for index in 0..self.runs.len() {
+            let batch = self.runs[index].1.take().unwrap();
+            // the number of rows not greater than the bound
+            let (mut low, mut high) = (0, batch.num_rows());
+            while low < high {
+                let mid = (low + high) / 2;
+                if self.compare(&batch, mid, &bound, 0)? == Ordering::Greater {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+            prefixes.push(batch.slice(0, low));
+            self.runs[index].1 = Some(batch.slice(low, batch.num_rows() - low));
+        }
+        let merged = concat_batches(&bound.schema(), &prefixes)?;
+        Ok(Some(sort_key_values(
+            &merged,
+            self.key_arity,
+            &self.sequence_indices,
+        )?))
+    }
+
+    /// Compare the rows of the key values by the sort columns, nulls first.
+    fn compare(
+        &self,
+        left: &RecordBatch,
+        left_row: usize,
+        right: &RecordBatch,
+        right_row: usize,
+    ) -> crate::Result<Ordering> {
+        for index in &self.sort_indices {
+            let comparator = make_comparator(
+                left.column(*index),
+                right.column(*index),
+                SortOptions::default(),
+            )?;
+            match comparator(left_row, right_row) {
+                Ordering::Equal => {}
+                ordering => return Ok(ordering),
+            }
+        }
+        Ok(Ordering::Equal)
+    }
+}
+
+/// Run the blocking function on the blocking threads of tokio.
+async fn spawn_blocking<T: Send + 'static>(
+    f: impl FnOnce() -> crate::Result<T> + Send + 'static,
+) -> crate::Result<T> {
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(std::io::Error::other)
+        .context(SpillUnexpectedSnafu {
+            message: "Failed to run spill task",
+        })?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::{Int32Array, Int64Array, Int8Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+    use std::sync::Arc;
+
+    fn key_values(keys: Vec<i32>, sequence_numbers: Vec<i64>) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("_KEY_k", DataType::Int32, false),
+            Field::new("_SEQUENCE_NUMBER", DataType::Int64, false),
+            Field::new("_VALUE_KIND", DataType::Int8, false),
+            Field::new("v", DataType::Utf8, true),
+        ]));
+        let values: Vec<String> = keys.iter().map(|k| format!("v{k}")).collect();
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(Int32Array::from(keys.clone())),
+                Arc::new(Int64Array::from(sequence_numbers)),
+                Arc::new(Int8Array::from(vec![0; keys.len()])),
+                Arc::new(StringArray::from(values)),
+            ],
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_merge_spilled_runs() {
+        let dir = Path::new("/tmp/test_merge_spilled_runs");
+        let first = SpilledRun::write(dir, key_values(vec![1, 3, 5, 7], vec![0, 1, 2, 3]))
+            .await
+            .unwrap();
+        let second = SpilledRun::write(dir, key_values(vec![2, 3, 8], vec![4, 5, 6]))
+            .await
+            .unwrap();
+        assert!(first.size() > 0);
+        let path = first.path.clone();
+
+        let memory = key_values(vec![0, 4, 5], vec![7, 8, 9]);
+        let runs: Vec<ArrowRecordBatchIter> = vec![
+            first.read().await.unwrap(),
+            second.read().await.unwrap(),
+            Box::new(std::iter::once(Ok(memory))),
+        ];
+        let mut merger = SortedRunsMerger::new(runs, 1, &[]);
+        let mut keys = vec![];
+        let mut sequence_numbers = vec![];
+        while let Some(batch) = merger.spawn_next_batch().await.unwrap() {
+            let k = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            let s = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<Int64Array>()
+                .unwrap();
+            keys.extend(k.values().iter().copied());
+            sequence_numbers.extend(s.values().iter().copied());
+        }
+        assert_eq!(keys, vec![0, 1, 2, 3, 3, 4, 5, 5, 7, 8]);
+        assert_eq!(sequence_numbers, vec![7, 0, 4, 1, 5, 8, 2, 9, 3, 6]);
+
+        drop(first);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,204 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::mergetree::LevelSortedRun;
+use crate::spec::DataFileMeta;
+
+/// The files of the sorted runs picked to be compacted into the output level.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct CompactUnit {
+    pub(crate) output_level: i32,
+    pub(crate) files: Vec<DataFileMeta>,
+}
+
+impl CompactUnit {
+    fn from_level_runs(output_level: i32, runs: &[LevelSortedRun]) -> Self {
+        Self {
+            output_level,
+            files: runs.iter().flat_map(|run| run.files.clone()).collect(),
+        }
+    }
+}
+
+/// Universal compaction strategy of the sorted runs of a bucket, picking the newest sorted runs
+/// to compact by the size amplification, the size ratio and the number of the sorted runs.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/UniversalCompaction.java>
+#[derive(Debug, Clone)]
+pub(crate) struct UniversalCompaction {
+    max_size_amp: i32,
+    size_ratio: i32,
+    num_run_compaction_trigger: usize,
+}
+
+impl UniversalCompaction {
+    pub(crate) fn new(
+        max_size_amp: i32,
+        size_ratio: i32,
+        num_run_compaction_trigger: usize,
+    ) -> Self {
+        Self {
+            max_size_amp,
+            size_ratio,
+            num_run_compaction_trigger,
+        }
+    }
+
+    /// Pick the sorted runs ordered from the newest one to compact, none if no compaction is
+    /// needed.
+    pub(crate) fn pick(&self, num_levels: i32, runs: &[LevelSortedRun]) -> Option<CompactUnit> {
+        let max_level = num_levels - 1;
+        if let Some(unit) = self.pick_for_size_amp(max_level, runs) {
+            return Some(unit);
+        }
+        if let Some(unit) = self.pick_for_size_ratio(max_level, runs) {
+            return Some(unit);
+        }
+        if runs.len() > self.num_run_compaction_trigger {
+            // compact the newest runs to reduce the number of runs to the trigger
+            let candidate_count = runs.len() - self.num_run_compaction_trigger + 1;
+            return self.pick_for_size_ratio_of(max_level, runs, candidate_count, true);
+        }
+        None
+    }
+
+    /// Compact all the runs if the size of the newer runs is too large compared to the oldest.
+    fn pick_for_size_amp(&self, max_level: i32, runs: &[LevelSortedRun]) -> Option<CompactUnit> {
+        if runs.len() < self.num_run_compaction_trigger {
+            return None;
+        }
+        let (earliest, candidates) = runs.split_last()?;
+        let candidate_size: i64 = candidates.iter().map(LevelSortedRun::total_size).sum();
+        // size amplification = percentage of additional size
+        if candidate_size * 100 > self.max_size_amp as i64 * earliest.total_size() {
+            return Some(CompactUnit::from_level_runs(max_level, runs));
+        }
+        None
+    }
+
+    fn pick_for_size_ratio(&self, max_level: i32, runs: &[LevelSortedRun]) -> Option<CompactUnit> {
+        if runs.len() < self.num_run_compaction_trigger {
+            return None;
+        }
+        self.pick_for_size_ratio_of(max_level, runs, 1, false)
+    }
+
+    /// Compact the candidate runs with the next runs not much larger than them.
+    fn pick_for_size_ratio_of(
+        &self,
+        max_level: i32,
+        runs: &[LevelSortedRun],
+        mut candidate_count: usize,
+        force_pick: bool,
+    ) -> Option<CompactUnit> {
+        let mut candidate_size: i64 = runs[..candidate_count]
+            .iter()
+            .map(LevelSortedRun::total_size)
+            .sum();
+        for next in &runs[candidate_count..] {
+            if (candidate_size as f64) * (100.0 + self.size_ratio as f64) / 100.0
+                < next.total_size() as f64
+            {
+                break;
+            }
+            candidate_size += next.total_size();
+            candidate_count += 1;
+        }
+        if force_pick || candidate_count > 1 {
+            return Some(Self::create_unit(runs, max_level, candidate_count));
+        }
+        None
+    }
+
+    fn create_unit(runs: &[LevelSortedRun], max_level: i32, mut run_count: usize) -> CompactUnit {
+        let mut output_level = if run_count == runs.len() {
+            max_level
+        } else {
+            // the output level is right above the next run
+            (runs[run_count].level - 1).max(0)
+        };
+        if output_level == 0 {
+            // level 0 files can't be the output, so include the runs until a level above 0
+            for next in &runs[run_count..] {
+                run_count += 1;
+                if next.level != 0 {
+                    output_level = next.level;
+                    break;
+                }
+            }
+        }
+        if run_count == runs.len() {
+            output_level = max_level;
+        }
+        CompactUnit::from_level_runs(output_level, &runs[..run_count])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    fn run(level: i32, size: i64) -> LevelSortedRun {
+        let file = DataFileMeta {
+            file_size: size,
+            level,
+            ..test_utils::data_file(&format!("data-{level}-{size}.parquet"))
+        };
+        LevelSortedRun {
+            level,
+            files: vec![file],
+        }
+    }
+
+    fn pick(compaction: &UniversalCompaction, runs: &[LevelSortedRun]) -> Option<(i32, Vec<i64>)> {
+        compaction.pick(3, runs).map(|unit| {
+            let sizes = unit.files.iter().map(|file| file.file_size).collect();
+            (unit.output_level, sizes)
+        })
+    }
+
+    #[test]
+    fn test_pick() {
+        let compaction = UniversalCompaction::new(25, 1, 3);
+        // too few runs
+        assert_eq!(pick(&compaction, &[run(0, 1), run(2, 100)]), None);
+        // the newer runs are too large compared to the oldest
+        assert_eq!(
+            pick(&compaction, &[run(0, 1), run(0, 30), run(2, 100)]),
+            Some((2, vec![1, 30, 100]))
+        );
+        // the newest run is not smaller than the next run
+        assert_eq!(
+            pick(&compaction, &[run(0, 10), run(0, 10), run(2, 100)]),
+            Some((1, vec![10, 10]))
+        );
+        // no runs of similar sizes
+        assert_eq!(
+            pick(&compaction, &[run(0, 1), run(1, 5), run(2, 100)]),
+            None
+        );
+        // the newest runs are compacted to reduce the number of runs to the trigger
+        assert_eq!(
+            pick(
+                &compaction,
+                &[run(0, 1), run(0, 5), run(1, 20), run(2, 200)]
+            ),
+            Some((1, vec![1, 5, 20]))
+        );
+    }
+}
@@ -0,0 +1,141 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Metrics of the scans, reads and commits of tables, reported to a pluggable
+//! [`MetricsReporter`].
+//!
+//! Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/metrics/MetricRegistry.java>
+
+mod prometheus;
+pub use prometheus::*;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Histogram of the milliseconds spent planning scans.
+pub const SCAN_DURATION: &str = "paimon_scan_duration_ms";
+/// Counter of the manifest files read by scans.
+pub const SCAN_MANIFESTS_READ: &str = "paimon_scan_manifests_read";
+/// Counter of the manifest files skipped by scans by their stats.
+pub const SCAN_MANIFESTS_SKIPPED: &str = "paimon_scan_manifests_skipped";
+/// Counter of the data files pruned by scans by their partitions and buckets.
+pub const SCAN_FILES_PRUNED: &str = "paimon_scan_files_pruned";
+/// Counter of the bytes of the data files read.
+pub const READ_BYTES: &str = "paimon_read_bytes";
+/// Counter of the attempts of committing snapshots, including the retried ones.
+pub const COMMIT_ATTEMPTS: &str = "paimon_commit_attempts";
+/// Histogram of the milliseconds spent committing snapshots.
+pub const COMMIT_DURATION: &str = "paimon_commit_duration_ms";
+
+/// The label of the metrics of a table, whose value is the full name of the table.
+pub const TABLE_LABEL: &str = "table";
+
+/// A reporter receiving the updates of metrics, like [`PrometheusReporter`].
+pub trait MetricsReporter: Send + Sync + Debug {
+    /// Increase the counter of the name and labels by the value.
+    fn increment_counter(&self, name: &str, labels: &[(&str, &str)], value: u64);
+
+    /// Record the value into the histogram of the name and labels.
+    fn record_histogram(&self, name: &str, labels: &[(&str, &str)], value: f64);
+}
+
+/// The metrics of a table labeled by its name, which are dropped without a reporter.
+#[derive(Debug, Clone)]
+pub(crate) struct TableMetrics {
+    reporter: Option<Arc<dyn MetricsReporter>>,
+    table: String,
+}
+
+impl TableMetrics {
+    pub(crate) fn new(reporter: Option<Arc<dyn MetricsReporter>>, table: String) -> Self {
+        Self { reporter, table }
+    }
+
+    pub(crate) fn increment_counter(&self, name: &str, value: u64) {
+        if let Some(reporter) = &self.reporter {
+            reporter.increment_counter(name, &[(TABLE_LABEL, &self.table)], value);
+        }
+    }
+
+    /// Record the milliseconds elapsed since the start into the histogram.
+    pub(crate) fn record_duration(&self, name: &str, start: Instant) {
+        if let Some(reporter) = &self.reporter {
+            let millis = start.elapsed().as_secs_f64() * 1000.0;
+            reporter.record_histogram(name, &[(TABLE_LABEL, &self.table)], millis);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::to_arrow_schema;
+    use crate::spec::{DataField, Schema};
+    use crate::table::Table;
+    use crate::test_utils::{create_table, write_and_commit};
+    use arrow_array::{Int32Array, RecordBatch};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_table_metrics() {
+        let options = HashMap::from([
+            ("file.format".to_string(), "parquet".to_string()),
+            ("bucket".to_string(), "2".to_string()),
+            ("bucket-key".to_string(), "id".to_string()),
+        ]);
+        let schema = Schema::builder()
+            .fields(vec![DataField::new(
+                0,
+                "id".to_string(),
+                "INT".parse().unwrap(),
+            )])
+            .options(options)
+            .build();
+        let reporter = Arc::new(PrometheusReporter::new());
+        let table = create_table("file:/tmp/test_table_metrics", &schema)
+            .await
+            .with_metrics_reporter(reporter.clone());
+
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![Arc::new(Int32Array::from(vec![0, 1, 2, 3]))],
+        )
+        .unwrap();
+        let messages = write_and_commit(&table, &batch).await;
+        assert_eq!(messages.len(), 2);
+
+        let plan = table.new_scan().with_bucket(0).plan().await.unwrap();
+        let read = table.new_read();
+        for split in plan.splits() {
+            read.read(split).await.unwrap().for_each(drop);
+        }
+
+        let labels = [(TABLE_LABEL, "db.t")];
+        assert_eq!(reporter.counter(COMMIT_ATTEMPTS, &labels), Some(1));
+        assert_eq!(reporter.histogram_count(COMMIT_DURATION, &labels), 1);
+        assert_eq!(reporter.histogram_count(SCAN_DURATION, &labels), 1);
+        assert_eq!(reporter.counter(SCAN_MANIFESTS_READ, &labels), Some(1));
+        // the file of the other bucket is pruned
+        assert_eq!(reporter.counter(SCAN_FILES_PRUNED, &labels), Some(1));
+        let file_size = plan.splits()[0].data_files()[0].file_size as u64;
+        assert_eq!(reporter.counter(READ_BYTES, &labels), Some(file_size));
+        assert!(reporter
+            .render()
+            .contains("paimon_commit_attempts{table=\"db.t\"} 1\n"));
+    }
+}
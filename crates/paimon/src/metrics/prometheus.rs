@@ -0,0 +1,202 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::metrics::MetricsReporter;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+
+/// Default upper bounds of the buckets of histograms, in milliseconds.
+pub const DEFAULT_HISTOGRAM_BUCKETS: [f64; 10] = [
+    1.0, 5.0, 10.0, 50.0, 100.0, 500.0, 1000.0, 5000.0, 10000.0, 60000.0,
+];
+
+#[derive(Debug, Default)]
+struct Histogram {
+    /// The counts of the values not greater than the bounds of the buckets.
+    buckets: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    /// Metrics by their names, then their labels rendered.
+    counters: BTreeMap<String, BTreeMap<String, u64>>,
+    histograms: BTreeMap<String, BTreeMap<String, Histogram>>,
+}
+
+/// A reporter aggregating the metrics in memory, rendered in the text format of prometheus to
+/// be served by the scrape endpoint of the application.
+///
+/// Reference: <https://prometheus.io/docs/instrumenting/exposition_formats/#text-based-format>
+#[derive(Debug)]
+pub struct PrometheusReporter {
+    buckets: Vec<f64>,
+    registry: Mutex<Registry>,
+}
+
+impl Default for PrometheusReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PrometheusReporter {
+    pub fn new() -> Self {
+        Self::with_buckets(DEFAULT_HISTOGRAM_BUCKETS.to_vec())
+    }
+
+    /// Create the reporter whose histograms have buckets of the ascending upper bounds.
+    pub fn with_buckets(buckets: Vec<f64>) -> Self {
+        Self {
+            buckets,
+            registry: Mutex::new(Registry::default()),
+        }
+    }
+
+    /// Get the value of the counter, none if it was never incremented.
+    pub fn counter(&self, name: &str, labels: &[(&str, &str)]) -> Option<u64> {
+        let registry = self.registry.lock().unwrap();
+        registry
+            .counters
+            .get(name)?
+            .get(&render_labels(labels))
+            .copied()
+    }
+
+    /// Get the count of the values recorded into the histogram.
+    pub fn histogram_count(&self, name: &str, labels: &[(&str, &str)]) -> u64 {
+        let registry = self.registry.lock().unwrap();
+        registry
+            .histograms
+            .get(name)
+            .and_then(|histograms| histograms.get(&render_labels(labels)))
+            .map_or(0, |histogram| histogram.count)
+    }
+
+    /// Render all metrics in the text format of prometheus.
+    pub fn render(&self) -> String {
+        let registry = self.registry.lock().unwrap();
+        let mut text = String::new();
+        for (name, counters) in &registry.counters {
+            let _ = writeln!(text, "# TYPE {name} counter");
+            for (labels, value) in counters {
+                let _ = writeln!(text, "{name}{{{labels}}} {value}");
+            }
+        }
+        for (name, histograms) in &registry.histograms {
+            let _ = writeln!(text, "# TYPE {name} histogram");
+            for (labels, histogram) in histograms {
+                let separator = if labels.is_empty() { "" } else { "," };
+                for (bound, count) in self.buckets.iter().zip(&histogram.buckets) {
+                    let _ = writeln!(
+                        text,
+                        "{name}_bucket{{{labels}{separator}le=\"{bound}\"}} {count}"
+                    );
+                }
+                let _ = writeln!(
+                    text,
+                    "{name}_bucket{{{labels}{separator}le=\"+Inf\"}} {}",
+                    histogram.count
+                );
+                let _ = writeln!(text, "{name}_sum{{{labels}}} {}", histogram.sum);
+                let _ = writeln!(text, "{name}_count{{{labels}}} {}", histogram.count);
+            }
+        }
+        text
+    }
+}
+
+impl MetricsReporter for PrometheusReporter {
+    fn increment_counter(&self, name: &str, labels: &[(&str, &str)], value: u64) {
+        let mut registry = self.registry.lock().unwrap();
+        *registry
+            .counters
+            .entry(name.to_string())
+            .or_default()
+            .entry(render_labels(labels))
+            .or_default() += value;
+    }
+
+    fn record_histogram(&self, name: &str, labels: &[(&str, &str)], value: f64) {
+        let mut registry = self.registry.lock().unwrap();
+        let histogram = registry
+            .histograms
+            .entry(name.to_string())
+            .or_default()
+            .entry(render_labels(labels))
+            .or_default();
+        histogram.buckets.resize(self.buckets.len(), 0);
+        for (bound, count) in self.buckets.iter().zip(histogram.buckets.iter_mut()) {
+            if value <= *bound {
+                *count += 1;
+            }
+        }
+        histogram.sum += value;
+        histogram.count += 1;
+    }
+}
+
+/// Render the labels like `a="1",b="2"` with the values escaped.
+fn render_labels(labels: &[(&str, &str)]) -> String {
+    let mut text = String::new();
+    for (i, (name, value)) in labels.iter().enumerate() {
+        if i > 0 {
+            text.push(',');
+        }
+        let value = value
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace('\n', "\\n");
+        let _ = write!(text, "{name}=\"{value}\"");
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prometheus_reporter() {
+        let reporter = PrometheusReporter::with_buckets(vec![10.0, 100.0]);
+        let labels = [("table", "default.t")];
+        reporter.increment_counter("reads", &labels, 2);
+        reporter.increment_counter("reads", &labels, 3);
+        reporter.increment_counter("reads", &[("table", "a\"b")], 1);
+        reporter.record_histogram("latency", &labels, 5.0);
+        reporter.record_histogram("latency", &labels, 50.0);
+        reporter.record_histogram("latency", &labels, 500.0);
+
+        assert_eq!(reporter.counter("reads", &labels), Some(5));
+        assert_eq!(reporter.counter("writes", &labels), None);
+        assert_eq!(reporter.histogram_count("latency", &labels), 3);
+        assert_eq!(
+            reporter.render(),
+            "# TYPE reads counter\n\
+             reads{table=\"a\\\"b\"} 1\n\
+             reads{table=\"default.t\"} 5\n\
+             # TYPE latency histogram\n\
+             latency_bucket{table=\"default.t\",le=\"10\"} 1\n\
+             latency_bucket{table=\"default.t\",le=\"100\"} 2\n\
+             latency_bucket{table=\"default.t\",le=\"+Inf\"} 3\n\
+             latency_sum{table=\"default.t\"} 555\n\
+             latency_count{table=\"default.t\"} 3\n"
+        );
+    }
+}
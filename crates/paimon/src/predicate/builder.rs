@@ -0,0 +1,255 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::predicate::{LeafFunction, Predicate};
+use crate::spec::{Datum, RowType};
+
+/// Builder of [`Predicate`]s on the fields of a row type, fields are referred by their indexes.
+///
+/// The builder panics if the index is out of the range of the fields.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/predicate/PredicateBuilder.java>
+#[derive(Debug, Clone)]
+pub struct PredicateBuilder {
+    row_type: RowType,
+}
+
+impl PredicateBuilder {
+    pub fn new(row_type: RowType) -> Self {
+        Self { row_type }
+    }
+
+    /// Get the index of the field with the name.
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.row_type.field_index(name)
+    }
+
+    fn leaf(&self, function: LeafFunction, index: usize, literals: Vec<Datum>) -> Predicate {
+        let field = &self.row_type.fields()[index];
+        Predicate::Leaf {
+            function,
+            index,
+            field_name: field.name().to_string(),
+            data_type: field.data_type().clone(),
+            literals,
+        }
+    }
+
+    pub fn equal(&self, index: usize, literal: Datum) -> Predicate {
+        self.leaf(LeafFunction::Equal, index, vec![literal])
+    }
+
+    pub fn not_equal(&self, index: usize, literal: Datum) -> Predicate {
+        self.leaf(LeafFunction::NotEqual, index, vec![literal])
+    }
+
+    pub fn less_than(&self, index: usize, literal: Datum) -> Predicate {
+        self.leaf(LeafFunction::LessThan, index, vec![literal])
+    }
+
+    pub fn less_or_equal(&self, index: usize, literal: Datum) -> Predicate {
+        self.leaf(LeafFunction::LessOrEqual, index, vec![literal])
+    }
+
+    pub fn greater_than(&self, index: usize, literal: Datum) -> Predicate {
+        self.leaf(LeafFunction::GreaterThan, index, vec![literal])
+    }
+
+    pub fn greater_or_equal(&self, index: usize, literal: Datum) -> Predicate {
+        self.leaf(LeafFunction::GreaterOrEqual, index, vec![literal])
+    }
+
+    pub fn is_null(&self, index: usize) -> Predicate {
+        self.leaf(LeafFunction::IsNull, index, vec![])
+    }
+
+    pub fn is_not_null(&self, index: usize) -> Predicate {
+        self.leaf(LeafFunction::IsNotNull, index, vec![])
+    }
+
+    pub fn is_in(&self, index: usize, literals: Vec<Datum>) -> Predicate {
+        self.leaf(LeafFunction::In, index, literals)
+    }
+
+    pub fn is_not_in(&self, index: usize, literals: Vec<Datum>) -> Predicate {
+        self.leaf(LeafFunction::NotIn, index, literals)
+    }
+
+    pub fn starts_with(&self, index: usize, prefix: impl Into<String>) -> Predicate {
+        self.leaf(
+            LeafFunction::StartsWith,
+            index,
+            vec![Datum::String(prefix.into())],
+        )
+    }
+
+    /// Match values within `[from, to]`.
+    pub fn between(&self, index: usize, from: Datum, to: Datum) -> Predicate {
+        Predicate::And(vec![
+            self.greater_or_equal(index, from),
+            self.less_or_equal(index, to),
+        ])
+    }
+
+    /// Combine the predicates by AND, nested ANDs are flattened.
+    pub fn and(predicates: impl IntoIterator<Item = Predicate>) -> Predicate {
+        let mut children = Vec::new();
+        for predicate in predicates {
+            match predicate {
+                Predicate::And(nested) => children.extend(nested),
+                predicate => children.push(predicate),
+            }
+        }
+        Predicate::And(children)
+    }
+
+    /// Combine the predicates by OR, nested ORs are flattened.
+    pub fn or(predicates: impl IntoIterator<Item = Predicate>) -> Predicate {
+        let mut children = Vec::new();
+        for predicate in predicates {
+            match predicate {
+                Predicate::Or(nested) => children.extend(nested),
+                predicate => children.push(predicate),
+            }
+        }
+        Predicate::Or(children)
+    }
+
+    /// Negate the predicate, which is pushed down to the leaves if possible.
+    pub fn not(predicate: Predicate) -> Predicate {
+        match predicate.negate() {
+            Some(negated) => negated,
+            None => Predicate::Not(Box::new(predicate)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{BinaryRow, BinaryRowWriter, DataField, DataType, IntType, VarCharType};
+
+    fn builder() -> PredicateBuilder {
+        PredicateBuilder::new(RowType::new(vec![
+            DataField::new(0, "id".to_string(), DataType::Int(IntType::new())),
+            DataField::new(
+                1,
+                "name".to_string(),
+                DataType::VarChar(VarCharType::default()),
+            ),
+        ]))
+    }
+
+    fn row(id: impl Into<Option<i32>>, name: Option<&str>) -> BinaryRow {
+        let mut writer = BinaryRowWriter::new(2);
+        match id.into() {
+            Some(id) => writer.write_int(0, id),
+            None => writer.set_null_at(0),
+        }
+        match name {
+            Some(name) => writer.write_string(1, name),
+            None => writer.set_null_at(1),
+        }
+        writer.build()
+    }
+
+    #[test]
+    fn test_row() {
+        let builder = builder();
+        let predicate = PredicateBuilder::and([
+            builder.between(0, Datum::Int(1), Datum::Int(5)),
+            PredicateBuilder::or([builder.starts_with(1, "ab"), builder.is_null(1)]),
+        ]);
+        assert!(matches!(&predicate, Predicate::And(children) if children.len() == 3));
+        assert!(predicate.test(&row(1, Some("abc"))).unwrap());
+        assert!(predicate.test(&row(5, None)).unwrap());
+        assert!(!predicate.test(&row(6, Some("abc"))).unwrap());
+        assert!(!predicate.test(&row(None, Some("abc"))).unwrap());
+        assert!(!predicate.test(&row(3, Some("b"))).unwrap());
+
+        let in_predicate = builder.is_in(0, vec![Datum::Int(1), Datum::Int(3)]);
+        assert!(in_predicate.test(&row(3, None)).unwrap());
+        let not_in = PredicateBuilder::not(in_predicate);
+        assert_eq!(
+            not_in,
+            builder.is_not_in(0, vec![Datum::Int(1), Datum::Int(3)])
+        );
+        assert!(!not_in.test(&row(3, None)).unwrap());
+        assert!(not_in.test(&row(2, None)).unwrap());
+        // null never matches except IS NULL
+        assert!(!not_in.test(&row(None, None)).unwrap());
+
+        let not_starts_with = PredicateBuilder::not(builder.starts_with(1, "ab"));
+        assert!(matches!(not_starts_with, Predicate::Not(_)));
+        assert!(not_starts_with.test(&row(1, Some("b"))).unwrap());
+    }
+
+    fn test_stats(predicate: &Predicate, min: (i32, &str), max: (i32, &str), nulls: i64) -> bool {
+        predicate
+            .test_stats(
+                10,
+                &row(Some(min.0), Some(min.1)),
+                &row(Some(max.0), Some(max.1)),
+                &[Some(nulls), Some(0)],
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_stats_skipping() {
+        let builder = builder();
+        let stats = |p: &Predicate| test_stats(p, (3, "b"), (7, "d"), 2);
+
+        assert!(stats(&builder.equal(0, Datum::Int(3))));
+        assert!(!stats(&builder.equal(0, Datum::Int(8))));
+        assert!(stats(&builder.not_equal(0, Datum::Int(3))));
+        assert!(!test_stats(
+            &builder.not_equal(0, Datum::Int(3)),
+            (3, "b"),
+            (3, "b"),
+            0
+        ));
+        assert!(!stats(&builder.less_than(0, Datum::Int(3))));
+        assert!(stats(&builder.less_or_equal(0, Datum::Int(3))));
+        assert!(!stats(&builder.greater_than(0, Datum::Int(7))));
+        assert!(stats(&builder.greater_or_equal(0, Datum::Int(7))));
+        assert!(stats(&builder.is_null(0)));
+        assert!(!stats(&builder.is_null(1)));
+        assert!(!test_stats(&builder.is_not_null(0), (3, "b"), (7, "d"), 10));
+        assert!(!test_stats(
+            &builder.equal(0, Datum::Int(3)),
+            (3, "b"),
+            (7, "d"),
+            10
+        ));
+        assert!(stats(&builder.is_in(0, vec![Datum::Int(1), Datum::Int(5)])));
+        assert!(!stats(
+            &builder.is_in(0, vec![Datum::Int(1), Datum::Int(9)])
+        ));
+        assert!(stats(&builder.between(0, Datum::Int(0), Datum::Int(3))));
+        assert!(!stats(&builder.between(0, Datum::Int(8), Datum::Int(9))));
+
+        assert!(stats(&builder.starts_with(1, "c")));
+        assert!(stats(&builder.starts_with(1, "ba")));
+        assert!(!stats(&builder.starts_with(1, "e")));
+        assert!(!stats(&builder.starts_with(1, "a")));
+
+        // incomparable literals never prune
+        assert!(stats(&builder.equal(0, Datum::String("x".to_string()))));
+        assert!(stats(&PredicateBuilder::not(builder.starts_with(1, "c"))));
+    }
+}
@@ -0,0 +1,345 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Predicates to filter rows and skip files by statistics.
+
+mod builder;
+pub use builder::*;
+
+mod partition_predicate;
+pub use partition_predicate::*;
+
+use crate::format::to_datum;
+use crate::spec::{BinaryRow, BinaryTableStats, DataType, Datum};
+use arrow_array::{BooleanArray, RecordBatch};
+use std::cmp::Ordering;
+
+/// Function of a [`Predicate::Leaf`], comparing a field with the literals.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/predicate/LeafFunction.java>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LeafFunction {
+    Equal,
+    NotEqual,
+    LessThan,
+    LessOrEqual,
+    GreaterThan,
+    GreaterOrEqual,
+    IsNull,
+    IsNotNull,
+    In,
+    NotIn,
+    StartsWith,
+}
+
+impl LeafFunction {
+    /// Get the function matching exactly the rows not matched by this one, none if there isn't.
+    pub fn negate(&self) -> Option<LeafFunction> {
+        match self {
+            LeafFunction::Equal => Some(LeafFunction::NotEqual),
+            LeafFunction::NotEqual => Some(LeafFunction::Equal),
+            LeafFunction::LessThan => Some(LeafFunction::GreaterOrEqual),
+            LeafFunction::LessOrEqual => Some(LeafFunction::GreaterThan),
+            LeafFunction::GreaterThan => Some(LeafFunction::LessOrEqual),
+            LeafFunction::GreaterOrEqual => Some(LeafFunction::LessThan),
+            LeafFunction::IsNull => Some(LeafFunction::IsNotNull),
+            LeafFunction::IsNotNull => Some(LeafFunction::IsNull),
+            LeafFunction::In => Some(LeafFunction::NotIn),
+            LeafFunction::NotIn => Some(LeafFunction::In),
+            LeafFunction::StartsWith => None,
+        }
+    }
+
+    /// Test the value of a row, null values never match except for [`LeafFunction::IsNull`].
+    fn test(&self, value: Option<&Datum>, literals: &[Datum]) -> bool {
+        let Some(value) = value else {
+            return *self == LeafFunction::IsNull;
+        };
+        let compare = |literal: &Datum| value.partial_cmp(literal);
+        match self {
+            LeafFunction::Equal => compare(&literals[0]) == Some(Ordering::Equal),
+            LeafFunction::NotEqual => {
+                matches!(
+                    compare(&literals[0]),
+                    Some(Ordering::Less | Ordering::Greater)
+                )
+            }
+            LeafFunction::LessThan => compare(&literals[0]) == Some(Ordering::Less),
+            LeafFunction::LessOrEqual => {
+                matches!(
+                    compare(&literals[0]),
+                    Some(Ordering::Less | Ordering::Equal)
+                )
+            }
+            LeafFunction::GreaterThan => compare(&literals[0]) == Some(Ordering::Greater),
+            LeafFunction::GreaterOrEqual => {
+                matches!(
+                    compare(&literals[0]),
+                    Some(Ordering::Greater | Ordering::Equal)
+                )
+            }
+            LeafFunction::IsNull => false,
+            LeafFunction::IsNotNull => true,
+            LeafFunction::In => literals.iter().any(|l| compare(l) == Some(Ordering::Equal)),
+            LeafFunction::NotIn => literals
+                .iter()
+                .all(|l| matches!(compare(l), Some(Ordering::Less | Ordering::Greater))),
+            LeafFunction::StartsWith => match (value, &literals[0]) {
+                (Datum::String(value), Datum::String(prefix)) => value.starts_with(prefix.as_str()),
+                _ => false,
+            },
+        }
+    }
+
+    /// Test whether a file or manifest with the statistics may contain matched rows.
+    ///
+    /// Missing statistics or incomparable values are conservatively treated as matched.
+    fn test_stats(
+        &self,
+        row_count: i64,
+        min: Option<&Datum>,
+        max: Option<&Datum>,
+        null_count: Option<i64>,
+        literals: &[Datum],
+    ) -> bool {
+        match self {
+            LeafFunction::IsNull => return !matches!(null_count, Some(0)),
+            LeafFunction::IsNotNull => return !matches!(null_count, Some(n) if n >= row_count),
+            _ => {}
+        }
+        // other functions never match null values
+        if null_count == Some(row_count) {
+            return false;
+        }
+        let (Some(min), Some(max)) = (min, max) else {
+            return true;
+        };
+        // `None` if incomparable, which never prunes
+        let le = |a: &Datum, b: &Datum| a.partial_cmp(b).map(|o| o != Ordering::Greater);
+        let lt = |a: &Datum, b: &Datum| a.partial_cmp(b).map(|o| o == Ordering::Less);
+        let eq = |a: &Datum, b: &Datum| a.partial_cmp(b).map(|o| o == Ordering::Equal);
+        let in_range = |l: &Datum| Some(le(min, l)? && le(l, max)?);
+        // all values equal to the literal
+        let all_equal = |l: &Datum| Some(eq(min, l)? && eq(max, l)?);
+        let matched = match self {
+            LeafFunction::Equal => in_range(&literals[0]),
+            LeafFunction::NotEqual => all_equal(&literals[0]).map(|all| !all),
+            LeafFunction::LessThan => lt(min, &literals[0]),
+            LeafFunction::LessOrEqual => le(min, &literals[0]),
+            LeafFunction::GreaterThan => lt(&literals[0], max),
+            LeafFunction::GreaterOrEqual => le(&literals[0], max),
+            LeafFunction::In => Some(literals.iter().any(|l| in_range(l).unwrap_or(true))),
+            LeafFunction::NotIn => Some(literals.iter().all(|l| !all_equal(l).unwrap_or(false))),
+            LeafFunction::StartsWith => match (min, max, &literals[0]) {
+                // strings with the prefix are within [prefix, next of prefix)
+                (Datum::String(min), Datum::String(max), Datum::String(prefix)) => Some(
+                    max.as_str() >= prefix.as_str()
+                        && (min.as_str() <= prefix.as_str() || min.starts_with(prefix.as_str())),
+                ),
+                _ => None,
+            },
+            LeafFunction::IsNull | LeafFunction::IsNotNull => unreachable!(),
+        };
+        matched.unwrap_or(true)
+    }
+}
+
+/// A predicate on the fields of a row, which is built by [`PredicateBuilder`].
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/predicate/Predicate.java>
+#[derive(Debug, Clone, PartialEq)]
+pub enum Predicate {
+    /// Compare the field at the index with the literals.
+    Leaf {
+        function: LeafFunction,
+        index: usize,
+        field_name: String,
+        data_type: DataType,
+        literals: Vec<Datum>,
+    },
+    And(Vec<Predicate>),
+    Or(Vec<Predicate>),
+    /// Negation of a predicate which can't be negated by [`Predicate::negate`].
+    Not(Box<Predicate>),
+}
+
+impl Predicate {
+    /// Get the predicate matching exactly the rows not matched by this one, none if there isn't.
+    pub fn negate(&self) -> Option<Predicate> {
+        match self {
+            Predicate::Leaf {
+                function,
+                index,
+                field_name,
+                data_type,
+                literals,
+            } => Some(Predicate::Leaf {
+                function: function.negate()?,
+                index: *index,
+                field_name: field_name.clone(),
+                data_type: data_type.clone(),
+                literals: literals.clone(),
+            }),
+            Predicate::And(children) => Some(Predicate::Or(
+                children.iter().map(|c| c.negate()).collect::<Option<_>>()?,
+            )),
+            Predicate::Or(children) => Some(Predicate::And(
+                children.iter().map(|c| c.negate()).collect::<Option<_>>()?,
+            )),
+            Predicate::Not(child) => Some(child.as_ref().clone()),
+        }
+    }
+
+    /// Test whether the row matches this predicate.
+    pub fn test(&self, row: &BinaryRow) -> crate::Result<bool> {
+        match self {
+            Predicate::Leaf {
+                function,
+                index,
+                data_type,
+                literals,
+                ..
+            } => Ok(function.test(row.get_datum(*index, data_type)?.as_ref(), literals)),
+            Predicate::And(children) => {
+                for child in children {
+                    if !child.test(row)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Predicate::Or(children) => {
+                for child in children {
+                    if child.test(row)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Predicate::Not(child) => Ok(!child.test(row)?),
+        }
+    }
+
+    /// Test which rows of the batch of the fields of the row type match this predicate.
+    pub fn test_batch(&self, batch: &RecordBatch) -> crate::Result<BooleanArray> {
+        (0..batch.num_rows())
+            .map(|row| self.test_batch_row(batch, row).map(Some))
+            .collect()
+    }
+
+    fn test_batch_row(&self, batch: &RecordBatch, row: usize) -> crate::Result<bool> {
+        match self {
+            Predicate::Leaf {
+                function,
+                index,
+                data_type,
+                literals,
+                ..
+            } => {
+                let datum = to_datum(batch.column(*index), row, data_type)?;
+                Ok(function.test(datum.as_ref(), literals))
+            }
+            Predicate::And(children) => {
+                for child in children {
+                    if !child.test_batch_row(batch, row)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Predicate::Or(children) => {
+                for child in children {
+                    if child.test_batch_row(batch, row)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Predicate::Not(child) => Ok(!child.test_batch_row(batch, row)?),
+        }
+    }
+
+    /// Test whether a file or manifest may contain rows matching this predicate by the
+    /// minimum values, maximum values and null counts of the fields.
+    pub fn test_stats(
+        &self,
+        row_count: i64,
+        min_values: &BinaryRow,
+        max_values: &BinaryRow,
+        null_counts: &[Option<i64>],
+    ) -> crate::Result<bool> {
+        match self {
+            Predicate::Leaf {
+                function,
+                index,
+                data_type,
+                literals,
+                ..
+            } => {
+                // stats may be collected only for a part of fields
+                if *index >= min_values.arity() as usize || *index >= max_values.arity() as usize {
+                    return Ok(true);
+                }
+                let min = min_values.get_datum(*index, data_type)?;
+                let max = max_values.get_datum(*index, data_type)?;
+                Ok(function.test_stats(
+                    row_count,
+                    min.as_ref(),
+                    max.as_ref(),
+                    null_counts.get(*index).copied().flatten(),
+                    literals,
+                ))
+            }
+            Predicate::And(children) => {
+                for child in children {
+                    if !child.test_stats(row_count, min_values, max_values, null_counts)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Predicate::Or(children) => {
+                for child in children {
+                    if child.test_stats(row_count, min_values, max_values, null_counts)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            // whether all rows match the child is unknown from the stats
+            Predicate::Not(_) => Ok(true),
+        }
+    }
+
+    /// Test by [`BinaryTableStats`] whose values are serialized [`BinaryRow`]s, empty stats
+    /// are treated as matched.
+    pub fn test_table_stats(
+        &self,
+        row_count: i64,
+        stats: &BinaryTableStats,
+    ) -> crate::Result<bool> {
+        if stats.min_values().is_empty() || stats.max_values().is_empty() {
+            return Ok(true);
+        }
+        self.test_stats(
+            row_count,
+            &BinaryRow::from_serialized_bytes(stats.min_values())?,
+            &BinaryRow::from_serialized_bytes(stats.max_values())?,
+            stats.null_counts(),
+        )
+    }
+}
@@ -0,0 +1,88 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::ConfigInvalidSnafu;
+use crate::predicate::{Predicate, PredicateBuilder};
+use crate::spec::{BinaryRow, Datum, ManifestFileMeta, RowType};
+use std::collections::HashMap;
+
+/// A predicate on the partitions of a table, testing the partitions of the data files and the
+/// partition stats of the manifests to prune them in scans.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/AbstractFileStoreScan.java>
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitionPredicate {
+    predicate: Predicate,
+}
+
+impl PartitionPredicate {
+    /// Create from a predicate on the fields of the partition type of the table.
+    pub fn new(predicate: Predicate) -> Self {
+        Self { predicate }
+    }
+
+    /// Create the predicate matching the partitions whose values equal to the given ones.
+    pub fn from_values(
+        partition_type: &RowType,
+        values: &HashMap<String, Datum>,
+    ) -> crate::Result<Self> {
+        let builder = PredicateBuilder::new(partition_type.clone());
+        let predicates = values
+            .iter()
+            .map(|(name, value)| match builder.index_of(name) {
+                Some(index) => Ok(builder.equal(index, value.clone())),
+                None => ConfigInvalidSnafu {
+                    message: format!("Partition filter on {name} which is not a partition key"),
+                }
+                .fail(),
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Self::new(PredicateBuilder::and(predicates)))
+    }
+
+    /// Create the predicate matching the partitions whose values equal to any of the given ones.
+    pub fn from_partitions(
+        partition_type: &RowType,
+        partitions: &[HashMap<String, Datum>],
+    ) -> crate::Result<Self> {
+        let predicates = partitions
+            .iter()
+            .map(|values| Ok(Self::from_values(partition_type, values)?.predicate))
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(Self::new(PredicateBuilder::or(predicates)))
+    }
+
+    /// Get the predicate on the fields of the partition type.
+    #[inline]
+    pub fn predicate(&self) -> &Predicate {
+        &self.predicate
+    }
+
+    /// Test whether the partition matches this predicate.
+    pub fn test(&self, partition: &BinaryRow) -> crate::Result<bool> {
+        self.predicate.test(partition)
+    }
+
+    /// Test whether the manifest may contain entries of matched partitions by its partition
+    /// stats, whose null counts are the numbers of the entries.
+    pub fn test_manifest(&self, manifest: &ManifestFileMeta) -> crate::Result<bool> {
+        self.predicate.test_table_stats(
+            manifest.num_added_files() + manifest.num_deleted_files(),
+            manifest.partition_stats(),
+        )
+    }
+}
@@ -0,0 +1,570 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::{DataCorruptedSnafu, DataTypeInvalidSnafu};
+use crate::spec::{DataType, Datum};
+use crate::utils::hash_bytes_by_words;
+use crate::Result;
+use std::borrow::Cow;
+
+pub const EMPTY_BINARY_ROW: BinaryRow = BinaryRow {
+    arity: 0,
+    null_bits_size_in_bytes: BinaryRow::cal_bit_set_width_in_bytes(0),
+    data: Cow::Borrowed(&[0; BinaryRow::cal_fix_part_size_in_bytes(0) as usize]),
+};
+
+/// Highest bit of a fixed-length slot, marking that the bytes are stored inline.
+const HIGHEST_FIRST_BIT: u64 = 0x80 << 56;
+/// Bits 2 to 8 of the highest byte of an inline slot, storing the length of the bytes.
+const HIGHEST_SECOND_TO_EIGHTH_BIT: u64 = 0x7F << 56;
+/// Max length of bytes that can be stored inline in a fixed-length slot.
+const MAX_FIX_PART_DATA_SIZE: usize = 7;
+
+/// An implementation of InternalRow backed by bytes.
+///
+/// A row consists of a fixed-length part and a variable-length part. The fixed-length part
+/// holds a header byte, a null bit set and one 8 bytes slot per field. Variable-length values
+/// longer than 7 bytes are stored in the variable-length part, with their offset and length
+/// stored in the slot.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/data/BinaryRow.java>
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct BinaryRow {
+    arity: i32,
+    null_bits_size_in_bytes: i32,
+    data: Cow<'static, [u8]>,
+}
+
+impl BinaryRow {
+    pub const HEADER_SIZE_IN_BYTES: i32 = 8;
+    pub const fn cal_bit_set_width_in_bytes(arity: i32) -> i32 {
+        ((arity + 63 + Self::HEADER_SIZE_IN_BYTES) / 64) * 8
+    }
+    pub const fn cal_fix_part_size_in_bytes(arity: i32) -> i32 {
+        Self::cal_bit_set_width_in_bytes(arity) + 8 * arity
+    }
+
+    /// Create a row with all fields set to zero.
+    pub fn new(arity: i32) -> Self {
+        Self {
+            arity,
+            null_bits_size_in_bytes: Self::cal_bit_set_width_in_bytes(arity),
+            data: Cow::Owned(vec![0; Self::cal_fix_part_size_in_bytes(arity) as usize]),
+        }
+    }
+
+    /// Create a row from the bytes of its fixed-length and variable-length parts.
+    pub fn from_bytes(arity: i32, data: Vec<u8>) -> Result<Self> {
+        if arity < 0 || data.len() < Self::cal_fix_part_size_in_bytes(arity) as usize {
+            return DataCorruptedSnafu {
+                message: format!(
+                    "binary row with arity {} requires at least {} bytes, but got {}",
+                    arity,
+                    Self::cal_fix_part_size_in_bytes(arity.max(0)),
+                    data.len()
+                ),
+            }
+            .fail();
+        }
+        Ok(Self {
+            arity,
+            null_bits_size_in_bytes: Self::cal_bit_set_width_in_bytes(arity),
+            data: Cow::Owned(data),
+        })
+    }
+
+    /// Create a row from bytes serialized by Java, which are the arity as a big-endian int followed by the row.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/utils/SerializationUtils.java>
+    pub fn from_serialized_bytes(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < 4 {
+            return DataCorruptedSnafu {
+                message: format!("serialized binary row is too short: {} bytes", bytes.len()),
+            }
+            .fail();
+        }
+        let arity = i32::from_be_bytes(bytes[..4].try_into().unwrap());
+        Self::from_bytes(arity, bytes[4..].to_vec())
+    }
+
+    /// Serialize the row into bytes compatible with Java, see [`BinaryRow::from_serialized_bytes`].
+    pub fn to_serialized_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(4 + self.data.len());
+        bytes.extend_from_slice(&self.arity.to_be_bytes());
+        bytes.extend_from_slice(&self.data);
+        bytes
+    }
+
+    #[inline]
+    pub fn arity(&self) -> i32 {
+        self.arity
+    }
+
+    /// Get the bytes of the row, without the arity.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get the murmur hash of the bytes of the row, the same as the hash code of paimon-java.
+    pub fn hash_code(&self) -> i32 {
+        hash_bytes_by_words(&self.data)
+    }
+
+    #[inline]
+    fn field_offset(&self, pos: usize) -> usize {
+        self.null_bits_size_in_bytes as usize + 8 * pos
+    }
+
+    fn read<const N: usize>(&self, offset: usize) -> Result<[u8; N]> {
+        match self.data.get(offset..offset + N) {
+            Some(bytes) => Ok(bytes.try_into().unwrap()),
+            None => DataCorruptedSnafu {
+                message: format!(
+                    "binary row reads {} bytes at offset {} out of its size {}",
+                    N,
+                    offset,
+                    self.data.len()
+                ),
+            }
+            .fail(),
+        }
+    }
+
+    fn read_var_bytes(&self, offset: usize, len: usize) -> Result<&[u8]> {
+        match self.data.get(offset..offset + len) {
+            Some(bytes) => Ok(bytes),
+            None => DataCorruptedSnafu {
+                message: format!(
+                    "binary row reads {} bytes at offset {} out of its size {}",
+                    len,
+                    offset,
+                    self.data.len()
+                ),
+            }
+            .fail(),
+        }
+    }
+
+    pub fn is_null_at(&self, pos: usize) -> Result<bool> {
+        if pos >= self.arity as usize {
+            return DataCorruptedSnafu {
+                message: format!("binary row of arity {} has no field at {}", self.arity, pos),
+            }
+            .fail();
+        }
+        let index = pos + Self::HEADER_SIZE_IN_BYTES as usize;
+        let [bits] = self.read::<1>(index / 8)?;
+        Ok(bits & (1 << (index % 8)) != 0)
+    }
+
+    pub fn get_boolean(&self, pos: usize) -> Result<bool> {
+        Ok(self.read::<1>(self.field_offset(pos))?[0] != 0)
+    }
+
+    pub fn get_byte(&self, pos: usize) -> Result<i8> {
+        Ok(i8::from_le_bytes(self.read(self.field_offset(pos))?))
+    }
+
+    pub fn get_short(&self, pos: usize) -> Result<i16> {
+        Ok(i16::from_le_bytes(self.read(self.field_offset(pos))?))
+    }
+
+    pub fn get_int(&self, pos: usize) -> Result<i32> {
+        Ok(i32::from_le_bytes(self.read(self.field_offset(pos))?))
+    }
+
+    pub fn get_long(&self, pos: usize) -> Result<i64> {
+        Ok(i64::from_le_bytes(self.read(self.field_offset(pos))?))
+    }
+
+    pub fn get_float(&self, pos: usize) -> Result<f32> {
+        Ok(f32::from_le_bytes(self.read(self.field_offset(pos))?))
+    }
+
+    pub fn get_double(&self, pos: usize) -> Result<f64> {
+        Ok(f64::from_le_bytes(self.read(self.field_offset(pos))?))
+    }
+
+    pub fn get_binary(&self, pos: usize) -> Result<&[u8]> {
+        let field_offset = self.field_offset(pos);
+        let offset_and_len = u64::from_le_bytes(self.read(field_offset)?);
+        if offset_and_len & HIGHEST_FIRST_BIT == 0 {
+            let offset = (offset_and_len >> 32) as usize;
+            let len = (offset_and_len & 0xFFFF_FFFF) as usize;
+            self.read_var_bytes(offset, len)
+        } else {
+            let len = ((offset_and_len & HIGHEST_SECOND_TO_EIGHTH_BIT) >> 56) as usize;
+            self.read_var_bytes(field_offset, len)
+        }
+    }
+
+    pub fn get_string(&self, pos: usize) -> Result<&str> {
+        std::str::from_utf8(self.get_binary(pos)?).map_err(|e| {
+            DataCorruptedSnafu {
+                message: format!("binary row field {pos} is not valid utf-8: {e}"),
+            }
+            .build()
+        })
+    }
+
+    /// Get the unscaled value of a decimal, which is stored compactly as a long if precision <= 18.
+    pub fn get_decimal(&self, pos: usize, precision: u32) -> Result<i128> {
+        if precision <= 18 {
+            return Ok(self.get_long(pos)? as i128);
+        }
+        let offset_and_len = u64::from_le_bytes(self.read(self.field_offset(pos))?);
+        let bytes = self.read_var_bytes(
+            (offset_and_len >> 32) as usize,
+            (offset_and_len & 0xFFFF_FFFF) as usize,
+        )?;
+        if bytes.is_empty() || bytes.len() > 16 {
+            return DataCorruptedSnafu {
+                message: format!("invalid decimal bytes length {}", bytes.len()),
+            }
+            .fail();
+        }
+        // sign extend the big-endian two's complement bytes
+        let fill = if bytes[0] & 0x80 != 0 { 0xFF } else { 0 };
+        let mut buf = [fill; 16];
+        buf[16 - bytes.len()..].copy_from_slice(bytes);
+        Ok(i128::from_be_bytes(buf))
+    }
+
+    /// Get the milliseconds and nanoseconds of millisecond of a timestamp, which is stored compactly as millis if precision <= 3.
+    pub fn get_timestamp(&self, pos: usize, precision: u32) -> Result<(i64, i32)> {
+        if precision <= 3 {
+            return Ok((self.get_long(pos)?, 0));
+        }
+        let offset_and_nanos = u64::from_le_bytes(self.read(self.field_offset(pos))?);
+        let millis = i64::from_le_bytes(self.read((offset_and_nanos >> 32) as usize)?);
+        Ok((millis, (offset_and_nanos & 0xFFFF_FFFF) as i32))
+    }
+
+    /// Get the field at `pos` as a typed value, none if it is null.
+    pub fn get_datum(&self, pos: usize, data_type: &DataType) -> Result<Option<Datum>> {
+        if self.is_null_at(pos)? {
+            return Ok(None);
+        }
+        let datum = match data_type {
+            DataType::Boolean(_) => Datum::Boolean(self.get_boolean(pos)?),
+            DataType::TinyInt(_) => Datum::TinyInt(self.get_byte(pos)?),
+            DataType::SmallInt(_) => Datum::SmallInt(self.get_short(pos)?),
+            DataType::Int(_) => Datum::Int(self.get_int(pos)?),
+            DataType::BigInt(_) => Datum::BigInt(self.get_long(pos)?),
+            DataType::Float(_) => Datum::Float(self.get_float(pos)?),
+            DataType::Double(_) => Datum::Double(self.get_double(pos)?),
+            DataType::Char(_) | DataType::VarChar(_) => {
+                Datum::String(self.get_string(pos)?.to_string())
+            }
+            DataType::Binary(_) | DataType::VarBinary(_) => {
+                Datum::Bytes(self.get_binary(pos)?.to_vec())
+            }
+            DataType::Date(_) => Datum::Date(self.get_int(pos)?),
+            DataType::Time(_) => Datum::Time(self.get_int(pos)?),
+            DataType::Timestamp(t) => {
+                let (millis, nanos) = self.get_timestamp(pos, t.precision())?;
+                Datum::Timestamp { millis, nanos }
+            }
+            DataType::LocalZonedTimestamp(t) => {
+                let (millis, nanos) = self.get_timestamp(pos, t.precision())?;
+                Datum::LocalZonedTimestamp { millis, nanos }
+            }
+            DataType::Decimal(t) => Datum::Decimal {
+                unscaled: self.get_decimal(pos, t.precision())?,
+                precision: t.precision(),
+                scale: t.scale(),
+            },
+            _ => {
+                return DataTypeInvalidSnafu {
+                    message: format!("decoding {data_type:?} from binary row is not supported"),
+                }
+                .fail()
+            }
+        };
+        Ok(Some(datum))
+    }
+}
+
+/// Writer to build a [`BinaryRow`] field by field.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/data/BinaryRowWriter.java>
+#[derive(Debug)]
+pub struct BinaryRowWriter {
+    row: BinaryRow,
+}
+
+impl BinaryRowWriter {
+    pub fn new(arity: i32) -> Self {
+        Self {
+            row: BinaryRow::new(arity),
+        }
+    }
+
+    #[inline]
+    fn write_fixed(&mut self, pos: usize, bytes: &[u8]) {
+        let offset = self.row.field_offset(pos);
+        self.row.data.to_mut()[offset..offset + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Append bytes to the variable-length part, padded to whole words, and return their offset.
+    fn append_var(&mut self, bytes: &[u8]) -> usize {
+        let data = self.row.data.to_mut();
+        let offset = data.len();
+        data.extend_from_slice(bytes);
+        data.resize(offset + bytes.len().div_ceil(8) * 8, 0);
+        offset
+    }
+
+    pub fn set_null_at(&mut self, pos: usize) {
+        let index = pos + BinaryRow::HEADER_SIZE_IN_BYTES as usize;
+        self.row.data.to_mut()[index / 8] |= 1 << (index % 8);
+        self.write_fixed(pos, &[0; 8]);
+    }
+
+    pub fn write_boolean(&mut self, pos: usize, value: bool) {
+        self.write_fixed(pos, &[value as u8]);
+    }
+
+    pub fn write_byte(&mut self, pos: usize, value: i8) {
+        self.write_fixed(pos, &value.to_le_bytes());
+    }
+
+    pub fn write_short(&mut self, pos: usize, value: i16) {
+        self.write_fixed(pos, &value.to_le_bytes());
+    }
+
+    pub fn write_int(&mut self, pos: usize, value: i32) {
+        self.write_fixed(pos, &value.to_le_bytes());
+    }
+
+    pub fn write_long(&mut self, pos: usize, value: i64) {
+        self.write_fixed(pos, &value.to_le_bytes());
+    }
+
+    pub fn write_float(&mut self, pos: usize, value: f32) {
+        self.write_fixed(pos, &value.to_le_bytes());
+    }
+
+    pub fn write_double(&mut self, pos: usize, value: f64) {
+        self.write_fixed(pos, &value.to_le_bytes());
+    }
+
+    pub fn write_binary(&mut self, pos: usize, value: &[u8]) {
+        if value.len() <= MAX_FIX_PART_DATA_SIZE {
+            let mut slot = [0u8; 8];
+            slot[..value.len()].copy_from_slice(value);
+            slot[7] = 0x80 | value.len() as u8;
+            self.write_fixed(pos, &slot);
+        } else {
+            let offset = self.append_var(value);
+            self.write_long(pos, ((offset as i64) << 32) | value.len() as i64);
+        }
+    }
+
+    pub fn write_string(&mut self, pos: usize, value: &str) {
+        self.write_binary(pos, value.as_bytes());
+    }
+
+    /// Write the unscaled value of a decimal, see [`BinaryRow::get_decimal`].
+    pub fn write_decimal(&mut self, pos: usize, unscaled: i128, precision: u32) {
+        if precision <= 18 {
+            self.write_long(pos, unscaled as i64);
+            return;
+        }
+        // minimal big-endian two's complement, the same as java BigInteger#toByteArray
+        let bytes = unscaled.to_be_bytes();
+        let mut start = 0;
+        while start < 15 {
+            let redundant = (bytes[start] == 0x00 && bytes[start + 1] & 0x80 == 0)
+                || (bytes[start] == 0xFF && bytes[start + 1] & 0x80 != 0);
+            if !redundant {
+                break;
+            }
+            start += 1;
+        }
+        let bytes = &bytes[start..];
+        // java always reserves 16 bytes for a non-compact decimal
+        let data = self.row.data.to_mut();
+        let offset = data.len();
+        data.extend_from_slice(bytes);
+        data.resize(offset + 16, 0);
+        self.write_long(pos, ((offset as i64) << 32) | bytes.len() as i64);
+    }
+
+    /// Write a timestamp, see [`BinaryRow::get_timestamp`].
+    pub fn write_timestamp(&mut self, pos: usize, millis: i64, nanos: i32, precision: u32) {
+        if precision <= 3 {
+            self.write_long(pos, millis);
+            return;
+        }
+        let offset = self.append_var(&millis.to_le_bytes());
+        self.write_long(pos, ((offset as i64) << 32) | nanos as i64);
+    }
+
+    /// Write a typed value at `pos`, none writes null.
+    ///
+    /// Like paimon-java, the variable-length part of null non-compact decimals and timestamps
+    /// is still reserved, so that the bytes and the hash of the row are the same.
+    pub fn write_datum(
+        &mut self,
+        pos: usize,
+        datum: Option<&Datum>,
+        data_type: &DataType,
+    ) -> Result<()> {
+        let Some(datum) = datum else {
+            let reserved = match data_type {
+                DataType::Decimal(t) if t.precision() > 18 => 16,
+                DataType::Timestamp(t) if t.precision() > 3 => 8,
+                DataType::LocalZonedTimestamp(t) if t.precision() > 3 => 8,
+                _ => 0,
+            };
+            self.set_null_at(pos);
+            if reserved > 0 {
+                let offset = self.append_var(&[0; 16][..reserved]);
+                self.write_long(pos, (offset as i64) << 32);
+            }
+            return Ok(());
+        };
+        match (datum, data_type) {
+            (Datum::Boolean(v), DataType::Boolean(_)) => self.write_boolean(pos, *v),
+            (Datum::TinyInt(v), DataType::TinyInt(_)) => self.write_byte(pos, *v),
+            (Datum::SmallInt(v), DataType::SmallInt(_)) => self.write_short(pos, *v),
+            (Datum::Int(v), DataType::Int(_)) => self.write_int(pos, *v),
+            (Datum::BigInt(v), DataType::BigInt(_)) => self.write_long(pos, *v),
+            (Datum::Float(v), DataType::Float(_)) => self.write_float(pos, *v),
+            (Datum::Double(v), DataType::Double(_)) => self.write_double(pos, *v),
+            (Datum::String(v), DataType::Char(_) | DataType::VarChar(_)) => {
+                self.write_string(pos, v)
+            }
+            (Datum::Bytes(v), DataType::Binary(_) | DataType::VarBinary(_)) => {
+                self.write_binary(pos, v)
+            }
+            (Datum::Date(v), DataType::Date(_)) => self.write_int(pos, *v),
+            (Datum::Time(v), DataType::Time(_)) => self.write_int(pos, *v),
+            (Datum::Timestamp { millis, nanos }, DataType::Timestamp(t)) => {
+                self.write_timestamp(pos, *millis, *nanos, t.precision())
+            }
+            (Datum::LocalZonedTimestamp { millis, nanos }, DataType::LocalZonedTimestamp(t)) => {
+                self.write_timestamp(pos, *millis, *nanos, t.precision())
+            }
+            (Datum::Decimal { unscaled, .. }, DataType::Decimal(t)) => {
+                self.write_decimal(pos, *unscaled, t.precision())
+            }
+            _ => {
+                return DataTypeInvalidSnafu {
+                    message: format!("writing {datum:?} as {data_type:?} into binary row"),
+                }
+                .fail()
+            }
+        }
+        Ok(())
+    }
+
+    pub fn build(self) -> BinaryRow {
+        self.row
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{DecimalType, IntType, TimestampType, VarCharType};
+
+    #[test]
+    fn test_decode_java_serialized_row() {
+        // partition value written by java with fields ('1', 1)
+        let bytes = vec![
+            0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0, 49, 0, 0, 0, 0, 0, 0, 129, 1, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let row = BinaryRow::from_serialized_bytes(&bytes).unwrap();
+        assert_eq!(row.arity(), 2);
+        assert_eq!(row.get_string(0).unwrap(), "1");
+        assert_eq!(row.get_int(1).unwrap(), 1);
+        assert_eq!(row.to_serialized_bytes(), bytes);
+
+        let mut writer = BinaryRowWriter::new(2);
+        writer.write_string(0, "1");
+        writer.write_int(1, 1);
+        assert_eq!(writer.build().to_serialized_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_binary_row_datum_round_trip() {
+        let types = [
+            DataType::Int(IntType::new()),
+            DataType::VarChar(VarCharType::new(100).unwrap()),
+            DataType::Decimal(DecimalType::new(38, 2).unwrap()),
+            DataType::Timestamp(TimestampType::new(9).unwrap()),
+            DataType::Int(IntType::new()),
+        ];
+        let datums = [
+            Some(Datum::Int(-7)),
+            Some(Datum::String("a string longer than 7 bytes".to_string())),
+            Some(Datum::Decimal {
+                unscaled: -123_456_789_012_345_678_901_234,
+                precision: 38,
+                scale: 2,
+            }),
+            Some(Datum::Timestamp {
+                millis: 1704067200001,
+                nanos: 999_999,
+            }),
+            None,
+        ];
+        let mut writer = BinaryRowWriter::new(types.len() as i32);
+        for (pos, (datum, typ)) in datums.iter().zip(&types).enumerate() {
+            writer.write_datum(pos, datum.as_ref(), typ).unwrap();
+        }
+        let row = BinaryRow::from_serialized_bytes(&writer.build().to_serialized_bytes()).unwrap();
+        for (pos, (datum, typ)) in datums.iter().zip(&types).enumerate() {
+            assert_eq!(&row.get_datum(pos, typ).unwrap(), datum);
+        }
+        assert!(row.is_null_at(4).unwrap());
+        assert!(row.is_null_at(types.len()).is_err());
+    }
+
+    #[test]
+    fn test_binary_row_hash_code() {
+        let mut writer = BinaryRowWriter::new(1);
+        writer.write_int(0, 1);
+        // the hash code of the same row computed by paimon-java
+        assert_eq!(writer.build().hash_code(), 1465514398);
+
+        // null non-compact timestamps still reserve the variable-length part
+        let mut writer = BinaryRowWriter::new(1);
+        let data_type = DataType::Timestamp(TimestampType::new(6).unwrap());
+        writer.write_datum(0, None, &data_type).unwrap();
+        let row = writer.build();
+        assert_eq!(row.data().len(), 24);
+        assert_eq!(row.get_datum(0, &data_type).unwrap(), None);
+    }
+
+    #[test]
+    fn test_empty_binary_row() {
+        assert_eq!(
+            EMPTY_BINARY_ROW.to_serialized_bytes(),
+            vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(EMPTY_BINARY_ROW, BinaryRowWriter::new(0).build());
+    }
+
+    #[test]
+    fn test_binary_row_corrupted() {
+        assert!(BinaryRow::from_serialized_bytes(&[0, 0, 0, 1, 0]).is_err());
+    }
+}
@@ -0,0 +1,67 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::JsonUnexpectedSnafu;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+/// Consumer of a table, recording the next snapshot to consume of a streaming read.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/consumer/Consumer.java>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Consumer {
+    next_snapshot: i64,
+}
+
+impl Consumer {
+    pub fn new(next_snapshot: i64) -> Self {
+        Self { next_snapshot }
+    }
+
+    /// Get the id of the next snapshot to consume.
+    #[inline]
+    pub fn next_snapshot(&self) -> i64 {
+        self.next_snapshot
+    }
+
+    /// Deserialize the consumer from its json representation.
+    pub fn from_json(json: &str) -> crate::Result<Consumer> {
+        serde_json::from_str(json).context(JsonUnexpectedSnafu {
+            message: "Failed to parse consumer",
+        })
+    }
+
+    /// Serialize this consumer into its json representation.
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string(self).context(JsonUnexpectedSnafu {
+            message: "Failed to serialize consumer",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consumer_json() {
+        let consumer = Consumer::from_json(r#"{"nextSnapshot":5}"#).unwrap();
+        assert_eq!(consumer.next_snapshot(), 5);
+        assert_eq!(consumer.to_json().unwrap(), r#"{"nextSnapshot":5}"#);
+    }
+}
@@ -0,0 +1,1199 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+const BRANCH: &str = "branch";
+const BUCKET: &str = "bucket";
+const BUCKET_KEY: &str = "bucket-key";
+const CHANGELOG_PRODUCER: &str = "changelog-producer";
+const CHANGELOG_PRODUCER_ROW_DEDUPLICATE: &str = "changelog-producer.row-deduplicate";
+const COMMIT_MAX_RETRIES: &str = "commit.max-retries";
+const COMPACTION_MAX_SIZE_AMPLIFICATION_PERCENT: &str = "compaction.max-size-amplification-percent";
+const COMPACTION_SIZE_RATIO: &str = "compaction.size-ratio";
+const CSV_FIELD_DELIMITER: &str = "csv.field-delimiter";
+const CSV_INCLUDE_HEADER: &str = "csv.include-header";
+const FILE_COMPRESSION: &str = "file.compression";
+const FILE_COMPRESSION_ZSTD_LEVEL: &str = "file.compression.zstd-level";
+const FILE_FORMAT: &str = "file.format";
+const FILE_INDEX_COLUMNS: &str = "columns";
+const FILE_INDEX_IN_MANIFEST_THRESHOLD: &str = "file-index.in-manifest-threshold";
+const FILE_INDEX_PREFIX: &str = "file-index.";
+const FILE_INDEX_READ_ENABLED: &str = "file-index.read.enabled";
+const AGGREGATE_FUNCTION: &str = "aggregate-function";
+const CONSUMER_EXPIRATION_TIME: &str = "consumer.expiration-time";
+pub(crate) const CONSUMER_ID: &str = "consumer-id";
+const DEFAULT_AGGREGATE_FUNCTION: &str = "default-aggregate-function";
+const DELETION_VECTORS_ENABLED: &str = "deletion-vectors.enabled";
+const DYNAMIC_BUCKET_TARGET_ROW_NUM: &str = "dynamic-bucket.target-row-num";
+const DYNAMIC_PARTITION_OVERWRITE: &str = "dynamic-partition-overwrite";
+const FIELDS_PREFIX: &str = "fields.";
+const IGNORE_DELETE: &str = "ignore-delete";
+const IGNORE_RETRACT: &str = "ignore-retract";
+const INCREMENTAL_BETWEEN: &str = "incremental-between";
+const INCREMENTAL_BETWEEN_SCAN_MODE: &str = "incremental-between-scan-mode";
+const INCREMENTAL_BETWEEN_TIMESTAMP: &str = "incremental-between-timestamp";
+const LIST_AGG_DELIMITER: &str = "list-agg-delimiter";
+const IGNORE_DELETE_FALLBACK_KEYS: [&str; 3] = [
+    "first-row.ignore-delete",
+    "deduplicate.ignore-delete",
+    "partial-update.ignore-delete",
+];
+const MANIFEST_FULL_COMPACTION_THRESHOLD_SIZE: &str = "manifest.full-compaction-threshold-size";
+const MANIFEST_MERGE_MIN_COUNT: &str = "manifest.merge-min-count";
+const MANIFEST_TARGET_FILE_SIZE: &str = "manifest.target-file-size";
+const MERGE_ENGINE: &str = "merge-engine";
+const METADATA_ICEBERG_STORAGE: &str = "metadata.iceberg.storage";
+const METADATA_STATS_MODE: &str = "metadata.stats-mode";
+const MEMORY_POOL_SIZE: &str = "memory-pool.size";
+const NUM_LEVELS: &str = "num-levels";
+const NUM_SORTED_RUN_COMPACTION_TRIGGER: &str = "num-sorted-run.compaction-trigger";
+const OBJECT_LOCATION: &str = "object-location";
+const PARTIAL_UPDATE_REMOVE_RECORD_ON_DELETE: &str = "partial-update.remove-record-on-delete";
+const PARTITION_DEFAULT_NAME: &str = "partition.default-name";
+const PARTITION_EXPIRATION_TIME: &str = "partition.expiration-time";
+const PARTITION_TIMESTAMP_FORMATTER: &str = "partition.timestamp-formatter";
+const PARTITION_TIMESTAMP_PATTERN: &str = "partition.timestamp-pattern";
+const READ_COALESCE_ENABLED: &str = "read.coalesce.enabled";
+const READ_COALESCE_MAX_GAP: &str = "read.coalesce.max-gap";
+const READ_COALESCE_MAX_SIZE: &str = "read.coalesce.max-size";
+const READ_PREFETCH_RANGES: &str = "read.prefetch.ranges";
+pub(crate) const SCAN_MODE: &str = "scan.mode";
+pub(crate) const SCAN_SNAPSHOT_ID: &str = "scan.snapshot-id";
+pub(crate) const SCAN_TAG_NAME: &str = "scan.tag-name";
+pub(crate) const SCAN_TIMESTAMP_MILLIS: &str = "scan.timestamp-millis";
+
+/// The options of tables known by paimon with their defaults, none if the option has no
+/// default.
+const KNOWN_OPTIONS: &[(&str, Option<&str>)] = &[
+    (BRANCH, Some("main")),
+    (BUCKET, Some("-1")),
+    (BUCKET_KEY, None),
+    (CHANGELOG_PRODUCER, Some("none")),
+    (CHANGELOG_PRODUCER_ROW_DEDUPLICATE, Some("false")),
+    (COMMIT_MAX_RETRIES, Some("10")),
+    (COMPACTION_MAX_SIZE_AMPLIFICATION_PERCENT, Some("200")),
+    (COMPACTION_SIZE_RATIO, Some("1")),
+    (CONSUMER_EXPIRATION_TIME, None),
+    (CONSUMER_ID, None),
+    (CSV_FIELD_DELIMITER, Some(",")),
+    (CSV_INCLUDE_HEADER, Some("false")),
+    (DELETION_VECTORS_ENABLED, Some("false")),
+    (DYNAMIC_BUCKET_TARGET_ROW_NUM, Some("2000000")),
+    (DYNAMIC_PARTITION_OVERWRITE, Some("true")),
+    (FILE_COMPRESSION, Some("zstd")),
+    (FILE_COMPRESSION_ZSTD_LEVEL, Some("1")),
+    (FILE_FORMAT, Some("orc")),
+    (FILE_INDEX_IN_MANIFEST_THRESHOLD, Some("500 b")),
+    (FILE_INDEX_READ_ENABLED, Some("true")),
+    (IGNORE_DELETE, Some("false")),
+    (IGNORE_DELETE_FALLBACK_KEYS[0], None),
+    (IGNORE_DELETE_FALLBACK_KEYS[1], None),
+    (IGNORE_DELETE_FALLBACK_KEYS[2], None),
+    (INCREMENTAL_BETWEEN, None),
+    (INCREMENTAL_BETWEEN_SCAN_MODE, Some("auto")),
+    (INCREMENTAL_BETWEEN_TIMESTAMP, None),
+    (MANIFEST_FULL_COMPACTION_THRESHOLD_SIZE, Some("16 mb")),
+    (MANIFEST_MERGE_MIN_COUNT, Some("30")),
+    (MANIFEST_TARGET_FILE_SIZE, Some("8 mb")),
+    (MEMORY_POOL_SIZE, None),
+    (MERGE_ENGINE, Some("deduplicate")),
+    (METADATA_ICEBERG_STORAGE, Some("disabled")),
+    (METADATA_STATS_MODE, Some("truncate(16)")),
+    (NUM_LEVELS, None),
+    (NUM_SORTED_RUN_COMPACTION_TRIGGER, Some("5")),
+    (OBJECT_LOCATION, None),
+    (PARTIAL_UPDATE_REMOVE_RECORD_ON_DELETE, Some("false")),
+    (PARTITION_DEFAULT_NAME, Some("__DEFAULT_PARTITION__")),
+    (PARTITION_EXPIRATION_TIME, None),
+    (PARTITION_TIMESTAMP_FORMATTER, None),
+    (PARTITION_TIMESTAMP_PATTERN, None),
+    (READ_COALESCE_ENABLED, Some("true")),
+    (READ_COALESCE_MAX_GAP, Some("1 mb")),
+    (READ_COALESCE_MAX_SIZE, Some("8 mb")),
+    (READ_PREFETCH_RANGES, Some("1")),
+    (SCAN_MODE, Some("default")),
+    (SCAN_SNAPSHOT_ID, None),
+    (SCAN_TAG_NAME, None),
+    (SCAN_TIMESTAMP_MILLIS, None),
+    (SEQUENCE_FIELD, None),
+    (SNAPSHOT_EXPIRE_LIMIT, Some("10")),
+    (SNAPSHOT_NUM_RETAINED_MAX, Some("2147483647")),
+    (SNAPSHOT_NUM_RETAINED_MIN, Some("10")),
+    (SNAPSHOT_TIME_RETAINED, Some("1 h")),
+    (SOURCE_SPLIT_OPEN_FILE_COST, Some("4 mb")),
+    (SOURCE_SPLIT_TARGET_SIZE, Some("128 mb")),
+    (TARGET_FILE_SIZE, Some("128 mb")),
+    (TYPE, Some("table")),
+    (WRITE_BUFFER_SIZE, Some("256 mb")),
+    (WRITE_BUFFER_SPILL_DIR, None),
+    (WRITE_BUFFER_SPILL_MAX_DISK_SIZE, None),
+    (WRITE_BUFFER_SPILLABLE, Some("false")),
+    (WRITE_MAX_WRITERS, None),
+    (WRITE_ONLY, Some("false")),
+];
+
+/// Prefixes of the known options of fields, file indexes and file systems.
+const KNOWN_OPTION_PREFIXES: &[&str] = &[
+    FIELDS_PREFIX,
+    FILE_INDEX_PREFIX,
+    "fs.",
+    "s3.",
+    "gcs.",
+    "webhdfs.",
+];
+
+/// An option whose effective value differs from its default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OptionDiff {
+    pub key: String,
+    /// The default value, none if the option has no default or is unknown.
+    pub default: Option<String>,
+    pub effective: String,
+}
+const SEQUENCE_FIELD: &str = "sequence.field";
+const SEQUENCE_GROUP: &str = "sequence-group";
+const SNAPSHOT_EXPIRE_LIMIT: &str = "snapshot.expire.limit";
+const SNAPSHOT_NUM_RETAINED_MAX: &str = "snapshot.num-retained.max";
+const SNAPSHOT_NUM_RETAINED_MIN: &str = "snapshot.num-retained.min";
+const SNAPSHOT_TIME_RETAINED: &str = "snapshot.time-retained";
+const STATS_MODE: &str = "stats-mode";
+const SOURCE_SPLIT_OPEN_FILE_COST: &str = "source.split.open-file-cost";
+const SOURCE_SPLIT_TARGET_SIZE: &str = "source.split.target-size";
+const TARGET_FILE_SIZE: &str = "target-file-size";
+const TYPE: &str = "type";
+const WRITE_BUFFER_SIZE: &str = "write-buffer-size";
+const WRITE_BUFFER_SPILL_DIR: &str = "write-buffer-spill.dir";
+const WRITE_BUFFER_SPILL_MAX_DISK_SIZE: &str = "write-buffer-spill.max-disk-size";
+const WRITE_BUFFER_SPILLABLE: &str = "write-buffer-spillable";
+const WRITE_MAX_WRITERS: &str = "write.max-writers";
+const WRITE_ONLY: &str = "write-only";
+
+/// Typed accessors of the options of a table, unrecognised or missing options fall back to
+/// the defaults of paimon-java, while unknown values of the enumerated options like
+/// [`MergeEngine`] are rejected.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/CoreOptions.java>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoreOptions<'a> {
+    options: &'a HashMap<String, String>,
+}
+
+impl<'a> CoreOptions<'a> {
+    /// Default level of the `zstd` compression of the data files.
+    pub const DEFAULT_FILE_COMPRESSION_ZSTD_LEVEL: i32 = 1;
+
+    pub fn new(options: &'a HashMap<String, String>) -> Self {
+        Self { options }
+    }
+
+    /// Get the raw value of the option.
+    pub fn get(&self, key: &str) -> Option<&'a str> {
+        self.options.get(key).map(String::as_str)
+    }
+
+    /// Get the default value of the option, `Some(None)` if the option is known without a
+    /// default, none if the option is unknown.
+    pub fn default_value(key: &str) -> Option<Option<&'static str>> {
+        if let Some((_, default)) = KNOWN_OPTIONS.iter().find(|(k, _)| *k == key) {
+            return Some(*default);
+        }
+        KNOWN_OPTION_PREFIXES
+            .iter()
+            .any(|prefix| key.starts_with(prefix))
+            .then_some(None)
+    }
+
+    /// The keys of the options not known by paimon in ascending order, which have no effect.
+    pub fn unknown_keys(&self) -> Vec<&'a str> {
+        let mut keys: Vec<_> = self
+            .options
+            .keys()
+            .map(String::as_str)
+            .filter(|key| Self::default_value(key).is_none())
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// The options whose values differ from their defaults in ascending order of keys.
+    pub fn diff_from_defaults(&self) -> Vec<OptionDiff> {
+        let mut diffs: Vec<_> = self
+            .options
+            .iter()
+            .filter_map(|(key, value)| {
+                let default = Self::default_value(key).flatten();
+                (default != Some(value.as_str())).then(|| OptionDiff {
+                    key: key.clone(),
+                    default: default.map(str::to_string),
+                    effective: value.clone(),
+                })
+            })
+            .collect();
+        diffs.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+        diffs
+    }
+
+    /// Branch of the table to read and write.
+    pub fn branch(&self) -> &'a str {
+        self.get(BRANCH).unwrap_or("main")
+    }
+
+    /// Number of buckets of the table, `-1` means dynamic bucket or bucket unaware.
+    pub fn bucket(&self) -> i32 {
+        self.get(BUCKET)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(-1)
+    }
+
+    /// Max number of keys of a bucket of dynamic bucket tables, new keys are assigned to new
+    /// buckets when all buckets are full.
+    pub fn dynamic_bucket_target_row_num(&self) -> i64 {
+        self.get(DYNAMIC_BUCKET_TARGET_ROW_NUM)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(2_000_000)
+    }
+
+    /// Whether overwrites without static partitions only overwrite the partitions written,
+    /// otherwise the whole table is overwritten.
+    pub fn dynamic_partition_overwrite(&self) -> bool {
+        self.get_bool(DYNAMIC_PARTITION_OVERWRITE).unwrap_or(true)
+    }
+
+    /// Fields to distribute rows into buckets, empty means the primary keys or the whole row.
+    pub fn bucket_key(&self) -> Vec<&'a str> {
+        self.get(BUCKET_KEY)
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|k| !k.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Producer of the changelog files of primary key tables, fails if the option is not a
+    /// known producer.
+    pub fn changelog_producer(&self) -> crate::Result<ChangelogProducer> {
+        self.get(CHANGELOG_PRODUCER)
+            .map_or(Ok(ChangelogProducer::None), str::parse)
+    }
+
+    /// Whether the `lookup` changelog producer skips the updates not changing the values.
+    pub fn changelog_producer_row_deduplicate(&self) -> bool {
+        self.get_bool(CHANGELOG_PRODUCER_ROW_DEDUPLICATE)
+            .unwrap_or(false)
+    }
+
+    /// Format of the data files, in lower case.
+    pub fn file_format(&self) -> String {
+        self.get(FILE_FORMAT).unwrap_or("orc").to_lowercase()
+    }
+
+    /// Delimiter of the fields of csv files, `,` by default, `\t` is the tab.
+    pub fn csv_field_delimiter(&self) -> u8 {
+        match self.get(CSV_FIELD_DELIMITER) {
+            Some("\\t") => b'\t',
+            Some(delimiter) if delimiter.len() == 1 => delimiter.as_bytes()[0],
+            _ => b',',
+        }
+    }
+
+    /// Whether the first lines of csv files are the headers of the column names.
+    pub fn csv_include_header(&self) -> bool {
+        self.get_bool(CSV_INCLUDE_HEADER).unwrap_or(false)
+    }
+
+    /// Compression of the data files, in lower case.
+    pub fn file_compression(&self) -> String {
+        self.get(FILE_COMPRESSION).unwrap_or("zstd").to_lowercase()
+    }
+
+    /// Level of the `zstd` compression of the data files, higher levels compress better but
+    /// slower.
+    pub fn file_compression_zstd_level(&self) -> i32 {
+        self.get(FILE_COMPRESSION_ZSTD_LEVEL)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(Self::DEFAULT_FILE_COMPRESSION_ZSTD_LEVEL)
+    }
+
+    /// Storage of the Iceberg compatible metadata written on commits, in lower case, one of
+    /// `disabled`, `table-location` and `hadoop-catalog`.
+    pub fn metadata_iceberg_storage(&self) -> String {
+        self.get(METADATA_ICEBERG_STORAGE)
+            .unwrap_or("disabled")
+            .to_lowercase()
+    }
+
+    /// Mode of the statistics collected of the fields of the data files, in lower case, one of
+    /// `none`, `counts`, `truncate(<length>)` and `full`.
+    pub fn metadata_stats_mode(&self) -> String {
+        self.get(METADATA_STATS_MODE)
+            .unwrap_or("truncate(16)")
+            .to_lowercase()
+    }
+
+    /// Mode of the statistics collected of the field, `fields.<field>.stats-mode`, falling back
+    /// to `metadata.stats-mode`.
+    pub fn field_stats_mode(&self, field: &str) -> String {
+        match self.field_option(field, STATS_MODE) {
+            Some(mode) => mode.to_lowercase(),
+            None => self.metadata_stats_mode(),
+        }
+    }
+
+    /// Merge engine of the primary key table, fails if the option is not a known engine.
+    pub fn merge_engine(&self) -> crate::Result<MergeEngine> {
+        self.get(MERGE_ENGINE)
+            .map_or(Ok(MergeEngine::Deduplicate), str::parse)
+    }
+
+    fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get(key).map(|v| v.trim().eq_ignore_ascii_case("true"))
+    }
+
+    /// Whether the deleted rows of data files are recorded in deletion vectors, so that the
+    /// files of primary key tables are read without merging.
+    pub fn deletion_vectors_enabled(&self) -> bool {
+        self.get_bool(DELETION_VECTORS_ENABLED).unwrap_or(false)
+    }
+
+    /// Columns of the file indexes of the data files by their index types, configured by
+    /// `file-index.<type>.columns`, sorted by the index types.
+    pub fn file_index_columns(&self) -> Vec<(&'a str, Vec<&'a str>)> {
+        let suffix = format!(".{FILE_INDEX_COLUMNS}");
+        let mut columns: Vec<_> = self
+            .options
+            .iter()
+            .filter_map(|(key, value)| {
+                let index_type = key.strip_prefix(FILE_INDEX_PREFIX)?.strip_suffix(&suffix)?;
+                let columns = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                    .collect();
+                Some((index_type, columns))
+            })
+            .collect();
+        columns.sort();
+        columns
+    }
+
+    /// Option of the file index of the type on the column, `file-index.<type>.<column>.<key>`.
+    pub fn file_index_option(&self, index_type: &str, column: &str, key: &str) -> Option<&'a str> {
+        self.get(&format!("{FILE_INDEX_PREFIX}{index_type}.{column}.{key}"))
+    }
+
+    /// Max size in bytes of the file indexes embedded in the metas of the data files, larger
+    /// ones are written into separate files.
+    pub fn file_index_in_manifest_threshold(&self) -> i64 {
+        self.get(FILE_INDEX_IN_MANIFEST_THRESHOLD)
+            .and_then(parse_memory_size)
+            .unwrap_or(500)
+    }
+
+    /// Whether to skip data files by their file indexes when reading.
+    pub fn file_index_read_enabled(&self) -> bool {
+        self.get_bool(FILE_INDEX_READ_ENABLED).unwrap_or(true)
+    }
+
+    /// Whether to read only the ranges of the projected columns of parquet files, coalescing
+    /// the ranges close to each other, instead of the whole files.
+    pub fn read_coalesce_enabled(&self) -> bool {
+        self.get_bool(READ_COALESCE_ENABLED).unwrap_or(true)
+    }
+
+    /// Max gap in bytes between ranges of a file coalesced into a single read.
+    pub fn read_coalesce_max_gap(&self) -> u64 {
+        self.get(READ_COALESCE_MAX_GAP)
+            .and_then(parse_memory_size)
+            .map_or(1024 * 1024, |size| size.max(0) as u64)
+    }
+
+    /// Max size in bytes of the coalesced ranges, larger ranges are read as they are.
+    pub fn read_coalesce_max_size(&self) -> u64 {
+        self.get(READ_COALESCE_MAX_SIZE)
+            .and_then(parse_memory_size)
+            .map_or(8 * 1024 * 1024, |size| size.max(0) as u64)
+    }
+
+    /// Number of coalesced ranges of a file read ahead while the current range is read.
+    pub fn read_prefetch_ranges(&self) -> usize {
+        self.get(READ_PREFETCH_RANGES)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(1)
+    }
+
+    /// Whether to ignore the delete records when merging the key values.
+    pub fn ignore_delete(&self) -> bool {
+        self.get_bool(IGNORE_DELETE)
+            .or_else(|| {
+                IGNORE_DELETE_FALLBACK_KEYS
+                    .iter()
+                    .find_map(|key| self.get_bool(key))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Whether the `partial-update` merge engine removes the whole row on delete records.
+    pub fn partial_update_remove_record_on_delete(&self) -> bool {
+        self.get_bool(PARTIAL_UPDATE_REMOVE_RECORD_ON_DELETE)
+            .unwrap_or(false)
+    }
+
+    /// Fields to order the records of the same key of primary key tables before the sequence
+    /// number, the record of the largest values wins.
+    pub fn sequence_field(&self) -> Vec<&'a str> {
+        self.get(SEQUENCE_FIELD)
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|f| !f.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Sequence groups of the `partial-update` merge engine, configured by
+    /// `fields.<sequence-field>.sequence-group`, sorted by the sequence fields.
+    pub fn sequence_groups(&self) -> Vec<(&'a str, Vec<&'a str>)> {
+        let suffix = format!(".{SEQUENCE_GROUP}");
+        let mut groups: Vec<_> = self
+            .options
+            .iter()
+            .filter_map(|(key, value)| {
+                let field = key.strip_prefix(FIELDS_PREFIX)?.strip_suffix(&suffix)?;
+                let fields = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|f| !f.is_empty())
+                    .collect();
+                Some((field, fields))
+            })
+            .collect();
+        groups.sort();
+        groups
+    }
+
+    fn field_option(&self, field: &str, key: &str) -> Option<&'a str> {
+        self.get(&format!("{FIELDS_PREFIX}{field}.{key}"))
+    }
+
+    /// Aggregate function of the field of the `aggregation` merge engine, falling back to
+    /// `fields.default-aggregate-function`.
+    pub fn field_aggregate_function(&self, field: &str) -> Option<&'a str> {
+        self.field_option(field, AGGREGATE_FUNCTION)
+            .or_else(|| self.get(&format!("{FIELDS_PREFIX}{DEFAULT_AGGREGATE_FUNCTION}")))
+    }
+
+    /// Whether the aggregate function of the field ignores retract records.
+    pub fn field_ignore_retract(&self, field: &str) -> bool {
+        self.get_bool(&format!("{FIELDS_PREFIX}{field}.{IGNORE_RETRACT}"))
+            .unwrap_or(false)
+    }
+
+    /// Delimiter of the `listagg` aggregate function of the field.
+    pub fn field_list_agg_delimiter(&self, field: &str) -> &'a str {
+        self.field_option(field, LIST_AGG_DELIMITER).unwrap_or(",")
+    }
+
+    /// Target size in bytes of a split when scanning a bucket.
+    pub fn split_target_size(&self) -> i64 {
+        self.get(SOURCE_SPLIT_TARGET_SIZE)
+            .and_then(parse_memory_size)
+            .unwrap_or(128 * 1024 * 1024)
+    }
+
+    /// Open file cost in bytes of a source file, to avoid reading too many small files in a split.
+    pub fn split_open_file_cost(&self) -> i64 {
+        self.get(SOURCE_SPLIT_OPEN_FILE_COST)
+            .and_then(parse_memory_size)
+            .unwrap_or(4 * 1024 * 1024)
+    }
+
+    /// Target size in bytes of a data file, files are rolled when reaching it.
+    pub fn target_file_size(&self) -> i64 {
+        self.get(TARGET_FILE_SIZE)
+            .and_then(parse_memory_size)
+            .unwrap_or(128 * 1024 * 1024)
+    }
+
+    /// Size in bytes of the rows buffered in memory before they are sorted and spilled into
+    /// data files.
+    pub fn write_buffer_size(&self) -> i64 {
+        self.get(WRITE_BUFFER_SIZE)
+            .and_then(parse_memory_size)
+            .unwrap_or(256 * 1024 * 1024)
+    }
+
+    /// Max size in bytes of the memory pool shared by the writers of a table write or by the
+    /// readers of a table read, unbounded by default.
+    pub fn memory_pool_size(&self) -> i64 {
+        self.get(MEMORY_POOL_SIZE)
+            .and_then(parse_memory_size)
+            .unwrap_or(i64::MAX)
+    }
+
+    /// Whether the full write buffer of primary key tables is sorted and spilled into local
+    /// files, which are merged into data files on flush, instead of being flushed directly.
+    pub fn write_buffer_spillable(&self) -> bool {
+        self.get_bool(WRITE_BUFFER_SPILLABLE).unwrap_or(false)
+    }
+
+    /// Local directory of the files spilled by the write buffer, defaults to the temporary
+    /// directory of the system.
+    pub fn write_buffer_spill_dir(&self) -> PathBuf {
+        self.get(WRITE_BUFFER_SPILL_DIR)
+            .map(PathBuf::from)
+            .unwrap_or_else(std::env::temp_dir)
+    }
+
+    /// Max size in bytes of the files spilled by the write buffer of a bucket, the buffer is
+    /// flushed into data files when reaching it.
+    pub fn write_buffer_spill_max_disk_size(&self) -> i64 {
+        self.get(WRITE_BUFFER_SPILL_MAX_DISK_SIZE)
+            .and_then(parse_memory_size)
+            .unwrap_or(i64::MAX)
+    }
+
+    /// Max number of the writers of buckets holding the rows written in memory, the rows of
+    /// the least recently used writers are flushed into data files beyond it. Unlimited by
+    /// default.
+    pub fn write_max_writers(&self) -> Option<usize> {
+        self.get(WRITE_MAX_WRITERS)
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|max| *max > 0)
+    }
+
+    /// Number of the levels of the merge tree of a bucket, full compactions rewrite the files
+    /// into the max level of `num-levels - 1`. Defaults to one more than
+    /// `num-sorted-run.compaction-trigger`.
+    pub fn num_levels(&self) -> i32 {
+        self.get(NUM_LEVELS)
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|levels| *levels > 1)
+            .unwrap_or(self.num_sorted_run_compaction_trigger() as i32 + 1)
+    }
+
+    /// Number of the sorted runs of a bucket to trigger a compaction on write, each level 0
+    /// file is a sorted run, while the files of each other level are a sorted run.
+    pub fn num_sorted_run_compaction_trigger(&self) -> usize {
+        self.get(NUM_SORTED_RUN_COMPACTION_TRIGGER)
+            .and_then(|v| v.trim().parse().ok())
+            .filter(|trigger| *trigger > 0)
+            .unwrap_or(5)
+    }
+
+    /// Max percentage of the size of the other sorted runs relative to the size of the oldest
+    /// sorted run, beyond which all the sorted runs are compacted.
+    pub fn compaction_max_size_amplification_percent(&self) -> i32 {
+        self.get(COMPACTION_MAX_SIZE_AMPLIFICATION_PERCENT)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(200)
+    }
+
+    /// Percentage of flexibility while comparing the sizes of sorted runs, a sorted run is
+    /// compacted with the newer ones if their total size is not smaller than it by the ratio.
+    pub fn compaction_size_ratio(&self) -> i32 {
+        self.get(COMPACTION_SIZE_RATIO)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(1)
+    }
+
+    /// Target size in bytes of a manifest file, small manifests are merged until reaching it.
+    pub fn manifest_target_file_size(&self) -> i64 {
+        self.get(MANIFEST_TARGET_FILE_SIZE)
+            .and_then(parse_memory_size)
+            .unwrap_or(8 * 1024 * 1024)
+    }
+
+    /// Total size in bytes of the delta manifests to trigger a full compaction of the manifests
+    /// when committing.
+    pub fn manifest_full_compaction_threshold_size(&self) -> i64 {
+        self.get(MANIFEST_FULL_COMPACTION_THRESHOLD_SIZE)
+            .and_then(parse_memory_size)
+            .unwrap_or(16 * 1024 * 1024)
+    }
+
+    /// Minimum number of small manifests to merge when committing.
+    pub fn manifest_merge_min_count(&self) -> usize {
+        self.get(MANIFEST_MERGE_MIN_COUNT)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(30)
+    }
+
+    /// Max number of retries of a commit conflicting with commits of other writers.
+    pub fn commit_max_retries(&self) -> usize {
+        self.get(COMMIT_MAX_RETRIES)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(10)
+    }
+
+    /// Id of the consumer recording the progress of streaming reads.
+    pub fn consumer_id(&self) -> Option<&'a str> {
+        self.get(CONSUMER_ID)
+    }
+
+    /// Time after which consumers not updated are expired, consumers never expire by default.
+    pub fn consumer_expiration_time(&self) -> Option<Duration> {
+        self.get(CONSUMER_EXPIRATION_TIME).and_then(parse_duration)
+    }
+
+    /// Startup mode of scans, in lower case.
+    pub fn scan_mode(&self) -> String {
+        self.get(SCAN_MODE).unwrap_or("default").to_lowercase()
+    }
+
+    /// Id of the snapshot to scan from.
+    pub fn scan_snapshot_id(&self) -> Option<i64> {
+        self.get(SCAN_SNAPSHOT_ID)
+            .and_then(|v| v.trim().parse().ok())
+    }
+
+    /// Time in milliseconds to scan the latest snapshot committed at or before.
+    pub fn scan_timestamp_millis(&self) -> Option<u64> {
+        self.get(SCAN_TIMESTAMP_MILLIS)
+            .and_then(|v| v.trim().parse().ok())
+    }
+
+    /// Name of the tag to scan.
+    pub fn scan_tag_name(&self) -> Option<&'a str> {
+        self.get(SCAN_TAG_NAME)
+    }
+
+    /// Start exclusive and end inclusive of the incremental scan, both snapshot ids or both
+    /// tag names separated by a comma.
+    pub fn incremental_between(&self) -> Option<(&'a str, &'a str)> {
+        self.get(INCREMENTAL_BETWEEN)
+            .and_then(|v| v.split_once(','))
+            .map(|(start, end)| (start.trim(), end.trim()))
+    }
+
+    /// Start exclusive and end inclusive times in milliseconds of the incremental scan.
+    pub fn incremental_between_timestamp(&self) -> Option<(u64, u64)> {
+        let (start, end) = self.get(INCREMENTAL_BETWEEN_TIMESTAMP)?.split_once(',')?;
+        Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+    }
+
+    /// Files read by the incremental scan between snapshots, `delta`, `changelog`, or `auto`,
+    /// the default, to read the changelog files if a changelog producer is set, in lower case.
+    pub fn incremental_between_scan_mode(&self) -> String {
+        self.get(INCREMENTAL_BETWEEN_SCAN_MODE)
+            .unwrap_or("auto")
+            .to_lowercase()
+    }
+
+    /// Min number of the latest snapshots retained by snapshot expiration.
+    pub fn snapshot_num_retained_min(&self) -> usize {
+        self.get(SNAPSHOT_NUM_RETAINED_MIN)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(10)
+    }
+
+    /// Max number of the latest snapshots retained by snapshot expiration.
+    pub fn snapshot_num_retained_max(&self) -> usize {
+        self.get(SNAPSHOT_NUM_RETAINED_MAX)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(i32::MAX as usize)
+    }
+
+    /// Time to retain the snapshots by snapshot expiration, one hour by default.
+    pub fn snapshot_time_retained(&self) -> Duration {
+        self.get(SNAPSHOT_TIME_RETAINED)
+            .and_then(parse_duration)
+            .unwrap_or(Duration::from_secs(60 * 60))
+    }
+
+    /// Max number of snapshots expired at a time.
+    pub fn snapshot_expire_limit(&self) -> usize {
+        self.get(SNAPSHOT_EXPIRE_LIMIT)
+            .and_then(|v| v.trim().parse().ok())
+            .unwrap_or(10)
+    }
+
+    /// Type of the table, in lower case, `table` for the tables of paimon by default.
+    pub fn table_type(&self) -> String {
+        self.get(TYPE).unwrap_or("table").to_lowercase()
+    }
+
+    /// Location of the directory of the files cataloged by the object table.
+    pub fn object_location(&self) -> Option<&'a str> {
+        self.get(OBJECT_LOCATION)
+    }
+
+    /// Whether commits skip expiring snapshots, leaving it to dedicated jobs.
+    pub fn write_only(&self) -> bool {
+        self.get_bool(WRITE_ONLY).unwrap_or(false)
+    }
+
+    /// Name of the partition whose value is null or empty.
+    pub fn partition_default_name(&self) -> &'a str {
+        self.get(PARTITION_DEFAULT_NAME)
+            .unwrap_or("__DEFAULT_PARTITION__")
+    }
+
+    /// Time after which partitions are expired by the time of their values, partitions never
+    /// expire by default.
+    pub fn partition_expiration_time(&self) -> Option<Duration> {
+        self.get(PARTITION_EXPIRATION_TIME).and_then(parse_duration)
+    }
+
+    /// Pattern of `java.time.format.DateTimeFormatter` to parse the time of partitions, like
+    /// `yyyyMMdd`.
+    pub fn partition_timestamp_formatter(&self) -> Option<&'a str> {
+        self.get(PARTITION_TIMESTAMP_FORMATTER)
+    }
+
+    /// Pattern of the time of partitions composed of their values, like `$dt $hour:00:00`, the
+    /// value of the first partition key by default.
+    pub fn partition_timestamp_pattern(&self) -> Option<&'a str> {
+        self.get(PARTITION_TIMESTAMP_PATTERN)
+    }
+}
+
+/// Merge engine of primary key tables, merging the records of the same key.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/CoreOptions.java#L1846>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeEngine {
+    /// Keep the last record.
+    Deduplicate,
+    /// Update the non-null fields by the last records.
+    PartialUpdate,
+    /// Aggregate the fields by their aggregate functions.
+    Aggregation,
+    /// Keep the first record.
+    FirstRow,
+}
+
+impl MergeEngine {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MergeEngine::Deduplicate => "deduplicate",
+            MergeEngine::PartialUpdate => "partial-update",
+            MergeEngine::Aggregation => "aggregation",
+            MergeEngine::FirstRow => "first-row",
+        }
+    }
+}
+
+impl Display for MergeEngine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MergeEngine {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "deduplicate" => Ok(MergeEngine::Deduplicate),
+            "partial-update" => Ok(MergeEngine::PartialUpdate),
+            "aggregation" => Ok(MergeEngine::Aggregation),
+            "first-row" => Ok(MergeEngine::FirstRow),
+            _ => ConfigInvalidSnafu {
+                message: format!("Unknown {MERGE_ENGINE} '{s}'"),
+            }
+            .fail(),
+        }
+    }
+}
+
+/// Producer of the changelog files of primary key tables.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/CoreOptions.java#L1918>
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangelogProducer {
+    /// No changelog files, the changes are read from the data files.
+    None,
+    /// The written records are also written as the changelog.
+    Input,
+    /// The changelog is produced by comparing the results of full compactions.
+    FullCompaction,
+    /// The changelog is produced by looking up the written keys before committing.
+    Lookup,
+}
+
+impl ChangelogProducer {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangelogProducer::None => "none",
+            ChangelogProducer::Input => "input",
+            ChangelogProducer::FullCompaction => "full-compaction",
+            ChangelogProducer::Lookup => "lookup",
+        }
+    }
+}
+
+impl Display for ChangelogProducer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ChangelogProducer {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Ok(ChangelogProducer::None),
+            "input" => Ok(ChangelogProducer::Input),
+            "full-compaction" => Ok(ChangelogProducer::FullCompaction),
+            "lookup" => Ok(ChangelogProducer::Lookup),
+            _ => ConfigInvalidSnafu {
+                message: format!("Unknown {CHANGELOG_PRODUCER} '{s}'"),
+            }
+            .fail(),
+        }
+    }
+}
+
+/// Parse the memory size like `128 mb` into bytes, units are case insensitive and bytes by default.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/options/MemorySize.java>
+fn parse_memory_size(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let multiplier: i64 = match unit.trim().to_lowercase().as_str() {
+        "" | "b" | "bytes" => 1,
+        "k" | "kb" | "kibibytes" => 1 << 10,
+        "m" | "mb" | "mebibytes" => 1 << 20,
+        "g" | "gb" | "gibibytes" => 1 << 30,
+        "t" | "tb" | "tebibytes" => 1 << 40,
+        _ => return None,
+    };
+    number.parse::<i64>().ok()?.checked_mul(multiplier)
+}
+
+/// Parse the duration like `30 min` or `1h`, units are case insensitive and milliseconds by
+/// default.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/utils/TimeUtils.java>
+pub(crate) fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    let split = text
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number = number.parse::<u64>().ok()?;
+    match unit.trim().to_lowercase().as_str() {
+        "ns" | "nano" | "nanos" | "nanosecond" | "nanoseconds" => {
+            Some(Duration::from_nanos(number))
+        }
+        "us" | "micro" | "micros" | "microsecond" | "microseconds" => {
+            Some(Duration::from_micros(number))
+        }
+        "" | "ms" | "milli" | "millis" | "millisecond" | "milliseconds" => {
+            Some(Duration::from_millis(number))
+        }
+        "s" | "sec" | "secs" | "second" | "seconds" => Some(Duration::from_secs(number)),
+        "m" | "min" | "minute" | "minutes" => Some(Duration::from_secs(number.checked_mul(60)?)),
+        "h" | "hour" | "hours" => Some(Duration::from_secs(number.checked_mul(60 * 60)?)),
+        "d" | "day" | "days" => Some(Duration::from_secs(number.checked_mul(24 * 60 * 60)?)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_core_options() {
+        let options = HashMap::from([
+            (BUCKET.to_string(), "4".to_string()),
+            (BRANCH.to_string(), "b".to_string()),
+            (BUCKET_KEY.to_string(), "a, b".to_string()),
+            (FILE_FORMAT.to_string(), "Parquet".to_string()),
+            (CSV_FIELD_DELIMITER.to_string(), "\\t".to_string()),
+            (WRITE_MAX_WRITERS.to_string(), "2".to_string()),
+            (DELETION_VECTORS_ENABLED.to_string(), "true".to_string()),
+            (FILE_INDEX_READ_ENABLED.to_string(), "false".to_string()),
+            (
+                "file-index.bloom-filter.columns".to_string(),
+                "a, b".to_string(),
+            ),
+            (
+                "file-index.bloom-filter.a.fpp".to_string(),
+                "0.01".to_string(),
+            ),
+            ("file-index.bitmap.columns".to_string(), "c".to_string()),
+            (
+                FILE_INDEX_IN_MANIFEST_THRESHOLD.to_string(),
+                "1 kb".to_string(),
+            ),
+            (
+                "partial-update.ignore-delete".to_string(),
+                "true".to_string(),
+            ),
+            (SEQUENCE_FIELD.to_string(), "a, b".to_string()),
+            ("fields.g_1.sequence-group".to_string(), "a, b".to_string()),
+            ("fields.g_0.sequence-group".to_string(), "c".to_string()),
+            ("fields.a.aggregate-function".to_string(), "sum".to_string()),
+            ("fields.a.ignore-retract".to_string(), "true".to_string()),
+            (
+                "fields.default-aggregate-function".to_string(),
+                "max".to_string(),
+            ),
+            (SOURCE_SPLIT_TARGET_SIZE.to_string(), "64 MB".to_string()),
+            (SOURCE_SPLIT_OPEN_FILE_COST.to_string(), "1024".to_string()),
+            (TARGET_FILE_SIZE.to_string(), "1 kb".to_string()),
+            (MANIFEST_MERGE_MIN_COUNT.to_string(), "2".to_string()),
+            (
+                MANIFEST_FULL_COMPACTION_THRESHOLD_SIZE.to_string(),
+                "1 mb".to_string(),
+            ),
+            (FILE_COMPRESSION.to_string(), "SNAPPY".to_string()),
+            (FILE_COMPRESSION_ZSTD_LEVEL.to_string(), "9".to_string()),
+            (CHANGELOG_PRODUCER.to_string(), "Lookup".to_string()),
+            (
+                CHANGELOG_PRODUCER_ROW_DEDUPLICATE.to_string(),
+                "true".to_string(),
+            ),
+            (SCAN_MODE.to_string(), "From-Snapshot".to_string()),
+            (SCAN_SNAPSHOT_ID.to_string(), "3".to_string()),
+            (SCAN_TAG_NAME.to_string(), "t".to_string()),
+            (SCAN_TIMESTAMP_MILLIS.to_string(), "1000".to_string()),
+            (INCREMENTAL_BETWEEN.to_string(), "t1, t2".to_string()),
+            (
+                INCREMENTAL_BETWEEN_SCAN_MODE.to_string(),
+                "Delta".to_string(),
+            ),
+            (
+                INCREMENTAL_BETWEEN_TIMESTAMP.to_string(),
+                "1000,2000".to_string(),
+            ),
+            (CONSUMER_ID.to_string(), "c".to_string()),
+            (CONSUMER_EXPIRATION_TIME.to_string(), "2 h".to_string()),
+            (SNAPSHOT_NUM_RETAINED_MIN.to_string(), "1".to_string()),
+            (SNAPSHOT_TIME_RETAINED.to_string(), "10 s".to_string()),
+            (WRITE_ONLY.to_string(), "true".to_string()),
+            (NUM_LEVELS.to_string(), "3".to_string()),
+            (
+                NUM_SORTED_RUN_COMPACTION_TRIGGER.to_string(),
+                "2".to_string(),
+            ),
+            (COMPACTION_SIZE_RATIO.to_string(), "10".to_string()),
+            (PARTITION_EXPIRATION_TIME.to_string(), "7 d".to_string()),
+            (
+                PARTITION_TIMESTAMP_FORMATTER.to_string(),
+                "yyyyMMdd".to_string(),
+            ),
+            (PARTITION_TIMESTAMP_PATTERN.to_string(), "$dt".to_string()),
+            (METADATA_STATS_MODE.to_string(), "Counts".to_string()),
+            ("fields.a.stats-mode".to_string(), "Full".to_string()),
+            (READ_COALESCE_ENABLED.to_string(), "false".to_string()),
+            (READ_COALESCE_MAX_GAP.to_string(), "64 kb".to_string()),
+            (READ_COALESCE_MAX_SIZE.to_string(), "1 mb".to_string()),
+            (READ_PREFETCH_RANGES.to_string(), "4".to_string()),
+        ]);
+        let core_options = CoreOptions::new(&options);
+        assert_eq!(core_options.bucket(), 4);
+        assert_eq!(core_options.branch(), "b");
+        assert_eq!(core_options.bucket_key(), vec!["a", "b"]);
+        assert_eq!(core_options.file_format(), "parquet");
+        assert_eq!(core_options.csv_field_delimiter(), b'\t');
+        assert_eq!(core_options.write_max_writers(), Some(2));
+        assert_eq!(
+            core_options.merge_engine().unwrap(),
+            MergeEngine::Deduplicate
+        );
+        assert!(core_options.deletion_vectors_enabled());
+        assert!(!core_options.file_index_read_enabled());
+        assert_eq!(
+            core_options.file_index_columns(),
+            vec![("bitmap", vec!["c"]), ("bloom-filter", vec!["a", "b"])]
+        );
+        assert_eq!(
+            core_options.file_index_option("bloom-filter", "a", "fpp"),
+            Some("0.01")
+        );
+        assert_eq!(core_options.file_index_in_manifest_threshold(), 1024);
+        assert!(core_options.ignore_delete());
+        assert_eq!(core_options.sequence_field(), vec!["a", "b"]);
+        assert_eq!(
+            core_options.sequence_groups(),
+            vec![("g_0", vec!["c"]), ("g_1", vec!["a", "b"])]
+        );
+        assert_eq!(core_options.field_aggregate_function("a"), Some("sum"));
+        assert_eq!(core_options.field_aggregate_function("b"), Some("max"));
+        assert!(core_options.field_ignore_retract("a"));
+        assert!(!core_options.field_ignore_retract("b"));
+        assert_eq!(core_options.field_list_agg_delimiter("b"), ",");
+        assert_eq!(core_options.split_target_size(), 64 * 1024 * 1024);
+        assert_eq!(core_options.split_open_file_cost(), 1024);
+        assert_eq!(core_options.target_file_size(), 1024);
+        assert_eq!(core_options.manifest_merge_min_count(), 2);
+        assert_eq!(
+            core_options.manifest_full_compaction_threshold_size(),
+            1024 * 1024
+        );
+        assert_eq!(core_options.file_compression(), "snappy");
+        assert_eq!(core_options.file_compression_zstd_level(), 9);
+        assert_eq!(
+            core_options.changelog_producer().unwrap(),
+            ChangelogProducer::Lookup
+        );
+        assert!(core_options.changelog_producer_row_deduplicate());
+        assert_eq!(core_options.scan_mode(), "from-snapshot");
+        assert_eq!(core_options.scan_snapshot_id(), Some(3));
+        assert_eq!(core_options.scan_tag_name(), Some("t"));
+        assert_eq!(core_options.scan_timestamp_millis(), Some(1000));
+        assert_eq!(core_options.incremental_between(), Some(("t1", "t2")));
+        assert_eq!(core_options.incremental_between_scan_mode(), "delta");
+        assert_eq!(
+            core_options.incremental_between_timestamp(),
+            Some((1000, 2000))
+        );
+        assert_eq!(core_options.consumer_id(), Some("c"));
+        assert_eq!(
+            core_options.consumer_expiration_time(),
+            Some(Duration::from_secs(2 * 60 * 60))
+        );
+        assert_eq!(core_options.snapshot_num_retained_min(), 1);
+        assert_eq!(
+            core_options.snapshot_time_retained(),
+            Duration::from_secs(10)
+        );
+        assert!(core_options.write_only());
+        assert_eq!(core_options.num_levels(), 3);
+        assert_eq!(core_options.num_sorted_run_compaction_trigger(), 2);
+        assert_eq!(core_options.compaction_size_ratio(), 10);
+        assert_eq!(
+            core_options.partition_expiration_time(),
+            Some(Duration::from_secs(7 * 24 * 60 * 60))
+        );
+        assert_eq!(
+            core_options.partition_timestamp_formatter(),
+            Some("yyyyMMdd")
+        );
+        assert_eq!(core_options.partition_timestamp_pattern(), Some("$dt"));
+        assert_eq!(core_options.field_stats_mode("a"), "full");
+        assert_eq!(core_options.field_stats_mode("b"), "counts");
+        assert!(!core_options.read_coalesce_enabled());
+        assert_eq!(core_options.read_coalesce_max_gap(), 64 * 1024);
+        assert_eq!(core_options.read_coalesce_max_size(), 1024 * 1024);
+        assert_eq!(core_options.read_prefetch_ranges(), 4);
+
+        let empty = HashMap::new();
+        let core_options = CoreOptions::new(&empty);
+        assert_eq!(core_options.bucket(), -1);
+        assert_eq!(core_options.branch(), "main");
+        assert!(core_options.bucket_key().is_empty());
+        assert_eq!(core_options.dynamic_bucket_target_row_num(), 2_000_000);
+        assert!(!core_options.deletion_vectors_enabled());
+        assert!(!core_options.ignore_delete());
+        assert_eq!(core_options.split_target_size(), 128 * 1024 * 1024);
+        assert_eq!(core_options.write_buffer_size(), 256 * 1024 * 1024);
+        assert!(!core_options.write_buffer_spillable());
+        assert_eq!(core_options.num_levels(), 6);
+        assert_eq!(core_options.num_sorted_run_compaction_trigger(), 5);
+        assert_eq!(
+            core_options.compaction_max_size_amplification_percent(),
+            200
+        );
+        assert_eq!(core_options.compaction_size_ratio(), 1);
+        assert_eq!(core_options.manifest_target_file_size(), 8 * 1024 * 1024);
+        assert_eq!(
+            core_options.manifest_full_compaction_threshold_size(),
+            16 * 1024 * 1024
+        );
+        assert_eq!(core_options.commit_max_retries(), 10);
+        assert_eq!(core_options.file_format(), "orc");
+        assert_eq!(core_options.csv_field_delimiter(), b',');
+        assert!(!core_options.csv_include_header());
+        assert_eq!(core_options.write_max_writers(), None);
+        assert_eq!(core_options.file_compression(), "zstd");
+        assert_eq!(core_options.file_compression_zstd_level(), 1);
+        assert_eq!(
+            core_options.changelog_producer().unwrap(),
+            ChangelogProducer::None
+        );
+        assert!(!core_options.changelog_producer_row_deduplicate());
+        assert_eq!(core_options.scan_mode(), "default");
+        assert_eq!(core_options.scan_snapshot_id(), None);
+        assert_eq!(core_options.incremental_between(), None);
+        assert_eq!(core_options.incremental_between_scan_mode(), "auto");
+        assert_eq!(core_options.consumer_expiration_time(), None);
+        assert_eq!(core_options.snapshot_num_retained_min(), 10);
+        assert_eq!(core_options.snapshot_num_retained_max(), i32::MAX as usize);
+        assert_eq!(
+            core_options.snapshot_time_retained(),
+            Duration::from_secs(3600)
+        );
+        assert_eq!(core_options.snapshot_expire_limit(), 10);
+        assert!(!core_options.write_only());
+        assert_eq!(
+            core_options.partition_default_name(),
+            "__DEFAULT_PARTITION__"
+        );
+        assert_eq!(core_options.partition_expiration_time(), None);
+        assert_eq!(core_options.partition_timestamp_formatter(), None);
+        assert_eq!(core_options.field_stats_mode("a"), "truncate(16)");
+        assert!(core_options.read_coalesce_enabled());
+        assert_eq!(core_options.read_coalesce_max_gap(), 1024 * 1024);
+        assert_eq!(core_options.read_coalesce_max_size(), 8 * 1024 * 1024);
+        assert_eq!(core_options.read_prefetch_ranges(), 1);
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("500"), Some(Duration::from_millis(500)));
+        assert_eq!(parse_duration("30 s"), Some(Duration::from_secs(30)));
+        assert_eq!(parse_duration("10min"), Some(Duration::from_secs(600)));
+        assert_eq!(parse_duration("1 D"), Some(Duration::from_secs(86400)));
+        assert_eq!(parse_duration("1 week"), None);
+    }
+
+    #[test]
+    fn test_merge_engine_and_changelog_producer() {
+        let options = HashMap::from([
+            (MERGE_ENGINE.to_string(), "Partial-Update".to_string()),
+            (
+                CHANGELOG_PRODUCER.to_string(),
+                "full-compaction".to_string(),
+            ),
+        ]);
+        let core_options = CoreOptions::new(&options);
+        assert_eq!(
+            core_options.merge_engine().unwrap(),
+            MergeEngine::PartialUpdate
+        );
+        assert_eq!(
+            core_options.changelog_producer().unwrap().to_string(),
+            "full-compaction"
+        );
+
+        let options = HashMap::from([
+            (MERGE_ENGINE.to_string(), "last-row".to_string()),
+            (CHANGELOG_PRODUCER.to_string(), "always".to_string()),
+        ]);
+        let core_options = CoreOptions::new(&options);
+        assert!(core_options.merge_engine().is_err());
+        assert!(core_options.changelog_producer().is_err());
+    }
+}
@@ -16,44 +16,17 @@
 // under the License.
 
 use crate::spec::stats::BinaryTableStats;
+use crate::spec::FileSource;
 use chrono::serde::ts_milliseconds::deserialize as from_millis;
 use chrono::serde::ts_milliseconds::serialize as to_millis;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
-pub const EMPTY_BINARY_ROW: BinaryRow = BinaryRow::new(0);
-
-/// An implementation of InternalRow.
-///
-/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/data/BinaryRow.java>
-#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct BinaryRow {
-    arity: i32,
-    null_bits_size_in_bytes: i32,
-}
-
-impl BinaryRow {
-    pub const HEADER_SIZE_IN_BYTES: i32 = 8;
-    pub const fn cal_bit_set_width_in_bytes(arity: i32) -> i32 {
-        ((arity + 63 + Self::HEADER_SIZE_IN_BYTES) / 64) * 8
-    }
-    pub const fn cal_fix_part_size_in_bytes(arity: i32) -> i32 {
-        Self::cal_bit_set_width_in_bytes(arity) + 8 * arity
-    }
-    pub const fn new(arity: i32) -> Self {
-        Self {
-            arity,
-            null_bits_size_in_bytes: (arity + 7) / 8,
-        }
-    }
-}
-
 /// Metadata of a data file.
 ///
 /// Impl References: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/io/DataFileMeta.java>
-#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DataFileMeta {
     #[serde(rename = "_FILE_NAME")]
@@ -87,19 +60,119 @@ pub struct DataFileMeta {
         deserialize_with = "from_millis"
     )]
     pub creation_time: DateTime<Utc>,
-    #[serde(rename = "_DELETE_ROW_COUNT")]
     // rowCount = add_row_count + delete_row_count.
+    #[serde(rename = "_DELETE_ROW_COUNT", default)]
     pub delete_row_count: Option<i64>,
     // file index filter bytes, if it is small, store in data file meta
-    #[serde(rename = "_EMBEDDED_FILE_INDEX", with = "serde_bytes")]
+    #[serde(rename = "_EMBEDDED_FILE_INDEX", with = "serde_bytes", default)]
     pub embedded_index: Option<Vec<u8>>,
+    // whether the file is produced by appending or compaction, none for files written by paimon < 0.9
+    #[serde(
+        rename = "_FILE_SOURCE",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub file_source: Option<FileSource>,
+    // the columns the value stats are collected for, none means all columns
+    #[serde(
+        rename = "_VALUE_STATS_COLS",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub value_stats_cols: Option<Vec<String>>,
 }
 
 impl Display for DataFileMeta {
-    fn fmt(&self, _: &mut Formatter<'_>) -> std::fmt::Result {
-        todo!()
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{{fileName: {}, fileSize: {}, rowCount: {}, embeddedIndex: {:?}, \
+            minKey: {:?}, maxKey: {:?}, keyStats: {:?}, valueStats: {:?}, \
+            minSequenceNumber: {}, maxSequenceNumber: {}, schemaId: {}, level: {}, \
+            extraFiles: {:?}, creationTime: {}, deleteRowCount: {:?}, fileSource: {:?}, \
+            valueStatsCols: {:?}}}",
+            self.file_name,
+            self.file_size,
+            self.row_count,
+            self.embedded_index,
+            self.min_key,
+            self.max_key,
+            self.key_stats,
+            self.value_stats,
+            self.min_sequence_number,
+            self.max_sequence_number,
+            self.schema_id,
+            self.level,
+            self.extra_files,
+            self.creation_time,
+            self.delete_row_count,
+            self.file_source,
+            self.value_stats_cols,
+        )
     }
 }
 
-#[allow(dead_code)]
-impl DataFileMeta {}
+impl DataFileMeta {
+    /// Get the number of added rows in this file.
+    ///
+    /// Files written by old versions don't record `delete_row_count`, all rows are treated as added.
+    pub fn add_row_count(&self) -> i64 {
+        self.row_count - self.delete_row_count.unwrap_or(0)
+    }
+
+    /// Get the format of this file, inferred from the extension of the file name.
+    pub fn file_format(&self) -> Option<&str> {
+        self.file_name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data_file_meta() -> DataFileMeta {
+        DataFileMeta {
+            file_name: "data-6fb1bd3b-6fb0-4b2c-a55d-a6e4b2a8fcc1-0.orc".to_string(),
+            file_size: 1024,
+            row_count: 100,
+            min_key: vec![],
+            max_key: vec![],
+            key_stats: BinaryTableStats::new(vec![], vec![], vec![]),
+            value_stats: BinaryTableStats::new(vec![], vec![], vec![]),
+            min_sequence_number: 0,
+            max_sequence_number: 99,
+            schema_id: 0,
+            level: 0,
+            extra_files: vec![],
+            creation_time: DateTime::<Utc>::from_timestamp_millis(1725614755039).unwrap(),
+            delete_row_count: Some(10),
+            embedded_index: None,
+            file_source: Some(FileSource::Append),
+            value_stats_cols: None,
+        }
+    }
+
+    #[test]
+    fn test_data_file_meta_helpers() {
+        let meta = test_data_file_meta();
+        assert_eq!(meta.add_row_count(), 90);
+        assert_eq!(meta.file_format(), Some("orc"));
+
+        let meta = DataFileMeta {
+            delete_row_count: None,
+            ..test_data_file_meta()
+        };
+        assert_eq!(meta.add_row_count(), 100);
+    }
+
+    #[test]
+    fn test_data_file_meta_avro_serde() {
+        let meta = test_data_file_meta();
+        let value = apache_avro::to_value(&meta).unwrap();
+        let decoded: DataFileMeta = apache_avro::from_value(&value).unwrap();
+        assert_eq!(decoded, meta);
+        assert!(meta.to_string().contains("rowCount: 100"));
+    }
+}
@@ -0,0 +1,216 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+/// A typed value of a [`DataType`](crate::spec::DataType), decoded from or encoded into a [`BinaryRow`](crate::spec::BinaryRow).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Datum {
+    Boolean(bool),
+    TinyInt(i8),
+    SmallInt(i16),
+    Int(i32),
+    BigInt(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Bytes(Vec<u8>),
+    /// Number of days since epoch.
+    Date(i32),
+    /// Number of milliseconds of the day.
+    Time(i32),
+    /// Milliseconds since epoch plus the nanoseconds within the millisecond.
+    Timestamp {
+        millis: i64,
+        nanos: i32,
+    },
+    /// Milliseconds since epoch plus the nanoseconds within the millisecond.
+    LocalZonedTimestamp {
+        millis: i64,
+        nanos: i32,
+    },
+    Decimal {
+        unscaled: i128,
+        precision: u32,
+        scale: u32,
+    },
+}
+
+impl Display for Datum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Datum::Boolean(v) => write!(f, "{v}"),
+            Datum::TinyInt(v) => write!(f, "{v}"),
+            Datum::SmallInt(v) => write!(f, "{v}"),
+            Datum::Int(v) => write!(f, "{v}"),
+            Datum::BigInt(v) => write!(f, "{v}"),
+            Datum::Float(v) => write!(f, "{v}"),
+            Datum::Double(v) => write!(f, "{v}"),
+            Datum::String(v) => write!(f, "{v}"),
+            Datum::Bytes(v) => write!(f, "{v:?}"),
+            Datum::Date(v) => match chrono::NaiveDate::from_num_days_from_ce_opt(v + 719_163) {
+                Some(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+                None => write!(f, "{v}"),
+            },
+            Datum::Time(v) => write!(f, "{v}"),
+            Datum::Timestamp { millis, nanos } | Datum::LocalZonedTimestamp { millis, nanos } => {
+                match chrono::DateTime::from_timestamp_millis(*millis) {
+                    Some(ts) => {
+                        let ts = ts + chrono::Duration::nanoseconds(*nanos as i64);
+                        write!(f, "{}", ts.naive_utc().format("%Y-%m-%d %H:%M:%S%.f"))
+                    }
+                    None => write!(f, "{millis}"),
+                }
+            }
+            Datum::Decimal {
+                unscaled, scale, ..
+            } => {
+                if *scale == 0 {
+                    return write!(f, "{unscaled}");
+                }
+                let digits = unscaled.unsigned_abs().to_string();
+                let scale = *scale as usize;
+                let digits = format!("{digits:0>width$}", width = scale + 1);
+                let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+                let sign = if *unscaled < 0 { "-" } else { "" };
+                write!(f, "{sign}{int_part}.{frac_part}")
+            }
+        }
+    }
+}
+
+/// Datums are only comparable with datums of the same type, decimals of different scales are
+/// compared by their values.
+impl PartialOrd for Datum {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Datum::Boolean(a), Datum::Boolean(b)) => a.partial_cmp(b),
+            (Datum::TinyInt(a), Datum::TinyInt(b)) => a.partial_cmp(b),
+            (Datum::SmallInt(a), Datum::SmallInt(b)) => a.partial_cmp(b),
+            (Datum::Int(a), Datum::Int(b)) => a.partial_cmp(b),
+            (Datum::BigInt(a), Datum::BigInt(b)) => a.partial_cmp(b),
+            (Datum::Float(a), Datum::Float(b)) => a.partial_cmp(b),
+            (Datum::Double(a), Datum::Double(b)) => a.partial_cmp(b),
+            (Datum::String(a), Datum::String(b)) => a.partial_cmp(b),
+            (Datum::Bytes(a), Datum::Bytes(b)) => a.partial_cmp(b),
+            (Datum::Date(a), Datum::Date(b)) => a.partial_cmp(b),
+            (Datum::Time(a), Datum::Time(b)) => a.partial_cmp(b),
+            (
+                Datum::Timestamp {
+                    millis: a,
+                    nanos: a_nanos,
+                },
+                Datum::Timestamp {
+                    millis: b,
+                    nanos: b_nanos,
+                },
+            )
+            | (
+                Datum::LocalZonedTimestamp {
+                    millis: a,
+                    nanos: a_nanos,
+                },
+                Datum::LocalZonedTimestamp {
+                    millis: b,
+                    nanos: b_nanos,
+                },
+            ) => (a, a_nanos).partial_cmp(&(b, b_nanos)),
+            (
+                Datum::Decimal {
+                    unscaled: a,
+                    scale: a_scale,
+                    ..
+                },
+                Datum::Decimal {
+                    unscaled: b,
+                    scale: b_scale,
+                    ..
+                },
+            ) => {
+                let scale = *a_scale.max(b_scale);
+                let a = a.checked_mul(10i128.checked_pow(scale - a_scale)?)?;
+                let b = b.checked_mul(10i128.checked_pow(scale - b_scale)?)?;
+                a.partial_cmp(&b)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_datum_display() {
+        assert_eq!(Datum::Date(19723).to_string(), "2024-01-01");
+        assert_eq!(
+            Datum::Decimal {
+                unscaled: -1234,
+                precision: 10,
+                scale: 3
+            }
+            .to_string(),
+            "-1.234"
+        );
+        assert_eq!(
+            Datum::Decimal {
+                unscaled: 5,
+                precision: 10,
+                scale: 2
+            }
+            .to_string(),
+            "0.05"
+        );
+        assert_eq!(
+            Datum::Timestamp {
+                millis: 1704067200001,
+                nanos: 0
+            }
+            .to_string(),
+            "2024-01-01 00:00:00.001"
+        );
+    }
+
+    #[test]
+    fn test_datum_compare() {
+        assert!(Datum::Int(1) < Datum::Int(2));
+        assert!(Datum::String("a".to_string()) < Datum::String("b".to_string()));
+        assert_eq!(Datum::Int(1).partial_cmp(&Datum::BigInt(1)), None);
+        assert!(
+            Datum::Decimal {
+                unscaled: 15,
+                precision: 10,
+                scale: 1
+            } > Datum::Decimal {
+                unscaled: 149,
+                precision: 10,
+                scale: 2
+            }
+        );
+        assert!(
+            Datum::Timestamp {
+                millis: 1,
+                nanos: 1
+            } > Datum::Timestamp {
+                millis: 1,
+                nanos: 0
+            }
+        );
+    }
+}
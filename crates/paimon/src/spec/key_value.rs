@@ -0,0 +1,125 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::DataCorruptedSnafu;
+use crate::spec::{BigIntType, DataField, DataType, TinyIntType};
+
+/// Prefix of the names of key fields in data files of primary key tables.
+pub const KEY_FIELD_PREFIX: &str = "_KEY_";
+
+/// Start of the ids of key fields, the id of a key field is the id of the value field plus this.
+pub const KEY_FIELD_ID_START: i32 = i32::MAX / 2;
+
+/// Name of the sequence number field in data files of primary key tables.
+pub const SEQUENCE_NUMBER: &str = "_SEQUENCE_NUMBER";
+
+/// Id of the sequence number field in data files of primary key tables.
+pub const SEQUENCE_NUMBER_FIELD_ID: i32 = i32::MAX - 1;
+
+/// Name of the value kind field in data files of primary key tables.
+pub const VALUE_KIND: &str = "_VALUE_KIND";
+
+/// Id of the value kind field in data files of primary key tables.
+pub const VALUE_KIND_FIELD_ID: i32 = i32::MAX - 2;
+
+/// Kind of a row, describing the change of the row in the changelog.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/types/RowKind.java>
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Hash)]
+#[repr(i8)]
+pub enum RowKind {
+    Insert = 0,
+    UpdateBefore = 1,
+    UpdateAfter = 2,
+    Delete = 3,
+}
+
+impl RowKind {
+    /// Get the row kind of the byte value stored in data files.
+    pub fn from_value(value: i8) -> crate::Result<RowKind> {
+        match value {
+            0 => Ok(RowKind::Insert),
+            1 => Ok(RowKind::UpdateBefore),
+            2 => Ok(RowKind::UpdateAfter),
+            3 => Ok(RowKind::Delete),
+            _ => DataCorruptedSnafu {
+                message: format!("unsupported byte value {value} for row kind"),
+            }
+            .fail(),
+        }
+    }
+
+    /// Get the byte value stored in data files.
+    pub fn to_value(self) -> i8 {
+        self as i8
+    }
+
+    /// Get the short string of the row kind, like `+I`.
+    pub fn short_string(&self) -> &'static str {
+        match self {
+            RowKind::Insert => "+I",
+            RowKind::UpdateBefore => "-U",
+            RowKind::UpdateAfter => "+U",
+            RowKind::Delete => "-D",
+        }
+    }
+
+    /// Whether the row is added, otherwise retracted.
+    pub fn is_add(&self) -> bool {
+        matches!(self, RowKind::Insert | RowKind::UpdateAfter)
+    }
+}
+
+/// Get the key field in data files of primary key tables of the value field.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/KeyValue.java>
+pub fn key_field(field: &DataField) -> DataField {
+    DataField::new(
+        KEY_FIELD_ID_START + field.id(),
+        format!("{KEY_FIELD_PREFIX}{}", field.name()),
+        field.data_type().clone(),
+    )
+}
+
+/// Get the sequence number field in data files of primary key tables.
+pub fn sequence_number_field() -> DataField {
+    DataField::new(
+        SEQUENCE_NUMBER_FIELD_ID,
+        SEQUENCE_NUMBER.to_string(),
+        DataType::BigInt(BigIntType::with_nullable(false)),
+    )
+}
+
+/// Get the value kind field in data files of primary key tables.
+pub fn value_kind_field() -> DataField {
+    DataField::new(
+        VALUE_KIND_FIELD_ID,
+        VALUE_KIND.to_string(),
+        DataType::TinyInt(TinyIntType::with_nullable(false)),
+    )
+}
+
+/// Get the fields in data files of primary key tables, the key fields, the sequence number,
+/// the value kind and then the value fields.
+pub fn key_value_fields(key_fields: &[DataField], value_fields: &[DataField]) -> Vec<DataField> {
+    key_fields
+        .iter()
+        .map(key_field)
+        .chain([sequence_number_field(), value_kind_field()])
+        .chain(value_fields.iter().cloned())
+        .collect()
+}
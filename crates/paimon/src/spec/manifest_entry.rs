@@ -15,10 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::error::DataCorruptedSnafu;
 use crate::spec::manifest_common::FileKind;
 use crate::spec::DataFileMeta;
+use crate::Result;
+use indexmap::IndexMap;
 use serde::Deserialize;
-use serde_with::serde_derive::Serialize;
+use serde::Serialize;
 
 /// The same {@link Identifier} indicates that the {@link ManifestEntry} refers to the same data file.
 ///
@@ -33,7 +36,7 @@ pub struct Identifier {
 
 /// Entry of a manifest file, representing an addition / deletion of a data file.
 /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/manifest/ManifestEntry.java>
-#[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ManifestEntry {
     #[serde(rename = "_KIND")]
     kind: FileKind,
@@ -54,37 +57,36 @@ pub struct ManifestEntry {
     version: i32,
 }
 
-#[allow(dead_code)]
 impl ManifestEntry {
-    fn kind(&self) -> &FileKind {
+    pub fn kind(&self) -> &FileKind {
         &self.kind
     }
 
-    fn partition(&self) -> &Vec<u8> {
+    pub fn partition(&self) -> &Vec<u8> {
         &self.partition
     }
 
-    fn bucket(&self) -> i32 {
+    pub fn bucket(&self) -> i32 {
         self.bucket
     }
 
-    fn level(&self) -> i32 {
+    pub fn level(&self) -> i32 {
         self.file.level
     }
 
-    fn file_name(&self) -> &str {
+    pub fn file_name(&self) -> &str {
         &self.file.file_name
     }
 
-    fn min_key(&self) -> &Vec<u8> {
+    pub fn min_key(&self) -> &Vec<u8> {
         &self.file.min_key
     }
 
-    fn max_key(&self) -> &Vec<u8> {
+    pub fn max_key(&self) -> &Vec<u8> {
         &self.file.max_key
     }
 
-    fn identifier(&self) -> Identifier {
+    pub fn identifier(&self) -> Identifier {
         Identifier {
             partition: self.partition.clone(),
             bucket: self.bucket,
@@ -101,6 +103,10 @@ impl ManifestEntry {
         &self.file
     }
 
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
     pub fn new(
         kind: FileKind,
         partition: Vec<u8>,
@@ -118,4 +124,86 @@ impl ManifestEntry {
             version,
         }
     }
+
+    /// Merge entries so that an added file deleted later is dropped, keeping the order of first appearance.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/manifest/FileEntry.java>
+    pub fn merge_entries(
+        entries: impl IntoIterator<Item = ManifestEntry>,
+    ) -> Result<Vec<ManifestEntry>> {
+        let mut merged: IndexMap<Identifier, ManifestEntry> = IndexMap::new();
+        for entry in entries {
+            let identifier = entry.identifier();
+            match entry.kind {
+                FileKind::Add => {
+                    if merged.contains_key(&identifier) {
+                        return DataCorruptedSnafu {
+                            message: format!(
+                                "trying to add file {} which is already added",
+                                identifier.file_name
+                            ),
+                        }
+                        .fail();
+                    }
+                    merged.insert(identifier, entry);
+                }
+                FileKind::Delete => {
+                    // a file added and then deleted cancels out, otherwise keep the delete
+                    // so that it can be applied on entries from other manifests
+                    if merged.shift_remove(&identifier).is_none() {
+                        merged.insert(identifier, entry);
+                    }
+                }
+            }
+        }
+        Ok(merged.into_values().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+
+    fn entry(kind: FileKind, file_name: &str) -> ManifestEntry {
+        let file = DataFileMeta {
+            file_size: 1024,
+            row_count: 10,
+            max_sequence_number: 9,
+            delete_row_count: Some(0),
+            ..test_utils::data_file(file_name)
+        };
+        ManifestEntry::new(kind, vec![], 0, 1, file, 2)
+    }
+
+    #[test]
+    fn test_merge_entries() {
+        let merged = ManifestEntry::merge_entries(vec![
+            entry(FileKind::Add, "a.parquet"),
+            entry(FileKind::Add, "b.parquet"),
+            entry(FileKind::Delete, "a.parquet"),
+            entry(FileKind::Delete, "c.parquet"),
+        ])
+        .unwrap();
+        let names: Vec<_> = merged
+            .iter()
+            .map(|e| (e.kind().clone(), e.file_name()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                (FileKind::Add, "b.parquet"),
+                (FileKind::Delete, "c.parquet")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_entries_duplicated_add() {
+        let result = ManifestEntry::merge_entries(vec![
+            entry(FileKind::Add, "a.parquet"),
+            entry(FileKind::Add, "a.parquet"),
+        ]);
+        assert!(matches!(result, Err(crate::Error::DataCorrupted { .. })));
+    }
 }
@@ -50,6 +50,22 @@ pub struct ManifestFileMeta {
     /// schema id when writing this manifest file.
     #[serde(rename = "_SCHEMA_ID")]
     schema_id: i64,
+
+    /// minimum bucket of the entries in this manifest, none if written by older versions.
+    #[serde(rename = "_MIN_BUCKET", default)]
+    min_bucket: Option<i32>,
+
+    /// maximum bucket of the entries in this manifest, none if written by older versions.
+    #[serde(rename = "_MAX_BUCKET", default)]
+    max_bucket: Option<i32>,
+
+    /// minimum level of the entries in this manifest, none if written by older versions.
+    #[serde(rename = "_MIN_LEVEL", default)]
+    min_level: Option<i32>,
+
+    /// maximum level of the entries in this manifest, none if written by older versions.
+    #[serde(rename = "_MAX_LEVEL", default)]
+    max_level: Option<i32>,
 }
 
 impl ManifestFileMeta {
@@ -88,6 +104,30 @@ impl ManifestFileMeta {
         self.schema_id
     }
 
+    /// Get the minimum bucket of the entries in this manifest file.
+    #[inline]
+    pub fn min_bucket(&self) -> Option<i32> {
+        self.min_bucket
+    }
+
+    /// Get the maximum bucket of the entries in this manifest file.
+    #[inline]
+    pub fn max_bucket(&self) -> Option<i32> {
+        self.max_bucket
+    }
+
+    /// Get the minimum level of the entries in this manifest file.
+    #[inline]
+    pub fn min_level(&self) -> Option<i32> {
+        self.min_level
+    }
+
+    /// Get the maximum level of the entries in this manifest file.
+    #[inline]
+    pub fn max_level(&self) -> Option<i32> {
+        self.max_level
+    }
+
     /// Get the version of this manifest file
     #[inline]
     pub fn version(&self) -> i32 {
@@ -111,8 +151,26 @@ impl ManifestFileMeta {
             num_deleted_files,
             partition_stats,
             schema_id,
+            min_bucket: None,
+            max_bucket: None,
+            min_level: None,
+            max_level: None,
         }
     }
+
+    /// Set the range of buckets of the entries in this manifest file.
+    pub fn with_bucket_range(mut self, min_bucket: i32, max_bucket: i32) -> Self {
+        self.min_bucket = Some(min_bucket);
+        self.max_bucket = Some(max_bucket);
+        self
+    }
+
+    /// Set the range of levels of the entries in this manifest file.
+    pub fn with_level_range(mut self, min_level: i32, max_level: i32) -> Self {
+        self.min_level = Some(min_level);
+        self.max_level = Some(max_level);
+        self
+    }
 }
 
 impl Display for ManifestFileMeta {
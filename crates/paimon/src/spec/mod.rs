@@ -19,15 +19,33 @@
 //!
 //! All paimon specs types are defined here.
 
+mod binary_row;
+pub use binary_row::*;
+
+mod consumer;
+pub use consumer::*;
+
+mod core_options;
+pub use core_options::*;
+
 mod data_file;
 pub use data_file::*;
 
+mod datum;
+pub use datum::*;
+
+mod key_value;
+pub use key_value::*;
+
 mod schema;
 pub use schema::*;
 
 mod schema_change;
 pub use schema_change::*;
 
+mod schema_validation;
+pub use schema_validation::*;
+
 mod snapshot;
 pub use snapshot::*;
 
@@ -38,10 +56,24 @@ mod index_file_meta;
 pub use index_file_meta::*;
 
 mod index_manifest;
+pub use index_manifest::*;
+
 mod manifest_common;
+pub use manifest_common::*;
+
 mod manifest_entry;
-mod objects_file;
+pub use manifest_entry::*;
+
+pub(crate) mod objects_file;
+
+mod statistics;
+pub use statistics::*;
+
 mod stats;
+pub use stats::*;
+
+mod type_parser;
+
 mod types;
 
 pub use types::*;
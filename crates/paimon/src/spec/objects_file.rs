@@ -17,19 +17,36 @@
 
 use crate::Error;
 use apache_avro::types::Value;
-use apache_avro::{from_value, Reader};
+use apache_avro::{from_value, to_value, Codec, Reader, Schema, Writer};
 use serde::de::DeserializeOwned;
+use serde::Serialize;
 
-#[allow(dead_code)]
 pub fn from_avro_bytes<T: DeserializeOwned>(bytes: &[u8]) -> crate::Result<Vec<T>> {
     let reader = Reader::new(bytes).map_err(Error::from)?;
+    // records are written in the nullable union of paimon-java, unwrap them so that records
+    // with flattened fields can be deserialized as maps
     let records = reader
         .collect::<Result<Vec<Value>, _>>()
-        .map_err(Error::from)?;
+        .map_err(Error::from)?
+        .into_iter()
+        .map(|record| match record {
+            Value::Union(_, record) => *record,
+            record => record,
+        })
+        .collect();
     let values = Value::Array(records);
     from_value::<Vec<T>>(&values).map_err(Error::from)
 }
 
+pub fn to_avro_bytes<T: Serialize>(schema: &Schema, records: &[T]) -> crate::Result<Vec<u8>> {
+    let mut writer = Writer::with_codec(schema, Vec::new(), Codec::Snappy);
+    for record in records {
+        let value = to_value(record)?.resolve(schema)?;
+        writer.append(value)?;
+    }
+    writer.into_inner().map_err(Error::from)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::spec::manifest_common::FileKind;
@@ -58,7 +75,11 @@ mod tests {
                     10,
                     10,
                     10,
-                    BinaryTableStats::new(value_bytes.clone(), value_bytes.clone(), vec![1, 2]),
+                    BinaryTableStats::new(
+                        value_bytes.clone(),
+                        value_bytes.clone(),
+                        vec![Some(1), Some(2)]
+                    ),
                     1
                 ),
                 ManifestFileMeta::new(
@@ -66,7 +87,11 @@ mod tests {
                     11,
                     0,
                     10,
-                    BinaryTableStats::new(value_bytes.clone(), value_bytes.clone(), vec![1, 2]),
+                    BinaryTableStats::new(
+                        value_bytes.clone(),
+                        value_bytes.clone(),
+                        vec![Some(1), Some(2)]
+                    ),
                     2
                 )
             ],
@@ -103,12 +128,12 @@ mod tests {
                         key_stats: BinaryTableStats::new(
                             value_bytes.clone(),
                             value_bytes.clone(),
-                            vec![1, 2]
+                            vec![Some(1), Some(2)]
                         ),
                         value_stats: BinaryTableStats::new(
                             value_bytes.clone(),
                             value_bytes.clone(),
-                            vec![1, 2]
+                            vec![Some(1), Some(2)]
                         ),
                         min_sequence_number: 1,
                         max_sequence_number: 100,
@@ -120,6 +145,8 @@ mod tests {
                             .unwrap(),
                         delete_row_count: Some(0),
                         embedded_index: None,
+                        file_source: None,
+                        value_stats_cols: None,
                     },
                     2
                 ),
@@ -137,12 +164,12 @@ mod tests {
                         key_stats: BinaryTableStats::new(
                             value_bytes.clone(),
                             value_bytes.clone(),
-                            vec![1, 2]
+                            vec![Some(1), Some(2)]
                         ),
                         value_stats: BinaryTableStats::new(
                             value_bytes.clone(),
                             value_bytes.clone(),
-                            vec![1, 2]
+                            vec![Some(1), Some(2)]
                         ),
                         min_sequence_number: 1,
                         max_sequence_number: 100,
@@ -154,6 +181,8 @@ mod tests {
                             .unwrap(),
                         delete_row_count: Some(1),
                         embedded_index: None,
+                        file_source: None,
+                        value_stats_cols: None,
                     },
                     2
                 ),
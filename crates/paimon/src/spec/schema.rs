@@ -15,29 +15,300 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use crate::spec::types::DataType;
+use crate::error::{JsonUnexpectedSnafu, SchemaInvalidSnafu};
+use crate::spec::types::{DataType, RowType};
+use crate::spec::CoreOptions;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use std::collections::HashMap;
+use snafu::ResultExt;
+use std::collections::{HashMap, HashSet};
+use typed_builder::TypedBuilder;
 
 /// The table schema for paimon table.
 ///
 /// Impl References: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/schema/TableSchema.java#L47>
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TypedBuilder)]
 #[serde(rename_all = "camelCase")]
 pub struct TableSchema {
-    /// version of schema for paimon
+    /// version of schema for paimon, schemas written by paimon <= 0.3 don't have it
+    #[builder(default = TableSchema::CURRENT_VERSION)]
+    #[serde(default = "default_version")]
     version: i32,
     id: i64,
     fields: Vec<DataField>,
     highest_field_id: i32,
+    #[builder(default)]
     partition_keys: Vec<String>,
+    #[builder(default)]
     primary_keys: Vec<String>,
+    #[builder(default)]
     options: HashMap<String, String>,
+    #[builder(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     comment: Option<String>,
+    #[builder(default = chrono::Utc::now().timestamp_millis())]
     time_millis: i64,
 }
 
+fn default_version() -> i32 {
+    1
+}
+
+impl TableSchema {
+    /// The version of schemas written by this implementation.
+    pub const CURRENT_VERSION: i32 = 2;
+
+    /// Parse a table schema from its json representation.
+    pub fn from_json(json: &str) -> crate::Result<TableSchema> {
+        serde_json::from_str(json).context(JsonUnexpectedSnafu {
+            message: "Failed to parse table schema",
+        })
+    }
+
+    /// Serialize this table schema into its json representation.
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string_pretty(self).context(JsonUnexpectedSnafu {
+            message: format!("Failed to serialize table schema {}", self.id),
+        })
+    }
+
+    /// Get the version of this schema.
+    #[inline]
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// Get the id of this schema.
+    #[inline]
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Get the fields of this schema.
+    #[inline]
+    pub fn fields(&self) -> &[DataField] {
+        &self.fields
+    }
+
+    /// Get the highest field id ever assigned in this table.
+    #[inline]
+    pub fn highest_field_id(&self) -> i32 {
+        self.highest_field_id
+    }
+
+    /// Get the partition keys of this schema.
+    #[inline]
+    pub fn partition_keys(&self) -> &[String] {
+        &self.partition_keys
+    }
+
+    /// Get the primary keys of this schema.
+    #[inline]
+    pub fn primary_keys(&self) -> &[String] {
+        &self.primary_keys
+    }
+
+    /// Get the options of this schema.
+    #[inline]
+    pub fn options(&self) -> &HashMap<String, String> {
+        &self.options
+    }
+
+    /// Get the comment of this schema.
+    #[inline]
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Get the creation time of this schema.
+    #[inline]
+    pub fn time_millis(&self) -> i64 {
+        self.time_millis
+    }
+
+    /// Get the names of all fields.
+    pub fn field_names(&self) -> Vec<&str> {
+        self.fields.iter().map(|f| f.name()).collect()
+    }
+
+    /// Get the primary keys without partition keys.
+    ///
+    /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/schema/TableSchema.java>
+    pub fn trimmed_primary_keys(&self) -> Vec<&str> {
+        self.primary_keys
+            .iter()
+            .filter(|pk| !self.partition_keys.contains(pk))
+            .map(|pk| pk.as_str())
+            .collect()
+    }
+
+    /// Get the fields of the primary keys without partition keys.
+    pub fn trimmed_primary_key_fields(&self) -> Vec<DataField> {
+        self.trimmed_primary_keys()
+            .iter()
+            .filter_map(|key| self.fields.iter().find(|f| f.name() == *key))
+            .cloned()
+            .collect()
+    }
+
+    /// Get the keys to distribute rows into buckets, the `bucket-key` option or the trimmed
+    /// primary keys by default.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/schema/TableSchema.java>
+    pub fn bucket_keys(&self) -> Vec<&str> {
+        let bucket_keys = CoreOptions::new(&self.options).bucket_key();
+        if bucket_keys.is_empty() {
+            self.trimmed_primary_keys()
+        } else {
+            bucket_keys
+        }
+    }
+
+    /// Get the row type of all fields.
+    pub fn logical_row_type(&self) -> RowType {
+        RowType::new(self.fields.clone())
+    }
+
+    /// Get the row type of the partition keys, in the order of the partition keys.
+    pub fn logical_partition_type(&self) -> RowType {
+        RowType::new(
+            self.partition_keys
+                .iter()
+                .filter_map(|key| self.fields.iter().find(|f| f.name() == key))
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Get a copy of this schema with the options replaced.
+    pub fn copy_with_options(&self, options: HashMap<String, String>) -> TableSchema {
+        TableSchema {
+            options,
+            ..self.clone()
+        }
+    }
+}
+
+/// Schema of a table to be created, see [`TableSchema`] for the schema of an existing table.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/schema/Schema.java>
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct Schema {
+    fields: Vec<DataField>,
+    #[builder(default)]
+    #[serde(default)]
+    partition_keys: Vec<String>,
+    #[builder(default)]
+    #[serde(default)]
+    primary_keys: Vec<String>,
+    #[builder(default)]
+    #[serde(default)]
+    options: HashMap<String, String>,
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+impl Schema {
+    /// Get the fields of this schema.
+    #[inline]
+    pub fn fields(&self) -> &[DataField] {
+        &self.fields
+    }
+
+    /// Get the partition keys of this schema.
+    #[inline]
+    pub fn partition_keys(&self) -> &[String] {
+        &self.partition_keys
+    }
+
+    /// Get the primary keys of this schema.
+    #[inline]
+    pub fn primary_keys(&self) -> &[String] {
+        &self.primary_keys
+    }
+
+    /// Get the options of this schema.
+    #[inline]
+    pub fn options(&self) -> &HashMap<String, String> {
+        &self.options
+    }
+
+    /// Get the comment of this schema.
+    #[inline]
+    pub fn comment(&self) -> Option<&str> {
+        self.comment.as_deref()
+    }
+
+    /// Validate this schema and convert it into the [`TableSchema`] with given id.
+    ///
+    /// Fields of primary keys are converted to `NOT NULL`.
+    pub fn to_table_schema(&self, id: i64) -> crate::Result<TableSchema> {
+        let mut names = HashSet::new();
+        for field in &self.fields {
+            if !names.insert(field.name()) {
+                return SchemaInvalidSnafu {
+                    message: format!("Field name {} is duplicated", field.name()),
+                }
+                .fail();
+            }
+        }
+        for (kind, keys) in [
+            ("partition", &self.partition_keys),
+            ("primary", &self.primary_keys),
+        ] {
+            if let Some(key) = keys.iter().find(|k| !names.contains(k.as_str())) {
+                return SchemaInvalidSnafu {
+                    message: format!(
+                        "Table column {:?} should include all {} key {}",
+                        names, kind, key
+                    ),
+                }
+                .fail();
+            }
+        }
+        if !self.primary_keys.is_empty()
+            && !self
+                .partition_keys
+                .iter()
+                .all(|k| self.primary_keys.contains(k))
+        {
+            return SchemaInvalidSnafu {
+                message: format!(
+                    "Primary key constraint {:?} should include all partition fields {:?}",
+                    self.primary_keys, self.partition_keys
+                ),
+            }
+            .fail();
+        }
+
+        let fields: Vec<DataField> = self
+            .fields
+            .iter()
+            .map(|field| {
+                if self.primary_keys.iter().any(|k| k == field.name()) {
+                    let typ = field.data_type().copy_with_nullable(false);
+                    field.clone().with_data_type(typ)
+                } else {
+                    field.clone()
+                }
+            })
+            .collect();
+        let highest_field_id = fields.iter().map(|f| f.id()).max().unwrap_or(-1);
+
+        Ok(TableSchema::builder()
+            .id(id)
+            .fields(fields)
+            .highest_field_id(highest_field_id)
+            .partition_keys(self.partition_keys.clone())
+            .primary_keys(self.primary_keys.clone())
+            .options(self.options.clone())
+            .comment(self.comment.clone())
+            .build())
+    }
+}
+
 /// Data field for paimon table.
 ///
 /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/types/DataField.java#L40>
@@ -88,6 +359,11 @@ impl DataField {
         self
     }
 
+    pub fn with_data_type(mut self, new_type: DataType) -> Self {
+        self.typ = new_type;
+        self
+    }
+
     pub fn with_description(mut self, new_description: Option<String>) -> Self {
         self.description = new_description;
         self
@@ -104,10 +380,76 @@ pub fn escape_single_quotes(text: &str) -> String {
 
 #[cfg(test)]
 mod tests {
-    use crate::spec::IntType;
+    use crate::spec::{BigIntType, DecimalType, IntType, VarCharType};
 
     use super::*;
 
+    fn load_fixture(name: &str) -> String {
+        let path = std::env::current_dir()
+            .unwrap_or_else(|err| panic!("current_dir must exist: {err}"))
+            .join(format!("tests/fixtures/schema/{name}.json"));
+        std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("fixtures {path:?} load failed: {err}"))
+    }
+
+    #[test]
+    fn test_table_schema_deserialize() {
+        let schema = TableSchema::from_json(&load_fixture("schema-0")).unwrap();
+        let expect = TableSchema::builder()
+            .id(0)
+            .fields(vec![
+                DataField::new(
+                    0,
+                    "dt".to_string(),
+                    DataType::VarChar(VarCharType::with_nullable(false, 10).unwrap()),
+                ),
+                DataField::new(
+                    1,
+                    "id".to_string(),
+                    DataType::BigInt(BigIntType::with_nullable(false)),
+                ),
+                DataField::new(
+                    2,
+                    "name".to_string(),
+                    DataType::VarChar(VarCharType::new(100).unwrap()),
+                )
+                .with_description(Some("user name".to_string())),
+                DataField::new(
+                    3,
+                    "price".to_string(),
+                    DataType::Decimal(DecimalType::new(10, 2).unwrap()),
+                ),
+            ])
+            .highest_field_id(3)
+            .partition_keys(vec!["dt".to_string()])
+            .primary_keys(vec!["dt".to_string(), "id".to_string()])
+            .options(HashMap::from([
+                ("bucket".to_string(), "2".to_string()),
+                ("file.format".to_string(), "parquet".to_string()),
+            ]))
+            .comment(Some("test table".to_string()))
+            .time_millis(1725614754918)
+            .build();
+
+        assert_eq!(schema, expect);
+        assert_eq!(schema.field_names(), vec!["dt", "id", "name", "price"]);
+        assert_eq!(schema.trimmed_primary_keys(), vec!["id"]);
+
+        let json = schema.to_json().unwrap();
+        assert_eq!(TableSchema::from_json(&json).unwrap(), schema);
+    }
+
+    #[test]
+    fn test_table_schema_without_version() {
+        let schema = TableSchema::from_json(
+            r#"{"id": 0, "fields": [], "highestFieldId": -1, "partitionKeys": [],
+                "primaryKeys": [], "options": {}, "timeMillis": 0}"#,
+        )
+        .unwrap();
+        assert_eq!(schema.version(), 1);
+        assert_eq!(schema.comment(), None);
+    }
+
     #[test]
     fn test_create_data_field() {
         let id = 1;
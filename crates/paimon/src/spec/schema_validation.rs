@@ -0,0 +1,247 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::{ConfigInvalidSnafu, JsonUnexpectedSnafu};
+use crate::spec::{ChangelogProducer, CoreOptions, MergeEngine, OptionDiff, TableSchema};
+use serde::Serialize;
+use snafu::ResultExt;
+
+/// Report of the options of a validated table schema.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct OptionsReport {
+    /// Keys of the options not known by paimon, which have no effect.
+    pub unknown_keys: Vec<String>,
+    /// Options whose effective values differ from their defaults.
+    pub changed: Vec<OptionDiff>,
+}
+
+impl OptionsReport {
+    /// Serialize the report as json.
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string(self).context(JsonUnexpectedSnafu {
+            message: "Failed to serialize options report",
+        })
+    }
+}
+
+/// Validate the options of the table schema against its fields and keys, fails on invalid
+/// combinations of options.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/schema/SchemaValidation.java>
+pub fn validate_table_schema(schema: &TableSchema) -> crate::Result<OptionsReport> {
+    let options = CoreOptions::new(schema.options());
+    let field_names = schema.field_names();
+    let has_primary_keys = !schema.primary_keys().is_empty();
+
+    let merge_engine = options.merge_engine()?;
+    let changelog_producer = options.changelog_producer()?;
+    if !has_primary_keys && changelog_producer != ChangelogProducer::None {
+        return invalid(format!(
+            "Can not set changelog-producer {changelog_producer} on table without primary keys"
+        ));
+    }
+
+    let bucket = options.bucket();
+    if bucket == 0 || bucket < -1 {
+        return invalid(format!(
+            "The number of buckets needs to be greater than 0 or -1, but is {bucket}"
+        ));
+    }
+    let bucket_key = options.bucket_key();
+    if !bucket_key.is_empty() {
+        if bucket == -1 {
+            return invalid(
+                "Cannot define bucket-key with bucket -1, please specify a bucket number"
+                    .to_string(),
+            );
+        }
+        if let Some(key) = bucket_key.iter().find(|k| !field_names.contains(k)) {
+            return invalid(format!("Bucket key {key} is not a field of the table"));
+        }
+        if has_primary_keys {
+            if let Some(key) = bucket_key
+                .iter()
+                .find(|k| !schema.primary_keys().iter().any(|pk| pk == *k))
+            {
+                return invalid(format!(
+                    "Primary keys {:?} should include all bucket keys, but not {key}",
+                    schema.primary_keys()
+                ));
+            }
+        }
+    }
+
+    if options.deletion_vectors_enabled() {
+        if matches!(
+            changelog_producer,
+            ChangelogProducer::Input | ChangelogProducer::FullCompaction
+        ) {
+            return invalid(format!(
+                "Deletion vectors mode is not supported with changelog-producer {changelog_producer}"
+            ));
+        }
+        if merge_engine == MergeEngine::FirstRow {
+            return invalid(format!(
+                "Deletion vectors mode is not supported with merge-engine {merge_engine}"
+            ));
+        }
+    }
+
+    let (min, max) = (
+        options.snapshot_num_retained_min(),
+        options.snapshot_num_retained_max(),
+    );
+    if min < 1 {
+        return invalid(format!(
+            "snapshot.num-retained.min should be at least 1, but is {min}"
+        ));
+    }
+    if min > max {
+        return invalid(format!(
+            "snapshot.num-retained.min {min} should not be larger than snapshot.num-retained.max {max}"
+        ));
+    }
+
+    if let Some(field) = options
+        .sequence_field()
+        .iter()
+        .find(|f| !field_names.contains(f))
+    {
+        return invalid(format!(
+            "Sequence field {field} is not a field of the table"
+        ));
+    }
+
+    Ok(OptionsReport {
+        unknown_keys: options
+            .unknown_keys()
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        changed: options.diff_from_defaults(),
+    })
+}
+
+fn invalid<T>(message: String) -> crate::Result<T> {
+    ConfigInvalidSnafu { message }.fail()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{DataField, DataType, IntType};
+
+    fn table_schema(primary_keys: &[&str], options: &[(&str, &str)]) -> TableSchema {
+        TableSchema::builder()
+            .id(0)
+            .fields(vec![
+                DataField::new(0, "id".to_string(), DataType::Int(IntType::new())),
+                DataField::new(1, "v".to_string(), DataType::Int(IntType::new())),
+            ])
+            .highest_field_id(1)
+            .primary_keys(primary_keys.iter().map(|k| k.to_string()).collect())
+            .options(
+                options
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_validate_table_schema() {
+        let report = validate_table_schema(&table_schema(
+            &["id"],
+            &[
+                ("bucket", "2"),
+                ("changelog-producer", "lookup"),
+                ("file.format", "orc"),
+                ("fields.v.aggregate-function", "sum"),
+                ("s3.region", "us-east-1"),
+                ("bukcet", "4"),
+            ],
+        ))
+        .unwrap();
+        assert_eq!(report.unknown_keys, vec!["bukcet"]);
+        assert_eq!(
+            report
+                .changed
+                .iter()
+                .map(|d| d.key.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "bucket",
+                "bukcet",
+                "changelog-producer",
+                "fields.v.aggregate-function",
+                "s3.region"
+            ]
+        );
+        assert_eq!(
+            report.changed[0],
+            OptionDiff {
+                key: "bucket".to_string(),
+                default: Some("-1".to_string()),
+                effective: "2".to_string(),
+            }
+        );
+        assert!(report
+            .to_json()
+            .unwrap()
+            .contains(r#""unknown_keys":["bukcet"]"#));
+
+        for (primary_keys, options) in [
+            (&[][..], &[("changelog-producer", "lookup")][..]),
+            (&["id"], &[("changelog-producer", "unknown")]),
+            (&["id"], &[("bucket", "0")]),
+            (&["id"], &[("bucket-key", "id")]),
+            (&["id"], &[("bucket", "2"), ("bucket-key", "v")]),
+            (
+                &["id"],
+                &[
+                    ("deletion-vectors.enabled", "true"),
+                    ("changelog-producer", "input"),
+                ],
+            ),
+            (
+                &["id"],
+                &[
+                    ("deletion-vectors.enabled", "true"),
+                    ("merge-engine", "first-row"),
+                ],
+            ),
+            (
+                &["id"],
+                &[
+                    ("snapshot.num-retained.min", "5"),
+                    ("snapshot.num-retained.max", "3"),
+                ],
+            ),
+            (&["id"], &[("sequence.field", "ts")]),
+        ] {
+            let schema = table_schema(primary_keys, options);
+            assert!(
+                matches!(
+                    validate_table_schema(&schema),
+                    Err(crate::Error::ConfigInvalid { .. })
+                ),
+                "{options:?}"
+            );
+        }
+    }
+}
@@ -15,7 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::error::JsonUnexpectedSnafu;
 use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
 use std::collections::HashMap;
 use typed_builder::TypedBuilder;
 
@@ -41,7 +43,8 @@ pub enum CommitKind {
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TypedBuilder)]
 #[serde(rename_all = "camelCase")]
 pub struct Snapshot {
-    /// version of snapshot
+    /// version of snapshot, snapshots written by paimon <= 0.3 don't have it
+    #[serde(default = "default_version")]
     version: i32,
     id: i64,
     schema_id: i64,
@@ -76,9 +79,11 @@ pub struct Snapshot {
     log_offsets: Option<HashMap<i32, i64>>,
     /// record count of all changes occurred in this snapshot
     #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     total_record_count: Option<i64>,
     /// record count of all new changes occurred in this snapshot
     #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
     delta_record_count: Option<i64>,
     /// record count of all changelog produced in this snapshot
     #[builder(default = None)]
@@ -94,7 +99,33 @@ pub struct Snapshot {
     statistics: Option<String>,
 }
 
+fn default_version() -> i32 {
+    1
+}
+
 impl Snapshot {
+    /// The id of the first snapshot of a table.
+    pub const FIRST_SNAPSHOT_ID: i64 = 1;
+
+    /// The version of snapshots written by this implementation.
+    pub const CURRENT_VERSION: i32 = 3;
+
+    /// Parse a snapshot from its json representation.
+    ///
+    /// Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/Snapshot.java>
+    pub fn from_json(json: &str) -> crate::Result<Snapshot> {
+        serde_json::from_str(json).context(JsonUnexpectedSnafu {
+            message: "Failed to parse snapshot",
+        })
+    }
+
+    /// Serialize this snapshot into its json representation.
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string_pretty(self).context(JsonUnexpectedSnafu {
+            message: format!("Failed to serialize snapshot {}", self.id),
+        })
+    }
+
     /// Get the version of this snapshot.
     #[inline]
     pub fn version(&self) -> i32 {
@@ -143,6 +174,12 @@ impl Snapshot {
         &self.commit_user
     }
 
+    /// Get the commit kind of this snapshot.
+    #[inline]
+    pub fn commit_kind(&self) -> &CommitKind {
+        &self.commit_kind
+    }
+
     /// Get the commit time of this snapshot.
     #[inline]
     pub fn time_millis(&self) -> u64 {
@@ -259,6 +296,51 @@ mod tests {
                     .changelog_record_count(Some(2))
                     .build(),
             ),
+            (
+                "snapshot-v3-java",
+                Snapshot::builder()
+                    .version(3)
+                    .id(5)
+                    .schema_id(1)
+                    .base_manifest_list(
+                        "manifest-list-6fb1bd3b-6fb0-4b2c-a55d-a6e4b2a8fcc1-8".to_string(),
+                    )
+                    .delta_manifest_list(
+                        "manifest-list-6fb1bd3b-6fb0-4b2c-a55d-a6e4b2a8fcc1-9".to_string(),
+                    )
+                    .index_manifest(Some(
+                        "index-manifest-85cc6729-f5af-431a-a1c3-ef45319328fb-0".to_string(),
+                    ))
+                    .commit_user("0b2a2c6a-7d1a-4fd4-b7a3-4a8b2f4bb0e5".to_string())
+                    .commit_identifier(9223372036854775807)
+                    .commit_kind(CommitKind::COMPACT)
+                    .time_millis(1725614755039)
+                    .log_offsets(Some(HashMap::default()))
+                    .total_record_count(Some(10))
+                    .delta_record_count(Some(-2))
+                    .changelog_record_count(Some(0))
+                    .watermark(Some(i64::MIN))
+                    .build(),
+            ),
+            (
+                "snapshot-v1",
+                Snapshot::builder()
+                    .version(1)
+                    .id(1)
+                    .schema_id(0)
+                    .base_manifest_list(
+                        "manifest-list-2d9d2f27-2e0e-4a4f-b8a5-2f2f4a6d2b7e-0".to_string(),
+                    )
+                    .delta_manifest_list(
+                        "manifest-list-2d9d2f27-2e0e-4a4f-b8a5-2f2f4a6d2b7e-1".to_string(),
+                    )
+                    .commit_user("c1f0a87e-33b8-4c64-92ec-2d06c4a8a5d4".to_string())
+                    .commit_identifier(1)
+                    .commit_kind(CommitKind::APPEND)
+                    .time_millis(1680000000000)
+                    .log_offsets(Some(HashMap::default()))
+                    .build(),
+            ),
         ]
     }
 
@@ -278,4 +360,29 @@ mod tests {
             assert_eq!(snapshot, deserialized);
         }
     }
+
+    #[test]
+    fn test_snapshot_json_round_trip() {
+        for (name, expect) in test_cases() {
+            let snapshot = Snapshot::from_json(&load_fixture(name)).unwrap();
+            assert_eq!(snapshot, expect);
+
+            let json = snapshot.to_json().unwrap();
+            assert_eq!(Snapshot::from_json(&json).unwrap(), expect);
+        }
+    }
+
+    #[test]
+    fn test_snapshot_skip_absent_record_count() {
+        let snapshot = Snapshot::from_json(&load_fixture("snapshot-v1")).unwrap();
+        let json = snapshot.to_json().unwrap();
+
+        assert!(!json.contains("totalRecordCount"));
+        assert!(!json.contains("deltaRecordCount"));
+    }
+
+    #[test]
+    fn test_snapshot_invalid_json() {
+        assert!(Snapshot::from_json("{\"id\": 1}").is_err());
+    }
 }
@@ -0,0 +1,210 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::JsonUnexpectedSnafu;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use std::collections::HashMap;
+use typed_builder::TypedBuilder;
+
+/// Table level statistics of the data of a snapshot, kept in the `statistics` directory and
+/// referenced by the snapshots, for cost-based optimizers.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/stats/Statistics.java>
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct Statistics {
+    /// the snapshot the statistics are computed of
+    snapshot_id: i64,
+    /// the schema of the fields of the column statistics
+    schema_id: i64,
+    /// number of the rows after merging by key
+    #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merged_record_count: Option<i64>,
+    /// size in bytes of the rows after merging by key
+    #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    merged_record_size: Option<i64>,
+    /// statistics of the columns by their names
+    #[builder(default)]
+    #[serde(default)]
+    col_stats: HashMap<String, ColStats>,
+}
+
+impl Statistics {
+    /// Parse the statistics from their json representation.
+    pub fn from_json(json: &str) -> crate::Result<Statistics> {
+        serde_json::from_str(json).context(JsonUnexpectedSnafu {
+            message: "Failed to parse statistics",
+        })
+    }
+
+    /// Serialize the statistics into their json representation.
+    pub fn to_json(&self) -> crate::Result<String> {
+        serde_json::to_string_pretty(self).context(JsonUnexpectedSnafu {
+            message: format!(
+                "Failed to serialize statistics of snapshot {}",
+                self.snapshot_id
+            ),
+        })
+    }
+
+    /// Get the id of the snapshot the statistics are of.
+    #[inline]
+    pub fn snapshot_id(&self) -> i64 {
+        self.snapshot_id
+    }
+
+    /// Get the id of the schema of the columns.
+    #[inline]
+    pub fn schema_id(&self) -> i64 {
+        self.schema_id
+    }
+
+    /// Get the number of the rows after merging by key.
+    #[inline]
+    pub fn merged_record_count(&self) -> Option<i64> {
+        self.merged_record_count
+    }
+
+    /// Get the size in bytes of the rows after merging by key.
+    #[inline]
+    pub fn merged_record_size(&self) -> Option<i64> {
+        self.merged_record_size
+    }
+
+    /// Get the statistics of the columns by their names.
+    #[inline]
+    pub fn col_stats(&self) -> &HashMap<String, ColStats> {
+        &self.col_stats
+    }
+}
+
+/// Statistics of a column, the min and max values are in the string representation of the
+/// type of the column, like the values of partitions.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/stats/ColStats.java>
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, TypedBuilder)]
+#[serde(rename_all = "camelCase")]
+pub struct ColStats {
+    /// id of the field of the column
+    col_id: i32,
+    #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    distinct_count: Option<i64>,
+    #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min: Option<String>,
+    #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max: Option<String>,
+    #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    null_count: Option<i64>,
+    /// average length in bytes of the values of variable length types
+    #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    avg_len: Option<i64>,
+    /// max length in bytes of the values of variable length types
+    #[builder(default = None)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_len: Option<i64>,
+}
+
+impl ColStats {
+    /// Get the id of the field of the column.
+    #[inline]
+    pub fn col_id(&self) -> i32 {
+        self.col_id
+    }
+
+    /// Get the number of the distinct values, which may be estimated.
+    #[inline]
+    pub fn distinct_count(&self) -> Option<i64> {
+        self.distinct_count
+    }
+
+    /// Get the min value.
+    #[inline]
+    pub fn min(&self) -> Option<&str> {
+        self.min.as_deref()
+    }
+
+    /// Get the max value.
+    #[inline]
+    pub fn max(&self) -> Option<&str> {
+        self.max.as_deref()
+    }
+
+    /// Get the number of the null values.
+    #[inline]
+    pub fn null_count(&self) -> Option<i64> {
+        self.null_count
+    }
+
+    /// Get the average length in bytes of the values.
+    #[inline]
+    pub fn avg_len(&self) -> Option<i64> {
+        self.avg_len
+    }
+
+    /// Get the max length in bytes of the values.
+    #[inline]
+    pub fn max_len(&self) -> Option<i64> {
+        self.max_len
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_statistics_json() {
+        let json = r#"{
+            "snapshotId": 3,
+            "schemaId": 0,
+            "mergedRecordCount": 10,
+            "mergedRecordSize": 1024,
+            "colStats": {
+                "id": {"colId": 0, "distinctCount": 10, "min": "1", "max": "10", "nullCount": 0},
+                "name": {"colId": 1, "distinctCount": 8, "nullCount": 2, "avgLen": 5, "maxLen": 9}
+            }
+        }"#;
+        let statistics = Statistics::from_json(json).unwrap();
+        assert_eq!(statistics.snapshot_id(), 3);
+        assert_eq!(statistics.merged_record_count(), Some(10));
+        let id = &statistics.col_stats()["id"];
+        assert_eq!((id.min(), id.max()), (Some("1"), Some("10")));
+        assert_eq!(id.avg_len(), None);
+        let name = &statistics.col_stats()["name"];
+        assert_eq!(name.col_id(), 1);
+        assert_eq!(name.null_count(), Some(2));
+        assert_eq!(name.max_len(), Some(9));
+        assert_eq!(
+            Statistics::from_json(&statistics.to_json().unwrap()).unwrap(),
+            statistics
+        );
+
+        let statistics = Statistics::builder().snapshot_id(1).schema_id(0).build();
+        assert_eq!(
+            serde_json::to_string(&statistics).unwrap(),
+            r#"{"snapshotId":1,"schemaId":0,"colStats":{}}"#
+        );
+    }
+}
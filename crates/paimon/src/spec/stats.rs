@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::spec::{BinaryRow, BinaryRowWriter, DataField, DataType, Datum};
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 
@@ -35,7 +36,7 @@ pub struct BinaryTableStats {
 
     /// the number of nulls of the columns
     #[serde(rename = "_NULL_COUNTS")]
-    null_counts: Vec<i64>,
+    null_counts: Vec<Option<i64>>,
 }
 
 impl BinaryTableStats {
@@ -51,16 +52,49 @@ impl BinaryTableStats {
         &self.max_values
     }
 
-    /// Get the number of nulls of the columns
+    /// Get the number of nulls of the columns, none if not collected
     #[inline]
-    pub fn null_counts(&self) -> &Vec<i64> {
+    pub fn null_counts(&self) -> &[Option<i64>] {
         &self.null_counts
     }
 
+    /// Get the minimum value of the field at the index, none if it is null or not collected.
+    pub fn min_value(&self, index: usize, data_type: &DataType) -> crate::Result<Option<Datum>> {
+        Self::value(&self.min_values, index, data_type)
+    }
+
+    /// Get the maximum value of the field at the index, none if it is null or not collected.
+    pub fn max_value(&self, index: usize, data_type: &DataType) -> crate::Result<Option<Datum>> {
+        Self::value(&self.max_values, index, data_type)
+    }
+
+    /// Get the number of nulls of the field at the index, none if it is not collected.
+    pub fn null_count(&self, index: usize) -> Option<i64> {
+        self.null_counts.get(index).copied().flatten()
+    }
+
+    /// Whether all the rows of the field at the index are null, false if the null count is
+    /// not collected.
+    pub fn is_all_null(&self, index: usize, row_count: i64) -> bool {
+        self.null_count(index) == Some(row_count)
+    }
+
+    fn value(values: &[u8], index: usize, data_type: &DataType) -> crate::Result<Option<Datum>> {
+        if values.is_empty() {
+            return Ok(None);
+        }
+        let row = BinaryRow::from_serialized_bytes(values)?;
+        // stats may be collected only for a part of fields
+        if index >= row.arity() as usize {
+            return Ok(None);
+        }
+        row.get_datum(index, data_type)
+    }
+
     pub fn new(
         min_values: Vec<u8>,
         max_values: Vec<u8>,
-        null_counts: Vec<i64>,
+        null_counts: Vec<Option<i64>>,
     ) -> BinaryTableStats {
         Self {
             min_values,
@@ -70,8 +104,209 @@ impl BinaryTableStats {
     }
 }
 
+impl BinaryTableStats {
+    /// Merge the stats of the fields into one, see [`BinaryTableStatsAggregator::merge`].
+    pub fn merge<'b>(
+        fields: &[DataField],
+        stats: impl IntoIterator<Item = &'b BinaryTableStats>,
+    ) -> crate::Result<BinaryTableStats> {
+        let mut aggregator = BinaryTableStatsAggregator::new(fields.to_vec());
+        for stats in stats {
+            aggregator.merge(stats)?;
+        }
+        aggregator.stats()
+    }
+
+    /// Compute the stats of the rows of the fields, such as the partitions of a manifest file.
+    pub fn from_rows<'b>(
+        fields: &[DataField],
+        rows: impl IntoIterator<Item = &'b BinaryRow>,
+    ) -> crate::Result<BinaryTableStats> {
+        let mut aggregator = BinaryTableStatsAggregator::new(fields.to_vec());
+        for row in rows {
+            aggregator.update(row)?;
+        }
+        aggregator.stats()
+    }
+}
+
+/// Aggregator of the min values, max values and null counts of the fields, from the rows of
+/// them or from the [`BinaryTableStats`] of parts of the rows.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/stats/SimpleStatsCollector.java>
+#[derive(Debug, Clone)]
+pub struct BinaryTableStatsAggregator {
+    fields: Vec<DataField>,
+    min_values: Vec<Option<Datum>>,
+    max_values: Vec<Option<Datum>>,
+    null_counts: Vec<Option<i64>>,
+}
+
+impl BinaryTableStatsAggregator {
+    pub fn new(fields: Vec<DataField>) -> Self {
+        Self {
+            min_values: vec![None; fields.len()],
+            max_values: vec![None; fields.len()],
+            null_counts: vec![Some(0); fields.len()],
+            fields,
+        }
+    }
+
+    /// Update the stats by the values of the row.
+    pub fn update(&mut self, row: &BinaryRow) -> crate::Result<()> {
+        for pos in 0..self.fields.len() {
+            match row.get_datum(pos, self.fields[pos].data_type())? {
+                Some(value) => self.update_min_max(pos, Some(value.clone()), Some(value)),
+                None => self.null_counts[pos] = self.null_counts[pos].map(|count| count + 1),
+            }
+        }
+        Ok(())
+    }
+
+    /// Merge the stats, the min and max values are the union of both, and the null counts are
+    /// summed up, unknown if the null count of either is not collected.
+    pub fn merge(&mut self, stats: &BinaryTableStats) -> crate::Result<()> {
+        for pos in 0..self.fields.len() {
+            let data_type = self.fields[pos].data_type();
+            let min = stats.min_value(pos, data_type)?;
+            let max = stats.max_value(pos, data_type)?;
+            self.update_min_max(pos, min, max);
+            self.null_counts[pos] = self.null_counts[pos]
+                .zip(stats.null_count(pos))
+                .map(|(a, b)| a + b);
+        }
+        Ok(())
+    }
+
+    /// Get the stats aggregated as binary rows of the fields.
+    pub fn stats(&self) -> crate::Result<BinaryTableStats> {
+        let to_row = |values: &[Option<Datum>]| -> crate::Result<Vec<u8>> {
+            let mut writer = BinaryRowWriter::new(self.fields.len() as i32);
+            for (pos, (value, field)) in values.iter().zip(&self.fields).enumerate() {
+                writer.write_datum(pos, value.as_ref(), field.data_type())?;
+            }
+            Ok(writer.build().to_serialized_bytes())
+        };
+        Ok(BinaryTableStats::new(
+            to_row(&self.min_values)?,
+            to_row(&self.max_values)?,
+            self.null_counts.clone(),
+        ))
+    }
+
+    fn update_min_max(&mut self, pos: usize, min: Option<Datum>, max: Option<Datum>) {
+        if let Some(min) = min {
+            if !self.min_values[pos].as_ref().is_some_and(|v| v <= &min) {
+                self.min_values[pos] = Some(min);
+            }
+        }
+        if let Some(max) = max {
+            if !self.max_values[pos].as_ref().is_some_and(|v| v >= &max) {
+                self.max_values[pos] = Some(max);
+            }
+        }
+    }
+}
+
 impl Display for BinaryTableStats {
     fn fmt(&self, _: &mut Formatter<'_>) -> std::fmt::Result {
         todo!()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{IntType, VarCharType};
+
+    #[test]
+    fn test_typed_values() {
+        let row = |id: Option<i32>, name: &str| {
+            let mut writer = BinaryRowWriter::new(2);
+            match id {
+                Some(id) => writer.write_int(0, id),
+                None => writer.set_null_at(0),
+            }
+            writer.write_string(1, name);
+            writer.build().to_serialized_bytes()
+        };
+        let stats = BinaryTableStats::new(row(None, "a"), row(Some(5), "c"), vec![Some(2), None]);
+        let (int_type, string_type) = (
+            DataType::Int(IntType::new()),
+            DataType::VarChar(VarCharType::default()),
+        );
+        assert_eq!(stats.min_value(0, &int_type).unwrap(), None);
+        assert_eq!(stats.max_value(0, &int_type).unwrap(), Some(Datum::Int(5)));
+        assert_eq!(
+            stats.min_value(1, &string_type).unwrap(),
+            Some(Datum::String("a".to_string()))
+        );
+        // the stats of the third field are not collected
+        assert_eq!(stats.max_value(2, &int_type).unwrap(), None);
+        assert_eq!(stats.null_count(0), Some(2));
+        assert_eq!(stats.null_count(1), None);
+        assert_eq!(stats.null_count(2), None);
+        assert!(stats.is_all_null(0, 2));
+        assert!(!stats.is_all_null(1, 2));
+
+        let empty = BinaryTableStats::new(vec![], vec![], vec![]);
+        assert_eq!(empty.min_value(0, &int_type).unwrap(), None);
+        assert!(!empty.is_all_null(0, 0));
+    }
+
+    #[test]
+    fn test_merge_and_rows_stats() {
+        let fields = vec![
+            DataField::new(0, "id".to_string(), DataType::Int(IntType::new())),
+            DataField::new(
+                1,
+                "name".to_string(),
+                DataType::VarChar(VarCharType::default()),
+            ),
+        ];
+        let row = |id: Option<i32>, name: &str| {
+            let mut writer = BinaryRowWriter::new(2);
+            match id {
+                Some(id) => writer.write_int(0, id),
+                None => writer.set_null_at(0),
+            }
+            writer.write_string(1, name);
+            writer.build()
+        };
+        let left = BinaryTableStats::from_rows(
+            &fields,
+            &[row(Some(3), "b"), row(None, "d"), row(Some(7), "c")],
+        )
+        .unwrap();
+        assert_eq!(
+            left.min_value(0, fields[0].data_type()).unwrap(),
+            Some(Datum::Int(3))
+        );
+        assert_eq!(
+            left.max_value(0, fields[0].data_type()).unwrap(),
+            Some(Datum::Int(7))
+        );
+        assert_eq!(left.null_counts(), &[Some(1), Some(0)]);
+
+        let right =
+            BinaryTableStats::from_rows(&fields, &[row(None, "a"), row(Some(1), "e")]).unwrap();
+        let merged = BinaryTableStats::merge(&fields, [&left, &right]).unwrap();
+        assert_eq!(
+            merged,
+            BinaryTableStats::new(
+                row(Some(1), "a").to_serialized_bytes(),
+                row(Some(7), "e").to_serialized_bytes(),
+                vec![Some(2), Some(0)]
+            )
+        );
+
+        // the null counts are unknown if not collected by any of the stats
+        let uncollected = BinaryTableStats::new(vec![], vec![], vec![]);
+        let merged = BinaryTableStats::merge(&fields, [&merged, &uncollected]).unwrap();
+        assert_eq!(
+            merged.min_value(0, fields[0].data_type()).unwrap(),
+            Some(Datum::Int(1))
+        );
+        assert_eq!(merged.null_counts(), &[None, None]);
+    }
+}
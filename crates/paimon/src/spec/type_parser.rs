@@ -0,0 +1,512 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Parser of SQL-style type strings, like `DECIMAL(10, 2)` or `ROW<f0 INT, f1 STRING>`.
+//!
+//! Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/types/DataTypeJsonParser.java>
+
+use crate::error::DataTypeInvalidSnafu;
+use crate::spec::*;
+use crate::{Error, Result};
+use std::iter::Peekable;
+use std::str::{CharIndices, FromStr};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// A keyword or an unquoted identifier.
+    Word(String),
+    /// A backtick quoted identifier.
+    Quoted(String),
+    /// A single quoted string literal.
+    Literal(String),
+    Number(u32),
+    Symbol(char),
+}
+
+fn tokenize(s: &str) -> Result<Vec<Token>> {
+    let mut tokens = vec![];
+    let mut chars = s.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '<' | '>' | '(' | ')' | ',' => {
+                tokens.push(Token::Symbol(c));
+                chars.next();
+            }
+            '`' => tokens.push(Token::Quoted(read_quoted(s, &mut chars, '`')?)),
+            '\'' => tokens.push(Token::Literal(read_quoted(s, &mut chars, '\'')?)),
+            c if c.is_ascii_digit() => {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !c.is_ascii_digit() {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                let number = s[start..end]
+                    .parse::<u32>()
+                    .map_err(|_| Error::DataTypeInvalid {
+                        message: format!(
+                            "Invalid number '{}' in type string '{}'",
+                            &s[start..end],
+                            s
+                        ),
+                    })?;
+                tokens.push(Token::Number(number));
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                let mut end = start;
+                while let Some(&(i, c)) = chars.peek() {
+                    if !(c.is_alphanumeric() || c == '_' || c == '$') {
+                        break;
+                    }
+                    end = i + c.len_utf8();
+                    chars.next();
+                }
+                tokens.push(Token::Word(s[start..end].to_string()));
+            }
+            _ => {
+                return DataTypeInvalidSnafu {
+                    message: format!("Unexpected character '{c}' in type string '{s}'"),
+                }
+                .fail()
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+/// Read a string quoted by `quote`, in which the quote is escaped by doubling it.
+fn read_quoted(s: &str, chars: &mut Peekable<CharIndices>, quote: char) -> Result<String> {
+    chars.next();
+    let mut value = String::new();
+    loop {
+        match chars.next() {
+            Some((_, c)) if c == quote => {
+                if matches!(chars.peek(), Some(&(_, c)) if c == quote) {
+                    value.push(quote);
+                    chars.next();
+                } else {
+                    return Ok(value);
+                }
+            }
+            Some((_, c)) => value.push(c),
+            None => {
+                return DataTypeInvalidSnafu {
+                    message: format!("Unclosed quote {quote} in type string '{s}'"),
+                }
+                .fail()
+            }
+        }
+    }
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(input: &'a str) -> Result<Self> {
+        Ok(Self {
+            input,
+            tokens: tokenize(input)?,
+            pos: 0,
+        })
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T> {
+        DataTypeInvalidSnafu {
+            message: format!("{} in type string '{}'", message.into(), self.input),
+        }
+        .fail()
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Word(w)) if w.eq_ignore_ascii_case(keyword))
+    }
+
+    fn next_keyword_if(&mut self, keyword: &str) -> bool {
+        let matched = self.peek_keyword(keyword);
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect_keyword(&mut self, keyword: &str) -> Result<()> {
+        if self.next_keyword_if(keyword) {
+            Ok(())
+        } else {
+            self.error(format!("Expect keyword {keyword}"))
+        }
+    }
+
+    fn next_symbol_if(&mut self, symbol: char) -> bool {
+        let matched = self.peek() == Some(&Token::Symbol(symbol));
+        if matched {
+            self.pos += 1;
+        }
+        matched
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<()> {
+        if self.next_symbol_if(symbol) {
+            Ok(())
+        } else {
+            self.error(format!("Expect '{symbol}'"))
+        }
+    }
+
+    fn expect_number(&mut self) -> Result<u32> {
+        match self.next() {
+            Some(Token::Number(n)) => Ok(n),
+            _ => self.error("Expect a number"),
+        }
+    }
+
+    /// Parse an optional `(n)`.
+    fn parse_optional_length(&mut self) -> Result<Option<u32>> {
+        if !self.next_symbol_if('(') {
+            return Ok(None);
+        }
+        let n = self.expect_number()?;
+        self.expect_symbol(')')?;
+        Ok(Some(n))
+    }
+
+    fn parse_nullable(&mut self) -> Result<bool> {
+        if self.next_keyword_if("NOT") {
+            self.expect_keyword("NULL")?;
+            Ok(false)
+        } else {
+            self.next_keyword_if("NULL");
+            Ok(true)
+        }
+    }
+
+    /// Parse the optional `WITHOUT TIME ZONE` or `WITH LOCAL TIME ZONE`, returns whether it has local time zone.
+    fn parse_time_zone(&mut self) -> Result<bool> {
+        if self.next_keyword_if("WITHOUT") {
+            self.expect_keyword("TIME")?;
+            self.expect_keyword("ZONE")?;
+            Ok(false)
+        } else if self.next_keyword_if("WITH") {
+            self.expect_keyword("LOCAL")?;
+            self.expect_keyword("TIME")?;
+            self.expect_keyword("ZONE")?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    fn parse_data_type(&mut self) -> Result<DataType> {
+        let Some(Token::Word(word)) = self.next() else {
+            return self.error("Expect a type name");
+        };
+        let typ = match word.to_ascii_uppercase().as_str() {
+            "BOOLEAN" => DataType::Boolean(BooleanType::with_nullable(self.parse_nullable()?)),
+            "TINYINT" => DataType::TinyInt(TinyIntType::with_nullable(self.parse_nullable()?)),
+            "SMALLINT" => DataType::SmallInt(SmallIntType::with_nullable(self.parse_nullable()?)),
+            "INT" | "INTEGER" => DataType::Int(IntType::with_nullable(self.parse_nullable()?)),
+            "BIGINT" => DataType::BigInt(BigIntType::with_nullable(self.parse_nullable()?)),
+            "FLOAT" => DataType::Float(FloatType::with_nullable(self.parse_nullable()?)),
+            "DOUBLE" => {
+                self.next_keyword_if("PRECISION");
+                DataType::Double(DoubleType::with_nullable(self.parse_nullable()?))
+            }
+            "DECIMAL" | "DEC" | "NUMERIC" => {
+                let mut precision = DecimalType::DEFAULT_PRECISION;
+                let mut scale = DecimalType::DEFAULT_SCALE;
+                if self.next_symbol_if('(') {
+                    precision = self.expect_number()?;
+                    if self.next_symbol_if(',') {
+                        scale = self.expect_number()?;
+                    }
+                    self.expect_symbol(')')?;
+                }
+                DataType::Decimal(DecimalType::with_nullable(
+                    self.parse_nullable()?,
+                    precision,
+                    scale,
+                )?)
+            }
+            "CHAR" | "CHARACTER" => {
+                let length = self.parse_optional_length()?;
+                DataType::Char(CharType::with_nullable(
+                    self.parse_nullable()?,
+                    length.map_or(CharType::DEFAULT_LENGTH, |n| n as usize),
+                )?)
+            }
+            "VARCHAR" => {
+                let length = self.parse_optional_length()?;
+                DataType::VarChar(VarCharType::with_nullable(
+                    self.parse_nullable()?,
+                    length.unwrap_or(VarCharType::DEFAULT_LENGTH),
+                )?)
+            }
+            "STRING" => DataType::VarChar(VarCharType::with_nullable(
+                self.parse_nullable()?,
+                VarCharType::MAX_LENGTH,
+            )?),
+            "BINARY" => {
+                let length = self.parse_optional_length()?;
+                DataType::Binary(BinaryType::with_nullable(
+                    self.parse_nullable()?,
+                    length.map_or(BinaryType::DEFAULT_LENGTH, |n| n as usize),
+                )?)
+            }
+            "VARBINARY" => {
+                let length = self.parse_optional_length()?;
+                DataType::VarBinary(VarBinaryType::try_new(
+                    self.parse_nullable()?,
+                    length.unwrap_or(VarBinaryType::DEFAULT_LENGTH),
+                )?)
+            }
+            "BYTES" => DataType::VarBinary(VarBinaryType::try_new(
+                self.parse_nullable()?,
+                VarBinaryType::MAX_LENGTH,
+            )?),
+            "DATE" => DataType::Date(DateType::with_nullable(self.parse_nullable()?)),
+            "TIME" => {
+                let precision = self.parse_optional_length()?;
+                if self.parse_time_zone()? {
+                    return self.error("TIME WITH LOCAL TIME ZONE is not supported");
+                }
+                DataType::Time(TimeType::with_nullable(
+                    self.parse_nullable()?,
+                    precision.unwrap_or(TimeType::DEFAULT_PRECISION),
+                )?)
+            }
+            "TIMESTAMP" => {
+                let precision = self.parse_optional_length()?;
+                if self.parse_time_zone()? {
+                    DataType::LocalZonedTimestamp(LocalZonedTimestampType::with_nullable(
+                        self.parse_nullable()?,
+                        precision.unwrap_or(LocalZonedTimestampType::DEFAULT_PRECISION),
+                    )?)
+                } else {
+                    DataType::Timestamp(TimestampType::with_nullable(
+                        self.parse_nullable()?,
+                        precision.unwrap_or(TimestampType::DEFAULT_PRECISION),
+                    )?)
+                }
+            }
+            "TIMESTAMP_LTZ" => {
+                let precision = self.parse_optional_length()?;
+                DataType::LocalZonedTimestamp(LocalZonedTimestampType::with_nullable(
+                    self.parse_nullable()?,
+                    precision.unwrap_or(LocalZonedTimestampType::DEFAULT_PRECISION),
+                )?)
+            }
+            "ARRAY" => {
+                self.expect_symbol('<')?;
+                let element = self.parse_data_type()?;
+                self.expect_symbol('>')?;
+                DataType::Array(ArrayType::with_nullable(self.parse_nullable()?, element))
+            }
+            "MULTISET" => {
+                self.expect_symbol('<')?;
+                let element = self.parse_data_type()?;
+                self.expect_symbol('>')?;
+                DataType::Multiset(MultisetType::with_nullable(self.parse_nullable()?, element))
+            }
+            "MAP" => {
+                self.expect_symbol('<')?;
+                let key = self.parse_data_type()?;
+                self.expect_symbol(',')?;
+                let value = self.parse_data_type()?;
+                self.expect_symbol('>')?;
+                DataType::Map(MapType::with_nullable(self.parse_nullable()?, key, value))
+            }
+            "ROW" => {
+                let close = if self.next_symbol_if('<') {
+                    '>'
+                } else {
+                    self.expect_symbol('(')?;
+                    ')'
+                };
+                let fields = self.parse_fields(close)?;
+                DataType::Row(RowType::with_nullable(self.parse_nullable()?, fields))
+            }
+            _ => return self.error(format!("Unknown type {word}")),
+        };
+        Ok(typ)
+    }
+
+    /// Parse fields like `name TYPE ['description'], ...` until `close`, ids are assigned by position.
+    fn parse_fields(&mut self, close: char) -> Result<Vec<DataField>> {
+        let mut fields = vec![];
+        if self.next_symbol_if(close) {
+            return Ok(fields);
+        }
+        loop {
+            let name = match self.next() {
+                Some(Token::Word(name)) | Some(Token::Quoted(name)) => name,
+                _ => return self.error("Expect a field name"),
+            };
+            let typ = self.parse_data_type()?;
+            let description = match self.peek() {
+                Some(Token::Literal(description)) => {
+                    let description = description.clone();
+                    self.pos += 1;
+                    Some(description)
+                }
+                _ => None,
+            };
+            fields
+                .push(DataField::new(fields.len() as i32, name, typ).with_description(description));
+            if self.next_symbol_if(close) {
+                return Ok(fields);
+            }
+            self.expect_symbol(',')?;
+        }
+    }
+}
+
+impl FromStr for DataType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s)?;
+        let typ = parser.parse_data_type()?;
+        if parser.peek().is_some() {
+            return parser.error("Unexpected trailing tokens");
+        }
+        Ok(typ)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_simple_types() {
+        let cases = [
+            ("INT", DataType::Int(IntType::new())),
+            (
+                "integer NOT NULL",
+                DataType::Int(IntType::with_nullable(false)),
+            ),
+            (
+                "DECIMAL(10, 2)",
+                DataType::Decimal(DecimalType::new(10, 2).unwrap()),
+            ),
+            (
+                "DECIMAL",
+                DataType::Decimal(DecimalType::new(10, 0).unwrap()),
+            ),
+            (
+                "STRING NOT NULL",
+                DataType::VarChar(
+                    VarCharType::with_nullable(false, VarCharType::MAX_LENGTH).unwrap(),
+                ),
+            ),
+            (
+                "TIMESTAMP(3) WITH LOCAL TIME ZONE",
+                DataType::LocalZonedTimestamp(LocalZonedTimestampType::new(3).unwrap()),
+            ),
+            (
+                "TIMESTAMP WITHOUT TIME ZONE",
+                DataType::Timestamp(TimestampType::new(6).unwrap()),
+            ),
+            ("DOUBLE PRECISION", DataType::Double(DoubleType::new())),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(input.parse::<DataType>().unwrap(), expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_parse_nested_types() {
+        let typ: DataType = "ROW<f0 INT, `f 1` ARRAY<INT NOT NULL> 'it''s an array', \
+            f2 MAP<STRING, ROW(a BIGINT)> NOT NULL>"
+            .parse()
+            .unwrap();
+        let expected = DataType::Row(RowType::new(vec![
+            DataField::new(0, "f0".to_string(), DataType::Int(IntType::new())),
+            DataField::new(
+                1,
+                "f 1".to_string(),
+                DataType::Array(ArrayType::new(DataType::Int(IntType::with_nullable(false)))),
+            )
+            .with_description(Some("it's an array".to_string())),
+            DataField::new(
+                2,
+                "f2".to_string(),
+                DataType::Map(MapType::with_nullable(
+                    false,
+                    DataType::VarChar(VarCharType::new(VarCharType::MAX_LENGTH).unwrap()),
+                    DataType::Row(RowType::new(vec![DataField::new(
+                        0,
+                        "a".to_string(),
+                        DataType::BigInt(BigIntType::new()),
+                    )])),
+                )),
+            ),
+        ]));
+        assert_eq!(typ, expected);
+        // display is parsable back
+        assert_eq!(typ.to_string().parse::<DataType>().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_parse_invalid_types() {
+        for input in [
+            "",
+            "UNKNOWN",
+            "ARRAY<INT",
+            "DECIMAL(10, 2) extra",
+            "ROW<f0>",
+            "TIME(3) WITH LOCAL TIME ZONE",
+        ] {
+            assert!(input.parse::<DataType>().is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn test_deserialize_type_strings() {
+        let cases = [
+            ("\"INTEGER NOT NULL\"", "INT NOT NULL"),
+            ("\"TIMESTAMP_LTZ(3)\"", "TIMESTAMP(3) WITH LOCAL TIME ZONE"),
+            ("\"ARRAY<INT>\"", "ARRAY<INT>"),
+            ("\"MAP<STRING, INT NOT NULL>\"", "MAP<STRING, INT NOT NULL>"),
+            ("\"ROW<a INT>\"", "ROW<`a` INT>"),
+        ];
+        for (json, expected) in cases {
+            let typ: DataType = serde_json::from_str(json).unwrap();
+            assert_eq!(typ, expected.parse::<DataType>().unwrap(), "{json}");
+        }
+        assert!(serde_json::from_str::<DataType>("\"TIME WITH LOCAL TIME ZONE\"").is_err());
+    }
+}
@@ -51,7 +51,10 @@ bitflags! {
 /// Data type for paimon table.
 ///
 /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/types/DataType.java#L45>
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Atomic types are (de)serialized as their SQL string such as `INT NOT NULL`, which is parsed
+/// with [`FromStr`]; `ARRAY`, `MAP`, `MULTISET` and `ROW` are JSON objects.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 #[serde(untagged)]
 pub enum DataType {
     /// Data type of a boolean with a (possibly) three-valued logic of `TRUE`, `FALSE`, `UNKNOWN`.
@@ -103,9 +106,34 @@ pub enum DataType {
     Row(RowType),
 }
 
-#[allow(dead_code)]
+impl<'de> Deserialize<'de> for DataType {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Name(String),
+            Array(ArrayType),
+            Map(MapType),
+            Multiset(MultisetType),
+            Row(RowType),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Name(s) => s.parse().map_err(serde::de::Error::custom),
+            Repr::Array(v) => Ok(DataType::Array(v)),
+            Repr::Map(v) => Ok(DataType::Map(v)),
+            Repr::Multiset(v) => Ok(DataType::Multiset(v)),
+            Repr::Row(v) => Ok(DataType::Row(v)),
+        }
+    }
+}
+
 impl DataType {
-    fn is_nullable(&self) -> bool {
+    /// Whether the type accepts null values.
+    pub fn is_nullable(&self) -> bool {
         match self {
             DataType::Boolean(v) => v.nullable,
             DataType::TinyInt(v) => v.nullable,
@@ -129,6 +157,150 @@ impl DataType {
             DataType::Row(v) => v.nullable,
         }
     }
+
+    /// Get a copy of the type with the given nullability.
+    pub fn copy_with_nullable(&self, nullable: bool) -> DataType {
+        let mut typ = self.clone();
+        match &mut typ {
+            DataType::Boolean(v) => v.nullable = nullable,
+            DataType::TinyInt(v) => v.nullable = nullable,
+            DataType::SmallInt(v) => v.nullable = nullable,
+            DataType::Int(v) => v.nullable = nullable,
+            DataType::BigInt(v) => v.nullable = nullable,
+            DataType::Decimal(v) => v.nullable = nullable,
+            DataType::Double(v) => v.nullable = nullable,
+            DataType::Float(v) => v.nullable = nullable,
+            DataType::Binary(v) => v.nullable = nullable,
+            DataType::VarBinary(v) => v.nullable = nullable,
+            DataType::Char(v) => v.nullable = nullable,
+            DataType::VarChar(v) => v.nullable = nullable,
+            DataType::Date(v) => v.nullable = nullable,
+            DataType::LocalZonedTimestamp(v) => v.nullable = nullable,
+            DataType::Time(v) => v.nullable = nullable,
+            DataType::Timestamp(v) => v.nullable = nullable,
+            DataType::Array(v) => v.nullable = nullable,
+            DataType::Map(v) => v.nullable = nullable,
+            DataType::Multiset(v) => v.nullable = nullable,
+            DataType::Row(v) => v.nullable = nullable,
+        }
+        typ
+    }
+
+    /// Get the family of the type.
+    pub fn family(&self) -> DataTypeFamily {
+        match self {
+            DataType::Boolean(v) => v.family(),
+            DataType::TinyInt(v) => v.family(),
+            DataType::SmallInt(v) => v.family(),
+            DataType::Int(v) => v.family(),
+            DataType::BigInt(v) => v.family(),
+            DataType::Decimal(v) => v.family(),
+            DataType::Double(v) => v.family(),
+            DataType::Float(v) => v.family(),
+            DataType::Binary(v) => v.family(),
+            DataType::VarBinary(v) => v.family(),
+            DataType::Char(v) => v.family(),
+            DataType::VarChar(v) => v.family(),
+            DataType::Date(v) => v.family(),
+            DataType::LocalZonedTimestamp(v) => v.family(),
+            DataType::Time(v) => v.family(),
+            DataType::Timestamp(v) => v.family(),
+            DataType::Array(v) => v.family(),
+            DataType::Map(v) => v.family(),
+            DataType::Multiset(v) => v.family(),
+            DataType::Row(v) => v.family(),
+        }
+    }
+}
+
+impl DataType {
+    /// Whether the values of the type can be cast to the target type without losing
+    /// information, ignoring the lengths and precisions. A nullable type can't be cast to a
+    /// `NOT NULL` type.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/types/DataTypeCasts.java>
+    pub fn supports_implicit_cast(&self, target: &DataType) -> bool {
+        if self.is_nullable() && !target.is_nullable() {
+            return false;
+        }
+        if self.copy_with_nullable(true) == target.copy_with_nullable(true) {
+            return true;
+        }
+        let family = self.family();
+        match target {
+            DataType::Char(_) => matches!(self, DataType::Char(_)),
+            DataType::VarChar(_) => family.contains(DataTypeFamily::CHARACTER_STRING),
+            DataType::Boolean(_) => matches!(self, DataType::Boolean(_)),
+            DataType::Binary(_) => matches!(self, DataType::Binary(_)),
+            DataType::VarBinary(_) => family.contains(DataTypeFamily::BINARY_STRING),
+            DataType::Decimal(_) => family.contains(DataTypeFamily::EXACT_NUMERIC),
+            DataType::TinyInt(_) => matches!(self, DataType::TinyInt(_)),
+            DataType::SmallInt(_) => matches!(self, DataType::TinyInt(_) | DataType::SmallInt(_)),
+            DataType::Int(_) => matches!(
+                self,
+                DataType::TinyInt(_) | DataType::SmallInt(_) | DataType::Int(_)
+            ),
+            DataType::BigInt(_) => family.contains(DataTypeFamily::INTEGER_NUMERIC),
+            DataType::Float(_) => matches!(
+                self,
+                DataType::TinyInt(_)
+                    | DataType::SmallInt(_)
+                    | DataType::Int(_)
+                    | DataType::BigInt(_)
+                    | DataType::Float(_)
+                    | DataType::Decimal(_)
+            ),
+            DataType::Double(_) => family.contains(DataTypeFamily::NUMERIC),
+            DataType::Date(_) => matches!(self, DataType::Date(_) | DataType::Timestamp(_)),
+            DataType::Time(_) => matches!(self, DataType::Time(_) | DataType::Timestamp(_)),
+            DataType::Timestamp(_) | DataType::LocalZonedTimestamp(_) => matches!(
+                self,
+                DataType::Timestamp(_) | DataType::LocalZonedTimestamp(_)
+            ),
+            DataType::Array(_) | DataType::Map(_) | DataType::Multiset(_) | DataType::Row(_) => {
+                false
+            }
+        }
+    }
+}
+
+impl Display for DataType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataType::Boolean(v) => write_simple_type::<serde_utils::BOOLEAN>(f, v.nullable),
+            DataType::TinyInt(v) => write_simple_type::<serde_utils::TINYINT>(f, v.nullable),
+            DataType::SmallInt(v) => write_simple_type::<serde_utils::SMALLINT>(f, v.nullable),
+            DataType::Int(v) => write_simple_type::<serde_utils::INT>(f, v.nullable),
+            DataType::BigInt(v) => write_simple_type::<serde_utils::BIGINT>(f, v.nullable),
+            DataType::Float(v) => write_simple_type::<serde_utils::FLOAT>(f, v.nullable),
+            DataType::Date(v) => write_simple_type::<serde_utils::DATE>(f, v.nullable),
+            DataType::Decimal(v) => write!(f, "{v}"),
+            DataType::Double(v) => write!(f, "{v}"),
+            DataType::Binary(v) => write!(f, "{v}"),
+            DataType::VarBinary(v) => write!(f, "{v}"),
+            DataType::Char(v) => write!(f, "{v}"),
+            DataType::VarChar(v) => write!(f, "{v}"),
+            DataType::LocalZonedTimestamp(v) => write!(f, "{v}"),
+            DataType::Time(v) => write!(f, "{v}"),
+            DataType::Timestamp(v) => write!(f, "{v}"),
+            DataType::Array(v) => write!(f, "{v}"),
+            DataType::Map(v) => write!(f, "{v}"),
+            DataType::Multiset(v) => write!(f, "{v}"),
+            DataType::Row(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+fn write_simple_type<T: DataTypeName>(f: &mut Formatter<'_>, nullable: bool) -> std::fmt::Result {
+    write!(f, "{}", T::NAME)?;
+    write_not_null(f, nullable)
+}
+
+fn write_not_null(f: &mut Formatter<'_>, nullable: bool) -> std::fmt::Result {
+    if !nullable {
+        write!(f, " NOT NULL")?;
+    }
+    Ok(())
 }
 
 /// ArrayType for paimon.
@@ -146,6 +318,13 @@ pub struct ArrayType {
     element_type: Box<DataType>,
 }
 
+impl Display for ArrayType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ARRAY<{}>", self.element_type)?;
+        write_not_null(f, self.nullable)
+    }
+}
+
 impl ArrayType {
     pub fn new(element_type: DataType) -> Self {
         Self::with_nullable(true, element_type)
@@ -158,6 +337,10 @@ impl ArrayType {
         }
     }
 
+    pub fn element_type(&self) -> &DataType {
+        &self.element_type
+    }
+
     pub fn family(&self) -> DataTypeFamily {
         DataTypeFamily::CONSTRUCTED | DataTypeFamily::COLLECTION
     }
@@ -1072,7 +1255,11 @@ pub struct VarBinaryType {
 
 impl Display for VarBinaryType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "VARBINARY({})", self.length)?;
+        if self.length == Self::MAX_LENGTH {
+            write!(f, "BYTES")?;
+        } else {
+            write!(f, "VARBINARY({})", self.length)?;
+        }
         if !self.nullable {
             write!(f, " NOT NULL")?;
         }
@@ -1090,6 +1277,13 @@ impl FromStr for VarBinaryType {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(rest) = s.strip_prefix(serde_utils::BYTES::NAME) {
+            let nullable = !rest.contains("NOT NULL");
+            return Ok(VarBinaryType {
+                nullable,
+                length: Self::MAX_LENGTH,
+            });
+        }
         if !s.starts_with(serde_utils::VARBINARY::NAME) {
             return DataTypeInvalidSnafu {
                 message: "Invalid VARBINARY type. Expected string to start with 'VARBINARY'.",
@@ -1115,7 +1309,7 @@ impl FromStr for VarBinaryType {
 impl VarBinaryType {
     pub const MIN_LENGTH: u32 = 1;
 
-    pub const MAX_LENGTH: u32 = isize::MAX as u32;
+    pub const MAX_LENGTH: u32 = i32::MAX as u32;
 
     pub const DEFAULT_LENGTH: u32 = 1;
 
@@ -1157,7 +1351,11 @@ pub struct VarCharType {
 
 impl Display for VarCharType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "VARCHAR({})", self.length)?;
+        if self.length == Self::MAX_LENGTH {
+            write!(f, "STRING")?;
+        } else {
+            write!(f, "VARCHAR({})", self.length)?;
+        }
         if !self.nullable {
             write!(f, " NOT NULL")?;
         }
@@ -1175,6 +1373,13 @@ impl FromStr for VarCharType {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(rest) = s.strip_prefix(serde_utils::STRING::NAME) {
+            let nullable = !rest.contains("NOT NULL");
+            return Ok(VarCharType {
+                nullable,
+                length: Self::MAX_LENGTH,
+            });
+        }
         if !s.starts_with(serde_utils::VARCHAR::NAME) {
             return DataTypeInvalidSnafu {
                 message: "Invalid VARCHAR type. Expected string to start with 'VARCHAR'.",
@@ -1200,7 +1405,7 @@ impl FromStr for VarCharType {
 impl VarCharType {
     pub const MIN_LENGTH: u32 = 1;
 
-    pub const MAX_LENGTH: u32 = isize::MAX as u32;
+    pub const MAX_LENGTH: u32 = i32::MAX as u32;
 
     pub const DEFAULT_LENGTH: u32 = 1;
 
@@ -1249,6 +1454,13 @@ pub struct MapType {
     value_type: Box<DataType>,
 }
 
+impl Display for MapType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MAP<{}, {}>", self.key_type, self.value_type)?;
+        write_not_null(f, self.nullable)
+    }
+}
+
 impl MapType {
     pub fn new(key_type: DataType, value_type: DataType) -> Self {
         Self::with_nullable(true, key_type, value_type)
@@ -1262,6 +1474,14 @@ impl MapType {
         }
     }
 
+    pub fn key_type(&self) -> &DataType {
+        &self.key_type
+    }
+
+    pub fn value_type(&self) -> &DataType {
+        &self.value_type
+    }
+
     pub fn family(&self) -> DataTypeFamily {
         DataTypeFamily::CONSTRUCTED | DataTypeFamily::COLLECTION
     }
@@ -1283,6 +1503,13 @@ pub struct MultisetType {
     element_type: Box<DataType>,
 }
 
+impl Display for MultisetType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MULTISET<{}>", self.element_type)?;
+        write_not_null(f, self.nullable)
+    }
+}
+
 impl MultisetType {
     pub fn new(element_type: DataType) -> Self {
         Self::with_nullable(true, element_type)
@@ -1295,6 +1522,10 @@ impl MultisetType {
         }
     }
 
+    pub fn element_type(&self) -> &DataType {
+        &self.element_type
+    }
+
     pub fn family(&self) -> DataTypeFamily {
         DataTypeFamily::CONSTRUCTED | DataTypeFamily::COLLECTION
     }
@@ -1317,6 +1548,23 @@ pub struct RowType {
     fields: Vec<DataField>,
 }
 
+impl Display for RowType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ROW<")?;
+        for (i, field) in self.fields.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "`{}` {}", field.name(), field.data_type())?;
+            if let Some(description) = field.description() {
+                write!(f, " '{}'", description.replace('\'', "''"))?;
+            }
+        }
+        write!(f, ">")?;
+        write_not_null(f, self.nullable)
+    }
+}
+
 impl RowType {
     pub const fn new(fields: Vec<DataField>) -> Self {
         Self::with_nullable(true, fields)
@@ -1326,6 +1574,19 @@ impl RowType {
         Self { nullable, fields }
     }
 
+    pub fn fields(&self) -> &[DataField] {
+        &self.fields
+    }
+
+    pub fn field_names(&self) -> Vec<&str> {
+        self.fields.iter().map(|f| f.name()).collect()
+    }
+
+    /// Get the index of the field with given name.
+    pub fn field_index(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|f| f.name() == name)
+    }
+
     pub fn family(&self) -> DataTypeFamily {
         DataTypeFamily::CONSTRUCTED
     }
@@ -1368,6 +1629,11 @@ mod serde_utils {
         const NAME: &'static str = "VARBINARY";
     }
 
+    pub struct BYTES;
+    impl DataTypeName for BYTES {
+        const NAME: &'static str = "BYTES";
+    }
+
     pub struct CHAR;
     impl DataTypeName for CHAR {
         const NAME: &'static str = "CHAR";
@@ -1378,6 +1644,11 @@ mod serde_utils {
         const NAME: &'static str = "VARCHAR";
     }
 
+    pub struct STRING;
+    impl DataTypeName for STRING {
+        const NAME: &'static str = "STRING";
+    }
+
     pub struct DECIMAL;
     impl DataTypeName for DECIMAL {
         const NAME: &'static str = "DECIMAL";
@@ -2080,4 +2351,87 @@ mod tests {
             assert_eq!(actual, expect, "test data type deserialize for {name}")
         }
     }
+
+    #[test]
+    fn test_string_and_bytes_type() {
+        let string: DataType = serde_json::from_str("\"STRING NOT NULL\"").unwrap();
+        assert_eq!(
+            string,
+            DataType::VarChar(VarCharType::with_nullable(false, VarCharType::MAX_LENGTH).unwrap())
+        );
+        assert_eq!(
+            serde_json::to_string(&string).unwrap(),
+            "\"STRING NOT NULL\""
+        );
+
+        let bytes: DataType = serde_json::from_str("\"BYTES\"").unwrap();
+        assert_eq!(
+            bytes,
+            DataType::VarBinary(VarBinaryType::new(VarBinaryType::MAX_LENGTH).unwrap())
+        );
+        assert_eq!(serde_json::to_string(&bytes).unwrap(), "\"BYTES\"");
+    }
+
+    #[test]
+    fn test_data_type_display() {
+        let row = DataType::Row(RowType::new(vec![
+            DataField::new(
+                0,
+                "id".to_string(),
+                DataType::Int(IntType::with_nullable(false)),
+            ),
+            DataField::new(
+                1,
+                "tags".to_string(),
+                DataType::Array(ArrayType::new(DataType::VarChar(
+                    VarCharType::new(VarCharType::MAX_LENGTH).unwrap(),
+                ))),
+            )
+            .with_description(Some("user's tags".to_string())),
+            DataField::new(
+                2,
+                "props".to_string(),
+                DataType::Map(MapType::with_nullable(
+                    false,
+                    DataType::VarChar(VarCharType::new(10).unwrap()),
+                    DataType::Decimal(DecimalType::new(10, 2).unwrap()),
+                )),
+            ),
+        ]));
+        assert_eq!(
+            row.to_string(),
+            "ROW<`id` INT NOT NULL, `tags` ARRAY<STRING> 'user''s tags', \
+            `props` MAP<VARCHAR(10), DECIMAL(10, 2)> NOT NULL>"
+        );
+        assert!(row.is_nullable());
+        assert_eq!(row.family(), DataTypeFamily::CONSTRUCTED);
+
+        let DataType::Row(row_type) = row else {
+            unreachable!()
+        };
+        assert_eq!(row_type.field_names(), vec!["id", "tags", "props"]);
+        assert_eq!(row_type.field_index("props"), Some(2));
+        assert_eq!(row_type.field_index("unknown"), None);
+    }
+
+    #[test]
+    fn test_supports_implicit_cast() {
+        let cast = |source: &str, target: &str| {
+            let source: DataType = source.parse().unwrap();
+            source.supports_implicit_cast(&target.parse().unwrap())
+        };
+        assert!(cast("INT", "INT"));
+        assert!(cast("INT NOT NULL", "INT"));
+        assert!(cast("INT", "BIGINT"));
+        assert!(cast("INT", "DECIMAL(10, 2)"));
+        assert!(cast("FLOAT", "DOUBLE"));
+        assert!(cast("CHAR(3)", "STRING"));
+        assert!(cast("TIMESTAMP(3)", "TIMESTAMP_LTZ(3)"));
+        assert!(!cast("BIGINT", "INT"));
+        assert!(!cast("DOUBLE", "FLOAT"));
+        assert!(!cast("STRING", "INT"));
+        assert!(!cast("INT", "STRING"));
+        assert!(!cast("INT", "INT NOT NULL"));
+        assert!(!cast("ARRAY<INT>", "ARRAY<BIGINT>"));
+    }
 }
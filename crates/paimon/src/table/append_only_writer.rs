@@ -0,0 +1,65 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::DataFileWriter;
+use crate::table::DataIncrement;
+use arrow_array::RecordBatch;
+
+/// Writer of a bucket of append only tables, the rows are written into data files directly
+/// in the order they arrive.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/append/AppendOnlyWriter.java>
+#[derive(Debug)]
+pub(crate) struct AppendOnlyWriter {
+    writer: DataFileWriter,
+    next_sequence_number: i64,
+}
+
+impl AppendOnlyWriter {
+    pub(crate) fn new(writer: DataFileWriter, next_sequence_number: i64) -> Self {
+        Self {
+            writer,
+            next_sequence_number,
+        }
+    }
+
+    /// Write the rows of the table fields.
+    pub(crate) async fn write(&mut self, batch: &RecordBatch) -> crate::Result<()> {
+        let num_rows = batch.num_rows() as i64;
+        if num_rows == 0 {
+            return Ok(());
+        }
+        let min_sequence_number = self.next_sequence_number;
+        self.next_sequence_number += num_rows;
+        self.writer
+            .write(batch, min_sequence_number, self.next_sequence_number - 1)
+            .await
+    }
+
+    /// Close the data file being written, the next rows are written into a new file.
+    pub(crate) async fn flush(&mut self) -> crate::Result<()> {
+        self.writer.roll().await
+    }
+
+    /// Close the data files and get the files written.
+    pub(crate) async fn prepare_commit(self) -> crate::Result<DataIncrement> {
+        Ok(DataIncrement {
+            new_files: self.writer.close().await?,
+            ..Default::default()
+        })
+    }
+}
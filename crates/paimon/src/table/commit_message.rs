@@ -0,0 +1,150 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::spec::{BinaryRow, DataFileMeta, IndexFileMeta};
+
+/// Files changed by writing new data into a bucket.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/io/DataIncrement.java>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DataIncrement {
+    /// The data files written.
+    pub new_files: Vec<DataFileMeta>,
+    /// The data files removed, like the files overwritten.
+    pub deleted_files: Vec<DataFileMeta>,
+    /// The changelog files written along with the data files.
+    pub changelog_files: Vec<DataFileMeta>,
+}
+
+impl DataIncrement {
+    /// Whether no file is changed.
+    pub fn is_empty(&self) -> bool {
+        self.new_files.is_empty()
+            && self.deleted_files.is_empty()
+            && self.changelog_files.is_empty()
+    }
+}
+
+/// Files changed by compacting the data files of a bucket.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/io/CompactIncrement.java>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CompactIncrement {
+    /// The data files compacted.
+    pub compact_before: Vec<DataFileMeta>,
+    /// The data files produced by the compaction.
+    pub compact_after: Vec<DataFileMeta>,
+    /// The changelog files produced by the compaction.
+    pub changelog_files: Vec<DataFileMeta>,
+}
+
+impl CompactIncrement {
+    /// Whether no file is changed.
+    pub fn is_empty(&self) -> bool {
+        self.compact_before.is_empty()
+            && self.compact_after.is_empty()
+            && self.changelog_files.is_empty()
+    }
+}
+
+/// Index files changed in a bucket, like the hash index files of dynamic bucket tables.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/io/IndexIncrement.java>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IndexIncrement {
+    pub new_index_files: Vec<IndexFileMeta>,
+    pub deleted_index_files: Vec<IndexFileMeta>,
+}
+
+impl IndexIncrement {
+    pub fn is_empty(&self) -> bool {
+        self.new_index_files.is_empty() && self.deleted_index_files.is_empty()
+    }
+}
+
+/// The files changed in a bucket of a partition, produced by [`TableWrite`](crate::table::TableWrite)
+/// and committed by [`TableCommit`](crate::table::TableCommit).
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/sink/CommitMessageImpl.java>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommitMessage {
+    partition: BinaryRow,
+    bucket: i32,
+    data_increment: DataIncrement,
+    compact_increment: CompactIncrement,
+    index_increment: IndexIncrement,
+}
+
+impl CommitMessage {
+    pub fn new(
+        partition: BinaryRow,
+        bucket: i32,
+        data_increment: DataIncrement,
+        compact_increment: CompactIncrement,
+    ) -> Self {
+        Self {
+            partition,
+            bucket,
+            data_increment,
+            compact_increment,
+            index_increment: IndexIncrement::default(),
+        }
+    }
+
+    /// Set the index files changed in the bucket.
+    pub fn with_index_increment(mut self, index_increment: IndexIncrement) -> Self {
+        self.index_increment = index_increment;
+        self
+    }
+
+    /// Get the partition of the changed files.
+    #[inline]
+    pub fn partition(&self) -> &BinaryRow {
+        &self.partition
+    }
+
+    /// Get the bucket of the changed files.
+    #[inline]
+    pub fn bucket(&self) -> i32 {
+        self.bucket
+    }
+
+    /// Get the files changed by writing new data.
+    #[inline]
+    pub fn data_increment(&self) -> &DataIncrement {
+        &self.data_increment
+    }
+
+    /// Get the files changed by compaction.
+    #[inline]
+    pub fn compact_increment(&self) -> &CompactIncrement {
+        &self.compact_increment
+    }
+
+    /// Get the index files changed in the bucket.
+    #[inline]
+    pub fn index_increment(&self) -> &IndexIncrement {
+        &self.index_increment
+    }
+
+    /// Whether no file is changed.
+    pub fn is_empty(&self) -> bool {
+        self.data_increment.is_empty()
+            && self.compact_increment.is_empty()
+            && self.index_increment.is_empty()
+    }
+}
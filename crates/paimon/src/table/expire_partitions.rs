@@ -0,0 +1,351 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::spec::Datum;
+use crate::table::{BatchWriteBuilder, FileStoreTable, Table, TableCommit};
+use crate::utils::partition_value_string;
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// An expiration of the partitions of [`FileStoreTable`] by a commit user, dropping the
+/// partitions whose time is `partition.expiration-time` before now.
+///
+/// The time of a partition is parsed by `partition.timestamp-formatter` from the values of
+/// its partition keys composed by `partition.timestamp-pattern`, or from the value of the first
+/// partition key by default. Partitions whose time can't be parsed are never expired. The data
+/// files and the index files of the partitions expired are deleted in a snapshot of kind
+/// `OVERWRITE`.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/PartitionExpire.java>
+#[derive(Debug, Clone)]
+pub struct ExpirePartitions {
+    table: FileStoreTable,
+    commit_user: String,
+    expiration_time: Option<Duration>,
+    now: NaiveDateTime,
+}
+
+impl ExpirePartitions {
+    pub fn new(table: FileStoreTable, commit_user: impl Into<String>) -> Self {
+        let expiration_time = table.core_options().partition_expiration_time();
+        Self {
+            table,
+            commit_user: commit_user.into(),
+            expiration_time,
+            now: Local::now().naive_local(),
+        }
+    }
+
+    /// Expire the partitions whose time is the given duration before now.
+    pub fn with_expiration_time(mut self, expiration_time: Duration) -> Self {
+        self.expiration_time = Some(expiration_time);
+        self
+    }
+
+    /// Expire by the given local time as now, instead of the current time.
+    pub fn with_now(mut self, now: NaiveDateTime) -> Self {
+        self.now = now;
+        self
+    }
+
+    /// Get the user committing the partitions dropped.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Drop the partitions expired, returns the values of the partition keys of them.
+    pub async fn expire(&self) -> crate::Result<Vec<HashMap<String, Datum>>> {
+        let Some(expiration_time) = self
+            .expiration_time
+            .and_then(|time| chrono::Duration::from_std(time).ok())
+        else {
+            return Ok(vec![]);
+        };
+        let Some(expire_before) = self.now.checked_sub_signed(expiration_time) else {
+            return Ok(vec![]);
+        };
+        let partition_type = self.table.schema().logical_partition_type();
+        let fields = partition_type.fields();
+        if fields.is_empty() {
+            return Ok(vec![]);
+        }
+        let options = self.table.core_options();
+        let extractor = PartitionTimeExtractor {
+            pattern: options.partition_timestamp_pattern(),
+            formatter: options.partition_timestamp_formatter(),
+        };
+
+        let mut expired = Vec::new();
+        for entry in self.table.list_partitions().await? {
+            let mut keys = Vec::with_capacity(fields.len());
+            let mut values = Vec::with_capacity(fields.len());
+            for (pos, field) in fields.iter().enumerate() {
+                if let Some(value) = entry.partition().get_datum(pos, field.data_type())? {
+                    keys.push(field.name());
+                    values.push(value);
+                }
+            }
+            // partitions with null values fall into the default partition without a time
+            if values.len() < fields.len() {
+                continue;
+            }
+            let strings: Vec<String> = values.iter().map(partition_value_string).collect();
+            if extractor
+                .extract(&keys, &strings)
+                .is_some_and(|time| time < expire_before)
+            {
+                let partition = keys.iter().map(|key| key.to_string()).zip(values);
+                expired.push(partition.collect());
+            }
+        }
+        if !expired.is_empty() {
+            TableCommit::new(
+                self.table.clone(),
+                self.commit_user.clone(),
+                BatchWriteBuilder::COMMIT_IDENTIFIER,
+            )
+            .drop_partitions(&expired)
+            .await?;
+        }
+        Ok(expired)
+    }
+}
+
+/// Extractor of the time of partitions from the values of their partition keys.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/utils/PartitionTimeExtractor.java>
+struct PartitionTimeExtractor<'a> {
+    pattern: Option<&'a str>,
+    formatter: Option<&'a str>,
+}
+
+impl PartitionTimeExtractor<'_> {
+    /// Extract the time from the values of the partition keys, none if it can't be parsed.
+    fn extract(&self, keys: &[&str], values: &[String]) -> Option<NaiveDateTime> {
+        let text = match self.pattern {
+            Some(pattern) => keys
+                .iter()
+                .zip(values)
+                .fold(pattern.to_string(), |text, (key, value)| {
+                    text.replace(&format!("${key}"), value)
+                }),
+            None => values.first()?.clone(),
+        };
+        let formats = match self.formatter {
+            Some(formatter) => vec![to_chrono_format(formatter)],
+            // `yyyy-MM-dd HH:mm:ss` with the optional time
+            None => vec!["%Y-%m-%d %H:%M:%S".to_string(), "%Y-%m-%d".to_string()],
+        };
+        formats.iter().find_map(|format| {
+            NaiveDateTime::parse_from_str(&text, format)
+                .ok()
+                .or_else(|| {
+                    NaiveDate::parse_from_str(&text, format)
+                        .ok()
+                        .and_then(|date| date.and_hms_opt(0, 0, 0))
+                })
+        })
+    }
+}
+
+/// Convert the pattern of `java.time.format.DateTimeFormatter` into the format of chrono, the
+/// letters of years, months, days, hours, minutes, seconds and fractions are supported, other
+/// characters and the quoted texts are literals.
+fn to_chrono_format(pattern: &str) -> String {
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut format = String::new();
+    let push_literal = |format: &mut String, c: char| {
+        if c == '%' {
+            format.push_str("%%");
+        } else {
+            format.push(c);
+        }
+    };
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\'' {
+            let end = chars[i + 1..]
+                .iter()
+                .position(|&c| c == '\'')
+                .map_or(chars.len(), |pos| i + 1 + pos);
+            for &c in &chars[i + 1..end] {
+                push_literal(&mut format, c);
+            }
+            i = end + 1;
+            continue;
+        }
+        let count = chars[i..].iter().take_while(|&&next| next == c).count();
+        match c {
+            'y' | 'u' if count == 2 => format.push_str("%y"),
+            'y' | 'u' => format.push_str("%Y"),
+            'M' => format.push_str("%m"),
+            'd' => format.push_str("%d"),
+            'H' => format.push_str("%H"),
+            'm' => format.push_str("%M"),
+            's' => format.push_str("%S"),
+            'S' if count <= 3 => format.push_str("%3f"),
+            'S' if count <= 6 => format.push_str("%6f"),
+            'S' => format.push_str("%9f"),
+            _ => (0..count).for_each(|_| push_literal(&mut format, c)),
+        }
+        i += count;
+    }
+    format
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{CommitKind, DataField, Schema};
+    use crate::utils::SchemaManager;
+    use arrow_array::{Int32Array, RecordBatch, StringArray};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_extract_partition_time() {
+        let time = |text: &str| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S").unwrap();
+        let values = |values: &[&str]| values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        let extractor = PartitionTimeExtractor {
+            pattern: None,
+            formatter: None,
+        };
+        assert_eq!(
+            extractor.extract(&["dt"], &values(&["2024-01-02"])),
+            Some(time("2024-01-02 00:00:00"))
+        );
+        assert_eq!(
+            extractor.extract(&["dt"], &values(&["2024-01-02 10:20:30"])),
+            Some(time("2024-01-02 10:20:30"))
+        );
+        assert_eq!(extractor.extract(&["dt"], &values(&["20240102"])), None);
+
+        let extractor = PartitionTimeExtractor {
+            pattern: Some("$dt $hour:00:00"),
+            formatter: Some("yyyyMMdd HH:mm:ss"),
+        };
+        assert_eq!(
+            extractor.extract(&["dt", "hour"], &values(&["20240102", "08"])),
+            Some(time("2024-01-02 08:00:00"))
+        );
+        let extractor = PartitionTimeExtractor {
+            pattern: None,
+            formatter: Some("yyyy'T'MM.dd"),
+        };
+        assert_eq!(
+            extractor.extract(&["dt"], &values(&["2024T01.02"])),
+            Some(time("2024-01-02 00:00:00"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_expire_partitions() {
+        let location = "file:/tmp/test_expire_partitions";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "dt".to_string(), "STRING".parse().unwrap()),
+                        DataField::new(1, "id".to_string(), "INT".parse().unwrap()),
+                    ])
+                    .partition_keys(vec!["dt".to_string()])
+                    .options(
+                        [
+                            ("file.format".to_string(), "parquet".to_string()),
+                            ("partition.expiration-time".to_string(), "2 d".to_string()),
+                            (
+                                "partition.timestamp-formatter".to_string(),
+                                "yyyyMMdd".to_string(),
+                            ),
+                        ]
+                        .into(),
+                    )
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema);
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(StringArray::from(vec![
+                    "20240101", "20240102", "20240103", "unknown",
+                ])),
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+            ],
+        )
+        .unwrap();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write.write(&batch).await.unwrap();
+        builder
+            .new_commit()
+            .commit(write.prepare_commit().await.unwrap())
+            .await
+            .unwrap();
+
+        let now =
+            NaiveDateTime::parse_from_str("2024-01-03 12:00:00", "%Y-%m-%d %H:%M:%S").unwrap();
+        let expired = table
+            .new_expire_partitions()
+            .with_now(now)
+            .expire()
+            .await
+            .unwrap();
+        assert_eq!(
+            expired,
+            vec![HashMap::from([(
+                "dt".to_string(),
+                Datum::String("20240101".to_string())
+            )])]
+        );
+        let snapshot = table
+            .snapshot_manager()
+            .latest_snapshot()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.commit_kind(), &CommitKind::OVERWRITE);
+        assert_eq!(snapshot.total_record_count(), Some(3));
+        let mut partitions: Vec<String> = table
+            .list_partitions()
+            .await
+            .unwrap()
+            .iter()
+            .map(|entry| entry.partition().get_string(0).unwrap().to_string())
+            .collect();
+        partitions.sort();
+        assert_eq!(partitions, vec!["20240102", "20240103", "unknown"]);
+
+        // nothing more is expired by the same time
+        let expire = table.new_expire_partitions().with_now(now);
+        assert!(expire.expire().await.unwrap().is_empty());
+        let expired = expire
+            .with_expiration_time(Duration::from_secs(60 * 60))
+            .expire()
+            .await
+            .unwrap();
+        assert_eq!(expired.len(), 2);
+    }
+}
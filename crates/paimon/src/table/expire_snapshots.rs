@@ -0,0 +1,455 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::spec::{FileKind, Identifier, ManifestEntry, Snapshot};
+use crate::table::file_deletion::FileDeletion;
+use crate::table::FileStoreTable;
+use chrono::Utc;
+use std::collections::{HashMap, HashSet};
+
+/// An expiration of the snapshots of [`FileStoreTable`], deleting the snapshots out of
+/// retention with the files only used by them.
+///
+/// The latest `snapshot.num-retained.min` snapshots are always retained, and the ones out of
+/// the latest `snapshot.num-retained.max` snapshots are always expired, the snapshots between
+/// them are expired if they are committed `snapshot.time-retained` ago. At most
+/// `snapshot.expire.limit` snapshots are expired at a time, and the snapshots from the next
+/// snapshot of any consumer are never expired. The files of the tagged snapshots and the
+/// snapshots branches are created from are retained.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/ExpireSnapshotsImpl.java>
+#[derive(Debug, Clone)]
+pub struct ExpireSnapshots {
+    table: FileStoreTable,
+    retain_min: usize,
+    retain_max: usize,
+    older_than_millis: u64,
+    max_deletes: usize,
+}
+
+impl ExpireSnapshots {
+    pub fn new(table: FileStoreTable) -> Self {
+        let options = table.core_options();
+        let time_retained = options.snapshot_time_retained().as_millis() as u64;
+        let retain_min = options.snapshot_num_retained_min();
+        let retain_max = options.snapshot_num_retained_max();
+        let max_deletes = options.snapshot_expire_limit();
+        Self {
+            table,
+            retain_min,
+            retain_max,
+            older_than_millis: (Utc::now().timestamp_millis() as u64).saturating_sub(time_retained),
+            max_deletes,
+        }
+    }
+
+    /// Retain at least the given number of the latest snapshots.
+    pub fn with_retain_min(mut self, retain_min: usize) -> Self {
+        self.retain_min = retain_min;
+        self
+    }
+
+    /// Retain at most the given number of the latest snapshots.
+    pub fn with_retain_max(mut self, retain_max: usize) -> Self {
+        self.retain_max = retain_max;
+        self
+    }
+
+    /// Only expire the snapshots committed before the given time in milliseconds.
+    pub fn with_older_than_millis(mut self, older_than_millis: u64) -> Self {
+        self.older_than_millis = older_than_millis;
+        self
+    }
+
+    /// Expire at most the given number of snapshots.
+    pub fn with_max_deletes(mut self, max_deletes: usize) -> Self {
+        self.max_deletes = max_deletes;
+        self
+    }
+
+    /// Expire the snapshots out of retention, returns the number of snapshots expired.
+    pub async fn expire(&self) -> crate::Result<usize> {
+        if self.retain_min < 1 {
+            return ConfigInvalidSnafu {
+                message: format!(
+                    "snapshot.num-retained.min should be at least 1, but is {}",
+                    self.retain_min
+                ),
+            }
+            .fail();
+        }
+        if self.retain_max < self.retain_min {
+            return ConfigInvalidSnafu {
+                message: format!(
+                    "snapshot.num-retained.max {} should not be less than snapshot.num-retained.min {}",
+                    self.retain_max, self.retain_min
+                ),
+            }
+            .fail();
+        }
+        let snapshot_manager = self.table.snapshot_manager();
+        let Some(latest) = snapshot_manager.latest_snapshot_id().await? else {
+            return Ok(0);
+        };
+        let Some(earliest) = snapshot_manager.earliest_snapshot_id().await? else {
+            return Ok(0);
+        };
+        let count = |n: usize| i64::try_from(n).unwrap_or(i64::MAX);
+        let min = (latest - count(self.retain_max) + 1).max(earliest);
+        let mut max_exclusive = (latest - count(self.retain_min) + 1)
+            .min(earliest.saturating_add(count(self.max_deletes)));
+        // the snapshots to be consumed are protected
+        if let Some(next_snapshot) = self.table.consumer_manager().min_next_snapshot().await? {
+            max_exclusive = max_exclusive.min(next_snapshot);
+        }
+        for id in min..max_exclusive {
+            if snapshot_manager.snapshot_exists(id).await?
+                && self.older_than_millis <= snapshot_manager.snapshot(id).await?.time_millis()
+            {
+                return self.expire_until(earliest, id).await;
+            }
+        }
+        self.expire_until(earliest, max_exclusive).await
+    }
+
+    /// Expire the snapshots before the given snapshot.
+    async fn expire_until(&self, earliest: i64, end_exclusive: i64) -> crate::Result<usize> {
+        if end_exclusive <= earliest {
+            return Ok(0);
+        }
+        let snapshot_manager = self.table.snapshot_manager();
+        // snapshots before a missing one are already expired
+        let mut begin = earliest;
+        for id in (earliest..end_exclusive).rev() {
+            if !snapshot_manager.snapshot_exists(id).await? {
+                begin = id + 1;
+                break;
+            }
+        }
+        let deletion = FileDeletion::new(self.table.clone());
+        // the data of the tagged snapshots and the snapshots branches are created from is
+        // retained
+        let mut tagged_snapshots = self.table.tag_manager().tagged_snapshots().await?;
+        tagged_snapshots.extend(
+            self.table
+                .branch_manager()
+                .branch_created_snapshots()
+                .await?,
+        );
+        tagged_snapshots.sort_by_key(Snapshot::id);
+        tagged_snapshots.dedup_by_key(|snapshot| snapshot.id());
+
+        // the data files deleted by a snapshot are only used by the snapshots before it, and
+        // the ones of the nearest tag before it are skipped
+        let mut tagged_files: Option<(i64, HashMap<Identifier, ManifestEntry>)> = None;
+        for id in begin + 1..=end_exclusive {
+            let snapshot = snapshot_manager.snapshot(id).await?;
+            let mut deleted: HashMap<Identifier, ManifestEntry> = HashMap::new();
+            for entry in deletion
+                .read_entries(snapshot.delta_manifest_list())
+                .await?
+            {
+                match entry.kind() {
+                    FileKind::Add => {
+                        deleted.remove(&entry.identifier());
+                    }
+                    FileKind::Delete => {
+                        deleted.insert(entry.identifier(), entry);
+                    }
+                }
+            }
+            let skipped = match tagged_snapshots.iter().rev().find(|s| s.id() < id) {
+                Some(tag) => {
+                    if tagged_files.as_ref().map(|(tag_id, _)| *tag_id) != Some(tag.id()) {
+                        tagged_files = Some((tag.id(), deletion.data_files(tag).await?));
+                    }
+                    tagged_files.as_ref().map(|(_, files)| files)
+                }
+                None => None,
+            };
+            for (identifier, entry) in &deleted {
+                if !skipped.is_some_and(|files| files.contains_key(identifier)) {
+                    deletion.delete_data_file(entry).await?;
+                }
+            }
+        }
+
+        // the changelog files are only used by their snapshots
+        for id in begin..end_exclusive {
+            let snapshot = snapshot_manager.snapshot(id).await?;
+            if let Some(changelog_manifest_list) = snapshot.changelog_manifest_list() {
+                for entry in deletion.read_entries(changelog_manifest_list).await? {
+                    if entry.kind() == &FileKind::Add {
+                        deletion.delete_data_file(&entry).await?;
+                    }
+                }
+            }
+        }
+
+        // the manifests and the index files still used by the retained snapshot and the tags
+        // are skipped
+        let mut skipped_manifests = HashSet::new();
+        let mut skipped_index_files = HashSet::new();
+        let retained = snapshot_manager.snapshot(end_exclusive).await?;
+        for snapshot in std::iter::once(&retained).chain(&tagged_snapshots) {
+            let (manifests, index_files) = deletion.used_files(snapshot).await?;
+            skipped_manifests.extend(manifests);
+            skipped_index_files.extend(index_files);
+        }
+        for id in begin..end_exclusive {
+            let snapshot = snapshot_manager.snapshot(id).await?;
+            deletion
+                .delete_manifests(&snapshot, &skipped_manifests, &skipped_index_files)
+                .await?;
+        }
+
+        let file_io = self.table.file_io();
+        for id in begin..end_exclusive {
+            file_io
+                .delete_file(&snapshot_manager.snapshot_path(id))
+                .await?;
+        }
+        snapshot_manager.commit_earliest_hint(end_exclusive).await?;
+        Ok((end_exclusive - begin) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::Consumer;
+    use crate::table::{CommitMessage, CompactIncrement, DataIncrement, Table};
+    use crate::test_utils::{create_id_name_table, id_name_batch, write_and_commit};
+    use arrow_array::Int32Array;
+
+    async fn write(table: &FileStoreTable, id: i32) -> Vec<CommitMessage> {
+        write_and_commit(
+            table,
+            &id_name_batch(table, &[id], &[&format!("name-{id}")]),
+        )
+        .await
+    }
+
+    async fn exists(table: &FileStoreTable, message: &CommitMessage, file_name: &str) -> bool {
+        let bucket_path = table
+            .path_factory()
+            .bucket_path(message.partition(), message.bucket())
+            .unwrap();
+        table
+            .file_io()
+            .exists(&format!("{bucket_path}/{file_name}"))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_expire_snapshots() {
+        let table = create_id_name_table(
+            "file:/tmp/test_expire_snapshots",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("changelog-producer", "input"),
+            ],
+        )
+        .await;
+        let mut messages = Vec::new();
+        for id in 1..=3 {
+            messages.extend(write(&table, id).await);
+        }
+        // snapshot 4 compacts the file of snapshot 1
+        table
+            .new_batch_write_builder()
+            .new_commit()
+            .commit(vec![CommitMessage::new(
+                messages[0].partition().clone(),
+                0,
+                DataIncrement::default(),
+                CompactIncrement {
+                    compact_before: messages[0].data_increment().new_files.clone(),
+                    ..Default::default()
+                },
+            )])
+            .await
+            .unwrap();
+        let snapshot_manager = table.snapshot_manager();
+        let first = snapshot_manager.snapshot(1).await.unwrap();
+
+        // the snapshots are retained by time
+        assert_eq!(table.new_expire_snapshots().expire().await.unwrap(), 0);
+        assert!(matches!(
+            table
+                .new_expire_snapshots()
+                .with_retain_min(2)
+                .with_retain_max(1)
+                .expire()
+                .await,
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+        // the latest snapshot is always retained
+        assert!(matches!(
+            table
+                .new_expire_snapshots()
+                .with_retain_min(0)
+                .with_older_than_millis(u64::MAX)
+                .expire()
+                .await,
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+        assert_eq!(
+            snapshot_manager.snapshot_ids().await.unwrap(),
+            vec![1, 2, 3, 4]
+        );
+
+        // the snapshots to be consumed are retained
+        let consumer_manager = table.consumer_manager();
+        consumer_manager
+            .reset_consumer("c", Consumer::new(2))
+            .await
+            .unwrap();
+        let expire = table
+            .new_expire_snapshots()
+            .with_retain_min(1)
+            .with_older_than_millis(u64::MAX);
+        assert_eq!(expire.expire().await.unwrap(), 1);
+        assert_eq!(
+            snapshot_manager.earliest_snapshot_id().await.unwrap(),
+            Some(2)
+        );
+        consumer_manager.delete_consumer("c").await.unwrap();
+
+        assert_eq!(
+            expire.clone().with_max_deletes(1).expire().await.unwrap(),
+            1
+        );
+        assert_eq!(expire.expire().await.unwrap(), 1);
+        assert_eq!(snapshot_manager.snapshot_ids().await.unwrap(), vec![4]);
+        assert_eq!(
+            snapshot_manager.earliest_snapshot_id().await.unwrap(),
+            Some(4)
+        );
+
+        // the compacted file and the changelog files of the expired snapshots are deleted
+        let data_file = &messages[0].data_increment().new_files[0].file_name;
+        assert!(!exists(&table, &messages[0], data_file).await);
+        for message in &messages {
+            let changelog_file = &message.data_increment().changelog_files[0].file_name;
+            assert!(!exists(&table, message, changelog_file).await);
+        }
+        for message in &messages[1..] {
+            let data_file = &message.data_increment().new_files[0].file_name;
+            assert!(exists(&table, message, data_file).await);
+        }
+
+        // only the manifests of the retained snapshot are left
+        let path_factory = table.path_factory();
+        let file_io = table.file_io();
+        assert!(!file_io
+            .exists(&path_factory.manifest_path(first.base_manifest_list()))
+            .await
+            .unwrap());
+        let (manifests, _) = FileDeletion::new(table.clone())
+            .used_files(&snapshot_manager.snapshot(4).await.unwrap())
+            .await
+            .unwrap();
+        let mut left: Vec<String> = file_io
+            .list_status(&path_factory.manifest_dir())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|status| status.path.rsplit('/').next().unwrap().to_string())
+            .collect();
+        left.sort();
+        let mut manifests: Vec<String> = manifests.into_iter().collect();
+        manifests.sort();
+        assert_eq!(left, manifests);
+
+        let ids: Vec<i32> = {
+            let plan = table.new_scan().plan().await.unwrap();
+            let mut ids = Vec::new();
+            for split in plan.splits() {
+                for batch in table.new_read().read(split).await.unwrap() {
+                    let batch = batch.unwrap();
+                    let column = batch.column(0).as_any().downcast_ref::<Int32Array>();
+                    ids.extend(column.unwrap().values().iter().copied());
+                }
+            }
+            ids
+        };
+        assert_eq!(ids, vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_expire_tagged_snapshots() {
+        let table = create_id_name_table(
+            "file:/tmp/test_expire_tagged_snapshots",
+            &["id"],
+            &[("file.format", "parquet"), ("bucket", "1")],
+        )
+        .await;
+        let mut messages = write(&table, 1).await;
+        messages.extend(write(&table, 2).await);
+        table.create_tag("t1", 1).await.unwrap();
+        // snapshot 3 compacts the file of snapshot 1
+        table
+            .new_batch_write_builder()
+            .new_commit()
+            .commit(vec![CommitMessage::new(
+                messages[0].partition().clone(),
+                0,
+                DataIncrement::default(),
+                CompactIncrement {
+                    compact_before: messages[0].data_increment().new_files.clone(),
+                    ..Default::default()
+                },
+            )])
+            .await
+            .unwrap();
+        write(&table, 3).await;
+        let expire = table
+            .new_expire_snapshots()
+            .with_retain_min(1)
+            .with_older_than_millis(u64::MAX);
+        assert_eq!(expire.expire().await.unwrap(), 3);
+
+        // the files of the tagged snapshot are retained
+        let data_file = &messages[0].data_increment().new_files[0].file_name;
+        assert!(exists(&table, &messages[0], data_file).await);
+        let tagged = table.tag_manager().tagged_snapshot("t1").await.unwrap();
+        let plan = table.new_scan().with_tag("t1").plan().await.unwrap();
+        assert_eq!(plan.snapshot_id(), Some(1));
+        assert_eq!(plan.splits()[0].data_files()[0].file_name, *data_file);
+
+        // deleting the tag deletes the files only used by it
+        table.delete_tag("t1").await.unwrap();
+        assert!(!exists(&table, &messages[0], data_file).await);
+        let data_file = &messages[1].data_increment().new_files[0].file_name;
+        assert!(exists(&table, &messages[1], data_file).await);
+        let file_io = table.file_io();
+        let path_factory = table.path_factory();
+        assert!(!file_io
+            .exists(&path_factory.manifest_path(tagged.base_manifest_list()))
+            .await
+            .unwrap());
+        assert!(matches!(
+            table.new_scan().with_tag("t1").plan().await,
+            Err(crate::Error::TagNotExist { .. })
+        ));
+    }
+}
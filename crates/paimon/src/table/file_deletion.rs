@@ -0,0 +1,197 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::manifest::{IndexManifestFile, ManifestFile, ManifestList};
+use crate::spec::{BinaryRow, FileKind, Identifier, ManifestEntry, Snapshot};
+use crate::table::FileStoreTable;
+use std::collections::{HashMap, HashSet};
+
+/// Deletion of the files of the snapshots no longer retained, shared by expiring snapshots and
+/// deleting tags.
+///
+/// Missing files are skipped, so that an interrupted deletion can be done again.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/FileDeletionBase.java>
+#[derive(Debug, Clone)]
+pub(crate) struct FileDeletion {
+    table: FileStoreTable,
+}
+
+impl FileDeletion {
+    pub(crate) fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+
+    /// Get the entries of the data files of the snapshot by their identifiers.
+    pub(crate) async fn data_files(
+        &self,
+        snapshot: &Snapshot,
+    ) -> crate::Result<HashMap<Identifier, ManifestEntry>> {
+        let mut entries = self.read_entries(snapshot.base_manifest_list()).await?;
+        entries.extend(self.read_entries(snapshot.delta_manifest_list()).await?);
+        Ok(ManifestEntry::merge_entries(entries)?
+            .into_iter()
+            .filter(|entry| entry.kind() == &FileKind::Add)
+            .map(|entry| (entry.identifier(), entry))
+            .collect())
+    }
+
+    /// Delete the data files, the manifests and the index files of the snapshot, except the
+    /// ones used by the skipped snapshots.
+    pub(crate) async fn delete_unused_files(
+        &self,
+        snapshot: &Snapshot,
+        skipped_snapshots: &[&Snapshot],
+    ) -> crate::Result<()> {
+        let mut data_files = self.data_files(snapshot).await?;
+        let mut skipped_manifests = HashSet::new();
+        let mut skipped_index_files = HashSet::new();
+        for skipped in skipped_snapshots {
+            for identifier in self.data_files(skipped).await?.keys() {
+                data_files.remove(identifier);
+            }
+            let (manifests, index_files) = self.used_files(skipped).await?;
+            skipped_manifests.extend(manifests);
+            skipped_index_files.extend(index_files);
+        }
+        for entry in data_files.values() {
+            self.delete_data_file(entry).await?;
+        }
+        self.delete_manifests(snapshot, &skipped_manifests, &skipped_index_files)
+            .await
+    }
+
+    /// Get the names of the manifests and the index files used by the data of the snapshot.
+    pub(crate) async fn used_files(
+        &self,
+        snapshot: &Snapshot,
+    ) -> crate::Result<(HashSet<String>, HashSet<String>)> {
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+        let manifest_list = ManifestList::new(file_io.clone());
+        let mut manifests = HashSet::new();
+        for list in [
+            snapshot.base_manifest_list(),
+            snapshot.delta_manifest_list(),
+        ] {
+            for manifest in manifest_list
+                .read(&path_factory.manifest_path(list))
+                .await?
+            {
+                manifests.insert(manifest.file_name().to_string());
+            }
+            manifests.insert(list.to_string());
+        }
+        let mut index_files = HashSet::new();
+        if let Some(index_manifest) = snapshot.index_manifest() {
+            for entry in IndexManifestFile::new(file_io.clone())
+                .read(&path_factory.manifest_path(index_manifest))
+                .await?
+            {
+                index_files.insert(entry.index_file.file_name);
+            }
+            manifests.insert(index_manifest.to_string());
+        }
+        Ok((manifests, index_files))
+    }
+
+    /// Read the manifest entries of all the manifests of the manifest list, none if the list
+    /// is already deleted.
+    pub(crate) async fn read_entries(&self, list: &str) -> crate::Result<Vec<ManifestEntry>> {
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+        let path = path_factory.manifest_path(list);
+        if !file_io.exists(&path).await? {
+            return Ok(vec![]);
+        }
+        let manifest_file = ManifestFile::new(file_io.clone());
+        let mut entries = Vec::new();
+        for manifest in ManifestList::new(file_io.clone()).read(&path).await? {
+            let path = path_factory.manifest_path(manifest.file_name());
+            if file_io.exists(&path).await? {
+                entries.extend(manifest_file.read(&path).await?);
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Delete the data file of the entry with its extra files.
+    pub(crate) async fn delete_data_file(&self, entry: &ManifestEntry) -> crate::Result<()> {
+        let partition = BinaryRow::from_serialized_bytes(entry.partition())?;
+        let bucket_path = self
+            .table
+            .path_factory()
+            .bucket_path(&partition, entry.bucket())?;
+        let file = entry.file();
+        for name in std::iter::once(&file.file_name).chain(&file.extra_files) {
+            let _ = self
+                .table
+                .file_io()
+                .delete_file(&format!("{bucket_path}/{name}"))
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Delete the manifest lists, the manifests and the index files of the snapshot, except
+    /// the skipped ones.
+    pub(crate) async fn delete_manifests(
+        &self,
+        snapshot: &Snapshot,
+        skipped_manifests: &HashSet<String>,
+        skipped_index_files: &HashSet<String>,
+    ) -> crate::Result<()> {
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+        let manifest_list = ManifestList::new(file_io.clone());
+        let lists = [
+            Some(snapshot.base_manifest_list()),
+            Some(snapshot.delta_manifest_list()),
+            snapshot.changelog_manifest_list(),
+        ];
+        for list in lists.into_iter().flatten() {
+            let path = path_factory.manifest_path(list);
+            if skipped_manifests.contains(list) || !file_io.exists(&path).await? {
+                continue;
+            }
+            for manifest in manifest_list.read(&path).await? {
+                if !skipped_manifests.contains(manifest.file_name()) {
+                    let _ = file_io
+                        .delete_file(&path_factory.manifest_path(manifest.file_name()))
+                        .await;
+                }
+            }
+            let _ = file_io.delete_file(&path).await;
+        }
+        let Some(index_manifest) = snapshot.index_manifest() else {
+            return Ok(());
+        };
+        let path = path_factory.manifest_path(index_manifest);
+        if skipped_manifests.contains(index_manifest) || !file_io.exists(&path).await? {
+            return Ok(());
+        }
+        for entry in IndexManifestFile::new(file_io.clone()).read(&path).await? {
+            if !skipped_index_files.contains(&entry.index_file.file_name) {
+                let _ = file_io
+                    .delete_file(&path_factory.index_path(&entry.index_file.file_name))
+                    .await;
+            }
+        }
+        let _ = file_io.delete_file(&path).await;
+        Ok(())
+    }
+}
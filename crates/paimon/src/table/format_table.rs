@@ -0,0 +1,564 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::catalog::Identifier;
+use crate::error::*;
+use crate::format::{
+    infer_fields, plain_format_reader, to_arrow_array, to_arrow_schema, to_arrow_type, to_datum,
+    ArrowRecordBatchIter, DEFAULT_BATCH_SIZE,
+};
+use crate::io::FileIO;
+use crate::spec::{CoreOptions, DataField, DataType, Datum, RowType, Schema, TableSchema};
+use crate::utils::unescape_path_name;
+use arrow_array::{new_null_array, ArrayRef, RecordBatch, StringArray};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Type of the format tables, the option `type` of the tables.
+pub const FORMAT_TABLE_TYPE: &str = "format-table";
+
+/// A table of the plain files of `file.format` under its location, such as the directories of
+/// parquet, csv or json files written by other engines, which is scanned without the snapshots
+/// of paimon.
+///
+/// The files of partitioned tables are in the directories of the partitions like
+/// `dt=2024-01-01/hr=0/`, whose values are parsed from the names of the directories. Hidden
+/// files starting with `.` or `_`, such as `_SUCCESS`, are skipped. Columns of the files are
+/// mapped to the fields by their names, the missing ones are read as nulls.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-core/src/main/java/org/apache/paimon/table/FormatTable.java>
+#[derive(Debug, Clone)]
+pub struct FormatTable {
+    file_io: Arc<dyn FileIO>,
+    identifier: Identifier,
+    location: String,
+    schema: TableSchema,
+}
+
+/// A file of a format table to read, with the values of its partition.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatTableSplit {
+    path: String,
+    file_size: u64,
+    partition: Vec<Option<Datum>>,
+}
+
+impl FormatTableSplit {
+    /// Get the path of the file.
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the size of the file in bytes.
+    #[inline]
+    pub fn file_size(&self) -> u64 {
+        self.file_size
+    }
+
+    /// Get the values of the partition keys of the file.
+    #[inline]
+    pub fn partition(&self) -> &[Option<Datum>] {
+        &self.partition
+    }
+}
+
+impl FormatTable {
+    /// Infer the schema of a format table of the files of `file.format` under the location,
+    /// such as files to import, from the first file found.
+    ///
+    /// Directories of partitions like `dt=2024-01-01/` on the way to the file are partition
+    /// keys of strings, following the fields inferred from the file.
+    pub async fn infer_schema(
+        file_io: &dyn FileIO,
+        location: &str,
+        mut options: HashMap<String, String>,
+    ) -> crate::Result<Schema> {
+        options.insert("type".to_string(), FORMAT_TABLE_TYPE.to_string());
+        let format = CoreOptions::new(&options).file_format();
+        let mut partition_keys = Vec::new();
+        let mut dir = location.trim_end_matches('/').to_string();
+        let file = loop {
+            let statuses = file_io.list_status(&dir).await?;
+            let mut partition_dir = None;
+            let mut file = None;
+            for status in statuses {
+                let name = status.path.rsplit('/').next().unwrap_or_default();
+                if name.starts_with('.') || name.starts_with('_') {
+                    continue;
+                }
+                match (status.is_dir, name.split_once('=')) {
+                    (false, _) => file = file.or(Some(status.path)),
+                    (true, Some((key, _))) if partition_dir.is_none() => {
+                        partition_dir = Some((unescape_path_name(key), status.path))
+                    }
+                    _ => {}
+                }
+            }
+            match (file, partition_dir) {
+                (Some(file), _) => break file,
+                (None, Some((key, path))) => {
+                    partition_keys.push(key);
+                    dir = path;
+                }
+                (None, None) => {
+                    return ConfigInvalidSnafu {
+                        message: format!("No {format} file is found under {location}"),
+                    }
+                    .fail()
+                }
+            }
+        };
+        let bytes = file_io.new_input(&file)?.read().await?;
+        let mut fields = infer_fields(&format, bytes, &CoreOptions::new(&options))?;
+        fields.retain(|field| !partition_keys.iter().any(|key| key == field.name()));
+        for key in &partition_keys {
+            fields.push(DataField::new(0, key.clone(), "STRING".parse()?));
+        }
+        let fields = fields
+            .into_iter()
+            .enumerate()
+            .map(|(id, field)| field.with_id(id as i32))
+            .collect();
+        Ok(Schema::builder()
+            .fields(fields)
+            .partition_keys(partition_keys)
+            .options(options)
+            .build())
+    }
+
+    pub fn new(
+        file_io: Arc<dyn FileIO>,
+        identifier: Identifier,
+        location: impl Into<String>,
+        schema: TableSchema,
+    ) -> crate::Result<Self> {
+        let table_type = CoreOptions::new(schema.options()).table_type();
+        if table_type != FORMAT_TABLE_TYPE {
+            return ConfigInvalidSnafu {
+                message: format!(
+                    "Table {} is of type {table_type} rather than a format table",
+                    identifier.full_name()
+                ),
+            }
+            .fail();
+        }
+        Ok(Self {
+            file_io,
+            identifier,
+            location: location.into().trim_end_matches('/').to_string(),
+            schema,
+        })
+    }
+
+    /// Get the identifier of this table.
+    #[inline]
+    pub fn identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    /// Get the location of the files of this table.
+    #[inline]
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Get the schema of this table.
+    #[inline]
+    pub fn schema(&self) -> &TableSchema {
+        &self.schema
+    }
+
+    /// Get the row type of this table.
+    pub fn row_type(&self) -> RowType {
+        self.schema.logical_row_type()
+    }
+
+    /// Get the format of the files, in lower case.
+    pub fn format(&self) -> String {
+        CoreOptions::new(self.schema.options()).file_format()
+    }
+
+    /// Create a scan to plan the files of this table.
+    pub fn new_scan(&self) -> FormatTableScan {
+        FormatTableScan {
+            table: self.clone(),
+            partition_filter: HashMap::new(),
+        }
+    }
+
+    /// Create a read to read the files of this table.
+    pub fn new_read(&self) -> FormatTableRead {
+        FormatTableRead {
+            table: self.clone(),
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+}
+
+/// A scan listing the files of a [`FormatTable`] as splits.
+#[derive(Debug, Clone)]
+pub struct FormatTableScan {
+    table: FormatTable,
+    partition_filter: HashMap<String, Datum>,
+}
+
+impl FormatTableScan {
+    /// Only scan the files of the partitions of the values of some of the partition keys.
+    pub fn with_partition_filter(mut self, partition: HashMap<String, Datum>) -> Self {
+        self.partition_filter = partition;
+        self
+    }
+
+    /// List the files of the partitions to scan, each file is a split.
+    pub async fn plan(&self) -> crate::Result<Vec<FormatTableSplit>> {
+        let partition_type = self.table.schema.logical_partition_type();
+        let partition_fields = partition_type.fields();
+        let default_partition_name =
+            CoreOptions::new(self.table.schema.options()).partition_default_name();
+        let mut splits = Vec::new();
+        let mut dirs = vec![(self.table.location.clone(), Vec::new())];
+        while let Some((dir, partition)) = dirs.pop() {
+            let Some(field) = partition_fields.get(partition.len()) else {
+                for status in self.table.file_io.list_status(&dir).await? {
+                    let name = status.path.rsplit('/').next().unwrap_or_default();
+                    if status.is_dir || name.starts_with('.') || name.starts_with('_') {
+                        continue;
+                    }
+                    splits.push(FormatTableSplit {
+                        path: status.path,
+                        file_size: status.size,
+                        partition: partition.clone(),
+                    });
+                }
+                continue;
+            };
+            for status in self.table.file_io.list_status(&dir).await? {
+                let name = status.path.rsplit('/').next().unwrap_or_default();
+                let value = match name.split_once('=') {
+                    Some((key, value))
+                        if status.is_dir && unescape_path_name(key) == field.name() =>
+                    {
+                        unescape_path_name(value)
+                    }
+                    _ => continue,
+                };
+                let datum = if value == default_partition_name {
+                    None
+                } else {
+                    partition_datum(&value, field.data_type())?
+                };
+                if matches!(self.partition_filter.get(field.name()), Some(filter) if datum.as_ref() != Some(filter))
+                {
+                    continue;
+                }
+                let mut partition = partition.clone();
+                partition.push(datum);
+                dirs.push((status.path, partition));
+            }
+        }
+        splits.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(splits)
+    }
+}
+
+/// Parse the value of the partition directory as the datum of the type.
+fn partition_datum(value: &str, data_type: &DataType) -> crate::Result<Option<Datum>> {
+    let array = arrow_cast::cast(&StringArray::from(vec![value]), &to_arrow_type(data_type))?;
+    to_datum(&array, 0, data_type)
+}
+
+/// A read of the files of a [`FormatTable`] into arrow record batches of the fields of the
+/// table.
+#[derive(Debug, Clone)]
+pub struct FormatTableRead {
+    table: FormatTable,
+    batch_size: usize,
+}
+
+impl FormatTableRead {
+    /// Read the record batches of the number of rows, [`DEFAULT_BATCH_SIZE`] by default.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Read the rows of the file of the split, with the values of its partition.
+    pub async fn read(&self, split: &FormatTableSplit) -> crate::Result<ArrowRecordBatchIter> {
+        let schema = &self.table.schema;
+        let partition_keys = schema.partition_keys();
+        let fields: Vec<DataField> = schema
+            .fields()
+            .iter()
+            .filter(|field| !partition_keys.iter().any(|key| key == field.name()))
+            .cloned()
+            .collect();
+        let bytes = self.table.file_io.new_input(&split.path)?.read().await?;
+        let batches = plain_format_reader(
+            &self.table.format(),
+            &CoreOptions::new(schema.options()),
+        )?
+        .read(bytes, &fields, self.batch_size)?;
+
+        let arrow_schema = Arc::new(to_arrow_schema(schema.fields()));
+        let partition: HashMap<String, Option<Datum>> = partition_keys
+            .iter()
+            .cloned()
+            .zip(split.partition.iter().cloned())
+            .collect();
+        Ok(Box::new(batches.map(move |batch| {
+            let batch = batch?;
+            let columns = arrow_schema
+                .fields()
+                .iter()
+                .map(|field| {
+                    let column = match partition.get(field.name()) {
+                        Some(datum) => to_arrow_array(datum.as_ref(), batch.num_rows())?,
+                        None => match batch.column_by_name(field.name()) {
+                            Some(column) => column.clone(),
+                            None => new_null_array(field.data_type(), batch.num_rows()),
+                        },
+                    };
+                    Ok(if column.data_type() == field.data_type() {
+                        column
+                    } else {
+                        arrow_cast::cast(&column, field.data_type())?
+                    })
+                })
+                .collect::<crate::Result<Vec<ArrayRef>>>()?;
+            Ok(RecordBatch::try_new(arrow_schema.clone(), columns)?)
+        })))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::{Catalog, FileSystemCatalog};
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataField, Schema};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Date32Type, Int32Type, Int64Type};
+    use arrow_array::{Array, Int32Array};
+    use bytes::Bytes;
+    use parquet::arrow::ArrowWriter;
+
+    async fn write_parquet(file_io: &dyn FileIO, path: &str, batch: &RecordBatch) {
+        let mut bytes = Vec::new();
+        let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), None).unwrap();
+        writer.write(batch).unwrap();
+        writer.close().unwrap();
+        file_io
+            .new_output(path)
+            .unwrap()
+            .write(Bytes::from(bytes))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_format_table() {
+        let warehouse = "file:/tmp/test_format_table";
+        let file_io = FileIOBuilder::from_url(warehouse).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{warehouse}/")).await;
+        let catalog = FileSystemCatalog::new(file_io.clone(), warehouse);
+        catalog
+            .create_database("db", false, HashMap::new())
+            .await
+            .unwrap();
+        let identifier = Identifier::new("db", "t");
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+                DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                DataField::new(2, "dt".to_string(), "DATE".parse().unwrap()),
+            ])
+            .partition_keys(vec!["dt".to_string()])
+            .options(HashMap::from([
+                ("type".to_string(), FORMAT_TABLE_TYPE.to_string()),
+                ("file.format".to_string(), "parquet".to_string()),
+            ]))
+            .build();
+        catalog
+            .create_table(&identifier, schema, false)
+            .await
+            .unwrap();
+        let table = catalog.get_format_table(&identifier).await.unwrap();
+        assert!(catalog
+            .get_format_table(&Identifier::new("db", "missing"))
+            .await
+            .is_err());
+
+        // the files are written by other engines, the name is missing in the first file
+        let ids = RecordBatch::try_from_iter([(
+            "id",
+            Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+        )])
+        .unwrap();
+        write_parquet(
+            file_io.as_ref(),
+            &format!("{}/dt=2024-01-01/part-0.parquet", table.location()),
+            &ids,
+        )
+        .await;
+        let rows = RecordBatch::try_from_iter([
+            ("id", Arc::new(Int32Array::from(vec![3])) as ArrayRef),
+            ("name", Arc::new(StringArray::from(vec!["c"])) as ArrayRef),
+        ])
+        .unwrap();
+        write_parquet(
+            file_io.as_ref(),
+            &format!("{}/dt=2024-01-02/part-0.parquet", table.location()),
+            &rows,
+        )
+        .await;
+        file_io
+            .new_output(&format!("{}/dt=2024-01-02/_SUCCESS", table.location()))
+            .unwrap()
+            .write(Bytes::new())
+            .await
+            .unwrap();
+
+        let splits = table.new_scan().plan().await.unwrap();
+        assert_eq!(splits.len(), 2);
+        assert_eq!(splits[0].partition(), &[Some(Datum::Date(19723))]);
+        let read = table.new_read();
+        let mut rows = Vec::new();
+        for split in &splits {
+            for batch in read.read(split).await.unwrap() {
+                let batch = batch.unwrap();
+                let ids = batch.column(0).as_primitive::<Int32Type>();
+                let names = batch.column(1).as_string::<i32>();
+                let dts = batch.column(2).as_primitive::<Date32Type>();
+                rows.extend((0..batch.num_rows()).map(|i| {
+                    (
+                        ids.value(i),
+                        names.is_valid(i).then(|| names.value(i).to_string()),
+                        dts.value(i),
+                    )
+                }));
+            }
+        }
+        assert_eq!(
+            rows,
+            vec![
+                (1, None, 19723),
+                (2, None, 19723),
+                (3, Some("c".to_string()), 19724)
+            ]
+        );
+
+        let splits = table
+            .new_scan()
+            .with_partition_filter(HashMap::from([("dt".to_string(), Datum::Date(19724))]))
+            .plan()
+            .await
+            .unwrap();
+        assert_eq!(splits.len(), 1);
+        assert!(splits[0].path().ends_with("dt=2024-01-02/part-0.parquet"));
+    }
+
+    #[tokio::test]
+    async fn test_infer_format_table() {
+        let location = "file:/tmp/test_infer_format_table";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let write = |path: String, content: &'static str| {
+            let file_io = file_io.clone();
+            async move {
+                file_io
+                    .new_output(&path)
+                    .unwrap()
+                    .write(Bytes::from(content))
+                    .await
+                    .unwrap();
+            }
+        };
+        write(
+            format!("{location}/csv/dt=2024-01-01/part-0.csv"),
+            "id,name\n1,a\n2,\n",
+        )
+        .await;
+        write(
+            format!("{location}/json/part-0.json"),
+            "{\"id\": 1, \"score\": 1.5}\n{\"id\": 2, \"tags\": [\"x\"]}\n",
+        )
+        .await;
+
+        let schema = FormatTable::infer_schema(
+            file_io.as_ref(),
+            &format!("{location}/csv"),
+            HashMap::from([
+                ("file.format".to_string(), "csv".to_string()),
+                ("csv.include-header".to_string(), "true".to_string()),
+            ]),
+        )
+        .await
+        .unwrap();
+        let types: Vec<(&str, String)> = schema
+            .fields()
+            .iter()
+            .map(|field| (field.name(), field.data_type().to_string()))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("id", "BIGINT".to_string()),
+                ("name", "STRING".to_string()),
+                ("dt", "STRING".to_string())
+            ]
+        );
+        assert_eq!(schema.partition_keys(), &["dt".to_string()]);
+        let table = FormatTable::new(
+            file_io.clone(),
+            Identifier::new("db", "csv"),
+            format!("{location}/csv"),
+            schema.to_table_schema(0).unwrap(),
+        )
+        .unwrap();
+        let splits = table.new_scan().plan().await.unwrap();
+        let batch = table.new_read().read(&splits[0]).await.unwrap().next();
+        let batch = batch.unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert_eq!(batch.column(0).as_primitive::<Int64Type>().value(1), 2);
+        assert!(batch.column(1).is_null(1));
+        assert_eq!(batch.column(2).as_string::<i32>().value(0), "2024-01-01");
+
+        let schema = FormatTable::infer_schema(
+            file_io.as_ref(),
+            &format!("{location}/json"),
+            HashMap::from([("file.format".to_string(), "json".to_string())]),
+        )
+        .await
+        .unwrap();
+        let names: Vec<&str> = schema.fields().iter().map(|field| field.name()).collect();
+        assert_eq!(names, vec!["id", "score", "tags"]);
+        let table = FormatTable::new(
+            file_io.clone(),
+            Identifier::new("db", "json"),
+            format!("{location}/json"),
+            schema.to_table_schema(0).unwrap(),
+        )
+        .unwrap();
+        let splits = table.new_scan().plan().await.unwrap();
+        let batch = table.new_read().read(&splits[0]).await.unwrap().next();
+        let batch = batch.unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 2);
+        assert!(batch.column(1).is_null(1));
+        assert_eq!(batch.column(2).as_list::<i32>().value(1).len(), 1);
+    }
+}
@@ -0,0 +1,369 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::SchemaInvalidSnafu;
+use crate::format::{to_arrow_schema, to_arrow_type, to_binary_row};
+use crate::spec::{
+    BinaryRowWriter, DataType, Datum, RowKind, CONSUMER_ID, SCAN_MODE, SCAN_SNAPSHOT_ID,
+    SCAN_TAG_NAME, SCAN_TIMESTAMP_MILLIS,
+};
+use crate::table::{FileStoreTable, Plan, StreamTableScan, Table};
+use arrow_array::{Array, RecordBatch};
+use arrow_row::{RowConverter, SortField};
+use arrow_schema::SchemaRef;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use std::path::Path;
+use std::sync::Arc;
+
+/// Key of the id of the next snapshot to apply.
+const CHECKPOINT_KEY: &str = "checkpoint";
+/// Key of the id of the schema the rows are encoded by.
+const SCHEMA_ID_KEY: &str = "schema-id";
+
+/// A local cache of the merged rows of primary key tables by their primary keys, kept in an
+/// embedded SQLite database and refreshed incrementally from the snapshots.
+///
+/// The cache starts up with all the rows of the latest snapshot, then applies the changes of
+/// the following snapshots planned by a [`StreamTableScan`]: inserts and updates after put the
+/// rows while deletes and updates before remove them. The changes of each snapshot are written
+/// atomically with the id of the next snapshot to apply, so that a cache reopened at the same
+/// path continues from there, unless the schema of the table is changed.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-flink/paimon-flink-common/src/main/java/org/apache/paimon/flink/lookup/FileStoreLookupFunction.java>
+#[derive(Debug)]
+pub struct LookupCache {
+    table: FileStoreTable,
+    db: SqlitePool,
+    scan: StreamTableScan,
+    schema: SchemaRef,
+    converter: RowConverter,
+    key_indices: Vec<usize>,
+    key_types: Vec<DataType>,
+}
+
+impl LookupCache {
+    /// Open the cache of the primary key table in the database file at the local path,
+    /// creating it if absent.
+    pub async fn open(table: FileStoreTable, path: impl AsRef<Path>) -> crate::Result<Self> {
+        let table_schema = table.schema();
+        if table_schema.primary_keys().is_empty() {
+            return SchemaInvalidSnafu {
+                message: format!("Table {} has no primary keys to look up", table.location()),
+            }
+            .fail();
+        }
+        let fields = table_schema.fields();
+        let (key_indices, key_types) = table_schema
+            .primary_keys()
+            .iter()
+            .map(|key| {
+                let index = fields
+                    .iter()
+                    .position(|f| f.name() == key)
+                    .expect("keys are validated by the schema");
+                (index, fields[index].data_type().clone())
+            })
+            .unzip();
+        let converter = RowConverter::new(
+            fields
+                .iter()
+                .map(|f| SortField::new(to_arrow_type(f.data_type())))
+                .collect(),
+        )?;
+
+        // a single connection serializes the batches of the changes applied
+        let db = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(
+                SqliteConnectOptions::new()
+                    .filename(path)
+                    .create_if_missing(true),
+            )
+            .await?;
+        sqlx::query("CREATE TABLE IF NOT EXISTS meta (k TEXT PRIMARY KEY, v INTEGER NOT NULL)")
+            .execute(&db)
+            .await?;
+        sqlx::query("CREATE TABLE IF NOT EXISTS rows (k BLOB PRIMARY KEY, v BLOB NOT NULL)")
+            .execute(&db)
+            .await?;
+        let schema_id = table_schema.id();
+        if get_meta(&db, SCHEMA_ID_KEY).await? != Some(schema_id) {
+            let mut tx = db.begin().await?;
+            sqlx::query("DELETE FROM rows").execute(&mut *tx).await?;
+            sqlx::query("DELETE FROM meta").execute(&mut *tx).await?;
+            put_meta(&mut tx, SCHEMA_ID_KEY, schema_id).await?;
+            tx.commit().await?;
+        }
+        // the cache always starts up with the full data of the latest snapshot
+        let mut options = table_schema.options().clone();
+        for key in [
+            CONSUMER_ID,
+            SCAN_SNAPSHOT_ID,
+            SCAN_TAG_NAME,
+            SCAN_TIMESTAMP_MILLIS,
+        ] {
+            options.remove(key);
+        }
+        options.insert(SCAN_MODE.to_string(), "latest-full".to_string());
+        let table = FileStoreTable::new(
+            table.file_io().clone(),
+            table.identifier().clone(),
+            table.location(),
+            table_schema.copy_with_options(options),
+        );
+        let mut scan = table.new_stream_scan();
+        if let Some(checkpoint) = get_meta(&db, CHECKPOINT_KEY).await? {
+            scan.restore(checkpoint);
+        }
+        Ok(Self {
+            schema: Arc::new(to_arrow_schema(fields)),
+            table,
+            db,
+            scan,
+            converter,
+            key_indices,
+            key_types,
+        })
+    }
+
+    /// Get the id of the next snapshot to apply, none if the cache hasn't started up yet.
+    pub fn checkpoint(&self) -> Option<i64> {
+        self.scan.checkpoint()
+    }
+
+    /// Apply the changes of the snapshots committed since the last refresh, until the latest.
+    pub async fn refresh(&mut self) -> crate::Result<()> {
+        let mut started = self.scan.checkpoint().is_some();
+        loop {
+            let plan = self.scan.plan().await?;
+            let caught_up = started && plan.snapshot_id().is_none();
+            self.apply(&plan).await?;
+            if caught_up {
+                return Ok(());
+            }
+            started = true;
+        }
+    }
+
+    /// Get the cached row of the key, which is the values of the primary keys in their order,
+    /// none if the key doesn't exist.
+    pub async fn get(&self, key: &[Datum]) -> crate::Result<Option<RecordBatch>> {
+        if key.len() != self.key_types.len() {
+            return SchemaInvalidSnafu {
+                message: format!(
+                    "Key of {} values doesn't match the primary keys {:?}",
+                    key.len(),
+                    self.table.schema().primary_keys()
+                ),
+            }
+            .fail();
+        }
+        let mut writer = BinaryRowWriter::new(key.len() as i32);
+        for (pos, (datum, data_type)) in key.iter().zip(&self.key_types).enumerate() {
+            writer.write_datum(pos, Some(datum), data_type)?;
+        }
+        let value: Option<Vec<u8>> = sqlx::query_scalar("SELECT v FROM rows WHERE k = ?")
+            .bind(writer.build().to_serialized_bytes())
+            .fetch_optional(&self.db)
+            .await?;
+        let Some(value) = value else {
+            return Ok(None);
+        };
+        let parser = self.converter.parser();
+        let columns = self.converter.convert_rows([parser.parse(&value)])?;
+        Ok(Some(RecordBatch::try_new(self.schema.clone(), columns)?))
+    }
+
+    /// Write the changes of the plan with the checkpoint after it atomically.
+    async fn apply(&self, plan: &Plan) -> crate::Result<()> {
+        let mut tx = self.db.begin().await?;
+        let read = self.table.new_read();
+        let key_types: Vec<&DataType> = self.key_types.iter().collect();
+        for split in plan.splits() {
+            for records in read.read_with_row_kinds(split).await? {
+                let (records, row_kinds) = records?;
+                let rows = self.converter.convert_columns(records.columns())?;
+                let key_columns: Vec<&dyn Array> = self
+                    .key_indices
+                    .iter()
+                    .map(|index| records.column(*index).as_ref())
+                    .collect();
+                for (row, row_kind) in row_kinds.iter().enumerate() {
+                    let key = to_binary_row(&key_columns, &key_types, row)?;
+                    let key = key.to_serialized_bytes();
+                    match row_kind {
+                        RowKind::Insert | RowKind::UpdateAfter => {
+                            sqlx::query("INSERT OR REPLACE INTO rows (k, v) VALUES (?, ?)")
+                                .bind(key)
+                                .bind(rows.row(row).as_ref())
+                                .execute(&mut *tx)
+                                .await?;
+                        }
+                        RowKind::UpdateBefore | RowKind::Delete => {
+                            sqlx::query("DELETE FROM rows WHERE k = ?")
+                                .bind(key)
+                                .execute(&mut *tx)
+                                .await?;
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(checkpoint) = self.scan.checkpoint() {
+            put_meta(&mut tx, CHECKPOINT_KEY, checkpoint).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+async fn get_meta(db: &SqlitePool, key: &str) -> crate::Result<Option<i64>> {
+    Ok(sqlx::query_scalar("SELECT v FROM meta WHERE k = ?")
+        .bind(key)
+        .fetch_optional(db)
+        .await?)
+}
+
+async fn put_meta(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    key: &str,
+    value: i64,
+) -> crate::Result<()> {
+    sqlx::query("INSERT OR REPLACE INTO meta (k, v) VALUES (?, ?)")
+        .bind(key)
+        .bind(value)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataField, Schema};
+    use crate::utils::SchemaManager;
+    use arrow_array::cast::AsArray;
+    use arrow_array::{Int32Array, StringArray};
+
+    async fn write(table: &FileStoreTable, rows: &[(RowKind, i32, &str)]) {
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.1))),
+                Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.2))),
+            ],
+        )
+        .unwrap();
+        let row_kinds: Vec<RowKind> = rows.iter().map(|r| r.0).collect();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write
+            .write_with_row_kinds(&batch, &row_kinds)
+            .await
+            .unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+    }
+
+    async fn name(cache: &LookupCache, id: i32) -> Option<String> {
+        cache.get(&[Datum::Int(id)]).await.unwrap().map(|batch| {
+            assert_eq!(
+                batch
+                    .column(0)
+                    .as_primitive::<arrow_array::types::Int32Type>()
+                    .value(0),
+                id
+            );
+            batch.column(1).as_string::<i32>().value(0).to_string()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_lookup_cache() {
+        let location = "file:/tmp/test_lookup_cache_table";
+        let cache_path = "/tmp/test_lookup_cache.db";
+        let _ = std::fs::remove_file(cache_path);
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                        DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                    ])
+                    .primary_keys(vec!["id".to_string()])
+                    .options(
+                        [
+                            ("bucket".to_string(), "1".to_string()),
+                            ("changelog-producer".to_string(), "input".to_string()),
+                            ("file.format".to_string(), "parquet".to_string()),
+                        ]
+                        .into(),
+                    )
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema);
+
+        write(
+            &table,
+            &[(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")],
+        )
+        .await;
+        write(&table, &[(RowKind::Insert, 1, "a2")]).await;
+        let mut cache = LookupCache::open(table.clone(), cache_path).await.unwrap();
+        assert_eq!(cache.checkpoint(), None);
+        // starts up with the merged rows of the latest snapshot
+        cache.refresh().await.unwrap();
+        assert_eq!(cache.checkpoint(), Some(3));
+        assert_eq!(name(&cache, 1).await.as_deref(), Some("a2"));
+        assert_eq!(name(&cache, 2).await.as_deref(), Some("b"));
+        assert_eq!(name(&cache, 3).await, None);
+
+        write(
+            &table,
+            &[
+                (RowKind::UpdateBefore, 1, "a2"),
+                (RowKind::UpdateAfter, 1, "a3"),
+                (RowKind::Delete, 2, "b"),
+                (RowKind::Insert, 3, "c"),
+            ],
+        )
+        .await;
+        cache.refresh().await.unwrap();
+        assert_eq!(cache.checkpoint(), Some(4));
+        assert_eq!(name(&cache, 1).await.as_deref(), Some("a3"));
+        assert_eq!(name(&cache, 2).await, None);
+        assert_eq!(name(&cache, 3).await.as_deref(), Some("c"));
+        drop(cache);
+
+        // the reopened cache continues from its checkpoint
+        write(&table, &[(RowKind::Delete, 3, "c")]).await;
+        let mut cache = LookupCache::open(table.clone(), cache_path).await.unwrap();
+        assert_eq!(cache.checkpoint(), Some(4));
+        assert_eq!(name(&cache, 3).await.as_deref(), Some("c"));
+        cache.refresh().await.unwrap();
+        assert_eq!(cache.checkpoint(), Some(5));
+        assert_eq!(name(&cache, 3).await, None);
+        assert_eq!(name(&cache, 1).await.as_deref(), Some("a3"));
+        assert!(cache.get(&[]).await.is_err());
+    }
+}
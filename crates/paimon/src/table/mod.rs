@@ -0,0 +1,634 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Table API for paimon.
+
+mod append_only_writer;
+
+mod commit_message;
+pub use commit_message::*;
+
+mod expire_partitions;
+pub use expire_partitions::*;
+
+mod expire_snapshots;
+pub use expire_snapshots::*;
+
+mod file_deletion;
+
+mod format_table;
+pub use format_table::*;
+
+#[cfg(feature = "lookup-cache")]
+mod lookup_cache;
+#[cfg(feature = "lookup-cache")]
+pub use lookup_cache::*;
+
+mod object_table;
+pub use object_table::*;
+
+mod parallel_reader;
+pub use parallel_reader::*;
+
+mod remove_orphan_files;
+pub use remove_orphan_files::*;
+
+mod row_key_extractor;
+
+mod snapshot_validator;
+pub use snapshot_validator::*;
+
+mod source;
+pub use source::*;
+
+mod split_generator;
+
+mod system;
+pub use system::*;
+
+mod stream_table_scan;
+pub use stream_table_scan::*;
+
+mod table_analyze;
+pub use table_analyze::*;
+
+mod table_commit;
+pub use table_commit::*;
+
+mod table_compact;
+pub use table_compact::*;
+
+mod table_delete;
+pub use table_delete::*;
+
+mod table_lookup;
+pub use table_lookup::*;
+
+mod table_merge_into;
+pub use table_merge_into::*;
+
+mod table_read;
+pub use table_read::*;
+
+mod table_scan;
+pub use table_scan::*;
+
+mod table_update;
+pub use table_update::*;
+
+mod table_write;
+pub use table_write::*;
+
+mod write_builder;
+pub use write_builder::*;
+
+use crate::catalog::{CatalogLock, Identifier};
+use crate::deletion_vectors::DELETION_VECTORS_INDEX;
+use crate::error::BranchNotExistSnafu;
+use crate::io::FileIO;
+use crate::manifest::{IndexManifestFile, ManifestCache, ManifestFile, ManifestFileWriter};
+use crate::metrics::{MetricsReporter, TableMetrics};
+use crate::spec::{
+    CoreOptions, Datum, FileKind, IndexFileMeta, IndexManifestEntry, RowType, Snapshot, Statistics,
+    TableSchema,
+};
+use crate::utils::{
+    BranchManager, ConsumerManager, FileStorePathFactory, SchemaManager, SnapshotManager,
+    StatsFileHandler, TagManager,
+};
+use arrow_array::RecordBatch;
+use async_trait::async_trait;
+use file_deletion::FileDeletion;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A table of paimon, the entry of scanning, reading and writing data.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/InnerTable.java>
+#[async_trait]
+pub trait Table: Send + Sync {
+    /// Get the identifier of this table.
+    fn identifier(&self) -> &Identifier;
+
+    /// Get the row type of this table.
+    fn row_type(&self) -> RowType;
+
+    /// Get the partition keys of this table.
+    fn partition_keys(&self) -> &[String];
+
+    /// Get the primary keys of this table.
+    fn primary_keys(&self) -> &[String];
+
+    /// Get the options of this table.
+    fn options(&self) -> &HashMap<String, String>;
+
+    /// Create a scan to plan the splits of this table.
+    fn new_scan(&self) -> TableScan;
+
+    /// Create a streaming scan to plan the splits of the snapshots committed to this table.
+    fn new_stream_scan(&self) -> StreamTableScan;
+
+    /// Create a read to read the splits of this table.
+    fn new_read(&self) -> TableRead;
+
+    /// Create a write to write data into this table by the given commit user.
+    fn new_write(&self, commit_user: &str) -> TableWrite;
+
+    /// Create a builder of the write and the commit of a batch job.
+    fn new_batch_write_builder(&self) -> BatchWriteBuilder;
+
+    /// Create an expiration of the snapshots of this table.
+    fn new_expire_snapshots(&self) -> ExpireSnapshots;
+
+    /// Create an expiration of the partitions of this table by a random commit user.
+    fn new_expire_partitions(&self) -> ExpirePartitions;
+
+    /// Create a full compaction of this table by a random commit user.
+    fn new_compact(&self) -> TableCompact;
+
+    /// Create an analysis of the statistics of this table by a random commit user.
+    fn new_analyze(&self) -> TableAnalyze;
+
+    /// Create a deletion of the rows of this table by a random commit user.
+    fn new_delete(&self) -> TableDelete;
+
+    /// Create an update of the rows of this table by a random commit user.
+    fn new_update(&self) -> TableUpdate;
+
+    /// Create a merge of source rows into this table by a random commit user.
+    fn new_merge_into(&self) -> TableMergeInto;
+
+    /// Create a removal of the orphan files of this table.
+    fn new_remove_orphan_files(&self) -> RemoveOrphanFiles;
+
+    /// List the partitions of this table with the stats of their data files.
+    async fn list_partitions(&self) -> crate::Result<Vec<PartitionEntry>>;
+
+    /// Look up the merged row of the key of this primary key table, which is the values of
+    /// the primary keys in their order, none if the key doesn't exist.
+    async fn lookup(&self, key: &[Datum]) -> crate::Result<Option<RecordBatch>>;
+
+    /// Get the table level statistics of the latest snapshot, none if there is no snapshot or
+    /// the statistics are not computed.
+    async fn statistics(&self) -> crate::Result<Option<Statistics>>;
+}
+
+/// A table of paimon stored in the file system.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/FileStoreTable.java>
+#[derive(Debug, Clone)]
+pub struct FileStoreTable {
+    file_io: Arc<dyn FileIO>,
+    identifier: Identifier,
+    location: String,
+    schema: TableSchema,
+    manifest_cache: Option<Arc<ManifestCache>>,
+    catalog_lock: Option<Arc<dyn CatalogLock>>,
+    metrics_reporter: Option<Arc<dyn MetricsReporter>>,
+}
+
+impl FileStoreTable {
+    pub fn new(
+        file_io: Arc<dyn FileIO>,
+        identifier: Identifier,
+        location: impl Into<String>,
+        schema: TableSchema,
+    ) -> Self {
+        Self {
+            file_io,
+            identifier,
+            location: location.into().trim_end_matches('/').to_string(),
+            schema,
+            manifest_cache: None,
+            catalog_lock: None,
+            metrics_reporter: None,
+        }
+    }
+
+    /// Read the manifest files of this table through the cache.
+    pub fn with_manifest_cache(mut self, cache: Arc<ManifestCache>) -> Self {
+        self.manifest_cache = Some(cache);
+        self
+    }
+
+    /// Create the snapshot files of commits while holding the lock of this table.
+    pub fn with_catalog_lock(mut self, lock: Arc<dyn CatalogLock>) -> Self {
+        self.catalog_lock = Some(lock);
+        self
+    }
+
+    /// Get the catalog lock held by commits of this table, if any.
+    pub fn catalog_lock(&self) -> Option<&Arc<dyn CatalogLock>> {
+        self.catalog_lock.as_ref()
+    }
+
+    /// Report the metrics of the scans, reads and commits of this table to the reporter.
+    pub fn with_metrics_reporter(mut self, reporter: Arc<dyn MetricsReporter>) -> Self {
+        self.metrics_reporter = Some(reporter);
+        self
+    }
+
+    /// Get the metrics of this table, labeled by its full name.
+    pub(crate) fn metrics(&self) -> TableMetrics {
+        TableMetrics::new(self.metrics_reporter.clone(), self.identifier.full_name())
+    }
+
+    /// Get the reader and writer of the manifest files of this table.
+    pub fn manifest_file(&self) -> ManifestFile {
+        ManifestFile::new(self.file_io.clone()).with_cache(self.manifest_cache.clone())
+    }
+
+    /// Get the writer of new manifest files of this table, rolling by
+    /// `manifest.target-file-size`.
+    pub fn manifest_file_writer(&self) -> ManifestFileWriter {
+        ManifestFileWriter::new(
+            self.manifest_file(),
+            self.path_factory().manifest_dir(),
+            self.schema.logical_partition_type(),
+            self.schema.id(),
+            self.core_options().manifest_target_file_size(),
+        )
+    }
+
+    /// Get the file io of this table.
+    #[inline]
+    pub fn file_io(&self) -> &Arc<dyn FileIO> {
+        &self.file_io
+    }
+
+    /// Get the location of this table.
+    #[inline]
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Get the latest schema of this table.
+    #[inline]
+    pub fn schema(&self) -> &TableSchema {
+        &self.schema
+    }
+
+    /// Get the typed options of this table.
+    pub fn core_options(&self) -> CoreOptions<'_> {
+        CoreOptions::new(self.schema.options())
+    }
+
+    /// Get the manager of snapshots of this table.
+    pub fn snapshot_manager(&self) -> SnapshotManager {
+        SnapshotManager::new(self.file_io.clone(), self.location.clone())
+            .with_branch(self.core_options().branch())
+    }
+
+    /// Get the manager of tags of this table.
+    pub fn tag_manager(&self) -> TagManager {
+        TagManager::new(self.file_io.clone(), self.location.clone())
+            .with_branch(self.core_options().branch())
+    }
+
+    /// Tag the snapshot of the given id by the given name.
+    pub async fn create_tag(&self, tag_name: &str, snapshot_id: i64) -> crate::Result<()> {
+        let snapshot = self.snapshot_manager().snapshot(snapshot_id).await?;
+        self.tag_manager().create_tag(&snapshot, tag_name).await
+    }
+
+    /// Delete the tag of the given name.
+    ///
+    /// If the snapshot tagged is already expired and not tagged by other names, the files only
+    /// used by it are deleted, the ones used by the adjacent tags or the earliest snapshot are
+    /// retained.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/utils/TagManager.java>
+    pub async fn delete_tag(&self, tag_name: &str) -> crate::Result<()> {
+        let tag_manager = self.tag_manager();
+        let snapshot_manager = self.snapshot_manager();
+        let tagged = tag_manager.tagged_snapshot(tag_name).await?;
+        if snapshot_manager.snapshot_exists(tagged.id()).await? {
+            return tag_manager.delete_tag(tag_name).await;
+        }
+        let tags = tag_manager.tags().await?;
+        tag_manager.delete_tag(tag_name).await?;
+        if tags
+            .iter()
+            .any(|(name, snapshot)| name != tag_name && snapshot.id() == tagged.id())
+        {
+            return Ok(());
+        }
+
+        let previous = tags
+            .iter()
+            .rev()
+            .map(|(_, snapshot)| snapshot)
+            .find(|snapshot| snapshot.id() < tagged.id());
+        let next = tags
+            .iter()
+            .map(|(_, snapshot)| snapshot)
+            .find(|snapshot| snapshot.id() > tagged.id());
+        let earliest = snapshot_manager.earliest_snapshot().await?;
+        let next = match (next, earliest.as_ref()) {
+            (Some(next), Some(earliest)) if earliest.id() < next.id() => Some(earliest),
+            (next, earliest) => next.or(earliest),
+        };
+        let skipped: Vec<&Snapshot> = previous.into_iter().chain(next).collect();
+        FileDeletion::new(self.clone())
+            .delete_unused_files(&tagged, &skipped)
+            .await
+    }
+
+    /// Get the manager of branches of this table.
+    pub fn branch_manager(&self) -> BranchManager {
+        BranchManager::new(self.file_io.clone(), self.location.clone())
+    }
+
+    /// Get a copy of this table on the given branch with the latest schema of the branch.
+    pub async fn switch_to_branch(&self, branch: &str) -> crate::Result<FileStoreTable> {
+        let schema_manager =
+            SchemaManager::new(self.file_io.clone(), self.location.clone()).with_branch(branch);
+        let Some(schema) = schema_manager.latest().await? else {
+            return BranchNotExistSnafu { branch }.fail();
+        };
+        let mut options = schema.options().clone();
+        options.insert("branch".to_string(), branch.to_string());
+        Ok(Self {
+            schema: schema.copy_with_options(options),
+            ..self.clone()
+        })
+    }
+
+    /// Get the manager of consumers of this table.
+    pub fn consumer_manager(&self) -> ConsumerManager {
+        ConsumerManager::new(self.file_io.clone(), self.location.clone())
+    }
+
+    /// Read the entries of the index manifest of the snapshot, which are the index files of
+    /// the hash indexes of dynamic buckets and the deletion vectors, empty if the snapshot has
+    /// no index manifest.
+    pub async fn index_manifest_entries(
+        &self,
+        snapshot: &Snapshot,
+    ) -> crate::Result<Vec<IndexManifestEntry>> {
+        let Some(index_manifest) = snapshot.index_manifest() else {
+            return Ok(vec![]);
+        };
+        IndexManifestFile::new(self.file_io.clone())
+            .read(&self.path_factory().manifest_path(index_manifest))
+            .await
+    }
+
+    /// Get the index files of the deletion vectors in the index manifest of the snapshot, keyed
+    /// by the partitions and buckets.
+    pub(crate) async fn deletion_vectors_index_files(
+        &self,
+        snapshot: &Snapshot,
+    ) -> crate::Result<HashMap<(Vec<u8>, i32), Vec<IndexFileMeta>>> {
+        let mut index_files: HashMap<_, Vec<_>> = HashMap::new();
+        for entry in self.index_manifest_entries(snapshot).await? {
+            if entry.kind == FileKind::Add && entry.index_file.index_type == DELETION_VECTORS_INDEX
+            {
+                index_files
+                    .entry((entry.partition, entry.bucket))
+                    .or_default()
+                    .push(entry.index_file);
+            }
+        }
+        Ok(index_files)
+    }
+
+    /// Get the handler of the statistics files of this table.
+    pub fn stats_file_handler(&self) -> StatsFileHandler {
+        StatsFileHandler::new(self.file_io.clone(), self.location.clone())
+    }
+
+    /// Get the factory of the paths of the files of this table.
+    pub fn path_factory(&self) -> FileStorePathFactory {
+        FileStorePathFactory::new(
+            self.location.clone(),
+            self.schema.logical_partition_type(),
+            self.core_options().partition_default_name(),
+        )
+    }
+
+    /// Get the manager of schemas of this table.
+    pub fn schema_manager(&self) -> SchemaManager {
+        SchemaManager::new(self.file_io.clone(), self.location.clone())
+            .with_branch(self.core_options().branch())
+    }
+}
+
+#[async_trait]
+impl Table for FileStoreTable {
+    fn identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    fn row_type(&self) -> RowType {
+        self.schema.logical_row_type()
+    }
+
+    fn partition_keys(&self) -> &[String] {
+        self.schema.partition_keys()
+    }
+
+    fn primary_keys(&self) -> &[String] {
+        self.schema.primary_keys()
+    }
+
+    fn options(&self) -> &HashMap<String, String> {
+        self.schema.options()
+    }
+
+    fn new_scan(&self) -> TableScan {
+        TableScan::new(self.clone())
+    }
+
+    fn new_stream_scan(&self) -> StreamTableScan {
+        StreamTableScan::new(self.clone())
+    }
+
+    fn new_read(&self) -> TableRead {
+        TableRead::new(self.clone())
+    }
+
+    fn new_write(&self, commit_user: &str) -> TableWrite {
+        TableWrite::new(self.clone(), commit_user)
+    }
+
+    fn new_batch_write_builder(&self) -> BatchWriteBuilder {
+        BatchWriteBuilder::new(self.clone())
+    }
+
+    fn new_expire_snapshots(&self) -> ExpireSnapshots {
+        ExpireSnapshots::new(self.clone())
+    }
+
+    fn new_expire_partitions(&self) -> ExpirePartitions {
+        ExpirePartitions::new(self.clone(), uuid::Uuid::new_v4().to_string())
+    }
+
+    fn new_compact(&self) -> TableCompact {
+        TableCompact::new(self.clone(), uuid::Uuid::new_v4().to_string())
+    }
+
+    fn new_analyze(&self) -> TableAnalyze {
+        TableAnalyze::new(self.clone(), uuid::Uuid::new_v4().to_string())
+    }
+
+    fn new_delete(&self) -> TableDelete {
+        TableDelete::new(self.clone(), uuid::Uuid::new_v4().to_string())
+    }
+
+    fn new_update(&self) -> TableUpdate {
+        TableUpdate::new(self.clone(), uuid::Uuid::new_v4().to_string())
+    }
+
+    fn new_merge_into(&self) -> TableMergeInto {
+        TableMergeInto::new(self.clone(), uuid::Uuid::new_v4().to_string())
+    }
+
+    fn new_remove_orphan_files(&self) -> RemoveOrphanFiles {
+        RemoveOrphanFiles::new(self.clone())
+    }
+
+    async fn list_partitions(&self) -> crate::Result<Vec<PartitionEntry>> {
+        self.new_scan().list_partitions().await
+    }
+
+    async fn lookup(&self, key: &[Datum]) -> crate::Result<Option<RecordBatch>> {
+        TableLookup::new(self.clone()).lookup(key).await
+    }
+
+    async fn statistics(&self) -> crate::Result<Option<Statistics>> {
+        match self.snapshot_manager().latest_snapshot().await? {
+            Some(snapshot) => self.stats_file_handler().read_stats(&snapshot).await,
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{
+        CommitKind, DataField, DataType, FileKind, IndexFileMeta, IntType, Schema, VarCharType,
+    };
+
+    fn test_table(location: &str) -> FileStoreTable {
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "id".to_string(), DataType::Int(IntType::new())),
+                DataField::new(
+                    1,
+                    "name".to_string(),
+                    DataType::VarChar(VarCharType::default()),
+                ),
+            ])
+            .primary_keys(vec!["id".to_string()])
+            .options(HashMap::from([("bucket".to_string(), "2".to_string())]))
+            .build()
+            .to_table_schema(0)
+            .unwrap();
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema)
+    }
+
+    #[test]
+    fn test_file_store_table() {
+        let table = test_table("memory:/db.db/t/");
+        assert_eq!(table.identifier().full_name(), "db.t");
+        assert_eq!(table.location(), "memory:/db.db/t");
+        assert_eq!(table.row_type().field_names(), vec!["id", "name"]);
+        assert_eq!(table.primary_keys(), &["id".to_string()]);
+        assert!(table.partition_keys().is_empty());
+        assert_eq!(table.core_options().bucket(), 2);
+        assert_eq!(
+            table.snapshot_manager().snapshot_dir(),
+            "memory:/db.db/t/snapshot"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_new_scan_read_write() {
+        let table = test_table("memory:/test_new_scan_read_write/t");
+        assert_eq!(table.new_scan().snapshot().await.unwrap(), None);
+        assert_eq!(table.statistics().await.unwrap(), None);
+
+        let read = table.new_read().with_projection(&["name", "id"]);
+        assert_eq!(read.read_type().unwrap().field_names(), vec!["name", "id"]);
+        assert!(matches!(
+            table.new_read().with_projection(&["unknown"]).read_type(),
+            Err(crate::Error::ColumnNotExist { .. })
+        ));
+
+        assert_eq!(table.new_write("user").commit_user(), "user");
+    }
+
+    #[tokio::test]
+    async fn test_index_manifest_entries() {
+        let table = test_table("memory:/test_index_manifest_entries/t");
+        let snapshot = |index_manifest: Option<&str>| {
+            Snapshot::builder()
+                .version(3)
+                .id(1)
+                .schema_id(0)
+                .base_manifest_list("manifest-list-0".to_string())
+                .delta_manifest_list("manifest-list-1".to_string())
+                .commit_user("user".to_string())
+                .commit_identifier(0)
+                .commit_kind(CommitKind::APPEND)
+                .time_millis(0)
+                .index_manifest(index_manifest.map(str::to_string))
+                .build()
+        };
+        assert!(table
+            .index_manifest_entries(&snapshot(None))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let entries = vec![IndexManifestEntry {
+            kind: FileKind::Add,
+            partition: vec![],
+            bucket: 1,
+            index_file: IndexFileMeta {
+                index_type: "HASH".to_string(),
+                file_name: "index-0".to_string(),
+                file_size: 8,
+                row_count: 2,
+                deletion_vectors_ranges: None,
+            },
+            version: 1,
+        }];
+        IndexManifestFile::new(table.file_io().clone())
+            .write(
+                &table.path_factory().manifest_path("index-manifest-0"),
+                &entries,
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            table
+                .index_manifest_entries(&snapshot(Some("index-manifest-0")))
+                .await
+                .unwrap(),
+            entries
+        );
+        assert!(table
+            .index_manifest_entries(&snapshot(Some("index-manifest-1")))
+            .await
+            .is_err());
+    }
+}
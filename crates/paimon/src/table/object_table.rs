@@ -0,0 +1,278 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::format::to_arrow_schema;
+use crate::io::FileStatus;
+use crate::spec::{BigIntType, DataField, DataType, Schema, VarCharType};
+use crate::table::{FileStoreTable, Table, TableRead, TableScan};
+use arrow_array::{Int64Array, RecordBatch, StringArray};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Type of the object tables, the option `type` of the tables.
+pub const OBJECT_TABLE_TYPE: &str = "object-table";
+
+/// A table cataloging the files under the directory of `object-location`, such as images and
+/// models, with a row of the path relative to the directory, the name, the length and the
+/// modification time in milliseconds of each file.
+///
+/// The rows are stored in the wrapped append only table, which are overwritten by the files
+/// listed on each [`refresh`](Self::refresh), and scanned through the wrapped table. The files
+/// are listed by the [`FileIO`](crate::io::FileIO) of the table.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.9/paimon-core/src/main/java/org/apache/paimon/table/object/ObjectTable.java>
+#[derive(Debug, Clone)]
+pub struct ObjectTable {
+    table: FileStoreTable,
+    object_location: String,
+}
+
+impl ObjectTable {
+    /// Fields of the rows of the files.
+    pub fn fields() -> Vec<DataField> {
+        let string = || {
+            DataType::VarChar(VarCharType::with_nullable(false, VarCharType::MAX_LENGTH).unwrap())
+        };
+        let bigint = || DataType::BigInt(BigIntType::with_nullable(false));
+        vec![
+            DataField::new(0, "path".to_string(), string()),
+            DataField::new(1, "name".to_string(), string()),
+            DataField::new(2, "length".to_string(), bigint()),
+            DataField::new(3, "mtime".to_string(), bigint()),
+        ]
+    }
+
+    /// Schema of the object table cataloging the files under the location, which is created
+    /// like the other tables of the catalog.
+    pub fn schema(
+        object_location: impl Into<String>,
+        mut options: HashMap<String, String>,
+    ) -> Schema {
+        options.insert("type".to_string(), OBJECT_TABLE_TYPE.to_string());
+        options.insert("object-location".to_string(), object_location.into());
+        // the table is overwritten as a whole by refreshes, even if no file is listed
+        options.insert(
+            "dynamic-partition-overwrite".to_string(),
+            "false".to_string(),
+        );
+        Schema::builder()
+            .fields(Self::fields())
+            .options(options)
+            .build()
+    }
+
+    /// Wrap the table created by [`schema`](Self::schema).
+    pub fn new(table: FileStoreTable) -> crate::Result<Self> {
+        let options = table.core_options();
+        if options.table_type() != OBJECT_TABLE_TYPE {
+            return ConfigInvalidSnafu {
+                message: format!(
+                    "Table {} is not an object table",
+                    table.identifier().full_name()
+                ),
+            }
+            .fail();
+        }
+        let Some(object_location) = options.object_location() else {
+            return ConfigInvalidSnafu {
+                message: "Object table requires the option object-location",
+            }
+            .fail();
+        };
+        let object_location = object_location.trim_end_matches('/').to_string();
+        let names: Vec<_> = table.schema().fields().iter().map(|f| f.name()).collect();
+        if names != ["path", "name", "length", "mtime"] {
+            return SchemaInvalidSnafu {
+                message: format!("Object table has unexpected fields {names:?}"),
+            }
+            .fail();
+        }
+        Ok(Self {
+            table,
+            object_location,
+        })
+    }
+
+    /// Get the wrapped table storing the rows of the files.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the location of the directory of the files.
+    #[inline]
+    pub fn object_location(&self) -> &str {
+        &self.object_location
+    }
+
+    /// Create a scan to plan the splits of the rows of the files.
+    pub fn new_scan(&self) -> TableScan {
+        self.table.new_scan()
+    }
+
+    /// Create a read to read the splits of the rows of the files.
+    pub fn new_read(&self) -> TableRead {
+        self.table.new_read()
+    }
+
+    /// List the files under the location recursively.
+    pub async fn list_files(&self) -> crate::Result<Vec<FileStatus>> {
+        let file_io = self.table.file_io();
+        let mut files = Vec::new();
+        let mut dirs = vec![self.object_location.clone()];
+        while let Some(dir) = dirs.pop() {
+            for status in file_io.list_status(&dir).await? {
+                if status.is_dir {
+                    dirs.push(status.path);
+                } else {
+                    files.push(status);
+                }
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// Overwrite the rows by the files listed under the location, returns the number of the
+    /// files.
+    pub async fn refresh(&self) -> crate::Result<usize> {
+        let files = self.list_files().await?;
+        let prefix = format!("{}/", self.object_location);
+        let paths: Vec<_> = files
+            .iter()
+            .map(|file| file.path.strip_prefix(&prefix).unwrap_or(&file.path))
+            .collect();
+        let names = paths
+            .iter()
+            .map(|path| path.rsplit('/').next().unwrap_or(path));
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(self.table.schema().fields())),
+            vec![
+                Arc::new(StringArray::from_iter_values(paths.iter())),
+                Arc::new(StringArray::from_iter_values(names)),
+                Arc::new(Int64Array::from_iter_values(
+                    files.iter().map(|f| f.size as i64),
+                )),
+                Arc::new(Int64Array::from_iter_values(files.iter().map(|f| {
+                    f.last_modified.map_or(0, |time| time.timestamp_millis())
+                }))),
+            ],
+        )?;
+
+        let builder = self
+            .table
+            .new_batch_write_builder()
+            .with_overwrite(HashMap::new());
+        let mut write = builder.new_write();
+        if batch.num_rows() > 0 {
+            write.write(&batch).await?;
+        }
+        let messages = write.prepare_commit().await?;
+        builder.new_commit().commit(messages).await?;
+        Ok(files.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::{Catalog, FileSystemCatalog, Identifier};
+    use crate::io::FileIOBuilder;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int64Type;
+    use bytes::Bytes;
+
+    async fn read_rows(table: &ObjectTable) -> Vec<(String, String, i64)> {
+        let plan = table.new_scan().plan().await.unwrap();
+        let read = table.new_read();
+        let mut rows = Vec::new();
+        for split in plan.splits() {
+            for batch in read.read(split).await.unwrap() {
+                let batch = batch.unwrap();
+                let paths = batch.column(0).as_string::<i32>();
+                let names = batch.column(1).as_string::<i32>();
+                let lengths = batch.column(2).as_primitive::<Int64Type>();
+                rows.extend((0..batch.num_rows()).map(|i| {
+                    (
+                        paths.value(i).to_string(),
+                        names.value(i).to_string(),
+                        lengths.value(i),
+                    )
+                }));
+            }
+        }
+        rows.sort();
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_object_table() {
+        let warehouse = "file:/tmp/test_object_table";
+        let objects = "file:/tmp/test_object_table_objects";
+        let file_io = FileIOBuilder::from_url(warehouse).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{warehouse}/")).await;
+        let _ = file_io.delete_dir(&format!("{objects}/")).await;
+        for (path, content) in [("a.png", "aa"), ("models/b.bin", "bbb")] {
+            file_io
+                .new_output(&format!("{objects}/{path}"))
+                .unwrap()
+                .write(Bytes::from(content))
+                .await
+                .unwrap();
+        }
+        let catalog = FileSystemCatalog::new(file_io.clone(), warehouse);
+        catalog
+            .create_database("db", false, HashMap::new())
+            .await
+            .unwrap();
+        let identifier = Identifier::new("db", "objects");
+        let options = HashMap::from([("file.format".to_string(), "parquet".to_string())]);
+        catalog
+            .create_table(&identifier, ObjectTable::schema(objects, options), false)
+            .await
+            .unwrap();
+        let table = ObjectTable::new(catalog.get_table(&identifier).await.unwrap()).unwrap();
+
+        assert_eq!(table.refresh().await.unwrap(), 2);
+        assert_eq!(
+            read_rows(&table).await,
+            vec![
+                ("a.png".to_string(), "a.png".to_string(), 2),
+                ("models/b.bin".to_string(), "b.bin".to_string(), 3),
+            ]
+        );
+
+        // the rows of the deleted files are removed by refreshing
+        file_io
+            .delete_file(&format!("{objects}/a.png"))
+            .await
+            .unwrap();
+        assert_eq!(table.refresh().await.unwrap(), 1);
+        assert_eq!(
+            read_rows(&table).await,
+            vec![("models/b.bin".to_string(), "b.bin".to_string(), 3)]
+        );
+        let plain = FileStoreTable::new(
+            file_io,
+            identifier,
+            table.table().location(),
+            table.table().schema().copy_with_options(HashMap::new()),
+        );
+        assert!(ObjectTable::new(plain).is_err());
+    }
+}
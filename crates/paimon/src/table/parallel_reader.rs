@@ -0,0 +1,255 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::ReadUnexpectedSnafu;
+use crate::table::{DataSplit, TableRead};
+use arrow_array::RecordBatch;
+use futures::stream::{self, BoxStream};
+use futures::{future, StreamExt};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// Stream of the record batches read from splits.
+pub type ArrowRecordBatchStream = BoxStream<'static, crate::Result<RecordBatch>>;
+
+/// Reader of splits concurrently on the Tokio runtime, each split is read by a task.
+///
+/// Batches of a split are buffered up to the split buffer size, the task pauses reading the
+/// split until they are consumed. The limit of the read applies to the batches of all the
+/// splits, once it's reached the tasks reading the remaining splits are stopped.
+#[derive(Debug, Clone)]
+pub struct ParallelReader {
+    read: TableRead,
+    concurrency: usize,
+    split_buffer_size: usize,
+    ordered: bool,
+}
+
+impl ParallelReader {
+    /// Create a reader reading as many splits as the available parallelism at the same time,
+    /// in the order of the splits.
+    pub fn new(read: TableRead) -> Self {
+        Self {
+            read,
+            concurrency: std::thread::available_parallelism().map_or(1, |n| n.get()),
+            split_buffer_size: 2,
+            ordered: true,
+        }
+    }
+
+    /// Set the max number of splits read at the same time.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the max number of batches buffered for each split.
+    pub fn with_split_buffer_size(mut self, split_buffer_size: usize) -> Self {
+        self.split_buffer_size = split_buffer_size.max(1);
+        self
+    }
+
+    /// Whether to produce the batches in the order of the splits, otherwise batches of different
+    /// splits are interleaved as they are read.
+    pub fn with_ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
+    /// Read the splits, must be called in the context of a Tokio runtime.
+    pub fn read(&self, splits: Vec<DataSplit>) -> ArrowRecordBatchStream {
+        let reader = self.clone();
+        let splits = stream::iter(splits).map(move |split| reader.spawn(split));
+        let batches = if self.ordered {
+            splits
+                .map(future::ready)
+                .buffered(self.concurrency)
+                .flatten()
+                .boxed()
+        } else {
+            splits.flatten_unordered(self.concurrency).boxed()
+        };
+        let Some(limit) = self.read.limit() else {
+            return batches;
+        };
+        batches
+            .scan(limit, |remaining, batch| {
+                if *remaining == 0 {
+                    return future::ready(None);
+                }
+                future::ready(Some(batch.map(|batch| {
+                    let batch = batch.slice(0, batch.num_rows().min(*remaining));
+                    *remaining -= batch.num_rows();
+                    batch
+                })))
+            })
+            .boxed()
+    }
+
+    /// Spawn the task reading the split, the stream of its batches fails if the task panics.
+    fn spawn(&self, split: DataSplit) -> ArrowRecordBatchStream {
+        let (sender, receiver) = mpsc::channel(self.split_buffer_size);
+        let read = self.read.clone();
+        let handle = tokio::spawn(async move {
+            let batches = match read.read(&split).await {
+                Ok(batches) => batches,
+                Err(e) => {
+                    let _ = sender.send(Err(e)).await;
+                    return;
+                }
+            };
+            for batch in batches {
+                // the stream is dropped
+                if sender.send(batch).await.is_err() {
+                    return;
+                }
+            }
+        });
+        stream::unfold(
+            (receiver, Some(handle)),
+            |(mut receiver, handle): (_, Option<JoinHandle<()>>)| async move {
+                if let Some(batch) = receiver.recv().await {
+                    return Some((batch, (receiver, handle)));
+                }
+                match handle?.await {
+                    Ok(()) => None,
+                    Err(e) => Some((
+                        ReadUnexpectedSnafu {
+                            message: format!("task reading split failed: {e}"),
+                        }
+                        .fail(),
+                        (receiver, None),
+                    )),
+                }
+            },
+        )
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::to_arrow_schema;
+    use crate::spec::{DataField, Schema};
+    use crate::table::Table;
+    use crate::test_utils::{create_table, write_and_commit};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+    use arrow_array::Int32Array;
+    use std::sync::Arc;
+
+    fn ids(batches: &[RecordBatch]) -> Vec<i32> {
+        batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(1)
+                    .as_primitive::<Int32Type>()
+                    .values()
+                    .to_vec()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_parallel_read() {
+        let table = create_table(
+            "file:/tmp/test_parallel_read",
+            &Schema::builder()
+                .fields(vec![
+                    DataField::new(0, "dt".to_string(), "INT".parse().unwrap()),
+                    DataField::new(1, "id".to_string(), "INT".parse().unwrap()),
+                ])
+                .partition_keys(vec!["dt".to_string()])
+                .options(
+                    [("bucket", "-1"), ("file.format", "parquet")]
+                        .into_iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                )
+                .build(),
+        )
+        .await;
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(Int32Array::from_iter_values((0..40).map(|i| i % 4))),
+                Arc::new(Int32Array::from_iter_values(0..40)),
+            ],
+        )
+        .unwrap();
+        write_and_commit(&table, &batch).await;
+
+        let splits = table.new_scan().plan().await.unwrap().splits().to_vec();
+        assert_eq!(splits.len(), 4);
+        let read = table.new_read();
+        let mut expected = vec![];
+        for split in &splits {
+            for batch in read.read(split).await.unwrap() {
+                expected.push(batch.unwrap());
+            }
+        }
+        let expected = ids(&expected);
+
+        let reader = ParallelReader::new(read.clone())
+            .with_concurrency(2)
+            .with_split_buffer_size(1);
+        let batches = reader
+            .read(splits.clone())
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(ids(&batches), expected);
+
+        // the limit applies to the rows of all the splits
+        let batches = ParallelReader::new(read.clone().with_limit(15))
+            .with_concurrency(2)
+            .read(splits.clone())
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(ids(&batches), expected[..15]);
+        let batches = ParallelReader::new(read.with_limit(15))
+            .with_ordered(false)
+            .read(splits.clone())
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(ids(&batches).len(), 15);
+
+        let batches = reader
+            .with_ordered(false)
+            .read(splits)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        let mut actual = ids(&batches);
+        actual.sort();
+        let mut expected = expected;
+        expected.sort();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,292 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::manifest::{IndexManifestFile, ManifestFile, ManifestList};
+use crate::spec::Snapshot;
+use crate::table::FileStoreTable;
+use crate::utils::{SnapshotManager, TagManager, DEFAULT_MAIN_BRANCH};
+use chrono::Utc;
+use std::collections::HashSet;
+
+/// Files older than this are orphans if not used, by default.
+const DEFAULT_OLDER_THAN_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// A removal of the orphan files of [`FileStoreTable`], the data files, the changelog files,
+/// the manifests and the index files not used by any snapshot or tag of any branch.
+///
+/// Only the files modified before the given time, one day ago by default, are removed, so
+/// that the files being written by a commit in progress are retained. The files are listed
+/// before the used files are collected, so the files of the snapshots committed meanwhile are
+/// not removed either.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/OrphanFilesClean.java>
+#[derive(Debug, Clone)]
+pub struct RemoveOrphanFiles {
+    table: FileStoreTable,
+    older_than_millis: u64,
+    dry_run: bool,
+}
+
+impl RemoveOrphanFiles {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self {
+            table,
+            older_than_millis: (Utc::now().timestamp_millis() as u64)
+                .saturating_sub(DEFAULT_OLDER_THAN_MILLIS),
+            dry_run: false,
+        }
+    }
+
+    /// Only remove the files modified before the given time in milliseconds.
+    pub fn with_older_than_millis(mut self, older_than_millis: u64) -> Self {
+        self.older_than_millis = older_than_millis;
+        self
+    }
+
+    /// Only report the orphan files without removing them.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Remove the orphan files, returns the paths of the files removed, or of the files to be
+    /// removed in a dry run.
+    pub async fn remove(&self) -> crate::Result<Vec<String>> {
+        let candidates = self.candidate_files().await?;
+        let used_files = self.used_files().await?;
+        let orphan_files: Vec<String> = candidates
+            .into_iter()
+            .filter(|(name, _)| !used_files.contains(name))
+            .map(|(_, path)| path)
+            .collect();
+        if !self.dry_run {
+            for path in &orphan_files {
+                self.table.file_io().delete_file(path).await?;
+            }
+        }
+        Ok(orphan_files)
+    }
+
+    /// List the names and the paths of the files modified before the time, in the manifest
+    /// directory, the index directory and the bucket directories.
+    async fn candidate_files(&self) -> crate::Result<Vec<(String, String)>> {
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+        let location = self.table.location().trim_end_matches('/');
+        let mut dirs = vec![path_factory.manifest_dir(), path_factory.index_dir()];
+        // the partition directories and the bucket directories of the data files
+        for status in file_io.list_status(location).await? {
+            if status.is_dir && is_data_dir(&status.path) {
+                dirs.push(status.path);
+            }
+        }
+        let mut files = Vec::new();
+        while let Some(dir) = dirs.pop() {
+            if !file_io.exists(&format!("{dir}/")).await? {
+                continue;
+            }
+            for status in file_io.list_status(&dir).await? {
+                if status.is_dir {
+                    if is_data_dir(&status.path) {
+                        dirs.push(status.path);
+                    }
+                    continue;
+                }
+                let old = status.last_modified.is_some_and(|modified| {
+                    (modified.timestamp_millis() as u64) < self.older_than_millis
+                });
+                if old {
+                    let name = file_name(&status.path).to_string();
+                    files.push((name, status.path));
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Get the names of the files used by the snapshots and the tags of all the branches.
+    async fn used_files(&self) -> crate::Result<HashSet<String>> {
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+        let manifest_list = ManifestList::new(file_io.clone());
+        let manifest_file = ManifestFile::new(file_io.clone());
+        let index_manifest_file = IndexManifestFile::new(file_io.clone());
+        let mut used_files = HashSet::new();
+        for snapshot in self.snapshots().await? {
+            let lists = [
+                Some(snapshot.base_manifest_list()),
+                Some(snapshot.delta_manifest_list()),
+                snapshot.changelog_manifest_list(),
+            ];
+            for list in lists.into_iter().flatten() {
+                // the manifests are shared by the snapshots, so each one is read once
+                if !used_files.insert(list.to_string()) {
+                    continue;
+                }
+                for manifest in manifest_list
+                    .read(&path_factory.manifest_path(list))
+                    .await?
+                {
+                    if !used_files.insert(manifest.file_name().to_string()) {
+                        continue;
+                    }
+                    for entry in manifest_file
+                        .read(&path_factory.manifest_path(manifest.file_name()))
+                        .await?
+                    {
+                        let file = entry.file();
+                        used_files.insert(file.file_name.clone());
+                        used_files.extend(file.extra_files.iter().cloned());
+                    }
+                }
+            }
+            let Some(index_manifest) = snapshot.index_manifest() else {
+                continue;
+            };
+            if !used_files.insert(index_manifest.to_string()) {
+                continue;
+            }
+            for entry in index_manifest_file
+                .read(&path_factory.manifest_path(index_manifest))
+                .await?
+            {
+                used_files.insert(entry.index_file.file_name);
+            }
+        }
+        Ok(used_files)
+    }
+
+    /// Read the snapshots and the tagged snapshots of the main branch and the other branches.
+    async fn snapshots(&self) -> crate::Result<Vec<Snapshot>> {
+        let file_io = self.table.file_io();
+        let location = self.table.location();
+        let mut branches = vec![DEFAULT_MAIN_BRANCH.to_string()];
+        branches.extend(self.table.branch_manager().branches().await?);
+        let mut snapshots = Vec::new();
+        for branch in branches {
+            let snapshot_manager =
+                SnapshotManager::new(file_io.clone(), location).with_branch(branch.clone());
+            snapshots.extend(snapshot_manager.snapshots().await?);
+            let tag_manager = TagManager::new(file_io.clone(), location).with_branch(branch);
+            snapshots.extend(tag_manager.tagged_snapshots().await?);
+        }
+        Ok(snapshots)
+    }
+}
+
+/// Whether the directory is a partition directory or a bucket directory.
+fn is_data_dir(path: &str) -> bool {
+    let name = file_name(path);
+    name.contains('=') || name.starts_with("bucket-")
+}
+
+fn file_name(path: &str) -> &str {
+    let path = path.trim_end_matches('/');
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{Table, TableScan};
+    use crate::test_utils::{create_id_name_table, id_name_batch, write_and_commit};
+    use bytes::Bytes;
+
+    async fn write(table: &FileStoreTable, id: i32) {
+        write_and_commit(
+            table,
+            &id_name_batch(table, &[id], &[&format!("name-{id}")]),
+        )
+        .await;
+    }
+
+    async fn read_rows(scan: TableScan) -> usize {
+        let read = scan.table().new_read();
+        let mut rows = 0;
+        for split in scan.plan().await.unwrap().splits() {
+            for batch in read.read(split).await.unwrap() {
+                rows += batch.unwrap().num_rows();
+            }
+        }
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_remove_orphan_files() {
+        let location = "file:/tmp/test_remove_orphan_files";
+        let table = create_id_name_table(
+            location,
+            &["id"],
+            &[("file.format", "parquet"), ("bucket", "1")],
+        )
+        .await;
+        write(&table, 1).await;
+        table.create_tag("tag-1", 1).await.unwrap();
+        write(&table, 2).await;
+        // the files of snapshot 1 are only used by the tag after the expiration
+        let expired = table
+            .new_expire_snapshots()
+            .with_retain_min(1)
+            .with_retain_max(1)
+            .with_older_than_millis(u64::MAX)
+            .expire()
+            .await
+            .unwrap();
+        assert_eq!(expired, 1);
+
+        let file_io = table.file_io();
+        let orphan_files = [
+            format!("{location}/bucket-0/data-orphan.parquet"),
+            format!("{location}/manifest/manifest-orphan"),
+        ];
+        for path in &orphan_files {
+            file_io
+                .new_output(path)
+                .unwrap()
+                .write(Bytes::from("orphan"))
+                .await
+                .unwrap();
+        }
+
+        // the files just written are in the grace period
+        let remove = table.new_remove_orphan_files();
+        assert!(remove.with_dry_run(true).remove().await.unwrap().is_empty());
+
+        let older_than_millis = Utc::now().timestamp_millis() as u64 + 60 * 1000;
+        let remove = table
+            .new_remove_orphan_files()
+            .with_older_than_millis(older_than_millis);
+        let mut removed = remove.clone().with_dry_run(true).remove().await.unwrap();
+        removed.sort();
+        assert_eq!(removed, orphan_files);
+        for path in &orphan_files {
+            assert!(file_io.exists(path).await.unwrap());
+        }
+
+        let mut removed = remove.remove().await.unwrap();
+        removed.sort();
+        assert_eq!(removed, orphan_files);
+        for path in &orphan_files {
+            assert!(!file_io.exists(path).await.unwrap());
+        }
+        assert!(remove.remove().await.unwrap().is_empty());
+
+        // the files of the tag and the latest snapshot are still readable
+        assert_eq!(read_rows(table.new_scan()).await, 2);
+        assert_eq!(read_rows(table.new_scan().with_tag("tag-1")).await, 1);
+    }
+}
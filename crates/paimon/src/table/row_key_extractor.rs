@@ -0,0 +1,140 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::to_binary_row;
+use crate::spec::{BinaryRow, DataType, TableSchema};
+use arrow_array::{Array, RecordBatch};
+
+/// Extractor of the partitions and the hashes of the bucket keys of the rows of record batches
+/// matching the fields of the table.
+///
+/// Keys are projected into binary rows the same as paimon-java, so that rows are distributed
+/// into the same buckets by writers of both.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/sink/FixedBucketRowKeyExtractor.java>
+#[derive(Debug, Clone)]
+pub(crate) struct RowKeyExtractor {
+    data_types: Vec<DataType>,
+    partition_indices: Vec<usize>,
+    bucket_key_indices: Vec<usize>,
+}
+
+impl RowKeyExtractor {
+    pub fn new(schema: &TableSchema) -> Self {
+        let index_of = |name: &str| {
+            schema
+                .fields()
+                .iter()
+                .position(|f| f.name() == name)
+                .expect("keys are validated by the schema")
+        };
+        Self {
+            data_types: schema
+                .fields()
+                .iter()
+                .map(|f| f.data_type().clone())
+                .collect(),
+            partition_indices: schema
+                .partition_keys()
+                .iter()
+                .map(|key| index_of(key))
+                .collect(),
+            bucket_key_indices: schema.bucket_keys().into_iter().map(index_of).collect(),
+        }
+    }
+
+    /// Whether the rows have bucket keys, rows of append only tables have none unless
+    /// `bucket-key` is set.
+    pub fn has_bucket_keys(&self) -> bool {
+        !self.bucket_key_indices.is_empty()
+    }
+
+    /// Get the partition of the row.
+    pub fn partition(&self, batch: &RecordBatch, row: usize) -> crate::Result<BinaryRow> {
+        self.project(&self.partition_indices, batch, row)
+    }
+
+    /// Get the hash code of the bucket key of the row.
+    pub fn bucket_key_hash(&self, batch: &RecordBatch, row: usize) -> crate::Result<i32> {
+        Ok(self
+            .project(&self.bucket_key_indices, batch, row)?
+            .hash_code())
+    }
+
+    fn project(
+        &self,
+        indices: &[usize],
+        batch: &RecordBatch,
+        row: usize,
+    ) -> crate::Result<BinaryRow> {
+        let columns: Vec<&dyn Array> = indices.iter().map(|i| batch.column(*i).as_ref()).collect();
+        let data_types: Vec<&DataType> = indices.iter().map(|i| &self.data_types[*i]).collect();
+        to_binary_row(&columns, &data_types, row)
+    }
+}
+
+/// Get the bucket of the hash code of a key, the same as paimon-java.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/sink/KeyAndBucketExtractor.java>
+pub(crate) fn bucket(hash_code: i32, num_buckets: i32) -> i32 {
+    (hash_code % num_buckets).abs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::to_arrow_schema;
+    use crate::spec::{DataField, Schema};
+    use arrow_array::{Int32Array, StringArray};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_row_key_extractor() {
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "dt".to_string(), "STRING".parse().unwrap()),
+                DataField::new(1, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+            ])
+            .partition_keys(vec!["dt".to_string()])
+            .primary_keys(vec!["dt".to_string(), "id".to_string()])
+            .options(HashMap::from([("bucket".to_string(), "3".to_string())]))
+            .build()
+            .to_table_schema(0)
+            .unwrap();
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(schema.fields())),
+            vec![
+                Arc::new(StringArray::from(vec!["ab"; 6])),
+                Arc::new(Int32Array::from((0..6).collect::<Vec<_>>())),
+            ],
+        )
+        .unwrap();
+        let extractor = RowKeyExtractor::new(&schema);
+        assert!(extractor.has_bucket_keys());
+        let partition = extractor.partition(&batch, 0).unwrap();
+        assert_eq!(partition.get_string(0).unwrap(), "ab");
+        assert_eq!(partition.hash_code(), -425866811);
+
+        // the buckets of the trimmed primary keys computed by paimon-java
+        let buckets: Vec<i32> = (0..6)
+            .map(|row| bucket(extractor.bucket_key_hash(&batch, row).unwrap(), 3))
+            .collect();
+        assert_eq!(buckets, vec![0, 1, 2, 1, 0, 2]);
+        assert_eq!(extractor.bucket_key_hash(&batch, 1).unwrap(), 1465514398);
+    }
+}
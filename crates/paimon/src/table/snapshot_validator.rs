@@ -0,0 +1,256 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::manifest::{ManifestFile, ManifestList};
+use crate::spec::{FileKind, ManifestFileMeta, Snapshot};
+use crate::table::FileStoreTable;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// A validator of the referential integrity of the snapshots of [`FileStoreTable`], for
+/// diagnosing corrupted tables.
+///
+/// The manifest lists, the manifests, the index manifest and the statistics file referenced by
+/// a snapshot must exist, and the record counts of the delta and the changelog of the snapshot
+/// must match the entries of their manifests. The data files are not checked.
+#[derive(Debug, Clone)]
+pub struct SnapshotValidator {
+    table: FileStoreTable,
+}
+
+/// A problem found in a snapshot by [`SnapshotValidator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotIssue {
+    /// The manifest list of the name doesn't exist.
+    ManifestListMissing(String),
+    /// The manifest of the name listed in a manifest list doesn't exist.
+    ManifestMissing(String),
+    /// The index manifest of the name doesn't exist.
+    IndexManifestMissing(String),
+    /// The statistics file of the name doesn't exist.
+    StatisticsMissing(String),
+    /// The delta record count of the snapshot differs from the one of the delta manifests.
+    DeltaRecordCountMismatch { expected: i64, actual: i64 },
+    /// The changelog record count of the snapshot differs from the one of the changelog
+    /// manifests.
+    ChangelogRecordCountMismatch { expected: i64, actual: i64 },
+}
+
+impl Display for SnapshotIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotIssue::ManifestListMissing(name) => write!(f, "manifest list {name} not exist"),
+            SnapshotIssue::ManifestMissing(name) => write!(f, "manifest {name} not exist"),
+            SnapshotIssue::IndexManifestMissing(name) => {
+                write!(f, "index manifest {name} not exist")
+            }
+            SnapshotIssue::StatisticsMissing(name) => write!(f, "statistics {name} not exist"),
+            SnapshotIssue::DeltaRecordCountMismatch { expected, actual } => write!(
+                f,
+                "delta record count {expected} mismatches {actual} of the delta manifests"
+            ),
+            SnapshotIssue::ChangelogRecordCountMismatch { expected, actual } => write!(
+                f,
+                "changelog record count {expected} mismatches {actual} of the changelog manifests"
+            ),
+        }
+    }
+}
+
+impl SnapshotValidator {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+
+    /// Validate all the snapshots of the table, returns the issues of the snapshots with any
+    /// by their ids.
+    pub async fn validate_all(&self) -> crate::Result<BTreeMap<i64, Vec<SnapshotIssue>>> {
+        let mut issues = BTreeMap::new();
+        for snapshot in self.table.snapshot_manager().snapshots().await? {
+            let snapshot_issues = self.validate(&snapshot).await?;
+            if !snapshot_issues.is_empty() {
+                issues.insert(snapshot.id(), snapshot_issues);
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Validate the snapshot, returns the issues found, empty if the snapshot is valid.
+    pub async fn validate(&self, snapshot: &Snapshot) -> crate::Result<Vec<SnapshotIssue>> {
+        let mut issues = Vec::new();
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+
+        self.validate_manifest_list(snapshot.base_manifest_list(), &mut issues)
+            .await?;
+        let delta_count = self
+            .validate_manifest_list(snapshot.delta_manifest_list(), &mut issues)
+            .await?;
+        if let (Some(expected), Some(actual)) = (snapshot.delta_record_count(), delta_count) {
+            if expected != actual {
+                issues.push(SnapshotIssue::DeltaRecordCountMismatch { expected, actual });
+            }
+        }
+        let changelog_count = match snapshot.changelog_manifest_list() {
+            Some(list) => self.validate_manifest_list(list, &mut issues).await?,
+            None => Some(0),
+        };
+        if let (Some(expected), Some(actual)) = (snapshot.changelog_record_count(), changelog_count)
+        {
+            if expected != actual {
+                issues.push(SnapshotIssue::ChangelogRecordCountMismatch { expected, actual });
+            }
+        }
+
+        if let Some(index_manifest) = snapshot.index_manifest() {
+            if !file_io
+                .exists(&path_factory.manifest_path(index_manifest))
+                .await?
+            {
+                issues.push(SnapshotIssue::IndexManifestMissing(
+                    index_manifest.to_string(),
+                ));
+            }
+        }
+        if let Some(statistics) = snapshot.statistics() {
+            let path = self.table.stats_file_handler().statistics_path(statistics);
+            if !file_io.exists(&path).await? {
+                issues.push(SnapshotIssue::StatisticsMissing(statistics.to_string()));
+            }
+        }
+        Ok(issues)
+    }
+
+    /// Check that the manifest list and its manifests exist, returns the record count of the
+    /// entries of the manifests, none if any of them is missing.
+    async fn validate_manifest_list(
+        &self,
+        list: &str,
+        issues: &mut Vec<SnapshotIssue>,
+    ) -> crate::Result<Option<i64>> {
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+        let path = path_factory.manifest_path(list);
+        if !file_io.exists(&path).await? {
+            issues.push(SnapshotIssue::ManifestListMissing(list.to_string()));
+            return Ok(None);
+        }
+        let manifests: Vec<ManifestFileMeta> =
+            ManifestList::new(file_io.clone()).read(&path).await?;
+        let manifest_file = ManifestFile::new(file_io.clone());
+        let mut record_count = Some(0);
+        for manifest in &manifests {
+            let path = path_factory.manifest_path(manifest.file_name());
+            if !file_io.exists(&path).await? {
+                issues.push(SnapshotIssue::ManifestMissing(
+                    manifest.file_name().to_string(),
+                ));
+                record_count = None;
+                continue;
+            }
+            let Some(count) = &mut record_count else {
+                continue;
+            };
+            for entry in manifest_file.read(&path).await? {
+                match entry.kind() {
+                    FileKind::Add => *count += entry.file().row_count,
+                    FileKind::Delete => *count -= entry.file().row_count,
+                }
+            }
+        }
+        Ok(record_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataField, Schema};
+    use crate::table::Table;
+    use crate::utils::SchemaManager;
+    use arrow_array::{Int32Array, RecordBatch};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_validate_snapshots() {
+        let location = "file:/tmp/test_validate_snapshots";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = Schema::builder()
+            .fields(vec![DataField::new(
+                0,
+                "id".to_string(),
+                "INT".parse().unwrap(),
+            )])
+            .options(HashMap::from([
+                ("bucket".to_string(), "-1".to_string()),
+                ("file.format".to_string(), "parquet".to_string()),
+            ]))
+            .build();
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(&schema)
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(
+            file_io.clone(),
+            Identifier::new("db", "t"),
+            location,
+            schema,
+        );
+        for ids in [vec![1, 2], vec![3]] {
+            let batch = RecordBatch::try_new(
+                Arc::new(to_arrow_schema(table.schema().fields())),
+                vec![Arc::new(Int32Array::from(ids))],
+            )
+            .unwrap();
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            write.write(&batch).await.unwrap();
+            let messages = write.prepare_commit().await.unwrap();
+            builder.new_commit().commit(messages).await.unwrap();
+        }
+        let validator = SnapshotValidator::new(table.clone());
+        assert!(validator.validate_all().await.unwrap().is_empty());
+
+        // the delta manifest of the first snapshot is also in the base of the second one
+        let snapshot = table.snapshot_manager().snapshot(1).await.unwrap();
+        let path_factory = table.path_factory();
+        let manifests = ManifestList::new(file_io.clone())
+            .read(&path_factory.manifest_path(snapshot.delta_manifest_list()))
+            .await
+            .unwrap();
+        let manifest = manifests[0].file_name().to_string();
+        file_io
+            .delete_file(&path_factory.manifest_path(&manifest))
+            .await
+            .unwrap();
+        let issues = validator.validate_all().await.unwrap();
+        assert_eq!(
+            issues[&1],
+            vec![SnapshotIssue::ManifestMissing(manifest.clone())]
+        );
+        assert_eq!(issues[&2], vec![SnapshotIssue::ManifestMissing(manifest)]);
+        assert_eq!(
+            issues[&1][0].to_string(),
+            format!("manifest {} not exist", manifests[0].file_name())
+        );
+    }
+}
@@ -0,0 +1,268 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::spec::{BinaryRow, DataFileMeta};
+use chrono::{DateTime, Utc};
+use typed_builder::TypedBuilder;
+
+/// Range of the deletion vector of a data file in an index file.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/source/DeletionFile.java>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletionFile {
+    path: String,
+    offset: i64,
+    length: i64,
+}
+
+impl DeletionFile {
+    pub fn new(path: impl Into<String>, offset: i64, length: i64) -> Self {
+        Self {
+            path: path.into(),
+            offset,
+            length,
+        }
+    }
+
+    /// Get the path of the index file.
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the start position of the deletion vector in the index file.
+    #[inline]
+    pub fn offset(&self) -> i64 {
+        self.offset
+    }
+
+    /// Get the length in bytes of the serialized deletion vector.
+    #[inline]
+    pub fn length(&self) -> i64 {
+        self.length
+    }
+}
+
+/// Input of reading a bucket of a partition, including the data files to read.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/source/DataSplit.java>
+#[derive(Debug, Clone, PartialEq, Eq, TypedBuilder)]
+pub struct DataSplit {
+    snapshot_id: i64,
+    partition: BinaryRow,
+    bucket: i32,
+    bucket_path: String,
+    data_files: Vec<DataFileMeta>,
+    /// Deletion files of the data files in the same order, empty if there are no deletion vectors.
+    #[builder(default)]
+    data_deletion_files: Vec<Option<DeletionFile>>,
+    /// Whether the data files can be read without merging.
+    #[builder(default)]
+    raw_convertible: bool,
+    /// Whether the data files are changes planned by a streaming scan.
+    #[builder(default)]
+    streaming: bool,
+    /// Data files of the bucket in the start snapshot of a diff of primary key tables.
+    #[builder(default)]
+    before_files: Vec<DataFileMeta>,
+}
+
+impl DataSplit {
+    /// Get the id of the snapshot this split is planned from.
+    #[inline]
+    pub fn snapshot_id(&self) -> i64 {
+        self.snapshot_id
+    }
+
+    /// Get the partition of this split.
+    #[inline]
+    pub fn partition(&self) -> &BinaryRow {
+        &self.partition
+    }
+
+    /// Get the bucket of this split.
+    #[inline]
+    pub fn bucket(&self) -> i32 {
+        self.bucket
+    }
+
+    /// Get the path of the bucket directory, which contains the data files.
+    #[inline]
+    pub fn bucket_path(&self) -> &str {
+        &self.bucket_path
+    }
+
+    /// Get the data files to read.
+    #[inline]
+    pub fn data_files(&self) -> &[DataFileMeta] {
+        &self.data_files
+    }
+
+    /// Get the deletion file of the data file at the index of [`Self::data_files`].
+    pub fn deletion_file(&self, index: usize) -> Option<&DeletionFile> {
+        self.data_deletion_files.get(index).and_then(Option::as_ref)
+    }
+
+    /// Whether the data files can be read without merging, such as the files of append only
+    /// tables, or the files of primary key tables with deletion vectors.
+    #[inline]
+    pub fn raw_convertible(&self) -> bool {
+        self.raw_convertible
+    }
+
+    /// Whether the data files are the changes of a snapshot planned by a
+    /// [`StreamTableScan`](crate::table::StreamTableScan), which are read in order with their
+    /// row kinds without merging.
+    #[inline]
+    pub fn is_streaming(&self) -> bool {
+        self.streaming
+    }
+
+    /// Get the data files of the bucket in the start snapshot if the split is a diff of
+    /// primary key tables, whose changes from the merged rows of these files to the merged
+    /// rows of [`Self::data_files`] are read.
+    #[inline]
+    pub fn before_files(&self) -> &[DataFileMeta] {
+        &self.before_files
+    }
+
+    /// Get the path of the data file.
+    pub fn data_file_path(&self, file: &DataFileMeta) -> String {
+        format!("{}/{}", self.bucket_path, file.file_name)
+    }
+
+    /// Get the total number of rows in the data files, including deletions.
+    pub fn row_count(&self) -> i64 {
+        self.data_files.iter().map(|f| f.row_count).sum()
+    }
+
+    /// Get the number of rows of the data file at the index of [`Self::data_files`] without the
+    /// rows deleted by its deletion file, zero if the number of deleted rows is unknown.
+    pub(crate) fn merged_row_count(&self, index: usize) -> i64 {
+        let file = &self.data_files[index];
+        match (self.deletion_file(index), file.delete_row_count) {
+            (None, _) => file.row_count,
+            (Some(_), Some(delete_row_count)) => file.row_count - delete_row_count,
+            (Some(_), None) => 0,
+        }
+    }
+
+    /// Only keep the first `len` data files and their deletion files.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.data_files.truncate(len);
+        self.data_deletion_files.truncate(len);
+    }
+}
+
+/// Result of planning a [`TableScan`](crate::table::TableScan).
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/source/TableScan.java>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    snapshot_id: Option<i64>,
+    splits: Vec<DataSplit>,
+}
+
+impl Plan {
+    pub fn new(snapshot_id: Option<i64>, splits: Vec<DataSplit>) -> Self {
+        Self {
+            snapshot_id,
+            splits,
+        }
+    }
+
+    /// Get the id of the snapshot planned, none if the table has no snapshot.
+    #[inline]
+    pub fn snapshot_id(&self) -> Option<i64> {
+        self.snapshot_id
+    }
+
+    /// Get the splits to read.
+    #[inline]
+    pub fn splits(&self) -> &[DataSplit] {
+        &self.splits
+    }
+
+    /// Take the splits to read.
+    pub fn into_splits(self) -> Vec<DataSplit> {
+        self.splits
+    }
+}
+
+/// A partition of a table with the stats of its data files, listed by
+/// [`TableScan::list_partitions`](crate::table::TableScan::list_partitions).
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/FileStoreScan.java>
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionEntry {
+    partition: BinaryRow,
+    record_count: i64,
+    file_size_in_bytes: i64,
+    file_count: i64,
+    last_file_creation_time: DateTime<Utc>,
+}
+
+impl PartitionEntry {
+    /// Create the entry of the partition with a data file.
+    pub fn new(partition: BinaryRow, file: &DataFileMeta) -> Self {
+        Self {
+            partition,
+            record_count: file.row_count,
+            file_size_in_bytes: file.file_size,
+            file_count: 1,
+            last_file_creation_time: file.creation_time,
+        }
+    }
+
+    /// Add the stats of another data file of the partition.
+    pub fn add(&mut self, file: &DataFileMeta) {
+        self.record_count += file.row_count;
+        self.file_size_in_bytes += file.file_size;
+        self.file_count += 1;
+        self.last_file_creation_time = self.last_file_creation_time.max(file.creation_time);
+    }
+
+    /// Get the values of the partition keys.
+    #[inline]
+    pub fn partition(&self) -> &BinaryRow {
+        &self.partition
+    }
+
+    /// Get the number of rows in the data files, including deletions.
+    #[inline]
+    pub fn record_count(&self) -> i64 {
+        self.record_count
+    }
+
+    /// Get the total size of the data files.
+    #[inline]
+    pub fn file_size_in_bytes(&self) -> i64 {
+        self.file_size_in_bytes
+    }
+
+    /// Get the number of the data files.
+    #[inline]
+    pub fn file_count(&self) -> i64 {
+        self.file_count
+    }
+
+    /// Get the creation time of the latest created data file, as the last update time.
+    #[inline]
+    pub fn last_file_creation_time(&self) -> DateTime<Utc> {
+        self.last_file_creation_time
+    }
+}
@@ -0,0 +1,100 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::spec::DataFileMeta;
+
+/// Split the data files of a bucket of append only tables, this keeps the order of the files by
+/// sequence number and packs them into splits of the target size.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/source/AppendOnlySplitGenerator.java>
+pub(crate) fn split_append_only_files(
+    mut files: Vec<DataFileMeta>,
+    target_size: i64,
+    open_file_cost: i64,
+) -> Vec<Vec<DataFileMeta>> {
+    files.sort_by_key(|file| file.min_sequence_number);
+    pack_for_ordered(
+        files,
+        |file| file.file_size.max(open_file_cost),
+        target_size,
+    )
+}
+
+/// Pack the ordered items into bins whose weights don't exceed the target weight, unless the
+/// bin has a single item.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/utils/BinPacking.java>
+fn pack_for_ordered<T>(
+    items: Vec<T>,
+    weight: impl Fn(&T) -> i64,
+    target_weight: i64,
+) -> Vec<Vec<T>> {
+    let mut packed = Vec::new();
+    let mut bin = Vec::new();
+    let mut bin_weight = 0;
+    for item in items {
+        let item_weight = weight(&item);
+        if bin_weight + item_weight > target_weight && !bin.is_empty() {
+            packed.push(std::mem::take(&mut bin));
+            bin_weight = 0;
+        }
+        bin_weight += item_weight;
+        bin.push(item);
+    }
+    if !bin.is_empty() {
+        packed.push(bin);
+    }
+    packed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::data_file;
+
+    #[test]
+    fn test_split_append_only_files() {
+        let splits = split_append_only_files(
+            [("c", 60, 2), ("a", 10, 0), ("b", 30, 1), ("d", 40, 3)]
+                .into_iter()
+                .map(|(name, file_size, sequence_number)| DataFileMeta {
+                    file_size,
+                    min_sequence_number: sequence_number,
+                    max_sequence_number: sequence_number,
+                    ..data_file(name)
+                })
+                .collect(),
+            100,
+            20,
+        );
+        let names: Vec<Vec<&str>> = splits
+            .iter()
+            .map(|files| files.iter().map(|f| f.file_name.as_str()).collect())
+            .collect();
+        assert_eq!(names, vec![vec!["a", "b"], vec!["c", "d"]]);
+    }
+
+    #[test]
+    fn test_pack_for_ordered() {
+        let packed = pack_for_ordered(vec![1, 2, 3, 4, 5, 10, 1], |i| *i, 6);
+        assert_eq!(
+            packed,
+            vec![vec![1, 2, 3], vec![4], vec![5], vec![10], vec![1]]
+        );
+        assert!(pack_for_ordered(Vec::<i64>::new(), |i| *i, 6).is_empty());
+    }
+}
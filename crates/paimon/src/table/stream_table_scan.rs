@@ -0,0 +1,536 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::spec::{ChangelogProducer, CommitKind, Consumer, CoreOptions, Datum, Snapshot};
+use crate::table::{FileStoreTable, Plan, TableScan};
+use crate::utils::SnapshotManager;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Poller of the next snapshot followed by a [`StreamTableScan`].
+#[async_trait]
+pub trait SnapshotPoller: Debug + Send + Sync {
+    /// Get the snapshot of the id, none if it is not committed yet.
+    async fn next_snapshot(
+        &self,
+        snapshot_manager: &SnapshotManager,
+        snapshot_id: i64,
+    ) -> crate::Result<Option<Snapshot>>;
+}
+
+/// Poller getting the next snapshot if it is committed, without waiting for it.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultSnapshotPoller;
+
+#[async_trait]
+impl SnapshotPoller for DefaultSnapshotPoller {
+    async fn next_snapshot(
+        &self,
+        snapshot_manager: &SnapshotManager,
+        snapshot_id: i64,
+    ) -> crate::Result<Option<Snapshot>> {
+        if !snapshot_manager.snapshot_exists(snapshot_id).await? {
+            return Ok(None);
+        }
+        snapshot_manager.snapshot(snapshot_id).await.map(Some)
+    }
+}
+
+/// A streaming scan of [`FileStoreTable`] following the snapshots committed.
+///
+/// The first plan starts up by `scan.mode`: `latest-full`, the default, plans all the data of
+/// the latest snapshot, `latest` plans nothing and follows the snapshots after the latest one,
+/// `from-snapshot` follows the snapshots from `scan.snapshot-id`, and `from-snapshot-full`
+/// plans all the data of `scan.snapshot-id` then follows the snapshots after it.
+///
+/// Each following plan reads the changes of the next snapshot: the data files added by the
+/// append snapshots, or the changelog files of the snapshots of primary key tables with a
+/// `changelog-producer`. The splits of the changes are streaming splits, read in order with
+/// their row kinds. Plans of no snapshot are empty, the id of the next snapshot to plan is
+/// the checkpoint to restore the scan from.
+///
+/// Scans with a `consumer-id` start up from the next snapshot of the consumer if it exists,
+/// and record the progress into the consumer once a checkpoint is completed.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/source/DataTableStreamScan.java>
+#[derive(Debug, Clone)]
+pub struct StreamTableScan {
+    scan: TableScan,
+    poller: Arc<dyn SnapshotPoller>,
+    next_snapshot_id: Option<i64>,
+}
+
+impl StreamTableScan {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self {
+            scan: TableScan::new(table),
+            poller: Arc::new(DefaultSnapshotPoller),
+            next_snapshot_id: None,
+        }
+    }
+
+    /// Only scan the partitions whose values equal to the given ones.
+    pub fn with_partition_filter(mut self, partition: HashMap<String, Datum>) -> Self {
+        self.scan = self.scan.with_partition_filter(partition);
+        self
+    }
+
+    /// Only scan the given bucket.
+    pub fn with_bucket(mut self, bucket: i32) -> Self {
+        self.scan = self.scan.with_bucket(bucket);
+        self
+    }
+
+    /// Poll the next snapshots by the given poller.
+    pub fn with_poller(mut self, poller: Arc<dyn SnapshotPoller>) -> Self {
+        self.poller = poller;
+        self
+    }
+
+    /// Get the table to scan.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        self.scan.table()
+    }
+
+    /// Get the id of the next snapshot to plan, none if the scan is not started up yet.
+    #[inline]
+    pub fn checkpoint(&self) -> Option<i64> {
+        self.next_snapshot_id
+    }
+
+    /// Restore the scan to plan from the snapshot of the id, skipping the start up.
+    pub fn restore(&mut self, next_snapshot_id: i64) {
+        self.next_snapshot_id = Some(next_snapshot_id);
+    }
+
+    /// Record the id of the next snapshot to plan into the consumer of `consumer-id` if any,
+    /// after the splits planned before are consumed.
+    pub async fn notify_checkpoint_complete(&self, next_snapshot_id: i64) -> crate::Result<()> {
+        let table = self.scan.table();
+        let Some(consumer_id) = table.core_options().consumer_id() else {
+            return Ok(());
+        };
+        table
+            .consumer_manager()
+            .reset_consumer(consumer_id, Consumer::new(next_snapshot_id))
+            .await
+    }
+
+    /// Plan the splits of the start up, or the changes of the next snapshot to scan.
+    pub async fn plan(&mut self) -> crate::Result<Plan> {
+        let Some(mut next_snapshot_id) = self.next_snapshot_id else {
+            return self.start_up().await;
+        };
+        let table = self.scan.table();
+        let snapshot_manager = table.snapshot_manager();
+        let schema = table.schema();
+        let changelog = !schema.primary_keys().is_empty()
+            && CoreOptions::new(schema.options()).changelog_producer()? != ChangelogProducer::None;
+        loop {
+            let Some(snapshot) = self
+                .poller
+                .next_snapshot(&snapshot_manager, next_snapshot_id)
+                .await?
+            else {
+                if let Some(earliest) = snapshot_manager.earliest_snapshot_id().await? {
+                    if earliest > next_snapshot_id {
+                        return SnapshotExpiredSnafu {
+                            snapshot_id: next_snapshot_id,
+                            earliest,
+                        }
+                        .fail();
+                    }
+                }
+                return Ok(Plan::new(None, vec![]));
+            };
+            next_snapshot_id += 1;
+            self.next_snapshot_id = Some(next_snapshot_id);
+            let scanned = if changelog {
+                snapshot.changelog_manifest_list().is_some()
+            } else {
+                snapshot.commit_kind() == &CommitKind::APPEND
+            };
+            if scanned {
+                return self.scan.plan_changes(&snapshot, changelog).await;
+            }
+        }
+    }
+
+    async fn start_up(&mut self) -> crate::Result<Plan> {
+        let table = self.scan.table();
+        let options = table.core_options();
+        if let Some(consumer_id) = options.consumer_id() {
+            if let Some(consumer) = table.consumer_manager().consumer(consumer_id).await? {
+                self.next_snapshot_id = Some(consumer.next_snapshot());
+                return Ok(Plan::new(None, vec![]));
+            }
+        }
+        let snapshot_id = options.scan_snapshot_id();
+        let full_snapshot_id = match (options.scan_mode().as_str(), snapshot_id) {
+            ("default" | "latest-full", None) => {
+                table.snapshot_manager().latest_snapshot_id().await?
+            }
+            ("latest", None) => {
+                let latest = table.snapshot_manager().latest_snapshot_id().await?;
+                self.next_snapshot_id = Some(latest.map_or(1, |id| id + 1));
+                return Ok(Plan::new(None, vec![]));
+            }
+            ("default" | "from-snapshot", Some(snapshot_id)) => {
+                self.next_snapshot_id = Some(snapshot_id);
+                return Ok(Plan::new(None, vec![]));
+            }
+            ("from-snapshot-full", Some(snapshot_id)) => Some(snapshot_id),
+            (mode, _) => {
+                return ConfigInvalidSnafu {
+                    message: format!(
+                        "Unsupported scan.mode {mode} with scan.snapshot-id {snapshot_id:?} for streaming scans"
+                    ),
+                }
+                .fail()
+            }
+        };
+        let Some(snapshot_id) = full_snapshot_id else {
+            return Ok(Plan::new(None, vec![]));
+        };
+        let plan = self.scan.clone().as_of_snapshot(snapshot_id).plan().await?;
+        self.next_snapshot_id = Some(snapshot_id + 1);
+        Ok(plan)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::spec::{BinaryRow, RowKind};
+    use crate::table::{CommitMessage, CompactIncrement, DataIncrement, DataSplit, Table};
+    use crate::test_utils::{create_id_name_table, id_name_batch, write_and_commit_with_row_kinds};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+
+    /// Write and commit the rows, returning the messages committed.
+    async fn write(table: &FileStoreTable, rows: &[(RowKind, i32, &str)]) -> Vec<CommitMessage> {
+        let ids: Vec<i32> = rows.iter().map(|r| r.1).collect();
+        let names: Vec<&str> = rows.iter().map(|r| r.2).collect();
+        let row_kinds: Vec<RowKind> = rows.iter().map(|r| r.0).collect();
+        write_and_commit_with_row_kinds(table, &id_name_batch(table, &ids, &names), &row_kinds)
+            .await
+    }
+
+    /// Read the (row kind, id, name) rows of the plan.
+    async fn read(table: &FileStoreTable, plan: &Plan) -> Vec<(RowKind, i32, String)> {
+        let read = table.new_read();
+        let mut rows = Vec::new();
+        for split in plan.splits() {
+            for batch in read.read_with_row_kinds(split).await.unwrap() {
+                let (batch, row_kinds) = batch.unwrap();
+                let ids = batch.column(0).as_primitive::<Int32Type>();
+                let names = batch.column(1).as_string::<i32>();
+                rows.extend(
+                    row_kinds
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, kind)| (kind, ids.value(i), names.value(i).to_string())),
+                );
+            }
+        }
+        rows
+    }
+
+    fn rows(rows: &[(RowKind, i32, &str)]) -> Vec<(RowKind, i32, String)> {
+        rows.iter()
+            .map(|(kind, id, name)| (*kind, *id, name.to_string()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_stream_scan_append_only() {
+        let table = create_id_name_table(
+            "file:/tmp/test_stream_scan_append_only",
+            &[],
+            &[("file.format", "parquet")],
+        )
+        .await;
+        let mut scan = table.new_stream_scan();
+        // nothing to scan before the first snapshot
+        let plan = scan.plan().await.unwrap();
+        assert!(plan.splits().is_empty());
+        assert_eq!(scan.checkpoint(), None);
+
+        write(
+            &table,
+            &[(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")],
+        )
+        .await;
+        // starts up with all the data of the latest snapshot
+        let plan = scan.plan().await.unwrap();
+        assert_eq!(plan.snapshot_id(), Some(1));
+        assert_eq!(
+            read(&table, &plan).await,
+            rows(&[(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")])
+        );
+        assert!(scan.plan().await.unwrap().splits().is_empty());
+        assert_eq!(scan.checkpoint(), Some(2));
+
+        let messages = write(&table, &[(RowKind::Insert, 3, "c")]).await;
+        // the compact snapshot is skipped
+        table
+            .new_batch_write_builder()
+            .new_commit()
+            .commit(vec![CommitMessage::new(
+                BinaryRow::new(0),
+                0,
+                DataIncrement::default(),
+                CompactIncrement {
+                    compact_before: messages[0].data_increment().new_files.clone(),
+                    compact_after: messages[0].data_increment().new_files.clone(),
+                    ..Default::default()
+                },
+            )])
+            .await
+            .unwrap();
+        write(&table, &[(RowKind::Insert, 4, "d")]).await;
+        let plan = scan.plan().await.unwrap();
+        assert_eq!(plan.snapshot_id(), Some(2));
+        assert!(plan.splits().iter().all(DataSplit::is_streaming));
+        assert_eq!(
+            read(&table, &plan).await,
+            rows(&[(RowKind::Insert, 3, "c")])
+        );
+        let plan = scan.plan().await.unwrap();
+        assert_eq!(plan.snapshot_id(), Some(4));
+        assert_eq!(
+            read(&table, &plan).await,
+            rows(&[(RowKind::Insert, 4, "d")])
+        );
+        assert_eq!(scan.checkpoint(), Some(5));
+
+        // restored scans follow the snapshots from the checkpoint
+        let mut scan = table.new_stream_scan();
+        scan.restore(4);
+        let plan = scan.plan().await.unwrap();
+        assert_eq!(plan.snapshot_id(), Some(4));
+
+        // expired snapshots fail the scan
+        let mut scan = table.new_stream_scan();
+        scan.restore(1);
+        let snapshot_manager = table.snapshot_manager();
+        table
+            .file_io()
+            .delete_file(&snapshot_manager.snapshot_path(1))
+            .await
+            .unwrap();
+        assert!(matches!(
+            scan.plan().await,
+            Err(crate::Error::SnapshotExpired {
+                snapshot_id: 1,
+                earliest: 2
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_scan_row_kind_column() {
+        let table = create_id_name_table(
+            "file:/tmp/test_stream_scan_row_kind_column",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("changelog-producer", "input"),
+                ("scan.mode", "latest"),
+            ],
+        )
+        .await;
+        write(&table, &[(RowKind::Insert, 1, "a")]).await;
+        let mut scan = table.new_stream_scan();
+        assert!(scan.plan().await.unwrap().splits().is_empty());
+        write(
+            &table,
+            &[
+                (RowKind::UpdateBefore, 1, "a"),
+                (RowKind::UpdateAfter, 1, "b"),
+            ],
+        )
+        .await;
+        let plan = scan.plan().await.unwrap();
+
+        let read = table
+            .new_read()
+            .with_projection(&["name"])
+            .with_row_kind_column("_row_kind");
+        let schema = read.arrow_schema().unwrap();
+        let names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(names, vec!["name", "_row_kind"]);
+        let mut rows = Vec::new();
+        for split in plan.splits() {
+            for batch in read.read(split).await.unwrap() {
+                let batch = batch.unwrap();
+                assert_eq!(batch.schema(), schema);
+                let names = batch.column(0).as_string::<i32>();
+                let row_kinds = batch.column(1).as_string::<i32>();
+                rows.extend(
+                    (0..batch.num_rows())
+                        .map(|i| (row_kinds.value(i).to_string(), names.value(i).to_string())),
+                );
+            }
+        }
+        assert_eq!(
+            rows,
+            vec![
+                ("-U".to_string(), "a".to_string()),
+                ("+U".to_string(), "b".to_string())
+            ]
+        );
+
+        // the row kind column cannot shadow the fields of the table
+        let read = table.new_read().with_row_kind_column("name");
+        assert!(matches!(
+            read.arrow_schema(),
+            Err(crate::Error::ColumnAlreadyExist { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_stream_scan_changelog() {
+        for producer in ["input", "lookup"] {
+            let table = create_id_name_table(
+                &format!("file:/tmp/test_stream_scan_changelog_{producer}"),
+                &["id"],
+                &[
+                    ("file.format", "parquet"),
+                    ("bucket", "1"),
+                    ("changelog-producer", producer),
+                    ("scan.mode", "latest"),
+                ],
+            )
+            .await;
+            write(
+                &table,
+                &[(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")],
+            )
+            .await;
+            // starts up after the latest snapshot
+            let mut scan = table.new_stream_scan();
+            assert!(scan.plan().await.unwrap().splits().is_empty());
+            assert_eq!(scan.checkpoint(), Some(2));
+
+            write(
+                &table,
+                &[(RowKind::UpdateAfter, 1, "a2"), (RowKind::Delete, 2, "b")],
+            )
+            .await;
+            let plan = scan.plan().await.unwrap();
+            let expected = if producer == "input" {
+                rows(&[(RowKind::UpdateAfter, 1, "a2"), (RowKind::Delete, 2, "b")])
+            } else {
+                rows(&[
+                    (RowKind::UpdateBefore, 1, "a"),
+                    (RowKind::UpdateAfter, 1, "a2"),
+                    (RowKind::Delete, 2, "b"),
+                ])
+            };
+            assert_eq!(read(&table, &plan).await, expected, "{producer}");
+            assert!(scan.plan().await.unwrap().splits().is_empty());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stream_scan_from_snapshot() {
+        let table = create_id_name_table(
+            "file:/tmp/test_stream_scan_from_snapshot",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("scan.mode", "from-snapshot-full"),
+                ("scan.snapshot-id", "1"),
+            ],
+        )
+        .await;
+        write(
+            &table,
+            &[(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")],
+        )
+        .await;
+        write(&table, &[(RowKind::Delete, 1, "a")]).await;
+        let mut scan = table.new_stream_scan();
+        // the merged rows of the snapshot, then the deltas of the following snapshots
+        let plan = scan.plan().await.unwrap();
+        assert_eq!(
+            read(&table, &plan).await,
+            rows(&[(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")])
+        );
+        let plan = scan.plan().await.unwrap();
+        assert_eq!(
+            read(&table, &plan).await,
+            rows(&[(RowKind::Delete, 1, "a")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_scan_consumer() {
+        let table = create_id_name_table(
+            "file:/tmp/test_stream_scan_consumer",
+            &[],
+            &[("file.format", "parquet"), ("consumer-id", "c")],
+        )
+        .await;
+        write(&table, &[(RowKind::Insert, 1, "a")]).await;
+        let mut scan = table.new_stream_scan();
+        assert_eq!(
+            read(&table, &scan.plan().await.unwrap()).await,
+            rows(&[(RowKind::Insert, 1, "a")])
+        );
+        write(&table, &[(RowKind::Insert, 2, "b")]).await;
+        scan.plan().await.unwrap();
+        scan.notify_checkpoint_complete(scan.checkpoint().unwrap())
+            .await
+            .unwrap();
+        let consumer_manager = table.consumer_manager();
+        assert_eq!(
+            consumer_manager.consumer("c").await.unwrap(),
+            Some(Consumer::new(3))
+        );
+
+        // new scans of the consumer start up from its next snapshot
+        write(&table, &[(RowKind::Insert, 3, "c")]).await;
+        let mut scan = table.new_stream_scan();
+        assert!(scan.plan().await.unwrap().splits().is_empty());
+        assert_eq!(
+            read(&table, &scan.plan().await.unwrap()).await,
+            rows(&[(RowKind::Insert, 3, "c")])
+        );
+
+        // commits expire the consumers not updated within the expiration time
+        let mut options = table.schema().options().clone();
+        options.insert("consumer.expiration-time".to_string(), "1 ms".to_string());
+        let table = FileStoreTable::new(
+            table.file_io().clone(),
+            Identifier::new("db", "t"),
+            table.location(),
+            table.schema().copy_with_options(options),
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        write(&table, &[(RowKind::Insert, 4, "d")]).await;
+        assert_eq!(consumer_manager.consumer("c").await.unwrap(), None);
+    }
+}
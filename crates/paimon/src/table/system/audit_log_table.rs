@@ -0,0 +1,209 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::{to_arrow_schema, ArrowRecordBatchIter};
+use crate::spec::{DataField, RowKind, RowType};
+use crate::table::system::{string_type, SystemTable};
+use crate::table::{DataSplit, FileStoreTable, StreamTableScan, Table, TableScan};
+use arrow_array::{ArrayRef, RecordBatch, StringArray};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Name of the system table of audit log.
+pub const AUDIT_LOG: &str = "audit_log";
+
+/// Name of the column of the row kinds of the rows in the audit log.
+pub const ROW_KIND: &str = "rowkind";
+
+/// A system table of the rows of a table with their row kinds like `+I` and `-D` in the
+/// `rowkind` column, ahead of the fields of the table.
+///
+/// A batch read reads the rows of the snapshot to scan as inserted ones, while the splits
+/// planned by the streaming scan are read with the row kinds of the changes, as in the
+/// changelog of the table.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/AuditLogTable.java>
+#[derive(Debug, Clone)]
+pub struct AuditLogTable {
+    table: FileStoreTable,
+}
+
+impl AuditLogTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+
+    /// Create a scan to plan the splits of the snapshot to read.
+    pub fn new_scan(&self) -> TableScan {
+        self.table.new_scan()
+    }
+
+    /// Create a streaming scan to plan the splits of the changes committed to the table.
+    pub fn new_stream_scan(&self) -> StreamTableScan {
+        self.table.new_stream_scan()
+    }
+
+    /// Read the rows of the split planned by the scans with their row kinds.
+    pub async fn read_split(&self, split: &DataSplit) -> crate::Result<ArrowRecordBatchIter> {
+        let batches = self.table.new_read().read_with_row_kinds(split).await?;
+        let schema = Arc::new(to_arrow_schema(self.row_type().fields()));
+        Ok(Box::new(batches.map(move |batch| {
+            let (batch, row_kinds) = batch?;
+            let row_kinds =
+                StringArray::from_iter_values(row_kinds.iter().map(RowKind::short_string));
+            let columns = std::iter::once(Arc::new(row_kinds) as ArrayRef)
+                .chain(batch.columns().iter().cloned())
+                .collect();
+            Ok(RecordBatch::try_new(schema.clone(), columns)?)
+        })))
+    }
+}
+
+#[async_trait]
+impl SystemTable for AuditLogTable {
+    fn name(&self) -> &str {
+        AUDIT_LOG
+    }
+
+    fn row_type(&self) -> RowType {
+        let mut fields = vec![DataField::new(0, ROW_KIND.to_string(), string_type(false))];
+        fields.extend(self.table.schema().fields().iter().cloned());
+        RowType::new(fields)
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let mut batches = Vec::new();
+        for split in self.new_scan().plan().await?.splits() {
+            batches.extend(self.read_split(split).await?);
+        }
+        Ok(Box::new(batches.into_iter()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::io::FileIOBuilder;
+    use crate::spec::Schema;
+    use crate::table::system_table;
+    use crate::utils::SchemaManager;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+    use arrow_array::Int32Array;
+
+    async fn write(table: &FileStoreTable, rows: &[(RowKind, i32, &str)]) {
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.1))),
+                Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.2))),
+            ],
+        )
+        .unwrap();
+        let row_kinds: Vec<RowKind> = rows.iter().map(|r| r.0).collect();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write
+            .write_with_row_kinds(&batch, &row_kinds)
+            .await
+            .unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+    }
+
+    /// Collect the (row kind, id, name) rows of the batches.
+    fn rows(batches: ArrowRecordBatchIter) -> Vec<(String, i32, String)> {
+        let mut rows = Vec::new();
+        for batch in batches {
+            let batch = batch.unwrap();
+            let row_kinds = batch.column(0).as_string::<i32>();
+            let ids = batch.column(1).as_primitive::<Int32Type>();
+            let names = batch.column(2).as_string::<i32>();
+            rows.extend((0..batch.num_rows()).map(|i| {
+                (
+                    row_kinds.value(i).to_string(),
+                    ids.value(i),
+                    names.value(i).to_string(),
+                )
+            }));
+        }
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_audit_log() {
+        let location = "file:/tmp/test_audit_log_table";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                        DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                    ])
+                    .primary_keys(vec!["id".to_string()])
+                    .options(
+                        [
+                            ("bucket".to_string(), "1".to_string()),
+                            ("changelog-producer".to_string(), "input".to_string()),
+                            ("file.format".to_string(), "parquet".to_string()),
+                        ]
+                        .into(),
+                    )
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema);
+        let audit_log = AuditLogTable::new(table.clone());
+        assert_eq!(
+            audit_log.row_type().field_names(),
+            vec![ROW_KIND, "id", "name"]
+        );
+        assert!(system_table(table.clone(), "AUDIT_LOG").is_some());
+
+        write(
+            &table,
+            &[(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")],
+        )
+        .await;
+        let mut scan = audit_log.new_stream_scan();
+        let plan = scan.plan().await.unwrap();
+        let row = |kind: &str, id, name: &str| (kind.to_string(), id, name.to_string());
+        assert_eq!(
+            rows(audit_log.read_split(&plan.splits()[0]).await.unwrap()),
+            vec![row("+I", 1, "a"), row("+I", 2, "b")]
+        );
+
+        write(
+            &table,
+            &[(RowKind::Delete, 1, "a"), (RowKind::Insert, 3, "c")],
+        )
+        .await;
+        let plan = scan.plan().await.unwrap();
+        assert_eq!(
+            rows(audit_log.read_split(&plan.splits()[0]).await.unwrap()),
+            vec![row("-D", 1, "a"), row("+I", 3, "c")]
+        );
+        assert_eq!(
+            rows(audit_log.read().await.unwrap()),
+            vec![row("+I", 2, "b"), row("+I", 3, "c")]
+        );
+    }
+}
@@ -0,0 +1,95 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::ArrowRecordBatchIter;
+use crate::spec::{BinaryRow, DataType, IntType, RowType};
+use crate::table::system::{
+    partition_string, read_columns, row_type, string_type, FilesStats, SystemTable,
+};
+use crate::table::{FileStoreTable, Table};
+use arrow_array::{ArrayRef, Int32Array, StringArray};
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use std::sync::Arc;
+
+/// Name of the system table of buckets.
+pub const BUCKETS: &str = "buckets";
+
+/// A system table of the buckets of the partitions of the snapshot to scan, with the numbers
+/// of records, bytes and files of them, to inspect the skew of data among buckets.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/BucketsTable.java>
+#[derive(Debug, Clone)]
+pub struct BucketsTable {
+    table: FileStoreTable,
+}
+
+impl BucketsTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+}
+
+#[async_trait]
+impl SystemTable for BucketsTable {
+    fn name(&self) -> &str {
+        BUCKETS
+    }
+
+    fn row_type(&self) -> RowType {
+        let mut fields = vec![
+            ("partition", string_type(true)),
+            ("bucket", DataType::Int(IntType::with_nullable(false))),
+        ];
+        fields.extend(FilesStats::fields());
+        row_type(fields)
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let scan = self.table.new_scan();
+        let entries = match scan.snapshot().await? {
+            Some(snapshot) => scan.data_files(&snapshot).await?,
+            None => vec![],
+        };
+        let mut buckets: IndexMap<(&[u8], i32), FilesStats> = IndexMap::new();
+        for entry in &entries {
+            buckets
+                .entry((entry.partition(), entry.bucket()))
+                .or_default()
+                .add(entry.file());
+        }
+
+        let partition_type = self.table.schema().logical_partition_type();
+        let partitions = buckets
+            .keys()
+            .map(|(partition, _)| {
+                partition_string(
+                    &BinaryRow::from_serialized_bytes(partition)?,
+                    &partition_type,
+                )
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(partitions)),
+            Arc::new(Int32Array::from_iter_values(
+                buckets.keys().map(|(_, bucket)| *bucket),
+            )),
+        ];
+        columns.extend(FilesStats::columns(buckets.values()));
+        read_columns(&self.row_type(), columns)
+    }
+}
@@ -0,0 +1,70 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::ArrowRecordBatchIter;
+use crate::spec::{BigIntType, DataType, RowType};
+use crate::table::system::{read_columns, row_type, string_type, SystemTable};
+use crate::table::FileStoreTable;
+use arrow_array::{ArrayRef, Int64Array, StringArray};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Name of the system table of consumers.
+pub const CONSUMERS: &str = "consumers";
+
+/// A system table of the consumers registered of the table, with the ids of the next
+/// snapshots they are going to consume, to inspect the progress of stream reading.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/ConsumersTable.java>
+#[derive(Debug, Clone)]
+pub struct ConsumersTable {
+    table: FileStoreTable,
+}
+
+impl ConsumersTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+}
+
+#[async_trait]
+impl SystemTable for ConsumersTable {
+    fn name(&self) -> &str {
+        CONSUMERS
+    }
+
+    fn row_type(&self) -> RowType {
+        row_type(vec![
+            ("consumer_id", string_type(false)),
+            (
+                "next_snapshot_id",
+                DataType::BigInt(BigIntType::with_nullable(false)),
+            ),
+        ])
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let consumers = self.table.consumer_manager().consumers().await?;
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from_iter_values(consumers.keys())),
+            Arc::new(Int64Array::from_iter_values(
+                consumers.values().map(|consumer| consumer.next_snapshot()),
+            )),
+        ];
+        read_columns(&self.row_type(), columns)
+    }
+}
@@ -0,0 +1,115 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::ArrowRecordBatchIter;
+use crate::spec::{BigIntType, BinaryRow, DataType, IntType, RowType};
+use crate::table::system::{
+    partition_string, read_columns, row_type, string_type, timestamp_type, SystemTable,
+};
+use crate::table::{FileStoreTable, Table};
+use arrow_array::{ArrayRef, Int32Array, Int64Array, StringArray, TimestampMillisecondArray};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Name of the system table of files.
+pub const FILES: &str = "files";
+
+/// A system table of the data files of the snapshot to scan, which is the latest one unless
+/// the options of the table travel to another one.
+///
+/// The partitions are the values of the partition keys like `[2024-01-01, 10]`.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/FilesTable.java>
+#[derive(Debug, Clone)]
+pub struct FilesTable {
+    table: FileStoreTable,
+}
+
+impl FilesTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+}
+
+#[async_trait]
+impl SystemTable for FilesTable {
+    fn name(&self) -> &str {
+        FILES
+    }
+
+    fn row_type(&self) -> RowType {
+        let int = || DataType::Int(IntType::with_nullable(false));
+        let bigint = |nullable| DataType::BigInt(BigIntType::with_nullable(nullable));
+        row_type(vec![
+            ("partition", string_type(true)),
+            ("bucket", int()),
+            ("file_path", string_type(false)),
+            ("file_format", string_type(false)),
+            ("schema_id", bigint(false)),
+            ("level", int()),
+            ("record_count", bigint(false)),
+            ("file_size_in_bytes", bigint(false)),
+            ("min_sequence_number", bigint(true)),
+            ("max_sequence_number", bigint(true)),
+            ("creation_time", timestamp_type(true)),
+        ])
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let scan = self.table.new_scan();
+        let entries = match scan.snapshot().await? {
+            Some(snapshot) => scan.data_files(&snapshot).await?,
+            None => vec![],
+        };
+        let path_factory = self.table.path_factory();
+        let partition_type = self.table.schema().logical_partition_type();
+        let default_format = self.table.core_options().file_format();
+        let mut partitions = Vec::with_capacity(entries.len());
+        let mut file_paths = Vec::with_capacity(entries.len());
+        for entry in &entries {
+            let partition = BinaryRow::from_serialized_bytes(entry.partition())?;
+            partitions.push(partition_string(&partition, &partition_type)?);
+            let bucket_path = path_factory.bucket_path(&partition, entry.bucket())?;
+            file_paths.push(format!("{bucket_path}/{}", entry.file().file_name));
+        }
+        let files = || entries.iter().map(|entry| entry.file());
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from(partitions)),
+            Arc::new(Int32Array::from_iter_values(
+                entries.iter().map(|entry| entry.bucket()),
+            )),
+            Arc::new(StringArray::from(file_paths)),
+            Arc::new(StringArray::from_iter_values(
+                files().map(|file| file.file_format().unwrap_or(&default_format)),
+            )),
+            Arc::new(Int64Array::from_iter_values(files().map(|f| f.schema_id))),
+            Arc::new(Int32Array::from_iter_values(files().map(|f| f.level))),
+            Arc::new(Int64Array::from_iter_values(files().map(|f| f.row_count))),
+            Arc::new(Int64Array::from_iter_values(files().map(|f| f.file_size))),
+            Arc::new(Int64Array::from_iter_values(
+                files().map(|f| f.min_sequence_number),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                files().map(|f| f.max_sequence_number),
+            )),
+            Arc::new(TimestampMillisecondArray::from_iter_values(
+                files().map(|f| f.creation_time.timestamp_millis()),
+            )),
+        ];
+        read_columns(&self.row_type(), columns)
+    }
+}
@@ -0,0 +1,97 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::ArrowRecordBatchIter;
+use crate::manifest::ManifestList;
+use crate::spec::{BigIntType, DataType, RowType};
+use crate::table::system::{read_columns, row_type, string_type, SystemTable};
+use crate::table::{FileStoreTable, Table};
+use arrow_array::{ArrayRef, Int64Array, StringArray};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Name of the system table of manifests.
+pub const MANIFESTS: &str = "manifests";
+
+/// A system table of the manifest files of the base and delta manifest lists of the snapshot
+/// to scan, which is the latest one unless the options of the table travel to another one.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/ManifestsTable.java>
+#[derive(Debug, Clone)]
+pub struct ManifestsTable {
+    table: FileStoreTable,
+}
+
+impl ManifestsTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+}
+
+#[async_trait]
+impl SystemTable for ManifestsTable {
+    fn name(&self) -> &str {
+        MANIFESTS
+    }
+
+    fn row_type(&self) -> RowType {
+        let bigint = || DataType::BigInt(BigIntType::with_nullable(false));
+        row_type(vec![
+            ("file_name", string_type(false)),
+            ("file_size", bigint()),
+            ("num_added_files", bigint()),
+            ("num_deleted_files", bigint()),
+            ("schema_id", bigint()),
+        ])
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let mut manifests = Vec::new();
+        if let Some(snapshot) = self.table.new_scan().snapshot().await? {
+            let manifest_list = ManifestList::new(self.table.file_io().clone());
+            let path_factory = self.table.path_factory();
+            for list in [
+                snapshot.base_manifest_list(),
+                snapshot.delta_manifest_list(),
+            ] {
+                manifests.extend(
+                    manifest_list
+                        .read(&path_factory.manifest_path(list))
+                        .await?,
+                );
+            }
+        }
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from_iter_values(
+                manifests.iter().map(|m| m.file_name()),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                manifests.iter().map(|m| m.file_size()),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                manifests.iter().map(|m| m.num_added_files()),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                manifests.iter().map(|m| m.num_deleted_files()),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                manifests.iter().map(|m| m.schema_id()),
+            )),
+        ];
+        read_columns(&self.row_type(), columns)
+    }
+}
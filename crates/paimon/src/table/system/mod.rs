@@ -0,0 +1,351 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! System tables of paimon, the read-only tables of the metadata of a table, which are named
+//! after the table and the name of the system table like `table$snapshots`.
+
+mod audit_log_table;
+pub use audit_log_table::*;
+
+mod buckets_table;
+pub use buckets_table::*;
+
+mod consumers_table;
+pub use consumers_table::*;
+
+mod files_table;
+pub use files_table::*;
+
+mod manifests_table;
+pub use manifests_table::*;
+
+mod options_table;
+pub use options_table::*;
+
+mod partitions_table;
+pub use partitions_table::*;
+
+mod schemas_table;
+pub use schemas_table::*;
+
+mod snapshots_table;
+pub use snapshots_table::*;
+
+mod tags_table;
+pub use tags_table::*;
+
+use crate::format::{to_arrow_schema, ArrowRecordBatchIter};
+use crate::spec::{
+    BigIntType, BinaryRow, DataField, DataFileMeta, DataType, RowType, TimestampType, VarCharType,
+};
+use crate::table::FileStoreTable;
+use crate::utils::partition_value_string;
+use arrow_array::{ArrayRef, Int64Array, RecordBatch, TimestampMillisecondArray};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Splitter of the name of the table and the name of its system table.
+pub const SYSTEM_TABLE_SPLITTER: &str = "$";
+
+/// A read-only table of the metadata of a [`FileStoreTable`].
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/ReadonlyTable.java>
+#[async_trait]
+pub trait SystemTable: Send + Sync {
+    /// Get the name of this system table, like `snapshots`.
+    fn name(&self) -> &str;
+
+    /// Get the row type of this system table.
+    fn row_type(&self) -> RowType;
+
+    /// Read all the rows of this system table.
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter>;
+}
+
+/// Create the system table of the name over the table, none if there is no such system table.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/SystemTableLoader.java>
+pub fn system_table(table: FileStoreTable, name: &str) -> Option<Box<dyn SystemTable>> {
+    let system_table: Box<dyn SystemTable> = match name.to_lowercase().as_str() {
+        SNAPSHOTS => Box::new(SnapshotsTable::new(table)),
+        SCHEMAS => Box::new(SchemasTable::new(table)),
+        OPTIONS => Box::new(OptionsTable::new(table)),
+        FILES => Box::new(FilesTable::new(table)),
+        MANIFESTS => Box::new(ManifestsTable::new(table)),
+        TAGS => Box::new(TagsTable::new(table)),
+        AUDIT_LOG => Box::new(AuditLogTable::new(table)),
+        PARTITIONS => Box::new(PartitionsTable::new(table)),
+        BUCKETS => Box::new(BucketsTable::new(table)),
+        CONSUMERS => Box::new(ConsumersTable::new(table)),
+        _ => return None,
+    };
+    Some(system_table)
+}
+
+/// Create the row type of the named fields, the ids of the fields are their positions.
+fn row_type(fields: Vec<(&str, DataType)>) -> RowType {
+    RowType::new(
+        fields
+            .into_iter()
+            .enumerate()
+            .map(|(id, (name, data_type))| DataField::new(id as i32, name.to_string(), data_type))
+            .collect(),
+    )
+}
+
+fn string_type(nullable: bool) -> DataType {
+    DataType::VarChar(VarCharType::with_nullable(nullable, VarCharType::MAX_LENGTH).unwrap())
+}
+
+fn timestamp_type(nullable: bool) -> DataType {
+    DataType::Timestamp(TimestampType::with_nullable(nullable, 3).unwrap())
+}
+
+/// Format the values of the partition like `[2024-01-01, 10]`.
+fn partition_string(partition: &BinaryRow, partition_type: &RowType) -> crate::Result<String> {
+    let mut values = Vec::with_capacity(partition_type.fields().len());
+    for (pos, field) in partition_type.fields().iter().enumerate() {
+        values.push(match partition.get_datum(pos, field.data_type())? {
+            Some(datum) => partition_value_string(&datum),
+            None => "null".to_string(),
+        });
+    }
+    Ok(format!("[{}]", values.join(", ")))
+}
+
+/// Stats of the data files of a partition or a bucket.
+#[derive(Debug, Default)]
+struct FilesStats {
+    record_count: i64,
+    file_size_in_bytes: i64,
+    file_count: i64,
+    last_update_time: i64,
+}
+
+impl FilesStats {
+    fn add(&mut self, file: &DataFileMeta) {
+        self.record_count += file.row_count;
+        self.file_size_in_bytes += file.file_size;
+        self.file_count += 1;
+        self.last_update_time = self
+            .last_update_time
+            .max(file.creation_time.timestamp_millis());
+    }
+
+    fn fields() -> Vec<(&'static str, DataType)> {
+        let bigint = || DataType::BigInt(BigIntType::with_nullable(false));
+        vec![
+            ("record_count", bigint()),
+            ("file_size_in_bytes", bigint()),
+            ("file_count", bigint()),
+            ("last_update_time", timestamp_type(true)),
+        ]
+    }
+
+    fn columns<'a>(stats: impl Iterator<Item = &'a FilesStats> + Clone) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(Int64Array::from_iter_values(
+                stats.clone().map(|s| s.record_count),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                stats.clone().map(|s| s.file_size_in_bytes),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                stats.clone().map(|s| s.file_count),
+            )),
+            Arc::new(TimestampMillisecondArray::from_iter_values(
+                stats.map(|s| s.last_update_time),
+            )),
+        ]
+    }
+}
+
+/// Read the columns of the rows as a single record batch of the row type.
+fn read_columns(row_type: &RowType, columns: Vec<ArrayRef>) -> crate::Result<ArrowRecordBatchIter> {
+    let batch = RecordBatch::try_new(Arc::new(to_arrow_schema(row_type.fields())), columns)?;
+    Ok(Box::new(std::iter::once(Ok(batch))))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::catalog::{Catalog, FileSystemCatalog, Identifier};
+    use crate::io::FileIOBuilder;
+    use crate::spec::{Consumer, DataField, DataType, IntType, Schema};
+    use crate::table::{FileStoreTable, Table};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Int32Type, Int64Type};
+    use arrow_array::{Int32Array, RecordBatch};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    async fn write(table: &FileStoreTable, rows: &[(i32, i32)]) {
+        let batch = RecordBatch::try_new(
+            Arc::new(crate::format::to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.0))),
+                Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.1))),
+            ],
+        )
+        .unwrap();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write.write(&batch).await.unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+    }
+
+    async fn read(catalog: &FileSystemCatalog, name: &str) -> RecordBatch {
+        let table = catalog
+            .get_system_table(&Identifier::new("db", format!("t${name}")))
+            .await
+            .unwrap();
+        assert_eq!(table.name(), name);
+        let batches = table
+            .read()
+            .await
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+        let batch = batches.into_iter().next().unwrap();
+        assert_eq!(
+            batch.schema().fields().len(),
+            table.row_type().fields().len()
+        );
+        batch
+    }
+
+    #[tokio::test]
+    async fn test_system_tables() {
+        let warehouse = "file:/tmp/test_system_tables";
+        let file_io = FileIOBuilder::from_url(warehouse).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{warehouse}/")).await;
+        let catalog = FileSystemCatalog::new(file_io, warehouse);
+        catalog
+            .create_database("db", false, HashMap::new())
+            .await
+            .unwrap();
+        let identifier = Identifier::new("db", "t");
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "dt".to_string(), DataType::Int(IntType::new())),
+                DataField::new(1, "id".to_string(), DataType::Int(IntType::new())),
+            ])
+            .partition_keys(vec!["dt".to_string()])
+            .options([("file.format".to_string(), "parquet".to_string())].into())
+            .build();
+        catalog
+            .create_table(&identifier, schema, false)
+            .await
+            .unwrap();
+        let table = catalog.get_table(&identifier).await.unwrap();
+        write(&table, &[(1, 1), (2, 2)]).await;
+        write(&table, &[(1, 3)]).await;
+        table.create_tag("t1", 1).await.unwrap();
+
+        let snapshots = read(&catalog, "snapshots").await;
+        let ids = snapshots.column(0).as_primitive::<Int64Type>();
+        assert_eq!(ids.values(), &[1, 2]);
+        assert_eq!(snapshots.column(4).as_string::<i32>().value(0), "APPEND");
+
+        let schemas = read(&catalog, "schemas").await;
+        assert_eq!(schemas.num_rows(), 1);
+        assert_eq!(schemas.column(2).as_string::<i32>().value(0), r#"["dt"]"#);
+
+        let options = read(&catalog, "options").await;
+        assert_eq!(options.column(0).as_string::<i32>().value(0), "file.format");
+        assert_eq!(options.column(1).as_string::<i32>().value(0), "parquet");
+
+        let files = read(&catalog, "files").await;
+        let mut partitions: Vec<_> = files.column(0).as_string::<i32>().iter().collect();
+        partitions.sort();
+        assert_eq!(partitions, vec![Some("[1]"), Some("[1]"), Some("[2]")]);
+        assert!(files.column(2).as_string::<i32>().value(0).contains("/dt="));
+        assert_eq!(files.column(3).as_string::<i32>().value(0), "parquet");
+        let record_count: i64 = files
+            .column(6)
+            .as_primitive::<Int64Type>()
+            .values()
+            .iter()
+            .sum();
+        assert_eq!(record_count, 3);
+        let buckets = files.column(1).as_primitive::<Int32Type>();
+        assert!(buckets.values().iter().all(|bucket| *bucket == 0));
+
+        let manifests = read(&catalog, "manifests").await;
+        let added: i64 = manifests
+            .column(2)
+            .as_primitive::<Int64Type>()
+            .values()
+            .iter()
+            .sum();
+        assert_eq!(added, 3);
+
+        let partitions = read(&catalog, "partitions").await;
+        let mut rows: Vec<_> = (0..partitions.num_rows())
+            .map(|i| {
+                (
+                    partitions.column(0).as_string::<i32>().value(i),
+                    partitions.column(1).as_primitive::<Int64Type>().value(i),
+                    partitions.column(3).as_primitive::<Int64Type>().value(i),
+                )
+            })
+            .collect();
+        rows.sort();
+        assert_eq!(rows, vec![("[1]", 2, 2), ("[2]", 1, 1)]);
+        assert!(partitions.column(2).as_primitive::<Int64Type>().value(0) > 0);
+        assert!(!partitions.column(4).is_null(0));
+
+        let buckets = read(&catalog, "buckets").await;
+        assert_eq!(buckets.num_rows(), 2);
+        assert!(buckets
+            .column(1)
+            .as_primitive::<Int32Type>()
+            .values()
+            .iter()
+            .all(|bucket| *bucket == 0));
+        let file_count: i64 = buckets
+            .column(4)
+            .as_primitive::<Int64Type>()
+            .values()
+            .iter()
+            .sum();
+        assert_eq!(file_count, 3);
+
+        table
+            .consumer_manager()
+            .reset_consumer("c1", Consumer::new(2))
+            .await
+            .unwrap();
+        let consumers = read(&catalog, "consumers").await;
+        assert_eq!(consumers.column(0).as_string::<i32>().value(0), "c1");
+        assert_eq!(consumers.column(1).as_primitive::<Int64Type>().value(0), 2);
+
+        let tags = read(&catalog, "tags").await;
+        assert_eq!(tags.column(0).as_string::<i32>().value(0), "t1");
+        assert_eq!(tags.column(1).as_primitive::<Int64Type>().value(0), 1);
+
+        for missing in ["t$unknown", "t", "unknown$snapshots"] {
+            assert!(matches!(
+                catalog
+                    .get_system_table(&Identifier::new("db", missing))
+                    .await,
+                Err(crate::Error::TableNotExist { .. })
+            ));
+        }
+    }
+}
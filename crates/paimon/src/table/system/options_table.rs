@@ -0,0 +1,68 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::ArrowRecordBatchIter;
+use crate::spec::RowType;
+use crate::table::system::{read_columns, row_type, string_type, SystemTable};
+use crate::table::FileStoreTable;
+use arrow_array::{ArrayRef, StringArray};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Name of the system table of options.
+pub const OPTIONS: &str = "options";
+
+/// A system table of the options of the latest schema of a table, in the order of the keys.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/OptionsTable.java>
+#[derive(Debug, Clone)]
+pub struct OptionsTable {
+    table: FileStoreTable,
+}
+
+impl OptionsTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+}
+
+#[async_trait]
+impl SystemTable for OptionsTable {
+    fn name(&self) -> &str {
+        OPTIONS
+    }
+
+    fn row_type(&self) -> RowType {
+        row_type(vec![
+            ("key", string_type(false)),
+            ("value", string_type(false)),
+        ])
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let options: BTreeMap<String, String> = match self.table.schema_manager().latest().await? {
+            Some(schema) => schema.options().clone().into_iter().collect(),
+            None => BTreeMap::new(),
+        };
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from_iter_values(options.keys())),
+            Arc::new(StringArray::from_iter_values(options.values())),
+        ];
+        read_columns(&self.row_type(), columns)
+    }
+}
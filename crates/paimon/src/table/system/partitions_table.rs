@@ -0,0 +1,87 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::ArrowRecordBatchIter;
+use crate::spec::{BinaryRow, RowType};
+use crate::table::system::{
+    partition_string, read_columns, row_type, string_type, FilesStats, SystemTable,
+};
+use crate::table::{FileStoreTable, Table};
+use arrow_array::{ArrayRef, StringArray};
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use std::sync::Arc;
+
+/// Name of the system table of partitions.
+pub const PARTITIONS: &str = "partitions";
+
+/// A system table of the partitions of the snapshot to scan, with the numbers of records,
+/// bytes and files of them, and the last time their files were created.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/PartitionsTable.java>
+#[derive(Debug, Clone)]
+pub struct PartitionsTable {
+    table: FileStoreTable,
+}
+
+impl PartitionsTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+}
+
+#[async_trait]
+impl SystemTable for PartitionsTable {
+    fn name(&self) -> &str {
+        PARTITIONS
+    }
+
+    fn row_type(&self) -> RowType {
+        let mut fields = vec![("partition", string_type(true))];
+        fields.extend(FilesStats::fields());
+        row_type(fields)
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let scan = self.table.new_scan();
+        let entries = match scan.snapshot().await? {
+            Some(snapshot) => scan.data_files(&snapshot).await?,
+            None => vec![],
+        };
+        let mut partitions: IndexMap<&[u8], FilesStats> = IndexMap::new();
+        for entry in &entries {
+            partitions
+                .entry(entry.partition())
+                .or_default()
+                .add(entry.file());
+        }
+
+        let partition_type = self.table.schema().logical_partition_type();
+        let names = partitions
+            .keys()
+            .map(|partition| {
+                partition_string(
+                    &BinaryRow::from_serialized_bytes(partition)?,
+                    &partition_type,
+                )
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        let mut columns: Vec<ArrayRef> = vec![Arc::new(StringArray::from(names))];
+        columns.extend(FilesStats::columns(partitions.values()));
+        read_columns(&self.row_type(), columns)
+    }
+}
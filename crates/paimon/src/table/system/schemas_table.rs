@@ -0,0 +1,94 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::ArrowRecordBatchIter;
+use crate::spec::{BigIntType, DataType, RowType};
+use crate::table::system::{read_columns, row_type, string_type, timestamp_type, SystemTable};
+use crate::table::FileStoreTable;
+use arrow_array::{ArrayRef, Int64Array, StringArray, TimestampMillisecondArray};
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Name of the system table of schemas.
+pub const SCHEMAS: &str = "schemas";
+
+/// A system table of the schemas of a table, in the order of their ids.
+///
+/// The fields, the keys and the options of the schemas are in json.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/SchemasTable.java>
+#[derive(Debug, Clone)]
+pub struct SchemasTable {
+    table: FileStoreTable,
+}
+
+impl SchemasTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+}
+
+#[async_trait]
+impl SystemTable for SchemasTable {
+    fn name(&self) -> &str {
+        SCHEMAS
+    }
+
+    fn row_type(&self) -> RowType {
+        row_type(vec![
+            (
+                "schema_id",
+                DataType::BigInt(BigIntType::with_nullable(false)),
+            ),
+            ("fields", string_type(false)),
+            ("partition_keys", string_type(false)),
+            ("primary_keys", string_type(false)),
+            ("options", string_type(false)),
+            ("comment", string_type(true)),
+            ("update_time", timestamp_type(false)),
+        ])
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let schemas = self.table.schema_manager().list_all().await?;
+        let mut fields = Vec::with_capacity(schemas.len());
+        let mut partition_keys = Vec::with_capacity(schemas.len());
+        let mut primary_keys = Vec::with_capacity(schemas.len());
+        let mut options = Vec::with_capacity(schemas.len());
+        for schema in &schemas {
+            fields.push(serde_json::to_string(schema.fields())?);
+            partition_keys.push(serde_json::to_string(schema.partition_keys())?);
+            primary_keys.push(serde_json::to_string(schema.primary_keys())?);
+            options.push(serde_json::to_string(
+                &schema.options().iter().collect::<BTreeMap<_, _>>(),
+            )?);
+        }
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from_iter_values(schemas.iter().map(|s| s.id()))),
+            Arc::new(StringArray::from(fields)),
+            Arc::new(StringArray::from(partition_keys)),
+            Arc::new(StringArray::from(primary_keys)),
+            Arc::new(StringArray::from(options)),
+            Arc::new(StringArray::from_iter(schemas.iter().map(|s| s.comment()))),
+            Arc::new(TimestampMillisecondArray::from_iter_values(
+                schemas.iter().map(|s| s.time_millis()),
+            )),
+        ];
+        read_columns(&self.row_type(), columns)
+    }
+}
@@ -0,0 +1,113 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::ArrowRecordBatchIter;
+use crate::spec::{BigIntType, DataType, RowType};
+use crate::table::system::{read_columns, row_type, string_type, timestamp_type, SystemTable};
+use crate::table::FileStoreTable;
+use arrow_array::{ArrayRef, Int64Array, StringArray, TimestampMillisecondArray};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Name of the system table of snapshots.
+pub const SNAPSHOTS: &str = "snapshots";
+
+/// A system table of the snapshots of a table, in the order of their ids.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/SnapshotsTable.java>
+#[derive(Debug, Clone)]
+pub struct SnapshotsTable {
+    table: FileStoreTable,
+}
+
+impl SnapshotsTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+}
+
+#[async_trait]
+impl SystemTable for SnapshotsTable {
+    fn name(&self) -> &str {
+        SNAPSHOTS
+    }
+
+    fn row_type(&self) -> RowType {
+        let bigint = |nullable| DataType::BigInt(BigIntType::with_nullable(nullable));
+        row_type(vec![
+            ("snapshot_id", bigint(false)),
+            ("schema_id", bigint(false)),
+            ("commit_user", string_type(false)),
+            ("commit_identifier", bigint(false)),
+            ("commit_kind", string_type(false)),
+            ("commit_time", timestamp_type(false)),
+            ("base_manifest_list", string_type(false)),
+            ("delta_manifest_list", string_type(false)),
+            ("changelog_manifest_list", string_type(true)),
+            ("total_record_count", bigint(true)),
+            ("delta_record_count", bigint(true)),
+            ("changelog_record_count", bigint(true)),
+            ("watermark", bigint(true)),
+        ])
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let snapshots = self.table.snapshot_manager().snapshots().await?;
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(Int64Array::from_iter_values(
+                snapshots.iter().map(|s| s.id()),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                snapshots.iter().map(|s| s.schema_id()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                snapshots.iter().map(|s| s.commit_user()),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                snapshots.iter().map(|s| s.commit_identifier()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                snapshots.iter().map(|s| format!("{:?}", s.commit_kind())),
+            )),
+            Arc::new(TimestampMillisecondArray::from_iter_values(
+                snapshots.iter().map(|s| s.time_millis() as i64),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                snapshots.iter().map(|s| s.base_manifest_list()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                snapshots.iter().map(|s| s.delta_manifest_list()),
+            )),
+            Arc::new(StringArray::from_iter(
+                snapshots.iter().map(|s| s.changelog_manifest_list()),
+            )),
+            Arc::new(Int64Array::from_iter(
+                snapshots.iter().map(|s| s.total_record_count()),
+            )),
+            Arc::new(Int64Array::from_iter(
+                snapshots.iter().map(|s| s.delta_record_count()),
+            )),
+            Arc::new(Int64Array::from_iter(
+                snapshots.iter().map(|s| s.changelog_record_count()),
+            )),
+            Arc::new(Int64Array::from_iter(
+                snapshots.iter().map(|s| s.watermark()),
+            )),
+        ];
+        read_columns(&self.row_type(), columns)
+    }
+}
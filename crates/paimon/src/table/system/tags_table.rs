@@ -0,0 +1,84 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::ArrowRecordBatchIter;
+use crate::spec::{BigIntType, DataType, RowType};
+use crate::table::system::{read_columns, row_type, string_type, timestamp_type, SystemTable};
+use crate::table::FileStoreTable;
+use arrow_array::{ArrayRef, Int64Array, StringArray, TimestampMillisecondArray};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// Name of the system table of tags.
+pub const TAGS: &str = "tags";
+
+/// A system table of the tags of a table and the snapshots they tag, in the order of the
+/// snapshot ids.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/system/TagsTable.java>
+#[derive(Debug, Clone)]
+pub struct TagsTable {
+    table: FileStoreTable,
+}
+
+impl TagsTable {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+}
+
+#[async_trait]
+impl SystemTable for TagsTable {
+    fn name(&self) -> &str {
+        TAGS
+    }
+
+    fn row_type(&self) -> RowType {
+        let bigint = |nullable| DataType::BigInt(BigIntType::with_nullable(nullable));
+        row_type(vec![
+            ("tag_name", string_type(false)),
+            ("snapshot_id", bigint(false)),
+            ("schema_id", bigint(false)),
+            ("commit_time", timestamp_type(false)),
+            ("record_count", bigint(true)),
+        ])
+    }
+
+    async fn read(&self) -> crate::Result<ArrowRecordBatchIter> {
+        let tags = self.table.tag_manager().tags().await?;
+        let columns: Vec<ArrayRef> = vec![
+            Arc::new(StringArray::from_iter_values(
+                tags.iter().map(|(name, _)| name),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                tags.iter().map(|(_, snapshot)| snapshot.id()),
+            )),
+            Arc::new(Int64Array::from_iter_values(
+                tags.iter().map(|(_, snapshot)| snapshot.schema_id()),
+            )),
+            Arc::new(TimestampMillisecondArray::from_iter_values(
+                tags.iter()
+                    .map(|(_, snapshot)| snapshot.time_millis() as i64),
+            )),
+            Arc::new(Int64Array::from_iter(
+                tags.iter()
+                    .map(|(_, snapshot)| snapshot.total_record_count()),
+            )),
+        ];
+        read_columns(&self.row_type(), columns)
+    }
+}
@@ -0,0 +1,342 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::format::to_datum;
+use crate::spec::{ColStats, DataField, DataType, Datum, Statistics};
+use crate::table::{BatchWriteBuilder, FileStoreTable, TableCommit, TableRead, TableScan};
+use crate::utils::HyperLogLog;
+use arrow_array::Array;
+use std::collections::HashMap;
+
+/// An analysis of [`FileStoreTable`] computing the statistics of the columns of the latest
+/// snapshot: the number of distinct values estimated by HyperLogLog, the number of nulls,
+/// the min and max values, and the average and max lengths of the strings and binaries.
+///
+/// The rows are read merged by key, all the columns are analyzed by default. Only the null
+/// counts of the columns of nested types are computed. The statistics are committed into a
+/// snapshot of kind `ANALYZE`.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-flink/paimon-flink-common/src/main/java/org/apache/paimon/flink/procedure/AnalyzeProcedure.java>
+#[derive(Debug, Clone)]
+pub struct TableAnalyze {
+    table: FileStoreTable,
+    commit_user: String,
+    columns: Vec<String>,
+}
+
+impl TableAnalyze {
+    pub fn new(table: FileStoreTable, commit_user: impl Into<String>) -> Self {
+        Self {
+            table,
+            commit_user: commit_user.into(),
+            columns: vec![],
+        }
+    }
+
+    /// Only analyze the columns of the given names.
+    pub fn with_columns(mut self, columns: &[&str]) -> Self {
+        self.columns = columns.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    /// Get the table to analyze.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the user committing the statistics.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Compute the statistics of the latest snapshot and commit them, nothing is committed if
+    /// the table has no snapshot.
+    pub async fn analyze(&self) -> crate::Result<Option<Statistics>> {
+        let Some(statistics) = self.compute().await? else {
+            return Ok(None);
+        };
+        TableCommit::new(
+            self.table.clone(),
+            self.commit_user.clone(),
+            BatchWriteBuilder::COMMIT_IDENTIFIER,
+        )
+        .commit_statistics(&statistics)
+        .await?;
+        Ok(Some(statistics))
+    }
+
+    /// Compute the statistics of the latest snapshot without committing them, none if the
+    /// table has no snapshot.
+    pub async fn compute(&self) -> crate::Result<Option<Statistics>> {
+        let Some(snapshot) = self.table.snapshot_manager().latest_snapshot().await? else {
+            return Ok(None);
+        };
+        let fields: Vec<&DataField> = if self.columns.is_empty() {
+            self.table.schema().fields().iter().collect()
+        } else {
+            let columns: Vec<&str> = self.columns.iter().map(String::as_str).collect();
+            // unknown columns are rejected by the projection of the read
+            TableRead::new(self.table.clone())
+                .with_projection(&columns)
+                .read_type()?;
+            self.columns
+                .iter()
+                .filter_map(|name| {
+                    self.table
+                        .schema()
+                        .fields()
+                        .iter()
+                        .find(|f| f.name() == name)
+                })
+                .collect()
+        };
+        let names: Vec<&str> = fields.iter().map(|f| f.name()).collect();
+        let read = TableRead::new(self.table.clone()).with_projection(&names);
+        let plan = TableScan::new(self.table.clone())
+            .as_of_snapshot(snapshot.id())
+            .plan()
+            .await?;
+
+        let mut collectors: Vec<ColumnCollector> = fields
+            .iter()
+            .map(|field| ColumnCollector::new(field.data_type()))
+            .collect();
+        let mut record_count = 0;
+        let mut record_size = 0;
+        for split in plan.splits() {
+            record_size += split
+                .data_files()
+                .iter()
+                .map(|file| file.file_size)
+                .sum::<i64>();
+            for batch in read.read(split).await? {
+                let batch = batch?;
+                record_count += batch.num_rows() as i64;
+                for (collector, column) in collectors.iter_mut().zip(batch.columns()) {
+                    collector.collect(column.as_ref())?;
+                }
+            }
+        }
+
+        let col_stats = fields
+            .iter()
+            .zip(collectors)
+            .map(|(field, collector)| (field.name().to_string(), collector.finish(field.id())))
+            .collect::<HashMap<_, _>>();
+        Ok(Some(
+            Statistics::builder()
+                .snapshot_id(snapshot.id())
+                .schema_id(self.table.schema().id())
+                .merged_record_count(Some(record_count))
+                .merged_record_size(Some(record_size))
+                .col_stats(col_stats)
+                .build(),
+        ))
+    }
+}
+
+/// Collector of the statistics of the values of a column.
+struct ColumnCollector {
+    data_type: DataType,
+    null_count: i64,
+    /// The sketch of the distinct values, none for nested types.
+    distinct: Option<HyperLogLog>,
+    min: Option<Datum>,
+    max: Option<Datum>,
+    /// Total and max lengths of the strings or binaries.
+    lengths: Option<(i64, i64)>,
+    value_count: i64,
+}
+
+impl ColumnCollector {
+    fn new(data_type: &DataType) -> Self {
+        let nested = matches!(
+            data_type,
+            DataType::Array(_) | DataType::Map(_) | DataType::Multiset(_) | DataType::Row(_)
+        );
+        let lengths = matches!(
+            data_type,
+            DataType::Char(_) | DataType::VarChar(_) | DataType::Binary(_) | DataType::VarBinary(_)
+        );
+        Self {
+            data_type: data_type.clone(),
+            null_count: 0,
+            distinct: (!nested).then(HyperLogLog::new),
+            min: None,
+            max: None,
+            lengths: lengths.then_some((0, 0)),
+            value_count: 0,
+        }
+    }
+
+    fn collect(&mut self, column: &dyn Array) -> crate::Result<()> {
+        self.null_count += column.null_count() as i64;
+        let Some(distinct) = &mut self.distinct else {
+            return Ok(());
+        };
+        for row in 0..column.len() {
+            let Some(datum) = to_datum(column, row, &self.data_type)? else {
+                continue;
+            };
+            self.value_count += 1;
+            let len = match &datum {
+                Datum::String(s) => Some(s.len() as i64),
+                Datum::Bytes(b) => Some(b.len() as i64),
+                _ => None,
+            };
+            if let (Some((total, max)), Some(len)) = (&mut self.lengths, len) {
+                *total += len;
+                *max = (*max).max(len);
+            }
+            distinct.add(datum.to_string());
+            // binaries have no readable min and max
+            if matches!(datum, Datum::Bytes(_)) {
+                continue;
+            }
+            if !matches!(&self.min, Some(min) if datum >= *min) {
+                self.min = Some(datum.clone());
+            }
+            if !matches!(&self.max, Some(max) if datum <= *max) {
+                self.max = Some(datum);
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(self, col_id: i32) -> ColStats {
+        let (avg_len, max_len) = match self.lengths {
+            Some((total, max)) if self.value_count > 0 => {
+                (Some(total / self.value_count), Some(max))
+            }
+            Some(_) => (Some(0), Some(0)),
+            None => (None, None),
+        };
+        ColStats::builder()
+            .col_id(col_id)
+            .distinct_count(self.distinct.map(|distinct| distinct.estimate() as i64))
+            .min(self.min.map(|min| min.to_string()))
+            .max(self.max.map(|max| max.to_string()))
+            .null_count(Some(self.null_count))
+            .avg_len(avg_len)
+            .max_len(max_len)
+            .build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{CommitKind, Schema};
+    use crate::table::Table;
+    use crate::utils::SchemaManager;
+    use arrow_array::{Int32Array, RecordBatch, StringArray};
+    use std::sync::Arc;
+
+    async fn write(table: &FileStoreTable, ids: Vec<i32>, names: Vec<Option<&str>>) {
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(Int32Array::from(ids)),
+                Arc::new(StringArray::from(names)),
+            ],
+        )
+        .unwrap();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write.write(&batch).await.unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_analyze_table() {
+        let location = "file:/tmp/test_analyze_table";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+            ])
+            .primary_keys(vec!["id".to_string()])
+            .options(HashMap::from([
+                ("bucket".to_string(), "1".to_string()),
+                ("file.format".to_string(), "parquet".to_string()),
+            ]))
+            .build();
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(&schema)
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema);
+        assert_eq!(table.new_analyze().analyze().await.unwrap(), None);
+
+        write(&table, vec![3, 1, 2], vec![Some("c"), None, Some("bb")]).await;
+        write(&table, vec![1, 4], vec![Some("aaaa"), Some("bb")]).await;
+        let statistics = table.new_analyze().analyze().await.unwrap().unwrap();
+        assert_eq!(statistics.snapshot_id(), 2);
+        assert_eq!(statistics.merged_record_count(), Some(4));
+        let id = &statistics.col_stats()["id"];
+        assert_eq!(id.distinct_count(), Some(4));
+        assert_eq!((id.min(), id.max()), (Some("1"), Some("4")));
+        assert_eq!(id.null_count(), Some(0));
+        assert_eq!(id.avg_len(), None);
+        let name = &statistics.col_stats()["name"];
+        assert_eq!(name.col_id(), 1);
+        assert_eq!(name.distinct_count(), Some(3));
+        assert_eq!((name.min(), name.max()), (Some("aaaa"), Some("c")));
+        assert_eq!(name.null_count(), Some(0));
+        assert_eq!((name.avg_len(), name.max_len()), (Some(2), Some(4)));
+
+        let snapshot = table
+            .snapshot_manager()
+            .latest_snapshot()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.commit_kind(), &CommitKind::ANALYZE);
+        assert_eq!(snapshot.total_record_count(), Some(5));
+        assert_eq!(table.statistics().await.unwrap(), Some(statistics.clone()));
+
+        // the statistics are kept by the later snapshots
+        write(&table, vec![5], vec![None]).await;
+        assert_eq!(table.statistics().await.unwrap(), Some(statistics));
+
+        let statistics = table
+            .new_analyze()
+            .with_columns(&["name"])
+            .compute()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(statistics.col_stats().len(), 1);
+        assert_eq!(statistics.col_stats()["name"].null_count(), Some(1));
+        assert!(matches!(
+            table
+                .new_analyze()
+                .with_columns(&["unknown"])
+                .compute()
+                .await,
+            Err(crate::Error::ColumnNotExist { .. })
+        ));
+    }
+}
@@ -0,0 +1,1324 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::deletion_vectors::DELETION_VECTORS_INDEX;
+use crate::error::*;
+use crate::iceberg::IcebergCommitCallback;
+use crate::manifest::{
+    IndexManifestFile, ManifestList, INDEX_MANIFEST_ENTRY_VERSION, MANIFEST_ENTRY_VERSION,
+};
+use crate::metrics::{COMMIT_ATTEMPTS, COMMIT_DURATION};
+use crate::predicate::{PartitionPredicate, PredicateBuilder};
+use crate::spec::{
+    BinaryRow, CommitKind, DataFileMeta, DataType, Datum, FileKind, Identifier, IndexFileMeta,
+    IndexManifestEntry, ManifestEntry, ManifestFileMeta, Snapshot, Statistics,
+};
+use crate::table::table_lookup::{compare_keys, decode_key, KeyRangedFile};
+use crate::table::{CommitMessage, FileStoreTable, Table, TableScan};
+use crate::utils::record_span;
+use bytes::Bytes;
+use chrono::Utc;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+/// A commit of [`FileStoreTable`] to commit the files written by
+/// [`TableWrite`](crate::table::TableWrite) into new snapshots.
+///
+/// The changed files are recorded in new manifests as the delta manifest list of the new
+/// snapshot, while the manifests of the latest snapshot are merged into its base manifest
+/// list. The snapshot file is created atomically by renaming, so that concurrent commits of
+/// other writers, including paimon-java, are detected and the commit is retried on top of
+/// their snapshots. Files changed by compaction are committed into another snapshot of kind
+/// `COMPACT`, along with the changes of the deletion vectors.
+///
+/// The statistics of the table are committed into a snapshot of kind `ANALYZE`, and are kept
+/// by the later snapshots until the schema changes.
+///
+/// Commits with overwrites delete the files of the overwritten partitions in the latest
+/// snapshot, and add the files written in the same snapshot of kind `OVERWRITE`. The files to
+/// delete are collected again on each retry, so files committed concurrently into the
+/// partitions are overwritten too, while overwrites of the partitions committed after the base
+/// snapshot conflict, see [`with_base_snapshot`](Self::with_base_snapshot).
+///
+/// Snapshot files are created while holding the catalog lock of the table if any, see
+/// [`CatalogLock`](crate::catalog::CatalogLock).
+///
+/// The Iceberg metadata of each committed snapshot is written too if `metadata.iceberg.storage`
+/// is enabled, see [`IcebergCommitCallback`].
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/FileStoreCommitImpl.java>
+#[derive(Debug, Clone)]
+pub struct TableCommit {
+    table: FileStoreTable,
+    commit_user: String,
+    commit_identifier: i64,
+    /// The static partition to overwrite, the values of some of its partition keys.
+    overwrite: Option<HashMap<String, Datum>>,
+    /// The latest snapshot when the data committed was written, 0 if there was none.
+    base_snapshot_id: Option<i64>,
+}
+
+/// Entries of the files changed in a snapshot.
+#[derive(Debug, Default)]
+struct Changes {
+    entries: Vec<ManifestEntry>,
+    changelog_entries: Vec<ManifestEntry>,
+    index_entries: Vec<IndexManifestEntry>,
+    /// Name of the new statistics file.
+    statistics: Option<String>,
+    /// Partitions whose files in the latest snapshot are deleted.
+    overwrite: Option<OverwritePartitions>,
+}
+
+/// Partitions overwritten by a commit.
+#[derive(Debug)]
+enum OverwritePartitions {
+    /// Partitions matching the static partition, all partitions if it is empty.
+    Static(PartitionPredicate),
+    /// Partitions written, by their serialized bytes.
+    Dynamic(HashSet<Vec<u8>>),
+}
+
+impl OverwritePartitions {
+    fn test(&self, partition: &[u8]) -> crate::Result<bool> {
+        match self {
+            Self::Static(predicate) => {
+                predicate.test(&BinaryRow::from_serialized_bytes(partition)?)
+            }
+            Self::Dynamic(partitions) => Ok(partitions.contains(partition)),
+        }
+    }
+}
+
+impl Changes {
+    fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+            && self.changelog_entries.is_empty()
+            && self.index_entries.is_empty()
+            && self.statistics.is_none()
+            && self.overwrite.is_none()
+    }
+}
+
+impl TableCommit {
+    pub fn new(
+        table: FileStoreTable,
+        commit_user: impl Into<String>,
+        commit_identifier: i64,
+    ) -> Self {
+        Self {
+            table,
+            commit_user: commit_user.into(),
+            commit_identifier,
+            overwrite: None,
+            base_snapshot_id: None,
+        }
+    }
+
+    /// Overwrite the static partition by the files committed, the values of some of its
+    /// partition keys.
+    ///
+    /// Partitions matching the static partition are overwritten, while an empty static
+    /// partition overwrites the partitions written if `dynamic-partition-overwrite` is
+    /// enabled, otherwise the whole table.
+    pub fn with_overwrite(mut self, static_partition: HashMap<String, Datum>) -> Self {
+        self.overwrite = Some(static_partition);
+        self
+    }
+
+    /// Set the latest snapshot when the data committed was written, see
+    /// [`TableWrite::base_snapshot_id`](crate::table::TableWrite::base_snapshot_id).
+    ///
+    /// The overwrite conflicts with the overwrites of the same partitions committed after the
+    /// base snapshot, which defaults to the latest snapshot of the first try of the commit.
+    pub fn with_base_snapshot(mut self, snapshot_id: Option<i64>) -> Self {
+        self.base_snapshot_id = snapshot_id;
+        self
+    }
+
+    /// Get the table to commit.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the user committing the files.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Commit the files changed in the commit messages, nothing is committed if no file is
+    /// changed.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "commit",
+            skip_all,
+            fields(table = %self.table.identifier(), message_count = messages.len())
+        )
+    )]
+    pub async fn commit(&self, messages: Vec<CommitMessage>) -> crate::Result<()> {
+        let total_buckets = self.table.core_options().bucket();
+        let entry = |kind: FileKind, message: &CommitMessage, file: &DataFileMeta| {
+            ManifestEntry::new(
+                kind,
+                message.partition().to_serialized_bytes(),
+                message.bucket(),
+                total_buckets,
+                file.clone(),
+                MANIFEST_ENTRY_VERSION,
+            )
+        };
+        let mut append = Changes::default();
+        let mut compact = Changes::default();
+        for message in &messages {
+            let data = message.data_increment();
+            append.entries.extend(
+                data.new_files
+                    .iter()
+                    .map(|f| entry(FileKind::Add, message, f)),
+            );
+            append.entries.extend(
+                data.deleted_files
+                    .iter()
+                    .map(|f| entry(FileKind::Delete, message, f)),
+            );
+            append.changelog_entries.extend(
+                data.changelog_files
+                    .iter()
+                    .map(|f| entry(FileKind::Add, message, f)),
+            );
+            let index = message.index_increment();
+            let index_entry = |kind: FileKind, file: &IndexFileMeta| IndexManifestEntry {
+                kind,
+                partition: message.partition().to_serialized_bytes(),
+                bucket: message.bucket(),
+                index_file: file.clone(),
+                version: INDEX_MANIFEST_ENTRY_VERSION,
+            };
+            // deletion vectors are changed by compaction, while hash indexes by new data
+            let index_entries = index
+                .deleted_index_files
+                .iter()
+                .map(|f| index_entry(FileKind::Delete, f))
+                .chain(
+                    index
+                        .new_index_files
+                        .iter()
+                        .map(|f| index_entry(FileKind::Add, f)),
+                );
+            for index_entry in index_entries {
+                if index_entry.index_file.index_type == DELETION_VECTORS_INDEX {
+                    compact.index_entries.push(index_entry);
+                } else {
+                    append.index_entries.push(index_entry);
+                }
+            }
+            let compaction = message.compact_increment();
+            compact.entries.extend(
+                compaction
+                    .compact_before
+                    .iter()
+                    .map(|f| entry(FileKind::Delete, message, f)),
+            );
+            compact.entries.extend(
+                compaction
+                    .compact_after
+                    .iter()
+                    .map(|f| entry(FileKind::Add, message, f)),
+            );
+            compact.changelog_entries.extend(
+                compaction
+                    .changelog_files
+                    .iter()
+                    .map(|f| entry(FileKind::Add, message, f)),
+            );
+        }
+
+        let mut append_kind = CommitKind::APPEND;
+        if let Some(static_partition) = &self.overwrite {
+            append_kind = CommitKind::OVERWRITE;
+            append.overwrite = self.overwrite_partitions(static_partition, &messages)?;
+        }
+        let names = FileNames::new();
+        for (changes, commit_kind) in [(append, append_kind), (compact, CommitKind::COMPACT)] {
+            if !changes.is_empty() {
+                self.commit_changes(&changes, commit_kind, &names).await?;
+            }
+        }
+        // consumers not updated within the expiration time are expired
+        let expire_time = self
+            .table
+            .core_options()
+            .consumer_expiration_time()
+            .and_then(|time| chrono::Duration::from_std(time).ok())
+            .and_then(|time| Utc::now().checked_sub_signed(time));
+        if let Some(expire_time) = expire_time {
+            self.table.consumer_manager().expire(expire_time).await?;
+        }
+        if !self.table.core_options().write_only() {
+            self.table.new_expire_snapshots().expire().await?;
+        }
+        Ok(())
+    }
+
+    /// Get the partitions overwritten by the commit messages, `None` if no partition is
+    /// overwritten by a dynamic overwrite without any file written.
+    fn overwrite_partitions(
+        &self,
+        static_partition: &HashMap<String, Datum>,
+        messages: &[CommitMessage],
+    ) -> crate::Result<Option<OverwritePartitions>> {
+        let written = messages
+            .iter()
+            .filter(|message| !message.data_increment().new_files.is_empty());
+        if static_partition.is_empty() && self.table.core_options().dynamic_partition_overwrite() {
+            let partitions: HashSet<_> = written
+                .map(|message| message.partition().to_serialized_bytes())
+                .collect();
+            return Ok((!partitions.is_empty()).then_some(OverwritePartitions::Dynamic(partitions)));
+        }
+        let partition_type = self.table.schema().logical_partition_type();
+        let predicate = PartitionPredicate::from_values(&partition_type, static_partition)?;
+        for message in written {
+            if !predicate.test(message.partition())? {
+                return WriteInvalidSnafu {
+                    message: format!(
+                        "Files written into bucket {} are not in the static partition {:?} to overwrite",
+                        message.bucket(),
+                        static_partition
+                    ),
+                }
+                .fail();
+            }
+        }
+        Ok(Some(OverwritePartitions::Static(predicate)))
+    }
+
+    /// Drop the given partitions, each of the values of its partition keys, by committing the
+    /// deletes of the data files and the index files in them into a snapshot of kind
+    /// `OVERWRITE`, nothing is committed if no file is in them.
+    pub async fn drop_partitions(
+        &self,
+        partitions: &[HashMap<String, Datum>],
+    ) -> crate::Result<()> {
+        let Some(latest) = self.table.snapshot_manager().latest_snapshot().await? else {
+            return Ok(());
+        };
+        if partitions.is_empty() {
+            return Ok(());
+        }
+        let partition_type = self.table.schema().logical_partition_type();
+        let predicate = PartitionPredicate::from_partitions(&partition_type, partitions)?;
+        let (entries, index_entries) = self
+            .overwrite_entries(&OverwritePartitions::Static(predicate), &latest)
+            .await?;
+        let changes = Changes {
+            entries,
+            index_entries,
+            ..Default::default()
+        };
+        if !changes.is_empty() {
+            self.commit_changes(&changes, CommitKind::OVERWRITE, &FileNames::new())
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Commit the statistics of the table into a new snapshot of kind `ANALYZE`, without
+    /// changing any file.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/FileStoreCommitImpl.java>
+    pub async fn commit_statistics(&self, statistics: &Statistics) -> crate::Result<()> {
+        let stats_file_handler = self.table.stats_file_handler();
+        let file_name = stats_file_handler.write_stats(statistics).await?;
+        let changes = Changes {
+            statistics: Some(file_name.clone()),
+            ..Default::default()
+        };
+        let result = self
+            .commit_changes(&changes, CommitKind::ANALYZE, &FileNames::new())
+            .await;
+        if result.is_err() {
+            let _ = stats_file_handler.delete_stats(&file_name).await;
+        }
+        result.map(|_| ())
+    }
+
+    /// Commit the changes into a new snapshot, retrying on top of the new latest snapshot if
+    /// another writer committed the same snapshot id first.
+    ///
+    /// The changes are checked against the latest snapshot on each try, files to delete must
+    /// still exist and compacted files of primary key tables must not overlap, while
+    /// overwrites conflict with the overwrites of the same partitions committed concurrently.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "commit.snapshot",
+            skip_all,
+            fields(
+                commit_kind = ?commit_kind,
+                entry_count = changes.entries.len(),
+                snapshot_id = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn commit_changes(
+        &self,
+        changes: &Changes,
+        commit_kind: CommitKind,
+        names: &FileNames,
+    ) -> crate::Result<Snapshot> {
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+        let snapshot_manager = self.table.snapshot_manager();
+        let max_retries = self.table.core_options().commit_max_retries();
+        let iceberg = IcebergCommitCallback::create(&self.table)?;
+        let metrics = self.table.metrics();
+        let start = Instant::now();
+        // the snapshot the data was written on, or the latest snapshot of the first try, the
+        // later ones are committed concurrently
+        let mut base_snapshot_id = self.base_snapshot_id;
+        for _ in 0..=max_retries {
+            metrics.increment_counter(COMMIT_ATTEMPTS, 1);
+            let latest = snapshot_manager.latest_snapshot().await?;
+            let latest_id = latest.as_ref().map_or(0, Snapshot::id);
+            match (&changes.overwrite, &latest, base_snapshot_id) {
+                (Some(overwrite), Some(latest), Some(base_snapshot_id)) => {
+                    self.check_overwrite_conflicts(overwrite, base_snapshot_id, latest)
+                        .await?
+                }
+                _ => base_snapshot_id = base_snapshot_id.or(Some(latest_id)),
+            }
+            let mut written = Vec::new();
+            let result = self
+                .try_commit(changes, commit_kind.clone(), latest, names, &mut written)
+                .await;
+            if let Ok(Some(snapshot)) = result {
+                metrics.record_duration(COMMIT_DURATION, start);
+                record_span!(snapshot_id = snapshot.id());
+                // the hint is only an optimization of finding the latest snapshot
+                let _ = snapshot_manager.commit_latest_hint(snapshot.id()).await;
+                if let Some(iceberg) = &iceberg {
+                    iceberg.call(&snapshot).await?;
+                }
+                return Ok(snapshot);
+            }
+            for file_name in written {
+                let _ = file_io
+                    .delete_file(&path_factory.manifest_path(&file_name))
+                    .await;
+            }
+            result?;
+        }
+        CommitConflictSnafu {
+            message: format!("failed to commit after {max_retries} retries"),
+        }
+        .fail()
+    }
+
+    /// Try to commit the changes into the snapshot next to the latest one, returns `None` if
+    /// the snapshot is already committed by others.
+    ///
+    /// The manifests of the latest snapshot are merged into the base manifest list, and the
+    /// files deleted by the changes must exist in them. The names of the manifests and the
+    /// manifest lists written are collected to be cleaned up if the commit fails.
+    async fn try_commit(
+        &self,
+        changes: &Changes,
+        commit_kind: CommitKind,
+        latest: Option<Snapshot>,
+        names: &FileNames,
+        written: &mut Vec<String>,
+    ) -> crate::Result<Option<Snapshot>> {
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+        let manifest_list = ManifestList::new(file_io.clone());
+
+        let mut base_manifests = Vec::new();
+        if let Some(latest) = &latest {
+            for list in [latest.base_manifest_list(), latest.delta_manifest_list()] {
+                base_manifests.extend(
+                    manifest_list
+                        .read(&path_factory.manifest_path(list))
+                        .await?,
+                );
+            }
+        }
+        let (entries, index_entries) = match (&changes.overwrite, &latest) {
+            (Some(overwrite), Some(latest)) => {
+                let (mut entries, mut index_entries) =
+                    self.overwrite_entries(overwrite, latest).await?;
+                entries.extend(changes.entries.iter().cloned());
+                index_entries.extend(changes.index_entries.iter().cloned());
+                (Cow::Owned(entries), Cow::Owned(index_entries))
+            }
+            _ => (
+                Cow::Borrowed(&changes.entries),
+                Cow::Borrowed(&changes.index_entries),
+            ),
+        };
+        self.check_conflicts(&base_manifests, &entries).await?;
+        let base_manifests = self.merge_manifests(base_manifests, written).await?;
+        let delta_manifests = self.write_manifests(&entries).await?;
+        written.extend(delta_manifests.iter().map(|m| m.file_name().to_string()));
+        let changelog_manifests = self.write_manifests(&changes.changelog_entries).await?;
+        written.extend(
+            changelog_manifests
+                .iter()
+                .map(|m| m.file_name().to_string()),
+        );
+
+        let base_manifest_list = names.manifest_list();
+        written.push(base_manifest_list.clone());
+        manifest_list
+            .write(
+                &path_factory.manifest_path(&base_manifest_list),
+                &base_manifests,
+            )
+            .await?;
+        let delta_manifest_list = names.manifest_list();
+        written.push(delta_manifest_list.clone());
+        manifest_list
+            .write(
+                &path_factory.manifest_path(&delta_manifest_list),
+                &delta_manifests,
+            )
+            .await?;
+        let changelog_manifest_list = if changelog_manifests.is_empty() {
+            None
+        } else {
+            let name = names.manifest_list();
+            written.push(name.clone());
+            manifest_list
+                .write(&path_factory.manifest_path(&name), &changelog_manifests)
+                .await?;
+            Some(name)
+        };
+
+        let index_manifest = self
+            .write_index_manifest(latest.as_ref(), &index_entries, names, written)
+            .await?;
+
+        let statistics = match &changes.statistics {
+            Some(statistics) => Some(statistics.clone()),
+            None => self.inherited_statistics(latest.as_ref()).await?,
+        };
+
+        let delta_record_count = record_count(&entries);
+        let total_record_count = latest
+            .as_ref()
+            .and_then(Snapshot::total_record_count)
+            .unwrap_or(0)
+            + delta_record_count;
+        let snapshot = Snapshot::builder()
+            .version(Snapshot::CURRENT_VERSION)
+            .id(latest
+                .as_ref()
+                .map_or(Snapshot::FIRST_SNAPSHOT_ID, |s| s.id() + 1))
+            .schema_id(self.table.schema().id())
+            .base_manifest_list(base_manifest_list)
+            .delta_manifest_list(delta_manifest_list)
+            .changelog_manifest_list(changelog_manifest_list)
+            .index_manifest(index_manifest)
+            .commit_user(self.commit_user.clone())
+            .commit_identifier(self.commit_identifier)
+            .commit_kind(commit_kind)
+            .time_millis(chrono::Utc::now().timestamp_millis() as u64)
+            .total_record_count(Some(total_record_count))
+            .delta_record_count(Some(delta_record_count))
+            .changelog_record_count(Some(record_count(&changes.changelog_entries)))
+            .statistics(statistics)
+            .build();
+
+        let path = self.table.snapshot_manager().snapshot_path(snapshot.id());
+        let content = Bytes::from(snapshot.to_json()?);
+        // Writers of the table are excluded by the catalog lock, so the snapshot is written
+        // without the atomic write the object stores may not support.
+        let committed = match self.table.catalog_lock() {
+            Some(lock) => {
+                let identifier = self.table.identifier();
+                lock.run_with_lock(identifier.database(), identifier.object(), || {
+                    file_io.try_to_write_locked(&path, content)
+                })
+                .await?
+            }
+            None => file_io.try_to_write_atomic(&path, content).await?,
+        };
+        Ok(committed.then_some(snapshot))
+    }
+
+    /// Get the deletes of the data files and the index files of the overwritten partitions in
+    /// the snapshot.
+    async fn overwrite_entries(
+        &self,
+        overwrite: &OverwritePartitions,
+        snapshot: &Snapshot,
+    ) -> crate::Result<(Vec<ManifestEntry>, Vec<IndexManifestEntry>)> {
+        let mut scan = TableScan::new(self.table.clone());
+        if let OverwritePartitions::Static(predicate) = overwrite {
+            scan = scan.with_partition_predicate(predicate.clone());
+        }
+        let mut entries = Vec::new();
+        for entry in scan.data_files(snapshot).await? {
+            if overwrite.test(entry.partition())? {
+                entries.push(ManifestEntry::new(
+                    FileKind::Delete,
+                    entry.partition().clone(),
+                    entry.bucket(),
+                    entry.total_buckets(),
+                    entry.file().clone(),
+                    MANIFEST_ENTRY_VERSION,
+                ));
+            }
+        }
+        let mut index_entries = Vec::new();
+        for entry in self.table.index_manifest_entries(snapshot).await? {
+            if overwrite.test(&entry.partition)? {
+                index_entries.push(IndexManifestEntry {
+                    kind: FileKind::Delete,
+                    ..entry
+                });
+            }
+        }
+        Ok((entries, index_entries))
+    }
+
+    /// Get the statistics file of the latest snapshot to keep, unless the statistics are of
+    /// another schema.
+    async fn inherited_statistics(
+        &self,
+        latest: Option<&Snapshot>,
+    ) -> crate::Result<Option<String>> {
+        let Some(latest) = latest else {
+            return Ok(None);
+        };
+        let stats_file_handler = self.table.stats_file_handler();
+        Ok(match stats_file_handler.read_stats(latest).await? {
+            Some(statistics) if statistics.schema_id() == self.table.schema().id() => {
+                latest.statistics().map(str::to_string)
+            }
+            _ => None,
+        })
+    }
+
+    /// Write the index files of the latest snapshot with the changes applied into a new index
+    /// manifest, the index manifest of the latest snapshot is kept if nothing changes.
+    async fn write_index_manifest(
+        &self,
+        latest: Option<&Snapshot>,
+        changes: &[IndexManifestEntry],
+        names: &FileNames,
+        written: &mut Vec<String>,
+    ) -> crate::Result<Option<String>> {
+        let previous = latest.and_then(|s| s.index_manifest());
+        if changes.is_empty() {
+            return Ok(previous.map(str::to_string));
+        }
+        let index_manifest_file = IndexManifestFile::new(self.table.file_io().clone());
+        let path_factory = self.table.path_factory();
+        let mut entries = match previous {
+            Some(previous) => {
+                index_manifest_file
+                    .read(&path_factory.manifest_path(previous))
+                    .await?
+            }
+            None => vec![],
+        };
+        let deleted: HashSet<&str> = changes
+            .iter()
+            .filter(|entry| entry.kind == FileKind::Delete)
+            .map(|entry| entry.index_file.file_name.as_str())
+            .collect();
+        entries.retain(|entry| !deleted.contains(entry.index_file.file_name.as_str()));
+        entries.extend(
+            changes
+                .iter()
+                .filter(|entry| entry.kind == FileKind::Add)
+                .cloned(),
+        );
+
+        let name = names.index_manifest();
+        written.push(name.clone());
+        index_manifest_file
+            .write(&path_factory.manifest_path(&name), &entries)
+            .await?;
+        Ok(Some(name))
+    }
+
+    /// Check that the files deleted by the changes exist in the manifests and are not deleted
+    /// by others, the files added are not checked as they have unique names.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/FileStoreCommitImpl.java#L1000>
+    async fn check_conflicts(
+        &self,
+        manifests: &[ManifestFileMeta],
+        changes: &[ManifestEntry],
+    ) -> crate::Result<()> {
+        if changes.iter().all(|entry| entry.kind() == &FileKind::Add) {
+            return Ok(());
+        }
+        let manifest_file = self.table.manifest_file();
+        let path_factory = self.table.path_factory();
+        let mut entries = Vec::new();
+        for manifest in manifests {
+            entries.extend(
+                manifest_file
+                    .read(&path_factory.manifest_path(manifest.file_name()))
+                    .await?,
+            );
+        }
+        entries.extend(changes.iter().cloned());
+        let merged = match ManifestEntry::merge_entries(entries) {
+            Ok(merged) => merged,
+            Err(e) => {
+                return CommitConflictSnafu {
+                    message: e.to_string(),
+                }
+                .fail()
+            }
+        };
+        if let Some(entry) = merged
+            .iter()
+            .find(|entry| entry.kind() == &FileKind::Delete)
+        {
+            return CommitConflictSnafu {
+                message: format!(
+                    "file {} to delete doesn't exist, it may be deleted by others",
+                    entry.file_name()
+                ),
+            }
+            .fail();
+        }
+
+        // the files of each level above 0 of the buckets changed must not overlap in keys,
+        // which happens if others compact the same buckets concurrently
+        let key_types: Vec<DataType> = self
+            .table
+            .schema()
+            .trimmed_primary_key_fields()
+            .iter()
+            .map(|field| field.data_type().clone())
+            .collect();
+        if key_types.is_empty() {
+            return Ok(());
+        }
+        let buckets: HashSet<(&Vec<u8>, i32)> = changes
+            .iter()
+            .map(|entry| (entry.partition(), entry.bucket()))
+            .collect();
+        let mut levels: HashMap<_, Vec<KeyRangedFile>> = HashMap::new();
+        for entry in &merged {
+            let level = entry.file().level;
+            if level == 0 || !buckets.contains(&(entry.partition(), entry.bucket())) {
+                continue;
+            }
+            levels
+                .entry((entry.partition(), entry.bucket(), level))
+                .or_default()
+                .push((
+                    decode_key(entry.min_key(), &key_types)?,
+                    decode_key(entry.max_key(), &key_types)?,
+                    entry.file().clone(),
+                ));
+        }
+        for ((_, bucket, level), mut files) in levels {
+            files.sort_by(|a, b| compare_keys(&a.0, &b.0));
+            for pair in files.windows(2) {
+                if compare_keys(&pair[0].1, &pair[1].0).is_ge() {
+                    return CommitConflictSnafu {
+                        message: format!(
+                            "keys of files {} and {} of level {level} of bucket {bucket} overlap, the bucket may be compacted by others",
+                            pair[0].2.file_name, pair[1].2.file_name
+                        ),
+                    }
+                    .fail();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that none of the snapshots committed after the base snapshot, concurrently with
+    /// the overwrite, overwrote the partitions to overwrite as well, whose files would be
+    /// silently replaced.
+    async fn check_overwrite_conflicts(
+        &self,
+        overwrite: &OverwritePartitions,
+        base_snapshot_id: i64,
+        latest: &Snapshot,
+    ) -> crate::Result<()> {
+        let snapshot_manager = self.table.snapshot_manager();
+        let manifest_list = ManifestList::new(self.table.file_io().clone());
+        let manifest_file = self.table.manifest_file();
+        let path_factory = self.table.path_factory();
+        for snapshot_id in base_snapshot_id + 1..=latest.id() {
+            let snapshot = snapshot_manager.snapshot(snapshot_id).await?;
+            if snapshot.commit_kind() != &CommitKind::OVERWRITE {
+                continue;
+            }
+            let manifests = manifest_list
+                .read(&path_factory.manifest_path(snapshot.delta_manifest_list()))
+                .await?;
+            for manifest in manifests {
+                let entries = manifest_file
+                    .read(&path_factory.manifest_path(manifest.file_name()))
+                    .await?;
+                for entry in entries {
+                    if overwrite.test(entry.partition())? {
+                        return CommitConflictSnafu {
+                            message: format!(
+                                "partitions to overwrite are overwritten by snapshot {snapshot_id} concurrently"
+                            ),
+                        }
+                        .fail();
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Merge the small manifests into larger ones to keep the number of manifests of a
+    /// snapshot small.
+    ///
+    /// All the manifests are merged by a full compaction if it is triggered, otherwise
+    /// manifests are merged in order until their total size reaches `manifest.target-file-size`,
+    /// the trailing manifests are merged only if there are at least `manifest.merge-min-count`
+    /// of them.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/ManifestFileMerger.java>
+    async fn merge_manifests(
+        &self,
+        manifests: Vec<ManifestFileMeta>,
+        written: &mut Vec<String>,
+    ) -> crate::Result<Vec<ManifestFileMeta>> {
+        if let Some(merged) = self.full_compaction(&manifests, written).await? {
+            return Ok(merged);
+        }
+        let options = self.table.core_options();
+        let target_size = options.manifest_target_file_size();
+        let min_count = options.manifest_merge_min_count();
+
+        let mut result = Vec::new();
+        let mut candidates = Vec::new();
+        let mut total_size = 0;
+        for manifest in manifests {
+            total_size += manifest.file_size();
+            candidates.push(manifest);
+            if total_size >= target_size {
+                result.extend(
+                    self.merge_candidates(std::mem::take(&mut candidates), written)
+                        .await?,
+                );
+                total_size = 0;
+            }
+        }
+        if candidates.len() >= min_count {
+            result.extend(self.merge_candidates(candidates, written).await?);
+        } else {
+            result.extend(candidates);
+        }
+        Ok(result)
+    }
+
+    /// Merge all the manifests without the deleted entries, if the total size of the delta
+    /// manifests reaches `manifest.full-compaction-threshold-size`, none if not triggered.
+    ///
+    /// The leading manifests of `manifest.target-file-size` without deleted entries are the
+    /// base, and the others are the delta. The base manifests before the first one containing
+    /// the files deleted by the delta are kept as they are, skipped by their partition stats
+    /// or by reading them.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/ManifestFileMerger.java#L130>
+    async fn full_compaction(
+        &self,
+        manifests: &[ManifestFileMeta],
+        written: &mut Vec<String>,
+    ) -> crate::Result<Option<Vec<ManifestFileMeta>>> {
+        let options = self.table.core_options();
+        let target_size = options.manifest_target_file_size();
+        let base_count = manifests
+            .iter()
+            .take_while(|m| m.num_deleted_files() == 0 && m.file_size() >= target_size)
+            .count();
+        let (base, delta) = manifests.split_at(base_count);
+        let delta_size: i64 = delta.iter().map(ManifestFileMeta::file_size).sum();
+        if delta_size < options.manifest_full_compaction_threshold_size() {
+            return Ok(None);
+        }
+
+        let manifest_file = self.table.manifest_file();
+        let path_factory = self.table.path_factory();
+        let read = |manifest: &ManifestFileMeta| {
+            let path = path_factory.manifest_path(manifest.file_name());
+            let manifest_file = &manifest_file;
+            async move { manifest_file.read(&path).await }
+        };
+        let mut delta_entries = Vec::new();
+        for manifest in delta {
+            delta_entries.extend(read(manifest).await?);
+        }
+        let delta_entries = ManifestEntry::merge_entries(delta_entries)?;
+        let deleted: HashSet<Identifier> = delta_entries
+            .iter()
+            .filter(|entry| entry.kind() == &FileKind::Delete)
+            .map(ManifestEntry::identifier)
+            .collect();
+
+        let mut result = Vec::new();
+        let mut next = 0;
+        let partition_type = self.table.schema().logical_partition_type();
+        if !partition_type.fields().is_empty() {
+            // the base manifests without the partitions of the deleted files are kept
+            let builder = PredicateBuilder::new(partition_type.clone());
+            let mut partitions = HashSet::new();
+            let mut predicates = Vec::new();
+            for entry in delta_entries
+                .iter()
+                .filter(|entry| entry.kind() == &FileKind::Delete)
+            {
+                if !partitions.insert(entry.partition().clone()) {
+                    continue;
+                }
+                let partition = BinaryRow::from_serialized_bytes(entry.partition())?;
+                let mut fields = Vec::with_capacity(partition_type.fields().len());
+                for (pos, field) in partition_type.fields().iter().enumerate() {
+                    fields.push(match partition.get_datum(pos, field.data_type())? {
+                        Some(value) => builder.equal(pos, value),
+                        None => builder.is_null(pos),
+                    });
+                }
+                predicates.push(PredicateBuilder::and(fields));
+            }
+            let predicate = PartitionPredicate::new(PredicateBuilder::or(predicates));
+            while next < base.len() && !predicate.test_manifest(&base[next])? {
+                result.push(base[next].clone());
+                next += 1;
+            }
+        }
+        // the base manifests before the first one containing the deleted files are kept
+        let mut entries = Vec::new();
+        while next < base.len() {
+            let base_entries = read(&base[next]).await?;
+            next += 1;
+            if base_entries
+                .iter()
+                .any(|entry| deleted.contains(&entry.identifier()))
+            {
+                entries.extend(base_entries);
+                break;
+            }
+            result.push(base[next - 1].clone());
+        }
+        for manifest in &base[next..] {
+            entries.extend(read(manifest).await?);
+        }
+        entries.retain(|entry| !deleted.contains(&entry.identifier()));
+        entries.extend(
+            delta_entries
+                .into_iter()
+                .filter(|entry| entry.kind() == &FileKind::Add),
+        );
+        let merged = self.write_manifests(&entries).await?;
+        written.extend(merged.iter().map(|m| m.file_name().to_string()));
+        result.extend(merged);
+        Ok(Some(result))
+    }
+
+    async fn merge_candidates(
+        &self,
+        candidates: Vec<ManifestFileMeta>,
+        written: &mut Vec<String>,
+    ) -> crate::Result<Vec<ManifestFileMeta>> {
+        if candidates.len() <= 1 {
+            return Ok(candidates);
+        }
+        let manifest_file = self.table.manifest_file();
+        let path_factory = self.table.path_factory();
+        let mut entries = Vec::new();
+        for manifest in &candidates {
+            entries.extend(
+                manifest_file
+                    .read(&path_factory.manifest_path(manifest.file_name()))
+                    .await?,
+            );
+        }
+        let merged = self
+            .write_manifests(&ManifestEntry::merge_entries(entries)?)
+            .await?;
+        written.extend(merged.iter().map(|m| m.file_name().to_string()));
+        Ok(merged)
+    }
+
+    /// Write the entries into new manifest files of about `manifest.target-file-size`, none
+    /// if there are no entries.
+    async fn write_manifests(
+        &self,
+        entries: &[ManifestEntry],
+    ) -> crate::Result<Vec<ManifestFileMeta>> {
+        let mut writer = self.table.manifest_file_writer();
+        for entry in entries {
+            writer.write(entry).await?;
+        }
+        writer.close().await
+    }
+}
+
+/// Generator of the names of the manifests and manifest lists of a commit.
+struct FileNames {
+    uuid: String,
+    count: AtomicUsize,
+}
+
+impl FileNames {
+    fn new() -> Self {
+        Self {
+            uuid: uuid::Uuid::new_v4().to_string(),
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    fn index_manifest(&self) -> String {
+        format!(
+            "index-manifest-{}-{}",
+            self.uuid,
+            self.count.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+
+    fn manifest_list(&self) -> String {
+        format!(
+            "manifest-list-{}-{}",
+            self.uuid,
+            self.count.fetch_add(1, Ordering::Relaxed)
+        )
+    }
+}
+
+/// Number of the rows added minus the rows deleted by the entries.
+fn record_count(entries: &[ManifestEntry]) -> i64 {
+    entries
+        .iter()
+        .map(|entry| match entry.kind() {
+            FileKind::Add => entry.file().row_count,
+            FileKind::Delete => -entry.file().row_count,
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::to_arrow_schema;
+    use crate::spec::{DataField, Schema};
+    use crate::table::{CompactIncrement, DataIncrement, Table};
+    use crate::test_utils;
+    use arrow_array::{Int32Array, RecordBatch};
+    use std::sync::Arc;
+
+    async fn create_table(location: &str, options: &[(&str, &str)]) -> FileStoreTable {
+        let schema = Schema::builder()
+            .fields(vec![DataField::new(
+                0,
+                "id".to_string(),
+                "INT".parse().unwrap(),
+            )])
+            .options(
+                options
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .chain([("file.format".to_string(), "parquet".to_string())])
+                    .collect(),
+            )
+            .build();
+        test_utils::create_table(location, &schema).await
+    }
+
+    async fn write(table: &FileStoreTable, ids: Vec<i32>) -> Vec<CommitMessage> {
+        let mut write = table.new_batch_write_builder().new_write();
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![Arc::new(Int32Array::from(ids))],
+        )
+        .unwrap();
+        write.write(&batch).await.unwrap();
+        write.prepare_commit().await.unwrap()
+    }
+
+    async fn manifest_list_len(table: &FileStoreTable, list: &str) -> usize {
+        ManifestList::new(table.file_io().clone())
+            .read(&table.path_factory().manifest_path(list))
+            .await
+            .unwrap()
+            .len()
+    }
+
+    #[tokio::test]
+    async fn test_commit_retry_on_conflict() {
+        let table = create_table("file:/tmp/test_commit_retry_on_conflict", &[]).await;
+        let (messages_0, messages_1) = (write(&table, vec![1]).await, write(&table, vec![2]).await);
+        let (commit_0, commit_1) = (
+            table.new_batch_write_builder().new_commit(),
+            table.new_batch_write_builder().new_commit(),
+        );
+        // both commits try to create the first snapshot, the loser retries on top of the winner
+        let (result_0, result_1) =
+            tokio::join!(commit_0.commit(messages_0), commit_1.commit(messages_1));
+        result_0.unwrap();
+        result_1.unwrap();
+
+        let snapshot_manager = table.snapshot_manager();
+        assert_eq!(snapshot_manager.snapshot_ids().await.unwrap(), vec![1, 2]);
+        let latest = snapshot_manager.latest_snapshot().await.unwrap().unwrap();
+        assert_eq!(latest.total_record_count(), Some(2));
+        // both commits update the hint concurrently, a stale one is verified by the next snapshot
+        let hint = table
+            .file_io()
+            .read_file_utf8(&format!("{}/LATEST", snapshot_manager.snapshot_dir()))
+            .await
+            .unwrap();
+        assert!(hint == "1" || hint == "2");
+        assert_eq!(
+            snapshot_manager.latest_snapshot_id().await.unwrap(),
+            Some(2)
+        );
+        let plan = table.new_scan().plan().await.unwrap();
+        assert_eq!(plan.splits()[0].data_files().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_commit_delete_conflict() {
+        let table = create_table("file:/tmp/test_commit_delete_conflict", &[]).await;
+        let messages = write(&table, vec![1]).await;
+        let file = messages[0].data_increment().new_files[0].clone();
+        let commit = table.new_batch_write_builder().new_commit();
+        commit.commit(messages).await.unwrap();
+
+        let compact = || {
+            vec![CommitMessage::new(
+                BinaryRow::new(0),
+                0,
+                DataIncrement::default(),
+                CompactIncrement {
+                    compact_before: vec![file.clone()],
+                    ..Default::default()
+                },
+            )]
+        };
+        commit.commit(compact()).await.unwrap();
+        let latest = table.snapshot_manager().latest_snapshot().await.unwrap();
+        assert_eq!(latest.unwrap().commit_kind(), &CommitKind::COMPACT);
+
+        // the file is already deleted
+        let manifest_dir = table.path_factory().manifest_dir();
+        let manifests = table.file_io().list_status(&manifest_dir).await.unwrap();
+        assert!(matches!(
+            commit.commit(compact()).await,
+            Err(crate::Error::CommitConflict { .. })
+        ));
+        let snapshot_ids = table.snapshot_manager().snapshot_ids().await.unwrap();
+        assert_eq!(snapshot_ids, vec![1, 2]);
+        let status = table.file_io().list_status(&manifest_dir).await.unwrap();
+        assert_eq!(status.len(), manifests.len());
+    }
+
+    #[tokio::test]
+    async fn test_commit_key_overlap_conflict() {
+        let schema = Schema::builder()
+            .fields(vec![DataField::new(
+                0,
+                "id".to_string(),
+                "INT".parse().unwrap(),
+            )])
+            .primary_keys(vec!["id".to_string()])
+            .options(HashMap::from([
+                ("bucket".to_string(), "1".to_string()),
+                ("file.format".to_string(), "parquet".to_string()),
+                ("write-only".to_string(), "true".to_string()),
+            ]))
+            .build();
+        let table =
+            test_utils::create_table("file:/tmp/test_commit_key_overlap_conflict", &schema).await;
+        let commit = table.new_batch_write_builder().new_commit();
+        let mut files = Vec::new();
+        for ids in [vec![1, 2], vec![2, 3]] {
+            let messages = write(&table, ids).await;
+            files.push(messages[0].data_increment().new_files[0].clone());
+            commit.commit(messages).await.unwrap();
+        }
+
+        // two compactions of the same bucket upgrade the files of overlapping keys to level 1
+        let compact = |file: &DataFileMeta| {
+            vec![CommitMessage::new(
+                BinaryRow::new(0),
+                0,
+                DataIncrement::default(),
+                CompactIncrement {
+                    compact_before: vec![file.clone()],
+                    compact_after: vec![DataFileMeta {
+                        file_name: format!("compacted-{}", file.file_name),
+                        level: 1,
+                        ..file.clone()
+                    }],
+                    ..Default::default()
+                },
+            )]
+        };
+        commit.commit(compact(&files[0])).await.unwrap();
+        let Err(err) = commit.commit(compact(&files[1])).await else {
+            panic!("the overlapping files of level 1 are committed");
+        };
+        assert!(matches!(err, crate::Error::CommitConflict { .. }));
+        let snapshot_ids = table.snapshot_manager().snapshot_ids().await.unwrap();
+        assert_eq!(snapshot_ids, vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn test_commit_overwrite_conflict() {
+        let table = create_table("file:/tmp/test_commit_overwrite_conflict", &[]).await;
+        let commit = table.new_batch_write_builder().new_commit();
+        commit.commit(write(&table, vec![1]).await).await.unwrap();
+
+        // overwrites written on the first snapshot race to commit
+        let builder = table
+            .new_batch_write_builder()
+            .with_overwrite(HashMap::new());
+        let mut writes = Vec::new();
+        for id in [2, 3, 5] {
+            let mut write = builder.new_write();
+            let batch = RecordBatch::try_new(
+                Arc::new(to_arrow_schema(table.schema().fields())),
+                vec![Arc::new(Int32Array::from(vec![id]))],
+            )
+            .unwrap();
+            write.write(&batch).await.unwrap();
+            assert_eq!(write.base_snapshot_id(), Some(1));
+            let messages = write.prepare_commit().await.unwrap();
+            let overwrite = builder
+                .new_commit()
+                .with_base_snapshot(write.base_snapshot_id());
+            writes.push((overwrite, messages));
+        }
+        // an append committed concurrently is overwritten
+        commit.commit(write(&table, vec![4]).await).await.unwrap();
+        let [(overwrite_0, messages_0), (overwrite_1, messages_1), (overwrite_2, messages_2)] =
+            <[_; 3]>::try_from(writes).unwrap();
+        let (result_0, result_1) = tokio::join!(
+            overwrite_0.commit(messages_0),
+            overwrite_1.commit(messages_1)
+        );
+        let conflict = match (result_0, result_1) {
+            (Ok(()), Err(e)) | (Err(e), Ok(())) => e,
+            results => panic!("exactly one of the overwrites is committed: {results:?}"),
+        };
+        assert!(matches!(conflict, crate::Error::CommitConflict { .. }));
+        // as well as the overwrite written on the first snapshot but committed later
+        assert!(matches!(
+            overwrite_2.commit(messages_2).await,
+            Err(crate::Error::CommitConflict { .. })
+        ));
+
+        let snapshot_manager = table.snapshot_manager();
+        assert_eq!(
+            snapshot_manager.snapshot_ids().await.unwrap(),
+            vec![1, 2, 3]
+        );
+        let latest = snapshot_manager.latest_snapshot().await.unwrap().unwrap();
+        assert_eq!(latest.commit_kind(), &CommitKind::OVERWRITE);
+        assert_eq!(latest.total_record_count(), Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_commit_merge_manifests() {
+        let table = create_table(
+            "file:/tmp/test_commit_merge_manifests",
+            &[("manifest.merge-min-count", "2")],
+        )
+        .await;
+        let commit = table.new_batch_write_builder().new_commit();
+        for id in 0..3 {
+            commit.commit(write(&table, vec![id]).await).await.unwrap();
+        }
+        let snapshot_manager = table.snapshot_manager();
+        let snapshot = snapshot_manager.snapshot(2).await.unwrap();
+        assert_eq!(
+            manifest_list_len(&table, snapshot.base_manifest_list()).await,
+            1
+        );
+        // the manifests of the first two snapshots are merged into one
+        let snapshot = snapshot_manager.snapshot(3).await.unwrap();
+        assert_eq!(
+            manifest_list_len(&table, snapshot.base_manifest_list()).await,
+            1
+        );
+        assert_eq!(
+            manifest_list_len(&table, snapshot.delta_manifest_list()).await,
+            1
+        );
+
+        let plan = table.new_scan().plan().await.unwrap();
+        assert_eq!(plan.splits()[0].data_files().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_commit_full_compaction_manifests() {
+        let table = create_table(
+            "file:/tmp/test_commit_full_compaction_manifests",
+            &[("manifest.full-compaction-threshold-size", "1 b")],
+        )
+        .await;
+        let commit = table.new_batch_write_builder().new_commit();
+        let messages = write(&table, vec![0]).await;
+        let file = messages[0].data_increment().new_files[0].clone();
+        commit.commit(messages).await.unwrap();
+        commit.commit(write(&table, vec![1]).await).await.unwrap();
+        commit
+            .commit(vec![CommitMessage::new(
+                BinaryRow::new(0),
+                0,
+                DataIncrement::default(),
+                CompactIncrement {
+                    compact_before: vec![file],
+                    ..Default::default()
+                },
+            )])
+            .await
+            .unwrap();
+        commit.commit(write(&table, vec![2]).await).await.unwrap();
+
+        // the deleted file is removed from the manifests merged
+        let snapshot = table.snapshot_manager().snapshot(4).await.unwrap();
+        let manifests = ManifestList::new(table.file_io().clone())
+            .read(
+                &table
+                    .path_factory()
+                    .manifest_path(snapshot.base_manifest_list()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(manifests.len(), 1);
+        assert_eq!(manifests[0].num_added_files(), 1);
+        assert_eq!(manifests[0].num_deleted_files(), 0);
+
+        let plan = table.new_scan().plan().await.unwrap();
+        assert_eq!(plan.splits()[0].data_files().len(), 2);
+    }
+}
@@ -0,0 +1,494 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::deletion_vectors::DeletionVectorsIndexFile;
+use crate::file_index::FileIndexOptions;
+use crate::format::{DataFileReader, DataFileWriter, FileCompression, StatsMode};
+use crate::predicate::PartitionPredicate;
+use crate::spec::{BinaryRow, DataFileMeta, Datum, IndexFileMeta};
+use crate::table::table_scan::group_by_bucket;
+use crate::table::table_write::merge_tree_compact_rewriter;
+use crate::table::{
+    BatchWriteBuilder, CommitMessage, CompactIncrement, DataIncrement, FileStoreTable,
+    IndexIncrement, TableCommit, TableScan,
+};
+use std::collections::HashMap;
+
+/// A full compaction of [`FileStoreTable`] by a commit user, rewriting the data files of each
+/// bucket of the latest snapshot, of all the partitions or the given ones.
+///
+/// The sorted runs of a bucket of primary key tables are merged by the merge engine and
+/// rewritten into the max level of `num-levels - 1` without the deleted keys, buckets of a
+/// single file in the max level are skipped. With `changelog-producer` of `full-compaction`,
+/// the keys in the files out of the max level are looked up, the changes from their merged
+/// values in the files of the max level to the merged values of all the files are written as
+/// the changelog. The files of a bucket of append only tables
+/// are rewritten in the order of sequence numbers into files of `target-file-size`.
+///
+/// The deleted rows of the deletion vectors are dropped by the rewrite, so the deletion
+/// vectors of the bucket are removed. The files changed are committed into a snapshot of kind
+/// `COMPACT`.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/mergetree/compact/MergeTreeCompactManager.java>
+#[derive(Debug, Clone)]
+pub struct TableCompact {
+    table: FileStoreTable,
+    commit_user: String,
+    partitions: Vec<HashMap<String, Datum>>,
+}
+
+impl TableCompact {
+    pub fn new(table: FileStoreTable, commit_user: impl Into<String>) -> Self {
+        Self {
+            table,
+            commit_user: commit_user.into(),
+            partitions: vec![],
+        }
+    }
+
+    /// Only compact the given partitions, each of the values of its partition keys.
+    pub fn with_partitions(mut self, partitions: Vec<HashMap<String, Datum>>) -> Self {
+        self.partitions = partitions;
+        self
+    }
+
+    /// Get the table to compact.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the user committing the files compacted.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Compact the data files and commit the files changed, nothing is committed if no bucket
+    /// needs to be compacted.
+    pub async fn compact(&self) -> crate::Result<()> {
+        let messages = self.prepare_commit().await?;
+        TableCommit::new(
+            self.table.clone(),
+            self.commit_user.clone(),
+            BatchWriteBuilder::COMMIT_IDENTIFIER,
+        )
+        .commit(messages)
+        .await
+    }
+
+    /// Compact the data files of the latest snapshot and get the files changed of each bucket
+    /// compacted, without committing them.
+    pub async fn prepare_commit(&self) -> crate::Result<Vec<CommitMessage>> {
+        let Some(snapshot) = self.table.snapshot_manager().latest_snapshot().await? else {
+            return Ok(vec![]);
+        };
+        let mut scan = TableScan::new(self.table.clone());
+        if !self.partitions.is_empty() {
+            let partition_type = self.table.schema().logical_partition_type();
+            scan = scan.with_partition_predicate(PartitionPredicate::from_partitions(
+                &partition_type,
+                &self.partitions,
+            )?);
+        }
+        let buckets = group_by_bucket(scan.data_files(&snapshot).await?);
+        let mut index_files = self.table.deletion_vectors_index_files(&snapshot).await?;
+
+        let append_only = self.table.schema().primary_keys().is_empty();
+        let max_level = self.table.core_options().num_levels() - 1;
+        let mut messages = Vec::new();
+        for ((partition_bytes, bucket), data_files) in buckets {
+            let index_files = index_files
+                .remove(&(partition_bytes.clone(), bucket))
+                .unwrap_or_default();
+            let compacted = match data_files.as_slice() {
+                [] => true,
+                [file] if append_only || file.level == max_level => index_files.is_empty(),
+                _ => false,
+            };
+            if compacted {
+                continue;
+            }
+            let partition = BinaryRow::from_serialized_bytes(&partition_bytes)?;
+            let compact_increment = if append_only {
+                self.compact_append_only(&partition, bucket, data_files, &index_files)
+                    .await?
+            } else {
+                self.compact_merge_tree(&partition, bucket, data_files, max_level)
+                    .await?
+            };
+            messages.push(
+                CommitMessage::new(
+                    partition,
+                    bucket,
+                    DataIncrement::default(),
+                    compact_increment,
+                )
+                .with_index_increment(IndexIncrement {
+                    new_index_files: vec![],
+                    deleted_index_files: index_files,
+                }),
+            );
+        }
+        Ok(messages)
+    }
+
+    /// Merge the files of a bucket of the primary key table and rewrite them into the max level.
+    ///
+    /// The deletion vectors are not applied, since the rows they delete are merged with the
+    /// newer rows of the same keys anyway.
+    async fn compact_merge_tree(
+        &self,
+        partition: &BinaryRow,
+        bucket: i32,
+        data_files: Vec<DataFileMeta>,
+        max_level: i32,
+    ) -> crate::Result<CompactIncrement> {
+        let path_factory = self.table.path_factory().data_file_path_factory(
+            partition,
+            bucket,
+            &self.table.core_options().file_format(),
+        )?;
+        merge_tree_compact_rewriter(&self.table, partition, bucket, path_factory)?
+            .rewrite(data_files, max_level, true)
+            .await
+    }
+
+    /// Rewrite the files of a bucket of the append only table without the rows deleted by the
+    /// deletion vectors.
+    async fn compact_append_only(
+        &self,
+        partition: &BinaryRow,
+        bucket: i32,
+        mut data_files: Vec<DataFileMeta>,
+        index_files: &[IndexFileMeta],
+    ) -> crate::Result<CompactIncrement> {
+        let table = &self.table;
+        let schema = table.schema();
+        let options = table.core_options();
+        let path_factory = table.path_factory().data_file_path_factory(
+            partition,
+            bucket,
+            &options.file_format(),
+        )?;
+        let index_file =
+            DeletionVectorsIndexFile::new(table.file_io().clone(), table.path_factory());
+        let mut deletion_vectors = HashMap::new();
+        for file in index_files {
+            deletion_vectors.extend(index_file.read_all(file).await?);
+        }
+
+        let reader = DataFileReader::new(
+            table.file_io().clone(),
+            table.schema_manager(),
+            schema.clone(),
+            schema.fields().to_vec(),
+        );
+        let mut writer = DataFileWriter::new(
+            table.file_io().clone(),
+            path_factory.clone(),
+            schema.fields().to_vec(),
+            FileCompression::of_options(&options),
+            0,
+            schema.id(),
+            options.target_file_size(),
+        )
+        .with_stats_modes(StatsMode::of_fields(&options, schema.fields())?)
+        .with_file_index(FileIndexOptions::of_fields(&options, schema.fields())?)
+        .with_compact_level(0);
+        data_files.sort_by_key(|file| file.min_sequence_number);
+        for file in &data_files {
+            let batches = reader
+                .read(&path_factory.to_path(&file.file_name), file)
+                .await?;
+            let batches = match deletion_vectors.remove(&file.file_name) {
+                Some(deletion_vector) => deletion_vector.apply(batches),
+                None => batches,
+            };
+            for batch in batches {
+                writer
+                    .write(&batch?, file.min_sequence_number, file.max_sequence_number)
+                    .await?;
+            }
+        }
+        Ok(CompactIncrement {
+            compact_before: data_files,
+            compact_after: writer.close().await?,
+            changelog_files: vec![],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::to_arrow_schema;
+    use crate::spec::{CommitKind, DataField, RowKind, Schema};
+    use crate::table::{Plan, Table};
+    use crate::test_utils::{create_table, write_and_commit_with_row_kinds};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{ArrayRef, Int32Array, Int64Array, RecordBatch, StringArray};
+    use std::sync::Arc;
+
+    /// Write and commit the rows of the columns with their row kinds to the table.
+    async fn write(table: &FileStoreTable, columns: Vec<ArrayRef>, row_kinds: &[RowKind]) {
+        let batch =
+            RecordBatch::try_new(Arc::new(to_arrow_schema(table.schema().fields())), columns)
+                .unwrap();
+        write_and_commit_with_row_kinds(table, &batch, row_kinds).await;
+    }
+
+    /// Read the (row kind, first column, second column) rows of the plan.
+    async fn read(table: &FileStoreTable, plan: &Plan) -> Vec<(RowKind, i32, String)> {
+        let read = table.new_read();
+        let mut rows = Vec::new();
+        for split in plan.splits() {
+            for batch in read.read_with_row_kinds(split).await.unwrap() {
+                let (batch, row_kinds) = batch.unwrap();
+                let first = batch.column(0).as_primitive::<Int32Type>();
+                let second = batch.column(1).as_string::<i32>();
+                rows.extend(
+                    row_kinds
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, kind)| (kind, first.value(i), second.value(i).to_string())),
+                );
+            }
+        }
+        rows
+    }
+
+    fn columns(ids: &[i32], names: &[&str]) -> Vec<ArrayRef> {
+        vec![
+            Arc::new(Int32Array::from(ids.to_vec())),
+            Arc::new(StringArray::from(names.to_vec())),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_compact_primary_key_table() {
+        let location = "file:/tmp/test_table_compact_primary_key_table";
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+            ])
+            .primary_keys(vec!["id".to_string()])
+            .options(
+                [
+                    ("bucket".to_string(), "1".to_string()),
+                    (
+                        "changelog-producer".to_string(),
+                        "full-compaction".to_string(),
+                    ),
+                    ("file.format".to_string(), "parquet".to_string()),
+                ]
+                .into(),
+            )
+            .build();
+        let table = create_table(location, &schema).await;
+        use RowKind::*;
+        write(&table, columns(&[1, 2], &["a", "b"]), &[Insert, Insert]).await;
+        write(
+            &table,
+            columns(&[1, 2, 3], &["c", "b", "d"]),
+            &[UpdateAfter, Delete, Insert],
+        )
+        .await;
+
+        table.new_compact().compact().await.unwrap();
+        let snapshot_manager = table.snapshot_manager();
+        let snapshot = snapshot_manager.latest_snapshot().await.unwrap().unwrap();
+        assert_eq!(
+            (snapshot.id(), snapshot.commit_kind()),
+            (3, &CommitKind::COMPACT)
+        );
+        let plan = table.new_scan().plan().await.unwrap();
+        let files = plan.splits()[0].data_files();
+        assert_eq!(files.len(), 1);
+        assert_eq!((files[0].level, files[0].row_count), (5, 2));
+        let rows = vec![(Insert, 1, "c".to_string()), (Insert, 3, "d".to_string())];
+        assert_eq!(read(&table, &plan).await, rows);
+        // no file is in the max level before the first full compaction
+        let changelog = table
+            .new_scan()
+            .plan_changes(&snapshot, true)
+            .await
+            .unwrap();
+        assert_eq!(read(&table, &changelog).await, rows);
+
+        write(
+            &table,
+            columns(&[3, 4], &["e", "f"]),
+            &[UpdateAfter, Insert],
+        )
+        .await;
+        table.new_compact().compact().await.unwrap();
+        let snapshot = snapshot_manager.latest_snapshot().await.unwrap().unwrap();
+        assert_eq!(snapshot.id(), 5);
+        let changelog = table
+            .new_scan()
+            .plan_changes(&snapshot, true)
+            .await
+            .unwrap();
+        assert_eq!(
+            read(&table, &changelog).await,
+            vec![
+                (UpdateBefore, 3, "d".to_string()),
+                (UpdateAfter, 3, "e".to_string()),
+                (Insert, 4, "f".to_string()),
+            ]
+        );
+
+        // the bucket of a single file in the max level is not compacted again
+        assert!(table
+            .new_compact()
+            .prepare_commit()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_compact_with_sequence_field() {
+        let location = "file:/tmp/test_table_compact_with_sequence_field";
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                DataField::new(2, "ts".to_string(), "BIGINT".parse().unwrap()),
+            ])
+            .primary_keys(vec!["id".to_string()])
+            .options(
+                [
+                    ("bucket".to_string(), "1".to_string()),
+                    ("sequence.field".to_string(), "ts".to_string()),
+                    ("file.format".to_string(), "parquet".to_string()),
+                ]
+                .into(),
+            )
+            .build();
+        let table = create_table(location, &schema).await;
+        let with_ts = |ids: &[i32], names: &[&str], ts: &[Option<i64>]| {
+            let mut columns = columns(ids, names);
+            columns.push(Arc::new(Int64Array::from(ts.to_vec())));
+            columns
+        };
+        use RowKind::*;
+        write(
+            &table,
+            with_ts(&[1, 2], &["a", "b"], &[Some(2), None]),
+            &[Insert, Insert],
+        )
+        .await;
+        // the records of smaller sequence fields lose regardless of the order of writes
+        write(
+            &table,
+            with_ts(
+                &[1, 2, 2, 3, 3],
+                &["c", "d", "e", "f", "g"],
+                &[Some(1), Some(3), Some(0), Some(5), Some(4)],
+            ),
+            &[Insert; 5],
+        )
+        .await;
+        let rows = vec![
+            (Insert, 1, "a".to_string()),
+            (Insert, 2, "d".to_string()),
+            (Insert, 3, "f".to_string()),
+        ];
+        let plan = table.new_scan().plan().await.unwrap();
+        assert_eq!(read(&table, &plan).await, rows);
+
+        // the sequence fields are kept by compactions, and merged even if not read
+        table.new_compact().compact().await.unwrap();
+        write(&table, with_ts(&[1], &["h"], &[Some(0)]), &[Delete]).await;
+        let plan = table.new_scan().plan().await.unwrap();
+        assert_eq!(read(&table, &plan).await, rows);
+        let read = table.new_read().with_projection(&["name"]);
+        let split = &plan.splits()[0];
+        let mut names = vec![];
+        for batch in read.read(split).await.unwrap() {
+            names.extend(
+                batch
+                    .unwrap()
+                    .column(0)
+                    .as_string::<i32>()
+                    .iter()
+                    .map(|name| name.map(str::to_string)),
+            );
+        }
+        assert_eq!(
+            names,
+            vec![
+                Some("a".to_string()),
+                Some("d".to_string()),
+                Some("f".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compact_partitions_of_append_only_table() {
+        let location = "file:/tmp/test_table_compact_partitions_of_append_only_table";
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "dt".to_string(), "INT".parse().unwrap()),
+                DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+            ])
+            .partition_keys(vec!["dt".to_string()])
+            .options([("file.format".to_string(), "parquet".to_string())].into())
+            .build();
+        let table = create_table(location, &schema).await;
+        let inserts = [RowKind::Insert; 2];
+        write(&table, columns(&[1, 2], &["a", "b"]), &inserts).await;
+        write(&table, columns(&[2, 1], &["c", "d"]), &inserts).await;
+
+        table
+            .new_compact()
+            .with_partitions(vec![[("dt".to_string(), Datum::Int(1))].into()])
+            .compact()
+            .await
+            .unwrap();
+        let plan = table.new_scan().plan().await.unwrap();
+        let files: Vec<_> = plan
+            .splits()
+            .iter()
+            .map(|split| (split.partition().get_int(0).unwrap(), split.data_files()))
+            .collect();
+        assert_eq!(files.len(), 2);
+        assert!(files
+            .iter()
+            .all(|(dt, files)| files.len() == if *dt == 1 { 1 } else { 2 }));
+        let dt_1 = plan
+            .splits()
+            .iter()
+            .find(|split| split.partition().get_int(0).unwrap() == 1);
+        let rows = read(&table, &Plan::new(None, vec![dt_1.unwrap().clone()])).await;
+        let names: Vec<_> = rows.into_iter().map(|(_, _, name)| name).collect();
+        assert_eq!(names, vec!["a", "d"]);
+        assert!(matches!(
+            table
+                .new_compact()
+                .with_partitions(vec![[("name".to_string(), Datum::Int(1))].into()])
+                .prepare_commit()
+                .await,
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+    }
+}
@@ -0,0 +1,297 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::deletion_vectors::{DeletionVector, DeletionVectorsIndexFile};
+use crate::error::*;
+use crate::format::DataFileReader;
+use crate::predicate::Predicate;
+use crate::spec::{BinaryRow, RowKind};
+use crate::table::table_scan::group_by_bucket;
+use crate::table::{
+    BatchWriteBuilder, CommitMessage, CompactIncrement, DataIncrement, FileStoreTable,
+    IndexIncrement, TableCommit, TableRead, TableScan, TableWrite,
+};
+use arrow_array::BooleanArray;
+use arrow_select::filter::filter_record_batch;
+use indexmap::IndexMap;
+
+/// A deletion of the rows of [`FileStoreTable`] matching a filter by a commit user, like
+/// `DELETE FROM ... WHERE`, all the rows are deleted if there is no filter.
+///
+/// Rows of primary key tables are deleted on read, the matched rows of the latest snapshot
+/// are written back as `-D` rows and merged with the deleted keys by later reads and
+/// compactions. Rows of append only tables are deleted by the deletion vectors of their data
+/// files, which requires `deletion-vectors.enabled`, the deletion vectors of each bucket
+/// changed are merged with the existing ones into a new index file.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-spark/paimon-spark-common/src/main/scala/org/apache/paimon/spark/commands/DeleteFromPaimonTableCommand.scala>
+#[derive(Debug, Clone)]
+pub struct TableDelete {
+    table: FileStoreTable,
+    commit_user: String,
+    filter: Option<Predicate>,
+}
+
+impl TableDelete {
+    pub fn new(table: FileStoreTable, commit_user: impl Into<String>) -> Self {
+        Self {
+            table,
+            commit_user: commit_user.into(),
+            filter: None,
+        }
+    }
+
+    /// Only delete the rows matching the predicate on the fields of the table.
+    pub fn with_filter(mut self, predicate: Predicate) -> Self {
+        self.filter = Some(predicate);
+        self
+    }
+
+    /// Get the table to delete from.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the user committing the deletes.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Delete the matched rows of the latest snapshot and commit the deletes, nothing is
+    /// committed if no row is matched.
+    pub async fn delete(&self) -> crate::Result<()> {
+        let messages = self.prepare_commit().await?;
+        TableCommit::new(
+            self.table.clone(),
+            self.commit_user.clone(),
+            BatchWriteBuilder::COMMIT_IDENTIFIER,
+        )
+        .commit(messages)
+        .await
+    }
+
+    /// Delete the matched rows of the latest snapshot and get the files changed of each
+    /// bucket, without committing them.
+    pub async fn prepare_commit(&self) -> crate::Result<Vec<CommitMessage>> {
+        let Some(snapshot) = self.table.snapshot_manager().latest_snapshot().await? else {
+            return Ok(vec![]);
+        };
+        if !self.table.schema().primary_keys().is_empty() {
+            return self.delete_merge_on_read(snapshot.id()).await;
+        }
+        if !self.table.core_options().deletion_vectors_enabled() {
+            return WriteInvalidSnafu {
+                message: "Deleting rows of append only tables requires deletion-vectors.enabled"
+                    .to_string(),
+            }
+            .fail();
+        }
+
+        let table = &self.table;
+        let schema = table.schema();
+        let reader = DataFileReader::new(
+            table.file_io().clone(),
+            table.schema_manager(),
+            schema.clone(),
+            schema.fields().to_vec(),
+        );
+        let index_file =
+            DeletionVectorsIndexFile::new(table.file_io().clone(), table.path_factory());
+        let mut index_files = table.deletion_vectors_index_files(&snapshot).await?;
+        let entries = TableScan::new(table.clone()).data_files(&snapshot).await?;
+        let mut messages = Vec::new();
+        for ((partition_bytes, bucket), data_files) in group_by_bucket(entries) {
+            let index_files = index_files
+                .remove(&(partition_bytes.clone(), bucket))
+                .unwrap_or_default();
+            let mut deletion_vectors = IndexMap::new();
+            for file in &index_files {
+                deletion_vectors.extend(index_file.read_all(file).await?);
+            }
+            let partition = BinaryRow::from_serialized_bytes(&partition_bytes)?;
+            let path_factory = table.path_factory().data_file_path_factory(
+                &partition,
+                bucket,
+                &table.core_options().file_format(),
+            )?;
+            let mut changed = false;
+            for file in &data_files {
+                let deletion_vector = deletion_vectors
+                    .entry(file.file_name.clone())
+                    .or_insert_with(DeletionVector::new);
+                let mut position = 0;
+                for batch in reader
+                    .read(&path_factory.to_path(&file.file_name), file)
+                    .await?
+                {
+                    let batch = batch?;
+                    let matched = match &self.filter {
+                        Some(filter) => filter.test_batch(&batch)?,
+                        None => BooleanArray::from(vec![true; batch.num_rows()]),
+                    };
+                    for (row, matched) in matched.iter().enumerate() {
+                        if matched == Some(true) {
+                            changed |= deletion_vector.delete((position + row) as u32);
+                        }
+                    }
+                    position += batch.num_rows();
+                }
+            }
+            if !changed {
+                continue;
+            }
+            deletion_vectors.retain(|_, deletion_vector| !deletion_vector.is_empty());
+            messages.push(
+                CommitMessage::new(
+                    partition,
+                    bucket,
+                    DataIncrement::default(),
+                    CompactIncrement::default(),
+                )
+                .with_index_increment(IndexIncrement {
+                    new_index_files: vec![index_file.write(&deletion_vectors).await?],
+                    deleted_index_files: index_files,
+                }),
+            );
+        }
+        Ok(messages)
+    }
+
+    /// Write the matched rows of the snapshot of the primary key table back as deletes.
+    async fn delete_merge_on_read(&self, snapshot_id: i64) -> crate::Result<Vec<CommitMessage>> {
+        let mut read = TableRead::new(self.table.clone());
+        if let Some(filter) = &self.filter {
+            read = read.with_filter(filter.clone());
+        }
+        let plan = TableScan::new(self.table.clone())
+            .as_of_snapshot(snapshot_id)
+            .plan()
+            .await?;
+        let mut write = TableWrite::new(self.table.clone(), self.commit_user.clone());
+        for split in plan.splits() {
+            for batch in read.read(split).await? {
+                let mut batch = batch?;
+                if let Some(filter) = &self.filter {
+                    batch = filter_record_batch(&batch, &filter.test_batch(&batch)?)?;
+                }
+                if batch.num_rows() > 0 {
+                    let row_kinds = vec![RowKind::Delete; batch.num_rows()];
+                    write.write_with_row_kinds(&batch, &row_kinds).await?;
+                }
+            }
+        }
+        write.prepare_commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::predicate::PredicateBuilder;
+    use crate::spec::{CommitKind, Datum};
+    use crate::table::Table;
+    use crate::test_utils::{create_id_name_table, id_name_batch, write_and_commit};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+
+    async fn read_ids(table: &FileStoreTable) -> Vec<i32> {
+        let plan = table.new_scan().plan().await.unwrap();
+        let read = table.new_read();
+        let mut ids = Vec::new();
+        for split in plan.splits() {
+            for batch in read.read(split).await.unwrap() {
+                let batch = batch.unwrap();
+                ids.extend(batch.column(0).as_primitive::<Int32Type>().values());
+            }
+        }
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_delete_primary_key_table() {
+        let table = create_id_name_table(
+            "file:/tmp/test_delete_primary_key_table",
+            &["id"],
+            &[("file.format", "parquet"), ("bucket", "1")],
+        )
+        .await;
+        write_and_commit(&table, &id_name_batch(&table, &[1, 2, 3], &["a", "b", "c"])).await;
+        let builder = PredicateBuilder::new(table.row_type());
+        table
+            .new_delete()
+            .with_filter(builder.greater_or_equal(0, Datum::Int(2)))
+            .delete()
+            .await
+            .unwrap();
+        let snapshot = table.snapshot_manager().latest_snapshot().await.unwrap();
+        assert_eq!(snapshot.unwrap().delta_record_count(), Some(2));
+        assert_eq!(read_ids(&table).await, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn test_delete_append_only_table() {
+        let location = "file:/tmp/test_delete_append_only_table";
+        let table = create_id_name_table(location, &[], &[("file.format", "parquet")]).await;
+        write_and_commit(&table, &id_name_batch(&table, &[1, 2], &["a", "b"])).await;
+        assert!(matches!(
+            table.new_delete().delete().await,
+            Err(crate::Error::WriteInvalid { .. })
+        ));
+
+        let table = create_id_name_table(
+            location,
+            &[],
+            &[
+                ("file.format", "parquet"),
+                ("deletion-vectors.enabled", "true"),
+            ],
+        )
+        .await;
+        write_and_commit(&table, &id_name_batch(&table, &[1, 2, 3], &["a", "b", "c"])).await;
+        write_and_commit(&table, &id_name_batch(&table, &[4, 5], &["d", "b"])).await;
+        let builder = PredicateBuilder::new(table.row_type());
+        let delete = table
+            .new_delete()
+            .with_filter(builder.equal(1, Datum::String("b".to_string())));
+        delete.delete().await.unwrap();
+        assert_eq!(read_ids(&table).await, vec![1, 3, 4]);
+        let snapshot = table
+            .snapshot_manager()
+            .latest_snapshot()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.commit_kind(), &CommitKind::COMPACT);
+        // nothing is committed if no row is deleted again
+        delete.delete().await.unwrap();
+        let latest = table.snapshot_manager().latest_snapshot_id().await.unwrap();
+        assert_eq!(latest, Some(snapshot.id()));
+
+        // the deletion vectors are merged with the existing ones
+        table.new_delete().delete().await.unwrap();
+        assert!(read_ids(&table).await.is_empty());
+        let snapshot = table.snapshot_manager().latest_snapshot().await.unwrap();
+        let index_files = table
+            .deletion_vectors_index_files(&snapshot.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(index_files.values().flatten().count(), 1);
+    }
+}
@@ -0,0 +1,383 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::SchemaInvalidSnafu;
+use crate::format::to_datum;
+use crate::spec::{BinaryRow, BinaryRowWriter, DataFileMeta, DataType, Datum};
+use crate::table::row_key_extractor::bucket;
+use crate::table::{DataSplit, FileStoreTable, Table};
+use arrow_array::RecordBatch;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+
+/// A lookup of the merged rows of primary key tables by their primary keys.
+///
+/// The bucket of a key is located by the hash of its bucket key, then the data files possibly
+/// containing the key are found by their min and max keys, binary searching the sorted runs of
+/// the levels above 0. Only these files are read and merged by the merge engine.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/query/LocalTableQuery.java>
+#[derive(Debug, Clone)]
+pub struct TableLookup {
+    table: FileStoreTable,
+}
+
+impl TableLookup {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self { table }
+    }
+
+    /// Look up the merged row of the latest snapshot of the key, which is the values of the
+    /// primary keys in their order, none if the key doesn't exist or is deleted.
+    pub async fn lookup(&self, key: &[Datum]) -> crate::Result<Option<RecordBatch>> {
+        let schema = self.table.schema();
+        let primary_keys = schema.primary_keys();
+        if primary_keys.is_empty() {
+            return SchemaInvalidSnafu {
+                message: format!(
+                    "Table {} has no primary keys to look up",
+                    self.table.location()
+                ),
+            }
+            .fail();
+        }
+        if key.len() != primary_keys.len() {
+            return SchemaInvalidSnafu {
+                message: format!(
+                    "Key of {} values doesn't match the primary keys {primary_keys:?}",
+                    key.len()
+                ),
+            }
+            .fail();
+        }
+        let field_type = |name: &str| {
+            schema
+                .fields()
+                .iter()
+                .find(|f| f.name() == name)
+                .map(|f| f.data_type().clone())
+                .expect("keys are validated by the schema")
+        };
+        let key_datum = |name: &str| {
+            let index = primary_keys.iter().position(|k| k == name);
+            index.map(|index| &key[index])
+        };
+        let project = |names: &[&str]| -> crate::Result<(BinaryRow, Vec<Datum>, Vec<DataType>)> {
+            let mut writer = BinaryRowWriter::new(names.len() as i32);
+            let mut datums = Vec::with_capacity(names.len());
+            let mut types = Vec::with_capacity(names.len());
+            for (pos, name) in names.iter().enumerate() {
+                let data_type = field_type(name);
+                let datum = key_datum(name).cloned().ok_or_else(|| {
+                    SchemaInvalidSnafu {
+                        message: format!("Key {name} is not a primary key"),
+                    }
+                    .build()
+                })?;
+                writer.write_datum(pos, Some(&datum), &data_type)?;
+                datums.push(datum);
+                types.push(data_type);
+            }
+            Ok((writer.build(), datums, types))
+        };
+
+        let partition_keys: Vec<&str> =
+            schema.partition_keys().iter().map(String::as_str).collect();
+        let (_, partition, _) = project(&partition_keys)?;
+        let partition_filter: HashMap<String, Datum> = partition_keys
+            .iter()
+            .map(|k| k.to_string())
+            .zip(partition)
+            .collect();
+        let mut scan = self
+            .table
+            .new_scan()
+            .with_partition_filter(partition_filter);
+        let num_buckets = self.table.core_options().bucket();
+        if num_buckets > 0 {
+            let (bucket_key, _, _) = project(&schema.bucket_keys())?;
+            scan = scan.with_bucket(bucket(bucket_key.hash_code(), num_buckets));
+        }
+        let Some(snapshot) = scan.snapshot().await? else {
+            return Ok(None);
+        };
+        let (_, trimmed_key, key_types) = project(&schema.trimmed_primary_keys())?;
+
+        let mut buckets: BTreeMap<(Vec<u8>, i32), Vec<DataFileMeta>> = BTreeMap::new();
+        for entry in scan.data_files(&snapshot).await? {
+            buckets
+                .entry((entry.partition().clone(), entry.bucket()))
+                .or_default()
+                .push(entry.file().clone());
+        }
+        let path_factory = self.table.path_factory();
+        let merge_tree_reader = self.table.new_read().merge_tree_reader()?;
+        let row_type = schema.logical_row_type();
+        let key_fields: Vec<(usize, &DataType)> = primary_keys
+            .iter()
+            .map(|k| {
+                let index = row_type
+                    .field_index(k)
+                    .expect("keys are validated by the schema");
+                (index, row_type.fields()[index].data_type())
+            })
+            .collect();
+        let matches = |batch: &RecordBatch, row: usize| -> crate::Result<bool> {
+            for ((index, data_type), datum) in key_fields.iter().zip(key) {
+                if to_datum(batch.column(*index), row, data_type)?.as_ref() != Some(datum) {
+                    return Ok(false);
+                }
+            }
+            Ok(true)
+        };
+        for ((partition_bytes, bucket), files) in buckets {
+            let files = candidate_files(files, &trimmed_key, &key_types)?;
+            if files.is_empty() {
+                continue;
+            }
+            let partition = BinaryRow::from_serialized_bytes(&partition_bytes)?;
+            let split = DataSplit::builder()
+                .snapshot_id(snapshot.id())
+                .bucket_path(path_factory.bucket_path(&partition, bucket)?)
+                .partition(partition)
+                .bucket(bucket)
+                .data_files(files)
+                .build();
+            for batch in merge_tree_reader.read(&split).await? {
+                let batch = batch?;
+                for row in 0..batch.num_rows() {
+                    if matches(&batch, row)? {
+                        return Ok(Some(batch.slice(row, 1)));
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// A data file with its decoded min and max keys.
+pub(crate) type KeyRangedFile = (Vec<Datum>, Vec<Datum>, DataFileMeta);
+
+/// Get the data files possibly containing the key by their min and max keys, all the files of
+/// level 0 are checked while the non-overlapping files of each level above are binary searched.
+fn candidate_files(
+    files: Vec<DataFileMeta>,
+    key: &[Datum],
+    key_types: &[DataType],
+) -> crate::Result<Vec<DataFileMeta>> {
+    let mut levels: BTreeMap<i32, Vec<KeyRangedFile>> = BTreeMap::new();
+    for file in files {
+        let min_key = decode_key(&file.min_key, key_types)?;
+        let max_key = decode_key(&file.max_key, key_types)?;
+        levels
+            .entry(file.level)
+            .or_default()
+            .push((min_key, max_key, file));
+    }
+    let mut candidates = Vec::new();
+    for (level, mut files) in levels {
+        if level == 0 {
+            candidates.extend(files.into_iter().filter_map(|(min_key, max_key, file)| {
+                let contains =
+                    compare_keys(&min_key, key).is_le() && compare_keys(&max_key, key).is_ge();
+                contains.then_some(file)
+            }));
+            continue;
+        }
+        files.sort_by(|a, b| compare_keys(&a.0, &b.0));
+        // the first file whose max key is not less than the key
+        let index = files.partition_point(|(_, max_key, _)| compare_keys(max_key, key).is_lt());
+        if let Some((min_key, _, file)) = files.into_iter().nth(index) {
+            if compare_keys(&min_key, key).is_le() {
+                candidates.push(file);
+            }
+        }
+    }
+    Ok(candidates)
+}
+
+/// Decode the serialized binary row of a key into its values.
+pub(crate) fn decode_key(bytes: &[u8], key_types: &[DataType]) -> crate::Result<Vec<Datum>> {
+    let row = BinaryRow::from_serialized_bytes(bytes)?;
+    key_types
+        .iter()
+        .enumerate()
+        .map(|(pos, data_type)| {
+            let datum = row.get_datum(pos, data_type)?;
+            datum.ok_or_else(|| {
+                SchemaInvalidSnafu {
+                    message: format!("Null value of primary key at {pos}"),
+                }
+                .build()
+            })
+        })
+        .collect()
+}
+
+/// Compare the keys by their values in order.
+pub(crate) fn compare_keys(a: &[Datum], b: &[Datum]) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataField, RowKind, Schema};
+    use crate::test_utils;
+    use crate::utils::SchemaManager;
+    use arrow_array::cast::AsArray;
+    use arrow_array::{Int32Array, StringArray};
+
+    use std::sync::Arc;
+
+    async fn write(table: &FileStoreTable, rows: &[(RowKind, &str, i32, &str)]) {
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.1))),
+                Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.2))),
+                Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.3))),
+            ],
+        )
+        .unwrap();
+        let row_kinds: Vec<RowKind> = rows.iter().map(|r| r.0).collect();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write
+            .write_with_row_kinds(&batch, &row_kinds)
+            .await
+            .unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_lookup() {
+        let location = "file:/tmp/test_table_lookup";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "dt".to_string(), "STRING".parse().unwrap()),
+                        DataField::new(1, "id".to_string(), "INT".parse().unwrap()),
+                        DataField::new(2, "name".to_string(), "STRING".parse().unwrap()),
+                    ])
+                    .partition_keys(vec!["dt".to_string()])
+                    .primary_keys(vec!["dt".to_string(), "id".to_string()])
+                    .options(
+                        [
+                            ("bucket".to_string(), "2".to_string()),
+                            ("file.format".to_string(), "parquet".to_string()),
+                        ]
+                        .into(),
+                    )
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema);
+        let key = |dt: &str, id: i32| vec![Datum::String(dt.to_string()), Datum::Int(id)];
+        let name = |batch: Option<RecordBatch>| {
+            batch.map(|batch| {
+                assert_eq!(batch.num_rows(), 1);
+                batch.column(2).as_string::<i32>().value(0).to_string()
+            })
+        };
+        assert_eq!(table.lookup(&key("a", 1)).await.unwrap(), None);
+
+        let rows: Vec<(RowKind, &str, i32, &str)> = (0..10)
+            .map(|id| (RowKind::Insert, "a", id, "v1"))
+            .chain([(RowKind::Insert, "b", 1, "b1")])
+            .collect();
+        write(&table, &rows).await;
+        // the compacted files are in the highest level, the new files in level 0
+        table.new_compact().compact().await.unwrap();
+        write(
+            &table,
+            &[
+                (RowKind::Insert, "a", 3, "v2"),
+                (RowKind::Delete, "a", 4, "v1"),
+            ],
+        )
+        .await;
+
+        assert_eq!(
+            name(table.lookup(&key("a", 1)).await.unwrap()).as_deref(),
+            Some("v1")
+        );
+        assert_eq!(
+            name(table.lookup(&key("a", 3)).await.unwrap()).as_deref(),
+            Some("v2")
+        );
+        assert_eq!(name(table.lookup(&key("a", 4)).await.unwrap()), None);
+        assert_eq!(name(table.lookup(&key("a", 10)).await.unwrap()), None);
+        assert_eq!(
+            name(table.lookup(&key("b", 1)).await.unwrap()).as_deref(),
+            Some("b1")
+        );
+        assert_eq!(name(table.lookup(&key("c", 1)).await.unwrap()), None);
+        assert!(matches!(
+            table.lookup(&[Datum::Int(1)]).await,
+            Err(crate::Error::SchemaInvalid { .. })
+        ));
+    }
+
+    #[test]
+    fn test_candidate_files() {
+        let key_types: Vec<DataType> = vec!["INT".parse().unwrap()];
+        let key = |id: i32| {
+            let mut writer = BinaryRowWriter::new(1);
+            writer.write_int(0, id);
+            writer.build().to_serialized_bytes()
+        };
+        let file = |name: &str, level: i32, min: i32, max: i32| DataFileMeta {
+            min_key: key(min),
+            max_key: key(max),
+            level,
+            ..test_utils::data_file(name)
+        };
+        let files = vec![
+            file("l0-a", 0, 0, 100),
+            file("l0-b", 0, 50, 60),
+            file("l1-c", 1, 21, 30),
+            file("l1-a", 1, 0, 10),
+            file("l1-b", 1, 11, 20),
+            file("l2-a", 2, 0, 9),
+        ];
+        let names = |id: i32| -> Vec<String> {
+            candidate_files(files.clone(), &[Datum::Int(id)], &key_types)
+                .unwrap()
+                .into_iter()
+                .map(|file| file.file_name)
+                .collect()
+        };
+        assert_eq!(names(15), vec!["l0-a", "l1-b"]);
+        assert_eq!(names(5), vec!["l0-a", "l1-a", "l2-a"]);
+        assert_eq!(names(55), vec!["l0-a", "l0-b"]);
+        assert!(names(101).is_empty());
+    }
+}
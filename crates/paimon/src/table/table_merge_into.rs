@@ -0,0 +1,357 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::format::{to_arrow_schema, to_arrow_type, to_datum};
+use crate::spec::RowKind;
+use crate::table::{
+    BatchWriteBuilder, CommitMessage, Expression, FileStoreTable, TableCommit, TableLookup,
+    TableWrite,
+};
+use arrow_array::RecordBatch;
+use arrow_cast::cast;
+use arrow_schema::{Field, Schema};
+use arrow_select::concat::concat_batches;
+use std::sync::Arc;
+
+/// Prefix of the names of the columns of the source rows in the expressions of updates.
+pub const MERGE_SOURCE_PREFIX: &str = "source.";
+
+/// Action on the source rows whose primary keys match the rows of the table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchedAction {
+    /// Update the matched rows by assigning the values of the expressions to the columns, the
+    /// matched rows are replaced by the source rows if there is no assignment.
+    Update(Vec<(String, Expression)>),
+    /// Delete the matched rows.
+    Delete,
+}
+
+/// A merge of source rows into [`FileStoreTable`] by a commit user, like `MERGE INTO`.
+///
+/// The source rows are joined with the merged rows of the latest snapshot on the primary
+/// keys, then the matched rows are updated or deleted by the matched action, and the source
+/// rows not matched are inserted if [`with_not_matched_insert`](Self::with_not_matched_insert)
+/// is set. Source rows without an action are ignored. The expressions of updates are
+/// evaluated over the columns of the matched rows, and the columns of the source rows named
+/// with the prefix of [`MERGE_SOURCE_PREFIX`].
+///
+/// The rows changed are written as `+I`, `+U` and `-D` rows of their keys. Only primary key
+/// tables are supported, and the source rows must have all the columns of the table.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-flink/paimon-flink-common/src/main/java/org/apache/paimon/flink/action/MergeIntoAction.java>
+#[derive(Debug, Clone)]
+pub struct TableMergeInto {
+    table: FileStoreTable,
+    commit_user: String,
+    matched_action: Option<MatchedAction>,
+    not_matched_insert: bool,
+}
+
+impl TableMergeInto {
+    pub fn new(table: FileStoreTable, commit_user: impl Into<String>) -> Self {
+        Self {
+            table,
+            commit_user: commit_user.into(),
+            matched_action: None,
+            not_matched_insert: false,
+        }
+    }
+
+    /// Update the matched rows by the assignments, or replace them by the source rows if there
+    /// is no assignment.
+    pub fn with_matched_update(mut self, assignments: Vec<(String, Expression)>) -> Self {
+        self.matched_action = Some(MatchedAction::Update(assignments));
+        self
+    }
+
+    /// Delete the matched rows.
+    pub fn with_matched_delete(mut self) -> Self {
+        self.matched_action = Some(MatchedAction::Delete);
+        self
+    }
+
+    /// Insert the source rows not matched.
+    pub fn with_not_matched_insert(mut self) -> Self {
+        self.not_matched_insert = true;
+        self
+    }
+
+    /// Get the table to merge into.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the user committing the rows changed.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Merge the source rows and commit the rows changed, nothing is committed if no row is
+    /// changed.
+    pub async fn merge(&self, source: &[RecordBatch]) -> crate::Result<()> {
+        let messages = self.prepare_commit(source).await?;
+        TableCommit::new(
+            self.table.clone(),
+            self.commit_user.clone(),
+            BatchWriteBuilder::COMMIT_IDENTIFIER,
+        )
+        .commit(messages)
+        .await
+    }
+
+    /// Merge the source rows and get the files written of each bucket, without committing
+    /// them.
+    pub async fn prepare_commit(
+        &self,
+        source: &[RecordBatch],
+    ) -> crate::Result<Vec<CommitMessage>> {
+        let schema = self.table.schema();
+        if schema.primary_keys().is_empty() {
+            return WriteInvalidSnafu {
+                message: "Merging into append only tables is not supported".to_string(),
+            }
+            .fail();
+        }
+        let arrow_schema = Arc::new(to_arrow_schema(schema.fields()));
+        let key_fields: Vec<_> = schema
+            .primary_keys()
+            .iter()
+            .filter_map(|key| schema.fields().iter().position(|f| f.name() == key))
+            .collect();
+        let lookup = TableLookup::new(self.table.clone());
+
+        let (mut inserted, mut matched_sources, mut matched_targets) = (vec![], vec![], vec![]);
+        for batch in source {
+            let batch = self.align(batch, &arrow_schema)?;
+            for row in 0..batch.num_rows() {
+                let key = key_fields
+                    .iter()
+                    .map(|&index| {
+                        let field = &schema.fields()[index];
+                        to_datum(batch.column(index), row, field.data_type())?.ok_or_else(|| {
+                            WriteInvalidSnafu {
+                                message: format!(
+                                    "Primary key {} of the source is null",
+                                    field.name()
+                                ),
+                            }
+                            .build()
+                        })
+                    })
+                    .collect::<crate::Result<Vec<_>>>()?;
+                match lookup.lookup(&key).await? {
+                    Some(target) if self.matched_action.is_some() => {
+                        matched_sources.push(batch.slice(row, 1));
+                        matched_targets.push(target);
+                    }
+                    None if self.not_matched_insert => inserted.push(batch.slice(row, 1)),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut changes = vec![(concat_batches(&arrow_schema, &inserted)?, RowKind::Insert)];
+        match &self.matched_action {
+            Some(MatchedAction::Delete) => changes.push((
+                concat_batches(&arrow_schema, &matched_targets)?,
+                RowKind::Delete,
+            )),
+            Some(MatchedAction::Update(assignments)) if assignments.is_empty() => changes.push((
+                concat_batches(&arrow_schema, &matched_sources)?,
+                RowKind::UpdateAfter,
+            )),
+            Some(MatchedAction::Update(assignments)) => changes.push((
+                self.update(
+                    &concat_batches(&arrow_schema, &matched_targets)?,
+                    &concat_batches(&arrow_schema, &matched_sources)?,
+                    assignments,
+                )?,
+                RowKind::UpdateAfter,
+            )),
+            None => {}
+        }
+        let mut write = TableWrite::new(self.table.clone(), self.commit_user.clone());
+        for (batch, row_kind) in changes {
+            if batch.num_rows() > 0 {
+                write
+                    .write_with_row_kinds(&batch, &vec![row_kind; batch.num_rows()])
+                    .await?;
+            }
+        }
+        write.prepare_commit().await
+    }
+
+    /// Cast the columns of the source batch of the table's columns into the arrow schema of
+    /// the table.
+    fn align(&self, batch: &RecordBatch, arrow_schema: &Arc<Schema>) -> crate::Result<RecordBatch> {
+        let columns = self
+            .table
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| match batch.column_by_name(field.name()) {
+                Some(column) => Ok(cast(column, &to_arrow_type(field.data_type()))?),
+                None => ColumnNotExistSnafu {
+                    column: field.name().to_string(),
+                }
+                .fail(),
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(RecordBatch::try_new(arrow_schema.clone(), columns)?)
+    }
+
+    /// Update the matched rows by the assignments evaluated over them and the source rows.
+    fn update(
+        &self,
+        targets: &RecordBatch,
+        sources: &RecordBatch,
+        assignments: &[(String, Expression)],
+    ) -> crate::Result<RecordBatch> {
+        let schema = self.table.schema();
+        let mut fields: Vec<Field> = targets
+            .schema()
+            .fields()
+            .iter()
+            .map(|f| f.as_ref().clone())
+            .collect();
+        fields.extend(sources.schema().fields().iter().map(|f| {
+            f.as_ref()
+                .clone()
+                .with_name(format!("{MERGE_SOURCE_PREFIX}{}", f.name()))
+        }));
+        let mut columns = targets.columns().to_vec();
+        columns.extend(sources.columns().iter().cloned());
+        let joined = RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)?;
+
+        let mut columns = targets.columns().to_vec();
+        for (column, expression) in assignments {
+            let Some(index) = schema.fields().iter().position(|f| f.name() == column) else {
+                return ColumnNotExistSnafu {
+                    column: column.clone(),
+                }
+                .fail();
+            };
+            if schema.primary_keys().contains(column) || schema.partition_keys().contains(column) {
+                return WriteInvalidSnafu {
+                    message: format!("Can't update the primary key or partition key {column}"),
+                }
+                .fail();
+            }
+            columns[index] = expression.evaluate(&joined, schema.fields()[index].data_type())?;
+        }
+        Ok(RecordBatch::try_new(targets.schema(), columns)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::Table;
+    use crate::test_utils::{create_table, id_name_price_schema};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{Int32Array, Int64Array, StringArray};
+
+    /// Source rows whose prices are of another type than the table.
+    fn source(ids: Vec<i32>, names: Vec<&str>, prices: Vec<i64>) -> Vec<RecordBatch> {
+        let schema = Schema::new(vec![
+            Field::new("price", arrow_schema::DataType::Int64, false),
+            Field::new("name", arrow_schema::DataType::Utf8, false),
+            Field::new("id", arrow_schema::DataType::Int32, false),
+        ]);
+        vec![RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(prices)),
+                Arc::new(StringArray::from(names)),
+                Arc::new(Int32Array::from(ids)),
+            ],
+        )
+        .unwrap()]
+    }
+
+    async fn read(table: &FileStoreTable) -> Vec<(i32, String, i32)> {
+        let plan = table.new_scan().plan().await.unwrap();
+        let read = table.new_read();
+        let mut rows = Vec::new();
+        for split in plan.splits() {
+            for batch in read.read(split).await.unwrap() {
+                let batch = batch.unwrap();
+                let ids = batch.column(0).as_primitive::<Int32Type>();
+                let names = batch.column(1).as_string::<i32>();
+                let prices = batch.column(2).as_primitive::<Int32Type>();
+                rows.extend(
+                    (0..batch.num_rows())
+                        .map(|i| (ids.value(i), names.value(i).to_string(), prices.value(i))),
+                );
+            }
+        }
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_merge_into_table() {
+        let table = create_table("file:/tmp/test_merge_into_table", &id_name_price_schema()).await;
+        table
+            .new_merge_into()
+            .with_not_matched_insert()
+            .merge(&source(vec![1, 2], vec!["a", "b"], vec![10, 20]))
+            .await
+            .unwrap();
+
+        let price = Expression::Add(
+            Box::new(Expression::column("price")),
+            Box::new(Expression::column("source.price")),
+        );
+        table
+            .new_merge_into()
+            .with_matched_update(vec![("price".to_string(), price)])
+            .with_not_matched_insert()
+            .merge(&source(vec![2, 3], vec!["x", "c"], vec![5, 30]))
+            .await
+            .unwrap();
+        assert_eq!(
+            read(&table).await,
+            vec![
+                (1, "a".to_string(), 10),
+                (2, "b".to_string(), 25),
+                (3, "c".to_string(), 30)
+            ]
+        );
+
+        // the source rows not matched are ignored without inserts
+        table
+            .new_merge_into()
+            .with_matched_delete()
+            .merge(&source(vec![1, 4], vec!["", ""], vec![0, 0]))
+            .await
+            .unwrap();
+        table
+            .new_merge_into()
+            .with_matched_update(vec![])
+            .merge(&source(vec![3, 5], vec!["z", "e"], vec![0, 0]))
+            .await
+            .unwrap();
+        assert_eq!(
+            read(&table).await,
+            vec![(2, "b".to_string(), 25), (3, "z".to_string(), 0)]
+        );
+    }
+}
@@ -0,0 +1,954 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::deletion_vectors::DeletionVector;
+use crate::error::*;
+use crate::file_index::{
+    FileIndexFormatReader, FileIndexPredicate, FileIndexResult, INDEX_PATH_SUFFIX,
+};
+use crate::format::{to_arrow_schema, ArrowRecordBatchIter, DataFileReader};
+use crate::memory::MemoryPool;
+use crate::mergetree::MergeTreeReader;
+use crate::metrics::READ_BYTES;
+use crate::predicate::Predicate;
+use crate::spec::{key_value_fields, DataField, DataFileMeta, DataType, RowKind, RowType};
+use crate::table::{DataSplit, FileStoreTable};
+use arrow_array::cast::AsArray;
+use arrow_array::types::Int8Type;
+use arrow_array::{RecordBatch, StringArray};
+use arrow_schema::{DataType as ArrowDataType, Field, Schema, SchemaRef};
+use bytes::Bytes;
+use futures::future::try_join_all;
+use roaring::RoaringBitmap;
+use std::sync::Arc;
+
+/// Iterator of the record batches read with the row kinds of their rows.
+pub type RowKindRecordBatchIter =
+    Box<dyn Iterator<Item = crate::Result<(RecordBatch, Vec<RowKind>)>> + Send>;
+
+/// A read of [`FileStoreTable`] to read the planned splits.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/source/InnerTableRead.java>
+#[derive(Debug, Clone)]
+pub struct TableRead {
+    table: FileStoreTable,
+    projection: Option<Vec<String>>,
+    row_kind_column: Option<String>,
+    limit: Option<usize>,
+    filter: Option<Predicate>,
+    memory_pool: Arc<MemoryPool>,
+}
+
+impl TableRead {
+    pub fn new(table: FileStoreTable) -> Self {
+        let memory_pool = MemoryPool::new(table.core_options().memory_pool_size() as usize);
+        Self {
+            table,
+            projection: None,
+            row_kind_column: None,
+            limit: None,
+            filter: None,
+            memory_pool: Arc::new(memory_pool),
+        }
+    }
+
+    /// Only read the given fields, in the given order.
+    ///
+    /// The fields of row columns can be selected by their paths like `a.b.c`, the row types of
+    /// the columns are pruned to the selected fields, so that only they are read from the files.
+    pub fn with_projection(mut self, fields: &[&str]) -> Self {
+        self.projection = Some(fields.iter().map(|f| f.to_string()).collect());
+        self
+    }
+
+    /// Append a column of the given name to the rows read, holding the short strings of their
+    /// row kinds, `+I`, `-U`, `+U` or `-D`.
+    pub fn with_row_kind_column(mut self, name: impl Into<String>) -> Self {
+        self.row_kind_column = Some(name.into());
+        self
+    }
+
+    /// Stop reading a split once `limit` rows are read from it.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// The limit of the rows read from a split.
+    pub(crate) fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+
+    /// Skip the data files whose file indexes show that no row of them matches the predicate on
+    /// the fields of the table, the rows read are not filtered.
+    ///
+    /// File indexes are only used by the files read without merging, unless
+    /// `file-index.read.enabled` is false.
+    pub fn with_filter(mut self, predicate: Predicate) -> Self {
+        self.filter = Some(predicate);
+        self
+    }
+
+    /// Share the memory pool with the other operators, which reserves the data files read by
+    /// this read. Defaults to a pool of `memory-pool.size` of its own.
+    pub fn with_memory_pool(mut self, memory_pool: Arc<MemoryPool>) -> Self {
+        self.memory_pool = memory_pool;
+        self
+    }
+
+    /// Get the memory pool of the data files read.
+    pub fn memory_pool(&self) -> &Arc<MemoryPool> {
+        &self.memory_pool
+    }
+
+    /// Get the table to read.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the row type of the rows read, with the projection applied.
+    pub fn read_type(&self) -> crate::Result<RowType> {
+        let row_type = self.table.schema().logical_row_type();
+        let Some(projection) = &self.projection else {
+            return Ok(row_type);
+        };
+        // the paths of the nested fields to read of the top-level fields, in the projection order
+        let mut paths: Vec<(usize, Vec<Vec<&str>>)> = Vec::new();
+        for name in projection {
+            let (index, path) = match row_type.field_index(name) {
+                Some(index) => (index, vec![]),
+                None => {
+                    let mut path = name.split('.');
+                    let root = path.next().unwrap_or_default();
+                    let Some(index) = row_type.field_index(root) else {
+                        return ColumnNotExistSnafu {
+                            column: name.clone(),
+                        }
+                        .fail();
+                    };
+                    (index, path.collect())
+                }
+            };
+            match paths.iter_mut().find(|(i, _)| *i == index) {
+                Some((_, field_paths)) => field_paths.push(path),
+                None => paths.push((index, vec![path])),
+            }
+        }
+        let fields = paths
+            .iter()
+            .map(|(index, field_paths)| {
+                let field = &row_type.fields()[*index];
+                let field_paths: Vec<&[&str]> = field_paths.iter().map(Vec::as_slice).collect();
+                prune_field(field, field.name(), &field_paths)
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        Ok(RowType::new(fields))
+    }
+
+    /// Get the arrow schema of the batches read, the fields of [`Self::read_type`] followed by
+    /// the row kind column if any.
+    pub fn arrow_schema(&self) -> crate::Result<SchemaRef> {
+        let mut fields = to_arrow_schema(self.read_type()?.fields())
+            .fields()
+            .to_vec();
+        if let Some(name) = &self.row_kind_column {
+            if fields.iter().any(|field| field.name() == name) {
+                return ColumnAlreadyExistSnafu {
+                    column: name.clone(),
+                }
+                .fail();
+            }
+            fields.push(Arc::new(Field::new(name, ArrowDataType::Utf8, false)));
+        }
+        Ok(Arc::new(Schema::new(fields)))
+    }
+
+    /// Create a reader of the data files of this table, reading the fields of [`Self::read_type`].
+    pub fn data_file_reader(&self) -> crate::Result<DataFileReader> {
+        Ok(DataFileReader::new(
+            self.table.file_io().clone(),
+            self.table.schema_manager(),
+            self.table.schema().clone(),
+            self.read_type()?.fields().to_vec(),
+        )
+        .with_memory_pool(self.memory_pool.clone()))
+    }
+
+    /// Create a reader of the data files of this primary key table, merging the key values by
+    /// the merge engine, reading the fields of [`Self::read_type`].
+    pub fn merge_tree_reader(&self) -> crate::Result<MergeTreeReader> {
+        MergeTreeReader::new(
+            self.table.file_io().clone(),
+            self.table.schema_manager(),
+            self.table.schema().clone(),
+            self.read_type()?.fields().to_vec(),
+        )
+        .map(|reader| reader.with_memory_pool(self.memory_pool.clone()))
+    }
+
+    /// Read the rows of the split, the key values of primary key tables are merged unless the
+    /// split is raw convertible or streaming.
+    ///
+    /// The files of raw convertible splits are read without merging, they are loaded
+    /// concurrently with the deleted rows of their deletion vectors filtered out, and the rows
+    /// are in the order of the files in the split.
+    ///
+    /// The row kinds of the rows are appended if [`Self::with_row_kind_column`] is set.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "read.split",
+            skip_all,
+            fields(
+                snapshot_id = split.snapshot_id(),
+                bucket = split.bucket(),
+                file_count = split.data_files().len(),
+            )
+        )
+    )]
+    pub async fn read(&self, split: &DataSplit) -> crate::Result<ArrowRecordBatchIter> {
+        let batches = self.read_with_row_kind_column(split).await?;
+        let Some(limit) = self.limit else {
+            return Ok(batches);
+        };
+        let mut remaining = limit;
+        Ok(Box::new(batches.map_while(move |batch| {
+            if remaining == 0 {
+                return None;
+            }
+            Some(batch.map(|batch| {
+                let batch = batch.slice(0, batch.num_rows().min(remaining));
+                remaining -= batch.num_rows();
+                batch
+            }))
+        })))
+    }
+
+    /// Read the rows of the split, with the row kind column if set.
+    async fn read_with_row_kind_column(
+        &self,
+        split: &DataSplit,
+    ) -> crate::Result<ArrowRecordBatchIter> {
+        if self.row_kind_column.is_none() {
+            return self.read_rows(split).await;
+        }
+        let schema = self.arrow_schema()?;
+        let batches = self.read_with_row_kinds(split).await?;
+        Ok(Box::new(batches.map(move |batch| {
+            let (batch, row_kinds) = batch?;
+            let row_kinds =
+                StringArray::from_iter_values(row_kinds.iter().map(RowKind::short_string));
+            let mut columns = batch.columns().to_vec();
+            columns.push(Arc::new(row_kinds));
+            Ok(RecordBatch::try_new(schema.clone(), columns)?)
+        })))
+    }
+
+    /// Read the rows of the split without the row kind column.
+    async fn read_rows(&self, split: &DataSplit) -> crate::Result<ArrowRecordBatchIter> {
+        let primary_keyed = !self.table.schema().primary_keys().is_empty();
+        let metrics = self.table.metrics();
+        if primary_keyed && (split.is_streaming() || !split.raw_convertible()) {
+            let bytes = split.data_files().iter().map(|file| file.file_size as u64);
+            metrics.increment_counter(READ_BYTES, bytes.sum());
+        }
+        if primary_keyed && split.is_streaming() {
+            let batches = self.read_key_values(split).await?;
+            return Ok(Box::new(batches.map(|batch| batch.map(|(batch, _)| batch))));
+        }
+        if primary_keyed && !split.before_files().is_empty() {
+            let batches = self.read_diff(split).await?;
+            return Ok(Box::new(batches.map(|batch| batch.map(|(batch, _)| batch))));
+        }
+        if primary_keyed && !split.raw_convertible() {
+            return self.merge_tree_reader()?.read(split).await;
+        }
+        let reader = self.data_file_reader()?;
+        let file_io = self.table.file_io();
+        let batches = try_join_all(split.data_files().iter().enumerate().map(|(i, file)| {
+            let reader = &reader;
+            let metrics = &metrics;
+            async move {
+                // rows not selected by the bitmaps of the file index are deleted on read
+                let mut deletion_vector = match self.evaluate_file_index(split, file).await? {
+                    FileIndexResult::Skip => {
+                        return Ok(Box::new(std::iter::empty()) as ArrowRecordBatchIter)
+                    }
+                    FileIndexResult::Remain => None,
+                    FileIndexResult::Bitmap(selected) => {
+                        let mut unselected = RoaringBitmap::new();
+                        unselected.insert_range(0..file.row_count as u32);
+                        Some(DeletionVector::from(unselected - selected))
+                    }
+                };
+                if let Some(deletion_file) = split.deletion_file(i) {
+                    let deleted = DeletionVector::read(file_io.as_ref(), deletion_file).await?;
+                    match &mut deletion_vector {
+                        Some(deletion_vector) => deletion_vector.merge(&deleted),
+                        None => deletion_vector = Some(deleted),
+                    }
+                }
+                metrics.increment_counter(READ_BYTES, file.file_size as u64);
+                let batches = reader.read(&split.data_file_path(file), file).await?;
+                let batches = match deletion_vector {
+                    Some(deletion_vector) => deletion_vector.apply(batches),
+                    None => batches,
+                };
+                Ok::<_, crate::Error>(batches)
+            }
+        }))
+        .await?;
+        Ok(Box::new(batches.into_iter().flatten()))
+    }
+
+    /// Test the rows of the data file matching the filter by its file index embedded in the
+    /// meta or in the extra file of [`INDEX_PATH_SUFFIX`], the file is skipped or its rows are
+    /// selected by the bitmaps of the index.
+    async fn evaluate_file_index(
+        &self,
+        split: &DataSplit,
+        file: &DataFileMeta,
+    ) -> crate::Result<FileIndexResult> {
+        let Some(filter) = &self.filter else {
+            return Ok(FileIndexResult::Remain);
+        };
+        // the fields of the filter may not be in files of other schemas by the same names
+        if file.schema_id != self.table.schema().id()
+            || !self.table.core_options().file_index_read_enabled()
+        {
+            return Ok(FileIndexResult::Remain);
+        }
+        let file_index = if let Some(bytes) = &file.embedded_index {
+            FileIndexFormatReader::get_file_index_of_bytes(Bytes::from(bytes.clone())).await?
+        } else if let Some(name) = file
+            .extra_files
+            .iter()
+            .find(|name| name.ends_with(INDEX_PATH_SUFFIX))
+        {
+            let input = self
+                .table
+                .file_io()
+                .new_input(&format!("{}/{name}", split.bucket_path()))?;
+            FileIndexFormatReader::get_file_index(input).await?
+        } else {
+            return Ok(FileIndexResult::Remain);
+        };
+        Ok(FileIndexPredicate::new(&file_index, filter)
+            .await?
+            .evaluate(filter))
+    }
+
+    /// Read the rows of the split with their row kinds, without the row kind column.
+    ///
+    /// The key values of the streaming splits of primary key tables are read in the order they
+    /// are written with their value kinds, the changes of the diff splits are read with the
+    /// kinds of the changes, the rows of other splits are all inserted rows.
+    pub async fn read_with_row_kinds(
+        &self,
+        split: &DataSplit,
+    ) -> crate::Result<RowKindRecordBatchIter> {
+        let primary_keyed = !self.table.schema().primary_keys().is_empty();
+        if primary_keyed && split.is_streaming() {
+            return self.read_key_values(split).await;
+        }
+        if primary_keyed && !split.before_files().is_empty() {
+            return self.read_diff(split).await;
+        }
+        let batches = self.read_rows(split).await?;
+        Ok(Box::new(batches.map(|batch| {
+            batch.map(|batch| {
+                let row_kinds = vec![RowKind::Insert; batch.num_rows()];
+                (batch, row_kinds)
+            })
+        })))
+    }
+
+    /// Read the changes of the diff split, all the fields are compared to find the changed
+    /// rows while only the read fields are returned.
+    async fn read_diff(&self, split: &DataSplit) -> crate::Result<RowKindRecordBatchIter> {
+        let mut fields = self.read_type()?.fields().to_vec();
+        let read_count = fields.len();
+        for field in self.table.schema().fields() {
+            if !fields.iter().any(|f| f.id() == field.id()) {
+                fields.push(field.clone());
+            }
+        }
+        let reader = MergeTreeReader::new(
+            self.table.file_io().clone(),
+            self.table.schema_manager(),
+            self.table.schema().clone(),
+            fields,
+        )?
+        .with_memory_pool(self.memory_pool.clone());
+        let projection: Vec<usize> = (0..read_count).collect();
+        let batches = reader.read_diff(split).await?;
+        Ok(Box::new(batches.map(move |batch| {
+            let (batch, row_kinds) = batch?;
+            Ok((batch.project(&projection)?, row_kinds))
+        })))
+    }
+
+    /// Read the key values of the files of the split without merging.
+    async fn read_key_values(&self, split: &DataSplit) -> crate::Result<RowKindRecordBatchIter> {
+        let schema = self.table.schema();
+        let key_fields = schema.trimmed_primary_key_fields();
+        let read_fields = self.read_type()?.fields().to_vec();
+        let reader = DataFileReader::new(
+            self.table.file_io().clone(),
+            self.table.schema_manager(),
+            schema.clone(),
+            key_value_fields(&key_fields, &read_fields),
+        )
+        .with_memory_pool(self.memory_pool.clone());
+        // the value kind follows the key fields and the sequence number
+        let value_kind = key_fields.len() + 1;
+        let projection: Vec<usize> = (value_kind + 1..value_kind + 1 + read_fields.len()).collect();
+        let mut batches = Vec::new();
+        for file in split.data_files() {
+            for batch in reader.read(&split.data_file_path(file), file).await? {
+                let batch = batch?;
+                let row_kinds = batch
+                    .column(value_kind)
+                    .as_primitive::<Int8Type>()
+                    .values()
+                    .iter()
+                    .map(|value| RowKind::from_value(*value))
+                    .collect::<crate::Result<Vec<_>>>()?;
+                batches.push(Ok((batch.project(&projection)?, row_kinds)));
+            }
+        }
+        Ok(Box::new(batches.into_iter()))
+    }
+}
+
+/// Prune the row type of the field to the nested fields of the paths, an empty path selects
+/// the whole field.
+fn prune_field(field: &DataField, name: &str, paths: &[&[&str]]) -> crate::Result<DataField> {
+    if paths.iter().any(|path| path.is_empty()) {
+        return Ok(field.clone());
+    }
+    let row = match field.data_type() {
+        DataType::Row(row) => row,
+        _ => {
+            return ColumnNotExistSnafu {
+                column: format!("{name}.{}", paths[0].join(".")),
+            }
+            .fail()
+        }
+    };
+    if let Some(path) = paths.iter().find(|path| row.field_index(path[0]).is_none()) {
+        return ColumnNotExistSnafu {
+            column: format!("{name}.{}", path.join(".")),
+        }
+        .fail();
+    }
+    let fields = row
+        .fields()
+        .iter()
+        .filter_map(|nested| {
+            let nested_paths: Vec<&[&str]> = paths
+                .iter()
+                .filter(|path| path[0] == nested.name())
+                .map(|path| &path[1..])
+                .collect();
+            if nested_paths.is_empty() {
+                return None;
+            }
+            let nested_name = format!("{name}.{}", nested.name());
+            Some(prune_field(nested, &nested_name, &nested_paths))
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+    let row = RowType::with_nullable(field.data_type().is_nullable(), fields);
+    Ok(field.clone().with_data_type(DataType::Row(row)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::deletion_vectors::DeletionVectorsIndexFile;
+    use crate::file_index::{fast_hash, write_column_indexes, BloomFilter64, BLOOM_FILTER};
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIO;
+    use crate::io::FileIOBuilder;
+    use crate::predicate::PredicateBuilder;
+    use crate::spec::{BinaryRow, DataField, DataFileMeta, Datum, Schema};
+    use crate::table::{DeletionFile, Table};
+    use crate::test_utils::data_file;
+    use crate::utils::SchemaManager;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{Array, Int32Array, RecordBatch, StringArray, StructArray};
+    use arrow_schema::DataType as ArrowDataType;
+    use bytes::Bytes;
+
+    use parquet::arrow::ArrowWriter;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_read_append_only() {
+        let location = "file:/tmp/test_table_read_append_only";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = create_schema(&file_io, location).await;
+        let table = FileStoreTable::new(
+            file_io.clone(),
+            Identifier::new("db", "t"),
+            location,
+            schema.clone(),
+        );
+
+        // bucket unaware tables write the files into bucket 0
+        let bucket_path = format!("{location}/bucket-0");
+        for (name, ids) in [("data-0.parquet", [1, 2]), ("data-1.parquet", [3, 4])] {
+            let batch = RecordBatch::try_new(
+                Arc::new(to_arrow_schema(schema.fields())),
+                vec![
+                    Arc::new(Int32Array::from(ids.to_vec())),
+                    Arc::new(StringArray::from_iter_values(
+                        ids.iter().map(|id| format!("name-{id}")),
+                    )),
+                ],
+            )
+            .unwrap();
+            let mut bytes = Vec::new();
+            let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), None).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+            file_io
+                .new_output(&format!("{bucket_path}/{name}"))
+                .unwrap()
+                .write(Bytes::from(bytes))
+                .await
+                .unwrap();
+        }
+        let split = || {
+            DataSplit::builder()
+                .snapshot_id(1)
+                .partition(BinaryRow::new(0))
+                .bucket(0)
+                .bucket_path(bucket_path.clone())
+                .data_files(
+                    ["data-1.parquet", "data-0.parquet"]
+                        .map(|name| DataFileMeta {
+                            row_count: 2,
+                            max_sequence_number: 1,
+                            ..data_file(name)
+                        })
+                        .to_vec(),
+                )
+        };
+        let read = TableRead::new(table.clone()).with_projection(&["name"]);
+        assert_eq!(
+            read_names(&read, &split().build()).await,
+            vec!["name-3", "name-4", "name-1", "name-2"]
+        );
+
+        // the first row of data-1.parquet is deleted by the deletion vector
+        let mut deletion_vector = DeletionVector::new();
+        deletion_vector.delete(0);
+        let index_file = DeletionVectorsIndexFile::new(file_io.clone(), table.path_factory())
+            .write(&[("data-1.parquet".to_string(), deletion_vector)].into())
+            .await
+            .unwrap();
+        let (offset, length) = index_file.deletion_vectors_ranges.unwrap()["data-1.parquet"];
+        let deletion_file = DeletionFile::new(
+            table.path_factory().index_path(&index_file.file_name),
+            offset as i64,
+            length as i64,
+        );
+        let split = split()
+            .data_deletion_files(vec![Some(deletion_file), None])
+            .raw_convertible(true)
+            .build();
+        assert_eq!(
+            read_names(&read, &split).await,
+            vec!["name-4", "name-1", "name-2"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_read_nested_projection() {
+        for format in ["parquet", "avro"] {
+            let location = format!("file:/tmp/test_table_read_nested_projection_{format}");
+            let file_io = FileIOBuilder::from_url(&location).unwrap().build().unwrap();
+            let _ = file_io.delete_dir(&format!("{location}/")).await;
+            let schema = SchemaManager::new(file_io.clone(), &location)
+                .create_table(
+                    &Schema::builder()
+                        .fields(vec![
+                            DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+                            DataField::new(
+                                1,
+                                "info".to_string(),
+                                DataType::Row(RowType::new(vec![
+                                    DataField::new(2, "a".to_string(), "INT".parse().unwrap()),
+                                    DataField::new(3, "b".to_string(), "STRING".parse().unwrap()),
+                                ])),
+                            ),
+                        ])
+                        .options([("file.format".to_string(), format.to_string())].into())
+                        .build(),
+                )
+                .await
+                .unwrap();
+            let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), &location, schema);
+            let arrow_schema = Arc::new(to_arrow_schema(table.schema().fields()));
+            let ArrowDataType::Struct(info_fields) = arrow_schema.field(1).data_type().clone()
+            else {
+                unreachable!()
+            };
+            let info = StructArray::try_new(
+                info_fields,
+                vec![
+                    Arc::new(Int32Array::from(vec![10, 20])),
+                    Arc::new(StringArray::from(vec!["x", "y"])),
+                ],
+                None,
+            )
+            .unwrap();
+            let batch = RecordBatch::try_new(
+                arrow_schema,
+                vec![Arc::new(Int32Array::from(vec![1, 2])), Arc::new(info)],
+            )
+            .unwrap();
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            write.write(&batch).await.unwrap();
+            let messages = write.prepare_commit().await.unwrap();
+            builder.new_commit().commit(messages).await.unwrap();
+
+            let read = table.new_read().with_projection(&["info.b", "id"]);
+            let read_type = read.read_type().unwrap();
+            assert_eq!(read_type.field_names(), vec!["info", "id"]);
+            assert_eq!(
+                read_type.fields()[0].data_type().to_string(),
+                "ROW<`b` STRING>"
+            );
+            let mut batches = Vec::new();
+            for split in table.new_scan().plan().await.unwrap().splits() {
+                for batch in read.read(split).await.unwrap() {
+                    batches.push(batch.unwrap());
+                }
+            }
+            assert_eq!(batches.len(), 1);
+            assert_eq!(batches[0].schema(), read.arrow_schema().unwrap());
+            let info = batches[0].column(0).as_struct();
+            assert_eq!(info.num_columns(), 1);
+            assert_eq!(
+                info.column(0).as_string::<i32>(),
+                &StringArray::from(vec!["x", "y"])
+            );
+
+            for column in ["info.c", "id.a", "info.b.c"] {
+                assert!(matches!(
+                    table.new_read().with_projection(&[column]).read_type(),
+                    Err(crate::Error::ColumnNotExist { .. })
+                ));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_with_file_index() {
+        let location = "file:/tmp/test_table_read_with_file_index";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = create_schema(&file_io, location).await;
+        let table = FileStoreTable::new(
+            file_io.clone(),
+            Identifier::new("db", "t"),
+            location,
+            schema.clone(),
+        );
+
+        let bucket_path = format!("{location}/bucket-0");
+        let mut files = Vec::new();
+        for (name, ids) in [("data-0.parquet", [1, 2]), ("data-1.parquet", [3, 4])] {
+            let batch = RecordBatch::try_new(
+                Arc::new(to_arrow_schema(schema.fields())),
+                vec![
+                    Arc::new(Int32Array::from(ids.to_vec())),
+                    Arc::new(StringArray::from_iter_values(
+                        ids.iter().map(|id| format!("name-{id}")),
+                    )),
+                ],
+            )
+            .unwrap();
+            let mut bytes = Vec::new();
+            let mut writer = ArrowWriter::try_new(&mut bytes, batch.schema(), None).unwrap();
+            writer.write(&batch).unwrap();
+            writer.close().unwrap();
+            file_io
+                .new_output(&format!("{bucket_path}/{name}"))
+                .unwrap()
+                .write(Bytes::from(bytes))
+                .await
+                .unwrap();
+
+            let mut filter = BloomFilter64::new(100, 0.01);
+            for id in ids {
+                filter.add_hash(fast_hash(&Datum::Int(id)).unwrap());
+            }
+            let index_name = format!("{name}{INDEX_PATH_SUFFIX}");
+            let index_path = format!("{bucket_path}/{index_name}");
+            let indexes = HashMap::from([(
+                "id".to_string(),
+                HashMap::from([(BLOOM_FILTER.to_string(), filter.to_bytes())]),
+            )]);
+            write_column_indexes(file_io.as_ref(), &index_path, indexes)
+                .await
+                .unwrap();
+            // the index of data-0.parquet is in an extra file, the other is embedded
+            let mut file = DataFileMeta {
+                row_count: 2,
+                max_sequence_number: 1,
+                ..data_file(name)
+            };
+            if files.is_empty() {
+                file.extra_files = vec![index_name];
+            } else {
+                let index = file_io
+                    .new_input(&index_path)
+                    .unwrap()
+                    .read()
+                    .await
+                    .unwrap();
+                file.embedded_index = Some(index.to_vec());
+            }
+            files.push(file);
+        }
+        let split = DataSplit::builder()
+            .snapshot_id(1)
+            .partition(BinaryRow::new(0))
+            .bucket(0)
+            .bucket_path(bucket_path.clone())
+            .data_files(files)
+            .build();
+
+        let builder = PredicateBuilder::new(schema.logical_row_type());
+        let read = |predicate| {
+            TableRead::new(table.clone())
+                .with_projection(&["name"])
+                .with_filter(predicate)
+        };
+        let names = read_names(&read(builder.equal(0, Datum::Int(2))), &split).await;
+        assert_eq!(names, vec!["name-1", "name-2"]);
+        let predicate = builder.is_in(0, vec![Datum::Int(4), Datum::Int(7)]);
+        let names = read_names(&read(predicate), &split).await;
+        assert_eq!(names, vec!["name-3", "name-4"]);
+        let predicate = PredicateBuilder::or([
+            builder.equal(0, Datum::Int(1)),
+            builder.equal(0, Datum::Int(3)),
+        ]);
+        assert_eq!(read_names(&read(predicate), &split).await.len(), 4);
+        // functions and fields without indexes never skip files
+        let predicate = PredicateBuilder::and([
+            builder.greater_than(0, Datum::Int(100)),
+            builder.equal(1, Datum::String("name-5".to_string())),
+        ]);
+        assert_eq!(read_names(&read(predicate), &split).await.len(), 4);
+        assert!(read_names(&read(builder.equal(0, Datum::Int(5))), &split)
+            .await
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_read_with_written_file_index() {
+        for threshold in ["0", "1 kb"] {
+            let location = format!("file:/tmp/test_table_read_with_written_file_index_{threshold}");
+            let file_io = FileIOBuilder::from_url(&location).unwrap().build().unwrap();
+            let _ = file_io.delete_dir(&format!("{location}/")).await;
+            let schema = SchemaManager::new(file_io.clone(), &location)
+                .create_table(
+                    &Schema::builder()
+                        .fields(vec![
+                            DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+                            DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                        ])
+                        .options(
+                            [
+                                ("bucket", "-1"),
+                                ("file.format", "parquet"),
+                                ("file-index.bloom-filter.columns", "id"),
+                                ("file-index.bloom-filter.id.items", "100"),
+                                ("file-index.in-manifest-threshold", threshold),
+                            ]
+                            .into_iter()
+                            .map(|(k, v)| (k.to_string(), v.to_string()))
+                            .collect(),
+                        )
+                        .build(),
+                )
+                .await
+                .unwrap();
+            let table = FileStoreTable::new(
+                file_io.clone(),
+                Identifier::new("db", "t"),
+                &location,
+                schema,
+            );
+            for ids in [[1, 2], [3, 4]] {
+                let batch = RecordBatch::try_new(
+                    Arc::new(to_arrow_schema(table.schema().fields())),
+                    vec![
+                        Arc::new(Int32Array::from(ids.to_vec())),
+                        Arc::new(StringArray::from_iter_values(
+                            ids.iter().map(|id| format!("name-{id}")),
+                        )),
+                    ],
+                )
+                .unwrap();
+                let builder = table.new_batch_write_builder();
+                let mut write = builder.new_write();
+                write.write(&batch).await.unwrap();
+                let messages = write.prepare_commit().await.unwrap();
+                builder.new_commit().commit(messages).await.unwrap();
+            }
+
+            let plan = table.new_scan().plan().await.unwrap();
+            let split = &plan.splits()[0];
+            for file in split.data_files() {
+                if threshold == "0" {
+                    assert_eq!(file.extra_files, vec![format!("{}.index", file.file_name)]);
+                    assert!(file.embedded_index.is_none());
+                } else {
+                    assert!(file.extra_files.is_empty());
+                    assert!(file.embedded_index.is_some());
+                }
+            }
+            let builder = PredicateBuilder::new(table.schema().logical_row_type());
+            let read = table
+                .new_read()
+                .with_projection(&["name"])
+                .with_filter(builder.equal(0, Datum::Int(3)));
+            assert_eq!(read_names(&read, split).await, vec!["name-3", "name-4"]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_with_bitmap_file_index() {
+        let location = "file:/tmp/test_table_read_with_bitmap_file_index";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+                        DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                    ])
+                    .options(
+                        [
+                            ("bucket", "-1"),
+                            ("file.format", "parquet"),
+                            ("file-index.bitmap.columns", "name"),
+                        ]
+                        .into_iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                    )
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(
+            file_io.clone(),
+            Identifier::new("db", "t"),
+            location,
+            schema,
+        );
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5])),
+                Arc::new(StringArray::from(vec!["a", "b", "a", "c", "b"])),
+            ],
+        )
+        .unwrap();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write.write(&batch).await.unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+
+        let plan = table.new_scan().plan().await.unwrap();
+        let split = &plan.splits()[0];
+        let builder = PredicateBuilder::new(table.schema().logical_row_type());
+        let string = |v: &str| Datum::String(v.to_string());
+        let read_ids = |filter: Predicate| {
+            let read = table
+                .new_read()
+                .with_projection(&["id"])
+                .with_filter(filter);
+            async move {
+                read.read(split)
+                    .await
+                    .unwrap()
+                    .flat_map(|batch| {
+                        let batch = batch.unwrap();
+                        let column = batch.column(0).as_primitive::<Int32Type>().clone();
+                        column.values().to_vec()
+                    })
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(read_ids(builder.equal(1, string("a"))).await, vec![1, 3]);
+        assert_eq!(
+            read_ids(builder.is_in(1, vec![string("b"), string("c")])).await,
+            vec![2, 4, 5]
+        );
+        assert!(read_ids(builder.equal(1, string("d"))).await.is_empty());
+        // predicates not tested by the index keep all the rows
+        assert_eq!(
+            read_ids(builder.greater_than(0, Datum::Int(3))).await,
+            vec![1, 2, 3, 4, 5]
+        );
+    }
+
+    async fn read_names(read: &TableRead, split: &DataSplit) -> Vec<String> {
+        let batches = read
+            .read(split)
+            .await
+            .unwrap()
+            .collect::<crate::Result<Vec<_>>>()
+            .unwrap();
+        batches
+            .iter()
+            .flat_map(|batch| {
+                let column = batch.column(0).as_any().downcast_ref::<StringArray>();
+                column.unwrap().iter().map(|name| name.unwrap().to_string())
+            })
+            .collect()
+    }
+
+    async fn create_schema(file_io: &Arc<dyn FileIO>, location: &str) -> crate::spec::TableSchema {
+        SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "id".to_string(), "INT".parse().unwrap()),
+                        DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                    ])
+                    .options([("file.format".to_string(), "parquet".to_string())].into())
+                    .build(),
+            )
+            .await
+            .unwrap()
+    }
+}
@@ -0,0 +1,1471 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::deletion_vectors::DELETION_VECTORS_INDEX;
+use crate::error::*;
+use crate::manifest::ManifestList;
+use crate::metrics::{
+    SCAN_DURATION, SCAN_FILES_PRUNED, SCAN_MANIFESTS_READ, SCAN_MANIFESTS_SKIPPED,
+};
+use crate::predicate::{PartitionPredicate, PredicateBuilder};
+use crate::spec::{
+    BinaryRow, ChangelogProducer, CommitKind, CoreOptions, DataFileMeta, Datum, FileKind,
+    Identifier, ManifestEntry, ManifestFileMeta, Snapshot,
+};
+use crate::table::split_generator::split_append_only_files;
+use crate::table::{DataSplit, DeletionFile, FileStoreTable, PartitionEntry, Plan};
+use crate::utils::record_span;
+use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+/// A scan of [`FileStoreTable`] to plan the splits to read.
+///
+/// Manifests are skipped by the partition stats and the bucket range recorded in the manifest
+/// list before being read, then entries are filtered by their partition and bucket.
+///
+/// The files of a bucket of append only tables, including the bucket unaware ones, are packed
+/// into splits of `source.split.target-size` in the order of sequence numbers.
+///
+/// With `deletion-vectors.enabled`, the deletion files of the data files are looked up in the
+/// index manifest of the snapshot, and the level 0 files of primary key tables are skipped so
+/// that the other files are read without merging.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/operation/AbstractFileStoreScan.java>
+#[derive(Debug, Clone)]
+pub struct TableScan {
+    table: FileStoreTable,
+    snapshot_id: Option<i64>,
+    timestamp_millis: Option<u64>,
+    tag_name: Option<String>,
+    partition_filter: HashMap<String, Datum>,
+    partition_predicate: Option<PartitionPredicate>,
+    bucket: Option<i32>,
+    limit: Option<usize>,
+}
+
+/// Limit the splits of the plan to the raw convertible ones with at least `limit` rows, unless
+/// they don't have enough rows.
+fn apply_limit(plan: Plan, limit: i64) -> Plan {
+    let snapshot_id = plan.snapshot_id();
+    if limit == 0 {
+        return Plan::new(snapshot_id, vec![]);
+    }
+    let mut row_count = 0;
+    let mut splits = Vec::new();
+    for split in plan.splits().iter().filter(|split| split.raw_convertible()) {
+        let mut split = split.clone();
+        let files = (0..split.data_files().len())
+            .position(|index| {
+                row_count += split.merged_row_count(index);
+                row_count >= limit
+            })
+            .map(|index| index + 1);
+        if let Some(files) = files {
+            split.truncate(files);
+            splits.push(split);
+            return Plan::new(snapshot_id, splits);
+        }
+        splits.push(split);
+    }
+    plan
+}
+
+/// Group the data files of the entries by their partitions and buckets, in the order of entries.
+pub(crate) fn group_by_bucket(
+    entries: Vec<ManifestEntry>,
+) -> IndexMap<(Vec<u8>, i32), Vec<DataFileMeta>> {
+    let mut buckets: IndexMap<_, Vec<_>> = IndexMap::new();
+    for entry in entries {
+        buckets
+            .entry((entry.partition().clone(), entry.bucket()))
+            .or_default()
+            .push(entry.file().clone());
+    }
+    buckets
+}
+
+impl TableScan {
+    pub fn new(table: FileStoreTable) -> Self {
+        Self {
+            table,
+            snapshot_id: None,
+            timestamp_millis: None,
+            tag_name: None,
+            partition_filter: HashMap::new(),
+            partition_predicate: None,
+            bucket: None,
+            limit: None,
+        }
+    }
+
+    /// Scan the snapshot with the given id instead of the latest one.
+    pub fn as_of_snapshot(mut self, snapshot_id: i64) -> Self {
+        self.snapshot_id = Some(snapshot_id);
+        self
+    }
+
+    /// Scan the latest snapshot committed at or before the given time in milliseconds instead
+    /// of the latest one, nothing is scanned if there is no such snapshot.
+    pub fn as_of_timestamp(mut self, timestamp_millis: u64) -> Self {
+        self.timestamp_millis = Some(timestamp_millis);
+        self
+    }
+
+    /// Scan the snapshot tagged by the given name instead of the latest one.
+    pub fn with_tag(mut self, tag_name: impl Into<String>) -> Self {
+        self.tag_name = Some(tag_name.into());
+        self
+    }
+
+    /// Only scan the partitions whose values equal to the given ones.
+    pub fn with_partition_filter(mut self, partition: HashMap<String, Datum>) -> Self {
+        self.partition_filter = partition;
+        self
+    }
+
+    /// Only scan the partitions matching the predicate, besides the partition filter.
+    pub fn with_partition_predicate(mut self, predicate: PartitionPredicate) -> Self {
+        self.partition_predicate = Some(predicate);
+        self
+    }
+
+    /// Only scan the given bucket.
+    pub fn with_bucket(mut self, bucket: i32) -> Self {
+        self.bucket = Some(bucket);
+        self
+    }
+
+    /// Stop planning once the splits have at least `limit` rows, which are counted by the row
+    /// counts of the data files of the splits that can be read without merging.
+    ///
+    /// The data files of these splits past the limit are skipped, the plan is not limited if
+    /// the rows are not enough.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/source/DataTableBatchScan.java>
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Get the table to scan.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Whether a snapshot, a time or a tag to scan is given.
+    fn time_travelled(&self) -> bool {
+        self.snapshot_id.is_some() || self.timestamp_millis.is_some() || self.tag_name.is_some()
+    }
+
+    /// Get the snapshot to scan, none if the table has no snapshot.
+    ///
+    /// If none of a snapshot, a time and a tag is given, the snapshot of `scan.snapshot-id`,
+    /// `scan.timestamp-millis` or `scan.tag-name` is scanned.
+    pub async fn snapshot(&self) -> crate::Result<Option<Snapshot>> {
+        let snapshot_manager = self.table.snapshot_manager();
+        let options = self.table.core_options();
+        let (snapshot_id, timestamp_millis, tag_name) = if self.time_travelled() {
+            (
+                self.snapshot_id,
+                self.timestamp_millis,
+                self.tag_name.as_deref(),
+            )
+        } else {
+            (
+                options.scan_snapshot_id(),
+                options.scan_timestamp_millis(),
+                options.scan_tag_name(),
+            )
+        };
+        if let Some(snapshot_id) = snapshot_id {
+            return snapshot_manager.snapshot(snapshot_id).await.map(Some);
+        }
+        if let Some(timestamp_millis) = timestamp_millis {
+            return snapshot_manager
+                .earlier_or_equal_time_millis(timestamp_millis)
+                .await;
+        }
+        match tag_name {
+            Some(tag_name) => self
+                .table
+                .tag_manager()
+                .tagged_snapshot(tag_name)
+                .await
+                .map(Some),
+            None => snapshot_manager.latest_snapshot().await,
+        }
+    }
+
+    /// Plan the splits of the snapshot, each split contains the data files of a bucket.
+    ///
+    /// If none of a snapshot, a time and a tag is given, the changes of `incremental-between`
+    /// or `incremental-between-timestamp` are planned instead if set.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan.plan",
+            skip_all,
+            fields(
+                table = %crate::table::Table::identifier(&self.table),
+                snapshot_id = tracing::field::Empty,
+                split_count = tracing::field::Empty,
+            )
+        )
+    )]
+    pub async fn plan(&self) -> crate::Result<Plan> {
+        let start = Instant::now();
+        let plan = self.plan_splits().await?;
+        self.table.metrics().record_duration(SCAN_DURATION, start);
+        record_span!(
+            snapshot_id = plan.snapshot_id(),
+            split_count = plan.splits().len()
+        );
+        Ok(plan)
+    }
+
+    async fn plan_splits(&self) -> crate::Result<Plan> {
+        if !self.time_travelled() {
+            let options = self.table.core_options();
+            if let Some((start, end)) = options.incremental_between() {
+                return match (start.parse(), end.parse()) {
+                    (Ok(start), Ok(end)) => self.plan_incremental(start, end).await,
+                    _ => self.plan_incremental_between_tags(start, end).await,
+                };
+            }
+            if let Some((start, end)) = options.incremental_between_timestamp() {
+                return self.plan_incremental_between_timestamps(start, end).await;
+            }
+        }
+        let Some(snapshot) = self.snapshot().await? else {
+            return Ok(Plan::new(None, vec![]));
+        };
+        let buckets = group_by_bucket(self.data_files(&snapshot).await?);
+        let plan = self.plan_files(&snapshot, buckets).await?;
+        Ok(match self.limit {
+            Some(limit) => apply_limit(plan, limit as i64),
+            None => plan,
+        })
+    }
+
+    /// List the partitions of the snapshot to scan that have data files matching the filters,
+    /// in the order they are found in the manifests.
+    pub async fn list_partitions(&self) -> crate::Result<Vec<PartitionEntry>> {
+        let Some(snapshot) = self.snapshot().await? else {
+            return Ok(vec![]);
+        };
+        let mut partitions: IndexMap<Vec<u8>, PartitionEntry> = IndexMap::new();
+        for entry in self.data_files(&snapshot).await? {
+            match partitions.get_mut(entry.partition()) {
+                Some(partition) => partition.add(entry.file()),
+                None => {
+                    let partition = BinaryRow::from_serialized_bytes(entry.partition())?;
+                    partitions.insert(
+                        entry.partition().clone(),
+                        PartitionEntry::new(partition, entry.file()),
+                    );
+                }
+            }
+        }
+        Ok(partitions.into_values().collect())
+    }
+
+    /// Plan the splits of the data files of the snapshot grouped by the partitions and buckets.
+    async fn plan_files(
+        &self,
+        snapshot: &Snapshot,
+        buckets: IndexMap<(Vec<u8>, i32), Vec<DataFileMeta>>,
+    ) -> crate::Result<Plan> {
+        let path_factory = self.table.path_factory();
+        let schema = self.table.schema();
+        let options = CoreOptions::new(schema.options());
+        let append_only = schema.primary_keys().is_empty();
+        let deletion_vectors_enabled = options.deletion_vectors_enabled();
+        let mut deletion_files = if deletion_vectors_enabled {
+            self.deletion_files(snapshot).await?
+        } else {
+            HashMap::new()
+        };
+        let mut splits = Vec::with_capacity(buckets.len());
+        for ((partition_bytes, bucket), mut data_files) in buckets {
+            let partition = BinaryRow::from_serialized_bytes(&partition_bytes)?;
+            let bucket_path = path_factory.bucket_path(&partition, bucket)?;
+            let bucket_deletion_files = deletion_files
+                .remove(&(partition_bytes, bucket))
+                .unwrap_or_default();
+            if !append_only && deletion_vectors_enabled {
+                // rows of level 0 files are not deduplicated by the deletion vectors yet
+                data_files.retain(|file| file.level > 0);
+                if data_files.is_empty() {
+                    continue;
+                }
+            }
+            // files of primary key tables are merged, so a bucket is read by one split
+            let files_of_splits = if append_only {
+                split_append_only_files(
+                    data_files,
+                    options.split_target_size(),
+                    options.split_open_file_cost(),
+                )
+            } else {
+                vec![data_files]
+            };
+            for data_files in files_of_splits {
+                let data_deletion_files = if bucket_deletion_files.is_empty() {
+                    vec![]
+                } else {
+                    data_files
+                        .iter()
+                        .map(|file| bucket_deletion_files.get(&file.file_name).cloned())
+                        .collect()
+                };
+                splits.push(
+                    DataSplit::builder()
+                        .snapshot_id(snapshot.id())
+                        .partition(partition.clone())
+                        .bucket(bucket)
+                        .bucket_path(bucket_path.clone())
+                        .data_files(data_files)
+                        .data_deletion_files(data_deletion_files)
+                        .raw_convertible(append_only || deletion_vectors_enabled)
+                        .build(),
+                );
+            }
+        }
+        Ok(Plan::new(Some(snapshot.id()), splits))
+    }
+
+    /// Plan the splits of the changes committed by the snapshot, which are the data files added
+    /// by the snapshot, or its changelog files if `changelog` is true.
+    ///
+    /// The files are read in the order they are committed with their row kinds without merging.
+    pub(crate) async fn plan_changes(
+        &self,
+        snapshot: &Snapshot,
+        changelog: bool,
+    ) -> crate::Result<Plan> {
+        let buckets = group_by_bucket(self.changed_files(snapshot, changelog).await?);
+        let splits = self.change_splits(snapshot.id(), buckets, true)?;
+        Ok(Plan::new(Some(snapshot.id()), splits))
+    }
+
+    /// Plan the splits of the changes committed by the snapshots after `start` until `end`
+    /// inclusive, reading the files of `incremental-between-scan-mode`.
+    ///
+    /// The data files added by the append snapshots are merged like a batch scan, while the
+    /// changelog files are read with their row kinds like a streaming scan.
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/source/snapshot/IncrementalStartingScanner.java>
+    async fn plan_incremental(&self, start: i64, end: i64) -> crate::Result<Plan> {
+        let snapshot_manager = self.table.snapshot_manager();
+        let earliest = snapshot_manager.earliest_snapshot_id().await?;
+        let latest = snapshot_manager.latest_snapshot_id().await?;
+        let (Some(earliest), Some(latest)) = (earliest, latest) else {
+            return Ok(Plan::new(None, vec![]));
+        };
+        if start > end || start < earliest - 1 || end > latest {
+            return ConfigInvalidSnafu {
+                message: format!(
+                    "The incremental snapshot id range ({start}, {end}] is out of the available snapshot id range [{earliest}, {latest}]"
+                ),
+            }
+            .fail();
+        }
+        let schema = self.table.schema();
+        let options = CoreOptions::new(schema.options());
+        let changelog = match options.incremental_between_scan_mode().as_str() {
+            "auto" => {
+                !schema.primary_keys().is_empty()
+                    && options.changelog_producer()? != ChangelogProducer::None
+            }
+            "delta" => false,
+            "changelog" => true,
+            mode => {
+                return ConfigInvalidSnafu {
+                    message: format!("Unsupported incremental-between-scan-mode {mode}"),
+                }
+                .fail()
+            }
+        };
+        let mut entries = Vec::new();
+        for snapshot_id in start + 1..=end {
+            let snapshot = snapshot_manager.snapshot(snapshot_id).await?;
+            if changelog || snapshot.commit_kind() == &CommitKind::APPEND {
+                entries.extend(self.changed_files(&snapshot, changelog).await?);
+            }
+        }
+        let splits = self.change_splits(end, group_by_bucket(entries), changelog)?;
+        Ok(Plan::new(Some(end), splits))
+    }
+
+    /// Plan the splits of the changes between the snapshots committed at or before the times,
+    /// the earliest snapshot is included if it is committed after `start`.
+    async fn plan_incremental_between_timestamps(
+        &self,
+        start: u64,
+        end: u64,
+    ) -> crate::Result<Plan> {
+        let snapshot_manager = self.table.snapshot_manager();
+        let Some(earliest) = snapshot_manager.earliest_snapshot().await? else {
+            return Ok(Plan::new(None, vec![]));
+        };
+        let Some(end) = snapshot_manager.earlier_or_equal_time_millis(end).await? else {
+            return Ok(Plan::new(None, vec![]));
+        };
+        let start = match snapshot_manager.earlier_or_equal_time_millis(start).await? {
+            Some(snapshot) => snapshot.id(),
+            None => earliest.id() - 1,
+        };
+        self.plan_incremental(start.min(end.id()), end.id()).await
+    }
+
+    /// Plan the splits of the changes from the snapshot tagged by `start` to the snapshot
+    /// tagged by `end`.
+    ///
+    /// The data files of append only tables in `end` but not in `start` are read. The files of
+    /// primary key tables are rewritten by compactions, so the merged rows of each changed
+    /// bucket in `start` are diffed with its merged rows in `end` by keys instead, see
+    /// [`DataSplit::before_files`].
+    ///
+    /// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/source/snapshot/IncrementalTagStartingScanner.java>
+    async fn plan_incremental_between_tags(&self, start: &str, end: &str) -> crate::Result<Plan> {
+        let tag_manager = self.table.tag_manager();
+        let start = tag_manager.tagged_snapshot(start).await?;
+        let end = tag_manager.tagged_snapshot(end).await?;
+        if !self.table.schema().primary_keys().is_empty() {
+            return self.plan_diff(&start, &end).await;
+        }
+        let start_files: HashSet<Identifier> = self
+            .data_files(&start)
+            .await?
+            .iter()
+            .map(ManifestEntry::identifier)
+            .collect();
+        let mut entries = self.data_files(&end).await?;
+        entries.retain(|entry| !start_files.contains(&entry.identifier()));
+        self.plan_files(&end, group_by_bucket(entries)).await
+    }
+
+    /// Plan a diff split of each bucket whose data files in `end` are not the ones in `start`.
+    async fn plan_diff(&self, start: &Snapshot, end: &Snapshot) -> crate::Result<Plan> {
+        let path_factory = self.table.path_factory();
+        let mut before = group_by_bucket(self.data_files(start).await?);
+        let mut buckets: Vec<_> = group_by_bucket(self.data_files(end).await?)
+            .into_iter()
+            .map(|(bucket, data_files)| {
+                let before_files = before.shift_remove(&bucket).unwrap_or_default();
+                (bucket, before_files, data_files)
+            })
+            .collect();
+        buckets.extend(
+            before
+                .into_iter()
+                .map(|(bucket, before_files)| (bucket, before_files, vec![])),
+        );
+
+        let mut splits = Vec::new();
+        for ((partition_bytes, bucket), before_files, data_files) in buckets {
+            let names = |files: &[DataFileMeta]| -> HashSet<String> {
+                files.iter().map(|file| file.file_name.clone()).collect()
+            };
+            if names(&before_files) == names(&data_files) {
+                continue;
+            }
+            let partition = BinaryRow::from_serialized_bytes(&partition_bytes)?;
+            splits.push(
+                DataSplit::builder()
+                    .snapshot_id(end.id())
+                    .bucket_path(path_factory.bucket_path(&partition, bucket)?)
+                    .partition(partition)
+                    .bucket(bucket)
+                    .data_files(data_files)
+                    .before_files(before_files)
+                    .build(),
+            );
+        }
+        Ok(Plan::new(Some(end.id()), splits))
+    }
+
+    /// Read the entries of the data files of the snapshot.
+    pub(crate) async fn data_files(
+        &self,
+        snapshot: &Snapshot,
+    ) -> crate::Result<Vec<ManifestEntry>> {
+        let entries = self
+            .read_entries(&[
+                snapshot.base_manifest_list(),
+                snapshot.delta_manifest_list(),
+            ])
+            .await?;
+        let mut entries = ManifestEntry::merge_entries(entries)?;
+        entries.retain(|entry| entry.kind() == &FileKind::Add);
+        Ok(entries)
+    }
+
+    /// Read the entries of the data files added by the snapshot, or its changelog files if
+    /// `changelog` is true.
+    async fn changed_files(
+        &self,
+        snapshot: &Snapshot,
+        changelog: bool,
+    ) -> crate::Result<Vec<ManifestEntry>> {
+        let manifest_list = if changelog {
+            snapshot.changelog_manifest_list()
+        } else {
+            Some(snapshot.delta_manifest_list())
+        };
+        let mut entries = match manifest_list {
+            Some(manifest_list) => self.read_entries(&[manifest_list]).await?,
+            None => vec![],
+        };
+        entries.retain(|entry| entry.kind() == &FileKind::Add);
+        Ok(entries)
+    }
+
+    /// Build a split of the changed files of each bucket, either read with their row kinds
+    /// without merging if `streaming`, or merged like a batch scan.
+    fn change_splits(
+        &self,
+        snapshot_id: i64,
+        buckets: IndexMap<(Vec<u8>, i32), Vec<DataFileMeta>>,
+        streaming: bool,
+    ) -> crate::Result<Vec<DataSplit>> {
+        let path_factory = self.table.path_factory();
+        let append_only = self.table.schema().primary_keys().is_empty();
+        let mut splits = Vec::with_capacity(buckets.len());
+        for ((partition_bytes, bucket), data_files) in buckets {
+            let partition = BinaryRow::from_serialized_bytes(&partition_bytes)?;
+            splits.push(
+                DataSplit::builder()
+                    .snapshot_id(snapshot_id)
+                    .bucket_path(path_factory.bucket_path(&partition, bucket)?)
+                    .partition(partition)
+                    .bucket(bucket)
+                    .data_files(data_files)
+                    .raw_convertible(append_only)
+                    .streaming(streaming)
+                    .build(),
+            );
+        }
+        Ok(splits)
+    }
+
+    /// Read the manifest entries of the manifest lists matching the filters.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "scan.read_entries",
+            skip_all,
+            fields(
+                manifest_count = tracing::field::Empty,
+                entry_count = tracing::field::Empty,
+            )
+        )
+    )]
+    async fn read_entries(&self, manifest_lists: &[&str]) -> crate::Result<Vec<ManifestEntry>> {
+        let partition_predicate = self.resolve_partition_predicate()?;
+        let file_io = self.table.file_io();
+        let path_factory = self.table.path_factory();
+
+        let manifest_list = ManifestList::new(file_io.clone());
+        let mut manifests = Vec::new();
+        for list in manifest_lists {
+            manifests.extend(
+                manifest_list
+                    .read(&path_factory.manifest_path(list))
+                    .await?,
+            );
+        }
+
+        let manifest_file = self.table.manifest_file();
+        let metrics = self.table.metrics();
+        let mut entries = Vec::new();
+        for manifest in &manifests {
+            if !self.filter_manifest(manifest, partition_predicate.as_ref())? {
+                metrics.increment_counter(SCAN_MANIFESTS_SKIPPED, 1);
+                continue;
+            }
+            metrics.increment_counter(SCAN_MANIFESTS_READ, 1);
+            let mut pruned = 0;
+            for entry in manifest_file
+                .read(&path_factory.manifest_path(manifest.file_name()))
+                .await?
+            {
+                if self.filter_entry(&entry, partition_predicate.as_ref())? {
+                    entries.push(entry);
+                } else {
+                    pruned += 1;
+                }
+            }
+            metrics.increment_counter(SCAN_FILES_PRUNED, pruned);
+        }
+        record_span!(
+            manifest_count = manifests.len(),
+            entry_count = entries.len()
+        );
+        Ok(entries)
+    }
+
+    /// Get the deletion files of the data files in the index manifest of the snapshot, keyed by
+    /// the partitions and buckets, then the names of the data files.
+    async fn deletion_files(
+        &self,
+        snapshot: &Snapshot,
+    ) -> crate::Result<HashMap<(Vec<u8>, i32), HashMap<String, DeletionFile>>> {
+        let mut deletion_files: HashMap<_, HashMap<_, _>> = HashMap::new();
+        let path_factory = self.table.path_factory();
+        for entry in self.table.index_manifest_entries(snapshot).await? {
+            if entry.kind != FileKind::Add
+                || entry.index_file.index_type != DELETION_VECTORS_INDEX
+                || self.bucket.is_some_and(|bucket| bucket != entry.bucket)
+            {
+                continue;
+            }
+            let index_path = path_factory.index_path(&entry.index_file.file_name);
+            let files = deletion_files
+                .entry((entry.partition, entry.bucket))
+                .or_default();
+            for (data_file, (offset, length)) in
+                entry.index_file.deletion_vectors_ranges.iter().flatten()
+            {
+                files.insert(
+                    data_file.clone(),
+                    DeletionFile::new(index_path.clone(), *offset as i64, *length as i64),
+                );
+            }
+        }
+        Ok(deletion_files)
+    }
+
+    /// Combine the partition filter and the partition predicate, none if there is neither.
+    fn resolve_partition_predicate(&self) -> crate::Result<Option<PartitionPredicate>> {
+        let mut predicates = Vec::new();
+        if !self.partition_filter.is_empty() {
+            let partition_type = self.table.schema().logical_partition_type();
+            let filter = PartitionPredicate::from_values(&partition_type, &self.partition_filter)?;
+            predicates.push(filter.predicate().clone());
+        }
+        if let Some(predicate) = &self.partition_predicate {
+            predicates.push(predicate.predicate().clone());
+        }
+        Ok(match predicates.len() {
+            0 => None,
+            1 => Some(PartitionPredicate::new(predicates.remove(0))),
+            _ => Some(PartitionPredicate::new(PredicateBuilder::and(predicates))),
+        })
+    }
+
+    /// Whether the manifest may contain entries matching the filters.
+    fn filter_manifest(
+        &self,
+        manifest: &ManifestFileMeta,
+        partition_predicate: Option<&PartitionPredicate>,
+    ) -> crate::Result<bool> {
+        if let (Some(bucket), Some(min_bucket), Some(max_bucket)) =
+            (self.bucket, manifest.min_bucket(), manifest.max_bucket())
+        {
+            if bucket < min_bucket || bucket > max_bucket {
+                return Ok(false);
+            }
+        }
+        match partition_predicate {
+            Some(predicate) => predicate.test_manifest(manifest),
+            None => Ok(true),
+        }
+    }
+
+    /// Whether the entry matches the filters.
+    fn filter_entry(
+        &self,
+        entry: &ManifestEntry,
+        partition_predicate: Option<&PartitionPredicate>,
+    ) -> crate::Result<bool> {
+        if self.bucket.is_some_and(|bucket| bucket != entry.bucket()) {
+            return Ok(false);
+        }
+        match partition_predicate {
+            Some(predicate) => {
+                predicate.test(&BinaryRow::from_serialized_bytes(entry.partition())?)
+            }
+            None => Ok(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIOBuilder;
+    use crate::manifest::ManifestFile;
+    use crate::spec::RowKind;
+    use crate::spec::{
+        BinaryRowWriter, BinaryTableStats, CommitKind, DataField, DataType, IndexFileMeta,
+        IndexManifestEntry, IntType, Schema, EMPTY_BINARY_ROW,
+    };
+    use crate::table::Table;
+    use crate::test_utils;
+    use crate::utils::SchemaManager;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{Int32Array, RecordBatch, StringArray};
+    use bytes::Bytes;
+
+    use std::sync::Arc;
+
+    fn partition(dt: i32) -> Vec<u8> {
+        let mut writer = BinaryRowWriter::new(1);
+        writer.write_int(0, dt);
+        writer.build().to_serialized_bytes()
+    }
+
+    fn entry(kind: FileKind, dt: i32, bucket: i32, file_name: &str) -> ManifestEntry {
+        let file = DataFileMeta {
+            file_size: 1024,
+            row_count: 10,
+            min_key: EMPTY_BINARY_ROW.to_serialized_bytes(),
+            max_key: EMPTY_BINARY_ROW.to_serialized_bytes(),
+            max_sequence_number: 9,
+            delete_row_count: Some(0),
+            ..test_utils::data_file(file_name)
+        };
+        ManifestEntry::new(kind, partition(dt), bucket, 2, file, 2)
+    }
+
+    fn manifest(name: &str, min_dt: i32, max_dt: i32, max_bucket: i32) -> ManifestFileMeta {
+        ManifestFileMeta::new(
+            name.to_string(),
+            1024,
+            1,
+            0,
+            BinaryTableStats::new(partition(min_dt), partition(max_dt), vec![Some(0)]),
+            0,
+        )
+        .with_bucket_range(0, max_bucket)
+    }
+
+    /// Create a table partitioned by `dt` with a snapshot committed from a base manifest of
+    /// `dt=1` in bucket 0 and 1, and a delta manifest of `dt=1` and `dt=2` in bucket 0.
+    async fn setup_table(location: &str) -> FileStoreTable {
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "dt".to_string(), DataType::Int(IntType::new())),
+                DataField::new(1, "id".to_string(), DataType::Int(IntType::new())),
+            ])
+            .partition_keys(vec!["dt".to_string()])
+            .build()
+            .to_table_schema(0)
+            .unwrap();
+        let table = FileStoreTable::new(
+            file_io.clone(),
+            Identifier::new("db", "t"),
+            location,
+            schema,
+        );
+
+        let path_factory = table.path_factory();
+        let manifest_file = ManifestFile::new(file_io.clone());
+        manifest_file
+            .write(
+                &path_factory.manifest_path("manifest-base"),
+                &[
+                    entry(FileKind::Add, 1, 0, "f1.parquet"),
+                    entry(FileKind::Add, 1, 1, "f2.parquet"),
+                ],
+            )
+            .await
+            .unwrap();
+        manifest_file
+            .write(
+                &path_factory.manifest_path("manifest-delta"),
+                &[
+                    entry(FileKind::Add, 2, 0, "f3.parquet"),
+                    entry(FileKind::Delete, 1, 0, "f1.parquet"),
+                    entry(FileKind::Add, 1, 0, "f4.parquet"),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let manifest_list = ManifestList::new(file_io.clone());
+        manifest_list
+            .write(
+                &path_factory.manifest_path("manifest-list-base"),
+                &[manifest("manifest-base", 1, 1, 1)],
+            )
+            .await
+            .unwrap();
+        manifest_list
+            .write(
+                &path_factory.manifest_path("manifest-list-delta"),
+                &[manifest("manifest-delta", 1, 2, 0)],
+            )
+            .await
+            .unwrap();
+
+        let snapshot = Snapshot::builder()
+            .version(Snapshot::CURRENT_VERSION)
+            .id(1)
+            .schema_id(0)
+            .base_manifest_list("manifest-list-base".to_string())
+            .delta_manifest_list("manifest-list-delta".to_string())
+            .commit_user("test".to_string())
+            .commit_identifier(1)
+            .commit_kind(CommitKind::APPEND)
+            .time_millis(1000)
+            .build();
+        file_io
+            .new_output(&table.snapshot_manager().snapshot_path(1))
+            .unwrap()
+            .write(Bytes::from(snapshot.to_json().unwrap()))
+            .await
+            .unwrap();
+        table
+    }
+
+    fn split_files(plan: &Plan) -> Vec<(String, Vec<String>)> {
+        let mut files: Vec<_> = plan
+            .splits()
+            .iter()
+            .map(|split| {
+                let names = split
+                    .data_files()
+                    .iter()
+                    .map(|f| f.file_name.clone())
+                    .collect();
+                (split.bucket_path().to_string(), names)
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[tokio::test]
+    async fn test_plan() {
+        let location = "file:/tmp/test_table_scan_plan";
+        let table = setup_table(location).await;
+
+        let plan = TableScan::new(table.clone()).plan().await.unwrap();
+        assert_eq!(plan.snapshot_id(), Some(1));
+        assert_eq!(
+            split_files(&plan),
+            vec![
+                (
+                    format!("{location}/dt=1/bucket-0"),
+                    vec!["f4.parquet".to_string()]
+                ),
+                (
+                    format!("{location}/dt=1/bucket-1"),
+                    vec!["f2.parquet".to_string()]
+                ),
+                (
+                    format!("{location}/dt=2/bucket-0"),
+                    vec!["f3.parquet".to_string()]
+                ),
+            ]
+        );
+        assert_eq!(plan.splits()[0].row_count(), 10);
+
+        let empty = FileStoreTable::new(
+            table.file_io().clone(),
+            Identifier::new("db", "empty"),
+            "file:/tmp/test_table_scan_plan_empty",
+            table.schema().clone(),
+        );
+        assert_eq!(
+            TableScan::new(empty).plan().await.unwrap(),
+            Plan::new(None, vec![])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plan_skip_manifests() {
+        let location = "file:/tmp/test_table_scan_skip_manifests";
+        let table = setup_table(location).await;
+
+        // the base manifest only contains dt=1, it must be skipped without being read
+        table
+            .file_io()
+            .delete_file(&table.path_factory().manifest_path("manifest-base"))
+            .await
+            .unwrap();
+        let plan = TableScan::new(table.clone())
+            .with_partition_filter(HashMap::from([("dt".to_string(), Datum::Int(2))]))
+            .plan()
+            .await
+            .unwrap();
+        assert_eq!(
+            split_files(&plan),
+            vec![(
+                format!("{location}/dt=2/bucket-0"),
+                vec!["f3.parquet".to_string()]
+            )]
+        );
+        assert!(TableScan::new(table.clone()).plan().await.is_err());
+
+        // the delta manifest only contains bucket 0
+        let table = setup_table(location).await;
+        table
+            .file_io()
+            .delete_file(&table.path_factory().manifest_path("manifest-delta"))
+            .await
+            .unwrap();
+        let plan = TableScan::new(table.clone())
+            .with_bucket(1)
+            .plan()
+            .await
+            .unwrap();
+        assert_eq!(
+            split_files(&plan),
+            vec![(
+                format!("{location}/dt=1/bucket-1"),
+                vec!["f2.parquet".to_string()]
+            )]
+        );
+
+        assert!(matches!(
+            TableScan::new(table)
+                .with_partition_filter(HashMap::from([("id".to_string(), Datum::Int(2))]))
+                .plan()
+                .await,
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_partitions() {
+        let location = "file:/tmp/test_table_scan_list_partitions";
+        let table = setup_table(location).await;
+        let partitions = |scan: TableScan| async move {
+            let partition_type = scan.table().schema().logical_partition_type();
+            let data_type = partition_type.fields()[0].data_type().clone();
+            scan.list_partitions()
+                .await
+                .unwrap()
+                .iter()
+                .map(|p| {
+                    let dt = p.partition().get_datum(0, &data_type).unwrap().unwrap();
+                    (dt, p.record_count(), p.file_size_in_bytes(), p.file_count())
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            partitions(TableScan::new(table.clone())).await,
+            vec![(Datum::Int(1), 20, 2048, 2), (Datum::Int(2), 10, 1024, 1)]
+        );
+
+        // the base manifest only contains dt=1, it must be pruned by the predicate
+        table
+            .file_io()
+            .delete_file(&table.path_factory().manifest_path("manifest-base"))
+            .await
+            .unwrap();
+        let builder = PredicateBuilder::new(table.schema().logical_partition_type());
+        let predicate = PartitionPredicate::new(builder.greater_than(0, Datum::Int(1)));
+        let scan = TableScan::new(table.clone()).with_partition_predicate(predicate.clone());
+        assert_eq!(partitions(scan).await, vec![(Datum::Int(2), 10, 1024, 1)]);
+        let plan = TableScan::new(table)
+            .with_partition_predicate(predicate)
+            .plan()
+            .await
+            .unwrap();
+        assert_eq!(
+            split_files(&plan),
+            vec![(
+                format!("{location}/dt=2/bucket-0"),
+                vec!["f3.parquet".to_string()]
+            )]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_plan_with_deletion_vectors() {
+        let location = "file:/tmp/test_table_scan_with_deletion_vectors";
+        let table = setup_table(location).await;
+        let schema = Schema::builder()
+            .fields(table.schema().fields().to_vec())
+            .partition_keys(vec!["dt".to_string()])
+            .options([("deletion-vectors.enabled".to_string(), "true".to_string())].into())
+            .build()
+            .to_table_schema(0)
+            .unwrap();
+        let table = FileStoreTable::new(
+            table.file_io().clone(),
+            Identifier::new("db", "t"),
+            location,
+            schema,
+        );
+
+        let path_factory = table.path_factory();
+        let index_file = |name: &str, data_file: &str| IndexFileMeta {
+            index_type: DELETION_VECTORS_INDEX.to_string(),
+            file_name: name.to_string(),
+            file_size: 33,
+            row_count: 1,
+            deletion_vectors_ranges: Some([(data_file.to_string(), (1, 24))].into()),
+        };
+        crate::manifest::IndexManifestFile::new(table.file_io().clone())
+            .write(
+                &path_factory.manifest_path("index-manifest"),
+                &[
+                    IndexManifestEntry {
+                        kind: FileKind::Add,
+                        partition: partition(1),
+                        bucket: 0,
+                        index_file: index_file("index-0", "f4.parquet"),
+                        version: 1,
+                    },
+                    IndexManifestEntry {
+                        kind: FileKind::Add,
+                        partition: partition(1),
+                        bucket: 1,
+                        index_file: index_file("index-1", "f2.parquet"),
+                        version: 1,
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+        let snapshot = Snapshot::builder()
+            .version(Snapshot::CURRENT_VERSION)
+            .id(2)
+            .schema_id(0)
+            .base_manifest_list("manifest-list-base".to_string())
+            .delta_manifest_list("manifest-list-delta".to_string())
+            .index_manifest(Some("index-manifest".to_string()))
+            .commit_user("test".to_string())
+            .commit_identifier(2)
+            .commit_kind(CommitKind::APPEND)
+            .time_millis(2000)
+            .build();
+        table
+            .file_io()
+            .new_output(&table.snapshot_manager().snapshot_path(2))
+            .unwrap()
+            .write(Bytes::from(snapshot.to_json().unwrap()))
+            .await
+            .unwrap();
+
+        let plan = TableScan::new(table.clone())
+            .as_of_snapshot(2)
+            .with_partition_filter(HashMap::from([("dt".to_string(), Datum::Int(1))]))
+            .with_bucket(0)
+            .plan()
+            .await
+            .unwrap();
+        assert_eq!(plan.splits().len(), 1);
+        let split = &plan.splits()[0];
+        assert!(split.raw_convertible());
+        assert_eq!(split.data_files()[0].file_name, "f4.parquet");
+        assert_eq!(
+            split.deletion_file(0),
+            Some(&DeletionFile::new(
+                path_factory.index_path("index-0"),
+                1,
+                24
+            ))
+        );
+
+        // the snapshot without index manifest has no deletion files
+        let plan = TableScan::new(table)
+            .as_of_snapshot(1)
+            .plan()
+            .await
+            .unwrap();
+        assert!(plan
+            .splits()
+            .iter()
+            .all(|split| split.deletion_file(0).is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_time_travel() {
+        let table = setup_table("file:/tmp/test_table_scan_time_travel").await;
+        let snapshot_manager = table.snapshot_manager();
+        let snapshot = snapshot_manager.snapshot(1).await.unwrap();
+        let mut json: serde_json::Value =
+            serde_json::from_str(&snapshot.to_json().unwrap()).unwrap();
+        json["id"] = 2.into();
+        json["timeMillis"] = 2000.into();
+        table
+            .file_io()
+            .new_output(&snapshot_manager.snapshot_path(2))
+            .unwrap()
+            .write(Bytes::from(json.to_string()))
+            .await
+            .unwrap();
+
+        let snapshot_id = |scan: TableScan| async move { scan.plan().await.unwrap().snapshot_id() };
+        let scan = || TableScan::new(table.clone());
+        assert_eq!(snapshot_id(scan()).await, Some(2));
+        assert_eq!(snapshot_id(scan().as_of_snapshot(1)).await, Some(1));
+        assert_eq!(snapshot_id(scan().as_of_timestamp(999)).await, None);
+        assert_eq!(snapshot_id(scan().as_of_timestamp(1500)).await, Some(1));
+        assert_eq!(snapshot_id(scan().as_of_timestamp(2000)).await, Some(2));
+
+        // the options travel unless a snapshot is given
+        let mut options = table.schema().options().clone();
+        options.insert("scan.timestamp-millis".to_string(), "1000".to_string());
+        let table = FileStoreTable::new(
+            table.file_io().clone(),
+            Identifier::new("db", "t"),
+            table.location(),
+            table.schema().copy_with_options(options),
+        );
+        assert_eq!(snapshot_id(TableScan::new(table.clone())).await, Some(1));
+        assert_eq!(
+            snapshot_id(TableScan::new(table).as_of_snapshot(2)).await,
+            Some(2)
+        );
+    }
+
+    /// Copy the table with the options added.
+    fn with_options(table: &FileStoreTable, options: &[(&str, &str)]) -> FileStoreTable {
+        let mut table_options = table.schema().options().clone();
+        for (key, value) in options {
+            table_options.insert(key.to_string(), value.to_string());
+        }
+        FileStoreTable::new(
+            table.file_io().clone(),
+            Identifier::new("db", "t"),
+            table.location(),
+            table.schema().copy_with_options(table_options),
+        )
+    }
+
+    /// Write and commit the (row kind, id, name) rows to the table.
+    async fn write(table: &FileStoreTable, rows: &[(RowKind, i32, &str)]) {
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.1))),
+                Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.2))),
+            ],
+        )
+        .unwrap();
+        let row_kinds: Vec<RowKind> = rows.iter().map(|r| r.0).collect();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write
+            .write_with_row_kinds(&batch, &row_kinds)
+            .await
+            .unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+    }
+
+    /// Read the (row kind, id, name) rows of the plan.
+    async fn read(table: &FileStoreTable, plan: &Plan) -> Vec<(RowKind, i32, String)> {
+        let read = table.new_read();
+        let mut rows = Vec::new();
+        for split in plan.splits() {
+            for batch in read.read_with_row_kinds(split).await.unwrap() {
+                let (batch, row_kinds) = batch.unwrap();
+                let ids = batch.column(0).as_primitive::<Int32Type>();
+                let names = batch.column(1).as_string::<i32>();
+                rows.extend(
+                    row_kinds
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, kind)| (kind, ids.value(i), names.value(i).to_string())),
+                );
+            }
+        }
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_plan_with_limit() {
+        let create_table = |location: &'static str, primary_keys: Vec<String>| async move {
+            let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+            let _ = file_io.delete_dir(&format!("{location}/")).await;
+            let bucket = if primary_keys.is_empty() { "-1" } else { "1" };
+            let schema = SchemaManager::new(file_io.clone(), location)
+                .create_table(
+                    &Schema::builder()
+                        .fields(vec![
+                            DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                            DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                        ])
+                        .primary_keys(primary_keys)
+                        .options(
+                            [
+                                ("bucket".to_string(), bucket.to_string()),
+                                ("file.format".to_string(), "parquet".to_string()),
+                            ]
+                            .into(),
+                        )
+                        .build(),
+                )
+                .await
+                .unwrap();
+            FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema)
+        };
+        let table = create_table("file:/tmp/test_table_scan_plan_with_limit", vec![]).await;
+        for ids in [[1, 2], [3, 4], [5, 6]] {
+            write(
+                &table,
+                &[
+                    (RowKind::Insert, ids[0], "a"),
+                    (RowKind::Insert, ids[1], "b"),
+                ],
+            )
+            .await;
+        }
+        let file_count = |plan: &Plan| -> usize {
+            plan.splits()
+                .iter()
+                .map(|split| split.data_files().len())
+                .sum()
+        };
+        assert_eq!(file_count(&table.new_scan().plan().await.unwrap()), 3);
+        // the files past the limit are skipped
+        let plan = table.new_scan().with_limit(3).plan().await.unwrap();
+        assert_eq!(file_count(&plan), 2);
+        assert_eq!(
+            file_count(&table.new_scan().with_limit(4).plan().await.unwrap()),
+            2
+        );
+        assert_eq!(
+            file_count(&table.new_scan().with_limit(10).plan().await.unwrap()),
+            3
+        );
+        assert!(table
+            .new_scan()
+            .with_limit(0)
+            .plan()
+            .await
+            .unwrap()
+            .splits()
+            .is_empty());
+
+        // the reads stop once the rows are read
+        let read = table.new_read().with_limit(3);
+        let mut row_count = 0;
+        for split in plan.splits() {
+            for batch in read.read(split).await.unwrap() {
+                row_count += batch.unwrap().num_rows();
+            }
+        }
+        assert_eq!(row_count, 3);
+
+        // the files of primary key tables are merged, so the rows can't be counted
+        let table = create_table(
+            "file:/tmp/test_table_scan_plan_with_limit_pk",
+            vec!["id".to_string()],
+        )
+        .await;
+        write(&table, &[(RowKind::Insert, 1, "a")]).await;
+        write(&table, &[(RowKind::Insert, 1, "b")]).await;
+        let plan = table.new_scan().with_limit(1).plan().await.unwrap();
+        assert_eq!(file_count(&plan), 2);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_between() {
+        let location = "file:/tmp/test_table_scan_incremental_between";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                        DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                    ])
+                    .primary_keys(vec!["id".to_string()])
+                    .options(
+                        [
+                            ("bucket".to_string(), "1".to_string()),
+                            ("changelog-producer".to_string(), "input".to_string()),
+                            ("file.format".to_string(), "parquet".to_string()),
+                        ]
+                        .into(),
+                    )
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema);
+        write(
+            &table,
+            &[(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")],
+        )
+        .await;
+        write(
+            &table,
+            &[(RowKind::Insert, 1, "a2"), (RowKind::Insert, 3, "c")],
+        )
+        .await;
+        write(&table, &[(RowKind::Delete, 2, "b")]).await;
+        table.create_tag("t1", 1).await.unwrap();
+        table.create_tag("t3", 3).await.unwrap();
+
+        let plan = |options: &[(&str, &str)]| {
+            let table = with_options(&table, options);
+            async move { table.new_scan().plan().await }
+        };
+        let rows = |rows: &[(RowKind, i32, &str)]| -> Vec<(RowKind, i32, String)> {
+            rows.iter()
+                .map(|(kind, id, name)| (*kind, *id, name.to_string()))
+                .collect()
+        };
+
+        // the changelog files are read with their row kinds
+        let changes = plan(&[("incremental-between", "1,3")]).await.unwrap();
+        assert_eq!(changes.snapshot_id(), Some(3));
+        assert_eq!(
+            read(&table, &changes).await,
+            rows(&[
+                (RowKind::Insert, 1, "a2"),
+                (RowKind::Insert, 3, "c"),
+                (RowKind::Delete, 2, "b"),
+            ])
+        );
+
+        // the data files are merged
+        let merged = rows(&[(RowKind::Insert, 1, "a2"), (RowKind::Insert, 3, "c")]);
+        let delta = plan(&[
+            ("incremental-between", "1,3"),
+            ("incremental-between-scan-mode", "delta"),
+        ])
+        .await
+        .unwrap();
+        assert_eq!(read(&table, &delta).await, merged);
+        // the merged rows of the tags are diffed
+        let tags = plan(&[("incremental-between", "t1,t3")]).await.unwrap();
+        assert_eq!(tags.snapshot_id(), Some(3));
+        assert_eq!(
+            read(&table, &tags).await,
+            rows(&[
+                (RowKind::Insert, 1, "a2"),
+                (RowKind::Delete, 2, "b"),
+                (RowKind::Insert, 3, "c"),
+            ])
+        );
+
+        // the earliest snapshot is included if committed after the start
+        let latest = table.snapshot_manager().latest_snapshot().await.unwrap();
+        let end = latest.unwrap().time_millis().to_string();
+        let timestamps = plan(&[
+            ("incremental-between-timestamp", &format!("0,{end}")),
+            ("incremental-between-scan-mode", "delta"),
+        ])
+        .await
+        .unwrap();
+        assert_eq!(
+            read(&table, &timestamps).await,
+            rows(&[(RowKind::Insert, 1, "a2"), (RowKind::Insert, 3, "c")])
+        );
+
+        assert!(plan(&[("incremental-between", "0,4")]).await.is_err());
+        assert!(plan(&[("incremental-between", "t1,t2")]).await.is_err());
+        // explicit snapshots are not incremental
+        let table = with_options(&table, &[("incremental-between", "1,3")]);
+        let plan = table.new_scan().as_of_snapshot(1).plan().await.unwrap();
+        assert_eq!(
+            read(&table, &plan).await,
+            rows(&[(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incremental_between_tags_compacted() {
+        let location = "file:/tmp/test_table_scan_incremental_between_tags_compacted";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![
+                        DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                        DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+                    ])
+                    .primary_keys(vec!["id".to_string()])
+                    .options(
+                        [
+                            ("bucket".to_string(), "1".to_string()),
+                            ("file.format".to_string(), "parquet".to_string()),
+                        ]
+                        .into(),
+                    )
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema);
+        write(
+            &table,
+            &[
+                (RowKind::Insert, 1, "a"),
+                (RowKind::Insert, 2, "b"),
+                (RowKind::Insert, 3, "c"),
+            ],
+        )
+        .await;
+        table.create_tag("t1", 1).await.unwrap();
+        write(
+            &table,
+            &[
+                (RowKind::Insert, 2, "b2"),
+                (RowKind::Insert, 4, "d"),
+                (RowKind::Delete, 3, "c"),
+            ],
+        )
+        .await;
+        write(&table, &[(RowKind::Insert, 1, "a")]).await;
+        table.create_tag("t3", 3).await.unwrap();
+        table.new_compact().compact().await.unwrap();
+        table.create_tag("t4", 4).await.unwrap();
+
+        let plan = |tags: &str| {
+            let table = with_options(&table, &[("incremental-between", tags)]);
+            async move { table.new_scan().plan().await.unwrap() }
+        };
+        // all the files are rewritten, only the changed keys are read
+        let compacted = table.snapshot_manager().snapshot(4).await.unwrap();
+        let files = table.new_scan().data_files(&compacted).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            read(&table, &plan("t1,t4").await).await,
+            vec![
+                (RowKind::Insert, 2, "b2".to_string()),
+                (RowKind::Delete, 3, "c".to_string()),
+                (RowKind::Insert, 4, "d".to_string()),
+            ]
+        );
+        // nothing is changed by the compaction
+        assert!(read(&table, &plan("t3,t4").await).await.is_empty());
+        assert!(plan("t4,t4").await.splits().is_empty());
+        // the changes are read without the row kinds by the batch reads
+        let row_counts: Vec<usize> = {
+            let plan = plan("t1,t4").await;
+            let mut counts = Vec::new();
+            for split in plan.splits() {
+                for batch in table.new_read().read(split).await.unwrap() {
+                    counts.push(batch.unwrap().num_rows());
+                }
+            }
+            counts
+        };
+        assert_eq!(row_counts, vec![3]);
+    }
+}
@@ -0,0 +1,315 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::format::{to_arrow_array, to_arrow_type};
+use crate::predicate::Predicate;
+use crate::spec::{DataType, Datum, RowKind};
+use crate::table::{
+    BatchWriteBuilder, CommitMessage, FileStoreTable, TableCommit, TableRead, TableScan, TableWrite,
+};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_cast::cast;
+use arrow_select::filter::filter_record_batch;
+
+/// An expression evaluated over the rows of a batch of the fields of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expression {
+    /// The value of the column of the name.
+    Column(String),
+    /// A literal value, null if none.
+    Literal(Option<Datum>),
+    Add(Box<Expression>, Box<Expression>),
+    Subtract(Box<Expression>, Box<Expression>),
+    Multiply(Box<Expression>, Box<Expression>),
+    Divide(Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    /// Create the expression of the column of the name.
+    pub fn column(name: impl Into<String>) -> Self {
+        Expression::Column(name.into())
+    }
+
+    /// Create the expression of the literal value.
+    pub fn literal(datum: Datum) -> Self {
+        Expression::Literal(Some(datum))
+    }
+
+    /// Evaluate the expression over the rows of the batch into an array of the data type,
+    /// the operands of an arithmetic are casted to the data type before the arithmetic.
+    pub fn evaluate(&self, batch: &RecordBatch, data_type: &DataType) -> crate::Result<ArrayRef> {
+        let arrow_type = to_arrow_type(data_type);
+        let array = match self {
+            Expression::Column(name) => match batch.column_by_name(name) {
+                Some(column) => column.clone(),
+                None => {
+                    return ColumnNotExistSnafu {
+                        column: name.clone(),
+                    }
+                    .fail()
+                }
+            },
+            Expression::Literal(datum) => to_arrow_array(datum.as_ref(), batch.num_rows())?,
+            Expression::Add(left, right)
+            | Expression::Subtract(left, right)
+            | Expression::Multiply(left, right)
+            | Expression::Divide(left, right) => {
+                let (left, right) = (
+                    left.evaluate(batch, data_type)?,
+                    right.evaluate(batch, data_type)?,
+                );
+                match self {
+                    Expression::Add(..) => arrow_arith::numeric::add(&left, &right)?,
+                    Expression::Subtract(..) => arrow_arith::numeric::sub(&left, &right)?,
+                    Expression::Multiply(..) => arrow_arith::numeric::mul(&left, &right)?,
+                    _ => arrow_arith::numeric::div(&left, &right)?,
+                }
+            }
+        };
+        Ok(cast(&array, &arrow_type)?)
+    }
+}
+
+/// An update of the rows of [`FileStoreTable`] matching a filter by a commit user, like
+/// `UPDATE ... SET ... WHERE`, all the rows are updated if there is no filter.
+///
+/// The matched rows of the latest snapshot are read merged by key, the columns assigned are
+/// replaced by the values of their expressions, and the rows are written back as `+U` rows
+/// of the same keys. Only primary key tables are supported, and the primary keys and the
+/// partition keys can't be assigned.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-spark/paimon-spark-common/src/main/scala/org/apache/paimon/spark/commands/UpdatePaimonTableCommand.scala>
+#[derive(Debug, Clone)]
+pub struct TableUpdate {
+    table: FileStoreTable,
+    commit_user: String,
+    filter: Option<Predicate>,
+    assignments: Vec<(String, Expression)>,
+}
+
+impl TableUpdate {
+    pub fn new(table: FileStoreTable, commit_user: impl Into<String>) -> Self {
+        Self {
+            table,
+            commit_user: commit_user.into(),
+            filter: None,
+            assignments: vec![],
+        }
+    }
+
+    /// Only update the rows matching the predicate on the fields of the table.
+    pub fn with_filter(mut self, predicate: Predicate) -> Self {
+        self.filter = Some(predicate);
+        self
+    }
+
+    /// Assign the value of the expression to the column of the name, the expression is
+    /// evaluated over the rows before the update.
+    pub fn with_assignment(mut self, column: impl Into<String>, expression: Expression) -> Self {
+        self.assignments.push((column.into(), expression));
+        self
+    }
+
+    /// Get the table to update.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the user committing the updates.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Update the matched rows of the latest snapshot and commit the updates, nothing is
+    /// committed if no row is matched.
+    pub async fn update(&self) -> crate::Result<()> {
+        let messages = self.prepare_commit().await?;
+        TableCommit::new(
+            self.table.clone(),
+            self.commit_user.clone(),
+            BatchWriteBuilder::COMMIT_IDENTIFIER,
+        )
+        .commit(messages)
+        .await
+    }
+
+    /// Update the matched rows of the latest snapshot and get the files written of each
+    /// bucket, without committing them.
+    pub async fn prepare_commit(&self) -> crate::Result<Vec<CommitMessage>> {
+        let schema = self.table.schema();
+        if schema.primary_keys().is_empty() {
+            return WriteInvalidSnafu {
+                message: "Updating rows of append only tables is not supported".to_string(),
+            }
+            .fail();
+        }
+        let mut assigned = vec![None; schema.fields().len()];
+        for (column, expression) in &self.assignments {
+            let Some(index) = schema.fields().iter().position(|f| f.name() == column) else {
+                return ColumnNotExistSnafu {
+                    column: column.clone(),
+                }
+                .fail();
+            };
+            if schema.primary_keys().contains(column) || schema.partition_keys().contains(column) {
+                return WriteInvalidSnafu {
+                    message: format!("Can't update the primary key or partition key {column}"),
+                }
+                .fail();
+            }
+            assigned[index] = Some(expression);
+        }
+        let Some(snapshot) = self.table.snapshot_manager().latest_snapshot().await? else {
+            return Ok(vec![]);
+        };
+
+        let mut read = TableRead::new(self.table.clone());
+        if let Some(filter) = &self.filter {
+            read = read.with_filter(filter.clone());
+        }
+        let plan = TableScan::new(self.table.clone())
+            .as_of_snapshot(snapshot.id())
+            .plan()
+            .await?;
+        let mut write = TableWrite::new(self.table.clone(), self.commit_user.clone());
+        for split in plan.splits() {
+            for batch in read.read(split).await? {
+                let mut batch = batch?;
+                if let Some(filter) = &self.filter {
+                    batch = filter_record_batch(&batch, &filter.test_batch(&batch)?)?;
+                }
+                if batch.num_rows() == 0 {
+                    continue;
+                }
+                let columns = schema
+                    .fields()
+                    .iter()
+                    .zip(&assigned)
+                    .zip(batch.columns())
+                    .map(|((field, expression), column)| match expression {
+                        Some(expression) => expression.evaluate(&batch, field.data_type()),
+                        None => Ok(column.clone()),
+                    })
+                    .collect::<crate::Result<Vec<_>>>()?;
+                let batch = RecordBatch::try_new(batch.schema(), columns)?;
+                let row_kinds = vec![RowKind::UpdateAfter; batch.num_rows()];
+                write.write_with_row_kinds(&batch, &row_kinds).await?;
+            }
+        }
+        write.prepare_commit().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::to_arrow_schema;
+    use crate::predicate::PredicateBuilder;
+    use crate::table::Table;
+    use crate::test_utils::{create_table, id_name_price_schema};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::Int32Type;
+    use arrow_array::{Array, Int32Array, StringArray};
+    use std::sync::Arc;
+
+    async fn read(table: &FileStoreTable) -> Vec<(i32, Option<String>, Option<i32>)> {
+        let plan = table.new_scan().plan().await.unwrap();
+        let read = table.new_read();
+        let mut rows = Vec::new();
+        for split in plan.splits() {
+            for batch in read.read(split).await.unwrap() {
+                let batch = batch.unwrap();
+                let ids = batch.column(0).as_primitive::<Int32Type>();
+                let names = batch.column(1).as_string::<i32>();
+                let prices = batch.column(2).as_primitive::<Int32Type>();
+                rows.extend((0..batch.num_rows()).map(|i| {
+                    (
+                        ids.value(i),
+                        names.is_valid(i).then(|| names.value(i).to_string()),
+                        prices.is_valid(i).then(|| prices.value(i)),
+                    )
+                }));
+            }
+        }
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_update_table() {
+        let table = create_table("file:/tmp/test_update_table", &id_name_price_schema()).await;
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec!["a", "b", "c"])),
+                Arc::new(Int32Array::from(vec![10, 20, 30])),
+            ],
+        )
+        .unwrap();
+        write.write(&batch).await.unwrap();
+        builder
+            .new_commit()
+            .commit(write.prepare_commit().await.unwrap())
+            .await
+            .unwrap();
+
+        let predicate = PredicateBuilder::new(table.row_type()).greater_or_equal(0, Datum::Int(2));
+        let price = Expression::Add(
+            Box::new(Expression::Multiply(
+                Box::new(Expression::column("price")),
+                Box::new(Expression::literal(Datum::Int(2))),
+            )),
+            Box::new(Expression::literal(Datum::Int(1))),
+        );
+        table
+            .new_update()
+            .with_filter(predicate)
+            .with_assignment("price", price)
+            .with_assignment("name", Expression::Literal(None))
+            .update()
+            .await
+            .unwrap();
+        assert_eq!(
+            read(&table).await,
+            vec![
+                (1, Some("a".to_string()), Some(10)),
+                (2, None, Some(41)),
+                (3, None, Some(61)),
+            ]
+        );
+
+        let update = table
+            .new_update()
+            .with_assignment("id", Expression::literal(Datum::Int(0)));
+        assert!(matches!(
+            update.update().await,
+            Err(crate::Error::WriteInvalid { .. })
+        ));
+        let update = table
+            .new_update()
+            .with_assignment("price", Expression::column("unknown"));
+        assert!(matches!(
+            update.update().await,
+            Err(crate::Error::ColumnNotExist { .. })
+        ));
+    }
+}
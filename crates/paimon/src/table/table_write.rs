@@ -0,0 +1,570 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::file_index::FileIndexOptions;
+use crate::format::{to_arrow_field, to_arrow_schema, DataFileWriter, FileCompression, StatsMode};
+use crate::index::HashBucketAssigner;
+use crate::memory::MemoryPool;
+use crate::mergetree::{
+    sequence_fields, ChangelogMode, KeyValueFileWriterFactory, Levels, LookupChangelog,
+    MergeTreeCompactManager, MergeTreeCompactRewriter, MergeTreeReader, MergeTreeWriter,
+    UniversalCompaction,
+};
+use crate::spec::{
+    key_value_fields, BinaryRow, ChangelogProducer, DataFileMeta, MergeEngine, RowKind,
+};
+use crate::table::append_only_writer::AppendOnlyWriter;
+use crate::table::row_key_extractor::{bucket, RowKeyExtractor};
+use crate::table::{
+    CommitMessage, CompactIncrement, DataIncrement, FileStoreTable, Plan, TableScan,
+};
+use crate::utils::{record_span, DataFilePathFactory};
+use arrow_array::{Array, ArrayRef, RecordBatch, UInt32Array};
+use arrow_select::take::take_record_batch;
+use indexmap::{IndexMap, IndexSet};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A write of [`FileStoreTable`] to write arrow record batches by a commit user.
+///
+/// Rows are distributed into the buckets of their partitions and written by the writer of
+/// each bucket. Rows of append only tables are written into data files directly, while rows
+/// of primary key tables are buffered, sorted by key and spilled into data files when the
+/// buffer is full. Data files are rolled by `target-file-size`, and the sequence numbers of a
+/// bucket continue from the files of the latest snapshot.
+///
+/// Primary key tables write changelog files by `changelog-producer`: `input` writes the
+/// written key values as the changelog, and `lookup` looks up the written keys in the files of
+/// the bucket to produce the changes of their values.
+///
+/// The sorted runs of a bucket of primary key tables are compacted on write by the universal
+/// compaction once they exceed `num-sorted-run.compaction-trigger`, and the files compacted
+/// are committed along with the files written, unless the table is `write-only`.
+///
+/// Rows are assigned to buckets by the hash of their bucket keys modulo `bucket`, the same as
+/// paimon-java, while rows of bucket unaware append only tables are written into bucket 0.
+/// Keys of dynamic bucket tables are assigned to buckets by the hash index, whose changes are
+/// committed with the data files.
+///
+/// Writers of buckets hold the rows written in memory until they are flushed, beyond
+/// `write.max-writers` writers holding rows, the rows of the least recently written writers
+/// are flushed into data files.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/sink/TableWriteImpl.java>
+#[derive(Debug)]
+pub struct TableWrite {
+    table: FileStoreTable,
+    commit_user: String,
+    key_extractor: RowKeyExtractor,
+    /// Assigner of the buckets of dynamic bucket tables, created on the first write.
+    bucket_assigner: Option<HashBucketAssigner>,
+    writers: IndexMap<(Vec<u8>, i32), (BinaryRow, RecordWriter)>,
+    /// Keys of the writers holding rows in memory, from the least recently written.
+    active_writers: IndexSet<BucketKey>,
+    /// Data files of the buckets in the latest snapshot, loaded on the first write.
+    restored_files: Option<HashMap<BucketKey, Vec<DataFileMeta>>>,
+    /// Whether the buckets are written regardless of their files in the latest snapshot.
+    ignore_previous_files: bool,
+    /// The latest snapshot when the buckets are restored, 0 if there is none.
+    base_snapshot_id: Option<i64>,
+    /// Pool of the memory buffered by the writers of the buckets.
+    memory_pool: Arc<MemoryPool>,
+}
+
+/// The serialized partition and the bucket.
+type BucketKey = (Vec<u8>, i32);
+
+/// How rows are distributed into buckets.
+#[derive(Debug, Clone, Copy)]
+enum BucketMode {
+    /// Rows of append only tables without buckets are all written into bucket 0.
+    Unaware,
+    /// Rows are distributed by the hash of their bucket keys modulo the number of buckets.
+    Fixed(i32),
+    /// Keys of primary key tables are assigned to buckets by the hash index.
+    Dynamic,
+}
+
+/// Writer of a bucket.
+#[derive(Debug)]
+enum RecordWriter {
+    AppendOnly(AppendOnlyWriter),
+    MergeTree(MergeTreeWriter),
+}
+
+impl TableWrite {
+    pub fn new(table: FileStoreTable, commit_user: impl Into<String>) -> Self {
+        let memory_pool = MemoryPool::new(table.core_options().memory_pool_size() as usize);
+        Self {
+            memory_pool: Arc::new(memory_pool),
+            key_extractor: RowKeyExtractor::new(table.schema()),
+            bucket_assigner: None,
+            table,
+            commit_user: commit_user.into(),
+            writers: IndexMap::new(),
+            active_writers: IndexSet::new(),
+            restored_files: None,
+            ignore_previous_files: false,
+            base_snapshot_id: None,
+        }
+    }
+
+    /// Write the buckets regardless of their files in the latest snapshot, which are neither
+    /// compacted nor looked up, as they are to be overwritten by the commit.
+    pub fn with_ignore_previous_files(mut self, ignore_previous_files: bool) -> Self {
+        self.ignore_previous_files = ignore_previous_files;
+        self
+    }
+
+    /// Share the memory pool with the other operators, the writers of primary key tables
+    /// flush their buffers once failing to reserve them from the pool. Defaults to a pool of
+    /// `memory-pool.size` of its own.
+    pub fn with_memory_pool(mut self, memory_pool: Arc<MemoryPool>) -> Self {
+        self.memory_pool = memory_pool;
+        self
+    }
+
+    /// Get the memory pool of the writers.
+    pub fn memory_pool(&self) -> &Arc<MemoryPool> {
+        &self.memory_pool
+    }
+
+    /// Get the table to write.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the user committing the data written.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Get the latest snapshot the data written is based on, 0 if there was none, which is
+    /// read on the first write. Committed by [`TableCommit::with_base_snapshot`](crate::table::TableCommit::with_base_snapshot),
+    /// overwrites conflict with the overwrites committed after it.
+    #[inline]
+    pub fn base_snapshot_id(&self) -> Option<i64> {
+        self.base_snapshot_id
+    }
+
+    /// Write the rows of the batch as inserts.
+    ///
+    /// The columns are matched to the fields of the table by name, and casted to the types of
+    /// the fields if needed.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(name = "write.batch", skip_all, fields(row_count = batch.num_rows()))
+    )]
+    pub async fn write(&mut self, batch: &RecordBatch) -> crate::Result<()> {
+        let row_kinds = vec![RowKind::Insert; batch.num_rows()];
+        self.write_with_row_kinds(batch, &row_kinds).await
+    }
+
+    /// Write the rows of the batch with their row kinds, append only tables only accept
+    /// inserts and update afters.
+    pub async fn write_with_row_kinds(
+        &mut self,
+        batch: &RecordBatch,
+        row_kinds: &[RowKind],
+    ) -> crate::Result<()> {
+        if row_kinds.len() != batch.num_rows() {
+            return WriteInvalidSnafu {
+                message: format!(
+                    "{} row kinds are given for {} rows",
+                    row_kinds.len(),
+                    batch.num_rows()
+                ),
+            }
+            .fail();
+        }
+        let append_only = self.table.schema().primary_keys().is_empty();
+        if append_only && row_kinds.iter().any(|kind| !kind.is_add()) {
+            return WriteInvalidSnafu {
+                message: "Append only tables can only accept insert or update after rows"
+                    .to_string(),
+            }
+            .fail();
+        }
+        let batch = self.align(batch)?;
+        let bucket_mode = self.bucket_mode()?;
+
+        let mut rows_of_buckets: IndexMap<(Vec<u8>, i32), (BinaryRow, Vec<u32>)> = IndexMap::new();
+        for row in 0..batch.num_rows() {
+            let partition = self.key_extractor.partition(&batch, row)?;
+            let bucket = match bucket_mode {
+                BucketMode::Unaware => 0,
+                BucketMode::Fixed(num_buckets) => bucket(
+                    self.key_extractor.bucket_key_hash(&batch, row)?,
+                    num_buckets,
+                ),
+                BucketMode::Dynamic => {
+                    let hash = self.key_extractor.bucket_key_hash(&batch, row)?;
+                    self.bucket_assigner
+                        .get_or_insert_with(|| HashBucketAssigner::new(self.table.clone()))
+                        .assign(&partition, hash)
+                        .await?
+                }
+            };
+            rows_of_buckets
+                .entry((partition.to_serialized_bytes(), bucket))
+                .or_insert_with(|| (partition, vec![]))
+                .1
+                .push(row as u32);
+        }
+
+        let max_writers = self.table.core_options().write_max_writers();
+        for (key, (partition, rows)) in rows_of_buckets {
+            let (rows, kinds) = if rows.len() == batch.num_rows() {
+                (batch.clone(), row_kinds.to_vec())
+            } else {
+                let kinds = rows.iter().map(|row| row_kinds[*row as usize]).collect();
+                let rows = take_record_batch(&batch, &UInt32Array::from(rows))?;
+                (rows, kinds)
+            };
+            if !self.writers.contains_key(&key) {
+                let writer = self.create_writer(&key, &partition).await?;
+                self.writers.insert(key.clone(), (partition, writer));
+            }
+            match &mut self.writers[&key].1 {
+                RecordWriter::AppendOnly(writer) => writer.write(&rows).await?,
+                RecordWriter::MergeTree(writer) => writer.write(&rows, &kinds).await?,
+            }
+            self.active_writers.shift_remove(&key);
+            self.active_writers.insert(key);
+            while max_writers.is_some_and(|max| self.active_writers.len() > max) {
+                let Some(key) = self.active_writers.shift_remove_index(0) else {
+                    break;
+                };
+                match &mut self.writers[&key].1 {
+                    RecordWriter::AppendOnly(writer) => writer.flush().await?,
+                    RecordWriter::MergeTree(writer) => writer.flush().await?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Close the writers of all buckets and get the files written, the write can't be used
+    /// anymore.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "write.prepare_commit",
+            skip_all,
+            fields(message_count = tracing::field::Empty)
+        )
+    )]
+    pub async fn prepare_commit(&mut self) -> crate::Result<Vec<CommitMessage>> {
+        let mut messages = Vec::with_capacity(self.writers.len());
+        self.active_writers.clear();
+        for ((_, bucket), (partition, writer)) in std::mem::take(&mut self.writers) {
+            let (data_increment, compact_increment) = match writer {
+                RecordWriter::AppendOnly(writer) => {
+                    (writer.prepare_commit().await?, CompactIncrement::default())
+                }
+                RecordWriter::MergeTree(writer) => writer.prepare_commit().await?,
+            };
+            let message = CommitMessage::new(partition, bucket, data_increment, compact_increment);
+            if !message.is_empty() {
+                messages.push(message);
+            }
+        }
+        if let Some(assigner) = &mut self.bucket_assigner {
+            for (partition, bucket, index_increment) in assigner.prepare_commit().await? {
+                match messages
+                    .iter()
+                    .position(|m| m.bucket() == bucket && m.partition() == &partition)
+                {
+                    Some(i) => {
+                        messages[i] = messages[i].clone().with_index_increment(index_increment)
+                    }
+                    None => messages.push(
+                        CommitMessage::new(
+                            partition,
+                            bucket,
+                            DataIncrement::default(),
+                            CompactIncrement::default(),
+                        )
+                        .with_index_increment(index_increment),
+                    ),
+                }
+            }
+        }
+        record_span!(message_count = messages.len());
+        Ok(messages)
+    }
+
+    /// Match the columns of the batch to the fields of the table.
+    fn align(&self, batch: &RecordBatch) -> crate::Result<RecordBatch> {
+        let fields = self.table.schema().fields();
+        let columns = fields
+            .iter()
+            .map(|field| {
+                let Some(column) = batch.column_by_name(field.name()) else {
+                    return ColumnNotExistSnafu {
+                        column: field.name().to_string(),
+                    }
+                    .fail();
+                };
+                let data_type = to_arrow_field(field).data_type().clone();
+                Ok(if column.data_type() == &data_type {
+                    column.clone()
+                } else {
+                    arrow_cast::cast(column, &data_type)?
+                })
+            })
+            .collect::<crate::Result<Vec<ArrayRef>>>()?;
+        Ok(RecordBatch::try_new(
+            Arc::new(to_arrow_schema(fields)),
+            columns,
+        )?)
+    }
+
+    /// Get how rows are distributed into buckets.
+    fn bucket_mode(&self) -> crate::Result<BucketMode> {
+        let schema = self.table.schema();
+        let append_only = schema.primary_keys().is_empty();
+        match self.table.core_options().bucket() {
+            -1 if append_only => Ok(BucketMode::Unaware),
+            -1 if schema
+                .partition_keys()
+                .iter()
+                .any(|key| !schema.primary_keys().contains(key)) =>
+            {
+                WriteInvalidSnafu {
+                    message: "Writing dynamic bucket tables of primary keys not containing all partition keys is not supported yet".to_string(),
+                }
+                .fail()
+            }
+            -1 => Ok(BucketMode::Dynamic),
+            num_buckets if num_buckets > 0 && self.key_extractor.has_bucket_keys() => {
+                Ok(BucketMode::Fixed(num_buckets))
+            }
+            num_buckets if num_buckets > 0 => WriteInvalidSnafu {
+                message: "bucket-key is required for append only tables of fixed buckets"
+                    .to_string(),
+            }
+            .fail(),
+            num_buckets => WriteInvalidSnafu {
+                message: format!("Invalid bucket {num_buckets}"),
+            }
+            .fail(),
+        }
+    }
+
+    async fn create_writer(
+        &mut self,
+        key: &(Vec<u8>, i32),
+        partition: &BinaryRow,
+    ) -> crate::Result<RecordWriter> {
+        let restored_files = self.restored_files(key).await?;
+        let max_sequence_number = restored_files
+            .iter()
+            .map(|file| file.max_sequence_number)
+            .max();
+        let table = &self.table;
+        let schema = table.schema();
+        let options = table.core_options();
+        let path_factory = table.path_factory().data_file_path_factory(
+            partition,
+            key.1,
+            &options.file_format(),
+        )?;
+        let next_sequence_number = max_sequence_number.map_or(0, |max| max + 1);
+        if schema.primary_keys().is_empty() {
+            let writer = DataFileWriter::new(
+                table.file_io().clone(),
+                path_factory,
+                schema.fields().to_vec(),
+                FileCompression::of_options(&options),
+                0,
+                schema.id(),
+                options.target_file_size(),
+            )
+            .with_stats_modes(StatsMode::of_fields(&options, schema.fields())?)
+            .with_file_index(FileIndexOptions::of_fields(&options, schema.fields())?);
+            return Ok(RecordWriter::AppendOnly(AppendOnlyWriter::new(
+                writer,
+                next_sequence_number,
+            )));
+        }
+        let changelog_producer = match options.changelog_producer()? {
+            // the changelog of full compactions is produced by the compactions
+            ChangelogProducer::None | ChangelogProducer::FullCompaction => ChangelogMode::None,
+            ChangelogProducer::Input => ChangelogMode::Input,
+            ChangelogProducer::Lookup => {
+                let reader = MergeTreeReader::new(
+                    table.file_io().clone(),
+                    table.schema_manager(),
+                    schema.clone(),
+                    schema.fields().to_vec(),
+                )?;
+                // first rows never change, so only the inserted keys are in the changelog
+                let row_deduplicate = options.changelog_producer_row_deduplicate()
+                    || options.merge_engine()? == MergeEngine::FirstRow;
+                ChangelogMode::Lookup(Box::new(LookupChangelog::new(
+                    reader,
+                    partition.clone(),
+                    key.1,
+                    path_factory.bucket_path(),
+                    restored_files.clone(),
+                    row_deduplicate,
+                )))
+            }
+        };
+        let mut writer = MergeTreeWriter::new(
+            key_value_file_writer_factory(table, path_factory.clone())?,
+            options.write_buffer_size(),
+            next_sequence_number,
+        )
+        .with_changelog_producer(changelog_producer)
+        .with_memory_reservation(
+            self.memory_pool
+                .reservation(format!("MergeTreeWriter of bucket {}", key.1)),
+        );
+        if options.write_buffer_spillable() {
+            writer = writer.with_spill_dir(
+                options.write_buffer_spill_dir(),
+                options.write_buffer_spill_max_disk_size(),
+            );
+        }
+        // the deletion vectors of the files compacted are not maintained on write, and write
+        // only tables are compacted by dedicated compactions
+        if !options.write_only() && !options.deletion_vectors_enabled() {
+            let levels = Levels::new(options.num_levels(), restored_files)?;
+            let strategy = UniversalCompaction::new(
+                options.compaction_max_size_amplification_percent(),
+                options.compaction_size_ratio(),
+                options.num_sorted_run_compaction_trigger(),
+            );
+            let rewriter = merge_tree_compact_rewriter(table, partition, key.1, path_factory)?;
+            writer = writer
+                .with_compact_manager(MergeTreeCompactManager::new(levels, strategy, rewriter));
+        }
+        Ok(RecordWriter::MergeTree(writer))
+    }
+
+    /// Get the data files of the bucket in the latest snapshot.
+    async fn restored_files(&mut self, key: &BucketKey) -> crate::Result<Vec<DataFileMeta>> {
+        if self.restored_files.is_none() {
+            let mut restored_files: HashMap<_, Vec<DataFileMeta>> = HashMap::new();
+            // the files are restored from the latest snapshot regardless of the scan options
+            let latest = self.table.snapshot_manager().latest_snapshot_id().await?;
+            self.base_snapshot_id = Some(latest.unwrap_or(0));
+            let plan = match latest {
+                Some(snapshot_id) if !self.ignore_previous_files => {
+                    TableScan::new(self.table.clone())
+                        .as_of_snapshot(snapshot_id)
+                        .plan()
+                        .await?
+                }
+                _ => Plan::new(None, vec![]),
+            };
+            for split in plan.splits() {
+                let key = (split.partition().to_serialized_bytes(), split.bucket());
+                restored_files
+                    .entry(key)
+                    .or_default()
+                    .extend(split.data_files().iter().cloned());
+            }
+            self.restored_files = Some(restored_files);
+        }
+        Ok(self
+            .restored_files
+            .as_ref()
+            .and_then(|restored_files| restored_files.get(key).cloned())
+            .unwrap_or_default())
+    }
+}
+
+/// Create the factory of the key value files of a bucket of the primary key table into the
+/// files of the path factory.
+pub(crate) fn key_value_file_writer_factory(
+    table: &FileStoreTable,
+    path_factory: DataFilePathFactory,
+) -> crate::Result<KeyValueFileWriterFactory> {
+    let schema = table.schema();
+    let options = table.core_options();
+    let key_fields = schema.trimmed_primary_key_fields();
+    let key_indices = key_fields
+        .iter()
+        .map(|key| {
+            schema
+                .fields()
+                .iter()
+                .position(|f| f.id() == key.id())
+                .expect("primary keys are validated by the schema")
+        })
+        .collect();
+    let sequence_indices = sequence_fields(schema)?
+        .iter()
+        .filter_map(|field| schema.fields().iter().position(|f| f.id() == field.id()))
+        .collect();
+    let fields = key_value_fields(&key_fields, schema.fields());
+    let stats_modes = StatsMode::of_fields(&options, &fields)?;
+    let file_index = FileIndexOptions::of_fields(&options, &fields)?;
+    Ok(KeyValueFileWriterFactory::new(
+        table.file_io().clone(),
+        path_factory,
+        schema.id(),
+        options.target_file_size(),
+        key_indices,
+        fields,
+        FileCompression::of_options(&options),
+    )
+    .with_sequence_indices(sequence_indices)
+    .with_stats_modes(stats_modes)
+    .with_file_index(file_index))
+}
+
+/// Create the rewriter of the files of a bucket of the primary key table into the levels below
+/// `num-levels`, producing the full changelog with `changelog-producer` of `full-compaction`.
+pub(crate) fn merge_tree_compact_rewriter(
+    table: &FileStoreTable,
+    partition: &BinaryRow,
+    bucket: i32,
+    path_factory: DataFilePathFactory,
+) -> crate::Result<MergeTreeCompactRewriter> {
+    let schema = table.schema();
+    let options = table.core_options();
+    let reader = MergeTreeReader::new(
+        table.file_io().clone(),
+        table.schema_manager(),
+        schema.clone(),
+        schema.fields().to_vec(),
+    )?;
+    let rewriter = MergeTreeCompactRewriter::new(
+        reader,
+        key_value_file_writer_factory(table, path_factory)?,
+        partition.clone(),
+        bucket,
+        options.num_levels() - 1,
+    );
+    Ok(
+        if options.changelog_producer()? == ChangelogProducer::FullCompaction {
+            // first rows never change, so only the inserted keys are in the changelog
+            let row_deduplicate = options.changelog_producer_row_deduplicate()
+                || options.merge_engine()? == MergeEngine::FirstRow;
+            rewriter.with_full_changelog(row_deduplicate)
+        } else {
+            rewriter
+        },
+    )
+}
@@ -0,0 +1,797 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::spec::Datum;
+use crate::table::{FileStoreTable, TableCommit, TableWrite};
+use std::collections::HashMap;
+
+/// Builder of the write and the commit of a batch job, the data is committed once by a
+/// random commit user.
+///
+/// The data committed overwrites the static partition if it is set by
+/// [`with_overwrite`](Self::with_overwrite), like `INSERT OVERWRITE`.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/table/sink/BatchWriteBuilderImpl.java>
+#[derive(Debug, Clone)]
+pub struct BatchWriteBuilder {
+    table: FileStoreTable,
+    commit_user: String,
+    overwrite: Option<HashMap<String, Datum>>,
+}
+
+impl BatchWriteBuilder {
+    /// The commit identifier of batch commits.
+    pub const COMMIT_IDENTIFIER: i64 = i64::MAX;
+
+    pub fn new(table: FileStoreTable) -> Self {
+        Self {
+            table,
+            commit_user: uuid::Uuid::new_v4().to_string(),
+            overwrite: None,
+        }
+    }
+
+    /// Overwrite the static partition by the data written, the values of some of its
+    /// partition keys, see [`TableCommit::with_overwrite`].
+    pub fn with_overwrite(mut self, static_partition: HashMap<String, Datum>) -> Self {
+        self.overwrite = Some(static_partition);
+        self
+    }
+
+    /// Get the table to write.
+    #[inline]
+    pub fn table(&self) -> &FileStoreTable {
+        &self.table
+    }
+
+    /// Get the user committing the data written.
+    #[inline]
+    pub fn commit_user(&self) -> &str {
+        &self.commit_user
+    }
+
+    /// Create a write to write the data.
+    pub fn new_write(&self) -> TableWrite {
+        TableWrite::new(self.table.clone(), self.commit_user.clone())
+            .with_ignore_previous_files(self.overwrite.is_some())
+    }
+
+    /// Create a commit to commit the data written.
+    pub fn new_commit(&self) -> TableCommit {
+        let commit = TableCommit::new(
+            self.table.clone(),
+            self.commit_user.clone(),
+            Self::COMMIT_IDENTIFIER,
+        );
+        match &self.overwrite {
+            Some(static_partition) => commit.with_overwrite(static_partition.clone()),
+            None => commit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::DataFileReader;
+    use crate::memory::MemoryPool;
+    use crate::spec::{key_value_fields, CommitKind, DataField, Datum, RowKind, Schema};
+    use crate::table::{CommitMessage, Table};
+    use crate::test_utils::{create_id_name_table, create_table, id_name_batch};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Int32Type, Int8Type};
+    use arrow_array::{Array, Int32Array, StringArray};
+    use arrow_select::concat::concat_batches;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    async fn read_all(table: &FileStoreTable) -> Vec<(i32, String)> {
+        let plan = table.new_scan().plan().await.unwrap();
+        let read = table.new_read();
+        let mut rows = Vec::new();
+        for split in plan.splits() {
+            let batches = read
+                .read(split)
+                .await
+                .unwrap()
+                .collect::<crate::Result<Vec<_>>>()
+                .unwrap();
+            let batch =
+                concat_batches(&read.data_file_reader().unwrap().read_schema(), &batches).unwrap();
+            let ids = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .unwrap();
+            let names = batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<StringArray>()
+                .unwrap();
+            rows.extend((0..batch.num_rows()).map(|i| (ids.value(i), names.value(i).to_string())));
+        }
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_write_append_only_table() {
+        let table = create_id_name_table(
+            "file:/tmp/test_write_append_only_table",
+            &[],
+            &[("file.format", "parquet"), ("target-file-size", "1 b")],
+        )
+        .await;
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write
+            .write(&id_name_batch(&table, &[2, 1], &["b", "a"]))
+            .await
+            .unwrap();
+        write
+            .write(&id_name_batch(&table, &[3], &["c"]))
+            .await
+            .unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        assert_eq!(messages.len(), 1);
+        // files are rolled after each batch by the tiny target file size
+        assert_eq!(messages[0].data_increment().new_files.len(), 2);
+        builder.new_commit().commit(messages).await.unwrap();
+
+        let snapshot = table
+            .snapshot_manager()
+            .latest_snapshot()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.id(), 1);
+        assert_eq!(snapshot.total_record_count(), Some(3));
+        assert_eq!(
+            read_all(&table).await,
+            vec![
+                (2, "b".to_string()),
+                (1, "a".to_string()),
+                (3, "c".to_string())
+            ]
+        );
+
+        // empty commits create no snapshot
+        builder.new_commit().commit(vec![]).await.unwrap();
+        let snapshot = table
+            .snapshot_manager()
+            .latest_snapshot()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.id(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_write_primary_key_table() {
+        let table = create_id_name_table(
+            "file:/tmp/test_write_primary_key_table",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("write-buffer-size", "1 b"),
+            ],
+        )
+        .await;
+        for (ids, names) in [(vec![3, 1], vec!["c", "a"]), (vec![2, 1], vec!["b", "a2"])] {
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            // the tiny write buffer spills every batch into a sorted file
+            write
+                .write(&id_name_batch(&table, &ids, &names))
+                .await
+                .unwrap();
+            write
+                .write(&id_name_batch(&table, &[4], &["d"]))
+                .await
+                .unwrap();
+            let messages = write.prepare_commit().await.unwrap();
+            assert_eq!(messages[0].data_increment().new_files.len(), 2);
+            builder.new_commit().commit(messages).await.unwrap();
+        }
+
+        let snapshot = table
+            .snapshot_manager()
+            .latest_snapshot()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(snapshot.id(), 2);
+        assert_eq!(
+            read_all(&table).await,
+            vec![
+                (1, "a2".to_string()),
+                (2, "b".to_string()),
+                (3, "c".to_string()),
+                (4, "d".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_overwrite() {
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+                DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+            ])
+            .partition_keys(vec!["name".to_string()])
+            .options(HashMap::from([(
+                "file.format".to_string(),
+                "parquet".to_string(),
+            )]))
+            .build();
+        let table = create_table("file:/tmp/test_write_overwrite", &schema).await;
+        let write = |builder: BatchWriteBuilder, ids: Vec<i32>, names: Vec<&'static str>| {
+            let table = table.clone();
+            async move {
+                let mut write = builder.new_write();
+                write.write(&id_name_batch(&table, &ids, &names)).await?;
+                builder
+                    .new_commit()
+                    .commit(write.prepare_commit().await?)
+                    .await
+            }
+        };
+        let sorted = |mut rows: Vec<(i32, String)>| {
+            rows.sort();
+            rows
+        };
+        let builder = table.new_batch_write_builder();
+        write(builder, vec![1, 2, 3], vec!["a", "a", "b"])
+            .await
+            .unwrap();
+
+        // only the partitions written are overwritten by dynamic overwrites
+        let builder = table
+            .new_batch_write_builder()
+            .with_overwrite(HashMap::new());
+        write(builder, vec![4], vec!["a"]).await.unwrap();
+        let snapshot = table.snapshot_manager().latest_snapshot().await.unwrap();
+        assert_eq!(snapshot.unwrap().commit_kind(), &CommitKind::OVERWRITE);
+        assert_eq!(
+            sorted(read_all(&table).await),
+            vec![(3, "b".to_string()), (4, "a".to_string())]
+        );
+
+        let static_partition = HashMap::from([("name".to_string(), Datum::String("b".into()))]);
+        let builder = table
+            .new_batch_write_builder()
+            .with_overwrite(static_partition.clone());
+        write(builder, vec![5], vec!["b"]).await.unwrap();
+        assert_eq!(
+            sorted(read_all(&table).await),
+            vec![(4, "a".to_string()), (5, "b".to_string())]
+        );
+        // the static partition is overwritten even if nothing is written
+        let builder = table
+            .new_batch_write_builder()
+            .with_overwrite(static_partition.clone());
+        write(builder, vec![], vec![]).await.unwrap();
+        assert_eq!(read_all(&table).await, vec![(4, "a".to_string())]);
+
+        let builder = table
+            .new_batch_write_builder()
+            .with_overwrite(static_partition);
+        assert!(matches!(
+            write(builder, vec![6], vec!["a"]).await,
+            Err(crate::Error::WriteInvalid { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_write_with_memory_pool() {
+        let table = create_id_name_table(
+            "file:/tmp/test_write_with_memory_pool",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("memory-pool.size", "1 b"),
+            ],
+        )
+        .await;
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        // the large write buffer is flushed by the exhausted memory pool
+        write
+            .write(&id_name_batch(&table, &[3, 1], &["c", "a"]))
+            .await
+            .unwrap();
+        write
+            .write(&id_name_batch(&table, &[2], &["b"]))
+            .await
+            .unwrap();
+        assert_eq!(write.memory_pool().used(), 0);
+        let messages = write.prepare_commit().await.unwrap();
+        assert_eq!(messages[0].data_increment().new_files.len(), 2);
+        builder.new_commit().commit(messages).await.unwrap();
+
+        let memory_pool = Arc::new(MemoryPool::new(1));
+        let read = table.new_read().with_memory_pool(memory_pool);
+        let plan = table.new_scan().plan().await.unwrap();
+        let Err(err) = read.read(&plan.splits()[0]).await else {
+            panic!("the data files are read beyond the memory pool");
+        };
+        assert!(matches!(err, crate::Error::MemoryExhausted { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_write_spillable_buffer() {
+        let spill_dir = "/tmp/test_write_spillable_buffer/spill";
+        let table = create_id_name_table(
+            "file:/tmp/test_write_spillable_buffer",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("write-buffer-size", "1 b"),
+                ("write-buffer-spillable", "true"),
+                ("write-buffer-spill.dir", spill_dir),
+            ],
+        )
+        .await;
+        for (ids, names) in [(vec![3, 1], vec!["c", "a"]), (vec![2, 1], vec!["b", "a2"])] {
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            // every batch is spilled locally, and the runs are merged into a single file
+            write
+                .write(&id_name_batch(&table, &ids, &names))
+                .await
+                .unwrap();
+            write
+                .write(&id_name_batch(&table, &[4], &["d"]))
+                .await
+                .unwrap();
+            assert_eq!(std::fs::read_dir(spill_dir).unwrap().count(), 2);
+            let messages = write.prepare_commit().await.unwrap();
+            assert_eq!(messages[0].data_increment().new_files.len(), 1);
+            assert_eq!(std::fs::read_dir(spill_dir).unwrap().count(), 0);
+            builder.new_commit().commit(messages).await.unwrap();
+        }
+        assert_eq!(
+            read_all(&table).await,
+            vec![
+                (1, "a2".to_string()),
+                (2, "b".to_string()),
+                (3, "c".to_string()),
+                (4, "d".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_compaction() {
+        let table = create_id_name_table(
+            "file:/tmp/test_write_compaction",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("write-buffer-size", "1 b"),
+                ("num-sorted-run.compaction-trigger", "2"),
+            ],
+        )
+        .await;
+        let commits = [
+            (vec![3, 1, 2], vec!["c", "a", "b"], vec![RowKind::Insert; 3]),
+            (
+                vec![1, 2, 4],
+                vec!["a2", "b", "d"],
+                vec![RowKind::UpdateAfter, RowKind::Delete, RowKind::Insert],
+            ),
+        ];
+        for (ids, names, kinds) in commits {
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            // the tiny write buffer spills every row into a sorted run
+            for i in 0..ids.len() {
+                write
+                    .write_with_row_kinds(
+                        &id_name_batch(&table, &ids[i..=i], &names[i..=i]),
+                        &kinds[i..=i],
+                    )
+                    .await
+                    .unwrap();
+            }
+            let messages = write.prepare_commit().await.unwrap();
+            assert_eq!(messages[0].data_increment().new_files.len(), 3);
+            let compact_increment = messages[0].compact_increment();
+            assert!(!compact_increment.compact_before.is_empty());
+            assert!(!compact_increment.compact_after.is_empty());
+            builder.new_commit().commit(messages).await.unwrap();
+
+            // the files compacted are committed into a snapshot after the appended files
+            let snapshot = table
+                .snapshot_manager()
+                .latest_snapshot()
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(snapshot.commit_kind(), &CommitKind::COMPACT);
+        }
+        assert_eq!(
+            read_all(&table).await,
+            vec![
+                (1, "a2".to_string()),
+                (3, "c".to_string()),
+                (4, "d".to_string())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_file_formats() {
+        let mut formats = vec![("avro", "snappy"), ("parquet", "gzip")];
+        if cfg!(feature = "format-orc") {
+            formats.push(("orc", "zstd"));
+        }
+        for (format, compression) in formats {
+            let table = create_id_name_table(
+                &format!("file:/tmp/test_write_file_formats_{format}"),
+                &["id"],
+                &[
+                    ("file.format", format),
+                    ("file.compression", compression),
+                    ("bucket", "1"),
+                ],
+            )
+            .await;
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            write
+                .write(&id_name_batch(&table, &[2, 1, 2], &["b", "a", "b2"]))
+                .await
+                .unwrap();
+            let messages = write.prepare_commit().await.unwrap();
+            let file_name = &messages[0].data_increment().new_files[0].file_name;
+            assert!(file_name.ends_with(&format!(".{format}")));
+            builder.new_commit().commit(messages).await.unwrap();
+            assert_eq!(
+                read_all(&table).await,
+                vec![(1, "a".to_string()), (2, "b2".to_string())],
+                "{format}"
+            );
+        }
+    }
+
+    /// Read the (row kind, id, name) rows of the changelog files of the messages.
+    async fn read_changelog(
+        table: &FileStoreTable,
+        messages: &[CommitMessage],
+    ) -> Vec<(RowKind, i32, String)> {
+        let schema = table.schema();
+        let reader = DataFileReader::new(
+            table.file_io().clone(),
+            table.schema_manager(),
+            schema.clone(),
+            key_value_fields(&schema.trimmed_primary_key_fields(), schema.fields()),
+        );
+        let mut rows = Vec::new();
+        for message in messages {
+            let bucket_path = table
+                .path_factory()
+                .bucket_path(message.partition(), message.bucket())
+                .unwrap();
+            for file in &message.data_increment().changelog_files {
+                assert!(file.file_name.starts_with("changelog-"));
+                let path = format!("{bucket_path}/{}", file.file_name);
+                for batch in reader.read(&path, file).await.unwrap() {
+                    let batch = batch.unwrap();
+                    let kinds = batch.column(2).as_primitive::<Int8Type>();
+                    let ids = batch.column(3).as_primitive::<Int32Type>();
+                    let names = batch.column(4).as_string::<i32>();
+                    rows.extend((0..batch.num_rows()).map(|i| {
+                        (
+                            RowKind::from_value(kinds.value(i)).unwrap(),
+                            ids.value(i),
+                            names.value(i).to_string(),
+                        )
+                    }));
+                }
+            }
+        }
+        rows
+    }
+
+    #[tokio::test]
+    async fn test_write_input_changelog() {
+        let table = create_id_name_table(
+            "file:/tmp/test_write_input_changelog",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("changelog-producer", "input"),
+            ],
+        )
+        .await;
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write
+            .write_with_row_kinds(
+                &id_name_batch(&table, &[2, 1, 2], &["b", "a", "b"]),
+                &[RowKind::Insert, RowKind::Insert, RowKind::Delete],
+            )
+            .await
+            .unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        // the written key values sorted by key and sequence number
+        assert_eq!(
+            read_changelog(&table, &messages).await,
+            vec![
+                (RowKind::Insert, 1, "a".to_string()),
+                (RowKind::Insert, 2, "b".to_string()),
+                (RowKind::Delete, 2, "b".to_string()),
+            ]
+        );
+        builder.new_commit().commit(messages).await.unwrap();
+        let snapshot = table
+            .snapshot_manager()
+            .latest_snapshot()
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(snapshot.changelog_manifest_list().is_some());
+        assert_eq!(snapshot.changelog_record_count(), Some(3));
+        assert_eq!(read_all(&table).await, vec![(1, "a".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_write_lookup_changelog() {
+        let table = create_id_name_table(
+            "file:/tmp/test_write_lookup_changelog",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("changelog-producer", "lookup"),
+            ],
+        )
+        .await;
+        let commits = [
+            (
+                vec![1, 2, 1],
+                vec!["a0", "b", "a"],
+                vec![RowKind::Insert; 3],
+                vec![(RowKind::Insert, 1, "a"), (RowKind::Insert, 2, "b")],
+            ),
+            (
+                vec![3, 1, 2, 4, 4],
+                vec!["c", "a2", "b", "d", "d"],
+                vec![
+                    RowKind::Insert,
+                    RowKind::UpdateAfter,
+                    RowKind::Delete,
+                    RowKind::Insert,
+                    RowKind::Delete,
+                ],
+                vec![
+                    (RowKind::UpdateBefore, 1, "a"),
+                    (RowKind::UpdateAfter, 1, "a2"),
+                    (RowKind::Delete, 2, "b"),
+                    (RowKind::Insert, 3, "c"),
+                ],
+            ),
+        ];
+        for (ids, names, kinds, expected) in commits {
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            write
+                .write_with_row_kinds(&id_name_batch(&table, &ids, &names), &kinds)
+                .await
+                .unwrap();
+            let messages = write.prepare_commit().await.unwrap();
+            let expected: Vec<_> = expected
+                .into_iter()
+                .map(|(kind, id, name)| (kind, id, name.to_string()))
+                .collect();
+            assert_eq!(read_changelog(&table, &messages).await, expected);
+            builder.new_commit().commit(messages).await.unwrap();
+        }
+        assert_eq!(
+            read_all(&table).await,
+            vec![(1, "a2".to_string()), (3, "c".to_string())]
+        );
+
+        // updates to equal values are skipped with row deduplicate
+        let table = create_id_name_table(
+            "file:/tmp/test_write_lookup_changelog_row_deduplicate",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "1"),
+                ("changelog-producer", "lookup"),
+                ("changelog-producer.row-deduplicate", "true"),
+            ],
+        )
+        .await;
+        let mut changelogs = Vec::new();
+        for names in [["a", "b"], ["a", "b2"]] {
+            let builder = table.new_batch_write_builder();
+            let mut write = builder.new_write();
+            write
+                .write(&id_name_batch(&table, &[1, 2], &names))
+                .await
+                .unwrap();
+            let messages = write.prepare_commit().await.unwrap();
+            changelogs.push(read_changelog(&table, &messages).await);
+            builder.new_commit().commit(messages).await.unwrap();
+        }
+        assert_eq!(
+            changelogs[1],
+            vec![
+                (RowKind::UpdateBefore, 2, "b".to_string()),
+                (RowKind::UpdateAfter, 2, "b2".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_fixed_bucket_table() {
+        let table = create_id_name_table(
+            "file:/tmp/test_write_fixed_bucket_table",
+            &["id"],
+            &[("file.format", "parquet"), ("bucket", "3")],
+        )
+        .await;
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        let names = ["a", "b", "c", "d", "e", "f"];
+        write
+            .write(&id_name_batch(&table, &[0, 1, 2, 3, 4, 5], &names))
+            .await
+            .unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        // the buckets of the ids computed by paimon-java
+        let mut buckets: Vec<_> = messages
+            .iter()
+            .map(|m| (m.bucket(), m.data_increment().new_files[0].row_count))
+            .collect();
+        buckets.sort();
+        assert_eq!(buckets, vec![(0, 2), (1, 2), (2, 2)]);
+        builder.new_commit().commit(messages).await.unwrap();
+
+        let mut rows = read_all(&table).await;
+        rows.sort();
+        let expected: Vec<_> = names
+            .iter()
+            .enumerate()
+            .map(|(id, name)| (id as i32, name.to_string()))
+            .collect();
+        assert_eq!(rows, expected);
+
+        let table = create_id_name_table(
+            "file:/tmp/test_write_fixed_bucket_table",
+            &[],
+            &[("bucket", "3")],
+        )
+        .await;
+        let mut write = table.new_batch_write_builder().new_write();
+        assert!(matches!(
+            write.write(&id_name_batch(&table, &[0], &["a"])).await,
+            Err(crate::Error::WriteInvalid { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_write_max_writers() {
+        let table = create_id_name_table(
+            "file:/tmp/test_write_max_writers",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("bucket", "3"),
+                ("write.max-writers", "1"),
+            ],
+        )
+        .await;
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        // the ids 0 and 2 are of different buckets, each write flushes the other bucket
+        write
+            .write(&id_name_batch(&table, &[0, 2], &["a", "c"]))
+            .await
+            .unwrap();
+        write
+            .write(&id_name_batch(&table, &[0, 2], &["a2", "c2"]))
+            .await
+            .unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        assert_eq!(messages.len(), 2);
+        for message in &messages {
+            assert_eq!(message.data_increment().new_files.len(), 2);
+        }
+        builder.new_commit().commit(messages).await.unwrap();
+
+        let mut rows = read_all(&table).await;
+        rows.sort();
+        assert_eq!(rows, vec![(0, "a2".to_string()), (2, "c2".to_string())]);
+    }
+
+    #[tokio::test]
+    async fn test_write_dynamic_bucket_table() {
+        let table = create_id_name_table(
+            "file:/tmp/test_write_dynamic_bucket_table",
+            &["id"],
+            &[
+                ("file.format", "parquet"),
+                ("dynamic-bucket.target-row-num", "2"),
+            ],
+        )
+        .await;
+        let commit = |ids: Vec<i32>, names: Vec<&'static str>| {
+            let table = table.clone();
+            async move {
+                let builder = table.new_batch_write_builder();
+                let mut write = builder.new_write();
+                write
+                    .write(&id_name_batch(&table, &ids, &names))
+                    .await
+                    .unwrap();
+                let messages = write.prepare_commit().await.unwrap();
+                builder.new_commit().commit(messages.clone()).await.unwrap();
+                messages
+            }
+        };
+        let messages = commit(vec![0, 1, 2, 3, 4], vec!["a", "b", "c", "d", "e"]).await;
+        let buckets: Vec<_> = messages.iter().map(|m| m.bucket()).collect();
+        assert_eq!(buckets, vec![0, 1, 2]);
+        assert!(messages
+            .iter()
+            .all(|m| m.index_increment().new_index_files.len() == 1));
+
+        // the bucket of the key written is restored from the hash index, while the new key
+        // fills the bucket not full
+        let messages = commit(vec![0, 5], vec!["a2", "f"]).await;
+        let buckets: Vec<_> = messages
+            .iter()
+            .map(|m| (m.bucket(), m.index_increment().deleted_index_files.len()))
+            .collect();
+        assert_eq!(buckets, vec![(0, 0), (2, 1)]);
+
+        let snapshot = table.snapshot_manager().latest_snapshot().await.unwrap();
+        let entries = table
+            .index_manifest_entries(&snapshot.unwrap())
+            .await
+            .unwrap();
+        let mut row_counts: Vec<_> = entries
+            .iter()
+            .map(|e| (e.bucket, e.index_file.row_count))
+            .collect();
+        row_counts.sort();
+        assert_eq!(row_counts, vec![(0, 2), (1, 2), (2, 2)]);
+
+        let mut rows = read_all(&table).await;
+        rows.sort();
+        assert_eq!(
+            rows,
+            vec![
+                (0, "a2".to_string()),
+                (1, "b".to_string()),
+                (2, "c".to_string()),
+                (3, "d".to_string()),
+                (4, "e".to_string()),
+                (5, "f".to_string())
+            ]
+        );
+    }
+}
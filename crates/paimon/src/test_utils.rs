@@ -0,0 +1,146 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Helpers shared by the tests of the crate.
+
+use crate::catalog::Identifier;
+use crate::format::to_arrow_schema;
+use crate::io::FileIOBuilder;
+use crate::spec::{BinaryTableStats, DataField, DataFileMeta, RowKind, Schema};
+use crate::table::{CommitMessage, FileStoreTable, Table};
+use crate::utils::SchemaManager;
+use arrow_array::{Int32Array, RecordBatch, StringArray};
+use chrono::DateTime;
+use std::sync::Arc;
+
+/// Create the table `db.t` of the schema at the location, deleting the files left there.
+pub(crate) async fn create_table(location: &str, schema: &Schema) -> FileStoreTable {
+    let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+    let _ = file_io.delete_dir(&format!("{location}/")).await;
+    let schema = SchemaManager::new(file_io.clone(), location)
+        .create_table(schema)
+        .await
+        .unwrap();
+    FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema)
+}
+
+/// The schema of the fields `id INT NOT NULL` and `name STRING`, with the primary keys and the
+/// options.
+pub(crate) fn id_name_schema(primary_keys: &[&str], options: &[(&str, &str)]) -> Schema {
+    Schema::builder()
+        .fields(vec![
+            DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+            DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+        ])
+        .primary_keys(primary_keys.iter().map(|k| k.to_string()).collect())
+        .options(
+            options
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+        .build()
+}
+
+/// Create the table `db.t` of [`id_name_schema`] at the location.
+pub(crate) async fn create_id_name_table(
+    location: &str,
+    primary_keys: &[&str],
+    options: &[(&str, &str)],
+) -> FileStoreTable {
+    create_table(location, &id_name_schema(primary_keys, options)).await
+}
+
+/// The schema of the fields `id INT NOT NULL`, `name STRING` and `price INT` keyed by `id`, in a
+/// single bucket of parquet files.
+pub(crate) fn id_name_price_schema() -> Schema {
+    Schema::builder()
+        .fields(vec![
+            DataField::new(0, "id".to_string(), "INT NOT NULL".parse().unwrap()),
+            DataField::new(1, "name".to_string(), "STRING".parse().unwrap()),
+            DataField::new(2, "price".to_string(), "INT".parse().unwrap()),
+        ])
+        .primary_keys(vec!["id".to_string()])
+        .options(
+            [
+                ("bucket".to_string(), "1".to_string()),
+                ("file.format".to_string(), "parquet".to_string()),
+            ]
+            .into(),
+        )
+        .build()
+}
+
+/// The batch of the ids and the names of a table of [`id_name_schema`].
+pub(crate) fn id_name_batch(table: &FileStoreTable, ids: &[i32], names: &[&str]) -> RecordBatch {
+    RecordBatch::try_new(
+        Arc::new(to_arrow_schema(table.schema().fields())),
+        vec![
+            Arc::new(Int32Array::from(ids.to_vec())),
+            Arc::new(StringArray::from(names.to_vec())),
+        ],
+    )
+    .unwrap()
+}
+
+/// Write the rows of the batch as inserts and commit them, returning the messages committed.
+pub(crate) async fn write_and_commit(
+    table: &FileStoreTable,
+    batch: &RecordBatch,
+) -> Vec<CommitMessage> {
+    write_and_commit_with_row_kinds(table, batch, &vec![RowKind::Insert; batch.num_rows()]).await
+}
+
+/// Write the rows of the batch with their row kinds and commit them, returning the messages
+/// committed.
+pub(crate) async fn write_and_commit_with_row_kinds(
+    table: &FileStoreTable,
+    batch: &RecordBatch,
+    row_kinds: &[RowKind],
+) -> Vec<CommitMessage> {
+    let builder = table.new_batch_write_builder();
+    let mut write = builder.new_write();
+    write.write_with_row_kinds(batch, row_kinds).await.unwrap();
+    let messages = write.prepare_commit().await.unwrap();
+    builder.new_commit().commit(messages.clone()).await.unwrap();
+    messages
+}
+
+/// The meta of a data file of a row of level 0 without stats, whose other fields are set by
+/// struct updates.
+pub(crate) fn data_file(name: &str) -> DataFileMeta {
+    let stats = BinaryTableStats::new(vec![], vec![], vec![]);
+    DataFileMeta {
+        file_name: name.to_string(),
+        file_size: 0,
+        row_count: 1,
+        min_key: vec![],
+        max_key: vec![],
+        key_stats: stats.clone(),
+        value_stats: stats,
+        min_sequence_number: 0,
+        max_sequence_number: 0,
+        schema_id: 0,
+        level: 0,
+        extra_files: vec![],
+        creation_time: DateTime::from_timestamp_millis(1725614755039).unwrap(),
+        delete_row_count: None,
+        embedded_index: None,
+        file_source: None,
+        value_stats_cols: None,
+    }
+}
@@ -0,0 +1,337 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::io::FileIO;
+use crate::spec::Snapshot;
+use crate::utils::{SchemaManager, SnapshotManager, TagManager};
+use std::sync::Arc;
+
+/// Name of the main branch, whose files are in the table directory.
+pub const DEFAULT_MAIN_BRANCH: &str = "main";
+
+const BRANCH_PREFIX: &str = "branch-";
+
+/// Get the path of the directory of the branch, the table path for the main branch.
+pub fn branch_path(table_path: &str, branch: &str) -> String {
+    if branch == DEFAULT_MAIN_BRANCH {
+        table_path.to_string()
+    } else {
+        format!("{table_path}/branch/{BRANCH_PREFIX}{branch}")
+    }
+}
+
+/// Manager for the branches of a table, providing utility methods around the `branch`
+/// directory.
+///
+/// A branch has its own schemas, snapshots and tags in `branch/branch-<name>`, while sharing
+/// the manifests and the data files with the main branch. Branches are created from the main
+/// branch, empty with the latest schema, or from a tag or a snapshot.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/utils/BranchManager.java>
+#[derive(Clone, Debug)]
+pub struct BranchManager {
+    file_io: Arc<dyn FileIO>,
+    table_path: String,
+}
+
+impl BranchManager {
+    pub fn new(file_io: Arc<dyn FileIO>, table_path: impl Into<String>) -> Self {
+        let table_path = table_path.into().trim_end_matches('/').to_string();
+        Self {
+            file_io,
+            table_path,
+        }
+    }
+
+    /// Get the path of the branch directory.
+    pub fn branch_dir(&self) -> String {
+        format!("{}/branch", self.table_path)
+    }
+
+    /// Get the path of the directory of the branch with the given name.
+    pub fn branch_path(&self, branch: &str) -> String {
+        branch_path(&self.table_path, branch)
+    }
+
+    /// Check if the branch with the given name exists.
+    pub async fn branch_exists(&self, branch: &str) -> crate::Result<bool> {
+        self.file_io
+            .exists(&format!("{}/", self.branch_path(branch)))
+            .await
+    }
+
+    /// List the names of all branches except the main branch, in ascending order.
+    pub async fn branches(&self) -> crate::Result<Vec<String>> {
+        let mut branches: Vec<String> = self
+            .file_io
+            .list_status(&self.branch_dir())
+            .await?
+            .into_iter()
+            .filter(|status| status.is_dir)
+            .filter_map(|status| {
+                let name = status.path.trim_end_matches('/').rsplit('/').next()?;
+                Some(name.strip_prefix(BRANCH_PREFIX)?.to_string())
+            })
+            .collect();
+        branches.sort_unstable();
+        Ok(branches)
+    }
+
+    /// Create an empty branch with the latest schema of the main branch.
+    pub async fn create_branch(&self, branch: &str) -> crate::Result<()> {
+        self.validate_branch(branch).await?;
+        let schema_manager = self.schema_manager();
+        let Some(schema) = schema_manager.latest().await? else {
+            return SchemaInvalidSnafu {
+                message: format!("Table {} has no schema", self.table_path),
+            }
+            .fail();
+        };
+        self.copy_file(
+            &schema_manager.schema_path(schema.id()),
+            &schema_manager.with_branch(branch).schema_path(schema.id()),
+        )
+        .await
+    }
+
+    /// Create a branch from the tag of the main branch, with the tag, the snapshot tagged and
+    /// the schemas up to the one of the snapshot.
+    pub async fn create_branch_from_tag(&self, branch: &str, tag_name: &str) -> crate::Result<()> {
+        self.validate_branch(branch).await?;
+        let tag_manager = TagManager::new(self.file_io.clone(), self.table_path.clone());
+        let snapshot = tag_manager.tagged_snapshot(tag_name).await?;
+        self.copy_file(
+            &tag_manager.tag_path(tag_name),
+            &tag_manager.with_branch(branch).tag_path(tag_name),
+        )
+        .await?;
+        self.copy_snapshot(branch, &snapshot).await
+    }
+
+    /// Create a branch from the snapshot of the main branch, with the snapshot and the schemas
+    /// up to the one of the snapshot.
+    pub async fn create_branch_from_snapshot(
+        &self,
+        branch: &str,
+        snapshot_id: i64,
+    ) -> crate::Result<()> {
+        self.validate_branch(branch).await?;
+        let snapshot = self.snapshot_manager().snapshot(snapshot_id).await?;
+        self.copy_snapshot(branch, &snapshot).await
+    }
+
+    /// Delete the branch with the given name, with its schemas, snapshots and tags.
+    pub async fn delete_branch(&self, branch: &str) -> crate::Result<()> {
+        if !self.branch_exists(branch).await? {
+            return BranchNotExistSnafu { branch }.fail();
+        }
+        self.file_io
+            .delete_dir(&format!("{}/", self.branch_path(branch)))
+            .await
+    }
+
+    /// Read the snapshots the branches are created from, in ascending order of id without
+    /// duplicates.
+    ///
+    /// The data files of them are still used by the branches.
+    pub async fn branch_created_snapshots(&self) -> crate::Result<Vec<Snapshot>> {
+        let mut snapshots = Vec::new();
+        for branch in self.branches().await? {
+            let snapshot_manager = self.snapshot_manager().with_branch(branch);
+            if let Some(snapshot) = snapshot_manager.earliest_snapshot().await? {
+                snapshots.push(snapshot);
+            }
+        }
+        snapshots.sort_by_key(Snapshot::id);
+        snapshots.dedup_by_key(|snapshot| snapshot.id());
+        Ok(snapshots)
+    }
+
+    async fn validate_branch(&self, branch: &str) -> crate::Result<()> {
+        let message = if branch == DEFAULT_MAIN_BRANCH {
+            format!("Branch name '{branch}' is the default branch and cannot be used")
+        } else if branch.trim().is_empty() {
+            format!("Branch name '{branch}' is blank")
+        } else if branch.chars().all(|c| c.is_ascii_digit()) {
+            format!("Branch name cannot be pure numeric string but is '{branch}'")
+        } else if self.branch_exists(branch).await? {
+            return BranchAlreadyExistSnafu { branch }.fail();
+        } else {
+            return Ok(());
+        };
+        ConfigInvalidSnafu { message }.fail()
+    }
+
+    async fn copy_snapshot(&self, branch: &str, snapshot: &Snapshot) -> crate::Result<()> {
+        let snapshot_manager = self.snapshot_manager();
+        self.copy_file(
+            &snapshot_manager.snapshot_path(snapshot.id()),
+            &snapshot_manager
+                .with_branch(branch)
+                .snapshot_path(snapshot.id()),
+        )
+        .await?;
+        let schema_manager = self.schema_manager();
+        let branch_schema_manager = schema_manager.clone().with_branch(branch);
+        for schema_id in schema_manager.list_all_ids().await? {
+            if schema_id <= snapshot.schema_id() {
+                self.copy_file(
+                    &schema_manager.schema_path(schema_id),
+                    &branch_schema_manager.schema_path(schema_id),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    async fn copy_file(&self, src: &str, dst: &str) -> crate::Result<()> {
+        let bytes = self.file_io.new_input(src)?.read().await?;
+        self.file_io.new_output(dst)?.write(bytes).await
+    }
+
+    fn snapshot_manager(&self) -> SnapshotManager {
+        SnapshotManager::new(self.file_io.clone(), self.table_path.clone())
+    }
+
+    fn schema_manager(&self) -> SchemaManager {
+        SchemaManager::new(self.file_io.clone(), self.table_path.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::catalog::Identifier;
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataField, Schema};
+    use crate::table::{FileStoreTable, Table};
+    use arrow_array::{Int32Array, RecordBatch};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    async fn write(table: &FileStoreTable, id: i32) {
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![Arc::new(Int32Array::from(vec![id]))],
+        )
+        .unwrap();
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        write.write(&batch).await.unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+    }
+
+    async fn read_ids(table: &FileStoreTable) -> Vec<i32> {
+        let mut ids = Vec::new();
+        for split in table.new_scan().plan().await.unwrap().splits() {
+            for batch in table.new_read().read(split).await.unwrap() {
+                let batch = batch.unwrap();
+                let column = batch.column(0).as_any().downcast_ref::<Int32Array>();
+                ids.extend(column.unwrap().values().iter().copied());
+            }
+        }
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_branch_manager() {
+        let location = "file:/tmp/test_branch_manager";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![DataField::new(
+                        0,
+                        "id".to_string(),
+                        "INT NOT NULL".parse().unwrap(),
+                    )])
+                    .primary_keys(vec!["id".to_string()])
+                    .options(HashMap::from([
+                        ("file.format".to_string(), "parquet".to_string()),
+                        ("bucket".to_string(), "1".to_string()),
+                    ]))
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema);
+        write(&table, 1).await;
+        table.create_tag("t1", 1).await.unwrap();
+        write(&table, 2).await;
+
+        let manager = table.branch_manager();
+        assert!(manager.branches().await.unwrap().is_empty());
+        manager.create_branch("b0").await.unwrap();
+        manager.create_branch_from_tag("b1", "t1").await.unwrap();
+        manager.create_branch_from_snapshot("b2", 2).await.unwrap();
+        for branch in ["main", " ", "123"] {
+            assert!(matches!(
+                manager.create_branch(branch).await,
+                Err(crate::Error::ConfigInvalid { .. })
+            ));
+        }
+        assert!(matches!(
+            manager.create_branch("b1").await,
+            Err(crate::Error::BranchAlreadyExist { .. })
+        ));
+        assert_eq!(manager.branches().await.unwrap(), vec!["b0", "b1", "b2"]);
+        assert_eq!(
+            manager.branch_path("b1"),
+            "file:/tmp/test_branch_manager/branch/branch-b1"
+        );
+
+        // branches read and write their own snapshots
+        let b0 = table.switch_to_branch("b0").await.unwrap();
+        assert!(read_ids(&b0).await.is_empty());
+        let b1 = table.switch_to_branch("b1").await.unwrap();
+        assert!(b1.tag_manager().tag_exists("t1").await.unwrap());
+        assert_eq!(read_ids(&b1).await, vec![1]);
+        write(&b1, 3).await;
+        assert_eq!(read_ids(&b1).await, vec![1, 3]);
+        assert_eq!(
+            b1.snapshot_manager().snapshot_ids().await.unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(read_ids(&table).await, vec![1, 2]);
+        let b2 = table.switch_to_branch("b2").await.unwrap();
+        assert_eq!(read_ids(&b2).await, vec![1, 2]);
+        let ids: Vec<i64> = manager
+            .branch_created_snapshots()
+            .await
+            .unwrap()
+            .iter()
+            .map(Snapshot::id)
+            .collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        manager.delete_branch("b0").await.unwrap();
+        assert!(matches!(
+            manager.delete_branch("b0").await,
+            Err(crate::Error::BranchNotExist { .. })
+        ));
+        assert!(matches!(
+            table.switch_to_branch("b0").await,
+            Err(crate::Error::BranchNotExist { .. })
+        ));
+        assert_eq!(manager.branches().await.unwrap(), vec!["b1", "b2"]);
+    }
+}
@@ -0,0 +1,181 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::io::FileIO;
+use crate::spec::Consumer;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+const CONSUMER_PREFIX: &str = "consumer-";
+
+/// Manager for the [`Consumer`]s of a table, providing utility methods around the `consumer`
+/// directory.
+///
+/// The snapshots from the min next snapshot of the consumers are still to be consumed, they
+/// are protected from expiration.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/consumer/ConsumerManager.java>
+#[derive(Clone, Debug)]
+pub struct ConsumerManager {
+    file_io: Arc<dyn FileIO>,
+    table_path: String,
+}
+
+impl ConsumerManager {
+    pub fn new(file_io: Arc<dyn FileIO>, table_path: impl Into<String>) -> Self {
+        let table_path = table_path.into().trim_end_matches('/').to_string();
+        Self {
+            file_io,
+            table_path,
+        }
+    }
+
+    /// Get the path of the consumer directory.
+    pub fn consumer_dir(&self) -> String {
+        format!("{}/consumer", self.table_path)
+    }
+
+    /// Get the path of the consumer file with the given id.
+    pub fn consumer_path(&self, consumer_id: &str) -> String {
+        format!("{}/{}{}", self.consumer_dir(), CONSUMER_PREFIX, consumer_id)
+    }
+
+    /// Read the consumer with the given id, returns `None` if it doesn't exist.
+    pub async fn consumer(&self, consumer_id: &str) -> crate::Result<Option<Consumer>> {
+        let path = self.consumer_path(consumer_id);
+        if !self.file_io.exists(&path).await? {
+            return Ok(None);
+        }
+        let content = self.file_io.read_file_utf8(&path).await?;
+        Consumer::from_json(&content).map(Some)
+    }
+
+    /// Write the consumer with the given id, overwriting the existing one.
+    pub async fn reset_consumer(&self, consumer_id: &str, consumer: Consumer) -> crate::Result<()> {
+        self.file_io
+            .new_output(&self.consumer_path(consumer_id))?
+            .write(Bytes::from(consumer.to_json()?))
+            .await
+    }
+
+    /// Delete the consumer with the given id.
+    pub async fn delete_consumer(&self, consumer_id: &str) -> crate::Result<()> {
+        self.file_io
+            .delete_file(&self.consumer_path(consumer_id))
+            .await
+    }
+
+    /// Read all the consumers by their ids.
+    pub async fn consumers(&self) -> crate::Result<BTreeMap<String, Consumer>> {
+        let mut consumers = BTreeMap::new();
+        for consumer_id in self.consumer_ids(None).await? {
+            if let Some(consumer) = self.consumer(&consumer_id).await? {
+                consumers.insert(consumer_id, consumer);
+            }
+        }
+        Ok(consumers)
+    }
+
+    /// Get the min next snapshot of all the consumers, returns `None` if there is no consumer.
+    pub async fn min_next_snapshot(&self) -> crate::Result<Option<i64>> {
+        Ok(self
+            .consumers()
+            .await?
+            .values()
+            .map(Consumer::next_snapshot)
+            .min())
+    }
+
+    /// Delete the consumers not updated since the given time.
+    pub async fn expire(&self, expire_time: DateTime<Utc>) -> crate::Result<()> {
+        for consumer_id in self.consumer_ids(Some(expire_time)).await? {
+            self.delete_consumer(&consumer_id).await?;
+        }
+        Ok(())
+    }
+
+    /// List the ids of the consumers, only the ones last modified before the given time if any.
+    async fn consumer_ids(
+        &self,
+        modified_before: Option<DateTime<Utc>>,
+    ) -> crate::Result<Vec<String>> {
+        Ok(self
+            .file_io
+            .list_status(&self.consumer_dir())
+            .await?
+            .into_iter()
+            .filter(|status| !status.is_dir)
+            .filter(|status| match (modified_before, status.last_modified) {
+                (Some(time), Some(last_modified)) => last_modified < time,
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .filter_map(|status| {
+                let name = status.path.rsplit('/').next()?;
+                Some(name.strip_prefix(CONSUMER_PREFIX)?.to_string())
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+
+    #[tokio::test]
+    async fn test_consumer_manager() {
+        let table_path = "file:/tmp/test_consumer_manager";
+        let file_io = FileIOBuilder::from_url(table_path)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{table_path}/")).await;
+        let manager = ConsumerManager::new(file_io, table_path);
+        assert_eq!(manager.consumer("a").await.unwrap(), None);
+        assert_eq!(manager.min_next_snapshot().await.unwrap(), None);
+
+        manager.reset_consumer("a", Consumer::new(5)).await.unwrap();
+        manager.reset_consumer("b", Consumer::new(3)).await.unwrap();
+        manager.reset_consumer("a", Consumer::new(7)).await.unwrap();
+        assert_eq!(manager.consumer("a").await.unwrap(), Some(Consumer::new(7)));
+        assert_eq!(manager.min_next_snapshot().await.unwrap(), Some(3));
+        assert_eq!(
+            manager
+                .consumers()
+                .await
+                .unwrap()
+                .into_keys()
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        // consumers modified after the expire time are kept
+        manager
+            .expire(Utc::now() - chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        assert_eq!(manager.consumers().await.unwrap().len(), 2);
+        manager
+            .expire(Utc::now() + chrono::Duration::hours(1))
+            .await
+            .unwrap();
+        assert!(manager.consumers().await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,46 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+mod snapshot_manager;
+pub use snapshot_manager::*;
+
+mod schema_manager;
+pub use schema_manager::*;
+
+mod path_factory;
+pub use path_factory::*;
+
+mod hyper_log_log;
+pub(crate) use hyper_log_log::*;
+
+mod murmur_hash;
+pub use murmur_hash::*;
+
+mod consumer_manager;
+pub use consumer_manager::*;
+
+mod tag_manager;
+pub use tag_manager::*;
+
+mod branch_manager;
+pub use branch_manager::*;
+
+mod stats_file_handler;
+pub use stats_file_handler::*;
+
+mod span;
+pub(crate) use span::*;
@@ -0,0 +1,81 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Murmur3 hashing of bytes, the same as paimon-java so that the hashes of rows are portable.
+//!
+//! Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/utils/MurmurHashUtils.java>
+
+const DEFAULT_SEED: u32 = 42;
+const C1: u32 = 0xcc9e2d51;
+const C2: u32 = 0x1b873593;
+
+/// Hash the bytes of a length multiple of 4 by words, with the default seed.
+pub fn hash_bytes_by_words(bytes: &[u8]) -> i32 {
+    hash_bytes_by_words_with_seed(bytes, DEFAULT_SEED) as i32
+}
+
+fn hash_bytes_by_words_with_seed(bytes: &[u8], seed: u32) -> u32 {
+    debug_assert_eq!(bytes.len() % 4, 0);
+    let h1 = bytes.chunks_exact(4).fold(seed, |h1, word| {
+        mix_h1(h1, mix_k1(u32::from_le_bytes(word.try_into().unwrap())))
+    });
+    fmix(h1 ^ bytes.len() as u32)
+}
+
+fn mix_k1(k1: u32) -> u32 {
+    k1.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2)
+}
+
+fn mix_h1(h1: u32, k1: u32) -> u32 {
+    (h1 ^ k1)
+        .rotate_left(13)
+        .wrapping_mul(5)
+        .wrapping_add(0xe6546b64)
+}
+
+fn fmix(mut h1: u32) -> u32 {
+    h1 ^= h1 >> 16;
+    h1 = h1.wrapping_mul(0x85ebca6b);
+    h1 ^= h1 >> 13;
+    h1 = h1.wrapping_mul(0xc2b2ae35);
+    h1 ^ (h1 >> 16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_bytes_by_words() {
+        // test vectors of murmur3 x86_32
+        assert_eq!(hash_bytes_by_words_with_seed(&[], 0), 0);
+        assert_eq!(hash_bytes_by_words_with_seed(&[0; 4], 0), 0x2362F9DE);
+        assert_eq!(hash_bytes_by_words_with_seed(&[0xff; 4], 0), 0x76293B50);
+        assert_eq!(
+            hash_bytes_by_words_with_seed(&[0x21, 0x43, 0x65, 0x87], 0),
+            0xF55B516B
+        );
+        assert_eq!(
+            hash_bytes_by_words_with_seed(&[0x21, 0x43, 0x65, 0x87], 0x5082EDEE),
+            0x2362F9DE
+        );
+        assert_eq!(
+            hash_bytes_by_words(&[0; 4]),
+            hash_bytes_by_words_with_seed(&[0; 4], 42) as i32
+        );
+    }
+}
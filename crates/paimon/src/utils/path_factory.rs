@@ -0,0 +1,332 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::spec::{BinaryRow, Datum, RowType};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+const MANIFEST_DIR: &str = "manifest";
+const INDEX_DIR: &str = "index";
+const BUCKET_PATH_PREFIX: &str = "bucket-";
+const DATA_FILE_PREFIX: &str = "data-";
+const CHANGELOG_FILE_PREFIX: &str = "changelog-";
+
+/// Factory of the paths of the files of a table, such as manifests and data files.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/utils/FileStorePathFactory.java>
+#[derive(Debug, Clone)]
+pub struct FileStorePathFactory {
+    root: String,
+    partition_type: RowType,
+    default_partition_name: String,
+}
+
+impl FileStorePathFactory {
+    pub fn new(
+        root: impl Into<String>,
+        partition_type: RowType,
+        default_partition_name: impl Into<String>,
+    ) -> Self {
+        Self {
+            root: root.into().trim_end_matches('/').to_string(),
+            partition_type,
+            default_partition_name: default_partition_name.into(),
+        }
+    }
+
+    /// Get the path of the manifest directory.
+    pub fn manifest_dir(&self) -> String {
+        format!("{}/{MANIFEST_DIR}", self.root)
+    }
+
+    /// Get the path of the manifest file or manifest list with the given name.
+    pub fn manifest_path(&self, name: &str) -> String {
+        format!("{}/{name}", self.manifest_dir())
+    }
+
+    /// Get the path of the index directory.
+    pub fn index_dir(&self) -> String {
+        format!("{}/{INDEX_DIR}", self.root)
+    }
+
+    /// Get the path of the index file or index manifest with the given name.
+    pub fn index_path(&self, name: &str) -> String {
+        format!("{}/{name}", self.index_dir())
+    }
+
+    /// Get the relative path of the partition like `dt=2024-01-01/hr=0/`, empty if the table
+    /// is not partitioned.
+    pub fn partition_path(&self, partition: &BinaryRow) -> crate::Result<String> {
+        let mut path = String::new();
+        for (pos, field) in self.partition_type.fields().iter().enumerate() {
+            let value = partition
+                .get_datum(pos, field.data_type())?
+                .map(|datum| partition_value_string(&datum))
+                .filter(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| self.default_partition_name.clone());
+            let _ = write!(
+                path,
+                "{}={}/",
+                escape_path_name(field.name()),
+                escape_path_name(&value)
+            );
+        }
+        Ok(path)
+    }
+
+    /// Get the path of the bucket directory in the partition.
+    pub fn bucket_path(&self, partition: &BinaryRow, bucket: i32) -> crate::Result<String> {
+        Ok(format!(
+            "{}/{}{BUCKET_PATH_PREFIX}{bucket}",
+            self.root,
+            self.partition_path(partition)?
+        ))
+    }
+
+    /// Create a factory of the data files written into the bucket of the partition.
+    pub fn data_file_path_factory(
+        &self,
+        partition: &BinaryRow,
+        bucket: i32,
+        format: &str,
+    ) -> crate::Result<DataFilePathFactory> {
+        Ok(DataFilePathFactory::new(
+            self.bucket_path(partition, bucket)?,
+            format,
+        ))
+    }
+}
+
+/// Factory of the paths of the data files written into a bucket, the files are named like
+/// `data-<uuid>-<count>.<format>`, or `changelog-<uuid>-<count>.<format>` for changelog
+/// files, the count is shared by the clones of the factory.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/io/DataFilePathFactory.java>
+#[derive(Debug, Clone)]
+pub struct DataFilePathFactory {
+    bucket_path: String,
+    format: String,
+    prefix: &'static str,
+    uuid: String,
+    count: Arc<AtomicUsize>,
+}
+
+impl DataFilePathFactory {
+    pub fn new(bucket_path: impl Into<String>, format: &str) -> Self {
+        Self {
+            bucket_path: bucket_path.into(),
+            format: format.to_lowercase(),
+            prefix: DATA_FILE_PREFIX,
+            uuid: uuid::Uuid::new_v4().to_string(),
+            count: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Get the path of the bucket directory.
+    #[inline]
+    pub fn bucket_path(&self) -> &str {
+        &self.bucket_path
+    }
+
+    /// Get the format of the data files.
+    #[inline]
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    /// Get the factory of the changelog files of the bucket, sharing the count with this one.
+    pub fn changelog(&self) -> Self {
+        Self {
+            prefix: CHANGELOG_FILE_PREFIX,
+            ..self.clone()
+        }
+    }
+
+    /// Get the name of a new data file.
+    pub fn new_file_name(&self) -> String {
+        let count = self.count.fetch_add(1, Ordering::Relaxed);
+        format!("{}{}-{count}.{}", self.prefix, self.uuid, self.format)
+    }
+
+    /// Get the path of the data file with the given name.
+    pub fn to_path(&self, file_name: &str) -> String {
+        format!("{}/{file_name}", self.bucket_path)
+    }
+}
+
+/// Convert the partition value to string like the `toString` of the internal objects of
+/// paimon-java, which is the legacy partition name it defaults to.
+pub(crate) fn partition_value_string(datum: &Datum) -> String {
+    match datum {
+        Datum::Float(v) => format!("{v:?}"),
+        Datum::Double(v) => format!("{v:?}"),
+        Datum::Date(v) | Datum::Time(v) => v.to_string(),
+        Datum::Timestamp { millis, nanos } | Datum::LocalZonedTimestamp { millis, nanos } => {
+            match chrono::DateTime::from_timestamp_millis(*millis) {
+                Some(ts) => {
+                    let ts = (ts + chrono::Duration::nanoseconds(*nanos as i64)).naive_utc();
+                    local_date_time_string(&ts)
+                }
+                None => millis.to_string(),
+            }
+        }
+        _ => datum.to_string(),
+    }
+}
+
+/// Format like `java.time.LocalDateTime#toString`, which omits zero seconds and keeps the
+/// fraction in groups of 3 digits.
+fn local_date_time_string(ts: &chrono::NaiveDateTime) -> String {
+    use chrono::Timelike;
+
+    let mut s = ts.format("%Y-%m-%dT%H:%M").to_string();
+    let nanos = ts.nanosecond();
+    if ts.second() > 0 || nanos > 0 {
+        let _ = write!(s, ":{:02}", ts.second());
+        if nanos > 0 {
+            let mut fraction = format!("{nanos:09}");
+            while fraction.ends_with("000") {
+                fraction.truncate(fraction.len() - 3);
+            }
+            let _ = write!(s, ".{fraction}");
+        }
+    }
+    s
+}
+
+/// Escape the characters which are not allowed in path names with `%XX`.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-common/src/main/java/org/apache/paimon/utils/PartitionPathUtils.java>
+fn escape_path_name(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for c in name.chars() {
+        let need_escape = matches!(
+            c,
+            '\u{01}'
+                ..='\u{1F}'
+                    | '"'
+                    | '#'
+                    | '%'
+                    | '\''
+                    | '*'
+                    | '/'
+                    | ':'
+                    | '='
+                    | '?'
+                    | '\\'
+                    | '\u{7F}'
+                    | '{'
+                    | '['
+                    | ']'
+                    | '^'
+        );
+        if need_escape {
+            let _ = write!(escaped, "%{:02X}", c as u32);
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/// Unescape the `%XX` escaped characters of the path name, the reverse of escaping.
+pub(crate) fn unescape_path_name(name: &str) -> String {
+    let mut unescaped = String::with_capacity(name.len());
+    let mut chars = name.char_indices();
+    while let Some((i, c)) = chars.next() {
+        let code = (c == '%')
+            .then(|| name.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match code {
+            Some(code) => {
+                unescaped.push(code as char);
+                chars.nth(1);
+            }
+            None => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spec::{BinaryRowWriter, DataField, DataType, DateType, IntType, VarCharType};
+
+    #[test]
+    fn test_paths() {
+        let partition_type = RowType::new(vec![
+            DataField::new(0, "dt".to_string(), DataType::Date(DateType::new())),
+            DataField::new(
+                1,
+                "name".to_string(),
+                DataType::VarChar(VarCharType::default()),
+            ),
+            DataField::new(2, "hr".to_string(), DataType::Int(IntType::new())),
+        ]);
+        let factory =
+            FileStorePathFactory::new("file:/tmp/t/", partition_type, "__DEFAULT_PARTITION__");
+        assert_eq!(factory.manifest_path("m-0"), "file:/tmp/t/manifest/m-0");
+        assert_eq!(factory.index_path("i-0"), "file:/tmp/t/index/i-0");
+
+        let mut writer = BinaryRowWriter::new(3);
+        writer.write_int(0, 19723);
+        writer.write_string(1, "a/b");
+        writer.set_null_at(2);
+        assert_eq!(
+            factory.bucket_path(&writer.build(), 1).unwrap(),
+            "file:/tmp/t/dt=19723/name=a%2Fb/hr=__DEFAULT_PARTITION__/bucket-1"
+        );
+
+        let factory = FileStorePathFactory::new("file:/tmp/t", RowType::new(vec![]), "default");
+        assert_eq!(
+            factory.bucket_path(&BinaryRow::new(0), 0).unwrap(),
+            "file:/tmp/t/bucket-0"
+        );
+
+        let data_file_path_factory = factory
+            .data_file_path_factory(&BinaryRow::new(0), 0, "Parquet")
+            .unwrap();
+        let first = data_file_path_factory.new_file_name();
+        let second = data_file_path_factory.clone().new_file_name();
+        assert!(first.starts_with("data-") && first.ends_with("-0.parquet"));
+        assert_eq!(second, first.replace("-0.parquet", "-1.parquet"));
+        assert_eq!(
+            data_file_path_factory.changelog().new_file_name(),
+            first
+                .replace("data-", "changelog-")
+                .replace("-0.parquet", "-2.parquet")
+        );
+        assert_eq!(
+            data_file_path_factory.to_path(&first),
+            format!("file:/tmp/t/bucket-0/{first}")
+        );
+        assert_eq!(unescape_path_name(&escape_path_name("a=b/c%")), "a=b/c%");
+    }
+
+    #[test]
+    fn test_partition_value_string() {
+        let ts = |millis, nanos| partition_value_string(&Datum::Timestamp { millis, nanos });
+        assert_eq!(ts(1704067200000, 0), "2024-01-01T00:00");
+        assert_eq!(ts(1704067201000, 0), "2024-01-01T00:00:01");
+        assert_eq!(ts(1704067200001, 0), "2024-01-01T00:00:00.001");
+        assert_eq!(ts(1704067200000, 1000), "2024-01-01T00:00:00.000001");
+        assert_eq!(partition_value_string(&Datum::Double(1.0)), "1.0");
+    }
+}
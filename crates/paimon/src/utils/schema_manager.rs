@@ -0,0 +1,526 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::{ColumnAlreadyExistSnafu, ColumnNotExistSnafu, SchemaInvalidSnafu};
+use crate::io::FileIO;
+use crate::spec::{
+    validate_table_schema, ColumnMove, ColumnMoveType, DataField, Schema, SchemaChange, TableSchema,
+};
+use crate::utils::{branch_path, DEFAULT_MAIN_BRANCH};
+use bytes::Bytes;
+use std::sync::Arc;
+
+const SCHEMA_PREFIX: &str = "schema-";
+
+/// Manager for [`TableSchema`]s of a table, providing utility methods around the `schema` directory.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/schema/SchemaManager.java>
+#[derive(Clone, Debug)]
+pub struct SchemaManager {
+    file_io: Arc<dyn FileIO>,
+    table_path: String,
+    branch: String,
+}
+
+impl SchemaManager {
+    pub fn new(file_io: Arc<dyn FileIO>, table_path: impl Into<String>) -> Self {
+        let table_path = table_path.into().trim_end_matches('/').to_string();
+        Self {
+            file_io,
+            table_path,
+            branch: DEFAULT_MAIN_BRANCH.to_string(),
+        }
+    }
+
+    /// Manage the schemas of the given branch instead of the main branch.
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = branch.into();
+        self
+    }
+
+    /// Get the path of the schema directory.
+    pub fn schema_dir(&self) -> String {
+        format!("{}/schema", branch_path(&self.table_path, &self.branch))
+    }
+
+    /// Get the path of the schema file with the given id.
+    pub fn schema_path(&self, schema_id: i64) -> String {
+        format!("{}/{}{}", self.schema_dir(), SCHEMA_PREFIX, schema_id)
+    }
+
+    /// Read the schema with the given id.
+    pub async fn schema(&self, schema_id: i64) -> crate::Result<TableSchema> {
+        let content = self
+            .file_io
+            .read_file_utf8(&self.schema_path(schema_id))
+            .await?;
+        TableSchema::from_json(&content)
+    }
+
+    /// Get the latest schema, returns `None` if the table has no schema.
+    pub async fn latest(&self) -> crate::Result<Option<TableSchema>> {
+        match self.list_all_ids().await?.last() {
+            Some(id) => Ok(Some(self.schema(*id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// List the ids of all schemas in ascending order.
+    pub async fn list_all_ids(&self) -> crate::Result<Vec<i64>> {
+        let mut ids: Vec<i64> = self
+            .file_io
+            .list_status(&self.schema_dir())
+            .await?
+            .into_iter()
+            .filter(|status| !status.is_dir)
+            .filter_map(|status| {
+                let name = status.path.rsplit('/').next()?;
+                name.strip_prefix(SCHEMA_PREFIX)?.parse::<i64>().ok()
+            })
+            .collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// Read all schemas in ascending order of id.
+    pub async fn list_all(&self) -> crate::Result<Vec<TableSchema>> {
+        let mut schemas = Vec::new();
+        for id in self.list_all_ids().await? {
+            schemas.push(self.schema(id).await?);
+        }
+        Ok(schemas)
+    }
+
+    /// Create the first schema of the table, fails if the table already has a schema.
+    pub async fn create_table(&self, schema: &Schema) -> crate::Result<TableSchema> {
+        loop {
+            if let Some(latest) = self.latest().await? {
+                return SchemaInvalidSnafu {
+                    message: format!(
+                        "Schema in filesystem exists, please use updating, latest schema is: {}",
+                        latest.id()
+                    ),
+                }
+                .fail();
+            }
+            let table_schema = schema.to_table_schema(0)?;
+            validate(&table_schema)?;
+            if self.commit(&table_schema).await? {
+                return Ok(table_schema);
+            }
+        }
+    }
+
+    /// Apply the changes on the latest schema and commit it as a new schema, retrying on conflicts.
+    pub async fn commit_changes(&self, changes: &[SchemaChange]) -> crate::Result<TableSchema> {
+        loop {
+            let Some(latest) = self.latest().await? else {
+                return SchemaInvalidSnafu {
+                    message: format!("Table {} has no schema", self.table_path),
+                }
+                .fail();
+            };
+            let new_schema = apply_changes(&latest, changes)?;
+            validate(&new_schema)?;
+            if self.commit(&new_schema).await? {
+                return Ok(new_schema);
+            }
+        }
+    }
+
+    /// Write the schema file, returns `false` if the schema id is already taken.
+    async fn commit(&self, schema: &TableSchema) -> crate::Result<bool> {
+        self.file_io
+            .try_to_write_atomic(
+                &self.schema_path(schema.id()),
+                Bytes::from(schema.to_json()?),
+            )
+            .await
+    }
+}
+
+/// Validate the schema before committing it, warning about the unknown options.
+fn validate(schema: &TableSchema) -> crate::Result<()> {
+    let report = validate_table_schema(schema)?;
+    #[cfg(feature = "tracing")]
+    if !report.unknown_keys.is_empty() {
+        tracing::warn!(unknown_keys = ?report.unknown_keys, "Table options have unknown keys");
+    }
+    #[cfg(not(feature = "tracing"))]
+    let _ = report;
+    Ok(())
+}
+
+/// Apply schema changes on the old schema, producing the schema with the next id.
+///
+/// Only top-level columns can be updated for now.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/schema/SchemaManager.java>
+fn apply_changes(old: &TableSchema, changes: &[SchemaChange]) -> crate::Result<TableSchema> {
+    let mut options = old.options().clone();
+    let mut comment = old.comment().map(|c| c.to_string());
+    let mut fields = old.fields().to_vec();
+    let mut highest_field_id = old.highest_field_id();
+
+    for change in changes {
+        match change {
+            SchemaChange::SetOption { key, value } => {
+                options.insert(key.clone(), value.clone());
+            }
+            SchemaChange::RemoveOption { key } => {
+                options.remove(key);
+            }
+            SchemaChange::UpdateComment {
+                comment: new_comment,
+            } => {
+                comment = new_comment.clone();
+            }
+            SchemaChange::AddColumn {
+                field_name,
+                data_type,
+                description,
+                column_move,
+            } => {
+                if fields.iter().any(|f| f.name() == field_name) {
+                    return ColumnAlreadyExistSnafu {
+                        column: field_name.clone(),
+                    }
+                    .fail();
+                }
+                if !data_type.is_nullable() {
+                    return SchemaInvalidSnafu {
+                        message: format!("Column {field_name} cannot specify NOT NULL"),
+                    }
+                    .fail();
+                }
+                highest_field_id += 1;
+                fields.push(
+                    DataField::new(highest_field_id, field_name.clone(), data_type.clone())
+                        .with_description(description.clone()),
+                );
+                if let Some(column_move) = column_move {
+                    apply_move(&mut fields, column_move)?;
+                }
+            }
+            SchemaChange::RenameColumn {
+                field_name,
+                new_name,
+            } => {
+                assert_not_updating_keys(old, field_name, "rename")?;
+                if fields.iter().any(|f| f.name() == new_name) {
+                    return ColumnAlreadyExistSnafu {
+                        column: new_name.clone(),
+                    }
+                    .fail();
+                }
+                let index = field_index(&fields, field_name)?;
+                fields[index] = fields[index].clone().with_name(new_name.clone());
+            }
+            SchemaChange::DropColumn { field_name } => {
+                assert_not_updating_keys(old, field_name, "drop")?;
+                let index = field_index(&fields, field_name)?;
+                fields.remove(index);
+                if fields.is_empty() {
+                    return SchemaInvalidSnafu {
+                        message: "Cannot drop all fields in table",
+                    }
+                    .fail();
+                }
+            }
+            SchemaChange::UpdateColumnType {
+                field_name,
+                data_type,
+            } => {
+                if old.partition_keys().contains(field_name) {
+                    return SchemaInvalidSnafu {
+                        message: format!("Cannot update partition column: [{field_name}]"),
+                    }
+                    .fail();
+                }
+                let index = field_index(&fields, field_name)?;
+                let old_type = fields[index].data_type();
+                // the nullability is kept, it can only be changed by `UpdateColumnNullability`
+                let typ = data_type.copy_with_nullable(old_type.is_nullable());
+                if !old_type.supports_implicit_cast(&typ) {
+                    return SchemaInvalidSnafu {
+                        message: format!(
+                            "Column type {old_type}[{field_name}] cannot be converted to {data_type} without losing information"
+                        ),
+                    }
+                    .fail();
+                }
+                fields[index] = fields[index].clone().with_data_type(typ);
+            }
+            SchemaChange::UpdateColumnPosition { column_move } => {
+                apply_move(&mut fields, column_move)?;
+            }
+            SchemaChange::UpdateColumnNullability {
+                field_name,
+                nullable,
+            } => {
+                let field_name = top_level_name(field_name)?;
+                if *nullable && old.primary_keys().iter().any(|k| k == field_name) {
+                    return SchemaInvalidSnafu {
+                        message: format!(
+                            "Cannot change nullability of primary key column: [{field_name}]"
+                        ),
+                    }
+                    .fail();
+                }
+                let index = field_index(&fields, field_name)?;
+                let old_type = fields[index].data_type();
+                let typ = old_type.copy_with_nullable(*nullable);
+                // the existing rows may be null
+                if !old_type.supports_implicit_cast(&typ) {
+                    return SchemaInvalidSnafu {
+                        message: format!(
+                            "Cannot update column type from {old_type} to {typ}: [{field_name}]"
+                        ),
+                    }
+                    .fail();
+                }
+                fields[index] = fields[index].clone().with_data_type(typ);
+            }
+            SchemaChange::UpdateColumnComment {
+                field_names,
+                new_description,
+            } => {
+                let index = field_index(&fields, top_level_name(field_names)?)?;
+                fields[index] = fields[index]
+                    .clone()
+                    .with_description(Some(new_description.clone()));
+            }
+        }
+    }
+
+    Ok(TableSchema::builder()
+        .id(old.id() + 1)
+        .fields(fields)
+        .highest_field_id(highest_field_id)
+        .partition_keys(old.partition_keys().to_vec())
+        .primary_keys(old.primary_keys().to_vec())
+        .options(options)
+        .comment(comment)
+        .build())
+}
+
+fn field_index(fields: &[DataField], name: &str) -> crate::Result<usize> {
+    fields.iter().position(|f| f.name() == name).ok_or_else(|| {
+        ColumnNotExistSnafu {
+            column: name.to_string(),
+        }
+        .build()
+    })
+}
+
+fn top_level_name(field_names: &[String]) -> crate::Result<&str> {
+    match field_names {
+        [name] => Ok(name),
+        _ => SchemaInvalidSnafu {
+            message: format!("Updating nested column {field_names:?} is not supported"),
+        }
+        .fail(),
+    }
+}
+
+fn assert_not_updating_keys(
+    schema: &TableSchema,
+    field_name: &str,
+    operation: &str,
+) -> crate::Result<()> {
+    let is_key = schema.partition_keys().iter().any(|k| k == field_name)
+        || schema.primary_keys().iter().any(|k| k == field_name);
+    if is_key {
+        return SchemaInvalidSnafu {
+            message: format!("Cannot {operation} partition or primary key column: [{field_name}]"),
+        }
+        .fail();
+    }
+    Ok(())
+}
+
+fn apply_move(fields: &mut Vec<DataField>, column_move: &ColumnMove) -> crate::Result<()> {
+    let index = field_index(fields, column_move.field_name())?;
+    let field = fields.remove(index);
+    let target = match column_move.move_type() {
+        ColumnMoveType::FIRST => 0,
+        ColumnMoveType::AFTER => {
+            let referenced = column_move.referenced_field_name().unwrap_or_default();
+            field_index(fields, referenced)? + 1
+        }
+    };
+    fields.insert(target, field);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataType, IntType, VarCharType};
+
+    fn test_schema(id: i64) -> TableSchema {
+        TableSchema::builder()
+            .id(id)
+            .fields(vec![DataField::new(
+                0,
+                "f0".to_string(),
+                DataType::Int(IntType::new()),
+            )])
+            .highest_field_id(0)
+            .time_millis(id * 1000)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_read_schemas() {
+        let table_path = "file:/tmp/test_schema_manager_read";
+        let file_io = FileIOBuilder::from_url(table_path)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{table_path}/")).await;
+        let manager = SchemaManager::new(file_io.clone(), table_path);
+        assert_eq!(manager.latest().await.unwrap(), None);
+
+        for id in [0, 2, 1] {
+            file_io
+                .new_output(&manager.schema_path(id))
+                .unwrap()
+                .write(Bytes::from(test_schema(id).to_json().unwrap()))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(manager.list_all_ids().await.unwrap(), vec![0, 1, 2]);
+        assert_eq!(manager.schema(1).await.unwrap(), test_schema(1));
+        assert_eq!(manager.latest().await.unwrap(), Some(test_schema(2)));
+        assert_eq!(manager.list_all().await.unwrap().len(), 3);
+        assert!(manager.schema(3).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_table_and_commit_changes() {
+        let table_path = "file:/tmp/test_schema_manager_commit_changes";
+        let file_io = FileIOBuilder::from_url(table_path)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{table_path}/")).await;
+        let manager = SchemaManager::new(file_io.clone(), table_path);
+
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "id".to_string(), DataType::Int(IntType::new())),
+                DataField::new(
+                    1,
+                    "name".to_string(),
+                    DataType::VarChar(VarCharType::new(10).unwrap()),
+                ),
+            ])
+            .primary_keys(vec!["id".to_string()])
+            .build();
+        let created = manager.create_table(&schema).await.unwrap();
+        assert_eq!(created.id(), 0);
+        assert_eq!(created.highest_field_id(), 1);
+        assert!(!created.fields()[0].data_type().is_nullable());
+        assert!(manager.create_table(&schema).await.is_err());
+
+        let changed = manager
+            .commit_changes(&[
+                SchemaChange::set_option("bucket".to_string(), "4".to_string()),
+                SchemaChange::add_column("age".to_string(), DataType::Int(IntType::new())),
+                SchemaChange::update_column_position(ColumnMove::move_first("age".to_string())),
+                SchemaChange::rename_column("name".to_string(), "user_name".to_string()),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(changed.id(), 1);
+        assert_eq!(changed.highest_field_id(), 2);
+        assert_eq!(changed.field_names(), vec!["age", "id", "user_name"]);
+        assert_eq!(changed.options().get("bucket"), Some(&"4".to_string()));
+        assert_eq!(manager.latest().await.unwrap(), Some(changed));
+
+        let result = manager
+            .commit_changes(&[SchemaChange::drop_column("id".to_string())])
+            .await;
+        assert!(matches!(result, Err(crate::Error::SchemaInvalid { .. })));
+        let result = manager
+            .commit_changes(&[SchemaChange::drop_column("unknown".to_string())])
+            .await;
+        assert!(matches!(result, Err(crate::Error::ColumnNotExist { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_commit_column_updates() {
+        let table_path = "file:/tmp/test_schema_manager_column_updates";
+        let file_io = FileIOBuilder::from_url(table_path)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{table_path}/")).await;
+        let manager = SchemaManager::new(file_io.clone(), table_path);
+        let schema = Schema::builder()
+            .fields(vec![
+                DataField::new(0, "dt".to_string(), "STRING".parse().unwrap()),
+                DataField::new(1, "id".to_string(), "INT".parse().unwrap()),
+                DataField::new(2, "v".to_string(), "INT".parse().unwrap()),
+            ])
+            .partition_keys(vec!["dt".to_string()])
+            .primary_keys(vec!["dt".to_string(), "id".to_string()])
+            .build();
+        manager.create_table(&schema).await.unwrap();
+
+        for change in [
+            SchemaChange::drop_column("dt".to_string()),
+            SchemaChange::rename_column("id".to_string(), "key".to_string()),
+            SchemaChange::update_column_type("dt".to_string(), "INT".parse().unwrap()),
+            SchemaChange::update_column_nullability("id".to_string(), true),
+            SchemaChange::update_column_type("v".to_string(), "MAP<INT, INT>".parse().unwrap()),
+            SchemaChange::update_column_type("v".to_string(), "SMALLINT".parse().unwrap()),
+            SchemaChange::update_column_type("v".to_string(), "STRING".parse().unwrap()),
+            SchemaChange::update_column_nullability("v".to_string(), false),
+            SchemaChange::add_column("w".to_string(), "INT NOT NULL".parse().unwrap()),
+        ] {
+            let result = manager.commit_changes(&[change]).await;
+            assert!(matches!(result, Err(crate::Error::SchemaInvalid { .. })));
+        }
+        // no schema file is written by the failed changes
+        assert_eq!(manager.list_all_ids().await.unwrap(), vec![0]);
+
+        let changed = manager
+            .commit_changes(&[
+                SchemaChange::update_column_type(
+                    "v".to_string(),
+                    "BIGINT NOT NULL".parse().unwrap(),
+                ),
+                SchemaChange::update_column_nullability("id".to_string(), false),
+                SchemaChange::update_column_comment("v".to_string(), "value".to_string()),
+                SchemaChange::add_column("w".to_string(), "STRING".parse().unwrap()),
+                SchemaChange::drop_column("w".to_string()),
+            ])
+            .await
+            .unwrap();
+        let v = &changed.fields()[2];
+        assert_eq!(v.data_type().to_string(), "BIGINT");
+        assert_eq!(v.description(), Some("value"));
+        assert_eq!(changed.field_names(), vec!["dt", "id", "v"]);
+        // the dropped field id is not reused
+        assert_eq!(changed.highest_field_id(), 3);
+        assert_eq!(manager.list_all_ids().await.unwrap(), vec![0, 1]);
+    }
+}
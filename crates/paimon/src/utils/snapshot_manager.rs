@@ -0,0 +1,324 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::io::FileIO;
+use crate::spec::Snapshot;
+use crate::utils::{branch_path, DEFAULT_MAIN_BRANCH};
+use bytes::Bytes;
+use std::sync::Arc;
+
+const SNAPSHOT_PREFIX: &str = "snapshot-";
+const EARLIEST: &str = "EARLIEST";
+const LATEST: &str = "LATEST";
+
+/// Manager for [`Snapshot`]s of a table, providing utility methods around the `snapshot` directory.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/utils/SnapshotManager.java>
+#[derive(Clone, Debug)]
+pub struct SnapshotManager {
+    file_io: Arc<dyn FileIO>,
+    table_path: String,
+    branch: String,
+}
+
+impl SnapshotManager {
+    pub fn new(file_io: Arc<dyn FileIO>, table_path: impl Into<String>) -> Self {
+        let table_path = table_path.into().trim_end_matches('/').to_string();
+        Self {
+            file_io,
+            table_path,
+            branch: DEFAULT_MAIN_BRANCH.to_string(),
+        }
+    }
+
+    /// Manage the snapshots of the given branch instead of the main branch.
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = branch.into();
+        self
+    }
+
+    /// Get the path of the table.
+    pub fn table_path(&self) -> &str {
+        &self.table_path
+    }
+
+    /// Get the path of the snapshot directory.
+    pub fn snapshot_dir(&self) -> String {
+        format!("{}/snapshot", branch_path(&self.table_path, &self.branch))
+    }
+
+    /// Get the path of the snapshot file with the given id.
+    pub fn snapshot_path(&self, snapshot_id: i64) -> String {
+        format!("{}/{}{}", self.snapshot_dir(), SNAPSHOT_PREFIX, snapshot_id)
+    }
+
+    /// Read the snapshot with the given id.
+    pub async fn snapshot(&self, snapshot_id: i64) -> crate::Result<Snapshot> {
+        let content = self
+            .file_io
+            .read_file_utf8(&self.snapshot_path(snapshot_id))
+            .await?;
+        Snapshot::from_json(&content)
+    }
+
+    /// Check if the snapshot with the given id exists.
+    pub async fn snapshot_exists(&self, snapshot_id: i64) -> crate::Result<bool> {
+        self.file_io.exists(&self.snapshot_path(snapshot_id)).await
+    }
+
+    /// Get the latest snapshot, returns `None` if the table has no snapshot.
+    pub async fn latest_snapshot(&self) -> crate::Result<Option<Snapshot>> {
+        match self.latest_snapshot_id().await? {
+            Some(id) => Ok(Some(self.snapshot(id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the earliest snapshot, returns `None` if the table has no snapshot.
+    pub async fn earliest_snapshot(&self) -> crate::Result<Option<Snapshot>> {
+        match self.earliest_snapshot_id().await? {
+            Some(id) => Ok(Some(self.snapshot(id).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the id of the latest snapshot.
+    ///
+    /// The `LATEST` hint is trusted only if the snapshot next to it doesn't exist,
+    /// otherwise the snapshot directory will be listed.
+    pub async fn latest_snapshot_id(&self) -> crate::Result<Option<i64>> {
+        if let Some(id) = self.read_hint(LATEST).await? {
+            if id > 0 && !self.snapshot_exists(id + 1).await? {
+                return Ok(Some(id));
+            }
+        }
+        Ok(self.snapshot_ids().await?.into_iter().max())
+    }
+
+    /// Get the id of the earliest snapshot.
+    ///
+    /// The `EARLIEST` hint is trusted only if the snapshot it points to exists and
+    /// the one before it doesn't, otherwise the snapshot directory will be listed.
+    pub async fn earliest_snapshot_id(&self) -> crate::Result<Option<i64>> {
+        if let Some(id) = self.read_hint(EARLIEST).await? {
+            if self.snapshot_exists(id).await? && !self.snapshot_exists(id - 1).await? {
+                return Ok(Some(id));
+            }
+        }
+        Ok(self.snapshot_ids().await?.into_iter().min())
+    }
+
+    /// List the ids of all snapshots in ascending order.
+    pub async fn snapshot_ids(&self) -> crate::Result<Vec<i64>> {
+        let mut ids: Vec<i64> = self
+            .file_io
+            .list_status(&self.snapshot_dir())
+            .await?
+            .into_iter()
+            .filter(|status| !status.is_dir)
+            .filter_map(|status| {
+                let name = status.path.rsplit('/').next()?;
+                name.strip_prefix(SNAPSHOT_PREFIX)?.parse::<i64>().ok()
+            })
+            .collect();
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    /// Read all snapshots in ascending order of id.
+    pub async fn snapshots(&self) -> crate::Result<Vec<Snapshot>> {
+        let mut snapshots = Vec::new();
+        for id in self.snapshot_ids().await? {
+            snapshots.push(self.snapshot(id).await?);
+        }
+        Ok(snapshots)
+    }
+
+    /// Read all snapshots committed within `[start_millis, end_millis)`, in ascending order of id.
+    pub async fn snapshots_in_time_range(
+        &self,
+        start_millis: u64,
+        end_millis: u64,
+    ) -> crate::Result<Vec<Snapshot>> {
+        Ok(self
+            .snapshots()
+            .await?
+            .into_iter()
+            .filter(|s| s.time_millis() >= start_millis && s.time_millis() < end_millis)
+            .collect())
+    }
+
+    /// Get the latest snapshot committed at or before the given time in milliseconds, by binary
+    /// searching the snapshots from the earliest to the latest, returns `None` if there is no
+    /// such snapshot.
+    pub async fn earlier_or_equal_time_millis(
+        &self,
+        timestamp_millis: u64,
+    ) -> crate::Result<Option<Snapshot>> {
+        let (Some(mut earliest), Some(mut latest)) = (
+            self.earliest_snapshot_id().await?,
+            self.latest_snapshot_id().await?,
+        ) else {
+            return Ok(None);
+        };
+        let mut found = None;
+        while earliest <= latest {
+            let mid = earliest + (latest - earliest) / 2;
+            let snapshot = self.snapshot(mid).await?;
+            match snapshot.time_millis().cmp(&timestamp_millis) {
+                std::cmp::Ordering::Greater => latest = mid - 1,
+                std::cmp::Ordering::Less => {
+                    earliest = mid + 1;
+                    found = Some(snapshot);
+                }
+                std::cmp::Ordering::Equal => return Ok(Some(snapshot)),
+            }
+        }
+        Ok(found)
+    }
+
+    /// Update the `LATEST` hint to the given snapshot id.
+    pub async fn commit_latest_hint(&self, snapshot_id: i64) -> crate::Result<()> {
+        self.commit_hint(LATEST, snapshot_id).await
+    }
+
+    /// Update the `EARLIEST` hint to the given snapshot id.
+    pub async fn commit_earliest_hint(&self, snapshot_id: i64) -> crate::Result<()> {
+        self.commit_hint(EARLIEST, snapshot_id).await
+    }
+
+    async fn commit_hint(&self, hint: &str, snapshot_id: i64) -> crate::Result<()> {
+        let path = format!("{}/{}", self.snapshot_dir(), hint);
+        self.file_io
+            .new_output(&path)?
+            .write(Bytes::from(snapshot_id.to_string()))
+            .await
+    }
+
+    async fn read_hint(&self, hint: &str) -> crate::Result<Option<i64>> {
+        let path = format!("{}/{}", self.snapshot_dir(), hint);
+        if !self.file_io.exists(&path).await? {
+            return Ok(None);
+        }
+        let content = self.file_io.read_file_utf8(&path).await?;
+        Ok(content.trim().parse::<i64>().ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::CommitKind;
+
+    fn test_snapshot(id: i64, time_millis: u64) -> Snapshot {
+        Snapshot::builder()
+            .version(Snapshot::CURRENT_VERSION)
+            .id(id)
+            .schema_id(0)
+            .base_manifest_list(format!("manifest-list-base-{id}"))
+            .delta_manifest_list(format!("manifest-list-delta-{id}"))
+            .commit_user("test".to_string())
+            .commit_identifier(id)
+            .commit_kind(CommitKind::APPEND)
+            .time_millis(time_millis)
+            .build()
+    }
+
+    async fn setup(table_path: &str, ids: &[i64]) -> SnapshotManager {
+        let file_io = FileIOBuilder::from_url(table_path)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{table_path}/")).await;
+        let manager = SnapshotManager::new(file_io.clone(), table_path);
+        for id in ids {
+            file_io
+                .new_output(&manager.snapshot_path(*id))
+                .unwrap()
+                .write(Bytes::from(
+                    test_snapshot(*id, *id as u64 * 1000).to_json().unwrap(),
+                ))
+                .await
+                .unwrap();
+        }
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_empty_table() {
+        let manager = setup("file:/tmp/test_snapshot_manager_empty", &[]).await;
+
+        assert!(manager.snapshot_ids().await.unwrap().is_empty());
+        assert_eq!(manager.latest_snapshot_id().await.unwrap(), None);
+        assert_eq!(manager.earliest_snapshot().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_list_and_read_snapshots() {
+        let manager = setup("file:/tmp/test_snapshot_manager_list", &[3, 1, 2]).await;
+
+        assert_eq!(manager.snapshot_ids().await.unwrap(), vec![1, 2, 3]);
+        assert_eq!(manager.snapshot(2).await.unwrap(), test_snapshot(2, 2000));
+        assert_eq!(manager.latest_snapshot_id().await.unwrap(), Some(3));
+        assert_eq!(manager.earliest_snapshot_id().await.unwrap(), Some(1));
+        assert!(manager.snapshot(4).await.is_err());
+
+        let ids: Vec<_> = manager
+            .snapshots_in_time_range(2000, 3000)
+            .await
+            .unwrap()
+            .iter()
+            .map(|s| s.id())
+            .collect();
+        assert_eq!(ids, vec![2]);
+    }
+
+    #[tokio::test]
+    async fn test_earlier_or_equal_time_millis() {
+        let manager = setup("file:/tmp/test_snapshot_manager_time", &[2, 3, 4, 5]).await;
+        let id = |millis| {
+            let manager = manager.clone();
+            async move {
+                let snapshot = manager.earlier_or_equal_time_millis(millis).await.unwrap();
+                snapshot.map(|s| s.id())
+            }
+        };
+        assert_eq!(id(1999).await, None);
+        assert_eq!(id(2000).await, Some(2));
+        assert_eq!(id(3500).await, Some(3));
+        assert_eq!(id(5000).await, Some(5));
+        assert_eq!(id(9000).await, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_resolve_hints() {
+        let manager = setup("file:/tmp/test_snapshot_manager_hints", &[2, 3, 4]).await;
+
+        manager.commit_latest_hint(4).await.unwrap();
+        manager.commit_earliest_hint(2).await.unwrap();
+        assert_eq!(manager.latest_snapshot_id().await.unwrap(), Some(4));
+        assert_eq!(manager.earliest_snapshot_id().await.unwrap(), Some(2));
+
+        // Outdated hints must not be trusted.
+        manager.commit_latest_hint(3).await.unwrap();
+        manager.commit_earliest_hint(3).await.unwrap();
+        assert_eq!(manager.latest_snapshot_id().await.unwrap(), Some(4));
+        assert_eq!(manager.earliest_snapshot_id().await.unwrap(), Some(2));
+        assert_eq!(manager.latest_snapshot().await.unwrap().unwrap().id(), 4);
+    }
+}
@@ -0,0 +1,169 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+/// Record the fields into the current span of `tracing`, the fields must be declared when the
+/// span is created. Nothing is recorded, and the values are not evaluated, without the feature
+/// `tracing`.
+macro_rules! record_span {
+    ($($field:ident = $value:expr),+ $(,)?) => {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::Span::current();
+            $(span.record(stringify!($field), $value);)+
+        }
+    };
+}
+
+pub(crate) use record_span;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use crate::catalog::Identifier;
+    use crate::format::to_arrow_schema;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{DataField, Schema};
+    use crate::table::{FileStoreTable, Table};
+    use crate::utils::SchemaManager;
+    use arrow_array::{Int32Array, RecordBatch};
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+    use tracing_core::span::Current;
+
+    type RecordedSpan = (&'static Metadata<'static>, HashMap<String, String>);
+
+    /// A subscriber collecting the fields of the spans, with the stack of the spans entered.
+    #[derive(Default, Clone)]
+    struct Recorder {
+        spans: Arc<Mutex<Vec<RecordedSpan>>>,
+        entered: Arc<Mutex<Vec<Id>>>,
+    }
+
+    struct FieldVisitor<'a>(&'a mut HashMap<String, String>);
+
+    impl Visit for FieldVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0
+                .insert(field.name().to_string(), format!("{value:?}"));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut spans = self.spans.lock().unwrap();
+            let mut fields = HashMap::new();
+            span.record(&mut FieldVisitor(&mut fields));
+            spans.push((span.metadata(), fields));
+            Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.spans.lock().unwrap();
+            let (_, fields) = &mut spans[span.into_u64() as usize - 1];
+            values.record(&mut FieldVisitor(fields));
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, span: &Id) {
+            self.entered.lock().unwrap().push(span.clone());
+        }
+
+        fn exit(&self, _: &Id) {
+            self.entered.lock().unwrap().pop();
+        }
+
+        fn current_span(&self) -> Current {
+            let spans = self.spans.lock().unwrap();
+            match self.entered.lock().unwrap().last() {
+                Some(id) => Current::new(id.clone(), spans[id.into_u64() as usize - 1].0),
+                None => Current::none(),
+            }
+        }
+    }
+
+    impl Recorder {
+        fn fields(&self, name: &str) -> HashMap<String, String> {
+            let spans = self.spans.lock().unwrap();
+            let (_, fields) = spans.iter().find(|(m, _)| m.name() == name).unwrap();
+            fields.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tracing_spans() {
+        let recorder = Recorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let location = "file:/tmp/test_tracing_spans";
+        let file_io = FileIOBuilder::from_url(location).unwrap().build().unwrap();
+        let _ = file_io.delete_dir(&format!("{location}/")).await;
+        let schema = SchemaManager::new(file_io.clone(), location)
+            .create_table(
+                &Schema::builder()
+                    .fields(vec![DataField::new(
+                        0,
+                        "id".to_string(),
+                        "INT".parse().unwrap(),
+                    )])
+                    .options(HashMap::from([(
+                        "file.format".to_string(),
+                        "parquet".to_string(),
+                    )]))
+                    .build(),
+            )
+            .await
+            .unwrap();
+        let table = FileStoreTable::new(file_io, Identifier::new("db", "t"), location, schema);
+
+        let builder = table.new_batch_write_builder();
+        let mut write = builder.new_write();
+        let batch = RecordBatch::try_new(
+            Arc::new(to_arrow_schema(table.schema().fields())),
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+        )
+        .unwrap();
+        write.write(&batch).await.unwrap();
+        let messages = write.prepare_commit().await.unwrap();
+        builder.new_commit().commit(messages).await.unwrap();
+        let plan = table.new_scan().plan().await.unwrap();
+        let read = table.new_read();
+        read.read(&plan.splits()[0]).await.unwrap().for_each(drop);
+
+        assert_eq!(recorder.fields("write.batch")["row_count"], "3");
+        assert_eq!(
+            recorder.fields("write.prepare_commit")["message_count"],
+            "1"
+        );
+        assert_eq!(recorder.fields("commit")["table"], "db.t");
+        assert_eq!(recorder.fields("commit.snapshot")["snapshot_id"], "1");
+        let scan = recorder.fields("scan.plan");
+        assert_eq!(scan["snapshot_id"], "1");
+        assert_eq!(scan["split_count"], "1");
+        assert_eq!(recorder.fields("scan.read_entries")["manifest_count"], "1");
+        assert_eq!(recorder.fields("read.split")["file_count"], "1");
+        assert!(recorder.fields("read.file")["path"].contains("bucket-0"));
+    }
+}
@@ -0,0 +1,138 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::io::FileIO;
+use crate::spec::{Snapshot, Statistics};
+use bytes::Bytes;
+use std::sync::Arc;
+
+const STATISTICS_PREFIX: &str = "stat-";
+
+/// Handler of the [`Statistics`] files of a table in the `statistics` directory, the file of
+/// a snapshot is referenced by [`Snapshot::statistics`].
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/stats/StatsFileHandler.java>
+#[derive(Clone, Debug)]
+pub struct StatsFileHandler {
+    file_io: Arc<dyn FileIO>,
+    table_path: String,
+}
+
+impl StatsFileHandler {
+    pub fn new(file_io: Arc<dyn FileIO>, table_path: impl Into<String>) -> Self {
+        let table_path = table_path.into().trim_end_matches('/').to_string();
+        Self {
+            file_io,
+            table_path,
+        }
+    }
+
+    /// Get the path of the statistics directory.
+    pub fn statistics_dir(&self) -> String {
+        format!("{}/statistics", self.table_path)
+    }
+
+    /// Get the path of the statistics file of the given name.
+    pub fn statistics_path(&self, file_name: &str) -> String {
+        format!("{}/{}", self.statistics_dir(), file_name)
+    }
+
+    /// Write the statistics into a new file and get the name of the file.
+    pub async fn write_stats(&self, statistics: &Statistics) -> crate::Result<String> {
+        let file_name = format!("{STATISTICS_PREFIX}{}-0", uuid::Uuid::new_v4());
+        self.file_io
+            .new_output(&self.statistics_path(&file_name))?
+            .write(Bytes::from(statistics.to_json()?))
+            .await?;
+        Ok(file_name)
+    }
+
+    /// Read the statistics of the snapshot, returns `None` if the snapshot has no statistics.
+    pub async fn read_stats(&self, snapshot: &Snapshot) -> crate::Result<Option<Statistics>> {
+        let Some(file_name) = snapshot.statistics() else {
+            return Ok(None);
+        };
+        let content = self
+            .file_io
+            .read_file_utf8(&self.statistics_path(file_name))
+            .await?;
+        Statistics::from_json(&content).map(Some)
+    }
+
+    /// Delete the statistics file of the given name.
+    pub async fn delete_stats(&self, file_name: &str) -> crate::Result<()> {
+        self.file_io
+            .delete_file(&self.statistics_path(file_name))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::{ColStats, CommitKind};
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_stats_file_handler() {
+        let file_io = FileIOBuilder::from_url("memory:/")
+            .unwrap()
+            .build()
+            .unwrap();
+        let handler = StatsFileHandler::new(file_io, "memory:/test_stats_file_handler/t/");
+        let statistics = Statistics::builder()
+            .snapshot_id(1)
+            .schema_id(0)
+            .merged_record_count(Some(3))
+            .col_stats(HashMap::from([(
+                "id".to_string(),
+                ColStats::builder().col_id(0).null_count(Some(0)).build(),
+            )]))
+            .build();
+        let file_name = handler.write_stats(&statistics).await.unwrap();
+        assert!(file_name.starts_with(STATISTICS_PREFIX));
+
+        let snapshot = |statistics: Option<String>| {
+            Snapshot::builder()
+                .id(2)
+                .schema_id(0)
+                .base_manifest_list("base".to_string())
+                .delta_manifest_list("delta".to_string())
+                .commit_user("user".to_string())
+                .commit_identifier(0)
+                .commit_kind(CommitKind::ANALYZE)
+                .time_millis(0)
+                .version(3)
+                .statistics(statistics)
+                .build()
+        };
+        assert_eq!(handler.read_stats(&snapshot(None)).await.unwrap(), None);
+        assert_eq!(
+            handler
+                .read_stats(&snapshot(Some(file_name.clone())))
+                .await
+                .unwrap(),
+            Some(statistics)
+        );
+        handler.delete_stats(&file_name).await.unwrap();
+        assert!(handler
+            .read_stats(&snapshot(Some(file_name)))
+            .await
+            .is_err());
+    }
+}
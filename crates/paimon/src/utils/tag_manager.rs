@@ -0,0 +1,225 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use crate::error::*;
+use crate::io::FileIO;
+use crate::spec::Snapshot;
+use crate::utils::{branch_path, DEFAULT_MAIN_BRANCH};
+use bytes::Bytes;
+use std::sync::Arc;
+
+const TAG_PREFIX: &str = "tag-";
+
+/// Manager for the tags of a table, providing utility methods around the `tag` directory.
+///
+/// A tag is a snapshot retained by name, the tag file is the json of the snapshot tagged.
+///
+/// Impl Reference: <https://github.com/apache/paimon/blob/release-0.8.2/paimon-core/src/main/java/org/apache/paimon/utils/TagManager.java>
+#[derive(Clone, Debug)]
+pub struct TagManager {
+    file_io: Arc<dyn FileIO>,
+    table_path: String,
+    branch: String,
+}
+
+impl TagManager {
+    pub fn new(file_io: Arc<dyn FileIO>, table_path: impl Into<String>) -> Self {
+        let table_path = table_path.into().trim_end_matches('/').to_string();
+        Self {
+            file_io,
+            table_path,
+            branch: DEFAULT_MAIN_BRANCH.to_string(),
+        }
+    }
+
+    /// Manage the tags of the given branch instead of the main branch.
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = branch.into();
+        self
+    }
+
+    /// Get the path of the tag directory.
+    pub fn tag_dir(&self) -> String {
+        format!("{}/tag", branch_path(&self.table_path, &self.branch))
+    }
+
+    /// Get the path of the tag file with the given name.
+    pub fn tag_path(&self, tag_name: &str) -> String {
+        format!("{}/{}{}", self.tag_dir(), TAG_PREFIX, tag_name)
+    }
+
+    /// Check if the tag with the given name exists.
+    pub async fn tag_exists(&self, tag_name: &str) -> crate::Result<bool> {
+        self.file_io.exists(&self.tag_path(tag_name)).await
+    }
+
+    /// Read the snapshot tagged by the given name.
+    pub async fn tagged_snapshot(&self, tag_name: &str) -> crate::Result<Snapshot> {
+        if !self.tag_exists(tag_name).await? {
+            return TagNotExistSnafu { tag: tag_name }.fail();
+        }
+        let content = self
+            .file_io
+            .read_file_utf8(&self.tag_path(tag_name))
+            .await?;
+        Snapshot::from_json(&content)
+    }
+
+    /// Tag the snapshot by the given name.
+    pub async fn create_tag(&self, snapshot: &Snapshot, tag_name: &str) -> crate::Result<()> {
+        if tag_name.trim().is_empty() {
+            return ConfigInvalidSnafu {
+                message: format!("Tag name '{tag_name}' is blank"),
+            }
+            .fail();
+        }
+        if self.tag_exists(tag_name).await? {
+            return TagAlreadyExistSnafu { tag: tag_name }.fail();
+        }
+        self.file_io
+            .new_output(&self.tag_path(tag_name))?
+            .write(Bytes::from(snapshot.to_json()?))
+            .await
+    }
+
+    /// Delete the tag file of the given name, the files of the snapshot tagged are left.
+    pub async fn delete_tag(&self, tag_name: &str) -> crate::Result<()> {
+        if !self.tag_exists(tag_name).await? {
+            return TagNotExistSnafu { tag: tag_name }.fail();
+        }
+        self.file_io.delete_file(&self.tag_path(tag_name)).await
+    }
+
+    /// List the names of all tags in ascending order.
+    pub async fn tag_names(&self) -> crate::Result<Vec<String>> {
+        let mut names: Vec<String> = self
+            .file_io
+            .list_status(&self.tag_dir())
+            .await?
+            .into_iter()
+            .filter(|status| !status.is_dir)
+            .filter_map(|status| {
+                let name = status.path.rsplit('/').next()?;
+                Some(name.strip_prefix(TAG_PREFIX)?.to_string())
+            })
+            .collect();
+        names.sort_unstable();
+        Ok(names)
+    }
+
+    /// Read all tags as `(tag name, snapshot tagged)`, in ascending order of the snapshot ids.
+    pub async fn tags(&self) -> crate::Result<Vec<(String, Snapshot)>> {
+        let mut tags = Vec::new();
+        for name in self.tag_names().await? {
+            let snapshot = self.tagged_snapshot(&name).await?;
+            tags.push((name, snapshot));
+        }
+        tags.sort_by_key(|(_, snapshot)| snapshot.id());
+        Ok(tags)
+    }
+
+    /// Read the snapshots tagged, in ascending order of id without duplicates.
+    pub async fn tagged_snapshots(&self) -> crate::Result<Vec<Snapshot>> {
+        let mut snapshots: Vec<Snapshot> = self.tags().await?.into_iter().map(|(_, s)| s).collect();
+        snapshots.dedup_by_key(|snapshot| snapshot.id());
+        Ok(snapshots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::FileIOBuilder;
+    use crate::spec::CommitKind;
+
+    fn test_snapshot(id: i64) -> Snapshot {
+        Snapshot::builder()
+            .version(Snapshot::CURRENT_VERSION)
+            .id(id)
+            .schema_id(0)
+            .base_manifest_list(format!("manifest-list-base-{id}"))
+            .delta_manifest_list(format!("manifest-list-delta-{id}"))
+            .commit_user("test".to_string())
+            .commit_identifier(id)
+            .commit_kind(CommitKind::APPEND)
+            .time_millis(1000 * id as u64)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_tag_manager() {
+        let table_path = "file:/tmp/test_tag_manager";
+        let file_io = FileIOBuilder::from_url(table_path)
+            .unwrap()
+            .build()
+            .unwrap();
+        let _ = file_io.delete_dir(&format!("{table_path}/")).await;
+        let manager = TagManager::new(file_io, table_path);
+        assert!(manager.tag_names().await.unwrap().is_empty());
+
+        manager.create_tag(&test_snapshot(2), "b").await.unwrap();
+        manager.create_tag(&test_snapshot(1), "c").await.unwrap();
+        manager.create_tag(&test_snapshot(2), "a").await.unwrap();
+        assert!(matches!(
+            manager.create_tag(&test_snapshot(3), "a").await,
+            Err(crate::Error::TagAlreadyExist { .. })
+        ));
+        assert!(matches!(
+            manager.create_tag(&test_snapshot(3), " ").await,
+            Err(crate::Error::ConfigInvalid { .. })
+        ));
+        assert_eq!(manager.tag_names().await.unwrap(), vec!["a", "b", "c"]);
+        assert_eq!(
+            manager.tagged_snapshot("a").await.unwrap(),
+            test_snapshot(2)
+        );
+        let tags: Vec<(String, i64)> = manager
+            .tags()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(name, snapshot)| (name, snapshot.id()))
+            .collect();
+        assert_eq!(
+            tags,
+            vec![
+                ("c".to_string(), 1),
+                ("a".to_string(), 2),
+                ("b".to_string(), 2)
+            ]
+        );
+        let ids: Vec<i64> = manager
+            .tagged_snapshots()
+            .await
+            .unwrap()
+            .iter()
+            .map(Snapshot::id)
+            .collect();
+        assert_eq!(ids, vec![1, 2]);
+
+        manager.delete_tag("a").await.unwrap();
+        assert!(!manager.tag_exists("a").await.unwrap());
+        assert!(matches!(
+            manager.delete_tag("a").await,
+            Err(crate::Error::TagNotExist { .. })
+        ));
+        assert!(matches!(
+            manager.tagged_snapshot("a").await,
+            Err(crate::Error::TagNotExist { .. })
+        ));
+    }
+}